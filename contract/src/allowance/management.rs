@@ -2,6 +2,7 @@ use crate::events::emit::emit_event;
 use crate::events::topics::{
     ACT_DECREASED, ACT_EXECUTED, ACT_GRANTED, ACT_INCREASED, ACT_REVOKED, MOD_ALLOWANCE,
 };
+use crate::utils::time;
 use soroban_sdk::{Address, Env, Vec};
 
 use super::storage;
@@ -31,7 +32,7 @@ pub fn approve(
         return Err(AllowanceError::InvalidAmount);
     }
 
-    let now = env.ledger().timestamp();
+    let now = time::now(env);
     if expires_at > 0 && expires_at <= now {
         return Err(AllowanceError::Expired);
     }
@@ -81,7 +82,7 @@ pub fn increase_allowance(
     let mut allowance =
         storage::get_allowance(env, &owner, &spender, &token).ok_or(AllowanceError::NotFound)?;
 
-    let now = env.ledger().timestamp();
+    let now = time::now(env);
     if allowance.is_expired(now) {
         return Err(AllowanceError::Expired);
     }
@@ -120,7 +121,7 @@ pub fn decrease_allowance(
     let mut allowance =
         storage::get_allowance(env, &owner, &spender, &token).ok_or(AllowanceError::NotFound)?;
 
-    let now = env.ledger().timestamp();
+    let now = time::now(env);
     if allowance.is_expired(now) {
         return Err(AllowanceError::Expired);
     }
@@ -198,7 +199,7 @@ pub fn spend(
         storage::get_allowance(env, owner, spender, token).ok_or(AllowanceError::NotFound)?;
 
     // Check expiry
-    let now = env.ledger().timestamp();
+    let now = time::now(env);
     if allowance.is_expired(now) {
         return Err(AllowanceError::Expired);
     }
@@ -1,18 +1,8 @@
-﻿use soroban_sdk::{symbol_short, Address, Env, Map, Symbol, Vec};
+﻿use crate::keys::allowance::{ALLOWANCES_KEY, OWNER_IDX_KEY, SPENDER_IDX_KEY};
+use soroban_sdk::{Address, Env, Map, Vec};
 
 use super::types::TokenAllowance;
 
-// â”€â”€ Storage Keys â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€
-
-/// Main allowance map: (owner, spender, token) â†’ TokenAllowance
-const ALLOWANCES_KEY: Symbol = symbol_short!("tkn_alw");
-
-/// Owner index: owner â†’ Vec<(spender, token)>
-const OWNER_IDX_KEY: Symbol = symbol_short!("alw_oidx");
-
-/// Spender index: spender â†’ Vec<(owner, token)>
-const SPENDER_IDX_KEY: Symbol = symbol_short!("alw_sidx");
-
 // â”€â”€ Composite Key Type â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€
 
 type AllowanceKey = (Address, Address, Option<Address>); // (owner, spender, token)
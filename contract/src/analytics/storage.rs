@@ -1,10 +1,8 @@
-﻿use soroban_sdk::{symbol_short, Env, Map, Symbol, Vec};
+﻿use crate::keys::analytics::{SNAPSHOTS_KEY, SNAP_CNT_KEY};
+use soroban_sdk::{Env, Map, Vec};
 
 use crate::analytics::types::TreasurySnapshot;
 
-const SNAPSHOTS_KEY: Symbol = symbol_short!("a_snaps");
-const SNAP_CNT_KEY: Symbol = symbol_short!("a_scnt");
-
 /// Maximum number of snapshots retained per treasury to bound storage growth.
 const MAX_SNAPSHOTS_PER_TREASURY: u32 = 200;
 
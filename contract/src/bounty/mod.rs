@@ -37,6 +37,7 @@ use crate::events::topics::{
 };
 use crate::guild::membership::has_permission;
 use crate::guild::types::Role;
+use crate::utils::time;
 use soroban_sdk::{Address, Env, String, Vec};
 
 pub use types::{Bounty, BountyStatus, PayoutSplit};
@@ -66,7 +67,7 @@ pub fn create_bounty(
         panic!("Invalid reward amount: must be non-negative");
     }
 
-    let created_at = env.ledger().timestamp();
+    let created_at = time::now(env);
     if expiry <= created_at {
         panic!("Expiry must be in the future");
     }
@@ -205,7 +206,7 @@ pub fn fund_bounty(env: &Env, bounty_id: u64, funder: Address, amount: i128) ->
 
     let mut bounty = get_bounty(env, bounty_id).expect("Bounty not found");
 
-    let now = env.ledger().timestamp();
+    let now = time::now(env);
     if now > bounty.expires_at {
         bounty.status = BountyStatus::Expired;
         store_bounty(env, &bounty);
@@ -259,7 +260,7 @@ pub fn claim_bounty(env: &Env, bounty_id: u64, claimer: Address) -> bool {
 
     let mut bounty = get_bounty(env, bounty_id).expect("Bounty not found");
 
-    let now = env.ledger().timestamp();
+    let now = time::now(env);
     if now > bounty.expires_at {
         bounty.status = BountyStatus::Expired;
         store_bounty(env, &bounty);
@@ -505,7 +506,7 @@ pub fn expire_bounty(env: &Env, bounty_id: u64) -> bool {
         return false;
     }
 
-    let now = env.ledger().timestamp();
+    let now = time::now(env);
     if now <= bounty.expires_at {
         return false;
     }
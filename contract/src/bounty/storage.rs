@@ -1,10 +1,6 @@
 ﻿use crate::bounty::types::Bounty;
-use soroban_sdk::{symbol_short, Env, Map, Symbol, Vec};
-
-// Storage keys
-const BOUNTIES_KEY: Symbol = symbol_short!("bounties");
-const BOUNTY_CNT_KEY: Symbol = symbol_short!("b_cnt");
-const GUILD_BOUNTIES_KEY: Symbol = symbol_short!("g_bnties");
+use crate::keys::bounty::{BOUNTIES_KEY, BOUNTY_CNT_KEY, GUILD_BOUNTIES_KEY};
+use soroban_sdk::{Env, Map, Vec};
 
 /// Initialize bounty storage
 #[allow(dead_code)]
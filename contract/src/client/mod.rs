@@ -0,0 +1,87 @@
+//! Typed client surface for off-chain integrations.
+//!
+//! Services talking to the contract should not hand-build symbol names and
+//! argument tuples. `#[contractimpl]` already generates a typed client for
+//! every public entrypoint; this module names the upgrade and proxy views of
+//! it and re-exports the error enums, argument types and event payloads
+//! they exchange. It is only compiled with the `client` feature (and in
+//! tests) so the on-chain build carries none of it.
+//!
+//! # Example — proposing an upgrade against a test `Env`
+//! ```
+//! use soroban_sdk::{testutils::Address as _, Address, Env, String};
+//! use stellar_guilds_contract::client::{GuildUpgradeClient, UpgradeStatus, Version};
+//! use stellar_guilds_contract::StellarGuildsContract;
+//!
+//! let env = Env::default();
+//! env.mock_all_auths();
+//! let contract_id = env.register_contract(None, StellarGuildsContract);
+//! let upgrades = GuildUpgradeClient::new(&env, &contract_id);
+//!
+//! let governance = Address::generate(&env);
+//! upgrades.initialize_upgrade_system(&1, &0, &0, &governance);
+//! let proposal_id = upgrades.propose_upgrade(
+//!     &governance,
+//!     &Address::generate(&env),
+//!     &1,
+//!     &1,
+//!     &0,
+//!     &String::from_str(&env, "Bump to 1.1.0"),
+//! );
+//!
+//! let detail = upgrades.get_upgrade_proposal_detail(&proposal_id, &None).unwrap();
+//! assert_eq!(detail.proposal.status, UpgradeStatus::Pending);
+//! assert_eq!(upgrades.get_current_version(), Version::new(1, 0, 0));
+//! ```
+//!
+//! # Example — driving the proxy
+//! ```
+//! use soroban_sdk::{testutils::Address as _, Address, Env};
+//! use stellar_guilds_contract::client::{GuildProxyClient, ProxyError, UpgradeOutcome};
+//! use stellar_guilds_contract::StellarGuildsContract;
+//!
+//! let env = Env::default();
+//! env.mock_all_auths();
+//! let contract_id = env.register_contract(None, StellarGuildsContract);
+//! let proxy = GuildProxyClient::new(&env, &contract_id);
+//!
+//! let admin = Address::generate(&env);
+//! let next = Address::generate(&env);
+//! proxy.initialize_proxy(&Address::generate(&env), &admin);
+//! proxy.proxy_set_skip_version_check(&admin, &true);
+//! // Refused until the target is on the allowlist; the refusal stays in the history
+//! assert_eq!(
+//!     proxy.proxy_upgrade(&admin, &next),
+//!     UpgradeOutcome::rejected(ProxyError::ImplementationNotApproved)
+//! );
+//! proxy.proxy_approve_implementation(&admin, &next);
+//! assert_eq!(proxy.proxy_upgrade(&admin, &next), UpgradeOutcome::Applied);
+//! assert_eq!(proxy.proxy_get_failed_upgrades().len(), 1);
+//! assert_eq!(proxy.proxy_get_info().implementation, next);
+//! ```
+
+#[cfg(test)]
+mod tests;
+
+/// Client for the upgrade governance entrypoints
+pub type GuildUpgradeClient<'a> = crate::StellarGuildsContractClient<'a>;
+
+/// Client for the proxy entrypoints (`initialize_proxy`, `proxy_*`)
+pub type GuildProxyClient<'a> = crate::StellarGuildsContractClient<'a>;
+
+pub use crate::proxy::types::{
+    Handover, HandoverStatus, ProxyConfig, ProxyError, ProxyPause, ScheduleStatus,
+    ScheduledUpgrade, UpgradeOutcome, UpgradeTransaction,
+};
+pub use crate::upgrade::types::*;
+pub use crate::utils::errors::{ErrorCatalog, IntegrationErrorCode};
+pub use crate::utils::pagination::{Cursor, CursorDirection, PageScope};
+
+/// Event envelope, history records and the topic vocabulary
+pub mod events {
+    pub use crate::events::topics::*;
+    pub use crate::events::types::{
+        EventDigest, EventEnvelope, EventEra, EventVerbosity, HistoryDigest, HistoryKind,
+        HistoryPage, HistoryRecord, HistoryRecordPage, EVENT_SCHEMA_VERSION,
+    };
+}
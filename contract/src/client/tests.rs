@@ -0,0 +1,175 @@
+use crate::client::events::{EventDigest, MOD_UPGRADE};
+use crate::client::{
+    ExecutionOutcome, GovernanceConfig, GuardError, GuildProxyClient, GuildUpgradeClient,
+    ProxyError, UpgradeError, UpgradeOutcome, UpgradeStatus, Version, VoteChoice, VoteError,
+};
+use crate::StellarGuildsContract;
+use soroban_sdk::testutils::{Address as _, Ledger};
+use soroban_sdk::{Address, Env, String, Symbol};
+
+fn setup() -> (Env, Address) {
+    let env = Env::default();
+    env.budget().reset_unlimited();
+    env.mock_all_auths();
+    env.ledger().with_mut(|l| l.timestamp = 1_000);
+    let contract_id = env.register_contract(None, StellarGuildsContract);
+    (env, contract_id)
+}
+
+#[test]
+fn test_upgrade_lifecycle_through_client() {
+    let (env, contract_id) = setup();
+    let upgrades = GuildUpgradeClient::new(&env, &contract_id);
+    let governance = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    upgrades.initialize_upgrade_system(&1, &0, &0, &governance);
+    let config: GovernanceConfig = upgrades.get_upgrade_governance_config();
+    upgrades.set_upgrade_governance_config(&governance, &config);
+    upgrades.grant_upgrade_voting_power(&governance, &alice, &1);
+    upgrades.grant_upgrade_voting_power(&governance, &bob, &1);
+    assert_eq!(upgrades.get_upgrade_voters().len(), 2);
+
+    let implementation = Address::generate(&env);
+    let proposal_id = upgrades.propose_upgrade(
+        &alice,
+        &implementation,
+        &1,
+        &1,
+        &0,
+        &String::from_str(&env, "Bump to 1.1.0"),
+    );
+    let detail = upgrades
+        .get_upgrade_proposal_detail(&proposal_id, &Some(bob.clone()))
+        .unwrap();
+    assert_eq!(detail.proposal.status, UpgradeStatus::Pending);
+    assert!(detail.viewer_can_vote);
+
+    upgrades.vote_on_upgrade_proposal(&alice, &proposal_id, &true);
+    upgrades.cast_upgrade_vote(&bob, &proposal_id, &VoteChoice::For);
+    let proposal = upgrades
+        .get_upgrade_proposal_detail(&proposal_id, &None)
+        .unwrap()
+        .proposal;
+    assert_eq!(proposal.status, UpgradeStatus::Approved);
+
+    assert!(upgrades
+        .get_upgrade_execution_blockers(&proposal_id)
+        .is_empty());
+    assert_eq!(
+        upgrades.execute_upgrade_proposal(&governance, &proposal_id),
+        ExecutionOutcome::Executed
+    );
+    assert_eq!(upgrades.get_current_version(), Version::new(1, 1, 0));
+    let receipt = upgrades.get_execution_receipt(&proposal_id).unwrap();
+    assert_eq!(receipt.implementation, implementation);
+
+    // Every step went through the event stream under the upgrade module
+    let digests = upgrades.get_recent_events(&0, &100);
+    assert!(digests
+        .iter()
+        .any(|d: EventDigest| d.module == Symbol::new(&env, MOD_UPGRADE)));
+}
+
+#[test]
+fn test_client_surfaces_rejections() {
+    let (env, contract_id) = setup();
+    let upgrades = GuildUpgradeClient::new(&env, &contract_id);
+    let governance = Address::generate(&env);
+    let outsider = Address::generate(&env);
+    upgrades.initialize_upgrade_system(&1, &0, &0, &governance);
+
+    // Rejections surface as the typed error of the module that refused
+    assert_eq!(
+        upgrades.try_execute_upgrade_proposal(&governance, &42),
+        Err(Ok(UpgradeError::ProposalNotFound.into()))
+    );
+    assert_eq!(
+        upgrades.try_toggle_emergency_upgrades(&outsider, &true),
+        Err(Ok(UpgradeError::NotAuthorized.into()))
+    );
+    assert!(upgrades
+        .try_grant_upgrade_voting_power(&outsider, &outsider, &1)
+        .is_err());
+
+    // The error catalog names every typed error code
+    let catalog = upgrades.error_catalog();
+    for error in UpgradeError::ALL.iter() {
+        assert!(catalog
+            .entries
+            .contains(&(*error as u32, Symbol::new(&env, error.name()))));
+    }
+    for error in ProxyError::ALL.iter() {
+        assert!(catalog
+            .entries
+            .contains(&(*error as u32, Symbol::new(&env, error.name()))));
+    }
+    for error in VoteError::ALL.iter() {
+        assert!(catalog
+            .entries
+            .contains(&(*error as u32, Symbol::new(&env, error.name()))));
+    }
+    for error in GuardError::ALL.iter() {
+        assert!(catalog
+            .entries
+            .contains(&(*error as u32, Symbol::new(&env, error.name()))));
+    }
+}
+
+#[test]
+fn test_proxy_lifecycle_through_client() {
+    let (env, contract_id) = setup();
+    let proxy = GuildProxyClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let outsider = Address::generate(&env);
+    let v1 = Address::generate(&env);
+    let v2 = Address::generate(&env);
+
+    proxy.initialize_proxy(&v1, &admin);
+    proxy.proxy_set_skip_version_check(&admin, &true);
+    proxy.proxy_set_min_upgrade_delay(&admin, &100);
+    assert_eq!(
+        proxy.try_proxy_approve_implementation(&outsider, &v2),
+        Err(Ok(ProxyError::NotApprover.into()))
+    );
+    proxy.proxy_approve_implementation(&admin, &v2);
+    assert!(proxy.proxy_is_implementation_approved(&v2));
+    let schedule_id = proxy.proxy_schedule_upgrade(&admin, &v2).unwrap();
+    assert_eq!(
+        proxy.try_proxy_execute_scheduled_upgrade(&admin, &schedule_id),
+        Err(Ok(ProxyError::DelayNotElapsed.into()))
+    );
+
+    env.ledger().with_mut(|l| l.timestamp += 100);
+    assert_eq!(
+        proxy.proxy_execute_scheduled_upgrade(&admin, &schedule_id),
+        UpgradeOutcome::Applied
+    );
+    let info = proxy.proxy_get_info();
+    assert_eq!(info.implementation, v2);
+    assert_eq!(info.admin, admin);
+
+    assert_eq!(
+        proxy.try_proxy_emergency_stop(&outsider),
+        Err(Ok(ProxyError::NotAdmin.into()))
+    );
+    proxy.proxy_emergency_stop(&admin);
+    assert!(proxy.proxy_is_paused());
+    assert_eq!(proxy.proxy_get_pause().unwrap().paused_by, admin);
+    assert_eq!(
+        proxy.proxy_upgrade(&admin, &v1),
+        UpgradeOutcome::rejected(ProxyError::Paused)
+    );
+    proxy.proxy_resume(&admin);
+    assert_eq!(
+        proxy.try_proxy_resume(&admin),
+        Err(Ok(ProxyError::NotPaused.into()))
+    );
+
+    proxy.proxy_transfer_admin(&admin, &outsider);
+    assert_eq!(proxy.proxy_get_pending_admin(), Some(outsider.clone()));
+    assert_eq!(proxy.proxy_get_info().admin, admin);
+    proxy.proxy_accept_admin(&outsider);
+    assert_eq!(proxy.proxy_get_info().admin, outsider);
+}
@@ -8,6 +8,7 @@ pub mod storage;
 pub mod types;
 pub mod voting;
 
+use crate::utils::time;
 use soroban_sdk::{Address, Env, String};
 
 use crate::bounty::storage as bounty_storage;
@@ -88,7 +89,7 @@ pub fn create_dispute(
         panic!("dispute already active for reference");
     }
 
-    let now = env.ledger().timestamp();
+    let now = time::now(env);
     let voting_deadline = now + VOTING_PERIOD_SECONDS;
     let dispute_id = dispute_storage::get_next_dispute_id(env);
 
@@ -145,7 +146,7 @@ pub fn submit_evidence(env: &Env, dispute_id: u64, party: Address, evidence_url:
         panic!("dispute closed");
     }
 
-    let now = env.ledger().timestamp();
+    let now = time::now(env);
     if now > dispute.voting_deadline {
         panic!("evidence period ended");
     }
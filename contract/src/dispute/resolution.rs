@@ -13,6 +13,7 @@ use crate::guild::storage as guild_storage;
 use crate::milestone::storage as milestone_storage;
 use crate::milestone::types::{MilestoneStatus, ProjectStatus};
 use crate::treasury::execute_milestone_payment;
+use crate::utils::time;
 
 const QUORUM_PERCENTAGE: u32 = 30;
 
@@ -78,7 +79,7 @@ pub fn resolve_dispute(env: &Env, dispute_id: u64) -> Resolution {
         panic!("dispute already closed");
     }
 
-    let now = env.ledger().timestamp();
+    let now = time::now(env);
     if now < dispute.voting_deadline {
         panic!("voting period still active");
     }
@@ -245,7 +246,7 @@ pub fn execute_resolution(env: &Env, dispute_id: u64) -> Vec<FundDistribution> {
 
                 project.released_amount = new_released;
                 milestone.is_payment_released = true;
-                milestone.last_updated_at = env.ledger().timestamp();
+                milestone.last_updated_at = time::now(env);
 
                 milestone.status = match decision {
                     VoteDecision::FavorDefendant => MilestoneStatus::Rejected,
@@ -1,12 +1,8 @@
-﻿use soroban_sdk::{symbol_short, Address, Env, Map, Symbol};
+﻿use crate::keys::dispute::{DISPUTES_KEY, DISPUTE_COUNTER_KEY, DISPUTE_VOTES_KEY, REF_LOCKS_KEY};
+use soroban_sdk::{Address, Env, Map};
 
 use crate::dispute::types::{Dispute, DisputeReference, Vote};
 
-const DISPUTES_KEY: Symbol = symbol_short!("dsp_all");
-const DISPUTE_COUNTER_KEY: Symbol = symbol_short!("dsp_cnt");
-const DISPUTE_VOTES_KEY: Symbol = symbol_short!("dsp_vot");
-const REF_LOCKS_KEY: Symbol = symbol_short!("dsp_ref");
-
 /// Get the next dispute ID and increment the counter.
 pub fn get_next_dispute_id(env: &Env) -> u64 {
     let current: u64 = env
@@ -6,6 +6,7 @@ use crate::events::emit::emit_event;
 use crate::events::topics::{ACT_VOTE_CAST, MOD_DISPUTE};
 use crate::governance::types::role_weight;
 use crate::guild::storage as guild_storage;
+use crate::utils::time;
 
 /// Calculate voting weight for a guild member based on role.
 pub fn calculate_vote_weight(env: &Env, guild_id: u64, voter: &soroban_sdk::Address) -> u32 {
@@ -35,7 +36,7 @@ pub fn cast_vote(
         panic!("dispute is closed");
     }
 
-    let now = env.ledger().timestamp();
+    let now = time::now(env);
     if now > dispute.voting_deadline {
         panic!("voting period ended");
     }
@@ -2,6 +2,7 @@
 use crate::emergency::types::{EmergencyConfig, EmergencyStatus};
 use crate::multisig::storage::get_operation;
 use crate::multisig::types::{MultiSigOperation, OperationStatus, OperationType};
+use crate::utils::time;
 use soroban_sdk::{Address, Env, String};
 
 pub fn pause_contract(
@@ -28,7 +29,7 @@ pub fn pause_contract(
         panic!("Duration must be between 7 and 30 days");
     }
 
-    let current_time = env.ledger().timestamp();
+    let current_time = time::now(env);
     let config = EmergencyConfig {
         status: EmergencyStatus::Active,
         paused_at: current_time,
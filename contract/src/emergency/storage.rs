@@ -1,6 +1,11 @@
 ﻿use crate::emergency::types::{EmergencyConfig, EmergencyStatus};
 use crate::events::emit::emit_event;
+use crate::events::history::append_history;
 use crate::events::topics::{ACT_EXECUTED, MOD_EMERGENCY};
+use crate::events::types::HistoryKind;
+use crate::upgrade::storage::adjust_footprint;
+use crate::upgrade::types::FootprintCategory;
+use crate::utils::time;
 use soroban_sdk::{contracttype, Address, Env, String};
 
 #[contracttype]
@@ -32,7 +37,7 @@ pub fn set_emergency_config(env: &Env, config: &EmergencyConfig) {
 pub fn is_paused(env: &Env) -> bool {
     let config = get_emergency_config(env);
     if config.status == EmergencyStatus::Active {
-        if env.ledger().timestamp() > config.expires_at {
+        if time::now(env) > config.expires_at {
             let mut new_config = config.clone();
             new_config.status = EmergencyStatus::Inactive;
             set_emergency_config(env, &new_config);
@@ -60,14 +65,16 @@ pub fn log_emergency_action(env: &Env, action: String, performed_by: Address, re
     let log = EmergencyActionLog {
         action,
         performed_by,
-        timestamp: env.ledger().timestamp(),
+        timestamp: time::now(env),
         reason,
     };
 
+    adjust_footprint(env, FootprintCategory::LogEntries, 1);
     let id = next_log_id(env);
     env.storage()
         .persistent()
         .set(&DataKey::EmergencyLog(id), &log);
+    append_history(env, HistoryKind::EmergencyLog, log.clone());
 
     emit_event(env, MOD_EMERGENCY, ACT_EXECUTED, log);
 }
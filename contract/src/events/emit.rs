@@ -1,3 +1,8 @@
+use crate::events::types::{
+    EventDigest, EventEnvelope, EventEra, EventVerbosity, EVENT_ERA_KEY, EVENT_RING_CAPACITY,
+    EVENT_RING_KEY, EVENT_SCHEMA_VERSION, EVENT_SEQUENCE_KEY, EVENT_VERBOSITY_KEY,
+};
+use crate::integration::events::record_standardized_event;
 /// Central event emission helper for the Stellar Guilds contract.
 ///
 /// All modules **must** emit events exclusively through `emit_event()`.
@@ -6,7 +11,7 @@
 /// format across the entire contract.
 ///
 /// # Example â€” emitting a guild creation event
-/// ```rust
+/// ```ignore
 /// use crate::events::emit::emit_event;
 /// use crate::events::topics::{MOD_GUILD, ACT_CREATED};
 /// use crate::guild::types::GuildCreatedEvent;
@@ -24,12 +29,31 @@
 /// incremented atomically on every emission. Off-chain indexers can use this
 /// to detect missed events if their subscription lapsed.
 ///
+/// # Replay buffer
+/// A digest of each event (sequence, module, action, payload hash) is also
+/// written to a fixed-size ring of `EVENT_RING_CAPACITY` persistent slots,
+/// one slot per event. `get_recent_events()` reads it back so an indexer can
+/// confirm it saw every recent sequence, or fall back to a full state export
+/// when the gap is older than the ring.
+///
+/// # Era topics
+/// Once the contract knows its version, the meta-event topic grows to
+/// `(stellar_guilds, event, major, proxy_version)` so indexers can subscribe
+/// to a single contract era. The era lives in instance storage, which is
+/// loaded once per invocation; `set_version_topics()` turns it off for
+/// deployments close to topic count limits.
+///
+/// # Verbosity
+/// Governance picks an `EventVerbosity`, kept in instance storage next to the
+/// era. `emit_leveled_event()` builds its payload for that level; every
+/// envelope records the level so consumers know how to parse the payload.
+///
 /// # Size budget
 /// Soroban charges per-byte for event data. Keep payload structs lean; use
 /// IDs to reference large blobs stored elsewhere rather than inlining them.
-use crate::events::types::{EventEnvelope, EVENT_SCHEMA_VERSION, EVENT_SEQUENCE_KEY};
-use crate::integration::events::record_standardized_event;
-use soroban_sdk::{Env, IntoVal, Symbol, Val};
+use crate::utils::time;
+use soroban_sdk::xdr::ToXdr;
+use soroban_sdk::{BytesN, Env, IntoVal, Symbol, Val, Vec};
 
 /// Read the current global event sequence number.
 /// Returns 0 if this is the first event ever emitted.
@@ -47,6 +71,73 @@ fn set_sequence(env: &Env, seq: u64) {
         .set(&Symbol::new(env, EVENT_SEQUENCE_KEY), &seq);
 }
 
+/// The contract era, if a version has been recorded or topics configured.
+pub fn get_event_era(env: &Env) -> Option<EventEra> {
+    env.storage()
+        .instance()
+        .get::<_, EventEra>(&Symbol::new(env, EVENT_ERA_KEY))
+}
+
+/// Apply `update` to the stored era, starting from an enabled, zero era.
+fn update_era(env: &Env, update: impl FnOnce(&mut EventEra)) {
+    let mut era = get_event_era(env).unwrap_or(EventEra {
+        enabled: true,
+        major: 0,
+        proxy_version: 0,
+    });
+    update(&mut era);
+    env.storage()
+        .instance()
+        .set(&Symbol::new(env, EVENT_ERA_KEY), &era);
+}
+
+/// Record the upgrade module's current major version for era topics.
+pub fn set_era_major(env: &Env, major: u32) {
+    update_era(env, |era| era.major = major);
+}
+
+/// Record the proxy config's current version for era topics.
+pub fn set_era_proxy_version(env: &Env, proxy_version: u32) {
+    update_era(env, |era| era.proxy_version = proxy_version);
+}
+
+/// The configured payload verbosity, `Standard` unless set.
+pub fn get_event_verbosity(env: &Env) -> EventVerbosity {
+    env.storage()
+        .instance()
+        .get::<_, EventVerbosity>(&Symbol::new(env, EVENT_VERBOSITY_KEY))
+        .unwrap_or(EventVerbosity::Standard)
+}
+
+/// Set the payload verbosity for leveled events.
+pub fn set_event_verbosity(env: &Env, verbosity: EventVerbosity) {
+    env.storage()
+        .instance()
+        .set(&Symbol::new(env, EVENT_VERBOSITY_KEY), &verbosity);
+}
+
+/// Turn the era topics on or off.
+pub fn set_version_topics(env: &Env, enabled: bool) {
+    update_era(env, |era| era.enabled = enabled);
+}
+
+/// Ring buffer slot holding the digest of event `seq`.
+fn ring_key(env: &Env, seq: u64) -> (Symbol, u64) {
+    (Symbol::new(env, EVENT_RING_KEY), seq % EVENT_RING_CAPACITY)
+}
+
+/// Overwrite the oldest ring slot with the digest of the event just emitted.
+fn record_digest(env: &Env, seq: u64, module: Symbol, action: Symbol, payload: Val) {
+    let hash = env.crypto().sha256(&payload.to_xdr(env)).to_array();
+    let digest = EventDigest {
+        sequence: seq,
+        module,
+        action,
+        payload_hash: BytesN::from_array(env, &hash),
+    };
+    env.storage().persistent().set(&ring_key(env, seq), &digest);
+}
+
 /// Emit a standardized event with full envelope metadata.
 ///
 /// # Type parameters
@@ -63,12 +154,57 @@ fn set_sequence(env: &Env, seq: u64) {
 /// The published topic tuple is `(module_symbol, action_symbol)`, giving
 /// Stellar's event filter system two independent axes to index on. Consumers
 /// can subscribe to all events from a module, all events of an action type,
-/// or the intersection of both.
+/// or the intersection of both. The meta-event additionally carries the era
+/// topics described in the module docs.
 /// emits event
 pub fn emit_event<T>(env: &Env, module: &str, action: &str, data: T)
 where
     T: IntoVal<Env, Val>,
 {
+    let verbosity = get_event_verbosity(env);
+    publish(env, module, action, None, verbosity, data.into_val(env));
+}
+
+/// Emit an event whose payload topic also carries the id of its subject,
+/// `(module_symbol, action_symbol, subject)`, so indexers can filter the
+/// events of one proposal or record without decoding payloads.
+pub fn emit_subject_event<T>(env: &Env, module: &str, action: &str, subject: u64, data: T)
+where
+    T: IntoVal<Env, Val>,
+{
+    let verbosity = get_event_verbosity(env);
+    publish(
+        env,
+        module,
+        action,
+        Some(subject),
+        verbosity,
+        data.into_val(env),
+    );
+}
+
+/// Emit an event whose payload depends on the configured `EventVerbosity`.
+///
+/// `payload` is called once, with the level in force, and returns the
+/// payload to publish. The event fires at every level; only its payload
+/// changes. Keep the `Standard` payload what `emit_event()` would publish.
+pub fn emit_leveled_event<F>(env: &Env, module: &str, action: &str, payload: F)
+where
+    F: FnOnce(EventVerbosity) -> Val,
+{
+    let verbosity = get_event_verbosity(env);
+    publish(env, module, action, None, verbosity, payload(verbosity));
+}
+
+/// Publish the envelope and payload events and record the digest.
+fn publish(
+    env: &Env,
+    module: &str,
+    action: &str,
+    subject: Option<u64>,
+    verbosity: EventVerbosity,
+    payload: Val,
+) {
     let seq = get_sequence(env) + 1;
     set_sequence(env, seq);
 
@@ -77,25 +213,39 @@ where
 
     let envelope = EventEnvelope {
         version: EVENT_SCHEMA_VERSION,
-        timestamp: env.ledger().timestamp(),
+        timestamp: time::now(env),
         module: module_sym.clone(),
         action: action_sym.clone(),
         sequence: seq,
+        verbosity,
     };
 
     // Publish envelope on the meta-topic so indexers can track all events
     // without needing to know every module+action pair in advance.
-    env.events().publish(
-        (
-            Symbol::new(env, "stellar_guilds"),
-            Symbol::new(env, "event"),
-        ),
-        envelope,
+    let meta_topic = (
+        Symbol::new(env, "stellar_guilds"),
+        Symbol::new(env, "event"),
     );
+    match get_event_era(env) {
+        Some(era) if era.enabled => env.events().publish(
+            (meta_topic.0, meta_topic.1, era.major, era.proxy_version),
+            envelope,
+        ),
+        _ => env.events().publish(meta_topic, envelope),
+    }
 
     // Publish the actual payload on the specific (module, action) topic so
     // consumers interested only in e.g. bounty:created can filter precisely.
-    env.events().publish((module_sym, action_sym), data);
+    match subject {
+        Some(subject) => env
+            .events()
+            .publish((module_sym.clone(), action_sym.clone(), subject), payload),
+        None => env
+            .events()
+            .publish((module_sym.clone(), action_sym.clone()), payload),
+    }
+
+    record_digest(env, seq, module_sym, action_sym, payload);
 
     record_standardized_event(env, module, action);
 }
@@ -103,7 +253,7 @@ where
 /// Convenience helper: emit an event and return a value in one expression.
 ///
 /// Useful inside function bodies where you want to emit and then return:
-/// ```rust
+/// ```ignore
 /// return emit_and_return(env, MOD_BOUNTY, ACT_CREATED, event, bounty_id);
 /// ```
 #[allow(dead_code)]
@@ -114,3 +264,32 @@ where
     emit_event(env, module, action, data);
     result
 }
+
+/// Read back event digests starting at `from_sequence`, oldest first.
+///
+/// Only the last `EVENT_RING_CAPACITY` events are kept; if `from_sequence`
+/// is older than that, the result starts at the oldest retained event and
+/// the caller can tell from the first `sequence` that it missed events.
+/// `limit` is capped at the ring capacity.
+pub fn get_recent_events(env: &Env, from_sequence: u64, limit: u32) -> Vec<EventDigest> {
+    let mut digests = Vec::new(env);
+    let latest = get_sequence(env);
+    let oldest = latest.saturating_sub(EVENT_RING_CAPACITY - 1).max(1);
+    let start = from_sequence.max(oldest);
+    let count = (limit as u64).min(EVENT_RING_CAPACITY);
+    if latest == 0 || start > latest || count == 0 {
+        return digests;
+    }
+
+    let end = latest.min(start + count - 1);
+    for seq in start..=end {
+        if let Some(digest) = env
+            .storage()
+            .persistent()
+            .get::<_, EventDigest>(&ring_key(env, seq))
+        {
+            digests.push_back(digest);
+        }
+    }
+    digests
+}
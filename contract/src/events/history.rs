@@ -0,0 +1,163 @@
+/// Append-only, hash-chained histories for compliance export.
+///
+/// Modules call `append_history()` when a record becomes final. Each record
+/// stores its XDR payload and the chain value `sha256(previous || payload)`,
+/// so an export read page by page can be checked end to end against
+/// `get_history_digest()`, which moves forward with every append.
+use crate::events::types::{
+    HistoryDigest, HistoryKind, HistoryPage, HistoryRecord, HistoryRecordPage,
+    HISTORY_FORMAT_VERSION, HISTORY_HEAD_KEY, HISTORY_PRUNED_KEY, HISTORY_RECORD_KEY,
+    MAX_HISTORY_PAGE,
+};
+use crate::upgrade::storage::adjust_footprint;
+use crate::upgrade::types::{FootprintCategory, ProposalError};
+use crate::utils::pagination::{self, Cursor, CursorDirection, PageScope};
+use soroban_sdk::xdr::ToXdr;
+use soroban_sdk::{Bytes, BytesN, Env, IntoVal, Symbol, Val, Vec};
+
+fn record_key(env: &Env, kind: HistoryKind, sequence: u64) -> (Symbol, HistoryKind, u64) {
+    (Symbol::new(env, HISTORY_RECORD_KEY), kind, sequence)
+}
+
+fn head_key(env: &Env, kind: HistoryKind) -> (Symbol, HistoryKind) {
+    (Symbol::new(env, HISTORY_HEAD_KEY), kind)
+}
+
+fn pruned_key(env: &Env, kind: HistoryKind) -> (Symbol, HistoryKind) {
+    (Symbol::new(env, HISTORY_PRUNED_KEY), kind)
+}
+
+/// The record of `kind` at `sequence`, unless it was never written or has been pruned.
+pub fn get_history_record(env: &Env, kind: HistoryKind, sequence: u64) -> Option<HistoryRecord> {
+    env.storage()
+        .persistent()
+        .get(&record_key(env, kind, sequence))
+}
+
+/// Highest sequence of `kind` pruned so far; 0 when nothing has been pruned.
+pub fn get_pruned_through(env: &Env, kind: HistoryKind) -> u64 {
+    env.storage()
+        .persistent()
+        .get(&pruned_key(env, kind))
+        .unwrap_or(0)
+}
+
+/// Delete the oldest remaining record of `kind`, which must be `sequence`.
+/// The digest is left alone, so later records keep chaining from it and
+/// pages simply start after the pruned range.
+pub fn prune_record(env: &Env, kind: HistoryKind, sequence: u64) {
+    debug_assert_eq!(sequence, get_pruned_through(env, kind) + 1);
+    adjust_footprint(env, FootprintCategory::HistoryEntries, -1);
+    pagination::bump_generation(env, PageScope::History);
+    env.storage()
+        .persistent()
+        .remove(&record_key(env, kind, sequence));
+    env.storage()
+        .persistent()
+        .set(&pruned_key(env, kind), &sequence);
+}
+
+/// The running digest of `kind`; an empty history has count 0 and a zero digest.
+pub fn get_history_digest(env: &Env, kind: HistoryKind) -> HistoryDigest {
+    env.storage()
+        .persistent()
+        .get(&head_key(env, kind))
+        .unwrap_or(HistoryDigest {
+            kind,
+            count: 0,
+            digest: BytesN::from_array(env, &[0u8; 32]),
+        })
+}
+
+/// Chain value following `previous` for `payload`.
+pub fn chain_hash(env: &Env, previous: &BytesN<32>, payload: &Bytes) -> BytesN<32> {
+    let mut preimage = Bytes::from(previous.clone());
+    preimage.append(payload);
+    env.crypto().sha256(&preimage).into()
+}
+
+/// Record `value` as the next entry of `kind` and advance its digest.
+pub fn append_history<T>(env: &Env, kind: HistoryKind, value: T)
+where
+    T: IntoVal<Env, Val>,
+{
+    adjust_footprint(env, FootprintCategory::HistoryEntries, 1);
+    let head = get_history_digest(env, kind);
+    let value: Val = value.into_val(env);
+    let payload = value.to_xdr(env);
+    let chain = chain_hash(env, &head.digest, &payload);
+
+    let record = HistoryRecord {
+        format: HISTORY_FORMAT_VERSION,
+        kind,
+        sequence: head.count + 1,
+        payload,
+        chain: chain.clone(),
+    };
+    env.storage()
+        .persistent()
+        .set(&record_key(env, kind, record.sequence), &record);
+    env.storage().persistent().set(
+        &head_key(env, kind),
+        &HistoryDigest {
+            kind,
+            count: record.sequence,
+            digest: chain,
+        },
+    );
+}
+
+/// Records of `kind` with sequence greater than `cursor`, oldest first,
+/// at most `limit` (capped at `MAX_HISTORY_PAGE`). Start from cursor 0 and
+/// pass `next_cursor` back until the page is `complete`.
+pub fn export_history_page(env: &Env, kind: HistoryKind, cursor: u64, limit: u32) -> HistoryPage {
+    let from = Cursor {
+        last_id: cursor,
+        ..pagination::start_cursor(env, PageScope::History, CursorDirection::Forward)
+    };
+    let page = export_history_after(env, kind, Some(from), limit)
+        .expect("a cursor of the current generation is valid");
+    let next_cursor = if page.complete {
+        get_history_digest(env, kind).count.max(cursor)
+    } else {
+        page.next.last_id
+    };
+
+    HistoryPage {
+        records: page.records,
+        next_cursor,
+        complete: page.complete,
+    }
+}
+
+/// Export up to `limit` live records of `kind` past `cursor`, starting
+/// from the oldest when no cursor is given. Pruning any history moves its
+/// generation on, so older cursors fail with `CursorInvalidated`.
+pub fn export_history_after(
+    env: &Env,
+    kind: HistoryKind,
+    cursor: Option<Cursor>,
+    limit: u32,
+) -> Result<HistoryRecordPage, ProposalError> {
+    let cursor = pagination::resolve(env, PageScope::History, cursor)?;
+    let (sequences, next, complete) = pagination::page_span(
+        env,
+        &cursor,
+        get_pruned_through(env, kind) + 1,
+        get_history_digest(env, kind).count,
+        limit.min(MAX_HISTORY_PAGE),
+    );
+
+    let mut records = Vec::new(env);
+    for sequence in sequences.iter() {
+        if let Some(record) = get_history_record(env, kind, sequence) {
+            records.push_back(record);
+        }
+    }
+
+    Ok(HistoryRecordPage {
+        records,
+        next,
+        complete,
+    })
+}
@@ -11,19 +11,22 @@
 /// to a consistent event stream, and made audit trails incomplete.
 ///
 /// # Architecture
-/// ```
+/// ```text
 /// events/
 ///   mod.rs    â† you are here; re-exports for easy import
 ///   types.rs  â† EventEnvelope + schema version constant
 ///   topics.rs â† MOD_* and ACT_* string constants (the controlled vocabulary)
 ///   emit.rs   â† emit_event() â€” the only function modules should call
+///   history.rs â† append_history() + paged, hash-chained exports
 /// ```
 ///
 /// # Two-event-per-action design
 /// `emit_event()` publishes **two** Soroban events per user action:
 ///
-/// 1. A meta-event on topic `(stellar_guilds, event)` carrying the
-///    `EventEnvelope` (version, timestamp, module, action, sequence).
+/// 1. A meta-event on topic `(stellar_guilds, event, major, proxy_version)`
+///    (just `(stellar_guilds, event)` until a version is known) carrying the
+///    `EventEnvelope` (version, timestamp, module, action, sequence,
+///    verbosity).
 ///    Off-chain indexers subscribe here to receive *all* events from the
 ///    contract in a single stream without knowing every topic pair.
 ///
@@ -32,7 +35,7 @@
 ///    directly on this topic for maximum efficiency.
 ///
 /// # Backward compatibility
-/// - `EVENT_SCHEMA_VERSION` (currently `1`) is embedded in every envelope.
+/// - `EVENT_SCHEMA_VERSION` (currently `2`) is embedded in every envelope.
 ///   When the envelope shape changes, bump this constant and update consumers.
 /// - Topic symbol strings in `topics.rs` must not be renamed once deployed;
 ///   treat them as stable API surface.
@@ -54,9 +57,13 @@
 ///    `emit_event(env, MOD_<MODULE>, ACT_<ACTION>, payload)`.
 /// 5. Document the new events in the module's top-level doc comment.
 pub mod emit;
+pub mod history;
 pub mod topics;
 pub mod types;
 
+#[cfg(test)]
+mod tests;
+
 // Convenience re-exports so module code only needs one import line:
 // `use crate::events::{emit_event, topics::*};`
 pub use emit::emit_event;
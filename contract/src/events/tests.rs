@@ -0,0 +1,199 @@
+#![cfg(test)]
+
+use crate::events::emit::{emit_event, get_recent_events};
+use crate::events::history::{
+    append_history, export_history_after, export_history_page, get_history_digest, prune_record,
+};
+use crate::events::topics::{ACT_CREATED, ACT_UPDATED, MOD_BOUNTY, MOD_UPGRADE};
+use crate::events::types::{HistoryKind, EVENT_RING_CAPACITY, HISTORY_FORMAT_VERSION};
+use crate::upgrade::types::ProposalError;
+use crate::StellarGuildsContract;
+use soroban_sdk::xdr::ToXdr;
+use soroban_sdk::{Bytes, BytesN, Env, Symbol};
+
+fn setup() -> (Env, soroban_sdk::Address) {
+    let env = Env::default();
+    env.budget().reset_unlimited();
+    let contract_id = env.register_contract(None, StellarGuildsContract);
+    (env, contract_id)
+}
+
+#[test]
+fn test_recent_events_are_gapless_across_modules() {
+    let (env, contract_id) = setup();
+
+    env.as_contract(&contract_id, || {
+        assert!(get_recent_events(&env, 0, 10).is_empty());
+
+        emit_event(&env, MOD_BOUNTY, ACT_CREATED, 7u64);
+        emit_event(&env, MOD_UPGRADE, ACT_UPDATED, true);
+        emit_event(&env, MOD_BOUNTY, ACT_UPDATED, (1u32, 2u32));
+
+        let digests = get_recent_events(&env, 1, 10);
+        assert_eq!(digests.len(), 3);
+        for (i, digest) in digests.iter().enumerate() {
+            assert_eq!(digest.sequence, i as u64 + 1);
+        }
+
+        let first = digests.get(0).unwrap();
+        assert_eq!(first.module, Symbol::new(&env, MOD_BOUNTY));
+        assert_eq!(first.action, Symbol::new(&env, ACT_CREATED));
+        assert_eq!(
+            first.payload_hash.to_array(),
+            env.crypto().sha256(&7u64.to_xdr(&env)).to_array()
+        );
+        assert_eq!(
+            digests.get(1).unwrap().module,
+            Symbol::new(&env, MOD_UPGRADE)
+        );
+
+        // Paging from the middle and past the end
+        let tail = get_recent_events(&env, 2, 1);
+        assert_eq!(tail.len(), 1);
+        assert_eq!(tail.get(0).unwrap().sequence, 2);
+        assert!(get_recent_events(&env, 4, 10).is_empty());
+        assert!(get_recent_events(&env, 1, 0).is_empty());
+    });
+}
+
+#[test]
+fn test_recent_events_ring_wraps() {
+    let (env, contract_id) = setup();
+    let total = EVENT_RING_CAPACITY + 5;
+
+    env.as_contract(&contract_id, || {
+        for i in 0..total {
+            emit_event(&env, MOD_BOUNTY, ACT_UPDATED, i);
+        }
+
+        // The first five events have been overwritten; an indexer asking for
+        // them sees the retained window start later and knows it has a gap
+        let digests = get_recent_events(&env, 1, u32::MAX);
+        assert_eq!(digests.len() as u64, EVENT_RING_CAPACITY);
+        assert_eq!(digests.get(0).unwrap().sequence, 6);
+        assert_eq!(digests.last().unwrap().sequence, total);
+        for (i, digest) in digests.iter().enumerate() {
+            assert_eq!(digest.sequence, 6 + i as u64);
+        }
+
+        // The newest slot holds the newest event, not a stale one
+        let newest = get_recent_events(&env, total, 1).get(0).unwrap();
+        assert_eq!(
+            newest.payload_hash.to_array(),
+            env.crypto().sha256(&(total - 1).to_xdr(&env)).to_array()
+        );
+    });
+}
+
+#[test]
+fn test_history_export_pages_rechain_to_digest() {
+    let (env, contract_id) = setup();
+
+    env.as_contract(&contract_id, || {
+        let empty = get_history_digest(&env, HistoryKind::EmergencyLog);
+        assert_eq!(empty.count, 0);
+        assert_eq!(empty.digest.to_array(), [0u8; 32]);
+
+        for value in 1u64..=7 {
+            append_history(&env, HistoryKind::EmergencyLog, value);
+        }
+        append_history(&env, HistoryKind::ProxyTransactions, 99u64);
+
+        // Walk the export three records at a time, recomputing the chain
+        let mut chain = BytesN::from_array(&env, &[0u8; 32]);
+        let mut cursor = 0u64;
+        let mut seen = 0u64;
+        loop {
+            let page = export_history_page(&env, HistoryKind::EmergencyLog, cursor, 3);
+            for record in page.records.iter() {
+                seen += 1;
+                assert_eq!(record.format, HISTORY_FORMAT_VERSION);
+                assert_eq!(record.sequence, seen);
+                assert_eq!(record.payload, seen.to_xdr(&env));
+
+                let mut preimage = Bytes::from(chain.clone());
+                preimage.append(&record.payload);
+                chain = env.crypto().sha256(&preimage).into();
+                assert_eq!(record.chain, chain);
+            }
+            cursor = page.next_cursor;
+            if page.complete {
+                break;
+            }
+        }
+        assert_eq!(seen, 7);
+
+        let digest = get_history_digest(&env, HistoryKind::EmergencyLog);
+        assert_eq!(digest.count, 7);
+        assert_eq!(digest.digest, chain);
+
+        // Kinds chain independently; a cursor past the end is an empty, complete page
+        assert_eq!(
+            get_history_digest(&env, HistoryKind::ProxyTransactions).count,
+            1
+        );
+        let tail = export_history_page(&env, HistoryKind::EmergencyLog, 7, 10);
+        assert!(tail.records.is_empty());
+        assert!(tail.complete);
+        assert_eq!(tail.next_cursor, 7);
+    });
+}
+
+#[test]
+fn test_history_cursor_is_invalidated_by_pruning() {
+    let (env, contract_id) = setup();
+
+    env.as_contract(&contract_id, || {
+        for value in 1u64..=5 {
+            append_history(&env, HistoryKind::EmergencyLog, value);
+        }
+        let sequences = |records: &soroban_sdk::Vec<crate::events::types::HistoryRecord>| {
+            let mut sequences = soroban_sdk::Vec::new(&env);
+            for record in records.iter() {
+                sequences.push_back(record.sequence);
+            }
+            sequences
+        };
+
+        let first = export_history_after(&env, HistoryKind::EmergencyLog, None, 2).unwrap();
+        assert_eq!(
+            sequences(&first.records),
+            soroban_sdk::Vec::from_array(&env, [1, 2])
+        );
+
+        // Pruning between pages invalidates the cursor
+        prune_record(&env, HistoryKind::EmergencyLog, 1);
+        assert_eq!(
+            export_history_after(&env, HistoryKind::EmergencyLog, Some(first.next.clone()), 2),
+            Err(ProposalError::CursorInvalidated)
+        );
+
+        // A restart begins after the pruned records and runs to the end
+        let restarted = export_history_after(&env, HistoryKind::EmergencyLog, None, 3).unwrap();
+        assert_eq!(
+            sequences(&restarted.records),
+            soroban_sdk::Vec::from_array(&env, [2, 3, 4])
+        );
+        let tail = export_history_after(
+            &env,
+            HistoryKind::EmergencyLog,
+            Some(restarted.next.clone()),
+            3,
+        )
+        .unwrap();
+        assert_eq!(
+            sequences(&tail.records),
+            soroban_sdk::Vec::from_array(&env, [5])
+        );
+        assert!(tail.complete);
+
+        // A completed cursor picks up records appended later
+        append_history(&env, HistoryKind::EmergencyLog, 6);
+        let appended =
+            export_history_after(&env, HistoryKind::EmergencyLog, Some(tail.next), 3).unwrap();
+        assert_eq!(
+            sequences(&appended.records),
+            soroban_sdk::Vec::from_array(&env, [6])
+        );
+    });
+}
@@ -129,3 +129,103 @@ pub const ACT_DECREASED: &str = "decreased";
 pub const ACT_UPGRADE_PROPOSED: &str = "upgrade_proposed";
 pub const ACT_UPGRADE_EXECUTED: &str = "upgrade_executed";
 pub const ACT_EMERGENCY_UPGRADE: &str = "emerg_upgrade";
+pub const ACT_FEATURE_SET: &str = "feature_set";
+pub const ACT_SUBSCRIBER_ADDED: &str = "sub_added";
+pub const ACT_SUBSCRIBER_REMOVED: &str = "sub_removed";
+pub const ACT_NOTIFIED: &str = "notified";
+pub const ACT_NOTIFY_QUEUED: &str = "notify_queued";
+pub const ACT_NOTIFY_DROPPED: &str = "notify_dropped";
+pub const ACT_INDICES_REPAIRED: &str = "idx_repaired";
+pub const ACT_RECOVERY_INITIATED: &str = "recovery_init";
+pub const ACT_RECOVERY_VOTED: &str = "recovery_voted";
+pub const ACT_RECOVERY_VETOED: &str = "recovery_vetoed";
+pub const ACT_RECOVERY_EXECUTED: &str = "recovery_exec";
+pub const ACT_UPGRADE_SCHEDULED: &str = "upgrade_scheduled";
+pub const ACT_DELAY_REQUIRED: &str = "delay_required";
+pub const ACT_IMPL_APPROVED: &str = "impl_approved";
+pub const ACT_IMPL_REVOKED: &str = "impl_revoked";
+pub const ACT_TWO_PARTY: &str = "two_party";
+pub const ACT_VERSION_CHECK_SKIPPED: &str = "vcheck_skipped";
+pub const ACT_INTERVAL_SET: &str = "interval_set";
+pub const ACT_UPGRADE_REVERTED: &str = "upgrade_reverted";
+pub const ACT_ACTION_APPROVED: &str = "action_approved";
+pub const ACT_ADMIN_ADDED: &str = "admin_added";
+pub const ACT_ADMIN_REMOVED: &str = "admin_removed";
+pub const ACT_THRESHOLD_SET: &str = "threshold_set";
+pub const ACT_AWAITING_PROXY: &str = "awaiting_proxy";
+pub const ACT_POWER_GRANTED: &str = "power_granted";
+pub const ACT_POWER_BATCH: &str = "power_batch";
+pub const ACT_MAX_POWER_SET: &str = "max_power_set";
+pub const ACT_POWER_ROOT_SET: &str = "power_root_set";
+pub const ACT_POWER_CLAIMED: &str = "power_claimed";
+pub const ACT_STATUS_CHANGED: &str = "status_changed";
+pub const ACT_AMENDED: &str = "amended";
+pub const ACT_ARCHIVED: &str = "archived";
+pub const ACT_HEARTBEAT: &str = "heartbeat";
+pub const ACT_SAFE_MODE_ENTERED: &str = "safe_mode_entered";
+pub const ACT_SAFE_MODE_EXITED: &str = "safe_mode_exited";
+pub const ACT_EMERGENCY_MAX_BUMP: &str = "emerg_max_bump";
+pub const ACT_EMERGENCY_OVERRIDE: &str = "emerg_override";
+pub const ACT_EMERGENCY_ONE_SHOT: &str = "emerg_one_shot";
+pub const ACT_EMERGENCY_WINDOW: &str = "emerg_window";
+pub const ACT_TRACK_SELECTED: &str = "track_selected";
+pub const ACT_VOTES_INVALIDATED: &str = "votes_invalidated";
+pub const ACT_PRIVATE_PROPOSED: &str = "private_proposed";
+pub const ACT_DESCRIPTION_REVEALED: &str = "desc_revealed";
+pub const ACT_BOND_POSTED: &str = "bond_posted";
+pub const ACT_BOND_REFUNDED: &str = "bond_refunded";
+pub const ACT_BOND_SLASHED: &str = "bond_slashed";
+pub const ACT_VERSION_DEPRECATED: &str = "version_deprecated";
+pub const ACT_REVALIDATION_FAILED: &str = "revalidation_failed";
+pub const ACT_COMPENSATION_QUEUED: &str = "comp_queued";
+pub const ACT_COMPENSATED: &str = "compensated";
+pub const ACT_COMPENSATION_GAVE_UP: &str = "comp_gave_up";
+pub const ACT_COMPENSATION_DISMISSED: &str = "comp_dismissed";
+pub const ACT_DUAL_CONTROL_ATTEMPT: &str = "dual_control_attempt";
+pub const ACT_DUAL_CONTROL_REJECTED: &str = "dual_control_rejected";
+pub const ACT_DUAL_CONTROL_WINDOW_SET: &str = "dual_control_window_set";
+pub const ACT_REHEARSED: &str = "rehearsed";
+pub const ACT_REHEARSAL_DISCARDED: &str = "rehearsal_discarded";
+pub const ACT_STORAGE_MIGRATED: &str = "storage_migrated";
+pub const ACT_MIGRATION_ABORTED: &str = "migration_aborted";
+pub const ACT_MIGRATION_DEFERRED: &str = "migration_deferred";
+pub const ACT_INSTANT_REVERTED: &str = "instant_reverted";
+pub const ACT_UPGRADE_CONFIRMED: &str = "upgrade_confirmed";
+pub const ACT_GOVERNANCE_PAUSED: &str = "governance_paused";
+pub const ACT_GOVERNANCE_RESUMED: &str = "governance_resumed";
+pub const ACT_GOVERNANCE_NOMINATED: &str = "governance_nominated";
+pub const ACT_GOVERNANCE_CHANGED: &str = "governance_changed";
+pub const ACT_GOVERNANCE_TRANSFER_CANCELLED: &str = "gov_transfer_cancelled";
+pub const ACT_ACTION_PROPOSED: &str = "action_proposed";
+pub const ACT_ACTION_EXECUTED: &str = "action_executed";
+pub const ACT_MIGRATION_STRANDED: &str = "migration_stranded";
+pub const ACT_ARCHIVE_DEFERRED: &str = "archive_deferred";
+pub const ACT_HISTORY_PRUNED: &str = "history_pruned";
+pub const ACT_PROPOSAL_CANCELLED: &str = "proposal_cancelled";
+pub const ACT_PROPOSAL_VETOED: &str = "proposal_vetoed";
+pub const ACT_GUARDIAN_SET: &str = "guardian_set";
+pub const ACT_MAINTENANCE_RUN: &str = "maintenance_run";
+pub const ACT_GENESIS_PAGE: &str = "genesis_page";
+pub const ACT_GENESIS_SEALED: &str = "genesis_sealed";
+pub const ACT_EXECUTION_DELAY_SET: &str = "execution_delay_set";
+pub const ACT_EXECUTION_WINDOW_SET: &str = "execution_window_set";
+pub const ACT_TEMPLATE_SET: &str = "template_set";
+pub const ACT_TEMPLATE_REMOVED: &str = "template_removed";
+pub const ACT_TEMPLATE_APPLIED: &str = "template_applied";
+pub const ACT_CALL_FAILED: &str = "call_failed";
+pub const ACT_FOOTPRINT: &str = "footprint";
+pub const ACT_POST_CHECK_FAILED: &str = "post_check_failed";
+pub const ACT_POST_CHECK_ACKNOWLEDGED: &str = "post_check_acknowledged";
+pub const ACT_UNDELEGATED: &str = "undelegated";
+pub const ACT_VOTE_CHANGED: &str = "vote_changed";
+pub const ACT_VOTER_REGISTERED: &str = "voter_registered";
+pub const ACT_VOTER_REMOVED: &str = "voter_removed";
+pub const ACT_ROLLED_BACK: &str = "rolled_back";
+
+// =========== Proxy-specific actions ===========
+
+pub const ACT_HANDOVER_STARTED: &str = "handover_started";
+pub const ACT_HANDOVER_CONFIRMED: &str = "handover_confirmed";
+pub const ACT_HANDOVER_ABORTED: &str = "handover_aborted";
+pub const ACT_HANDOVER_TIMED_OUT: &str = "handover_timed_out";
+pub const ACT_ADMIN_TRANSFER_CANCELLED: &str = "admin_transfer_cancelled";
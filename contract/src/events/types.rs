@@ -18,15 +18,76 @@
 /// Never call `env.events().publish()` directly from module code.
 /// Always go through `emit::emit_event()` so the envelope is populated
 /// consistently and the sequence counter is incremented atomically.
-use soroban_sdk::{contracttype, Symbol};
+use crate::utils::pagination::Cursor;
+use soroban_sdk::{contracttype, Bytes, BytesN, Symbol, Vec};
 
 /// Current event schema version. Increment on any breaking envelope change.
-pub const EVENT_SCHEMA_VERSION: u32 = 1;
+pub const EVENT_SCHEMA_VERSION: u32 = 2;
 
 /// Storage key used for the global monotonic event sequence counter.
 /// Stored as a persistent instance value so it survives ledger closures.
 pub const EVENT_SEQUENCE_KEY: &str = "evt_seq";
 
+/// Storage key prefix for the replay ring buffer of event digests.
+pub const EVENT_RING_KEY: &str = "evt_ring";
+
+/// Number of most recent event digests kept on-chain.
+pub const EVENT_RING_CAPACITY: u64 = 64;
+
+/// Instance storage key for the configured `EventVerbosity`.
+pub const EVENT_VERBOSITY_KEY: &str = "evt_verb";
+
+/// Instance storage key for the contract era appended to meta-event topics.
+pub const EVENT_ERA_KEY: &str = "evt_era";
+
+/// Storage key prefix for exported history records, one per (kind, sequence).
+pub const HISTORY_RECORD_KEY: &str = "hist_rec";
+
+/// Storage key prefix for the running digest of each history kind.
+pub const HISTORY_HEAD_KEY: &str = "hist_head";
+
+/// Storage key prefix for the highest pruned sequence of each history kind.
+pub const HISTORY_PRUNED_KEY: &str = "hist_prune";
+
+/// Version of the `HistoryRecord` interchange format.
+pub const HISTORY_FORMAT_VERSION: u32 = 1;
+
+/// Most records returned by one `export_history_page` call.
+pub const MAX_HISTORY_PAGE: u32 = 50;
+
+/// The contract era added to the meta-event topic, kept in instance storage
+/// so `emit_event()` reads it once per invocation however many events it
+/// publishes. Upgrade and proxy storage keep it current as versions change.
+///
+/// Fields:
+/// - `enabled`       - Whether the era topics are published at all.
+/// - `major`         - Current `Version.major` of the upgrade module.
+/// - `proxy_version` - Current proxy config version, or 0 without a proxy.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EventEra {
+    pub enabled: bool,
+    pub major: u32,
+    pub proxy_version: u32,
+}
+
+/// How much detail leveled event payloads carry. Verbosity never changes
+/// which events fire, only the payload published for them; events emitted
+/// through plain `emit_event()` carry the same payload at every level.
+///
+/// Levels:
+/// - `Minimal`  - Ids and status codes only.
+/// - `Standard` - The structured payload (the default).
+/// - `Verbose`  - The structured payload plus tallies and snapshots.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum EventVerbosity {
+    Minimal = 0,
+    Standard = 1,
+    Verbose = 2,
+}
+
 /// Metadata attached to every event published by this contract.
 ///
 /// Fields:
@@ -37,6 +98,7 @@ pub const EVENT_SEQUENCE_KEY: &str = "evt_seq";
 /// - `sequence`  â€” Monotonically increasing counter scoped to this contract.
 ///                 Useful for ordering events when multiple are emitted in one
 ///                 transaction and for detecting gaps in off-chain listeners.
+/// - `verbosity` â€” Level the payload was built at; parse leveled payloads by it.
 #[contracttype]
 #[derive(Clone, Debug)]
 pub struct EventEnvelope {
@@ -50,4 +112,89 @@ pub struct EventEnvelope {
     pub action: Symbol,
     /// Contract-global monotonic sequence number.
     pub sequence: u64,
+    /// Verbosity in force when the event was emitted (added in schema 2).
+    pub verbosity: EventVerbosity,
+}
+
+/// Compact on-chain record of one emitted event, kept in the replay ring.
+///
+/// Fields:
+/// - `sequence`     - The event's envelope sequence number.
+/// - `module`       - Module topic of the payload event.
+/// - `action`       - Action topic of the payload event.
+/// - `payload_hash` - sha256 of the XDR-encoded payload, so an indexer can
+///                    check the payload it received matches what was emitted.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EventDigest {
+    pub sequence: u64,
+    pub module: Symbol,
+    pub action: Symbol,
+    pub payload_hash: BytesN<32>,
+}
+
+/// Append-only histories kept for compliance export.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum HistoryKind {
+    /// Upgrade proposals, recorded with final tallies once they reach a terminal status
+    Proposals = 0,
+    /// Upgrade execution receipts
+    ExecutionReceipts = 1,
+    /// Proxy implementation transactions, including failed handovers
+    ProxyTransactions = 2,
+    /// Emergency action log entries
+    EmergencyLog = 3,
+}
+
+/// One exported history record.
+///
+/// Fields:
+/// - `format`   - `HISTORY_FORMAT_VERSION` at the time of recording.
+/// - `sequence` - Position within its kind, starting at 1.
+/// - `payload`  - XDR of the recorded value (`UpgradeProposal`,
+///                `ExecutionReceipt`, `UpgradeTransaction` or
+///                `EmergencyActionLog`, by kind), frozen when recorded.
+/// - `chain`    - sha256(previous chain || payload); the first record chains
+///                from 32 zero bytes. The last record's chain is the digest.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct HistoryRecord {
+    pub format: u32,
+    pub kind: HistoryKind,
+    pub sequence: u64,
+    pub payload: Bytes,
+    pub chain: BytesN<32>,
+}
+
+/// Running digest of one history kind: the chain of its last record.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct HistoryDigest {
+    pub kind: HistoryKind,
+    pub count: u64,
+    pub digest: BytesN<32>,
+}
+
+/// A page of history records and where the next page starts.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct HistoryPage {
+    pub records: Vec<HistoryRecord>,
+    /// Sequence to pass as the next cursor
+    pub next_cursor: u64,
+    /// Whether this page reaches the newest record
+    pub complete: bool,
+}
+
+/// A page of history records and the cursor to continue from
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct HistoryRecordPage {
+    pub records: Vec<HistoryRecord>,
+    /// Cursor to pass back for the following page
+    pub next: Cursor,
+    /// Whether this page reaches the end of the list
+    pub complete: bool,
 }
@@ -1,5 +1,6 @@
 use crate::events::emit::emit_event;
 use crate::events::topics::{ACT_EXECUTED, MOD_GOVERNANCE};
+use crate::utils::time;
 use soroban_sdk::{Address, Env};
 
 use crate::governance::proposals::get_proposal as load_proposal;
@@ -15,7 +16,7 @@ pub fn execute_proposal(env: &Env, proposal_id: u64, executor: Address) -> bool
     let mut proposal = load_proposal(env, proposal_id);
     executor.require_auth(); // Enforce the new auth check for security
 
-    let now = env.ledger().timestamp();
+    let now = time::now(env);
     if matches!(proposal.status, ProposalStatus::Active) && now >= proposal.voting_end {
         let _status = finalize_proposal(env, proposal_id);
         proposal = load_proposal(env, proposal_id);
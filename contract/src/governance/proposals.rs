@@ -1,5 +1,6 @@
 use crate::events::emit::emit_event;
 use crate::events::topics::{ACT_CANCELLED, ACT_PROPOSED, ACT_UPDATED, MOD_GOVERNANCE};
+use crate::utils::time;
 use soroban_sdk::{Address, Env, String, Vec};
 
 use crate::governance::storage::{
@@ -81,7 +82,7 @@ pub fn create_proposal(
     validate_execution_payload(env, guild_id, &proposal_type, &execution_payload);
 
     let id = get_next_proposal_id(env);
-    let now = env.ledger().timestamp();
+    let now = time::now(env);
     let voting_period_secs = (cfg.voting_period_days as u64) * 24 * 60 * 60;
 
     let proposal = Proposal {
@@ -1,17 +1,11 @@
-﻿use soroban_sdk::{symbol_short, Address, Env, Map, Symbol, Vec};
+﻿use crate::keys::governance::{
+    DELEGATIONS_KEY, GOV_CONFIG_KEY, GUILD_PROPOSALS_KEY, PROPOSALS_KEY, PROPOSAL_COUNTER_KEY,
+    VOTES_KEY,
+};
+use soroban_sdk::{Address, Env, Map, Vec};
 
 use crate::governance::types::{GovernanceConfig, Proposal, Vote};
 
-const PROPOSALS_KEY: Symbol = symbol_short!("g_props");
-const PROPOSAL_COUNTER_KEY: Symbol = symbol_short!("g_pcnt");
-const GUILD_PROPOSALS_KEY: Symbol = symbol_short!("g_pidx");
-
-const VOTES_KEY: Symbol = symbol_short!("g_votes");
-
-const DELEGATIONS_KEY: Symbol = symbol_short!("g_deleg");
-
-const GOV_CONFIG_KEY: Symbol = symbol_short!("g_conf");
-
 pub fn get_next_proposal_id(env: &Env) -> u64 {
     let current: u64 = env
         .storage()
@@ -1,5 +1,6 @@
 use crate::events::emit::emit_event;
 use crate::events::topics::{ACT_DELEGATED, ACT_FINALIZED, ACT_UPDATED, ACT_VOTED, MOD_GOVERNANCE};
+use crate::utils::time;
 use soroban_sdk::{Address, Env};
 
 use crate::governance::storage::{
@@ -77,7 +78,7 @@ pub fn vote(env: &Env, proposal_id: u64, voter: Address, decision: VoteDecision)
         panic!("proposal not active");
     }
 
-    let now = env.ledger().timestamp();
+    let now = time::now(env);
     if now < proposal.voting_start || now > proposal.voting_end {
         panic!("voting period closed");
     }
@@ -171,7 +172,7 @@ pub fn finalize_proposal(env: &Env, proposal_id: u64) -> ProposalStatus {
         return proposal.status;
     }
 
-    let now = env.ledger().timestamp();
+    let now = time::now(env);
     if now < proposal.voting_end {
         panic!("voting period not finished");
     }
@@ -7,6 +7,7 @@ use crate::guild::types::{
     Guild, GuildCreatedEvent, GuildJoinedEvent, Member, MemberAddedEvent, MemberRemovedEvent, Role,
     RoleUpdatedEvent,
 };
+use crate::utils::time;
 use soroban_sdk::{Address, Env, String, Vec};
 
 const PERMISSION_UPDATE_INFO: u32 = 1 << 0;
@@ -62,7 +63,7 @@ pub fn create_guild(
     }
 
     let guild_id = storage::get_next_guild_id(env);
-    let timestamp = env.ledger().timestamp();
+    let timestamp = time::now(env);
 
     let guild = Guild {
         id: guild_id,
@@ -150,7 +151,7 @@ pub fn add_member(
         }
     }
 
-    let timestamp = env.ledger().timestamp();
+    let timestamp = time::now(env);
     let member = Member {
         address: address.clone(),
         role: role.clone(),
@@ -380,7 +381,7 @@ pub fn join_guild(env: &Env, guild_id: u64, caller: Address) -> Result<bool, Str
         return Err(String::from_str(env, "Already a member of this guild"));
     }
 
-    let timestamp = env.ledger().timestamp();
+    let timestamp = time::now(env);
     let member = Member {
         address: caller.clone(),
         role: Role::Member,
@@ -1,10 +1,6 @@
 ﻿use crate::guild::types::{Guild, Member, Role};
-use soroban_sdk::{symbol_short, Address, Env, Map, Symbol, Vec};
-
-// Storage keys as symbols for efficient lookup
-const GUILDS_KEY: Symbol = symbol_short!("guilds");
-const MEMBERS_KEY: Symbol = symbol_short!("members");
-const GUILD_COUNTER_KEY: Symbol = symbol_short!("guild_cnt");
+use crate::keys::guild::{GUILDS_KEY, GUILD_COUNTER_KEY, MEMBERS_KEY};
+use soroban_sdk::{Address, Env, Map, Vec};
 
 /// Initialize storage for guilds and members
 /// This should be called during contract initialization
@@ -13,12 +13,10 @@ use crate::events::{
 };
 use crate::integration::registry;
 use crate::integration::types::{ContractType, EventFilter, EventType, PlatformEvent};
+use crate::keys::integration::{EVENT_COUNTER_KEY, EVENT_LOG_KEY, SUBSCRIPTIONS_KEY};
 use crate::utils::errors::{format_error, IntegrationErrorCode};
-use soroban_sdk::{symbol_short, Address, Env, Map, Symbol, Vec};
-
-const EVENT_LOG_KEY: Symbol = symbol_short!("i_evt");
-const SUBSCRIPTIONS_KEY: Symbol = symbol_short!("i_sub");
-const EVENT_COUNTER_KEY: Symbol = symbol_short!("i_cnt");
+use crate::utils::time;
+use soroban_sdk::{Address, Env, Map, Vec};
 
 pub fn initialize(env: &Env) {
     let events: Vec<PlatformEvent> = Vec::new(env);
@@ -53,7 +51,7 @@ pub fn emit_event(
         event_type,
         contract_source: source_contract,
         contract_address,
-        timestamp: env.ledger().timestamp(),
+        timestamp: time::now(env),
         schema_version,
         data,
     };
@@ -87,7 +85,7 @@ pub fn record_standardized_event(env: &Env, module: &str, action: &str) {
         event_type,
         contract_source,
         contract_address: registry::get_contract_address(env, contract_source),
-        timestamp: env.ledger().timestamp(),
+        timestamp: time::now(env),
         schema_version: 1,
         data: soroban_sdk::String::from_str(env, action),
     };
@@ -172,7 +170,7 @@ pub fn create_event_id(env: &Env) -> u128 {
     env.storage()
         .persistent()
         .set(&EVENT_COUNTER_KEY, &next_counter);
-    ((env.ledger().timestamp() as u128) << 64) | next_counter as u128
+    ((time::now(env) as u128) << 64) | next_counter as u128
 }
 
 fn get_events_log(env: &Env) -> Vec<PlatformEvent> {
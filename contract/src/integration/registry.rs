@@ -1,12 +1,11 @@
 use crate::integration::events as integration_events;
 use crate::integration::types::{ContractType, ContractVersion, EventType};
+use crate::keys::integration::{HISTORY_KEY, REGISTRY_KEY};
 use crate::upgrade::types::Version;
 use crate::utils::errors::{format_error, IntegrationErrorCode};
+use crate::utils::time;
 use crate::utils::validation::{is_version_increment, validate_address};
-use soroban_sdk::{symbol_short, Address, Env, Map, Symbol, Vec};
-
-const REGISTRY_KEY: Symbol = symbol_short!("i_reg");
-const HISTORY_KEY: Symbol = symbol_short!("i_hist");
+use soroban_sdk::{Address, Env, Map, Vec};
 
 pub fn initialize(env: &Env) {
     let registry: Map<ContractType, ContractVersion> = Map::new(env);
@@ -53,7 +52,7 @@ pub fn register_contract(
         contract_type,
         version: version.clone(),
         address: address.clone(),
-        deployed_at: env.ledger().timestamp(),
+        deployed_at: time::now(env),
     };
 
     registry.set(contract_type, record.clone());
@@ -132,7 +131,7 @@ pub fn update_contract(
         contract_type,
         version: new_version.clone(),
         address: new_address,
-        deployed_at: env.ledger().timestamp(),
+        deployed_at: time::now(env),
     };
 
     registry.set(contract_type, updated.clone());
@@ -0,0 +1,505 @@
+//! Registry of every raw `Symbol` the crate uses as a storage key or tag.
+//!
+//! Modules import their keys from here and never declare them locally, so a
+//! key reused by a new module is visible in one file and fails the duplicate
+//! check in `tests::storage_keys_are_unique`.
+
+use soroban_sdk::Symbol;
+
+/// Token allowances
+pub mod allowance {
+    use soroban_sdk::{symbol_short, Symbol};
+
+    /// Main allowance map: (owner, spender, token) -> TokenAllowance
+    pub const ALLOWANCES_KEY: Symbol = symbol_short!("tkn_alw");
+    /// Owner index: owner -> Vec<(spender, token)>
+    pub const OWNER_IDX_KEY: Symbol = symbol_short!("alw_oidx");
+    /// Spender index: spender -> Vec<(owner, token)>
+    pub const SPENDER_IDX_KEY: Symbol = symbol_short!("alw_sidx");
+}
+
+/// Treasury analytics snapshots
+pub mod analytics {
+    use soroban_sdk::{symbol_short, Symbol};
+
+    pub const SNAPSHOTS_KEY: Symbol = symbol_short!("a_snaps");
+    pub const SNAP_CNT_KEY: Symbol = symbol_short!("a_scnt");
+}
+
+/// Bounties
+pub mod bounty {
+    use soroban_sdk::{symbol_short, Symbol};
+
+    pub const BOUNTIES_KEY: Symbol = symbol_short!("bounties");
+    pub const BOUNTY_CNT_KEY: Symbol = symbol_short!("b_cnt");
+    pub const GUILD_BOUNTIES_KEY: Symbol = symbol_short!("g_bnties");
+}
+
+/// Disputes
+pub mod dispute {
+    use soroban_sdk::{symbol_short, Symbol};
+
+    pub const DISPUTES_KEY: Symbol = symbol_short!("dsp_all");
+    pub const DISPUTE_COUNTER_KEY: Symbol = symbol_short!("dsp_cnt");
+    pub const DISPUTE_VOTES_KEY: Symbol = symbol_short!("dsp_vot");
+    pub const REF_LOCKS_KEY: Symbol = symbol_short!("dsp_ref");
+}
+
+/// Guild governance proposals
+pub mod governance {
+    use soroban_sdk::{symbol_short, Symbol};
+
+    pub const PROPOSALS_KEY: Symbol = symbol_short!("g_props");
+    pub const PROPOSAL_COUNTER_KEY: Symbol = symbol_short!("g_pcnt");
+    pub const GUILD_PROPOSALS_KEY: Symbol = symbol_short!("g_pidx");
+    pub const VOTES_KEY: Symbol = symbol_short!("g_votes");
+    pub const DELEGATIONS_KEY: Symbol = symbol_short!("g_deleg");
+    pub const GOV_CONFIG_KEY: Symbol = symbol_short!("g_conf");
+}
+
+/// Guilds and members
+pub mod guild {
+    use soroban_sdk::{symbol_short, Symbol};
+
+    pub const GUILDS_KEY: Symbol = symbol_short!("guilds");
+    pub const MEMBERS_KEY: Symbol = symbol_short!("members");
+    pub const GUILD_COUNTER_KEY: Symbol = symbol_short!("guild_cnt");
+}
+
+/// Cross-contract registry and event log
+pub mod integration {
+    use soroban_sdk::{symbol_short, Symbol};
+
+    pub const REGISTRY_KEY: Symbol = symbol_short!("i_reg");
+    pub const HISTORY_KEY: Symbol = symbol_short!("i_hist");
+    pub const EVENT_LOG_KEY: Symbol = symbol_short!("i_evt");
+    pub const SUBSCRIPTIONS_KEY: Symbol = symbol_short!("i_sub");
+    pub const EVENT_COUNTER_KEY: Symbol = symbol_short!("i_cnt");
+}
+
+/// Upgrade proxy
+pub mod proxy {
+    use soroban_sdk::{symbol_short, Symbol};
+
+    pub const PROXY_CONFIG_KEY: Symbol = symbol_short!("prx_cfg");
+    pub const UPGRADE_HISTORY_KEY: Symbol = symbol_short!("upg_hist");
+    pub const IMPLEMENTATION_SLOT_KEY: Symbol = symbol_short!("impl_slot");
+    pub const MIN_UPGRADE_DELAY_KEY: Symbol = symbol_short!("min_delay");
+    pub const NEXT_SCHEDULE_ID_KEY: Symbol = symbol_short!("nxt_sched");
+    pub const NEXT_HANDOVER_ID_KEY: Symbol = symbol_short!("nxt_hand");
+    pub const ACTIVE_HANDOVER_KEY: Symbol = symbol_short!("hand_act");
+    pub const HANDOVER_TIMEOUT_KEY: Symbol = symbol_short!("hand_tmo");
+    pub const SCHEDULED_UPGRADE_KEY: Symbol = symbol_short!("sched_upg");
+    pub const NEXT_UPGRADE_ID_KEY: Symbol = symbol_short!("nxt_upg");
+    pub const HANDOVER_KEY: Symbol = symbol_short!("handover");
+    pub const PROXY_PAUSE_KEY: Symbol = symbol_short!("prx_pause");
+    pub const PENDING_ADMIN_KEY: Symbol = symbol_short!("pend_adm");
+    pub const REQUIRE_DELAY_KEY: Symbol = symbol_short!("req_delay");
+    pub const APPROVED_IMPL_KEY: Symbol = symbol_short!("impl_ok");
+    pub const TWO_PARTY_KEY: Symbol = symbol_short!("two_party");
+    pub const SKIP_VERSION_CHECK_KEY: Symbol = symbol_short!("skip_vchk");
+    pub const MIN_UPGRADE_INTERVAL_KEY: Symbol = symbol_short!("min_intvl");
+    pub const FROZEN_KEY: Symbol = symbol_short!("frozen");
+    pub const ADMINS_KEY: Symbol = symbol_short!("prx_adms");
+    pub const ADMIN_THRESHOLD_KEY: Symbol = symbol_short!("prx_thr");
+    pub const ACTION_APPROVALS_KEY: Symbol = symbol_short!("prx_apv");
+    pub const GOVERNANCE_SCHEDULE_KEY: Symbol = symbol_short!("prx_gsch");
+}
+
+/// Reputation profiles, contributions and badges
+pub mod reputation {
+    use soroban_sdk::{symbol_short, Symbol};
+
+    pub const PROFILES_KEY: Symbol = symbol_short!("r_prof");
+    pub const CONTRIBS_KEY: Symbol = symbol_short!("r_cont");
+    pub const CONTRIB_IDX: Symbol = symbol_short!("r_cidx");
+    pub const BADGES_KEY: Symbol = symbol_short!("r_badge");
+    pub const BADGE_IDX: Symbol = symbol_short!("r_bidx");
+    pub const CONTRIB_CNT: Symbol = symbol_short!("r_ccnt");
+    pub const BADGE_CNT: Symbol = symbol_short!("r_bcnt");
+}
+
+/// Treasuries and their transactions
+pub mod treasury {
+    use soroban_sdk::{symbol_short, Symbol};
+
+    pub const TREASURY_CNT_KEY: Symbol = symbol_short!("t_cnt");
+    pub const TREASURIES_KEY: Symbol = symbol_short!("trsries");
+    pub const TX_CNT_KEY: Symbol = symbol_short!("tx_cnt");
+    pub const TRANSACTIONS_KEY: Symbol = symbol_short!("txs");
+    pub const TREASURY_TX_INDEX_KEY: Symbol = symbol_short!("t_tx_idx");
+    pub const BUDGETS_KEY: Symbol = symbol_short!("budgets");
+    pub const ALLOWANCES_KEY: Symbol = symbol_short!("allows");
+}
+
+/// Upgrade governance
+pub mod upgrade {
+    use soroban_sdk::{symbol_short, Symbol};
+
+    pub const CURRENT_VERSION_KEY: Symbol = symbol_short!("cur_ver");
+    pub const UPGRADE_PROPOSALS_KEY: Symbol = symbol_short!("upg_prop");
+    pub const VOTING_POWER_KEY: Symbol = symbol_short!("vote_pow");
+    pub const GOVERNANCE_ADDRESS_KEY: Symbol = symbol_short!("gov_addr");
+    pub const EMERGENCY_UPGRADE_KEY: Symbol = symbol_short!("emg_upg");
+    pub const MIGRATION_PLANS_KEY: Symbol = symbol_short!("migr_pln");
+    pub const RATE_LIMIT_KEY: Symbol = symbol_short!("rate_lim");
+    pub const ID_MODE_KEY: Symbol = symbol_short!("id_mode");
+    pub const FEATURE_INDEX_KEY: Symbol = symbol_short!("feat_idx");
+    pub const SUBSCRIBERS_KEY: Symbol = symbol_short!("upg_subs");
+    pub const RETRY_QUEUE_KEY: Symbol = symbol_short!("retry_q");
+    pub const MAX_ATTEMPTS_KEY: Symbol = symbol_short!("max_att");
+    pub const NEXT_RECOVERY_ID_KEY: Symbol = symbol_short!("rec_next");
+    pub const GOVERNANCE_CONFIG_KEY: Symbol = symbol_short!("gov_cfg");
+    pub const POWER_ROOT_KEY: Symbol = symbol_short!("pow_root");
+    pub const POWER_ROOT_EPOCH_KEY: Symbol = symbol_short!("pow_epoch");
+    pub const LAST_HEARTBEAT_KEY: Symbol = symbol_short!("hb_last");
+    pub const MAX_SILENCE_KEY: Symbol = symbol_short!("hb_max");
+    pub const SAFE_MODE_KEY: Symbol = symbol_short!("safe_mode");
+    pub const EMERGENCY_MAX_BUMP_KEY: Symbol = symbol_short!("emg_bump");
+    pub const REQUIRE_REVEAL_KEY: Symbol = symbol_short!("req_revl");
+    pub const DEPRECATED_VERSIONS_KEY: Symbol = symbol_short!("depr_ver");
+    pub const COMPENSATIONS_KEY: Symbol = symbol_short!("comp_reg");
+    pub const NEXT_COMPENSATION_ID_KEY: Symbol = symbol_short!("comp_next");
+    pub const DUAL_CONTROL_WINDOW_KEY: Symbol = symbol_short!("dual_win");
+    pub const BOND_CONFIG_KEY: Symbol = symbol_short!("bond_cfg");
+    pub const SLASH_HISTORY_KEY: Symbol = symbol_short!("slash_his");
+    pub const REVERT_WINDOW_KEY: Symbol = symbol_short!("rev_win");
+    pub const PENDING_GRANTS_KEY: Symbol = symbol_short!("vote_pend");
+    pub const GOVERNANCE_PAUSE_KEY: Symbol = symbol_short!("gov_pause");
+    pub const MIGRATING_KEY: Symbol = symbol_short!("migrating");
+    pub const STRANDED_KEY: Symbol = symbol_short!("stranded");
+    pub const VOTING_PERIOD_KEY: Symbol = symbol_short!("vote_per");
+    pub const ARCHIVE_QUEUE_KEY: Symbol = symbol_short!("arch_q");
+    pub const KEEPER_CONFIG_KEY: Symbol = symbol_short!("keep_cfg");
+    pub const KEEPER_STATS_KEY: Symbol = symbol_short!("keep_stat");
+    pub const TASK_LAST_RUN_KEY: Symbol = symbol_short!("task_run");
+    pub const SCHEMA_VERSION_KEY: Symbol = symbol_short!("schema");
+    pub const GENESIS_KEY: Symbol = symbol_short!("genesis");
+    pub const PROPOSAL_ID_INDEX_KEY: Symbol = symbol_short!("prop_ids");
+    pub const EXECUTION_DELAY_KEY: Symbol = symbol_short!("exec_dly");
+    pub const EXECUTION_WINDOW_KEY: Symbol = symbol_short!("exec_win");
+    pub const TEMPLATE_INDEX_KEY: Symbol = symbol_short!("tmpl_idx");
+    pub const FOOTPRINT_KEY: Symbol = symbol_short!("footprint");
+    pub const EXECUTING_KEY: Symbol = symbol_short!("executing");
+    pub const CURSOR_GENERATION_KEY: Symbol = symbol_short!("cur_gen");
+    pub const POST_CHECK_KEY: Symbol = symbol_short!("post_chk");
+    pub const PROPOSER_WINDOW_KEY: Symbol = symbol_short!("prop_win");
+    pub const FEATURE_KEY: Symbol = symbol_short!("feature");
+    pub const NOTIFY_RESULTS_KEY: Symbol = symbol_short!("notif_res");
+    pub const RECEIPT_KEY: Symbol = symbol_short!("receipt");
+    pub const PARAMS_KEY: Symbol = symbol_short!("prm_snap");
+    pub const RECOVERY_KEY: Symbol = symbol_short!("recovery");
+    pub const RECOVERY_VOTERS_KEY: Symbol = symbol_short!("rec_vote");
+    pub const PROXY_LINK_KEY: Symbol = symbol_short!("prx_link");
+    pub const AUTH_MATRIX_KEY: Symbol = symbol_short!("auth_mx");
+    pub const ROLE_MEMBERS_KEY: Symbol = symbol_short!("role_mem");
+    pub const STATUS_INDEX_KEY: Symbol = symbol_short!("st_idx");
+    pub const PROPOSER_INDEX_KEY: Symbol = symbol_short!("prop_idx");
+    pub const VOTE_RECEIPTS_KEY: Symbol = symbol_short!("vote_rcpt");
+    pub const POWER_CLAIM_KEY: Symbol = symbol_short!("pow_claim");
+    pub const VERSION_INDEX_KEY: Symbol = symbol_short!("ver_idx");
+    pub const VERSION_MINORS_KEY: Symbol = symbol_short!("ver_mins");
+    pub const EMERGENCY_OVERRIDE_KEY: Symbol = symbol_short!("emg_ovr");
+    pub const PRIVATE_DESCRIPTION_KEY: Symbol = symbol_short!("priv_desc");
+    pub const DESCRIPTION_BLOB_KEY: Symbol = symbol_short!("desc_blob");
+    pub const PROPOSAL_BOND_KEY: Symbol = symbol_short!("prop_bond");
+    pub const TRACK_KEY: Symbol = symbol_short!("gov_trk");
+    pub const TRACK_IDS_KEY: Symbol = symbol_short!("trk_ids");
+    pub const APPROVED_AT_KEY: Symbol = symbol_short!("appr_at");
+    pub const ELIGIBLE_AT_KEY: Symbol = symbol_short!("elig_at");
+    pub const TEMPLATE_KEY: Symbol = symbol_short!("template");
+    pub const APPLIED_TEMPLATE_KEY: Symbol = symbol_short!("tmpl_use");
+    pub const NEXT_PROPOSAL_ID_KEY: Symbol = symbol_short!("nxt_prop");
+    pub const CONTROL_CHANGE_KEY: Symbol = symbol_short!("ctl_chg");
+    pub const MIGRATION_ENTRY_KEY: Symbol = symbol_short!("migr_ent");
+    pub const MIGRATION_KEYS_KEY: Symbol = symbol_short!("migr_keys");
+    pub const REHEARSAL_KEY: Symbol = symbol_short!("rehearsal");
+    pub const MIGRATION_PROGRESS_KEY: Symbol = symbol_short!("migr_prog");
+    pub const REVERSE_MIGRATION_KEY: Symbol = symbol_short!("migr_rev");
+    pub const PREVIOUS_IMPL_KEY: Symbol = symbol_short!("prev_impl");
+    pub const VOTERS_KEY: Symbol = symbol_short!("voted");
+    pub const POWER_SNAPSHOT_KEY: Symbol = symbol_short!("pow_snap");
+    pub const DELEGATION_KEY: Symbol = symbol_short!("delegates");
+    pub const DELEGATORS_KEY: Symbol = symbol_short!("delegtrs");
+    pub const COUNTED_POWER_KEY: Symbol = symbol_short!("pow_used");
+    pub const REGISTRY_POWER_KEY: Symbol = symbol_short!("reg_power");
+    pub const EMERGENCY_EXECUTIONS_KEY: Symbol = symbol_short!("emrg_exec");
+    pub const MAX_VOTING_POWER_KEY: Symbol = symbol_short!("max_power");
+    pub const LAST_EMERGENCY_KEY: Symbol = symbol_short!("emrg_last");
+    pub const EMERGENCY_ONE_SHOT_KEY: Symbol = symbol_short!("emrg_once");
+    pub const EMERGENCY_EXPIRY_KEY: Symbol = symbol_short!("emrg_exp");
+    pub const VERSION_HISTORY_KEY: Symbol = symbol_short!("ver_hist");
+    pub const VERSION_COUNT_KEY: Symbol = symbol_short!("ver_count");
+    pub const VERSION_IMPL_KEY: Symbol = symbol_short!("ver_impl");
+    pub const PENDING_GOVERNANCE_KEY: Symbol = symbol_short!("gov_pend");
+    pub const PROPOSAL_KIND_KEY: Symbol = symbol_short!("prop_kind");
+}
+
+/// Every storage key above, for the duplicate checks
+pub const STORAGE_KEYS: [Symbol; 160] = [
+    allowance::ALLOWANCES_KEY,
+    allowance::OWNER_IDX_KEY,
+    allowance::SPENDER_IDX_KEY,
+    analytics::SNAPSHOTS_KEY,
+    analytics::SNAP_CNT_KEY,
+    bounty::BOUNTIES_KEY,
+    bounty::BOUNTY_CNT_KEY,
+    bounty::GUILD_BOUNTIES_KEY,
+    dispute::DISPUTES_KEY,
+    dispute::DISPUTE_COUNTER_KEY,
+    dispute::DISPUTE_VOTES_KEY,
+    dispute::REF_LOCKS_KEY,
+    governance::PROPOSALS_KEY,
+    governance::PROPOSAL_COUNTER_KEY,
+    governance::GUILD_PROPOSALS_KEY,
+    governance::VOTES_KEY,
+    governance::DELEGATIONS_KEY,
+    governance::GOV_CONFIG_KEY,
+    guild::GUILDS_KEY,
+    guild::MEMBERS_KEY,
+    guild::GUILD_COUNTER_KEY,
+    integration::REGISTRY_KEY,
+    integration::HISTORY_KEY,
+    integration::EVENT_LOG_KEY,
+    integration::SUBSCRIPTIONS_KEY,
+    integration::EVENT_COUNTER_KEY,
+    proxy::PROXY_CONFIG_KEY,
+    proxy::UPGRADE_HISTORY_KEY,
+    proxy::IMPLEMENTATION_SLOT_KEY,
+    proxy::MIN_UPGRADE_DELAY_KEY,
+    proxy::NEXT_SCHEDULE_ID_KEY,
+    proxy::NEXT_HANDOVER_ID_KEY,
+    proxy::ACTIVE_HANDOVER_KEY,
+    proxy::HANDOVER_TIMEOUT_KEY,
+    proxy::SCHEDULED_UPGRADE_KEY,
+    proxy::NEXT_UPGRADE_ID_KEY,
+    proxy::HANDOVER_KEY,
+    proxy::PROXY_PAUSE_KEY,
+    proxy::PENDING_ADMIN_KEY,
+    proxy::REQUIRE_DELAY_KEY,
+    proxy::APPROVED_IMPL_KEY,
+    proxy::TWO_PARTY_KEY,
+    proxy::SKIP_VERSION_CHECK_KEY,
+    proxy::MIN_UPGRADE_INTERVAL_KEY,
+    proxy::FROZEN_KEY,
+    proxy::ADMINS_KEY,
+    proxy::ADMIN_THRESHOLD_KEY,
+    proxy::ACTION_APPROVALS_KEY,
+    proxy::GOVERNANCE_SCHEDULE_KEY,
+    reputation::PROFILES_KEY,
+    reputation::CONTRIBS_KEY,
+    reputation::CONTRIB_IDX,
+    reputation::BADGES_KEY,
+    reputation::BADGE_IDX,
+    reputation::CONTRIB_CNT,
+    reputation::BADGE_CNT,
+    treasury::TREASURY_CNT_KEY,
+    treasury::TREASURIES_KEY,
+    treasury::TX_CNT_KEY,
+    treasury::TRANSACTIONS_KEY,
+    treasury::TREASURY_TX_INDEX_KEY,
+    treasury::BUDGETS_KEY,
+    treasury::ALLOWANCES_KEY,
+    upgrade::CURRENT_VERSION_KEY,
+    upgrade::UPGRADE_PROPOSALS_KEY,
+    upgrade::VOTING_POWER_KEY,
+    upgrade::GOVERNANCE_ADDRESS_KEY,
+    upgrade::EMERGENCY_UPGRADE_KEY,
+    upgrade::MIGRATION_PLANS_KEY,
+    upgrade::RATE_LIMIT_KEY,
+    upgrade::ID_MODE_KEY,
+    upgrade::FEATURE_INDEX_KEY,
+    upgrade::SUBSCRIBERS_KEY,
+    upgrade::RETRY_QUEUE_KEY,
+    upgrade::MAX_ATTEMPTS_KEY,
+    upgrade::NEXT_RECOVERY_ID_KEY,
+    upgrade::GOVERNANCE_CONFIG_KEY,
+    upgrade::POWER_ROOT_KEY,
+    upgrade::POWER_ROOT_EPOCH_KEY,
+    upgrade::LAST_HEARTBEAT_KEY,
+    upgrade::MAX_SILENCE_KEY,
+    upgrade::SAFE_MODE_KEY,
+    upgrade::EMERGENCY_MAX_BUMP_KEY,
+    upgrade::REQUIRE_REVEAL_KEY,
+    upgrade::DEPRECATED_VERSIONS_KEY,
+    upgrade::COMPENSATIONS_KEY,
+    upgrade::NEXT_COMPENSATION_ID_KEY,
+    upgrade::DUAL_CONTROL_WINDOW_KEY,
+    upgrade::BOND_CONFIG_KEY,
+    upgrade::SLASH_HISTORY_KEY,
+    upgrade::REVERT_WINDOW_KEY,
+    upgrade::PENDING_GRANTS_KEY,
+    upgrade::GOVERNANCE_PAUSE_KEY,
+    upgrade::MIGRATING_KEY,
+    upgrade::STRANDED_KEY,
+    upgrade::VOTING_PERIOD_KEY,
+    upgrade::ARCHIVE_QUEUE_KEY,
+    upgrade::KEEPER_CONFIG_KEY,
+    upgrade::KEEPER_STATS_KEY,
+    upgrade::TASK_LAST_RUN_KEY,
+    upgrade::SCHEMA_VERSION_KEY,
+    upgrade::GENESIS_KEY,
+    upgrade::PROPOSAL_ID_INDEX_KEY,
+    upgrade::EXECUTION_DELAY_KEY,
+    upgrade::EXECUTION_WINDOW_KEY,
+    upgrade::TEMPLATE_INDEX_KEY,
+    upgrade::FOOTPRINT_KEY,
+    upgrade::EXECUTING_KEY,
+    upgrade::CURSOR_GENERATION_KEY,
+    upgrade::POST_CHECK_KEY,
+    upgrade::PROPOSER_WINDOW_KEY,
+    upgrade::FEATURE_KEY,
+    upgrade::NOTIFY_RESULTS_KEY,
+    upgrade::RECEIPT_KEY,
+    upgrade::PARAMS_KEY,
+    upgrade::RECOVERY_KEY,
+    upgrade::RECOVERY_VOTERS_KEY,
+    upgrade::PROXY_LINK_KEY,
+    upgrade::AUTH_MATRIX_KEY,
+    upgrade::ROLE_MEMBERS_KEY,
+    upgrade::STATUS_INDEX_KEY,
+    upgrade::PROPOSER_INDEX_KEY,
+    upgrade::VOTE_RECEIPTS_KEY,
+    upgrade::POWER_CLAIM_KEY,
+    upgrade::VERSION_INDEX_KEY,
+    upgrade::VERSION_MINORS_KEY,
+    upgrade::EMERGENCY_OVERRIDE_KEY,
+    upgrade::PRIVATE_DESCRIPTION_KEY,
+    upgrade::DESCRIPTION_BLOB_KEY,
+    upgrade::PROPOSAL_BOND_KEY,
+    upgrade::TRACK_KEY,
+    upgrade::TRACK_IDS_KEY,
+    upgrade::APPROVED_AT_KEY,
+    upgrade::ELIGIBLE_AT_KEY,
+    upgrade::TEMPLATE_KEY,
+    upgrade::APPLIED_TEMPLATE_KEY,
+    upgrade::NEXT_PROPOSAL_ID_KEY,
+    upgrade::CONTROL_CHANGE_KEY,
+    upgrade::MIGRATION_ENTRY_KEY,
+    upgrade::MIGRATION_KEYS_KEY,
+    upgrade::REHEARSAL_KEY,
+    upgrade::MIGRATION_PROGRESS_KEY,
+    upgrade::REVERSE_MIGRATION_KEY,
+    upgrade::PREVIOUS_IMPL_KEY,
+    upgrade::VOTERS_KEY,
+    upgrade::POWER_SNAPSHOT_KEY,
+    upgrade::DELEGATION_KEY,
+    upgrade::DELEGATORS_KEY,
+    upgrade::COUNTED_POWER_KEY,
+    upgrade::REGISTRY_POWER_KEY,
+    upgrade::EMERGENCY_EXECUTIONS_KEY,
+    upgrade::MAX_VOTING_POWER_KEY,
+    upgrade::LAST_EMERGENCY_KEY,
+    upgrade::EMERGENCY_ONE_SHOT_KEY,
+    upgrade::EMERGENCY_EXPIRY_KEY,
+    upgrade::VERSION_HISTORY_KEY,
+    upgrade::VERSION_COUNT_KEY,
+    upgrade::VERSION_IMPL_KEY,
+    upgrade::PENDING_GOVERNANCE_KEY,
+    upgrade::PROPOSAL_KIND_KEY,
+];
+
+/// Symbols that are not keys of this contract's storage, so they may repeat
+/// a key: transition reasons, event payload tags, track names and the keys
+/// of stub contracts
+pub mod tags {
+    use soroban_sdk::{symbol_short, Symbol};
+
+    pub const VOTE_REASON: Symbol = symbol_short!("vote");
+    pub const DEADLINE_REASON: Symbol = symbol_short!("deadline");
+    pub const CANCEL_REASON: Symbol = symbol_short!("cancel");
+    pub const EXECUTE_REASON: Symbol = symbol_short!("execute");
+    pub const PROXY_REASON: Symbol = symbol_short!("proxy");
+    pub const REVERT_REASON: Symbol = symbol_short!("revert");
+    pub const VETO_REASON: Symbol = symbol_short!("veto");
+    /// Governance config parameters a `SetParameter` proposal can change
+    pub const QUORUM_PARAM: Symbol = symbol_short!("quorum");
+    pub const APPROVAL_PARAM: Symbol = symbol_short!("approval");
+    pub const ACTIVATION_DELAY_PARAM: Symbol = symbol_short!("act_delay");
+    pub const REVERT_WINDOW_PARAM: Symbol = symbol_short!("rev_win");
+    pub const MIGRATION_ABORTED_REASON: Symbol = symbol_short!("migr_abrt");
+    /// An approved proposal outlived its execution window
+    pub const WINDOW_REASON: Symbol = symbol_short!("window");
+    /// Payload tag of the storage schema migration event
+    pub const SCHEMA_TAG: Symbol = symbol_short!("schema");
+    pub const FAST_TRACK_NAME: Symbol = symbol_short!("fast");
+    pub const STANDARD_TRACK_NAME: Symbol = symbol_short!("standard");
+    pub const SUPERMAJORITY_TRACK_NAME: Symbol = symbol_short!("supermaj");
+    /// Instance key of the deferring migration stub
+    #[cfg(any(test, feature = "testutils"))]
+    pub const STUB_READY_AT_KEY: Symbol = symbol_short!("ready_at");
+    /// Instance keys of the re-entering migration stub
+    #[cfg(any(test, feature = "testutils"))]
+    pub const STUB_TARGET_KEY: Symbol = symbol_short!("target");
+    #[cfg(any(test, feature = "testutils"))]
+    pub const STUB_REENTRY_KEY: Symbol = symbol_short!("reentry");
+    /// Instance key of the versioned implementation stub
+    #[cfg(any(test, feature = "testutils"))]
+    pub const STUB_VERSION_KEY: Symbol = symbol_short!("reported");
+
+    /// Every stub contract key above, for the duplicate check; the stubs
+    /// share no storage with this contract, so only their own keys must differ
+    #[cfg(any(test, feature = "testutils"))]
+    pub const STUB_KEYS: [Symbol; 4] = [
+        STUB_READY_AT_KEY,
+        STUB_TARGET_KEY,
+        STUB_REENTRY_KEY,
+        STUB_VERSION_KEY,
+    ];
+}
+
+/// The first key listed more than once, if any
+pub fn find_duplicate(keys: &[Symbol]) -> Option<Symbol> {
+    keys.iter()
+        .enumerate()
+        .find(|(i, key)| keys[..*i].contains(key))
+        .map(|(_, key)| key.clone())
+}
+
+/// Panic in debug builds when two modules registered the same storage key.
+/// Called by the storage layer on initialization; compiled out of release
+/// builds.
+pub fn debug_assert_unique_keys() {
+    #[cfg(debug_assertions)]
+    if let Some(key) = find_duplicate(&STORAGE_KEYS) {
+        panic!("storage key {:?} is registered twice", key);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use soroban_sdk::{symbol_short, Env, Map};
+
+    #[test]
+    fn storage_keys_are_unique() {
+        let env = Env::default();
+        let mut seen: Map<Symbol, u32> = Map::new(&env);
+        for key in STORAGE_KEYS.iter() {
+            let count = seen.get(key.clone()).unwrap_or(0);
+            seen.set(key.clone(), count + 1);
+        }
+        for (key, count) in seen.iter() {
+            assert_eq!(count, 1, "storage key {:?} is registered twice", key);
+        }
+        assert_eq!(seen.len() as usize, STORAGE_KEYS.len());
+        assert_eq!(find_duplicate(&STORAGE_KEYS), None);
+        debug_assert_unique_keys();
+    }
+
+    #[test]
+    fn stub_keys_are_unique() {
+        assert_eq!(find_duplicate(&tags::STUB_KEYS), None);
+    }
+
+    #[test]
+    fn duplicate_keys_are_reported() {
+        let keys = [
+            upgrade::SCHEMA_VERSION_KEY,
+            symbol_short!("unrelated"),
+            tags::SCHEMA_TAG,
+        ];
+        assert_eq!(find_duplicate(&keys), Some(symbol_short!("schema")));
+        assert_eq!(find_duplicate(&keys[..2]), None);
+    }
+}
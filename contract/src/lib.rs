@@ -1,11 +1,14 @@
 #![no_std]
 
-use soroban_sdk::{contract, contractimpl, Address, Env, String, Vec};
+use soroban_sdk::{contract, contractimpl, panic_with_error, Address, BytesN, Env, String, Vec};
 
+#[cfg(any(test, feature = "client"))]
+pub mod client;
 mod events;
 mod guild;
 mod integration;
 mod interfaces;
+mod keys;
 mod utils;
 use guild::membership::{
     add_member, create_guild, get_all_members, get_member, has_permission, is_member, join_guild,
@@ -152,7 +155,7 @@ use multisig::{
 mod upgrade;
 use upgrade::logic as upgrade_logic;
 use upgrade::storage as upgrade_storage;
-use upgrade::types::Version;
+use upgrade::types::{ProposalIdMode, Version};
 
 mod proxy;
 use integration::types::{
@@ -1239,7 +1242,7 @@ impl StellarGuildsContract {
         num_periods: u32,
         period_length_secs: u64,
     ) -> SpendingForecast {
-        let current_time = env.ledger().timestamp();
+        let current_time = utils::time::now(&env);
         compute_forecast(
             &env,
             treasury_id,
@@ -1273,7 +1276,7 @@ impl StellarGuildsContract {
         let index = analytics::get_snapshot_count(&env, treasury_id);
         let snapshot = TreasurySnapshot {
             treasury_id,
-            timestamp: env.ledger().timestamp(),
+            timestamp: utils::time::now(&env),
             balance_xlm: treasury.balance_xlm,
             total_deposits: treasury.total_deposits,
             total_withdrawals: treasury.total_withdrawals,
@@ -2367,14 +2370,21 @@ impl StellarGuildsContract {
         initial_version_minor: u32,
         initial_version_patch: u32,
         governance_address: Address,
-    ) -> bool {
+    ) {
+        upgrade_storage::reject_reentry(&env);
         let version = Version::new(
             initial_version_major,
             initial_version_minor,
             initial_version_patch,
         );
-        upgrade_storage::initialize(&env, version, governance_address);
-        true
+        if let Err(e) = upgrade_storage::initialize(&env, version, governance_address) {
+            panic_with_error!(&env, e);
+        }
+    }
+
+    /// Whether the upgrade system has been initialized
+    pub fn is_upgrade_initialized(env: Env) -> bool {
+        upgrade_storage::is_initialized(&env)
     }
 
     /// Propose an upgrade
@@ -2387,145 +2397,1964 @@ impl StellarGuildsContract {
         target_version_patch: u32,
         description: String,
     ) -> u64 {
+        upgrade_storage::reject_reentry(&env);
+        let target_version = Version::new(
+            target_version_major,
+            target_version_minor,
+            target_version_patch,
+        );
+        match upgrade_logic::propose_upgrade(
+            &env,
+            &proposer,
+            &new_contract_address,
+            &target_version,
+            description,
+        ) {
+            Ok(id) => id,
+            Err(e) => panic_with_error!(&env, e),
+        }
+    }
+
+    /// Propose an upgrade with a salt feeding the content hash proposal id
+    pub fn propose_upgrade_with_salt(
+        env: Env,
+        proposer: Address,
+        new_contract_address: Address,
+        target_version_major: u32,
+        target_version_minor: u32,
+        target_version_patch: u32,
+        description: String,
+        salt: BytesN<32>,
+    ) -> u64 {
+        upgrade_storage::reject_reentry(&env);
         let target_version = Version::new(
             target_version_major,
             target_version_minor,
             target_version_patch,
         );
-        upgrade_logic::propose_upgrade(
+        match upgrade_logic::propose_upgrade_with_salt(
             &env,
             &proposer,
             &new_contract_address,
             &target_version,
             description,
+            salt,
+        ) {
+            Ok(id) => id,
+            Err(e) => panic_with_error!(&env, e),
+        }
+    }
+
+    /// Compute the content hash id a salted proposal would receive
+    pub fn compute_upgrade_proposal_id(
+        env: Env,
+        proposer: Address,
+        new_contract_address: Address,
+        target_version_major: u32,
+        target_version_minor: u32,
+        target_version_patch: u32,
+        salt: BytesN<32>,
+    ) -> u64 {
+        let target_version = Version::new(
+            target_version_major,
+            target_version_minor,
+            target_version_patch,
+        );
+        upgrade_logic::compute_content_hash_id(
+            &env,
+            &proposer,
+            &new_contract_address,
+            &target_version,
+            &salt,
         )
     }
 
+    /// Switch between sequential and content hash upgrade proposal ids
+    pub fn set_proposal_id_mode(env: Env, caller: Address, mode: ProposalIdMode) {
+        upgrade_storage::reject_reentry(&env);
+        if let Err(e) = upgrade_logic::set_proposal_id_mode(&env, &caller, mode) {
+            panic_with_error!(&env, e);
+        }
+    }
+
+    /// Configure the per-proposer upgrade proposal rate limit (0 disables it)
+    pub fn set_upgrade_rate_limit(
+        env: Env,
+        caller: Address,
+        max_proposals_per_window: u32,
+        window_seconds: u64,
+    ) {
+        upgrade_storage::reject_reentry(&env);
+        if let Err(e) =
+            upgrade_logic::set_rate_limit(&env, &caller, max_proposals_per_window, window_seconds)
+        {
+            panic_with_error!(&env, e);
+        }
+    }
+
+    /// Earliest timestamp at which the proposer may create another upgrade proposal
+    pub fn get_next_proposal_time(env: Env, proposer: Address) -> u64 {
+        upgrade_logic::next_proposal_allowed_at(&env, &proposer)
+    }
+
     /// Vote on an upgrade proposal
-    pub fn vote_on_upgrade_proposal(
+    pub fn vote_on_upgrade_proposal(env: Env, voter: Address, proposal_id: u64, vote_for: bool) {
+        upgrade_storage::reject_reentry(&env);
+        if let Err(e) = upgrade_logic::vote_on_proposal(&env, &voter, proposal_id, vote_for) {
+            panic_with_error!(&env, e);
+        }
+    }
+
+    /// Vote For, Against or Abstain on an upgrade proposal
+    pub fn cast_upgrade_vote(
         env: Env,
         voter: Address,
         proposal_id: u64,
-        vote_for: bool,
-    ) -> bool {
-        match upgrade_logic::vote_on_proposal(&env, &voter, proposal_id, vote_for) {
-            Ok(_) => true,
-            Err(_) => false,
+        choice: upgrade::types::VoteChoice,
+    ) {
+        upgrade_storage::reject_reentry(&env);
+        if let Err(e) = upgrade_logic::cast_vote(&env, &voter, proposal_id, choice) {
+            panic_with_error!(&env, e);
         }
     }
 
-    /// Execute an approved upgrade
-    pub fn execute_upgrade_proposal(env: Env, executor: Address, proposal_id: u64) -> bool {
-        match upgrade_logic::execute_upgrade(&env, &executor, proposal_id) {
-            Ok(_) => true,
-            Err(_) => false,
+    /// Switch an earlier upgrade vote while the proposal is still pending
+    pub fn change_upgrade_vote(env: Env, voter: Address, proposal_id: u64, new_vote_for: bool) {
+        upgrade_storage::reject_reentry(&env);
+        if let Err(e) = upgrade_logic::change_vote(&env, &voter, proposal_id, new_vote_for) {
+            panic_with_error!(&env, e);
         }
     }
 
-    /// Perform emergency upgrade
-    pub fn emergency_upgrade(
+    /// Delegate upgrade votes to another address until undelegated
+    pub fn delegate_upgrade_votes(env: Env, delegator: Address, delegate_to: Address) {
+        upgrade_storage::reject_reentry(&env);
+        if let Err(e) = upgrade_logic::delegate(&env, &delegator, &delegate_to) {
+            panic_with_error!(&env, e);
+        }
+    }
+
+    /// Withdraw an upgrade vote delegation
+    pub fn undelegate_upgrade_votes(env: Env, delegator: Address) {
+        upgrade_storage::reject_reentry(&env);
+        if let Err(e) = upgrade_logic::undelegate(&env, &delegator) {
+            panic_with_error!(&env, e);
+        }
+    }
+
+    /// The address a delegator's upgrade votes go to, if any
+    pub fn get_upgrade_delegate(env: Env, delegator: Address) -> Option<Address> {
+        upgrade_storage::get_delegate(&env, &delegator)
+    }
+
+    /// Settle an upgrade proposal whose voting deadline has passed; anyone may call this
+    pub fn finalize_upgrade_proposal(env: Env, proposal_id: u64) -> upgrade::types::UpgradeStatus {
+        upgrade_storage::reject_reentry(&env);
+        match upgrade_logic::finalize_proposal(&env, proposal_id) {
+            Ok(status) => status,
+            Err(e) => panic_with_error!(&env, e),
+        }
+    }
+
+    /// Set the voting period of upgrade proposals on the governance-wide rules
+    pub fn set_upgrade_voting_period(env: Env, caller: Address, period: u64) {
+        upgrade_storage::reject_reentry(&env);
+        if let Err(e) = upgrade_logic::set_voting_period(&env, &caller, period) {
+            panic_with_error!(&env, e);
+        }
+    }
+
+    /// Set the most upgrade voting power one address may hold (0 removes the maximum)
+    pub fn set_max_upgrade_voting_power(env: Env, caller: Address, max: u32) {
+        upgrade_storage::reject_reentry(&env);
+        if let Err(e) = upgrade_logic::set_max_voting_power(&env, &caller, max) {
+            panic_with_error!(&env, e);
+        }
+    }
+
+    /// Get the most upgrade voting power one address may hold (0 means no maximum)
+    pub fn get_max_upgrade_voting_power(env: Env) -> u32 {
+        upgrade_storage::get_max_voting_power(&env)
+    }
+
+    /// Get the voting period of upgrade proposals on the governance-wide rules
+    pub fn get_upgrade_voting_period(env: Env) -> u64 {
+        upgrade_storage::get_voting_period(&env)
+    }
+
+    /// Propose an upgrade with the defaults of a registered proposal template
+    pub fn propose_upgrade_from_template(
         env: Env,
-        caller: Address,
+        proposer: Address,
+        template: soroban_sdk::Symbol,
         new_contract_address: Address,
-        new_version_major: u32,
-        new_version_minor: u32,
-        new_version_patch: u32,
-    ) -> bool {
-        let new_version = Version::new(new_version_major, new_version_minor, new_version_patch);
-        match upgrade_logic::emergency_upgrade(&env, &caller, &new_contract_address, &new_version) {
-            Ok(_) => true,
-            Err(_) => false,
+        target_version_major: u32,
+        target_version_minor: u32,
+        target_version_patch: u32,
+        description: String,
+    ) -> u64 {
+        upgrade_storage::reject_reentry(&env);
+        let target_version = Version::new(
+            target_version_major,
+            target_version_minor,
+            target_version_patch,
+        );
+        match upgrade_logic::propose_from_template(
+            &env,
+            &proposer,
+            &template,
+            &new_contract_address,
+            &target_version,
+            description,
+        ) {
+            Ok(id) => id,
+            Err(e) => panic_with_error!(&env, e),
         }
     }
 
-    /// Toggle emergency upgrades on/off
-    pub fn toggle_emergency_upgrades(env: Env, caller: Address, enable: bool) -> bool {
-        match upgrade_logic::toggle_emergency_upgrades(&env, &caller, enable) {
-            Ok(_) => true,
-            Err(_) => false,
+    /// Register or replace an upgrade proposal template
+    pub fn set_upgrade_template(
+        env: Env,
+        caller: Address,
+        template: upgrade::types::ProposalTemplate,
+    ) {
+        upgrade_storage::reject_reentry(&env);
+        if let Err(e) = upgrade_logic::set_proposal_template(&env, &caller, &template) {
+            panic_with_error!(&env, e);
         }
     }
 
-    /// Get current contract version
-    pub fn get_current_version(env: Env) -> Version {
-        upgrade_storage::get_current_version(&env)
+    /// Delete an upgrade proposal template
+    pub fn remove_upgrade_template(env: Env, caller: Address, name: soroban_sdk::Symbol) {
+        upgrade_storage::reject_reentry(&env);
+        if let Err(e) = upgrade_logic::remove_proposal_template(&env, &caller, &name) {
+            panic_with_error!(&env, e);
+        }
     }
 
-    /// Register a migration plan for an upgrade
-    pub fn register_migration_plan(
+    /// Get an upgrade proposal template by name
+    pub fn get_upgrade_template(
+        env: Env,
+        name: soroban_sdk::Symbol,
+    ) -> Option<upgrade::types::ProposalTemplate> {
+        upgrade_storage::get_template(&env, &name)
+    }
+
+    /// List the registered upgrade proposal templates
+    pub fn get_upgrade_templates(env: Env) -> Vec<upgrade::types::ProposalTemplate> {
+        upgrade_logic::get_proposal_templates(&env)
+    }
+
+    /// Get the template an upgrade proposal was created from and the defaults it received
+    pub fn get_upgrade_applied_template(
+        env: Env,
+        proposal_id: u64,
+    ) -> Option<upgrade::types::AppliedTemplate> {
+        upgrade_storage::get_applied_template(&env, proposal_id)
+    }
+
+    /// Set how long approved upgrade proposals wait before they may execute
+    pub fn set_upgrade_execution_delay(env: Env, caller: Address, delay: u64) {
+        upgrade_storage::reject_reentry(&env);
+        if let Err(e) = upgrade_logic::set_execution_delay(&env, &caller, delay) {
+            panic_with_error!(&env, e);
+        }
+    }
+
+    /// Get how long approved upgrade proposals wait before they may execute
+    pub fn get_upgrade_execution_delay(env: Env) -> u64 {
+        upgrade_storage::get_execution_delay(&env)
+    }
+
+    /// Limit how long an approved upgrade proposal stays executable; `None` removes the limit
+    pub fn set_upgrade_execution_window(env: Env, caller: Address, window: Option<u64>) {
+        upgrade_storage::reject_reentry(&env);
+        if let Err(e) = upgrade_logic::set_execution_window(&env, &caller, window) {
+            panic_with_error!(&env, e);
+        }
+    }
+
+    /// Get how long an approved upgrade proposal stays executable, if limited
+    pub fn get_upgrade_execution_window(env: Env) -> Option<u64> {
+        upgrade_storage::get_execution_window(&env)
+    }
+
+    /// When an approved upgrade proposal clears the execution delay
+    pub fn get_upgrade_eligible_at(env: Env, proposal_id: u64) -> Option<u64> {
+        upgrade_storage::get_eligible_at(&env, proposal_id)
+    }
+
+    /// Set the quorum rules snapshotted by new upgrade proposals
+    pub fn set_upgrade_governance_config(
         env: Env,
         caller: Address,
+        config: upgrade::types::GovernanceConfig,
+    ) {
+        upgrade_storage::reject_reentry(&env);
+        if let Err(e) = upgrade_logic::set_governance_config(&env, &caller, &config) {
+            panic_with_error!(&env, e);
+        }
+    }
+
+    /// Get the quorum rules new upgrade proposals will snapshot
+    pub fn get_upgrade_governance_config(env: Env) -> upgrade::types::GovernanceConfig {
+        upgrade_storage::get_governance_config(&env)
+    }
+
+    /// Require new upgrade proposals to post a bond in `token` (0 disables)
+    pub fn set_upgrade_proposal_bond(env: Env, caller: Address, token: Address, amount: i128) {
+        upgrade_storage::reject_reentry(&env);
+        if let Err(e) = upgrade_logic::set_proposal_bond(&env, &caller, &token, amount) {
+            panic_with_error!(&env, e);
+        }
+    }
+
+    /// Get the bond an upgrade proposal posted, if any
+    pub fn get_upgrade_proposal_bond(
+        env: Env,
         proposal_id: u64,
-        from_version_major: u32,
-        from_version_minor: u32,
-        from_version_patch: u32,
-        to_version_major: u32,
-        to_version_minor: u32,
-        to_version_patch: u32,
-        migration_function_selector: soroban_sdk::Symbol,
-        estimated_gas: u64,
-    ) -> bool {
-        let from_version = Version::new(from_version_major, from_version_minor, from_version_patch);
-        let to_version = Version::new(to_version_major, to_version_minor, to_version_patch);
-        let migration_plan = upgrade::types::MigrationPlan {
-            from_version,
-            to_version,
-            migration_function_selector,
-            estimated_gas,
-        };
-        match upgrade_logic::register_migration_plan(&env, &caller, proposal_id, &migration_plan) {
-            Ok(_) => true,
-            Err(_) => false,
+    ) -> Option<upgrade::types::ProposalBond> {
+        upgrade_storage::get_proposal_bond(&env, proposal_id)
+    }
+
+    /// Page through slashed upgrade proposal bonds, oldest first
+    pub fn get_slash_history(
+        env: Env,
+        offset: u32,
+        limit: u32,
+    ) -> Vec<upgrade::types::SlashRecord> {
+        upgrade_logic::get_slash_history(&env, offset, limit)
+    }
+
+    /// Propose a governance action: an upgrade, a config parameter change,
+    /// a new governance address or an emergency upgrade toggle
+    pub fn propose_governance_action(
+        env: Env,
+        proposer: Address,
+        kind: upgrade::types::ProposalKind,
+        description: String,
+    ) -> u64 {
+        upgrade_storage::reject_reentry(&env);
+        match upgrade_logic::propose_action(&env, &proposer, &kind, description) {
+            Ok(id) => id,
+            Err(e) => panic_with_error!(&env, e),
         }
     }
 
-    // ============ Proxy Functions ============
+    /// Compute the content hash id `propose_governance_action` would assign
+    pub fn compute_action_proposal_id(
+        env: Env,
+        proposer: Address,
+        kind: upgrade::types::ProposalKind,
+    ) -> u64 {
+        upgrade_logic::compute_action_hash_id(
+            &env,
+            &proposer,
+            &kind,
+            &BytesN::from_array(&env, &[0u8; 32]),
+        )
+    }
 
-    /// Initialize proxy functionality
-    pub fn initialize_proxy(env: Env, initial_implementation: Address, admin: Address) -> bool {
-        proxy_storage::initialize(&env, initial_implementation, admin);
-        true
+    /// Get the action an upgrade proposal carries; none for a plain upgrade
+    pub fn get_upgrade_proposal_kind(
+        env: Env,
+        proposal_id: u64,
+    ) -> Option<upgrade::types::ProposalKind> {
+        upgrade_storage::get_proposal_kind(&env, proposal_id)
+    }
+
+    /// Propose an upgrade on a governance track (1 fast, 2 standard, 3 supermajority)
+    pub fn propose_upgrade_on_track(
+        env: Env,
+        proposer: Address,
+        new_contract_address: Address,
+        target_version_major: u32,
+        target_version_minor: u32,
+        target_version_patch: u32,
+        description: String,
+        track_id: u32,
+    ) -> u64 {
+        upgrade_storage::reject_reentry(&env);
+        let target_version = Version::new(
+            target_version_major,
+            target_version_minor,
+            target_version_patch,
+        );
+        match upgrade_logic::propose_upgrade_on_track(
+            &env,
+            &proposer,
+            &new_contract_address,
+            &target_version,
+            description,
+            track_id,
+        ) {
+            Ok(id) => id,
+            Err(e) => panic_with_error!(&env, e),
+        }
+    }
+
+    /// Propose an upgrade whose description stays private until revealed
+    pub fn propose_private_upgrade(
+        env: Env,
+        proposer: Address,
+        new_contract_address: Address,
+        target_version: Version,
+        placeholder: String,
+        commitment: BytesN<32>,
+        reveal_deadline: u64,
+    ) -> u64 {
+        upgrade_storage::reject_reentry(&env);
+        match upgrade_logic::propose_private_upgrade(
+            &env,
+            &proposer,
+            &new_contract_address,
+            &target_version,
+            placeholder,
+            commitment,
+            reveal_deadline,
+        ) {
+            Ok(id) => id,
+            Err(e) => panic_with_error!(&env, e),
+        }
     }
 
-    /// Upgrade the proxy to a new implementation
-    pub fn proxy_upgrade(env: Env, caller: Address, new_implementation: Address) -> bool {
-        match proxy_impl::upgrade(&env, &caller, &new_implementation) {
-            Ok(_) => true,
-            Err(_) => false,
+    /// Reveal a private upgrade proposal's description against its commitment
+    pub fn reveal_upgrade_description(
+        env: Env,
+        proposer: Address,
+        proposal_id: u64,
+        full_text: String,
+    ) {
+        upgrade_storage::reject_reentry(&env);
+        if let Err(e) = upgrade_logic::reveal_description(&env, &proposer, proposal_id, full_text) {
+            panic_with_error!(&env, e);
         }
     }
 
-    /// Transfer admin rights of the proxy
-    pub fn proxy_transfer_admin(env: Env, caller: Address, new_admin: Address) -> bool {
-        match proxy_impl::transfer_admin(&env, &caller, &new_admin) {
-            Ok(_) => true,
-            Err(_) => false,
+    /// Require private upgrade proposals to be revealed before execution
+    pub fn set_upgrade_reveal_required(env: Env, caller: Address, required: bool) {
+        upgrade_storage::reject_reentry(&env);
+        if let Err(e) = upgrade_logic::set_reveal_required(&env, &caller, required) {
+            panic_with_error!(&env, e);
         }
     }
 
-    /// Get proxy information
-    pub fn proxy_get_info(env: Env) -> ProxyConfig {
-        proxy_impl::get_proxy_info(&env)
+    /// Get the commitment behind a private upgrade proposal
+    pub fn get_upgrade_private_description(
+        env: Env,
+        proposal_id: u64,
+    ) -> Option<upgrade::types::PrivateDescription> {
+        upgrade_storage::get_private_description(&env, proposal_id)
     }
 
-    /// Check if proxy is paused
-    pub fn proxy_is_paused(env: Env) -> bool {
-        proxy_impl::is_paused(&env)
+    /// Hash of the active upgrade governance parameters
+    pub fn get_governance_digest(env: Env) -> BytesN<32> {
+        upgrade_logic::get_governance_digest(&env)
+    }
+
+    /// Every upgrade governance setting below `min`; empty when all minimums are met
+    pub fn assert_min_standards(
+        env: Env,
+        min: upgrade::types::GovernanceMinimums,
+    ) -> Vec<upgrade::types::GovernanceShortfall> {
+        match upgrade_logic::assert_min_standards(&env, &min) {
+            Ok(()) => Vec::new(&env),
+            Err(shortfalls) => shortfalls,
+        }
+    }
+
+    /// Define or replace a governance track
+    pub fn set_upgrade_governance_track(
+        env: Env,
+        caller: Address,
+        track: upgrade::types::GovernanceTrack,
+    ) {
+        upgrade_storage::reject_reentry(&env);
+        if let Err(e) = upgrade_logic::set_governance_track(&env, &caller, &track) {
+            panic_with_error!(&env, e);
+        }
+    }
+
+    /// Get a governance track's rules
+    pub fn get_upgrade_governance_track(
+        env: Env,
+        track_id: u32,
+    ) -> Option<upgrade::types::GovernanceTrack> {
+        upgrade_storage::get_governance_track(&env, track_id)
+    }
+
+    /// Set an address's upgrade voting power; increases wait out the activation delay
+    pub fn grant_upgrade_voting_power(
+        env: Env,
+        caller: Address,
+        member: Address,
+        power: u32,
+    ) -> u64 {
+        upgrade_storage::reject_reentry(&env);
+        match upgrade_logic::grant_voting_power(&env, &caller, &member, power) {
+            Ok(active_from) => active_from,
+            Err(e) => panic_with_error!(&env, e),
+        }
+    }
+
+    /// Commit to a merkle root of claimable upgrade voting power
+    pub fn set_upgrade_voting_power_root(env: Env, caller: Address, root: BytesN<32>) -> u32 {
+        upgrade_storage::reject_reentry(&env);
+        match upgrade_logic::set_voting_power_root(&env, &caller, &root) {
+            Ok(epoch) => epoch,
+            Err(e) => panic_with_error!(&env, e),
+        }
+    }
+
+    /// Claim upgrade voting power with a merkle proof against the current root
+    pub fn claim_upgrade_voting_power(
+        env: Env,
+        claimer: Address,
+        power: u32,
+        proof: Vec<BytesN<32>>,
+    ) -> u64 {
+        upgrade_storage::reject_reentry(&env);
+        match upgrade_logic::claim_voting_power(&env, &claimer, power, &proof) {
+            Ok(active_from) => active_from,
+            Err(e) => panic_with_error!(&env, e),
+        }
+    }
+
+    /// Whether an address has already voted on an upgrade proposal
+    pub fn has_voted_on_upgrade(env: Env, proposal_id: u64, voter: Address) -> bool {
+        upgrade_storage::has_voted(&env, proposal_id, &voter)
+    }
+
+    /// List upgrade voters with their active and pending voting power
+    pub fn get_upgrade_voters(env: Env) -> Vec<upgrade::types::VoterEntry> {
+        upgrade_storage::get_voters(&env)
+    }
+
+    /// Page through upgrade voters from position `start`, counting from 0
+    pub fn get_upgrade_voters_page(
+        env: Env,
+        start: u32,
+        limit: u32,
+    ) -> Vec<upgrade::types::VoterEntry> {
+        upgrade_logic::get_voters(&env, start, limit)
+    }
+
+    /// Set the upgrade voting power of up to 100 addresses at once; returns the entries applied
+    pub fn set_voting_power_batch(env: Env, caller: Address, entries: Vec<(Address, u32)>) -> u32 {
+        upgrade_storage::reject_reentry(&env);
+        match upgrade_logic::set_voting_power_batch(&env, &caller, &entries) {
+            Ok(applied) => applied,
+            Err(e) => panic_with_error!(&env, e),
+        }
+    }
+
+    /// Add a voter to the upgrade voter registry; returns when its power activates
+    pub fn register_upgrade_voter(env: Env, caller: Address, voter: Address, power: u32) -> u64 {
+        upgrade_storage::reject_reentry(&env);
+        match upgrade_logic::register_voter(&env, &caller, &voter, power) {
+            Ok(active_from) => active_from,
+            Err(e) => panic_with_error!(&env, e),
+        }
+    }
+
+    /// Remove a voter from the upgrade voter registry; returns the base power removed
+    pub fn remove_upgrade_voter(env: Env, caller: Address, voter: Address) -> u32 {
+        upgrade_storage::reject_reentry(&env);
+        match upgrade_logic::remove_voter(&env, &caller, &voter) {
+            Ok(removed) => removed,
+            Err(e) => panic_with_error!(&env, e),
+        }
+    }
+
+    /// Page through upgrade voters from `cursor`, or from the first voter
+    pub fn get_upgrade_voters_by_cursor(
+        env: Env,
+        cursor: Option<utils::pagination::Cursor>,
+        limit: u32,
+    ) -> upgrade::types::VoterPage {
+        match upgrade_logic::get_voters_after(&env, cursor, limit) {
+            Ok(page) => page,
+            Err(e) => panic_with_error!(&env, e),
+        }
+    }
+
+    /// A fresh cursor for walking a paged list in `direction`
+    pub fn start_page_cursor(
+        env: Env,
+        scope: utils::pagination::PageScope,
+        direction: utils::pagination::CursorDirection,
+    ) -> utils::pagination::Cursor {
+        utils::pagination::start_cursor(&env, scope, direction)
+    }
+
+    /// Page through the ids of upgrade proposals targeting a major (and optionally minor) version
+    pub fn get_upgrade_proposals_by_version(
+        env: Env,
+        major: u32,
+        minor: Option<u32>,
+        offset: u32,
+        limit: u32,
+    ) -> Vec<u64> {
+        upgrade_logic::get_proposals_by_version(&env, major, minor, offset, limit)
+    }
+
+    /// Page through upgrade proposals in ascending id order from `start_id`.
+    /// Superseded by `get_upgrade_proposals_by_cursor`; kept for one release.
+    pub fn get_upgrade_proposals(
+        env: Env,
+        start_id: u64,
+        limit: u32,
+    ) -> Vec<upgrade::types::UpgradeProposal> {
+        upgrade_logic::get_proposals(&env, start_id, limit)
+    }
+
+    /// Page through upgrade proposals from `cursor`, or from the lowest id
+    pub fn get_upgrade_proposals_by_cursor(
+        env: Env,
+        cursor: Option<utils::pagination::Cursor>,
+        limit: u32,
+    ) -> upgrade::types::ProposalPage {
+        match upgrade_logic::get_proposals_after(&env, cursor, limit) {
+            Ok(page) => page,
+            Err(e) => panic_with_error!(&env, e),
+        }
+    }
+
+    /// Number of stored upgrade proposals
+    pub fn get_upgrade_proposal_count(env: Env) -> u64 {
+        upgrade_storage::get_proposal_count(&env)
+    }
+
+    /// List pending upgrade proposals in ascending id order
+    pub fn get_pending_upgrade_proposals(env: Env) -> Vec<upgrade::types::UpgradeProposal> {
+        upgrade_storage::get_pending_proposals(&env)
+    }
+
+    /// List upgrade proposals in a status, in ascending id order
+    pub fn get_upgrade_proposals_by_status(
+        env: Env,
+        status: upgrade::types::UpgradeStatus,
+    ) -> Vec<upgrade::types::UpgradeProposal> {
+        upgrade_storage::get_proposals_by_status(&env, status)
+    }
+
+    /// Cancel a pending upgrade proposal as its proposer or governance
+    pub fn cancel_upgrade_proposal(env: Env, caller: Address, proposal_id: u64) {
+        upgrade_storage::reject_reentry(&env);
+        if let Err(e) = upgrade_logic::cancel_proposal(&env, &caller, proposal_id) {
+            panic_with_error!(&env, e);
+        }
+    }
+
+    /// Veto a pending or approved upgrade proposal as the guardian
+    pub fn veto_upgrade_proposal(env: Env, guardian: Address, proposal_id: u64) {
+        upgrade_storage::reject_reentry(&env);
+        if let Err(e) = upgrade_logic::veto_proposal(&env, &guardian, proposal_id) {
+            panic_with_error!(&env, e);
+        }
+    }
+
+    /// Amend a pending upgrade proposal; a changed target resets its votes
+    pub fn amend_upgrade_proposal(
+        env: Env,
+        proposer: Address,
+        proposal_id: u64,
+        new_contract_address: Address,
+        version: Version,
+        description: String,
+    ) -> upgrade::types::AmendmentKind {
+        upgrade_storage::reject_reentry(&env);
+        match upgrade_logic::amend_proposal(
+            &env,
+            &proposer,
+            proposal_id,
+            &new_contract_address,
+            &version,
+            description,
+        ) {
+            Ok(kind) => kind,
+            Err(e) => panic_with_error!(&env, e),
+        }
+    }
+
+    /// Remove a finalized upgrade proposal from storage; false if it was
+    /// queued because the archive sink did not acknowledge it
+    pub fn archive_upgrade_proposal(env: Env, caller: Address, proposal_id: u64) -> bool {
+        upgrade_storage::reject_reentry(&env);
+        match upgrade_logic::archive_proposal(&env, &caller, proposal_id) {
+            Ok(archived) => archived,
+            Err(e) => panic_with_error!(&env, e),
+        }
+    }
+
+    /// Retry up to `max` deferred archivals; returns how many succeeded
+    pub fn retry_upgrade_archival(env: Env, max: u32) -> u32 {
+        upgrade_storage::reject_reentry(&env);
+        upgrade_logic::retry_archival(&env, max)
+    }
+
+    /// Proposal ids awaiting acknowledgment from the archive sink
+    pub fn get_upgrade_archive_queue(env: Env) -> Vec<u64> {
+        upgrade_storage::get_archive_queue(&env)
+    }
+
+    /// Page through the vote receipts of a finalized upgrade proposal.
+    /// Superseded by `export_upgrade_votes_by_cursor`; kept for one release.
+    pub fn export_upgrade_votes(
+        env: Env,
+        proposal_id: u64,
+        offset: u32,
+        limit: u32,
+    ) -> Vec<upgrade::types::VoteReceipt> {
+        match upgrade_logic::export_votes(&env, proposal_id, offset, limit) {
+            Ok(receipts) => receipts,
+            Err(e) => panic_with_error!(&env, e),
+        }
+    }
+
+    /// Page through the vote receipts of a finalized upgrade proposal from `cursor`
+    pub fn export_upgrade_votes_by_cursor(
+        env: Env,
+        proposal_id: u64,
+        cursor: Option<utils::pagination::Cursor>,
+        limit: u32,
+    ) -> upgrade::types::VoteReceiptPage {
+        match upgrade_logic::export_votes_after(&env, proposal_id, cursor, limit) {
+            Ok(page) => page,
+            Err(e) => panic_with_error!(&env, e),
+        }
+    }
+
+    /// Get the final tallies and receipt count of a finalized upgrade proposal
+    pub fn get_upgrade_tally_checkpoint(
+        env: Env,
+        proposal_id: u64,
+    ) -> upgrade::types::TallyCheckpoint {
+        match upgrade_logic::get_tally_checkpoint(&env, proposal_id) {
+            Ok(checkpoint) => checkpoint,
+            Err(e) => panic_with_error!(&env, e),
+        }
+    }
+
+    /// Get the voting rules an upgrade proposal is decided under
+    pub fn get_upgrade_proposal_params(
+        env: Env,
+        proposal_id: u64,
+    ) -> upgrade::types::ProposalParams {
+        upgrade_storage::get_proposal_params(&env, proposal_id)
+    }
+
+    /// Execute an approved upgrade proposal of any kind. A migration that
+    /// aborts or defers comes back as the outcome, with its progress kept.
+    pub fn execute_upgrade_proposal(
+        env: Env,
+        executor: Address,
+        proposal_id: u64,
+    ) -> upgrade::types::ExecutionOutcome {
+        upgrade_storage::reject_reentry(&env);
+        match upgrade_logic::execute_proposal(&env, &executor, proposal_id) {
+            Ok(outcome) => outcome,
+            Err(e) => panic_with_error!(&env, e),
+        }
+    }
+
+    /// Perform emergency upgrade
+    pub fn emergency_upgrade(
+        env: Env,
+        caller: Address,
+        new_contract_address: Address,
+        new_version_major: u32,
+        new_version_minor: u32,
+        new_version_patch: u32,
+    ) {
+        upgrade_storage::reject_reentry(&env);
+        let new_version = Version::new(new_version_major, new_version_minor, new_version_patch);
+        if let Err(e) =
+            upgrade_logic::emergency_upgrade(&env, &caller, &new_contract_address, &new_version)
+        {
+            panic_with_error!(&env, e);
+        }
+    }
+
+    /// Emergency upgrade past the configured version bump limit, approved by a multisig emergency operation
+    pub fn emergency_upgrade_with_override(
+        env: Env,
+        caller: Address,
+        new_contract_address: Address,
+        new_version: Version,
+        multisig_op_id: u64,
+    ) {
+        upgrade_storage::reject_reentry(&env);
+        if let Err(e) = upgrade_logic::emergency_upgrade_with_override(
+            &env,
+            &caller,
+            &new_contract_address,
+            &new_version,
+            multisig_op_id,
+        ) {
+            panic_with_error!(&env, e);
+        }
+    }
+
+    /// Set the largest version change an emergency upgrade may make
+    pub fn set_emergency_max_bump(env: Env, caller: Address, max_bump: upgrade::types::BumpKind) {
+        upgrade_storage::reject_reentry(&env);
+        if let Err(e) = upgrade_logic::set_emergency_max_bump(&env, &caller, max_bump) {
+            panic_with_error!(&env, e);
+        }
+    }
+
+    /// Toggle emergency upgrades on/off
+    pub fn toggle_emergency_upgrades(env: Env, caller: Address, enable: bool) {
+        upgrade_storage::reject_reentry(&env);
+        if let Err(e) = upgrade_logic::toggle_emergency_upgrades(&env, &caller, enable) {
+            panic_with_error!(&env, e);
+        }
+    }
+
+    /// Enable emergency upgrades until a timestamp, inclusive
+    pub fn enable_emergency_upgrades_until(env: Env, caller: Address, expiry: u64) {
+        upgrade_storage::reject_reentry(&env);
+        if let Err(e) = upgrade_logic::enable_emergency_upgrades_until(&env, &caller, expiry) {
+            panic_with_error!(&env, e);
+        }
+    }
+
+    /// When the emergency upgrade window closes, if emergency upgrades were enabled with one
+    pub fn get_emergency_upgrade_expiry(env: Env) -> Option<u64> {
+        upgrade_storage::get_emergency_expiry(&env)
+    }
+
+    /// Choose whether an emergency upgrade turns emergency upgrades off again
+    pub fn set_emergency_one_shot(env: Env, caller: Address, one_shot: bool) {
+        upgrade_storage::reject_reentry(&env);
+        if let Err(e) = upgrade_logic::set_emergency_one_shot(&env, &caller, one_shot) {
+            panic_with_error!(&env, e);
+        }
+    }
+
+    /// Whether an emergency upgrade turns emergency upgrades off again
+    pub fn is_emergency_one_shot(env: Env) -> bool {
+        upgrade_storage::is_emergency_one_shot(&env)
+    }
+
+    /// The most recent emergency upgrade, if any
+    pub fn get_last_emergency_upgrade(env: Env) -> Option<upgrade::types::EmergencyUpgradeRecord> {
+        upgrade_storage::get_last_emergency_upgrade(&env)
+    }
+
+    /// Get everything needed to render an upgrade proposal page in one call
+    pub fn get_upgrade_proposal_detail(
+        env: Env,
+        proposal_id: u64,
+        viewer: Option<Address>,
+    ) -> Option<upgrade::types::ProposalDetail> {
+        upgrade_logic::get_proposal_detail(&env, proposal_id, viewer)
+    }
+
+    /// For, against and abstain weights of an upgrade proposal, with quorum and approval share
+    pub fn get_proposal_result(
+        env: Env,
+        proposal_id: u64,
+    ) -> Option<upgrade::types::ProposalResult> {
+        upgrade_logic::get_proposal_result(&env, proposal_id)
+    }
+
+    /// Set a version-gated feature flag
+    pub fn set_upgrade_feature(
+        env: Env,
+        caller: Address,
+        name: soroban_sdk::Symbol,
+        enabled: bool,
+        min_version: Option<Version>,
+    ) {
+        upgrade_storage::reject_reentry(&env);
+        if let Err(e) = upgrade_logic::set_feature(&env, &caller, name, enabled, min_version) {
+            panic_with_error!(&env, e);
+        }
+    }
+
+    /// Check whether a feature flag is active for the current version
+    pub fn is_upgrade_feature_enabled(env: Env, name: soroban_sdk::Symbol) -> bool {
+        upgrade_logic::is_feature_enabled(&env, &name)
+    }
+
+    /// List feature flags with pagination
+    pub fn get_upgrade_features(
+        env: Env,
+        offset: u32,
+        limit: u32,
+    ) -> Vec<upgrade::types::FeatureFlag> {
+        upgrade_logic::get_features(&env, offset, limit)
+    }
+
+    /// Register a contract to receive `on_upgrade` after executed upgrades
+    pub fn add_upgrade_subscriber(env: Env, caller: Address, subscriber: Address) {
+        upgrade_storage::reject_reentry(&env);
+        if let Err(e) = upgrade_logic::add_subscriber(&env, &caller, &subscriber) {
+            panic_with_error!(&env, e);
+        }
+    }
+
+    /// Stop notifying a contract about executed upgrades
+    pub fn remove_upgrade_subscriber(env: Env, caller: Address, subscriber: Address) {
+        upgrade_storage::reject_reentry(&env);
+        if let Err(e) = upgrade_logic::remove_subscriber(&env, &caller, &subscriber) {
+            panic_with_error!(&env, e);
+        }
+    }
+
+    /// List contracts notified after executed upgrades
+    pub fn get_upgrade_subscribers(env: Env) -> Vec<Address> {
+        upgrade_storage::get_subscribers(&env)
+    }
+
+    /// Per-subscriber notification outcomes for an executed proposal
+    pub fn get_notification_results(
+        env: Env,
+        proposal_id: u64,
+    ) -> Vec<upgrade::types::NotificationResult> {
+        upgrade_storage::get_notification_results(&env, proposal_id)
+    }
+
+    /// Retry up to `max` failed upgrade notifications; returns how many were delivered
+    pub fn retry_upgrade_notifications(env: Env, max: u32) -> u32 {
+        upgrade_storage::reject_reentry(&env);
+        upgrade_logic::retry_notifications(&env, max)
+    }
+
+    /// Failed upgrade notifications awaiting retry
+    pub fn get_notification_retry_queue(env: Env) -> Vec<upgrade::types::PendingNotification> {
+        upgrade_storage::get_retry_queue(&env)
+    }
+
+    /// Retry up to `max` due compensations; returns how many succeeded
+    pub fn run_compensations(env: Env, max: u32) -> u32 {
+        upgrade_storage::reject_reentry(&env);
+        upgrade_logic::run_compensations(&env, max)
+    }
+
+    /// Side effects that failed after an upgrade, pending or given up
+    pub fn get_pending_compensations(env: Env) -> Vec<upgrade::types::PendingCompensation> {
+        upgrade_logic::get_pending_compensations(&env)
+    }
+
+    /// Drop a given-up compensation (governance only)
+    pub fn dismiss_compensation(env: Env, caller: Address, compensation_id: u64) {
+        upgrade_storage::reject_reentry(&env);
+        if let Err(e) = upgrade_logic::dismiss_compensation(&env, &caller, compensation_id) {
+            panic_with_error!(&env, e);
+        }
+    }
+
+    /// Set how many attempts an upgrade notification gets before it is dropped
+    pub fn set_notification_max_attempts(env: Env, caller: Address, max_attempts: u32) {
+        upgrade_storage::reject_reentry(&env);
+        if let Err(e) = upgrade_logic::set_notification_max_attempts(&env, &caller, max_attempts) {
+            panic_with_error!(&env, e);
+        }
+    }
+
+    /// Rebuild the index entries of a proposal restored after ledger expiry
+    pub fn repair_upgrade_indices(env: Env, caller: Address, proposal_id: u64) {
+        upgrade_storage::reject_reentry(&env);
+        if let Err(e) = upgrade_logic::repair_indices(&env, &caller, proposal_id) {
+            panic_with_error!(&env, e);
+        }
+    }
+
+    /// Finish an executed upgrade proposal once its proxy schedule has run
+    pub fn complete_upgrade_execution(env: Env, proposal_id: u64) -> upgrade::types::UpgradeStatus {
+        upgrade_storage::reject_reentry(&env);
+        match upgrade_logic::complete_execution(&env, proposal_id) {
+            Ok(status) => status,
+            Err(e) => panic_with_error!(&env, e),
+        }
+    }
+
+    /// Get the proxy schedule an executed upgrade proposal is waiting on
+    pub fn get_upgrade_proxy_link(env: Env, proposal_id: u64) -> Option<u64> {
+        upgrade_storage::get_proxy_link(&env, proposal_id)
+    }
+
+    /// Open a voter-driven proposal to replace a lost governance address
+    pub fn initiate_upgrade_recovery(env: Env, initiator: Address, new_governance: Address) -> u64 {
+        upgrade_storage::reject_reentry(&env);
+        match upgrade_logic::initiate_recovery(&env, &initiator, &new_governance) {
+            Ok(id) => id,
+            Err(e) => panic_with_error!(&env, e),
+        }
+    }
+
+    /// Support an open governance recovery
+    pub fn vote_upgrade_recovery(env: Env, voter: Address, recovery_id: u64) {
+        upgrade_storage::reject_reentry(&env);
+        if let Err(e) = upgrade_logic::vote_recovery(&env, &voter, recovery_id) {
+            panic_with_error!(&env, e);
+        }
+    }
+
+    /// Nominate a new upgrade governance address (current governance only)
+    pub fn transfer_upgrade_governance(env: Env, caller: Address, new_governance: Address) {
+        upgrade_storage::reject_reentry(&env);
+        if let Err(e) = upgrade_logic::transfer_governance(&env, &caller, &new_governance) {
+            panic_with_error!(&env, e);
+        }
+    }
+
+    /// Accept the upgrade governance role as the nominated address
+    pub fn accept_upgrade_governance(env: Env, new_governance: Address) {
+        upgrade_storage::reject_reentry(&env);
+        if let Err(e) = upgrade_logic::accept_governance(&env, &new_governance) {
+            panic_with_error!(&env, e);
+        }
+    }
+
+    /// Withdraw a pending upgrade governance nomination
+    pub fn cancel_governance_transfer(env: Env, caller: Address) {
+        upgrade_storage::reject_reentry(&env);
+        if let Err(e) = upgrade_logic::cancel_governance_transfer(&env, &caller) {
+            panic_with_error!(&env, e);
+        }
+    }
+
+    /// The address nominated for upgrade governance, if any
+    pub fn get_pending_upgrade_governance(env: Env) -> Option<Address> {
+        upgrade_storage::get_pending_governance(&env)
+    }
+
+    /// Cancel an open governance recovery (current governance only)
+    pub fn veto_upgrade_recovery(env: Env, caller: Address, recovery_id: u64) {
+        upgrade_storage::reject_reentry(&env);
+        if let Err(e) = upgrade_logic::veto_recovery(&env, &caller, recovery_id) {
+            panic_with_error!(&env, e);
+        }
+    }
+
+    /// Replace governance once a recovery has passed and its timelock elapsed
+    pub fn execute_upgrade_recovery(env: Env, caller: Address, recovery_id: u64) {
+        upgrade_storage::reject_reentry(&env);
+        if let Err(e) = upgrade_logic::execute_recovery(&env, &caller, recovery_id) {
+            panic_with_error!(&env, e);
+        }
+    }
+
+    /// Get a governance recovery proposal
+    pub fn get_upgrade_recovery(
+        env: Env,
+        recovery_id: u64,
+    ) -> Option<upgrade::types::RecoveryProposal> {
+        upgrade_storage::get_recovery(&env, recovery_id)
+    }
+
+    /// Get the immutable receipt recorded when a proposal was executed
+    pub fn get_execution_receipt(
+        env: Env,
+        proposal_id: u64,
+    ) -> Option<upgrade::types::ExecutionReceipt> {
+        upgrade_storage::get_execution_receipt(&env, proposal_id)
+    }
+
+    /// Who executed a proposal and when, if it was executed
+    pub fn get_execution_record(
+        env: Env,
+        proposal_id: u64,
+    ) -> Option<upgrade::types::ExecutionRecord> {
+        upgrade_logic::get_execution_record(&env, proposal_id)
+    }
+
+    /// Every emergency upgrade with who applied it and when, oldest first
+    pub fn get_emergency_executions(env: Env) -> Vec<upgrade::types::ExecutionRecord> {
+        upgrade_storage::get_emergency_executions(&env)
+    }
+
+    /// Change which roles may perform a non-protected upgrade action
+    pub fn set_upgrade_action_roles(
+        env: Env,
+        caller: Address,
+        action: upgrade::types::Action,
+        roles: Vec<upgrade::types::Role>,
+    ) {
+        upgrade_storage::reject_reentry(&env);
+        if let Err(e) = upgrade::auth::set_action_roles(&env, &caller, action, &roles) {
+            panic_with_error!(&env, e);
+        }
+    }
+
+    /// Get the roles allowed to perform an upgrade action
+    pub fn get_upgrade_action_roles(
+        env: Env,
+        action: upgrade::types::Action,
+    ) -> Vec<upgrade::types::Role> {
+        upgrade_storage::get_action_roles(&env, action)
+    }
+
+    /// Grant an upgrade-module role to an address
+    pub fn grant_upgrade_role(
+        env: Env,
+        caller: Address,
+        role: upgrade::types::Role,
+        member: Address,
+    ) {
+        upgrade_storage::reject_reentry(&env);
+        if let Err(e) = upgrade::auth::grant_role(&env, &caller, role, &member) {
+            panic_with_error!(&env, e);
+        }
+    }
+
+    /// Revoke an upgrade-module role from an address
+    pub fn revoke_upgrade_role(
+        env: Env,
+        caller: Address,
+        role: upgrade::types::Role,
+        member: Address,
+    ) {
+        upgrade_storage::reject_reentry(&env);
+        if let Err(e) = upgrade::auth::revoke_role(&env, &caller, role, &member) {
+            panic_with_error!(&env, e);
+        }
+    }
+
+    /// Set the sole upgrade guardian, or clear the role with `None` (governance only)
+    pub fn set_upgrade_guardian(env: Env, caller: Address, guardian: Option<Address>) {
+        upgrade_storage::reject_reentry(&env);
+        if let Err(e) = upgrade::auth::set_guardian(&env, &caller, guardian) {
+            panic_with_error!(&env, e);
+        }
+    }
+
+    /// The upgrade guardian, if the role is held
+    pub fn get_upgrade_guardian(env: Env) -> Option<Address> {
+        upgrade::auth::get_guardian(&env)
+    }
+
+    /// Check whether an address holds an upgrade-module role
+    pub fn has_upgrade_role(env: Env, address: Address, role: upgrade::types::Role) -> bool {
+        upgrade::auth::has_role(&env, &address, role)
+    }
+
+    /// List everything that currently prevents an upgrade proposal from executing
+    pub fn get_upgrade_execution_blockers(
+        env: Env,
+        proposal_id: u64,
+    ) -> Vec<upgrade::types::Blocker> {
+        upgrade_logic::get_execution_blockers(&env, proposal_id)
+    }
+
+    /// Check a group of internal invariants in bounded batches; permissionless
+    pub fn verify_invariants(
+        env: Env,
+        scope: upgrade::types::InvariantScope,
+        cursor: u32,
+        max_work: u32,
+    ) -> upgrade::types::InvariantReport {
+        upgrade::invariants::verify_invariants(&env, scope, cursor, max_work)
+    }
+
+    /// Approximate entry counts of governance state, for watching its growth
+    pub fn get_storage_footprint(env: Env) -> upgrade::types::StorageFootprint {
+        upgrade_storage::get_storage_footprint(&env)
+    }
+
+    /// Export a page of hash-chained history records after `cursor`; permissionless.
+    /// Superseded by `export_history_by_cursor`; kept for one release.
+    pub fn export_history_page(
+        env: Env,
+        kind: events::types::HistoryKind,
+        cursor: u64,
+        limit: u32,
+    ) -> events::types::HistoryPage {
+        events::history::export_history_page(&env, kind, cursor, limit)
+    }
+
+    /// Export live history records of `kind` from `cursor`, or from the oldest
+    pub fn export_history_by_cursor(
+        env: Env,
+        kind: events::types::HistoryKind,
+        cursor: Option<utils::pagination::Cursor>,
+        limit: u32,
+    ) -> events::types::HistoryRecordPage {
+        match events::history::export_history_after(&env, kind, cursor, limit) {
+            Ok(page) => page,
+            Err(e) => panic_with_error!(&env, e),
+        }
+    }
+
+    /// Get the running digest that the last exported record of `kind` must chain to
+    pub fn get_history_digest(
+        env: Env,
+        kind: events::types::HistoryKind,
+    ) -> events::types::HistoryDigest {
+        events::history::get_history_digest(&env, kind)
+    }
+
+    /// Hand history records of `kind` up to `through` to the archive sink
+    /// and prune them; returns the highest sequence pruned
+    pub fn prune_upgrade_history(
+        env: Env,
+        caller: Address,
+        kind: events::types::HistoryKind,
+        through: u64,
+    ) -> u64 {
+        upgrade_storage::reject_reentry(&env);
+        match upgrade_logic::prune_history(&env, &caller, kind, through) {
+            Ok(pruned) => pruned,
+            Err(e) => panic_with_error!(&env, e),
+        }
+    }
+
+    /// Turn the contract-era topics on event meta-topics on or off
+    pub fn set_event_version_topics(env: Env, caller: Address, enabled: bool) {
+        upgrade_storage::reject_reentry(&env);
+        if let Err(e) = upgrade_logic::set_event_version_topics(&env, &caller, enabled) {
+            panic_with_error!(&env, e);
+        }
+    }
+
+    /// Set how much detail leveled event payloads carry
+    pub fn set_event_verbosity(
+        env: Env,
+        caller: Address,
+        verbosity: events::types::EventVerbosity,
+    ) {
+        upgrade_storage::reject_reentry(&env);
+        if let Err(e) = upgrade_logic::set_event_verbosity(&env, &caller, verbosity) {
+            panic_with_error!(&env, e);
+        }
+    }
+
+    /// Deprecate an upgrade target version; approved proposals for it stop executing
+    pub fn deprecate_upgrade_version(
+        env: Env,
+        caller: Address,
+        major: u32,
+        minor: u32,
+        patch: u32,
+    ) {
+        upgrade_storage::reject_reentry(&env);
+        let version = upgrade::types::Version::new(major, minor, patch);
+        if let Err(e) = upgrade_logic::deprecate_version(&env, &caller, &version) {
+            panic_with_error!(&env, e);
+        }
+    }
+
+    /// Check whether an upgrade target version has been deprecated
+    pub fn is_upgrade_version_deprecated(env: Env, major: u32, minor: u32, patch: u32) -> bool {
+        upgrade_storage::is_version_deprecated(
+            &env,
+            &upgrade::types::Version::new(major, minor, patch),
+        )
+    }
+
+    /// Set the longest governance silence tolerated before safe mode (0 disables)
+    pub fn set_upgrade_max_silence(env: Env, caller: Address, max_silence: u64) {
+        upgrade_storage::reject_reentry(&env);
+        if let Err(e) = upgrade_logic::set_max_silence(&env, &caller, max_silence) {
+            panic_with_error!(&env, e);
+        }
+    }
+
+    /// Set the minimum seconds between a governance change and a guardian change
+    pub fn set_dual_control_window(env: Env, caller: Address, window: u64) {
+        upgrade_storage::reject_reentry(&env);
+        if let Err(e) = upgrade_logic::set_dual_control_window(&env, &caller, window) {
+            panic_with_error!(&env, e);
+        }
+    }
+
+    /// Minimum seconds between a governance change and a guardian change
+    pub fn get_dual_control_window(env: Env) -> u64 {
+        upgrade_storage::get_dual_control_window(&env)
+    }
+
+    /// Record a governance heartbeat, leaving safe mode if active
+    pub fn upgrade_heartbeat(env: Env, governance: Address) {
+        upgrade_storage::reject_reentry(&env);
+        if let Err(e) = upgrade_logic::heartbeat(&env, &governance) {
+            panic_with_error!(&env, e);
+        }
+    }
+
+    /// Enter safe mode if governance has been silent too long; returns whether safe mode is active
+    pub fn check_upgrade_heartbeat(env: Env) -> bool {
+        upgrade_storage::reject_reentry(&env);
+        upgrade_logic::check_heartbeat(&env)
+    }
+
+    /// Digests of recently emitted events from `from_sequence` on, for indexer gap detection
+    pub fn get_recent_events(
+        env: Env,
+        from_sequence: u64,
+        limit: u32,
+    ) -> Vec<events::types::EventDigest> {
+        events::emit::get_recent_events(&env, from_sequence, limit)
+    }
+
+    /// Error code to name table for the upgrade, proxy, voting and guard errors
+    pub fn error_catalog(env: Env) -> utils::errors::ErrorCatalog {
+        utils::errors::error_catalog(&env)
+    }
+
+    /// Discover which modules and governance features this contract supports
+    pub fn capabilities(env: Env) -> upgrade::types::Capabilities {
+        upgrade_logic::capabilities(&env)
+    }
+
+    /// Get current contract version
+    pub fn get_current_version(env: Env) -> Version {
+        upgrade_storage::get_current_version(&env)
+    }
+
+    /// Page through the version history, oldest first, from position `start`
+    pub fn get_version_history(
+        env: Env,
+        start: u32,
+        limit: u32,
+    ) -> Vec<upgrade::types::VersionChange> {
+        upgrade_storage::get_version_history(&env, start, limit)
+    }
+
+    /// The contract version that was current at a timestamp
+    pub fn get_version_at(env: Env, timestamp: u64) -> Option<Version> {
+        upgrade_storage::get_version_at(&env, timestamp)
+    }
+
+    /// The implementation that was live while a version was current, if known
+    pub fn get_version_implementation(env: Env, version: Version) -> Option<Address> {
+        upgrade_storage::get_version_implementation(&env, &version)
+    }
+
+    /// Register a migration plan for an upgrade
+    pub fn register_migration_plan(
+        env: Env,
+        caller: Address,
+        proposal_id: u64,
+        from_version_major: u32,
+        from_version_minor: u32,
+        from_version_patch: u32,
+        to_version_major: u32,
+        to_version_minor: u32,
+        to_version_patch: u32,
+        migration_function_selector: soroban_sdk::Symbol,
+        estimated_gas: u64,
+    ) {
+        upgrade_storage::reject_reentry(&env);
+        let from_version = Version::new(from_version_major, from_version_minor, from_version_patch);
+        let to_version = Version::new(to_version_major, to_version_minor, to_version_patch);
+        let migration_plan = upgrade::types::MigrationPlan {
+            from_version,
+            to_version,
+            migration_function_selector,
+            estimated_gas,
+        };
+        if let Err(e) =
+            upgrade_logic::register_migration_plan(&env, &caller, proposal_id, &migration_plan)
+        {
+            panic_with_error!(&env, e);
+        }
+    }
+
+    /// Register the migration an instant revert of a proposal runs
+    pub fn register_reverse_migration(
+        env: Env,
+        caller: Address,
+        proposal_id: u64,
+        from_version_major: u32,
+        from_version_minor: u32,
+        from_version_patch: u32,
+        to_version_major: u32,
+        to_version_minor: u32,
+        to_version_patch: u32,
+        migration_function_selector: soroban_sdk::Symbol,
+        estimated_gas: u64,
+    ) {
+        upgrade_storage::reject_reentry(&env);
+        let plan = upgrade::types::MigrationPlan {
+            from_version: Version::new(from_version_major, from_version_minor, from_version_patch),
+            to_version: Version::new(to_version_major, to_version_minor, to_version_patch),
+            migration_function_selector,
+            estimated_gas,
+        };
+        if let Err(e) = upgrade_logic::register_reverse_migration(&env, &caller, proposal_id, &plan)
+        {
+            panic_with_error!(&env, e);
+        }
+    }
+
+    /// Revert a just-executed upgrade inside its revert window (guardian or governance)
+    pub fn instant_revert(env: Env, caller: Address, proposal_id: u64, force: bool) {
+        upgrade_storage::reject_reentry(&env);
+        if let Err(e) = upgrade_logic::instant_revert(&env, &caller, proposal_id, force) {
+            panic_with_error!(&env, e);
+        }
+    }
+
+    /// Roll back to an earlier version of the same major (governance only)
+    pub fn rollback_to_version(env: Env, caller: Address, target_version: Version) {
+        upgrade_storage::reject_reentry(&env);
+        if let Err(e) = upgrade_logic::rollback_to_version(&env, &caller, &target_version) {
+            panic_with_error!(&env, e);
+        }
+    }
+
+    /// End a just-executed upgrade's revert window early
+    pub fn confirm_upgrade(env: Env, caller: Address, proposal_id: u64) {
+        upgrade_storage::reject_reentry(&env);
+        if let Err(e) = upgrade_logic::confirm_upgrade(&env, &caller, proposal_id) {
+            panic_with_error!(&env, e);
+        }
+    }
+
+    /// The revert window of the last executed upgrade, if one was opened
+    pub fn get_revert_window(env: Env) -> Option<upgrade::types::RevertWindow> {
+        upgrade_storage::get_revert_window(&env)
+    }
+
+    /// Pause upgrade governance; `force` is needed while a migration is in progress
+    pub fn pause_upgrade_governance(env: Env, caller: Address, force: bool) {
+        upgrade_storage::reject_reentry(&env);
+        if let Err(e) = upgrade_logic::pause_governance(&env, &caller, force) {
+            panic_with_error!(&env, e);
+        }
+    }
+
+    /// Lift an upgrade governance pause
+    pub fn resume_upgrade_governance(env: Env, caller: Address) {
+        upgrade_storage::reject_reentry(&env);
+        if let Err(e) = upgrade_logic::resume_governance(&env, &caller) {
+            panic_with_error!(&env, e);
+        }
+    }
+
+    /// The active upgrade governance pause, if any
+    pub fn get_governance_pause(env: Env) -> Option<upgrade::types::GovernancePause> {
+        upgrade_storage::get_governance_pause(&env)
+    }
+
+    /// Clear a failed post-execution check so upgrades may execute again
+    pub fn acknowledge_post_check(env: Env, caller: Address) {
+        upgrade_storage::reject_reentry(&env);
+        if let Err(e) = upgrade_logic::acknowledge_post_check(&env, &caller) {
+            panic_with_error!(&env, e);
+        }
+    }
+
+    /// The unacknowledged post-execution check failure, if any
+    pub fn get_post_check_failure(env: Env) -> Option<upgrade::types::PostCheckFailure> {
+        upgrade_storage::get_post_check_failure(&env)
+    }
+
+    /// Pause and migration state, flagging migrations stranded by a forced
+    /// pause and unacknowledged post-execution check failures
+    pub fn health(env: Env) -> upgrade::types::HealthReport {
+        upgrade_logic::health(&env)
+    }
+
+    /// Run upkeep tasks as a keeper within a shared work budget; pays the
+    /// configured bounty per completed unit
+    pub fn run_maintenance(
+        env: Env,
+        keeper: Address,
+        tasks: Vec<upgrade::types::MaintenanceTask>,
+        max_work: u32,
+    ) -> upgrade::types::MaintenanceReport {
+        upgrade_storage::reject_reentry(&env);
+        match upgrade_logic::run_maintenance(&env, &keeper, &tasks, max_work) {
+            Ok(report) => report,
+            Err(e) => panic_with_error!(&env, e),
+        }
+    }
+
+    /// Configure keeper bounties and task cooldowns
+    pub fn set_keeper_config(env: Env, caller: Address, config: upgrade::types::KeeperConfig) {
+        upgrade_storage::reject_reentry(&env);
+        if let Err(e) = upgrade_logic::set_keeper_config(&env, &caller, &config) {
+            panic_with_error!(&env, e);
+        }
+    }
+
+    /// Get the keeper bounty and cooldown config
+    pub fn get_keeper_config(env: Env) -> upgrade::types::KeeperConfig {
+        upgrade_storage::get_keeper_config(&env)
+    }
+
+    /// Get a keeper's lifetime work counters
+    pub fn get_keeper_stats(env: Env, keeper: Address) -> upgrade::types::KeeperStats {
+        upgrade_storage::get_keeper_stats(&env, &keeper)
+    }
+
+    /// Apply one in-order page of the genesis voter list; false when the
+    /// page was already applied
+    pub fn load_genesis_voters(
+        env: Env,
+        governance: Address,
+        page: Vec<(Address, u32)>,
+        page_index: u32,
+        total_pages: u32,
+    ) -> bool {
+        upgrade_storage::reject_reentry(&env);
+        match upgrade_logic::load_genesis_voters(&env, &governance, &page, page_index, total_pages)
+        {
+            Ok(applied) => applied,
+            Err(e) => panic_with_error!(&env, e),
+        }
+    }
+
+    /// Close the genesis voter list early and unlock proposals
+    pub fn seal_genesis_voters(env: Env, governance: Address) {
+        upgrade_storage::reject_reentry(&env);
+        if let Err(e) = upgrade_logic::seal_genesis_voters(&env, &governance) {
+            panic_with_error!(&env, e);
+        }
+    }
+
+    /// Progress of the genesis voter list, if one was started or sealed
+    pub fn get_genesis_progress(env: Env) -> Option<upgrade::types::GenesisProgress> {
+        upgrade_storage::get_genesis_progress(&env)
+    }
+
+    /// Resume a deferred migration and finish its upgrade
+    pub fn continue_migration(
+        env: Env,
+        executor: Address,
+        proposal_id: u64,
+    ) -> upgrade::types::ExecutionOutcome {
+        upgrade_storage::reject_reentry(&env);
+        match upgrade_logic::continue_migration(&env, &executor, proposal_id) {
+            Ok(outcome) => outcome,
+            Err(e) => panic_with_error!(&env, e),
+        }
+    }
+
+    /// How far a proposal's live migration has got
+    pub fn get_migration_progress(
+        env: Env,
+        proposal_id: u64,
+    ) -> Option<upgrade::types::MigrationProgress> {
+        upgrade_storage::get_migration_progress(&env, proposal_id)
+    }
+
+    /// Move the stored upgrade layout one schema step forward to `target`
+    pub fn migrate_upgrade_storage_schema(env: Env, caller: Address, target: u32) -> u32 {
+        upgrade_storage::reject_reentry(&env);
+        match upgrade_logic::migrate_storage_schema(&env, &caller, target) {
+            Ok(migrated) => migrated,
+            Err(e) => panic_with_error!(&env, e),
+        }
+    }
+
+    /// Schema of the stored upgrade layout
+    pub fn get_upgrade_storage_schema(env: Env) -> u32 {
+        upgrade_storage::get_storage_schema(&env)
+    }
+
+    /// The stored upgrade layout against the one this code reads, when they differ
+    pub fn check_upgrade_storage_schema(env: Env) -> Option<upgrade::types::SchemaMismatch> {
+        upgrade_storage::check_schema(&env).err()
+    }
+
+    /// Give proposals stored before the voting power snapshot existed their snapshot
+    pub fn migrate_proposal_snapshots(env: Env, caller: Address) -> u32 {
+        upgrade_storage::reject_reentry(&env);
+        match upgrade_logic::migrate_proposal_snapshots(&env, &caller) {
+            Ok(migrated) => migrated,
+            Err(e) => panic_with_error!(&env, e),
+        }
+    }
+
+    /// Rehearse a proposal's migration on shadow copies of the sampled entries
+    pub fn start_rehearsal(
+        env: Env,
+        caller: Address,
+        proposal_id: u64,
+        samples: Vec<upgrade::types::RehearsalSample>,
+    ) -> upgrade::types::RehearsalReport {
+        upgrade_storage::reject_reentry(&env);
+        match upgrade_logic::start_rehearsal(&env, &caller, proposal_id, &samples) {
+            Ok(report) => report,
+            Err(e) => panic_with_error!(&env, e),
+        }
+    }
+
+    /// Delete a proposal's shadow rehearsal data
+    pub fn discard_rehearsal(env: Env, caller: Address, proposal_id: u64) {
+        upgrade_storage::reject_reentry(&env);
+        if let Err(e) = upgrade_logic::discard_rehearsal(&env, &caller, proposal_id) {
+            panic_with_error!(&env, e);
+        }
+    }
+
+    /// The report of a proposal's last rehearsal, if not discarded
+    pub fn get_rehearsal_report(
+        env: Env,
+        proposal_id: u64,
+    ) -> Option<upgrade::types::RehearsalReport> {
+        upgrade_storage::get_rehearsal_report(&env, proposal_id)
+    }
+
+    // ============ Proxy Functions ============
+
+    /// Initialize proxy functionality
+    pub fn initialize_proxy(env: Env, initial_implementation: Address, admin: Address) {
+        proxy_storage::reject_reentry(&env);
+        if let Err(e) = proxy_storage::initialize(&env, initial_implementation, admin) {
+            panic_with_error!(&env, e);
+        }
+    }
+
+    /// Whether the proxy has been initialized
+    pub fn is_proxy_initialized(env: Env) -> bool {
+        proxy_storage::is_initialized(&env)
+    }
+
+    /// Upgrade the proxy to a new implementation. A refused attempt does not
+    /// trap: it comes back as `Rejected` and stays in the upgrade history.
+    pub fn proxy_upgrade(
+        env: Env,
+        caller: Address,
+        new_implementation: Address,
+    ) -> proxy::types::UpgradeOutcome {
+        proxy_storage::reject_reentry(&env);
+        proxy_impl::upgrade(&env, &caller, &new_implementation)
+    }
+
+    /// Approve a proxy upgrade; it runs once the admin threshold approves.
+    /// A refused approval comes back as `Rejected` and stays in the history.
+    pub fn proxy_approve_upgrade(
+        env: Env,
+        admin: Address,
+        new_implementation: Address,
+    ) -> proxy::types::UpgradeOutcome {
+        proxy_storage::reject_reentry(&env);
+        proxy_impl::approve_upgrade(&env, &admin, &new_implementation)
+    }
+
+    /// Admins that approved upgrading to `new_implementation` so far
+    pub fn proxy_get_upgrade_approvals(env: Env, new_implementation: Address) -> Vec<Address> {
+        proxy_storage::get_action_approvals(
+            &env,
+            &proxy::types::ProxyAction::Upgrade(new_implementation),
+        )
+    }
+
+    /// Approve adding a proxy admin; governance adds one directly.
+    /// Returns whether the admin was added.
+    pub fn proxy_add_admin(env: Env, caller: Address, new_admin: Address) -> bool {
+        proxy_storage::reject_reentry(&env);
+        match proxy_impl::add_admin(&env, &caller, &new_admin) {
+            Ok(applied) => applied,
+            Err(e) => panic_with_error!(&env, e),
+        }
+    }
+
+    /// Approve removing a proxy admin; governance removes one directly.
+    /// Returns whether the admin was removed.
+    pub fn proxy_remove_admin(env: Env, caller: Address, admin: Address) -> bool {
+        proxy_storage::reject_reentry(&env);
+        match proxy_impl::remove_admin(&env, &caller, &admin) {
+            Ok(applied) => applied,
+            Err(e) => panic_with_error!(&env, e),
+        }
+    }
+
+    /// Approve a new admin threshold; governance sets it directly.
+    /// Returns whether it was set.
+    pub fn proxy_set_threshold(env: Env, caller: Address, threshold: u32) -> bool {
+        proxy_storage::reject_reentry(&env);
+        match proxy_impl::set_threshold(&env, &caller, threshold) {
+            Ok(applied) => applied,
+            Err(e) => panic_with_error!(&env, e),
+        }
+    }
+
+    /// Every proxy admin
+    pub fn proxy_get_admins(env: Env) -> Vec<Address> {
+        proxy_storage::get_admins(&env)
+    }
+
+    /// Distinct admin approvals a proxy action needs
+    pub fn proxy_get_threshold(env: Env) -> u32 {
+        proxy_storage::get_admin_threshold(&env)
+    }
+
+    /// Switch the proxy back to the implementation its last upgrade replaced
+    /// once the admin threshold approves; true when it switched
+    pub fn proxy_revert_last_upgrade(env: Env, caller: Address) -> bool {
+        proxy_storage::reject_reentry(&env);
+        match proxy_impl::revert_last_upgrade(&env, &caller) {
+            Ok(reverted) => reverted.is_some(),
+            Err(e) => panic_with_error!(&env, e),
+        }
+    }
+
+    /// Set the minimum delay between scheduling and executing a proxy upgrade
+    pub fn proxy_set_min_upgrade_delay(env: Env, caller: Address, delay: u64) {
+        proxy_storage::reject_reentry(&env);
+        if let Err(e) = proxy_impl::set_min_upgrade_delay(&env, &caller, delay) {
+            panic_with_error!(&env, e);
+        }
+    }
+
+    /// Allow the proxy admin to upgrade to an implementation
+    pub fn proxy_approve_implementation(env: Env, caller: Address, implementation: Address) {
+        proxy_storage::reject_reentry(&env);
+        if let Err(e) = proxy_impl::approve_implementation(&env, &caller, &implementation) {
+            panic_with_error!(&env, e);
+        }
+    }
+
+    /// Take an implementation off the proxy allowlist
+    pub fn proxy_revoke_implementation(env: Env, caller: Address, implementation: Address) {
+        proxy_storage::reject_reentry(&env);
+        if let Err(e) = proxy_impl::revoke_implementation(&env, &caller, &implementation) {
+            panic_with_error!(&env, e);
+        }
+    }
+
+    /// Whether an implementation is on the proxy allowlist
+    pub fn proxy_is_implementation_approved(env: Env, implementation: Address) -> bool {
+        proxy_storage::is_implementation_approved(&env, &implementation)
+    }
+
+    /// Skip asking new proxy implementations for their version
+    pub fn proxy_set_skip_version_check(env: Env, caller: Address, skip: bool) {
+        proxy_storage::reject_reentry(&env);
+        if let Err(e) = proxy_impl::set_skip_version_check(&env, &caller, skip) {
+            panic_with_error!(&env, e);
+        }
+    }
+
+    /// Require a different address to upgrade than the one that approved the target
+    pub fn proxy_set_two_party_upgrades(env: Env, caller: Address, enabled: bool) {
+        proxy_storage::reject_reentry(&env);
+        if let Err(e) = proxy_impl::set_two_party_upgrades(&env, &caller, enabled) {
+            panic_with_error!(&env, e);
+        }
+    }
+
+    /// Reject immediate proxy upgrades so every switch must be scheduled,
+    /// once the admin threshold approves; true when the mode changed
+    pub fn proxy_set_require_delay(env: Env, caller: Address, required: bool) -> bool {
+        proxy_storage::reject_reentry(&env);
+        match proxy_impl::set_require_delay(&env, &caller, required) {
+            Ok(applied) => applied,
+            Err(e) => panic_with_error!(&env, e),
+        }
+    }
+
+    /// Whether immediate proxy upgrades are rejected
+    pub fn proxy_is_delay_required(env: Env) -> bool {
+        proxy_storage::is_delay_required(&env)
+    }
+
+    /// Permanently freeze proxy upgrades, confirmed by naming the current
+    /// implementation, once the admin threshold approves; true when frozen
+    pub fn proxy_freeze_upgrades(
+        env: Env,
+        caller: Address,
+        current_implementation: Address,
+    ) -> bool {
+        proxy_storage::reject_reentry(&env);
+        match proxy_impl::freeze_upgrades(&env, &caller, &current_implementation) {
+            Ok(applied) => applied,
+            Err(e) => panic_with_error!(&env, e),
+        }
+    }
+
+    /// Whether proxy upgrades were permanently frozen
+    pub fn proxy_is_frozen(env: Env) -> bool {
+        proxy_storage::is_frozen(&env)
+    }
+
+    /// Set the minimum time between two proxy upgrades
+    pub fn proxy_set_min_upgrade_interval(env: Env, caller: Address, interval: u64) {
+        proxy_storage::reject_reentry(&env);
+        if let Err(e) = proxy_impl::set_min_upgrade_interval(&env, &caller, interval) {
+            panic_with_error!(&env, e);
+        }
+    }
+
+    /// Get the minimum time between two proxy upgrades
+    pub fn proxy_get_min_upgrade_interval(env: Env) -> u64 {
+        proxy_storage::get_min_upgrade_interval(&env)
+    }
+
+    /// Schedule a proxy upgrade that can execute after the minimum delay,
+    /// once the admin threshold approves; the schedule id when it was put up
+    pub fn proxy_schedule_upgrade(
+        env: Env,
+        caller: Address,
+        new_implementation: Address,
+    ) -> Option<u64> {
+        proxy_storage::reject_reentry(&env);
+        match proxy_impl::schedule_upgrade(&env, &caller, &new_implementation) {
+            Ok(id) => id,
+            Err(e) => panic_with_error!(&env, e),
+        }
+    }
+
+    /// Execute a scheduled proxy upgrade whose delay has elapsed. A target
+    /// the allowlist or cooldown now refuses comes back as `Rejected`.
+    pub fn proxy_execute_scheduled_upgrade(
+        env: Env,
+        caller: Address,
+        schedule_id: u64,
+    ) -> proxy::types::UpgradeOutcome {
+        proxy_storage::reject_reentry(&env);
+        match proxy_impl::execute_scheduled_upgrade(&env, &caller, schedule_id) {
+            Ok(outcome) => outcome,
+            Err(e) => panic_with_error!(&env, e),
+        }
+    }
+
+    /// Cancel a scheduled proxy upgrade
+    pub fn proxy_cancel_scheduled_upgrade(env: Env, caller: Address, schedule_id: u64) {
+        proxy_storage::reject_reentry(&env);
+        if let Err(e) = proxy_impl::cancel_scheduled_upgrade(&env, &caller, schedule_id) {
+            panic_with_error!(&env, e);
+        }
+    }
+
+    /// Get a scheduled proxy upgrade
+    pub fn proxy_get_scheduled_upgrade(
+        env: Env,
+        schedule_id: u64,
+    ) -> Option<proxy::types::ScheduledUpgrade> {
+        proxy_storage::get_scheduled_upgrade(&env, schedule_id)
+    }
+
+    /// Set how long a proxy handover may wait for confirmation
+    pub fn proxy_set_handover_timeout(env: Env, caller: Address, timeout: u64) {
+        proxy_storage::reject_reentry(&env);
+        if let Err(e) = proxy_impl::set_handover_timeout(&env, &caller, timeout) {
+            panic_with_error!(&env, e);
+        }
+    }
+
+    /// Begin a handover that switches once the new implementation confirms,
+    /// once the admin threshold approves; the handover id when it began
+    pub fn proxy_begin_handover(
+        env: Env,
+        caller: Address,
+        new_implementation: Address,
+    ) -> Option<u64> {
+        proxy_storage::reject_reentry(&env);
+        match proxy_impl::begin_handover(&env, &caller, &new_implementation) {
+            Ok(id) => id,
+            Err(e) => panic_with_error!(&env, e),
+        }
+    }
+
+    /// Confirm the pending handover, switching the implementation. A
+    /// handover past its timeout comes back as `Rejected`, recorded as failed.
+    pub fn proxy_confirm_handover(env: Env, caller: Address) -> proxy::types::UpgradeOutcome {
+        proxy_storage::reject_reentry(&env);
+        match proxy_impl::confirm_handover(&env, &caller) {
+            Ok(outcome) => outcome,
+            Err(e) => panic_with_error!(&env, e),
+        }
+    }
+
+    /// Abort the pending handover
+    pub fn proxy_abort_handover(env: Env, caller: Address) {
+        proxy_storage::reject_reentry(&env);
+        if let Err(e) = proxy_impl::abort_handover(&env, &caller) {
+            panic_with_error!(&env, e);
+        }
+    }
+
+    /// Time out the pending handover if it is stale
+    pub fn proxy_expire_handover(env: Env) -> bool {
+        proxy_storage::reject_reentry(&env);
+        proxy_impl::expire_handover(&env)
+    }
+
+    /// Get a proxy handover
+    pub fn proxy_get_handover(env: Env, handover_id: u64) -> Option<proxy::types::Handover> {
+        proxy_storage::get_handover(&env, handover_id)
+    }
+
+    /// Nominate a new proxy admin; it takes over once it accepts
+    pub fn proxy_transfer_admin(env: Env, caller: Address, new_admin: Address) {
+        proxy_storage::reject_reentry(&env);
+        if let Err(e) = proxy_impl::transfer_admin(&env, &caller, &new_admin) {
+            panic_with_error!(&env, e);
+        }
+    }
+
+    /// Accept proxy admin rights as the pending admin
+    pub fn proxy_accept_admin(env: Env, new_admin: Address) {
+        proxy_storage::reject_reentry(&env);
+        if let Err(e) = proxy_impl::accept_admin(&env, &new_admin) {
+            panic_with_error!(&env, e);
+        }
+    }
+
+    /// Withdraw a pending proxy admin nomination
+    pub fn proxy_cancel_admin_transfer(env: Env, caller: Address) {
+        proxy_storage::reject_reentry(&env);
+        if let Err(e) = proxy_impl::cancel_admin_transfer(&env, &caller) {
+            panic_with_error!(&env, e);
+        }
+    }
+
+    /// Number of recorded proxy upgrade transactions
+    pub fn proxy_get_upgrade_count(env: Env) -> u64 {
+        proxy_storage::get_upgrade_count(&env)
+    }
+
+    /// Proxy upgrade transactions, newest first, skipping the `start` most recent
+    pub fn proxy_get_upgrade_history(
+        env: Env,
+        start: u64,
+        limit: u32,
+    ) -> Vec<proxy::types::UpgradeTransaction> {
+        proxy_storage::get_upgrade_history_page(&env, start, limit)
+    }
+
+    /// The most recent proxy upgrade transaction
+    pub fn proxy_get_latest_upgrade(env: Env) -> Option<proxy::types::UpgradeTransaction> {
+        proxy_storage::get_latest_upgrade(&env)
+    }
+
+    /// Proxy upgrade attempts that were rejected or did not complete
+    pub fn proxy_get_failed_upgrades(env: Env) -> Vec<proxy::types::UpgradeTransaction> {
+        proxy_storage::get_failed_upgrades(&env)
+    }
+
+    /// Address nominated as proxy admin that has not accepted yet
+    pub fn proxy_get_pending_admin(env: Env) -> Option<Address> {
+        proxy_storage::get_pending_admin(&env)
+    }
+
+    /// Get proxy information
+    pub fn proxy_get_info(env: Env) -> ProxyConfig {
+        proxy_impl::get_proxy_info(&env)
+    }
+
+    /// Check if proxy is paused
+    pub fn proxy_is_paused(env: Env) -> bool {
+        proxy_impl::is_paused(&env)
+    }
+
+    /// Who triggered the proxy's emergency stop and when, while it is in force
+    pub fn proxy_get_pause(env: Env) -> Option<proxy::types::ProxyPause> {
+        proxy_storage::get_pause(&env)
     }
 
     /// Trigger emergency stop for the proxy
-    pub fn proxy_emergency_stop(env: Env, caller: Address) -> bool {
-        match proxy_impl::emergency_stop(&env, &caller) {
-            Ok(_) => true,
-            Err(_) => false,
+    pub fn proxy_emergency_stop(env: Env, caller: Address) {
+        proxy_storage::reject_reentry(&env);
+        if let Err(e) = proxy_impl::emergency_stop(&env, &caller) {
+            panic_with_error!(&env, e);
         }
     }
 
     /// Resume proxy after emergency stop
-    pub fn proxy_resume(env: Env, caller: Address) -> bool {
-        match proxy_impl::resume(&env, &caller) {
-            Ok(_) => true,
-            Err(_) => false,
+    pub fn proxy_resume(env: Env, caller: Address) {
+        proxy_storage::reject_reentry(&env);
+        if let Err(e) = proxy_impl::resume(&env, &caller) {
+            panic_with_error!(&env, e);
         }
     }
 }
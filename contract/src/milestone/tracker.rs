@@ -1,3 +1,4 @@
+use crate::utils::time;
 use soroban_sdk::{Address, Env, String, Vec};
 
 use crate::dispute::storage as dispute_storage;
@@ -32,7 +33,7 @@ fn assert_project_active(project: &Project, _env: &Env) {
 }
 
 fn ensure_not_expired(env: &Env, milestone: &mut Milestone) {
-    let now = env.ledger().timestamp();
+    let now = time::now(env);
     if now > milestone.deadline && milestone.status != MilestoneStatus::Approved {
         milestone.status = MilestoneStatus::Expired;
         store_milestone(env, milestone);
@@ -59,7 +60,7 @@ pub fn create_project(
         panic!("at least one milestone required");
     }
 
-    let now = env.ledger().timestamp();
+    let now = time::now(env);
 
     // Validate milestones and compute allocation
     let mut allocated: i128 = 0;
@@ -171,7 +172,7 @@ pub fn add_milestone(
         panic!("caller must be guild admin");
     }
 
-    let now = env.ledger().timestamp();
+    let now = time::now(env);
     if amount <= 0 {
         panic!("amount must be positive");
     }
@@ -263,7 +264,7 @@ pub fn start_milestone(env: &Env, milestone_id: u64, contributor: Address) -> bo
 
     let old_status = milestone.status.clone();
     milestone.status = MilestoneStatus::InProgress;
-    milestone.last_updated_at = env.ledger().timestamp();
+    milestone.last_updated_at = time::now(env);
     store_milestone(env, &milestone);
 
     let event = MilestoneStatusChangedEvent {
@@ -294,7 +295,7 @@ pub fn submit_milestone(env: &Env, milestone_id: u64, proof_url: String) -> bool
         panic!("milestone not in progress or previously rejected");
     }
 
-    let now = env.ledger().timestamp();
+    let now = time::now(env);
     let old_status = milestone.status.clone();
     milestone.status = MilestoneStatus::Submitted;
     milestone.proof_url = proof_url.clone();
@@ -341,7 +342,7 @@ pub fn approve_milestone(env: &Env, milestone_id: u64, approver: Address) -> boo
 
     let old_status = milestone.status.clone();
     milestone.status = MilestoneStatus::Approved;
-    milestone.last_updated_at = env.ledger().timestamp();
+    milestone.last_updated_at = time::now(env);
     store_milestone(env, &milestone);
 
     let status_event = MilestoneStatusChangedEvent {
@@ -381,7 +382,7 @@ pub fn reject_milestone(env: &Env, milestone_id: u64, approver: Address, reason:
 
     let old_status = milestone.status.clone();
     milestone.status = MilestoneStatus::Rejected;
-    milestone.last_updated_at = env.ledger().timestamp();
+    milestone.last_updated_at = time::now(env);
     store_milestone(env, &milestone);
 
     let reject_event = MilestoneRejectedEvent {
@@ -473,7 +474,7 @@ fn release_milestone_payment_internal(
 
     project.released_amount = new_released;
     milestone.is_payment_released = true;
-    milestone.last_updated_at = env.ledger().timestamp();
+    milestone.last_updated_at = time::now(env);
 
     store_project(env, project);
     store_milestone(env, milestone);
@@ -531,7 +532,7 @@ pub fn extend_milestone_deadline(
         panic!("caller must be guild admin");
     }
 
-    let now = env.ledger().timestamp();
+    let now = time::now(env);
     if new_deadline <= now || new_deadline <= milestone.deadline {
         panic!("new deadline must be in the future and after current deadline");
     }
@@ -5,6 +5,7 @@ use crate::multisig::storage::{
 use crate::multisig::types::{
     AccountStatus, MultiSigOperation, OperationStatus, OperationType, TIMEOUT_24H, TIMEOUT_48H,
 };
+use crate::utils::time;
 use soroban_sdk::{Address, Env, String, Vec};
 
 pub fn ms_propose_operation(
@@ -21,7 +22,7 @@ pub fn ms_propose_operation(
     }
     let policy = ms_get_operation_policy(env, account_id, op_type.clone());
     let op_id = next_operation_id(env);
-    let current_time = env.ledger().timestamp();
+    let current_time = time::now(env);
     let mut signatures = Vec::new(env);
     signatures.push_back(proposer.clone());
     let timeout = policy.timeout_seconds.clamp(TIMEOUT_24H, TIMEOUT_48H);
@@ -51,7 +52,7 @@ pub fn ms_sign_operation(env: &Env, op_id: u64, signer: Address) -> Result<u32,
     if operation.status != OperationStatus::Pending {
         return Err(4u32);
     }
-    if env.ledger().timestamp() > operation.expires_at {
+    if time::now(env) > operation.expires_at {
         operation.status = OperationStatus::Expired;
         store_operation(env, op_id, &operation);
         return Err(5u32);
@@ -72,7 +73,7 @@ pub fn ms_execute_operation(env: &Env, op_id: u64, executor: Address) -> Result<
     if operation.status != OperationStatus::Pending {
         return Err(4u32);
     }
-    if env.ledger().timestamp() > operation.expires_at {
+    if time::now(env) > operation.expires_at {
         operation.status = OperationStatus::Expired;
         store_operation(env, op_id, &operation);
         return Err(5u32);
@@ -113,7 +114,7 @@ pub fn ms_cancel_operation(env: &Env, op_id: u64, caller: Address) -> Result<(),
 
 pub fn ms_check_and_expire(env: &Env, op_id: u64) -> Result<bool, u32> {
     let mut op = get_operation(env, op_id).ok_or(3u32)?;
-    if op.status == OperationStatus::Pending && env.ledger().timestamp() > op.expires_at {
+    if op.status == OperationStatus::Pending && time::now(env) > op.expires_at {
         op.status = OperationStatus::Expired;
         store_operation(env, op_id, &op);
         return Ok(true);
@@ -146,7 +147,7 @@ pub fn ms_emergency_extend_timeout(
         return Err(2u32);
     }
     let timeout = new_timeout_seconds.clamp(TIMEOUT_24H, TIMEOUT_48H);
-    op.expires_at = env.ledger().timestamp() + timeout;
+    op.expires_at = time::now(env) + timeout;
     store_operation(env, op_id, &op);
     Ok(())
 }
@@ -171,7 +172,7 @@ pub fn ms_require_executed_operation(
 }
 
 pub fn ms_get_pending_operations(env: &Env, account_id: u64) -> Vec<MultiSigOperation> {
-    let now = env.ledger().timestamp();
+    let now = time::now(env);
     let max_id: u64 = env
         .storage()
         .instance()
@@ -192,7 +193,7 @@ pub fn ms_get_pending_operations(env: &Env, account_id: u64) -> Vec<MultiSigOper
 }
 
 pub fn ms_sweep_expired_operations(env: &Env, account_id: u64) -> u32 {
-    let now = env.ledger().timestamp();
+    let now = time::now(env);
     let max_id: u64 = env
         .storage()
         .instance()
@@ -10,6 +10,7 @@ use crate::payment::types::{
     DistributionExecutedEvent, DistributionFailedEvent, DistributionRule, DistributionStatus,
     PaymentPool, PaymentPoolCreatedEvent, PoolCancelledEvent, Recipient, RecipientAddedEvent,
 };
+use crate::utils::time;
 use soroban_sdk::{contracterror, Address, Env, String, Vec};
 
 /// Error types for payment distribution operations
@@ -67,7 +68,7 @@ pub fn create_payment_pool(
         status: DistributionStatus::Pending,
         created_by: creator.clone(),
         rule: rule.clone(),
-        created_at: env.ledger().timestamp(),
+        created_at: time::now(env),
     };
     store_payment_pool(env, &pool);
 
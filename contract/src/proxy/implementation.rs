@@ -1,44 +1,506 @@
 use crate::events::emit::emit_event;
 use crate::events::topics::{
-    ACT_ACCEPTED, ACT_PAUSED, ACT_RESUMED, ACT_TRANSFERRED, ACT_UPGRADE_EXECUTED, MOD_PROXY,
+    ACT_ACCEPTED, ACT_ACTION_APPROVED, ACT_ADMIN_ADDED, ACT_ADMIN_REMOVED,
+    ACT_ADMIN_TRANSFER_CANCELLED, ACT_CANCELLED, ACT_DELAY_REQUIRED, ACT_FROZEN,
+    ACT_HANDOVER_ABORTED, ACT_HANDOVER_CONFIRMED, ACT_HANDOVER_STARTED, ACT_HANDOVER_TIMED_OUT,
+    ACT_IMPL_APPROVED, ACT_IMPL_REVOKED, ACT_INTERVAL_SET, ACT_PAUSED, ACT_RESUMED,
+    ACT_THRESHOLD_SET, ACT_TRANSFERRED, ACT_TWO_PARTY, ACT_UPDATED, ACT_UPGRADE_EXECUTED,
+    ACT_UPGRADE_REVERTED, ACT_UPGRADE_SCHEDULED, ACT_VERSION_CHECK_SKIPPED, MOD_PROXY,
 };
 use crate::proxy::storage;
-use crate::proxy::types::{ProxyConfig, UpgradeTransaction};
-use soroban_sdk::{symbol_short, Address, Env};
+use crate::proxy::types::{
+    Handover, HandoverStatus, ProxyAction, ProxyConfig, ProxyError, ProxyPause, ScheduleStatus,
+    ScheduledUpgrade, UpgradeOutcome, UpgradeTransaction,
+};
+use crate::upgrade::calls;
+use crate::upgrade::storage as upgrade_storage;
+use crate::upgrade::types::{Version, MAX_VERSION_HISTORY_PAGE};
+use crate::utils::time;
+use soroban_sdk::{Address, Env, IntoVal, String, Symbol, Val, Vec};
+
+/// Upgrade the proxy to a new implementation. With an admin threshold
+/// above one this only counts the caller's approval; see `approve_upgrade`.
+pub fn upgrade(env: &Env, caller: &Address, new_implementation: &Address) -> UpgradeOutcome {
+    approve_upgrade(env, caller, new_implementation)
+}
+
+/// Approve switching the proxy to `new_implementation`. The switch happens
+/// once the admin threshold of distinct approvals is reached, which clears
+/// them. A rejected attempt is not an error: it is recorded in the upgrade
+/// history with the error name as its reason, and that record must outlive
+/// the call, so the refusal comes back as `UpgradeOutcome::Rejected`.
+pub fn approve_upgrade(env: &Env, admin: &Address, new_implementation: &Address) -> UpgradeOutcome {
+    admin.require_auth();
+
+    let action = ProxyAction::Upgrade(new_implementation.clone());
+    match check_upgrade(env, admin, new_implementation)
+        .and_then(|_| add_approval(env, admin, &action))
+    {
+        Err(error) => {
+            record_failed_upgrade(env, admin, new_implementation, error.name());
+            UpgradeOutcome::rejected(error)
+        }
+        Ok(false) => UpgradeOutcome::Approved,
+        Ok(true) => {
+            apply_upgrade(env, admin, new_implementation);
+            UpgradeOutcome::Applied
+        }
+    }
+}
+
+/// Count `admin`'s approval of `action`. Returns whether the admin threshold
+/// is now reached, in which case the approvals are cleared for the caller to
+/// carry the action out.
+fn add_approval(env: &Env, admin: &Address, action: &ProxyAction) -> Result<bool, ProxyError> {
+    let mut approvers = storage::get_action_approvals(env, action);
+    if approvers.contains(admin) {
+        return Err(ProxyError::AlreadyApproved);
+    }
+    approvers.push_back(admin.clone());
+    if approvers.len() >= storage::get_admin_threshold(env) {
+        storage::clear_action_approvals(env, action);
+        return Ok(true);
+    }
+
+    storage::set_action_approvals(env, action, &approvers);
+
+    emit_event(
+        env,
+        MOD_PROXY,
+        ACT_ACTION_APPROVED,
+        (action.clone(), admin.clone()),
+    );
+
+    Ok(false)
+}
+
+/// Whether `caller` is upgrade governance, which changes the admin set
+/// without collecting admin approvals
+fn is_governance(env: &Env, caller: &Address) -> bool {
+    upgrade_storage::is_initialized(env) && *caller == upgrade_storage::get_governance_address(env)
+}
+
+/// Admit `new_admin`, once the admin threshold approves or governance asks.
+/// Returns whether the admin was added.
+pub fn add_admin(env: &Env, caller: &Address, new_admin: &Address) -> Result<bool, ProxyError> {
+    caller.require_auth();
+
+    let governance = is_governance(env, caller);
+    if !governance && !storage::is_admin(env, caller) {
+        return Err(ProxyError::NotAdmin);
+    }
+    if is_paused(env) {
+        return Err(ProxyError::Paused);
+    }
+    if storage::is_admin(env, new_admin) {
+        return Err(ProxyError::AlreadyAdmin);
+    }
+    if !governance && !add_approval(env, caller, &ProxyAction::AddAdmin(new_admin.clone()))? {
+        return Ok(false);
+    }
+
+    let mut admins = storage::get_admins(env);
+    admins.push_back(new_admin.clone());
+    storage::set_admins(env, &admins);
+
+    emit_event(
+        env,
+        MOD_PROXY,
+        ACT_ADMIN_ADDED,
+        (caller.clone(), new_admin.clone()),
+    );
+
+    Ok(true)
+}
+
+/// Drop `admin`, once the admin threshold approves or governance asks. The
+/// admins left must still be able to meet the threshold. Returns whether the
+/// admin was removed.
+pub fn remove_admin(env: &Env, caller: &Address, admin: &Address) -> Result<bool, ProxyError> {
+    caller.require_auth();
+
+    let governance = is_governance(env, caller);
+    if !governance && !storage::is_admin(env, caller) {
+        return Err(ProxyError::NotAdmin);
+    }
+    if is_paused(env) {
+        return Err(ProxyError::Paused);
+    }
+    let mut admins = storage::get_admins(env);
+    let index = admins
+        .first_index_of(admin)
+        .ok_or(ProxyError::UnknownAdmin)?;
+    if admins.len() - 1 < storage::get_admin_threshold(env) {
+        return Err(ProxyError::BelowThreshold);
+    }
+    if !governance && !add_approval(env, caller, &ProxyAction::RemoveAdmin(admin.clone()))? {
+        return Ok(false);
+    }
+
+    admins.remove(index);
+    storage::set_admins(env, &admins);
+
+    emit_event(
+        env,
+        MOD_PROXY,
+        ACT_ADMIN_REMOVED,
+        (caller.clone(), admin.clone()),
+    );
+
+    Ok(true)
+}
+
+/// Set how many distinct admins must approve an action, once the current
+/// threshold approves or governance asks. Returns whether it was set.
+pub fn set_threshold(env: &Env, caller: &Address, threshold: u32) -> Result<bool, ProxyError> {
+    caller.require_auth();
+
+    let governance = is_governance(env, caller);
+    if !governance && !storage::is_admin(env, caller) {
+        return Err(ProxyError::NotAdmin);
+    }
+    if is_paused(env) {
+        return Err(ProxyError::Paused);
+    }
+    if threshold == 0 || threshold > storage::get_admins(env).len() {
+        return Err(ProxyError::InvalidThreshold);
+    }
+    if !governance && !add_approval(env, caller, &ProxyAction::SetThreshold(threshold))? {
+        return Ok(false);
+    }
+
+    storage::set_admin_threshold(env, threshold);
+
+    emit_event(
+        env,
+        MOD_PROXY,
+        ACT_THRESHOLD_SET,
+        (caller.clone(), threshold),
+    );
+
+    Ok(true)
+}
+
+/// Why `caller` may not switch the proxy to `new_implementation` right now
+fn check_upgrade(
+    env: &Env,
+    caller: &Address,
+    new_implementation: &Address,
+) -> Result<(), ProxyError> {
+    if storage::is_frozen(env) {
+        return Err(ProxyError::Frozen);
+    }
+    if !storage::is_admin(env, caller) {
+        return Err(ProxyError::NotAdmin);
+    }
+    if is_paused(env) {
+        return Err(ProxyError::Paused);
+    }
+    if storage::is_delay_required(env) {
+        return Err(ProxyError::DelayRequired);
+    }
+    check_cooldown(env)?;
+    if storage::is_current_implementation(env, new_implementation) {
+        return Err(ProxyError::SameImplementation);
+    }
+    check_allowlisted(env, caller, new_implementation)?;
+    check_reported_version(env, new_implementation, None)
+}
+
+/// Whether the minimum interval since the last switch has passed
+fn check_cooldown(env: &Env) -> Result<(), ProxyError> {
+    let since = time::now(env).saturating_sub(storage::get_proxy_config(env).last_updated);
+    if since < storage::get_min_upgrade_interval(env) {
+        return Err(ProxyError::CooldownActive);
+    }
+    Ok(())
+}
+
+/// Ask `new_implementation` for its version through the conventional
+/// `version()` entry point. It must report `expected` when given, and
+/// otherwise a version newer than the contract's current one.
+pub(crate) fn check_reported_version(
+    env: &Env,
+    new_implementation: &Address,
+    expected: Option<&Version>,
+) -> Result<(), ProxyError> {
+    if storage::is_version_check_skipped(env) {
+        return Ok(());
+    }
+
+    let reported: Version = calls::invoke(
+        env,
+        new_implementation,
+        &Symbol::new(env, "version"),
+        Vec::new(env),
+    )
+    .map_err(|_| ProxyError::ImplementationVersionMismatch)?;
+    let matches = match expected {
+        Some(expected) => reported == *expected,
+        None => {
+            !upgrade_storage::is_initialized(env)
+                || reported.is_newer_than(&upgrade_storage::get_current_version(env))
+        }
+    };
+    if !matches {
+        return Err(ProxyError::ImplementationVersionMismatch);
+    }
+    Ok(())
+}
+
+/// Whether `caller` may move the proxy to `new_implementation` as far as the
+/// allowlist is concerned
+fn check_allowlisted(
+    env: &Env,
+    caller: &Address,
+    new_implementation: &Address,
+) -> Result<(), ProxyError> {
+    let approver = storage::get_implementation_approver(env, new_implementation)
+        .ok_or(ProxyError::ImplementationNotApproved)?;
+    if storage::is_two_party_upgrades(env) && approver == *caller {
+        return Err(ProxyError::SameApprover);
+    }
+    Ok(())
+}
+
+/// Who manages the implementation allowlist: upgrade governance once the
+/// upgrade module is initialized, the proxy admin before that
+fn implementation_approver(env: &Env) -> Address {
+    if upgrade_storage::is_initialized(env) {
+        upgrade_storage::get_governance_address(env)
+    } else {
+        storage::get_admin(env)
+    }
+}
+
+/// Put an implementation on the allowlist the admin may upgrade to
+pub fn approve_implementation(
+    env: &Env,
+    caller: &Address,
+    implementation: &Address,
+) -> Result<(), ProxyError> {
+    caller.require_auth();
+
+    if *caller != implementation_approver(env) {
+        return Err(ProxyError::NotApprover);
+    }
+
+    storage::approve_implementation(env, implementation, caller);
+
+    emit_event(
+        env,
+        MOD_PROXY,
+        ACT_IMPL_APPROVED,
+        (implementation.clone(), caller.clone()),
+    );
+
+    Ok(())
+}
+
+/// Take an implementation off the allowlist; the approver or the admin may revoke
+pub fn revoke_implementation(
+    env: &Env,
+    caller: &Address,
+    implementation: &Address,
+) -> Result<(), ProxyError> {
+    caller.require_auth();
+
+    if *caller != implementation_approver(env) && !storage::is_admin(env, caller) {
+        return Err(ProxyError::NotApprover);
+    }
+    if !storage::is_implementation_approved(env, implementation) {
+        return Err(ProxyError::ImplementationNotApproved);
+    }
+
+    storage::revoke_implementation(env, implementation);
+
+    emit_event(
+        env,
+        MOD_PROXY,
+        ACT_IMPL_REVOKED,
+        (implementation.clone(), caller.clone()),
+    );
+
+    Ok(())
+}
+
+/// Skip the `version()` check for implementations that do not expose it
+pub fn set_skip_version_check(env: &Env, caller: &Address, skip: bool) -> Result<(), ProxyError> {
+    caller.require_auth();
+
+    if *caller != implementation_approver(env) {
+        return Err(ProxyError::NotApprover);
+    }
+
+    storage::set_skip_version_check(env, skip);
 
-/// Upgrade the proxy to a new implementation
-pub fn upgrade(
+    emit_event(env, MOD_PROXY, ACT_VERSION_CHECK_SKIPPED, skip);
+
+    Ok(())
+}
+
+/// Require that an implementation's approver and whoever upgrades to it
+/// are different addresses
+pub fn set_two_party_upgrades(
     env: &Env,
     caller: &Address,
+    enabled: bool,
+) -> Result<(), ProxyError> {
+    caller.require_auth();
+
+    if *caller != implementation_approver(env) {
+        return Err(ProxyError::NotApprover);
+    }
+
+    storage::set_two_party_upgrades(env, enabled);
+
+    emit_event(env, MOD_PROXY, ACT_TWO_PARTY, enabled);
+
+    Ok(())
+}
+
+/// Append a transaction that did not switch the implementation
+fn record_failed_upgrade(
+    env: &Env,
+    initiator: &Address,
+    new_implementation: &Address,
+    reason: &str,
+) {
+    storage::record_upgrade_transaction(
+        env,
+        &UpgradeTransaction {
+            id: storage::take_next_upgrade_id(env),
+            new_implementation: new_implementation.clone(),
+            initiator: initiator.clone(),
+            timestamp: time::now(env),
+            success: false,
+            failure_reason: Some(String::from_str(env, reason)),
+            emergency: false,
+            old_implementation: storage::get_implementation(env),
+            revert: false,
+        },
+    );
+}
+
+/// Switch the implementation and record the transaction; callers check authorization
+pub(crate) fn apply_upgrade(env: &Env, initiator: &Address, new_implementation: &Address) -> u64 {
+    switch_implementation(env, initiator, new_implementation, false, false)
+}
+
+/// Switch the implementation for an emergency upgrade, tagging its transaction.
+/// Only reachable while emergency upgrades are enabled, so the cooldown
+/// between upgrades does not apply.
+pub(crate) fn apply_emergency_upgrade(
+    env: &Env,
+    initiator: &Address,
     new_implementation: &Address,
-) -> Result<(), &'static str> {
+) -> u64 {
+    switch_implementation(env, initiator, new_implementation, true, false)
+}
+
+/// Switch back to `implementation` on behalf of a governance revert, tagging
+/// its transaction as a revert; callers check authorization
+pub(crate) fn apply_revert(env: &Env, initiator: &Address, implementation: &Address) -> u64 {
+    switch_implementation(env, initiator, implementation, false, true)
+}
+
+/// Switch back to the implementation the latest successful transaction
+/// replaced, recording the switch as a revert. Reverting a revert bounces
+/// between the same two implementations.
+///
+/// This is the fast path out of a broken upgrade, so the cooldown, the
+/// allowlist and the version check do not apply; delay-only mode does. When
+/// the upgrade module records which version ran the restored implementation,
+/// its current version moves back with the code, and a revert that would
+/// leave them disagreeing is refused. The revert happens once the admin
+/// threshold approves; returns its upgrade id when it did.
+pub fn revert_last_upgrade(env: &Env, caller: &Address) -> Result<Option<u64>, ProxyError> {
     caller.require_auth();
 
-    // Check if the caller is authorized to perform upgrades
+    if storage::is_frozen(env) {
+        return Err(ProxyError::Frozen);
+    }
     if !storage::is_admin(env, caller) {
-        return Err("Only admin can perform upgrades");
+        return Err(ProxyError::NotAdmin);
+    }
+    if storage::get_pause(env).is_some() {
+        return Err(ProxyError::Paused);
+    }
+    if storage::is_delay_required(env) {
+        return Err(ProxyError::DelayRequired);
     }
 
-    // Validate that the new implementation is a valid contract address
-    // In a real implementation, we might want to validate the contract
+    let last = storage::get_latest_successful_upgrade(env).ok_or(ProxyError::NothingToRevert)?;
+    if storage::is_current_implementation(env, &last.old_implementation) {
+        return Err(ProxyError::NothingToRevert);
+    }
+    let version = reverted_version(env, &last.old_implementation)?;
+    if !add_approval(
+        env,
+        caller,
+        &ProxyAction::Revert(last.old_implementation.clone()),
+    )? {
+        return Ok(None);
+    }
+
+    let upgrade_id = switch_implementation(env, caller, &last.old_implementation, false, true);
+    if let Some(version) = version {
+        upgrade_storage::set_current_version(env, &version, caller, None);
+    }
+
+    Ok(Some(upgrade_id))
+}
 
+/// The upgrade module version a revert to `implementation` has to move to,
+/// if any. None when the module is not initialized, or its current version
+/// records no implementation or already runs this one; otherwise the latest
+/// recent version that ran it.
+fn reverted_version(env: &Env, implementation: &Address) -> Result<Option<Version>, ProxyError> {
+    if !upgrade_storage::is_initialized(env) {
+        return Ok(None);
+    }
+    let current = upgrade_storage::get_current_version(env);
+    match upgrade_storage::get_version_implementation(env, &current) {
+        None => return Ok(None),
+        Some(running) if running == *implementation => return Ok(None),
+        Some(_) => {}
+    }
+
+    let count = upgrade_storage::get_version_count(env);
+    let start = count.saturating_sub(MAX_VERSION_HISTORY_PAGE);
+    upgrade_storage::get_version_history(env, start, MAX_VERSION_HISTORY_PAGE)
+        .iter()
+        .rev()
+        .map(|change| change.version)
+        .find(|version| {
+            *version != current
+                && upgrade_storage::get_version_implementation(env, version).as_ref()
+                    == Some(implementation)
+        })
+        .map(Some)
+        .ok_or(ProxyError::RevertVersionUnknown)
+}
+
+fn switch_implementation(
+    env: &Env,
+    initiator: &Address,
+    new_implementation: &Address,
+    emergency: bool,
+    revert: bool,
+) -> u64 {
     // Record the upgrade transaction before performing the upgrade
-    let upgrade_id = env
-        .storage()
-        .instance()
-        .get(&symbol_short!("nxt_upg"))
-        .unwrap_or(1u64);
-    env.storage()
-        .instance()
-        .set(&symbol_short!("nxt_upg"), &(upgrade_id + 1));
+    let upgrade_id = storage::take_next_upgrade_id(env);
 
     let upgrade_tx = UpgradeTransaction {
         id: upgrade_id,
         new_implementation: new_implementation.clone(),
-        initiator: caller.clone(),
-        timestamp: env.ledger().timestamp(),
-        success: true, // Assume success for now
+        initiator: initiator.clone(),
+        timestamp: time::now(env),
+        success: true,
         failure_reason: None,
+        emergency,
+        old_implementation: storage::get_implementation(env),
+        revert,
     };
 
     // Perform the upgrade by setting the new implementation
@@ -48,33 +510,393 @@ pub fn upgrade(
     storage::record_upgrade_transaction(env, &upgrade_tx);
 
     // Emit upgrade event
+    let action = if revert {
+        ACT_UPGRADE_REVERTED
+    } else {
+        ACT_UPGRADE_EXECUTED
+    };
     emit_event(
         env,
         MOD_PROXY,
-        ACT_UPGRADE_EXECUTED,
+        action,
         (upgrade_id, new_implementation.clone()),
     );
 
+    upgrade_id
+}
+
+/// Set the minimum delay enforced between scheduling and executing an upgrade
+pub fn set_min_upgrade_delay(env: &Env, caller: &Address, delay: u64) -> Result<(), ProxyError> {
+    caller.require_auth();
+
+    if !storage::is_admin(env, caller) {
+        return Err(ProxyError::NotAdmin);
+    }
+
+    storage::set_min_upgrade_delay(env, delay);
+
+    emit_event(env, MOD_PROXY, ACT_UPDATED, delay);
+
     Ok(())
 }
 
-/// Transfer admin rights to a new address
-pub fn transfer_admin(
+/// Opt in to (or out of) delay-only mode, where `upgrade` is rejected and
+/// every switch has to go through `schedule_upgrade`. Returns whether the
+/// admin threshold was reached and the mode changed.
+pub fn set_require_delay(env: &Env, caller: &Address, required: bool) -> Result<bool, ProxyError> {
+    caller.require_auth();
+
+    if !storage::is_admin(env, caller) {
+        return Err(ProxyError::NotAdmin);
+    }
+    if !add_approval(env, caller, &ProxyAction::RequireDelay(required))? {
+        return Ok(false);
+    }
+
+    storage::set_require_delay(env, required);
+
+    emit_event(env, MOD_PROXY, ACT_DELAY_REQUIRED, required);
+
+    Ok(true)
+}
+
+/// Set the minimum time that must pass between two implementation switches
+pub fn set_min_upgrade_interval(
     env: &Env,
     caller: &Address,
-    new_admin: &Address,
-) -> Result<(), &'static str> {
+    interval: u64,
+) -> Result<(), ProxyError> {
     caller.require_auth();
 
-    // Only current admin can transfer admin rights
     if !storage::is_admin(env, caller) {
-        return Err("Only admin can transfer admin rights");
+        return Err(ProxyError::NotAdmin);
     }
 
-    // Update the admin
-    storage::set_admin(env, new_admin);
+    storage::set_min_upgrade_interval(env, interval);
+
+    emit_event(env, MOD_PROXY, ACT_INTERVAL_SET, interval);
+
+    Ok(())
+}
+
+/// Schedule a switch to a new implementation after the minimum delay, once
+/// the admin threshold approves. Returns the schedule id when it was put up.
+pub fn schedule_upgrade(
+    env: &Env,
+    caller: &Address,
+    new_implementation: &Address,
+) -> Result<Option<u64>, ProxyError> {
+    caller.require_auth();
+
+    if !storage::is_admin(env, caller) {
+        return Err(ProxyError::NotAdmin);
+    }
+    if storage::is_frozen(env) {
+        return Err(ProxyError::Frozen);
+    }
+    if is_paused(env) {
+        return Err(ProxyError::Paused);
+    }
+    check_allowlisted(env, caller, new_implementation)?;
+    if !add_approval(
+        env,
+        caller,
+        &ProxyAction::Schedule(new_implementation.clone()),
+    )? {
+        return Ok(None);
+    }
+
+    Ok(Some(store_schedule(env, caller, new_implementation)))
+}
+
+/// Schedule an upgrade on behalf of another module that has already
+/// authorized `initiator` (e.g. an executed governance proposal). The
+/// allowlist does not apply to it, now or when it executes.
+pub(crate) fn schedule_upgrade_unchecked(
+    env: &Env,
+    initiator: &Address,
+    new_implementation: &Address,
+) -> u64 {
+    let schedule_id = store_schedule(env, initiator, new_implementation);
+    storage::set_governance_schedule(env, schedule_id);
+    schedule_id
+}
+
+fn store_schedule(env: &Env, initiator: &Address, new_implementation: &Address) -> u64 {
+    let now = time::now(env);
+    let scheduled = ScheduledUpgrade {
+        id: storage::take_next_schedule_id(env),
+        new_implementation: new_implementation.clone(),
+        initiator: initiator.clone(),
+        scheduled_at: now,
+        executable_at: now + storage::get_min_upgrade_delay(env),
+        status: ScheduleStatus::Pending,
+    };
+    storage::store_scheduled_upgrade(env, &scheduled);
+
+    emit_event(env, MOD_PROXY, ACT_UPGRADE_SCHEDULED, scheduled.clone());
+
+    scheduled.id
+}
+
+/// Execute a scheduled upgrade once its delay has elapsed.
+/// The admin or whoever scheduled it may execute it. A target revoked from
+/// the allowlist meanwhile, or a cooldown still running, is recorded in the
+/// upgrade history and comes back as `Rejected`; the schedule stays pending.
+pub fn execute_scheduled_upgrade(
+    env: &Env,
+    caller: &Address,
+    schedule_id: u64,
+) -> Result<UpgradeOutcome, ProxyError> {
+    upgrade_storage::with_execution_guard(env, ProxyError::ReentrantCall, || {
+        execute_due_schedule(env, caller, schedule_id)
+    })
+}
+
+fn execute_due_schedule(
+    env: &Env,
+    caller: &Address,
+    schedule_id: u64,
+) -> Result<UpgradeOutcome, ProxyError> {
+    caller.require_auth();
+
+    let mut scheduled = load_pending_schedule(env, schedule_id)?;
+    if !storage::is_admin(env, caller) && *caller != scheduled.initiator {
+        return Err(ProxyError::NotAdminOrInitiator);
+    }
+    if storage::is_frozen(env) {
+        return Err(ProxyError::Frozen);
+    }
+    if time::now(env) < scheduled.executable_at {
+        return Err(ProxyError::DelayNotElapsed);
+    }
+    if is_paused(env) {
+        return Err(ProxyError::Paused);
+    }
+    check_reported_version(env, &scheduled.new_implementation, None)?;
+    // The implementation may have been revoked while the delay ran
+    let checked = if storage::is_governance_schedule(env, schedule_id) {
+        check_cooldown(env)
+    } else {
+        check_allowlisted(env, &scheduled.initiator, &scheduled.new_implementation)
+            .and_then(|_| check_cooldown(env))
+    };
+    if let Err(error) = checked {
+        record_failed_upgrade(env, caller, &scheduled.new_implementation, error.name());
+        return Ok(UpgradeOutcome::rejected(error));
+    }
+
+    apply_upgrade(env, caller, &scheduled.new_implementation);
+    scheduled.status = ScheduleStatus::Executed;
+    storage::store_scheduled_upgrade(env, &scheduled);
+
+    Ok(UpgradeOutcome::Applied)
+}
+
+/// Cancel a scheduled upgrade that has not executed yet
+pub fn cancel_scheduled_upgrade(
+    env: &Env,
+    caller: &Address,
+    schedule_id: u64,
+) -> Result<(), ProxyError> {
+    caller.require_auth();
+
+    if !storage::is_admin(env, caller) {
+        return Err(ProxyError::NotAdmin);
+    }
+
+    let mut scheduled = load_pending_schedule(env, schedule_id)?;
+    scheduled.status = ScheduleStatus::Cancelled;
+    storage::store_scheduled_upgrade(env, &scheduled);
+
+    emit_event(env, MOD_PROXY, ACT_CANCELLED, schedule_id);
+
+    Ok(())
+}
+
+fn load_pending_schedule(env: &Env, schedule_id: u64) -> Result<ScheduledUpgrade, ProxyError> {
+    let scheduled =
+        storage::get_scheduled_upgrade(env, schedule_id).ok_or(ProxyError::ScheduleNotFound)?;
+    if scheduled.status != ScheduleStatus::Pending {
+        return Err(ProxyError::ScheduleNotPending);
+    }
+    Ok(scheduled)
+}
+
+/// Set how long a handover may wait for the new implementation to confirm
+pub fn set_handover_timeout(env: &Env, caller: &Address, timeout: u64) -> Result<(), ProxyError> {
+    caller.require_auth();
+
+    if !storage::is_admin(env, caller) {
+        return Err(ProxyError::NotAdmin);
+    }
+
+    storage::set_handover_timeout(env, timeout);
+
+    emit_event(env, MOD_PROXY, ACT_UPDATED, timeout);
+
+    Ok(())
+}
+
+/// Begin a two-phase switch to `new_implementation`. The implementation is
+/// told through `on_handover_start(proxy_config)` and the slot only switches
+/// once it (or the admin) calls `confirm_handover`. A stale pending handover
+/// is timed out first; a live one blocks a new start. The handover begins
+/// once the admin threshold approves; returns its id when it did.
+pub fn begin_handover(
+    env: &Env,
+    caller: &Address,
+    new_implementation: &Address,
+) -> Result<Option<u64>, ProxyError> {
+    caller.require_auth();
+
+    if !storage::is_admin(env, caller) {
+        return Err(ProxyError::NotAdmin);
+    }
+    if storage::is_frozen(env) {
+        return Err(ProxyError::Frozen);
+    }
+    if is_paused(env) {
+        return Err(ProxyError::Paused);
+    }
+    expire_handover(env);
+    if storage::get_active_handover(env).is_some() {
+        return Err(ProxyError::HandoverPending);
+    }
+    check_allowlisted(env, caller, new_implementation)?;
+    check_reported_version(env, new_implementation, None)?;
+    if !add_approval(
+        env,
+        caller,
+        &ProxyAction::Handover(new_implementation.clone()),
+    )? {
+        return Ok(None);
+    }
+
+    let args: Vec<Val> = (storage::get_proxy_config(env),).into_val(env);
+    let started = calls::invoke_outcome(
+        env,
+        new_implementation,
+        &Symbol::new(env, "on_handover_start"),
+        args,
+    );
+    if started.is_err() {
+        return Err(ProxyError::HandoverRejected);
+    }
+
+    let now = time::now(env);
+    let handover = Handover {
+        id: storage::take_next_handover_id(env),
+        new_implementation: new_implementation.clone(),
+        initiator: caller.clone(),
+        started_at: now,
+        expires_at: now + storage::get_handover_timeout(env),
+        status: HandoverStatus::Pending,
+    };
+    storage::store_handover(env, &handover);
+    storage::set_active_handover(env, handover.id);
+
+    emit_event(env, MOD_PROXY, ACT_HANDOVER_STARTED, handover.clone());
+
+    Ok(Some(handover.id))
+}
+
+/// Switch to the pending handover's implementation once its setup succeeded.
+/// Only that implementation or the admin may confirm. A handover past its
+/// timeout is closed and recorded as failed, and comes back as `Rejected`.
+pub fn confirm_handover(env: &Env, caller: &Address) -> Result<UpgradeOutcome, ProxyError> {
+    caller.require_auth();
+
+    if expire_handover(env) {
+        return Ok(UpgradeOutcome::rejected(ProxyError::HandoverExpired));
+    }
+    let mut handover = storage::get_active_handover(env).ok_or(ProxyError::NoHandover)?;
+    if !storage::is_admin(env, caller) && *caller != handover.new_implementation {
+        return Err(ProxyError::NotAdminOrImplementation);
+    }
+    if storage::is_frozen(env) {
+        return Err(ProxyError::Frozen);
+    }
+    if is_paused(env) {
+        return Err(ProxyError::Paused);
+    }
+    check_cooldown(env)?;
+
+    let upgrade_id = apply_upgrade(env, caller, &handover.new_implementation);
+    handover.status = HandoverStatus::Confirmed;
+    storage::store_handover(env, &handover);
+
+    emit_event(
+        env,
+        MOD_PROXY,
+        ACT_HANDOVER_CONFIRMED,
+        (handover.id, upgrade_id),
+    );
+
+    Ok(UpgradeOutcome::Applied)
+}
+
+/// Cancel the pending handover, leaving the current implementation in place.
+/// The admin or the new implementation may abort.
+pub fn abort_handover(env: &Env, caller: &Address) -> Result<(), ProxyError> {
+    caller.require_auth();
+
+    let mut handover = storage::get_active_handover(env).ok_or(ProxyError::NoHandover)?;
+    if !storage::is_admin(env, caller) && *caller != handover.new_implementation {
+        return Err(ProxyError::NotAdminOrImplementation);
+    }
+
+    handover.status = HandoverStatus::Aborted;
+    close_handover(env, caller, &handover, "handover aborted");
+
+    emit_event(env, MOD_PROXY, ACT_HANDOVER_ABORTED, handover.id);
+
+    Ok(())
+}
+
+/// Time out the pending handover if it has passed `expires_at`. Anyone may
+/// call this. Returns true when a handover was timed out.
+pub fn expire_handover(env: &Env) -> bool {
+    let mut handover = match storage::get_active_handover(env) {
+        Some(handover) if time::now(env) > handover.expires_at => handover,
+        _ => return false,
+    };
+
+    handover.status = HandoverStatus::TimedOut;
+    close_handover(env, &handover.initiator, &handover, "handover timed out");
+
+    emit_event(env, MOD_PROXY, ACT_HANDOVER_TIMED_OUT, handover.id);
+
+    true
+}
+
+/// Store a handover that ended without a switch and record it in the
+/// upgrade history as a failed transaction
+fn close_handover(env: &Env, initiator: &Address, handover: &Handover, reason: &str) {
+    storage::store_handover(env, handover);
+    record_failed_upgrade(env, initiator, &handover.new_implementation, reason);
+}
+
+/// Nominate a new admin. The current admin keeps its rights until the
+/// nominee accepts; nominating again replaces the pending candidate. With
+/// several admins only the first hands its seat over this way; the others
+/// come and go through `add_admin` and `remove_admin`.
+pub fn transfer_admin(env: &Env, caller: &Address, new_admin: &Address) -> Result<(), ProxyError> {
+    caller.require_auth();
+
+    // Only current admin can transfer admin rights
+    if *caller != storage::get_admin(env) {
+        return Err(ProxyError::NotAdmin);
+    }
+    if is_paused(env) {
+        return Err(ProxyError::Paused);
+    }
+    if storage::is_admin(env, new_admin) {
+        return Err(ProxyError::AlreadyAdmin);
+    }
+
+    storage::set_pending_admin(env, new_admin);
 
-    // Emit admin transfer event
     emit_event(
         env,
         MOD_PROXY,
@@ -85,45 +907,130 @@ pub fn transfer_admin(
     Ok(())
 }
 
-/// Accept admin rights (if transferred by current admin)
-pub fn accept_admin(env: &Env, new_admin: &Address) -> Result<(), &'static str> {
+/// Accept admin rights nominated by the current admin
+pub fn accept_admin(env: &Env, new_admin: &Address) -> Result<(), ProxyError> {
     new_admin.require_auth();
 
-    // In a real implementation, this would involve a two-step process
-    // where the new admin accepts the transfer
-    // For simplicity, we'll just emit an event
-    emit_event(env, MOD_PROXY, ACT_ACCEPTED, new_admin.clone());
+    let pending = storage::get_pending_admin(env).ok_or(ProxyError::NoPendingAdmin)?;
+    if pending != *new_admin {
+        return Err(ProxyError::NotPendingAdmin);
+    }
+    if is_paused(env) {
+        return Err(ProxyError::Paused);
+    }
+    // Added through `add_admin` since the nomination
+    if storage::is_admin(env, new_admin) {
+        return Err(ProxyError::AlreadyAdmin);
+    }
+
+    let previous = storage::get_admin(env);
+    storage::set_admin(env, new_admin);
+    storage::clear_pending_admin(env);
+
+    emit_event(env, MOD_PROXY, ACT_ACCEPTED, (previous, new_admin.clone()));
 
     Ok(())
 }
 
-/// Emergency stop functionality to pause upgrades
-pub fn emergency_stop(env: &Env, caller: &Address) -> Result<(), &'static str> {
+/// Withdraw a pending admin nomination
+pub fn cancel_admin_transfer(env: &Env, caller: &Address) -> Result<(), ProxyError> {
+    caller.require_auth();
+
+    if !storage::is_admin(env, caller) {
+        return Err(ProxyError::NotAdmin);
+    }
+    let pending = storage::get_pending_admin(env).ok_or(ProxyError::NoPendingAdmin)?;
+    storage::clear_pending_admin(env);
+
+    emit_event(
+        env,
+        MOD_PROXY,
+        ACT_ADMIN_TRANSFER_CANCELLED,
+        (caller.clone(), pending),
+    );
+
+    Ok(())
+}
+
+/// Give up upgradability for good. The admin confirms by naming the current
+/// implementation; afterwards every path that switches it fails, including
+/// governance execution and emergency upgrades, and there is no way back.
+/// Takes effect once the admin threshold approves; returns whether it did.
+pub fn freeze_upgrades(
+    env: &Env,
+    caller: &Address,
+    current_implementation: &Address,
+) -> Result<bool, ProxyError> {
+    caller.require_auth();
+
+    if !storage::is_admin(env, caller) {
+        return Err(ProxyError::NotAdmin);
+    }
+    if storage::is_frozen(env) {
+        return Err(ProxyError::Frozen);
+    }
+    if !storage::is_current_implementation(env, current_implementation) {
+        return Err(ProxyError::FreezeNotConfirmed);
+    }
+    if !add_approval(
+        env,
+        caller,
+        &ProxyAction::Freeze(current_implementation.clone()),
+    )? {
+        return Ok(false);
+    }
+
+    storage::freeze(env);
+    // An open revert window could otherwise still restore the previous implementation
+    upgrade_storage::clear_revert_window(env);
+
+    emit_event(
+        env,
+        MOD_PROXY,
+        ACT_FROZEN,
+        (caller.clone(), current_implementation.clone()),
+    );
+
+    Ok(true)
+}
+
+/// Emergency stop: pause upgrades and admin transfers until `resume`
+pub fn emergency_stop(env: &Env, caller: &Address) -> Result<(), ProxyError> {
     caller.require_auth();
 
     // Only admin can trigger emergency stop
     if !storage::is_admin(env, caller) {
-        return Err("Only admin can trigger emergency stop");
+        return Err(ProxyError::NotAdmin);
+    }
+    if storage::get_pause(env).is_some() {
+        return Err(ProxyError::Paused);
     }
 
-    // In a real implementation, this would set a paused state
-    // For now, we'll just emit an event
+    storage::set_pause(
+        env,
+        &ProxyPause {
+            paused_by: caller.clone(),
+            paused_at: time::now(env),
+        },
+    );
     emit_event(env, MOD_PROXY, ACT_PAUSED, caller.clone());
 
     Ok(())
 }
 
 /// Resume after emergency stop
-pub fn resume(env: &Env, caller: &Address) -> Result<(), &'static str> {
+pub fn resume(env: &Env, caller: &Address) -> Result<(), ProxyError> {
     caller.require_auth();
 
     // Only admin can resume
     if !storage::is_admin(env, caller) {
-        return Err("Only admin can resume");
+        return Err(ProxyError::NotAdmin);
+    }
+    if storage::get_pause(env).is_none() {
+        return Err(ProxyError::NotPaused);
     }
 
-    // In a real implementation, this would unset a paused state
-    // For now, we'll just emit an event
+    storage::clear_pause(env);
     emit_event(env, MOD_PROXY, ACT_RESUMED, caller.clone());
 
     Ok(())
@@ -134,9 +1041,8 @@ pub fn get_proxy_info(env: &Env) -> ProxyConfig {
     storage::get_proxy_config(env)
 }
 
-/// Check if the proxy is paused
-pub fn is_paused(_env: &Env) -> bool {
-    // In a real implementation, this would check a paused state
-    // For now, return false
-    false
+/// Check if the proxy is paused: by an emergency stop, or while the upgrade
+/// module's dead-man switch holds the contract in safe mode
+pub fn is_paused(env: &Env) -> bool {
+    storage::get_pause(env).is_some() || upgrade_storage::is_safe_mode(env)
 }
@@ -1,64 +1,138 @@
-use crate::proxy::types::{ProxyConfig, UpgradeTransaction};
-use soroban_sdk::{symbol_short, Address, Env, Map, Symbol};
+use crate::events::emit::set_era_proxy_version;
+use crate::events::history::append_history;
+use crate::events::types::HistoryKind;
+use crate::keys::proxy::{
+    ACTION_APPROVALS_KEY, ACTIVE_HANDOVER_KEY, ADMINS_KEY, ADMIN_THRESHOLD_KEY, APPROVED_IMPL_KEY,
+    FROZEN_KEY, GOVERNANCE_SCHEDULE_KEY, HANDOVER_KEY, HANDOVER_TIMEOUT_KEY,
+    IMPLEMENTATION_SLOT_KEY, MIN_UPGRADE_DELAY_KEY, MIN_UPGRADE_INTERVAL_KEY, NEXT_HANDOVER_ID_KEY,
+    NEXT_SCHEDULE_ID_KEY, NEXT_UPGRADE_ID_KEY, PENDING_ADMIN_KEY, PROXY_CONFIG_KEY,
+    PROXY_PAUSE_KEY, REQUIRE_DELAY_KEY, SCHEDULED_UPGRADE_KEY, SKIP_VERSION_CHECK_KEY,
+    TWO_PARTY_KEY, UPGRADE_HISTORY_KEY,
+};
+use crate::proxy::types::{
+    Handover, HandoverStatus, LegacyUpgradeTransaction, ProxyAction, ProxyConfig, ProxyError,
+    ProxyPause, ScheduledUpgrade, TaggedUpgradeTransaction, UpgradeTransaction,
+};
+use crate::upgrade::storage::is_executing;
+use crate::utils::time;
+use crate::utils::typed_storage::Slot;
+use soroban_sdk::{panic_with_error, Address, Env, IntoVal, Map, Symbol, TryFromVal, Val, Vec};
 
-// Storage keys for proxy functionality
-const PROXY_CONFIG_KEY: Symbol = symbol_short!("prx_cfg");
-const UPGRADE_HISTORY_KEY: Symbol = symbol_short!("upg_hist");
-const IMPLEMENTATION_SLOT: Symbol = symbol_short!("impl_slot");
-const ADMIN_SLOT: Symbol = symbol_short!("adm_slot");
+// Storage slots for proxy functionality; each key is bound to its value type here
+pub(crate) const PROXY_CONFIG: Slot<Symbol, ProxyConfig> = Slot::new(PROXY_CONFIG_KEY);
+/// Upgrade history as a single map, before each transaction got its own entry
+pub(crate) const LEGACY_UPGRADE_HISTORY: Slot<Symbol, Map<u64, LegacyUpgradeTransaction>> =
+    Slot::new(UPGRADE_HISTORY_KEY);
+pub(crate) const IMPLEMENTATION_SLOT: Slot<Symbol, Address> = Slot::new(IMPLEMENTATION_SLOT_KEY);
+pub(crate) const MIN_UPGRADE_DELAY: Slot<Symbol, u64> = Slot::new(MIN_UPGRADE_DELAY_KEY);
+pub(crate) const MIN_UPGRADE_INTERVAL: Slot<Symbol, u64> = Slot::new(MIN_UPGRADE_INTERVAL_KEY);
+pub(crate) const FROZEN: Slot<Symbol, bool> = Slot::new(FROZEN_KEY);
+pub(crate) const REQUIRE_DELAY: Slot<Symbol, bool> = Slot::new(REQUIRE_DELAY_KEY);
+pub(crate) const TWO_PARTY: Slot<Symbol, bool> = Slot::new(TWO_PARTY_KEY);
+pub(crate) const SKIP_VERSION_CHECK: Slot<Symbol, bool> = Slot::new(SKIP_VERSION_CHECK_KEY);
+pub(crate) const NEXT_SCHEDULE_ID: Slot<Symbol, u64> = Slot::new(NEXT_SCHEDULE_ID_KEY);
+pub(crate) const NEXT_HANDOVER_ID: Slot<Symbol, u64> = Slot::new(NEXT_HANDOVER_ID_KEY);
+pub(crate) const ACTIVE_HANDOVER: Slot<Symbol, u64> = Slot::new(ACTIVE_HANDOVER_KEY);
+pub(crate) const HANDOVER_TIMEOUT: Slot<Symbol, u64> = Slot::new(HANDOVER_TIMEOUT_KEY);
+pub(crate) const PROXY_PAUSE: Slot<Symbol, ProxyPause> = Slot::new(PROXY_PAUSE_KEY);
+pub(crate) const PENDING_ADMIN: Slot<Symbol, Address> = Slot::new(PENDING_ADMIN_KEY);
+/// Every proxy admin, once more than the configured one was added
+pub(crate) const ADMINS: Slot<Symbol, Vec<Address>> = Slot::new(ADMINS_KEY);
+pub(crate) const ADMIN_THRESHOLD: Slot<Symbol, u32> = Slot::new(ADMIN_THRESHOLD_KEY);
+/// Admins that approved each pending action
+pub(crate) const ACTION_APPROVALS: Slot<Symbol, Map<ProxyAction, Vec<Address>>> =
+    Slot::new(ACTION_APPROVALS_KEY);
 
-/// Initialize proxy storage
-pub fn initialize(env: &Env, initial_implementation: Address, admin: Address) {
+/// Handover timeout used until the admin configures one
+pub const DEFAULT_HANDOVER_TIMEOUT: u64 = 86_400;
+
+/// Largest page `get_upgrade_history_page` returns
+pub const MAX_UPGRADE_HISTORY_PAGE: u32 = 50;
+
+pub(crate) fn upgrade_transaction_slot(id: u64) -> Slot<(Symbol, u64), UpgradeTransaction> {
+    Slot::new((UPGRADE_HISTORY_KEY, id))
+}
+
+pub(crate) fn scheduled_upgrade_slot(schedule_id: u64) -> Slot<(Symbol, u64), ScheduledUpgrade> {
+    Slot::new((SCHEDULED_UPGRADE_KEY, schedule_id))
+}
+
+/// Set for a schedule an executed governance proposal put up
+pub(crate) fn governance_schedule_slot(schedule_id: u64) -> Slot<(Symbol, u64), bool> {
+    Slot::new((GOVERNANCE_SCHEDULE_KEY, schedule_id))
+}
+
+/// Allowlisted implementation to the address that approved it
+pub(crate) fn approved_implementation_slot(
+    implementation: &Address,
+) -> Slot<(Symbol, Address), Address> {
+    Slot::new((APPROVED_IMPL_KEY, implementation.clone()))
+}
+
+pub(crate) fn handover_slot(handover_id: u64) -> Slot<(Symbol, u64), Handover> {
+    Slot::new((HANDOVER_KEY, handover_id))
+}
+
+/// Whether the proxy has been configured
+pub fn is_initialized(env: &Env) -> bool {
+    PROXY_CONFIG.has(env)
+}
+
+/// Initialize proxy storage. A second call is refused rather than
+/// replacing the admin and implementation.
+pub fn initialize(
+    env: &Env,
+    initial_implementation: Address,
+    admin: Address,
+) -> Result<(), ProxyError> {
+    if is_initialized(env) {
+        return Err(ProxyError::AlreadyInitialized);
+    }
     let config = ProxyConfig {
         implementation: initial_implementation,
         admin,
         version: 1, // Start with version 1
-        last_updated: env.ledger().timestamp(),
+        last_updated: time::now(env),
     };
 
-    env.storage().persistent().set(&PROXY_CONFIG_KEY, &config);
+    PROXY_CONFIG.store(env, &config);
+    set_era_proxy_version(env, config.version);
 
     // Also store implementation in a dedicated slot for easy access
-    env.storage()
-        .persistent()
-        .set(&IMPLEMENTATION_SLOT, &config.implementation);
-
-    // Initialize upgrade history
-    let upgrade_history: Map<u64, UpgradeTransaction> = Map::new(env);
-    env.storage()
-        .persistent()
-        .set(&UPGRADE_HISTORY_KEY, &upgrade_history);
+    IMPLEMENTATION_SLOT.store(env, &config.implementation);
+    Ok(())
 }
 
 /// Get the current proxy configuration
 pub fn get_proxy_config(env: &Env) -> ProxyConfig {
-    env.storage()
-        .persistent()
-        .get(&PROXY_CONFIG_KEY)
+    PROXY_CONFIG
+        .load(env)
         .expect("Proxy config not initialized")
 }
 
 /// Get the current implementation address
 pub fn get_implementation(env: &Env) -> Address {
-    env.storage()
-        .persistent()
-        .get(&IMPLEMENTATION_SLOT)
+    IMPLEMENTATION_SLOT
+        .load(env)
         .expect("Implementation address not set")
 }
 
-/// Set a new implementation address
+/// Set a new implementation address. Panics once upgrades are frozen, as a
+/// backstop for every path that switches the implementation.
 pub fn set_implementation(env: &Env, implementation: &Address) {
-    env.storage()
-        .persistent()
-        .set(&IMPLEMENTATION_SLOT, implementation);
+    if is_frozen(env) {
+        panic_with_error!(env, ProxyError::Frozen);
+    }
+    IMPLEMENTATION_SLOT.store(env, implementation);
 
     // Also update the config
     let mut config = get_proxy_config(env);
     config.implementation = implementation.clone();
     config.version += 1; // Increment version
-    config.last_updated = env.ledger().timestamp();
+    config.last_updated = time::now(env);
 
-    env.storage().persistent().set(&PROXY_CONFIG_KEY, &config);
+    PROXY_CONFIG.store(env, &config);
+    set_era_proxy_version(env, config.version);
 }
 
 /// Get the admin address
@@ -67,44 +141,219 @@ pub fn get_admin(env: &Env) -> Address {
     config.admin
 }
 
-/// Set a new admin address
+/// Set a new admin address. With several admins this replaces the first
+/// one, which the config mirrors.
 pub fn set_admin(env: &Env, admin: &Address) {
     let mut config = get_proxy_config(env);
+    if let Some(mut admins) = ADMINS.load(env) {
+        admins.set(0, admin.clone());
+        ADMINS.store(env, &admins);
+    }
     config.admin = admin.clone();
-    config.last_updated = env.ledger().timestamp();
+    config.last_updated = time::now(env);
+
+    PROXY_CONFIG.store(env, &config);
+}
+
+/// Every proxy admin; just the configured one until others are added
+pub fn get_admins(env: &Env) -> Vec<Address> {
+    ADMINS.load_or_else(env, || Vec::from_array(env, [get_admin(env)]))
+}
+
+/// Replace the admin set. The first admin is mirrored into the config, and
+/// approvals collected under the old set are dropped.
+pub fn set_admins(env: &Env, admins: &Vec<Address>) {
+    let mut config = get_proxy_config(env);
+    config.admin = admins.get(0).expect("at least one admin");
+    PROXY_CONFIG.store(env, &config);
+    ADMINS.store(env, admins);
+    ACTION_APPROVALS.remove(env);
+}
+
+/// Distinct admin approvals an action needs (1 = any admin alone)
+pub fn get_admin_threshold(env: &Env) -> u32 {
+    ADMIN_THRESHOLD.load(env).unwrap_or(1)
+}
+
+/// Set the approval threshold, dropping approvals collected under the old one
+pub fn set_admin_threshold(env: &Env, threshold: u32) {
+    ADMIN_THRESHOLD.store(env, &threshold);
+    ACTION_APPROVALS.remove(env);
+}
+
+/// Admins that approved `action` so far
+pub fn get_action_approvals(env: &Env, action: &ProxyAction) -> Vec<Address> {
+    ACTION_APPROVALS
+        .load(env)
+        .and_then(|approvals| approvals.get(action.clone()))
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+pub fn set_action_approvals(env: &Env, action: &ProxyAction, approvers: &Vec<Address>) {
+    let mut approvals = ACTION_APPROVALS.load_or_else(env, || Map::new(env));
+    approvals.set(action.clone(), approvers.clone());
+    ACTION_APPROVALS.store(env, &approvals);
+}
+
+pub fn clear_action_approvals(env: &Env, action: &ProxyAction) {
+    if let Some(mut approvals) = ACTION_APPROVALS.load(env) {
+        approvals.remove(action.clone());
+        ACTION_APPROVALS.store(env, &approvals);
+    }
+}
+
+/// The address nominated by `transfer_admin` that has not accepted yet
+pub fn get_pending_admin(env: &Env) -> Option<Address> {
+    PENDING_ADMIN.load(env)
+}
+
+pub fn set_pending_admin(env: &Env, candidate: &Address) {
+    PENDING_ADMIN.store(env, candidate);
+}
 
-    env.storage().persistent().set(&PROXY_CONFIG_KEY, &config);
+pub fn clear_pending_admin(env: &Env) {
+    PENDING_ADMIN.remove(env);
 }
 
-/// Record an upgrade transaction
+/// Record an upgrade transaction under its own id and append it to the
+/// exported history
 pub fn record_upgrade_transaction(env: &Env, transaction: &UpgradeTransaction) {
-    let mut upgrade_history: Map<u64, UpgradeTransaction> = env
-        .storage()
-        .persistent()
-        .get(&UPGRADE_HISTORY_KEY)
-        .unwrap_or_else(|| Map::new(env));
+    upgrade_transaction_slot(transaction.id).store(env, transaction);
+    append_history(env, HistoryKind::ProxyTransactions, transaction.clone());
+}
 
-    upgrade_history.set(transaction.id, transaction.clone());
-    env.storage()
-        .persistent()
-        .set(&UPGRADE_HISTORY_KEY, &upgrade_history);
+/// Number of recorded upgrade transactions. Every id the counter hands out
+/// is recorded, so ids run from 1 to the count.
+pub fn get_upgrade_count(env: &Env) -> u64 {
+    peek_next_upgrade_id(env) - 1
 }
 
 /// Get an upgrade transaction by ID
 pub fn get_upgrade_transaction(env: &Env, id: u64) -> Option<UpgradeTransaction> {
-    let upgrade_history: Map<u64, UpgradeTransaction> = env
-        .storage()
-        .persistent()
-        .get(&UPGRADE_HISTORY_KEY)
-        .unwrap_or_else(|| Map::new(env));
+    upgrade_transaction_slot(id).load(env)
+}
+
+/// The most recently recorded upgrade transaction
+pub fn get_latest_upgrade(env: &Env) -> Option<UpgradeTransaction> {
+    get_upgrade_transaction(env, get_upgrade_count(env))
+}
+
+/// The most recent transaction that switched the implementation
+pub fn get_latest_successful_upgrade(env: &Env) -> Option<UpgradeTransaction> {
+    let mut id = get_upgrade_count(env);
+    while id > 0 {
+        if let Some(transaction) = get_upgrade_transaction(env, id) {
+            if transaction.success {
+                return Some(transaction);
+            }
+        }
+        id -= 1;
+    }
+    None
+}
 
-    upgrade_history.get(id)
+/// Up to `limit` upgrade transactions, newest first, skipping the `start`
+/// most recent ones (capped at `MAX_UPGRADE_HISTORY_PAGE`)
+pub fn get_upgrade_history_page(env: &Env, start: u64, limit: u32) -> Vec<UpgradeTransaction> {
+    let mut page = Vec::new(env);
+    let mut id = get_upgrade_count(env).saturating_sub(start);
+    while id > 0 && page.len() < limit.min(MAX_UPGRADE_HISTORY_PAGE) {
+        if let Some(transaction) = get_upgrade_transaction(env, id) {
+            page.push_back(transaction);
+        }
+        id -= 1;
+    }
+    page
 }
 
-/// Check if an address is the admin
+/// Recorded upgrade transactions that did not switch the implementation, oldest first
+pub fn get_failed_upgrades(env: &Env) -> Vec<UpgradeTransaction> {
+    let mut failed = Vec::new(env);
+    for id in 1..=get_upgrade_count(env) {
+        if let Some(transaction) = get_upgrade_transaction(env, id) {
+            if !transaction.success {
+                failed.push_back(transaction);
+            }
+        }
+    }
+    failed
+}
+
+/// Move transactions kept in the single history map into their own entries
+/// and drop the map. Returns the number of transactions moved.
+pub fn migrate_upgrade_history(env: &Env) -> u32 {
+    let Some(history) = LEGACY_UPGRADE_HISTORY.load(env) else {
+        return 0;
+    };
+    let mut live = None;
+    for transaction in history.values().iter() {
+        let old_implementation = replaced_implementation(
+            &mut live,
+            &transaction.new_implementation,
+            transaction.success,
+        );
+        upgrade_transaction_slot(transaction.id)
+            .store(env, &transaction.into_current(old_implementation));
+    }
+    LEGACY_UPGRADE_HISTORY.remove(env);
+    history.len()
+}
+
+/// Rewrite transactions recorded before the `emergency` tag or the replaced
+/// implementation existed. Returns the number of transactions rewritten.
+pub fn migrate_upgrade_transactions(env: &Env) -> u32 {
+    let emergency = Symbol::new(env, "emergency");
+    let old_implementation = Symbol::new(env, "old_implementation");
+    let mut live = None;
+    let mut migrated = 0u32;
+    for id in 1..=get_upgrade_count(env) {
+        let raw: Slot<(Symbol, u64), Val> = Slot::new((UPGRADE_HISTORY_KEY, id));
+        let Some(value) = raw.load(env) else {
+            continue;
+        };
+        let fields = Map::<Symbol, Val>::try_from_val(env, &value).unwrap();
+        if fields.contains_key(old_implementation.clone()) {
+            let transaction = UpgradeTransaction::try_from_val(env, &value).unwrap();
+            replaced_implementation(
+                &mut live,
+                &transaction.new_implementation,
+                transaction.success,
+            );
+            continue;
+        }
+        let tagged = if fields.contains_key(emergency.clone()) {
+            TaggedUpgradeTransaction::try_from_val(env, &value).unwrap()
+        } else {
+            LegacyUpgradeTransaction::try_from_val(env, &value)
+                .unwrap()
+                .into_tagged()
+        };
+        let replaced =
+            replaced_implementation(&mut live, &tagged.new_implementation, tagged.success);
+        raw.store(env, &tagged.into_current(replaced).into_val(env));
+        migrated += 1;
+    }
+    migrated
+}
+
+/// The implementation a historical transaction replaced, walking the history
+/// oldest first with `live` tracking the last successful switch. Before the
+/// first one nothing earlier is known, so a switch counts as replacing itself.
+fn replaced_implementation(
+    live: &mut Option<Address>,
+    new_implementation: &Address,
+    success: bool,
+) -> Address {
+    let replaced = live.clone().unwrap_or_else(|| new_implementation.clone());
+    if success {
+        *live = Some(new_implementation.clone());
+    }
+    replaced
+}
+
+/// Check if an address is one of the admins
 pub fn is_admin(env: &Env, address: &Address) -> bool {
-    let config = get_proxy_config(env);
-    &config.admin == address
+    get_admins(env).contains(address)
 }
 
 /// Check if an address is the current implementation
@@ -112,3 +361,186 @@ pub fn is_current_implementation(env: &Env, address: &Address) -> bool {
     let current_impl = get_implementation(env);
     &current_impl == address
 }
+
+/// The id the next upgrade transaction will receive
+pub fn peek_next_upgrade_id(env: &Env) -> u64 {
+    env.storage()
+        .instance()
+        .get(&NEXT_UPGRADE_ID_KEY)
+        .unwrap_or(1u64)
+}
+
+/// Hand out the next upgrade transaction id
+pub fn take_next_upgrade_id(env: &Env) -> u64 {
+    let upgrade_id = peek_next_upgrade_id(env);
+    env.storage()
+        .instance()
+        .set(&NEXT_UPGRADE_ID_KEY, &(upgrade_id + 1));
+    upgrade_id
+}
+
+/// Get the minimum delay between scheduling and executing an upgrade (0 = none)
+pub fn get_min_upgrade_delay(env: &Env) -> u64 {
+    MIN_UPGRADE_DELAY.load(env).unwrap_or(0)
+}
+
+/// Set the minimum delay between scheduling and executing an upgrade
+pub fn set_min_upgrade_delay(env: &Env, delay: u64) {
+    MIN_UPGRADE_DELAY.store(env, &delay);
+}
+
+/// Whether upgrades must go through the schedule rather than switch immediately
+pub fn is_delay_required(env: &Env) -> bool {
+    REQUIRE_DELAY.load(env).unwrap_or(false)
+}
+
+pub fn set_require_delay(env: &Env, required: bool) {
+    REQUIRE_DELAY.store(env, &required);
+}
+
+/// Whether `implementation` is on the allowlist
+pub fn is_implementation_approved(env: &Env, implementation: &Address) -> bool {
+    approved_implementation_slot(implementation).has(env)
+}
+
+/// Who put `implementation` on the allowlist, if it is there
+pub fn get_implementation_approver(env: &Env, implementation: &Address) -> Option<Address> {
+    approved_implementation_slot(implementation).load(env)
+}
+
+pub fn approve_implementation(env: &Env, implementation: &Address, approver: &Address) {
+    approved_implementation_slot(implementation).store(env, approver);
+}
+
+pub fn revoke_implementation(env: &Env, implementation: &Address) {
+    approved_implementation_slot(implementation).remove(env);
+}
+
+/// Whether an implementation must be upgraded to by someone other than its approver
+pub fn is_two_party_upgrades(env: &Env) -> bool {
+    TWO_PARTY.load(env).unwrap_or(false)
+}
+
+pub fn set_two_party_upgrades(env: &Env, enabled: bool) {
+    TWO_PARTY.store(env, &enabled);
+}
+
+/// Whether upgrades were permanently frozen
+pub fn is_frozen(env: &Env) -> bool {
+    FROZEN.load(env).unwrap_or(false)
+}
+
+/// Freeze upgrades for good; nothing clears the flag
+pub fn freeze(env: &Env) {
+    FROZEN.store(env, &true);
+}
+
+/// Get the minimum time between two implementation switches (0 = none)
+pub fn get_min_upgrade_interval(env: &Env) -> u64 {
+    MIN_UPGRADE_INTERVAL.load(env).unwrap_or(0)
+}
+
+/// Set the minimum time between two implementation switches
+pub fn set_min_upgrade_interval(env: &Env, interval: u64) {
+    MIN_UPGRADE_INTERVAL.store(env, &interval);
+}
+
+/// Whether switches skip asking the new implementation for its version
+pub fn is_version_check_skipped(env: &Env) -> bool {
+    SKIP_VERSION_CHECK.load(env).unwrap_or(false)
+}
+
+pub fn set_skip_version_check(env: &Env, skip: bool) {
+    SKIP_VERSION_CHECK.store(env, &skip);
+}
+
+/// Hand out the next scheduled upgrade id
+pub fn take_next_schedule_id(env: &Env) -> u64 {
+    let schedule_id = NEXT_SCHEDULE_ID.load(env).unwrap_or(1);
+    NEXT_SCHEDULE_ID.store(env, &(schedule_id + 1));
+    schedule_id
+}
+
+/// Get a scheduled upgrade by ID
+pub fn get_scheduled_upgrade(env: &Env, schedule_id: u64) -> Option<ScheduledUpgrade> {
+    scheduled_upgrade_slot(schedule_id).load(env)
+}
+
+/// Store a scheduled upgrade
+pub fn store_scheduled_upgrade(env: &Env, scheduled: &ScheduledUpgrade) {
+    scheduled_upgrade_slot(scheduled.id).store(env, scheduled);
+}
+
+/// Whether governance put up this schedule, so the admin allowlist does not apply
+pub fn is_governance_schedule(env: &Env, schedule_id: u64) -> bool {
+    governance_schedule_slot(schedule_id)
+        .load(env)
+        .unwrap_or(false)
+}
+
+pub fn set_governance_schedule(env: &Env, schedule_id: u64) {
+    governance_schedule_slot(schedule_id).store(env, &true);
+}
+
+/// Get the seconds a handover may wait for confirmation
+pub fn get_handover_timeout(env: &Env) -> u64 {
+    HANDOVER_TIMEOUT
+        .load(env)
+        .unwrap_or(DEFAULT_HANDOVER_TIMEOUT)
+}
+
+/// Set the seconds a handover may wait for confirmation
+pub fn set_handover_timeout(env: &Env, timeout: u64) {
+    HANDOVER_TIMEOUT.store(env, &timeout);
+}
+
+/// Hand out the next handover id
+pub fn take_next_handover_id(env: &Env) -> u64 {
+    let handover_id = NEXT_HANDOVER_ID.load(env).unwrap_or(1);
+    NEXT_HANDOVER_ID.store(env, &(handover_id + 1));
+    handover_id
+}
+
+/// Get a handover by ID
+pub fn get_handover(env: &Env, handover_id: u64) -> Option<Handover> {
+    handover_slot(handover_id).load(env)
+}
+
+/// Store a handover
+pub fn store_handover(env: &Env, handover: &Handover) {
+    handover_slot(handover.id).store(env, handover);
+}
+
+/// Get the handover that is still pending, if any
+pub fn get_active_handover(env: &Env) -> Option<Handover> {
+    ACTIVE_HANDOVER
+        .load(env)
+        .and_then(|handover_id| get_handover(env, handover_id))
+        .filter(|handover| handover.status == HandoverStatus::Pending)
+}
+
+/// Mark a handover as the pending one
+pub fn set_active_handover(env: &Env, handover_id: u64) {
+    ACTIVE_HANDOVER.store(env, &handover_id);
+}
+
+/// The emergency stop in force, if any
+pub fn get_pause(env: &Env) -> Option<ProxyPause> {
+    PROXY_PAUSE.load(env)
+}
+
+pub fn set_pause(env: &Env, pause: &ProxyPause) {
+    PROXY_PAUSE.store(env, pause);
+}
+
+pub fn clear_pause(env: &Env) {
+    PROXY_PAUSE.remove(env);
+}
+
+/// Fail with `ProxyError::ReentrantCall` when a proxy entrypoint is reached
+/// while an execute path is running
+pub fn reject_reentry(env: &Env) {
+    if is_executing(env) {
+        panic_with_error!(env, ProxyError::ReentrantCall);
+    }
+}
@@ -1,9 +1,57 @@
 #![cfg(test)]
 
-use crate::proxy::{implementation, storage, types::ProxyConfig};
+use crate::proxy::{
+    implementation, storage,
+    types::{
+        HandoverStatus, ProxyAction, ProxyConfig, ProxyError, ProxyPause, ScheduleStatus,
+        UpgradeOutcome, UpgradeTransaction,
+    },
+};
+use crate::upgrade::types::Version;
 use crate::StellarGuildsContract;
-use soroban_sdk::testutils::Address as _;
-use soroban_sdk::{Address, Env};
+use soroban_sdk::testutils::{Address as _, Ledger};
+use soroban_sdk::{Address, Env, String};
+
+/// Implementation that remembers the proxy config it was handed over with
+mod handover_impl {
+    use crate::proxy::types::ProxyConfig;
+    use crate::upgrade::types::CallOutcome;
+    use soroban_sdk::{contract, contractimpl, symbol_short, Env};
+
+    #[contract]
+    pub struct HandoverImpl;
+
+    #[contractimpl]
+    impl HandoverImpl {
+        pub fn on_handover_start(env: Env, config: ProxyConfig) -> CallOutcome {
+            env.storage()
+                .instance()
+                .set(&symbol_short!("started"), &config.version);
+            CallOutcome::ok()
+        }
+
+        pub fn started_with(env: Env) -> Option<u32> {
+            env.storage().instance().get(&symbol_short!("started"))
+        }
+    }
+}
+
+/// Implementation whose setup hook always traps
+mod rejecting_impl {
+    use crate::proxy::types::ProxyConfig;
+    use crate::upgrade::types::CallOutcome;
+    use soroban_sdk::{contract, contractimpl, Env};
+
+    #[contract]
+    pub struct RejectingImpl;
+
+    #[contractimpl]
+    impl RejectingImpl {
+        pub fn on_handover_start(_env: Env, _config: ProxyConfig) -> CallOutcome {
+            panic!("setup failed");
+        }
+    }
+}
 
 fn setup_proxy() -> (Env, Address, Address, Address, Address) {
     let env = Env::default();
@@ -15,12 +63,21 @@ fn setup_proxy() -> (Env, Address, Address, Address, Address) {
     let admin = Address::generate(&env);
     let outsider = Address::generate(&env);
     env.as_contract(&contract_id, || {
-        storage::initialize(&env, implementation.clone(), admin.clone());
+        storage::initialize(&env, implementation.clone(), admin.clone()).unwrap();
+        // Most tests switch to plain addresses that expose no `version()`
+        storage::set_skip_version_check(&env, true);
     });
 
     (env, contract_id, implementation, admin, outsider)
 }
 
+/// Put `target` on the allowlist, approved by `approver`
+fn approve(env: &Env, contract_id: &Address, approver: &Address, target: &Address) {
+    env.as_contract(contract_id, || {
+        implementation::approve_implementation(env, approver, target).unwrap();
+    });
+}
+
 #[test]
 fn test_proxy_storage_and_getters() {
     let (env, contract_id, implementation, admin, _) = setup_proxy();
@@ -41,9 +98,13 @@ fn test_proxy_storage_and_getters() {
 fn test_proxy_upgrade_records_transaction_and_bumps_version() {
     let (env, contract_id, implementation, admin, _) = setup_proxy();
     let new_implementation = Address::generate(&env);
+    approve(&env, &contract_id, &admin, &new_implementation);
 
     env.as_contract(&contract_id, || {
-        assert!(implementation::upgrade(&env, &admin, &new_implementation).is_ok());
+        assert_eq!(
+            implementation::upgrade(&env, &admin, &new_implementation),
+            UpgradeOutcome::Applied
+        );
     });
 
     env.as_contract(&contract_id, || {
@@ -60,6 +121,119 @@ fn test_proxy_upgrade_records_transaction_and_bumps_version() {
     });
 }
 
+#[test]
+fn test_rejected_proxy_upgrades_are_recorded_as_failures() {
+    let (env, contract_id, implementation, admin, outsider) = setup_proxy();
+    let new_implementation = Address::generate(&env);
+    approve(&env, &contract_id, &admin, &new_implementation);
+    env.ledger().with_mut(|l| l.timestamp = 2_000);
+
+    // Through the contract entrypoint, so each refusal has to survive the call
+    let client = crate::StellarGuildsContractClient::new(&env, &contract_id);
+    assert_eq!(
+        client.try_proxy_upgrade(&outsider, &new_implementation),
+        Ok(Ok(UpgradeOutcome::rejected(ProxyError::NotAdmin)))
+    );
+    assert_eq!(
+        client.try_proxy_upgrade(&admin, &implementation),
+        Ok(Ok(UpgradeOutcome::rejected(ProxyError::SameImplementation)))
+    );
+    client.proxy_emergency_stop(&admin);
+    assert_eq!(
+        client.try_proxy_upgrade(&admin, &new_implementation),
+        Ok(Ok(UpgradeOutcome::rejected(ProxyError::Paused)))
+    );
+    client.proxy_resume(&admin);
+    assert_eq!(
+        client.try_proxy_upgrade(&admin, &new_implementation),
+        Ok(Ok(UpgradeOutcome::Applied))
+    );
+
+    // Nothing was switched until the last attempt
+    assert_eq!(client.proxy_get_info().version, 2);
+    let failed = client.proxy_get_failed_upgrades();
+    let attempts = [
+        (1, &outsider, &new_implementation, "NotAdmin"),
+        (2, &admin, &implementation, "SameImplementation"),
+        (3, &admin, &new_implementation, "Paused"),
+    ];
+    assert_eq!(failed.len(), attempts.len() as u32);
+    for (tx, (id, initiator, target, reason)) in failed.iter().zip(attempts) {
+        assert_eq!(tx.id, id);
+        assert_eq!(&tx.initiator, initiator);
+        assert_eq!(&tx.new_implementation, target);
+        assert_eq!(tx.timestamp, 2_000);
+        assert!(!tx.success);
+        assert_eq!(tx.failure_reason, Some(String::from_str(&env, reason)));
+    }
+
+    let applied = client.proxy_get_latest_upgrade().unwrap();
+    assert_eq!(applied.id, 4);
+    assert!(applied.success);
+    assert_eq!(applied.failure_reason, None);
+}
+
+#[test]
+fn test_upgrade_history_pages_newest_first() {
+    let (env, contract_id, _, admin, outsider) = setup_proxy();
+
+    env.as_contract(&contract_id, || {
+        assert_eq!(storage::get_upgrade_count(&env), 0);
+        assert!(storage::get_upgrade_history_page(&env, 0, 10).is_empty());
+        assert!(storage::get_latest_upgrade(&env).is_none());
+    });
+
+    // Four switches with a rejected attempt in the middle
+    for attempt in 0..5 {
+        let caller = if attempt == 2 { &outsider } else { &admin };
+        let target = Address::generate(&env);
+        approve(&env, &contract_id, &admin, &target);
+        env.as_contract(&contract_id, || {
+            let _ = implementation::upgrade(&env, caller, &target);
+        });
+    }
+
+    env.as_contract(&contract_id, || {
+        let ids = |page: soroban_sdk::Vec<UpgradeTransaction>| {
+            let mut ids = soroban_sdk::Vec::new(&env);
+            for transaction in page.iter() {
+                ids.push_back(transaction.id);
+            }
+            ids
+        };
+        assert_eq!(storage::get_upgrade_count(&env), 5);
+        assert_eq!(
+            ids(storage::get_upgrade_history_page(&env, 0, 2)),
+            soroban_sdk::Vec::from_array(&env, [5, 4])
+        );
+        assert_eq!(
+            ids(storage::get_upgrade_history_page(&env, 2, 2)),
+            soroban_sdk::Vec::from_array(&env, [3, 2])
+        );
+        assert_eq!(
+            ids(storage::get_upgrade_history_page(&env, 4, 10)),
+            soroban_sdk::Vec::from_array(&env, [1])
+        );
+        assert!(storage::get_upgrade_history_page(&env, 5, 10).is_empty());
+        assert!(storage::get_upgrade_history_page(&env, u64::MAX, 10).is_empty());
+        assert!(
+            !storage::get_upgrade_history_page(&env, 2, 1)
+                .get(0)
+                .unwrap()
+                .success
+        );
+
+        let latest = storage::get_latest_upgrade(&env).unwrap();
+        assert_eq!(latest.id, 5);
+        assert_eq!(latest.new_implementation, storage::get_implementation(&env));
+    });
+
+    let client = crate::StellarGuildsContractClient::new(&env, &contract_id);
+    assert_eq!(client.proxy_get_upgrade_count(), 5);
+    assert_eq!(client.proxy_get_upgrade_history(&1, &2).len(), 2);
+    assert_eq!(client.proxy_get_latest_upgrade().unwrap().id, 5);
+}
+
 #[test]
 fn test_proxy_admin_rotation_and_guards() {
     let (env, contract_id, _, admin, outsider) = setup_proxy();
@@ -69,25 +243,83 @@ fn test_proxy_admin_rotation_and_guards() {
     env.as_contract(&contract_id, || {
         assert_eq!(
             implementation::upgrade(&env, &outsider, &next_impl),
-            Err("Only admin can perform upgrades")
+            UpgradeOutcome::rejected(ProxyError::NotAdmin)
         );
     });
     env.as_contract(&contract_id, || {
         assert_eq!(
             implementation::transfer_admin(&env, &outsider, &new_admin),
-            Err("Only admin can transfer admin rights")
+            Err(ProxyError::NotAdmin)
+        );
+    });
+
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            implementation::accept_admin(&env, &new_admin),
+            Err(ProxyError::NoPendingAdmin)
         );
     });
 
+    // A nomination leaves the current admin in charge
+    env.as_contract(&contract_id, || {
+        assert!(implementation::transfer_admin(&env, &admin, &outsider).is_ok());
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(storage::get_pending_admin(&env), Some(outsider.clone()));
+        assert!(storage::is_admin(&env, &admin));
+        assert!(!storage::is_admin(&env, &outsider));
+    });
+
+    // Nominating again replaces the candidate
     env.as_contract(&contract_id, || {
         assert!(implementation::transfer_admin(&env, &admin, &new_admin).is_ok());
     });
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            implementation::accept_admin(&env, &outsider),
+            Err(ProxyError::NotPendingAdmin)
+        );
+    });
+    env.as_contract(&contract_id, || {
+        assert!(implementation::accept_admin(&env, &new_admin).is_ok());
+    });
     env.as_contract(&contract_id, || {
         assert_eq!(storage::get_admin(&env), new_admin);
         assert!(!storage::is_admin(&env, &admin));
+        assert_eq!(storage::get_pending_admin(&env), None);
+    });
+}
+
+#[test]
+fn test_proxy_admin_transfer_can_be_cancelled() {
+    let (env, contract_id, _, admin, outsider) = setup_proxy();
+    let candidate = Address::generate(&env);
+
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            implementation::cancel_admin_transfer(&env, &admin),
+            Err(ProxyError::NoPendingAdmin)
+        );
     });
     env.as_contract(&contract_id, || {
-        assert!(implementation::accept_admin(&env, &new_admin).is_ok());
+        assert!(implementation::transfer_admin(&env, &admin, &candidate).is_ok());
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            implementation::cancel_admin_transfer(&env, &outsider),
+            Err(ProxyError::NotAdmin)
+        );
+    });
+    env.as_contract(&contract_id, || {
+        assert!(implementation::cancel_admin_transfer(&env, &admin).is_ok());
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(storage::get_pending_admin(&env), None);
+        assert_eq!(
+            implementation::accept_admin(&env, &candidate),
+            Err(ProxyError::NoPendingAdmin)
+        );
+        assert!(storage::is_admin(&env, &admin));
     });
 }
 
@@ -98,23 +330,1077 @@ fn test_proxy_pause_resume_and_paused_query() {
     env.as_contract(&contract_id, || {
         assert_eq!(
             implementation::emergency_stop(&env, &outsider),
-            Err("Only admin can trigger emergency stop")
+            Err(ProxyError::NotAdmin)
         );
     });
     env.as_contract(&contract_id, || {
         assert_eq!(
             implementation::resume(&env, &outsider),
-            Err("Only admin can resume")
+            Err(ProxyError::NotAdmin)
         );
     });
 
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            implementation::resume(&env, &admin),
+            Err(ProxyError::NotPaused)
+        );
+    });
+
+    env.ledger().with_mut(|l| l.timestamp = 5_000);
     env.as_contract(&contract_id, || {
         assert!(implementation::emergency_stop(&env, &admin).is_ok());
     });
+    env.as_contract(&contract_id, || {
+        assert!(implementation::is_paused(&env));
+        assert_eq!(
+            storage::get_pause(&env),
+            Some(ProxyPause {
+                paused_by: admin.clone(),
+                paused_at: 5_000,
+            })
+        );
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            implementation::emergency_stop(&env, &admin),
+            Err(ProxyError::Paused)
+        );
+    });
+
+    // Upgrades and admin transfers wait for the resume
+    let next_impl = Address::generate(&env);
+    approve(&env, &contract_id, &admin, &next_impl);
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            implementation::upgrade(&env, &admin, &next_impl),
+            UpgradeOutcome::rejected(ProxyError::Paused)
+        );
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            implementation::transfer_admin(&env, &admin, &outsider),
+            Err(ProxyError::Paused)
+        );
+    });
+
     env.as_contract(&contract_id, || {
         assert!(implementation::resume(&env, &admin).is_ok());
     });
     env.as_contract(&contract_id, || {
         assert!(!implementation::is_paused(&env));
+        assert_eq!(storage::get_pause(&env), None);
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            implementation::upgrade(&env, &admin, &next_impl),
+            UpgradeOutcome::Applied
+        );
+    });
+}
+
+#[test]
+fn test_proxy_typed_slots_round_trip_every_key() {
+    let (env, contract_id, implementation, admin, outsider) = setup_proxy();
+
+    env.as_contract(&contract_id, || {
+        let config = storage::PROXY_CONFIG.load(&env).unwrap();
+        assert_eq!(config.admin, admin);
+        storage::PROXY_CONFIG.store(
+            &env,
+            &ProxyConfig {
+                admin: outsider.clone(),
+                ..config
+            },
+        );
+        assert_eq!(storage::PROXY_CONFIG.load(&env).unwrap().admin, outsider);
+
+        assert_eq!(
+            storage::IMPLEMENTATION_SLOT.load(&env),
+            Some(implementation)
+        );
+        storage::IMPLEMENTATION_SLOT.store(&env, &outsider);
+        assert_eq!(
+            storage::IMPLEMENTATION_SLOT.load(&env),
+            Some(outsider.clone())
+        );
+
+        assert!(storage::LEGACY_UPGRADE_HISTORY.load(&env).is_none());
+        assert!(storage::upgrade_transaction_slot(1).load(&env).is_none());
+        storage::upgrade_transaction_slot(1).store(
+            &env,
+            &UpgradeTransaction {
+                id: 1,
+                new_implementation: outsider.clone(),
+                initiator: admin.clone(),
+                timestamp: 0,
+                success: true,
+                failure_reason: None,
+                emergency: false,
+                old_implementation: Address::generate(&env),
+                revert: false,
+            },
+        );
+        assert_eq!(
+            storage::get_upgrade_transaction(&env, 1)
+                .unwrap()
+                .new_implementation,
+            outsider
+        );
+
+        assert!(!storage::is_governance_schedule(&env, 1));
+        storage::governance_schedule_slot(1).store(&env, &true);
+        assert!(storage::is_governance_schedule(&env, 1));
+    });
+}
+
+/// Begin a handover from `admin` to a fresh recording implementation
+fn begin_recorded_handover(env: &Env, contract_id: &Address, admin: &Address) -> (u64, Address) {
+    let new_implementation = env.register_contract(None, handover_impl::HandoverImpl);
+    approve(env, contract_id, admin, &new_implementation);
+    let handover_id = env.as_contract(contract_id, || {
+        implementation::begin_handover(env, admin, &new_implementation)
+            .unwrap()
+            .unwrap()
+    });
+    (handover_id, new_implementation)
+}
+
+#[test]
+fn test_handover_switches_only_after_confirmation() {
+    let (env, contract_id, implementation, admin, outsider) = setup_proxy();
+    let (handover_id, new_implementation) = begin_recorded_handover(&env, &contract_id, &admin);
+
+    // The new implementation saw the proxy config; the slot has not moved
+    let recorder = handover_impl::HandoverImplClient::new(&env, &new_implementation);
+    assert_eq!(recorder.started_with(), Some(1));
+    env.as_contract(&contract_id, || {
+        assert_eq!(storage::get_implementation(&env), implementation);
+        assert_eq!(
+            implementation::begin_handover(&env, &admin, &Address::generate(&env)),
+            Err(ProxyError::HandoverPending)
+        );
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            implementation::confirm_handover(&env, &outsider),
+            Err(ProxyError::NotAdminOrImplementation)
+        );
+    });
+
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            implementation::confirm_handover(&env, &new_implementation),
+            Ok(UpgradeOutcome::Applied)
+        );
+        assert_eq!(storage::get_implementation(&env), new_implementation);
+        assert!(storage::get_latest_upgrade(&env).unwrap().success);
+        assert_eq!(
+            storage::get_handover(&env, handover_id).unwrap().status,
+            HandoverStatus::Confirmed
+        );
+        assert!(storage::get_active_handover(&env).is_none());
+    });
+}
+
+#[test]
+fn test_unconfirmed_handover_times_out() {
+    let (env, contract_id, implementation, admin, _) = setup_proxy();
+    env.ledger().with_mut(|l| l.timestamp = 1_000);
+    env.as_contract(&contract_id, || {
+        implementation::set_handover_timeout(&env, &admin, 600).unwrap();
+    });
+    let (handover_id, new_implementation) = begin_recorded_handover(&env, &contract_id, &admin);
+    env.as_contract(&contract_id, || {
+        let handover = storage::get_handover(&env, handover_id).unwrap();
+        assert_eq!(handover.expires_at, 1_600);
+        assert!(!implementation::expire_handover(&env));
+    });
+
+    // The timeout is an outcome, so closing the handover outlives the call
+    env.ledger().with_mut(|l| l.timestamp = 1_601);
+    let client = crate::StellarGuildsContractClient::new(&env, &contract_id);
+    assert_eq!(
+        client.try_proxy_confirm_handover(&new_implementation),
+        Ok(Ok(UpgradeOutcome::rejected(ProxyError::HandoverExpired)))
+    );
+    assert_eq!(client.proxy_get_info().implementation, implementation);
+    assert_eq!(
+        client.proxy_get_handover(&handover_id).unwrap().status,
+        HandoverStatus::TimedOut
+    );
+    let record = client.proxy_get_latest_upgrade().unwrap();
+    assert!(!record.success);
+    assert_eq!(
+        record.failure_reason,
+        Some(String::from_str(&env, "handover timed out"))
+    );
+
+    // A fresh handover can start once the stale one is closed
+    begin_recorded_handover(&env, &contract_id, &admin);
+}
+
+#[test]
+fn test_aborted_handover_keeps_current_implementation() {
+    let (env, contract_id, implementation, admin, _) = setup_proxy();
+    let (handover_id, new_implementation) = begin_recorded_handover(&env, &contract_id, &admin);
+
+    env.as_contract(&contract_id, || {
+        implementation::abort_handover(&env, &new_implementation).unwrap();
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            implementation::confirm_handover(&env, &new_implementation),
+            Err(ProxyError::NoHandover)
+        );
+        assert_eq!(storage::get_implementation(&env), implementation);
+        assert_eq!(
+            storage::get_handover(&env, handover_id).unwrap().status,
+            HandoverStatus::Aborted
+        );
+        assert_eq!(
+            storage::get_upgrade_transaction(&env, 1)
+                .unwrap()
+                .failure_reason,
+            Some(String::from_str(&env, "handover aborted"))
+        );
+    });
+
+    // An implementation that fails its setup hook never gets a handover
+    let rejecting = env.register_contract(None, rejecting_impl::RejectingImpl);
+    approve(&env, &contract_id, &admin, &rejecting);
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            implementation::begin_handover(&env, &admin, &rejecting),
+            Err(ProxyError::HandoverRejected)
+        );
+        assert!(storage::get_active_handover(&env).is_none());
+    });
+
+    // So does one answering with a failed or malformed outcome
+    let refusing = env.register_contract(None, crate::upgrade::testutils::RejectingHook);
+    let malformed = env.register_contract(None, crate::upgrade::testutils::MalformedHook);
+    for hook in [refusing, malformed] {
+        approve(&env, &contract_id, &admin, &hook);
+        env.as_contract(&contract_id, || {
+            assert_eq!(
+                implementation::begin_handover(&env, &admin, &hook),
+                Err(ProxyError::HandoverRejected)
+            );
+            assert!(storage::get_active_handover(&env).is_none());
+        });
+    }
+}
+
+#[test]
+fn test_second_initialize_keeps_original_admin() {
+    let (env, contract_id, implementation, admin, outsider) = setup_proxy();
+
+    env.as_contract(&contract_id, || {
+        assert!(storage::is_initialized(&env));
+        assert_eq!(
+            storage::initialize(&env, outsider.clone(), outsider.clone()),
+            Err(ProxyError::AlreadyInitialized)
+        );
+        assert_eq!(storage::get_proxy_config(&env).admin, admin);
+        assert_eq!(storage::get_implementation(&env), implementation);
+    });
+
+    let client = crate::StellarGuildsContractClient::new(&env, &contract_id);
+    assert!(client.is_proxy_initialized());
+    assert_eq!(
+        client.try_initialize_proxy(&outsider, &outsider),
+        Err(Ok(ProxyError::AlreadyInitialized.into()))
+    );
+}
+
+#[test]
+fn test_delay_only_mode_routes_upgrades_through_the_schedule() {
+    let (env, contract_id, implementation, admin, outsider) = setup_proxy();
+    let new_implementation = Address::generate(&env);
+    env.ledger().with_mut(|l| l.timestamp = 1_000);
+    env.as_contract(&contract_id, || {
+        implementation::set_min_upgrade_delay(&env, &admin, 3_600).unwrap();
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            implementation::set_require_delay(&env, &outsider, true),
+            Err(ProxyError::NotAdmin)
+        );
+    });
+    env.as_contract(&contract_id, || {
+        implementation::set_require_delay(&env, &admin, true).unwrap();
+        assert!(storage::is_delay_required(&env));
+    });
+
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            implementation::upgrade(&env, &admin, &new_implementation),
+            UpgradeOutcome::rejected(ProxyError::DelayRequired)
+        );
+    });
+    approve(&env, &contract_id, &admin, &outsider);
+    approve(&env, &contract_id, &admin, &new_implementation);
+    let cancelled = env.as_contract(&contract_id, || {
+        implementation::schedule_upgrade(&env, &admin, &outsider)
+            .unwrap()
+            .unwrap()
+    });
+    let schedule_id = env.as_contract(&contract_id, || {
+        implementation::schedule_upgrade(&env, &admin, &new_implementation)
+            .unwrap()
+            .unwrap()
+    });
+    env.as_contract(&contract_id, || {
+        let scheduled = storage::get_scheduled_upgrade(&env, schedule_id).unwrap();
+        assert_eq!(scheduled.executable_at, 4_600);
+        assert_eq!(scheduled.status, ScheduleStatus::Pending);
+        implementation::cancel_scheduled_upgrade(&env, &admin, cancelled).unwrap();
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            implementation::execute_scheduled_upgrade(&env, &admin, schedule_id),
+            Err(ProxyError::DelayNotElapsed)
+        );
+    });
+
+    env.ledger().with_mut(|l| l.timestamp = 4_600);
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            implementation::execute_scheduled_upgrade(&env, &admin, cancelled),
+            Err(ProxyError::ScheduleNotPending)
+        );
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            implementation::execute_scheduled_upgrade(&env, &admin, schedule_id),
+            Ok(UpgradeOutcome::Applied)
+        );
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(storage::get_implementation(&env), new_implementation);
+        assert_ne!(storage::get_implementation(&env), implementation);
+        assert_eq!(
+            storage::get_scheduled_upgrade(&env, schedule_id)
+                .unwrap()
+                .status,
+            ScheduleStatus::Executed
+        );
+        // Only the immediate attempt was refused and recorded
+        let failed = storage::get_failed_upgrades(&env);
+        assert_eq!(failed.len(), 1);
+        assert_eq!(
+            failed.get(0).unwrap().failure_reason,
+            Some(String::from_str(&env, "DelayRequired"))
+        );
+    });
+}
+
+#[test]
+fn test_upgrades_are_limited_to_the_allowlist() {
+    let (env, contract_id, _, admin, outsider) = setup_proxy();
+    let target = Address::generate(&env);
+    let client = crate::StellarGuildsContractClient::new(&env, &contract_id);
+
+    assert_eq!(
+        client.try_proxy_upgrade(&admin, &target),
+        Ok(Ok(UpgradeOutcome::rejected(
+            ProxyError::ImplementationNotApproved
+        )))
+    );
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            implementation::schedule_upgrade(&env, &admin, &target),
+            Err(ProxyError::ImplementationNotApproved)
+        );
     });
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            implementation::begin_handover(&env, &admin, &target),
+            Err(ProxyError::ImplementationNotApproved)
+        );
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            implementation::approve_implementation(&env, &outsider, &target),
+            Err(ProxyError::NotApprover)
+        );
+        assert!(!storage::is_implementation_approved(&env, &target));
+    });
+
+    // Approval and revocation round trip
+    approve(&env, &contract_id, &admin, &target);
+    env.as_contract(&contract_id, || {
+        assert!(storage::is_implementation_approved(&env, &target));
+        implementation::revoke_implementation(&env, &admin, &target).unwrap();
+    });
+    env.as_contract(&contract_id, || {
+        assert!(!storage::is_implementation_approved(&env, &target));
+        assert_eq!(
+            implementation::revoke_implementation(&env, &admin, &target),
+            Err(ProxyError::ImplementationNotApproved)
+        );
+    });
+
+    // With two-party upgrades on, the approver cannot also perform the switch
+    approve(&env, &contract_id, &admin, &target);
+    env.as_contract(&contract_id, || {
+        implementation::set_two_party_upgrades(&env, &admin, true).unwrap();
+    });
+    assert_eq!(
+        client.try_proxy_upgrade(&admin, &target),
+        Ok(Ok(UpgradeOutcome::rejected(ProxyError::SameApprover)))
+    );
+    env.as_contract(&contract_id, || {
+        implementation::set_two_party_upgrades(&env, &admin, false).unwrap();
+    });
+    assert_eq!(
+        client.try_proxy_upgrade(&admin, &target),
+        Ok(Ok(UpgradeOutcome::Applied))
+    );
+
+    // Both refusals outlived the calls that made them
+    assert_eq!(client.proxy_get_info().implementation, target);
+    let reasons = ["ImplementationNotApproved", "SameApprover"];
+    let failed = client.proxy_get_failed_upgrades();
+    assert_eq!(failed.len(), reasons.len() as u32);
+    for (tx, reason) in failed.iter().zip(reasons) {
+        assert!(!tx.success);
+        assert_eq!(tx.failure_reason, Some(String::from_str(&env, reason)));
+    }
+}
+
+#[test]
+fn test_revoked_implementation_cannot_execute_its_schedule() {
+    let (env, contract_id, _, admin, _) = setup_proxy();
+    let target = Address::generate(&env);
+    env.ledger().with_mut(|l| l.timestamp = 1_000);
+    env.as_contract(&contract_id, || {
+        implementation::set_min_upgrade_delay(&env, &admin, 3_600).unwrap();
+    });
+    approve(&env, &contract_id, &admin, &target);
+    let schedule_id = env.as_contract(&contract_id, || {
+        implementation::schedule_upgrade(&env, &admin, &target)
+            .unwrap()
+            .unwrap()
+    });
+    env.as_contract(&contract_id, || {
+        implementation::revoke_implementation(&env, &admin, &target).unwrap();
+    });
+
+    env.ledger().with_mut(|l| l.timestamp = 4_600);
+    let client = crate::StellarGuildsContractClient::new(&env, &contract_id);
+    assert_eq!(
+        client.try_proxy_execute_scheduled_upgrade(&admin, &schedule_id),
+        Ok(Ok(UpgradeOutcome::rejected(
+            ProxyError::ImplementationNotApproved
+        )))
+    );
+    assert_ne!(client.proxy_get_info().implementation, target);
+    let failed = client.proxy_get_failed_upgrades();
+    assert_eq!(failed.len(), 1);
+    assert_eq!(
+        failed.get(0).unwrap().failure_reason,
+        Some(String::from_str(&env, "ImplementationNotApproved"))
+    );
+    assert_eq!(
+        client
+            .proxy_get_scheduled_upgrade(&schedule_id)
+            .unwrap()
+            .status,
+        ScheduleStatus::Pending
+    );
+
+    // Approved again, the same schedule goes through
+    approve(&env, &contract_id, &admin, &target);
+    assert_eq!(
+        client.proxy_execute_scheduled_upgrade(&admin, &schedule_id),
+        UpgradeOutcome::Applied
+    );
+    assert_eq!(client.proxy_get_info().implementation, target);
+}
+
+#[test]
+fn test_governance_approves_and_admin_upgrades() {
+    let (env, contract_id, _, admin, _) = setup_proxy();
+    let governance = Address::generate(&env);
+    let target = Address::generate(&env);
+    env.as_contract(&contract_id, || {
+        crate::upgrade::storage::initialize(
+            &env,
+            crate::upgrade::types::Version::new(1, 0, 0),
+            governance.clone(),
+        )
+        .unwrap();
+    });
+
+    // Once governance exists the admin no longer manages the allowlist
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            implementation::approve_implementation(&env, &admin, &target),
+            Err(ProxyError::NotApprover)
+        );
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            implementation::set_two_party_upgrades(&env, &admin, true),
+            Err(ProxyError::NotApprover)
+        );
+    });
+    env.as_contract(&contract_id, || {
+        implementation::set_two_party_upgrades(&env, &governance, true).unwrap();
+    });
+    approve(&env, &contract_id, &governance, &target);
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            storage::get_implementation_approver(&env, &target),
+            Some(governance.clone())
+        );
+        assert_eq!(
+            implementation::upgrade(&env, &admin, &target),
+            UpgradeOutcome::Applied
+        );
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(storage::get_implementation(&env), target);
+    });
+}
+
+#[test]
+fn test_upgrade_requires_a_newer_reported_version() {
+    let (env, contract_id, implementation, admin, _) = setup_proxy();
+    let governance = Address::generate(&env);
+    let candidate = env.register_contract(None, crate::upgrade::testutils::VersionedImplementation);
+    let reporter = crate::upgrade::testutils::VersionedImplementationClient::new(&env, &candidate);
+    env.as_contract(&contract_id, || {
+        crate::upgrade::storage::initialize(&env, Version::new(1, 2, 0), governance.clone())
+            .unwrap();
+        storage::set_skip_version_check(&env, false);
+    });
+    approve(&env, &contract_id, &governance, &candidate);
+
+    // Nothing reported yet, then an older build
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            implementation::upgrade(&env, &admin, &candidate),
+            UpgradeOutcome::rejected(ProxyError::ImplementationVersionMismatch)
+        );
+    });
+    reporter.report(&Version::new(1, 1, 0));
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            implementation::upgrade(&env, &admin, &candidate),
+            UpgradeOutcome::rejected(ProxyError::ImplementationVersionMismatch)
+        );
+        assert_eq!(storage::get_implementation(&env), implementation);
+    });
+
+    reporter.report(&Version::new(1, 3, 0));
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            implementation::upgrade(&env, &admin, &candidate),
+            UpgradeOutcome::Applied
+        );
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(storage::get_implementation(&env), candidate);
+        assert_eq!(storage::get_failed_upgrades(&env).len(), 2);
+    });
+}
+
+#[test]
+fn test_upgrades_wait_out_the_minimum_interval() {
+    let (env, contract_id, _, admin, outsider) = setup_proxy();
+    let (first, second, third) = (
+        Address::generate(&env),
+        Address::generate(&env),
+        Address::generate(&env),
+    );
+    for target in [&first, &second, &third] {
+        approve(&env, &contract_id, &admin, target);
+    }
+    env.ledger().with_mut(|l| l.timestamp = 10_000);
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            implementation::set_min_upgrade_interval(&env, &outsider, 600),
+            Err(ProxyError::NotAdmin)
+        );
+    });
+    env.as_contract(&contract_id, || {
+        implementation::set_min_upgrade_interval(&env, &admin, 600).unwrap();
+        assert_eq!(storage::get_min_upgrade_interval(&env), 600);
+    });
+    let client = crate::StellarGuildsContractClient::new(&env, &contract_id);
+    assert_eq!(
+        client.try_proxy_upgrade(&admin, &first),
+        Ok(Ok(UpgradeOutcome::Applied))
+    );
+
+    // A second switch right after the first is refused, immediate or scheduled
+    env.ledger().with_mut(|l| l.timestamp = 10_599);
+    assert_eq!(
+        client.try_proxy_upgrade(&admin, &second),
+        Ok(Ok(UpgradeOutcome::rejected(ProxyError::CooldownActive)))
+    );
+    let schedule_id = env.as_contract(&contract_id, || {
+        implementation::schedule_upgrade(&env, &admin, &third)
+            .unwrap()
+            .unwrap()
+    });
+    assert_eq!(
+        client.try_proxy_execute_scheduled_upgrade(&admin, &schedule_id),
+        Ok(Ok(UpgradeOutcome::rejected(ProxyError::CooldownActive)))
+    );
+    assert_eq!(client.proxy_get_info().implementation, first);
+
+    // Both refusals were kept, and the schedule can still run afterwards
+    let failed = client.proxy_get_failed_upgrades();
+    assert_eq!(failed.len(), 2);
+    for (tx, target) in failed.iter().zip([&second, &third]) {
+        assert!(!tx.success);
+        assert_eq!(&tx.new_implementation, target);
+        assert_eq!(
+            tx.failure_reason,
+            Some(String::from_str(&env, "CooldownActive"))
+        );
+    }
+    env.ledger().with_mut(|l| l.timestamp = 10_600);
+    assert_eq!(
+        client.try_proxy_execute_scheduled_upgrade(&admin, &schedule_id),
+        Ok(Ok(UpgradeOutcome::Applied))
+    );
+    assert_eq!(client.proxy_get_info().implementation, third);
+    assert_eq!(client.proxy_get_failed_upgrades().len(), 2);
+}
+
+#[test]
+fn test_frozen_proxy_refuses_every_switch() {
+    let (env, contract_id, implementation, admin, outsider) = setup_proxy();
+    let target = Address::generate(&env);
+    approve(&env, &contract_id, &admin, &target);
+    let schedule_id = env.as_contract(&contract_id, || {
+        implementation::schedule_upgrade(&env, &admin, &target)
+            .unwrap()
+            .unwrap()
+    });
+
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            implementation::freeze_upgrades(&env, &outsider, &implementation),
+            Err(ProxyError::NotAdmin)
+        );
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            implementation::freeze_upgrades(&env, &admin, &target),
+            Err(ProxyError::FreezeNotConfirmed)
+        );
+        assert!(!storage::is_frozen(&env));
+    });
+    env.as_contract(&contract_id, || {
+        implementation::freeze_upgrades(&env, &admin, &implementation).unwrap();
+    });
+    env.as_contract(&contract_id, || {
+        assert!(storage::is_frozen(&env));
+        assert_eq!(
+            implementation::freeze_upgrades(&env, &admin, &implementation),
+            Err(ProxyError::Frozen)
+        );
+    });
+
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            implementation::upgrade(&env, &admin, &target),
+            UpgradeOutcome::rejected(ProxyError::Frozen)
+        );
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            implementation::schedule_upgrade(&env, &admin, &target),
+            Err(ProxyError::Frozen)
+        );
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            implementation::execute_scheduled_upgrade(&env, &admin, schedule_id),
+            Err(ProxyError::Frozen)
+        );
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            implementation::begin_handover(&env, &admin, &target),
+            Err(ProxyError::Frozen)
+        );
+        assert_eq!(storage::get_implementation(&env), implementation);
+    });
+
+    let client = crate::StellarGuildsContractClient::new(&env, &contract_id);
+    assert!(client.proxy_is_frozen());
+    assert_eq!(
+        client.proxy_upgrade(&admin, &target),
+        UpgradeOutcome::rejected(ProxyError::Frozen)
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #423)")]
+fn test_frozen_proxy_refuses_direct_implementation_writes() {
+    let (env, contract_id, implementation, admin, _) = setup_proxy();
+    env.as_contract(&contract_id, || {
+        implementation::freeze_upgrades(&env, &admin, &implementation).unwrap();
+    });
+    env.as_contract(&contract_id, || {
+        storage::set_implementation(&env, &Address::generate(&env));
+    });
+}
+
+#[test]
+fn test_revert_last_upgrade_bounces_between_recent_implementations() {
+    let (env, contract_id, implementation, admin, outsider) = setup_proxy();
+    let second = Address::generate(&env);
+    let third = Address::generate(&env);
+    approve(&env, &contract_id, &admin, &second);
+    approve(&env, &contract_id, &admin, &third);
+
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            implementation::revert_last_upgrade(&env, &admin),
+            Err(ProxyError::NothingToRevert)
+        );
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            implementation::upgrade(&env, &admin, &second),
+            UpgradeOutcome::Applied
+        );
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            implementation::upgrade(&env, &admin, &third),
+            UpgradeOutcome::Applied
+        );
+        // A rejected attempt in between is not what gets reverted
+        assert_eq!(
+            implementation::upgrade(&env, &outsider, &second),
+            UpgradeOutcome::rejected(ProxyError::NotAdmin)
+        );
+    });
+    env.as_contract(&contract_id, || {
+        let latest = storage::get_latest_successful_upgrade(&env).unwrap();
+        assert_eq!(latest.old_implementation, second);
+        assert_eq!(
+            storage::get_upgrade_transaction(&env, 1)
+                .unwrap()
+                .old_implementation,
+            implementation
+        );
+        assert_eq!(
+            implementation::revert_last_upgrade(&env, &outsider),
+            Err(ProxyError::NotAdmin)
+        );
+    });
+
+    env.as_contract(&contract_id, || {
+        let id = implementation::revert_last_upgrade(&env, &admin)
+            .unwrap()
+            .unwrap();
+        assert_eq!(storage::get_implementation(&env), second);
+        let revert = storage::get_upgrade_transaction(&env, id).unwrap();
+        assert!(revert.success && revert.revert);
+        assert_eq!(revert.new_implementation, second);
+        assert_eq!(revert.old_implementation, third);
+    });
+    env.as_contract(&contract_id, || {
+        implementation::revert_last_upgrade(&env, &admin).unwrap();
+        assert_eq!(storage::get_implementation(&env), third);
+    });
+
+    env.as_contract(&contract_id, || {
+        implementation::emergency_stop(&env, &admin).unwrap();
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            implementation::revert_last_upgrade(&env, &admin),
+            Err(ProxyError::Paused)
+        );
+    });
+    env.as_contract(&contract_id, || {
+        implementation::resume(&env, &admin).unwrap();
+    });
+    env.as_contract(&contract_id, || {
+        implementation::freeze_upgrades(&env, &admin, &third).unwrap();
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            implementation::revert_last_upgrade(&env, &admin),
+            Err(ProxyError::Frozen)
+        );
+        assert_eq!(storage::get_implementation(&env), third);
+    });
+
+    let client = crate::StellarGuildsContractClient::new(&env, &contract_id);
+    assert_eq!(
+        client.try_proxy_revert_last_upgrade(&admin),
+        Err(Ok(ProxyError::Frozen.into()))
+    );
+}
+
+#[test]
+fn test_upgrade_waits_for_the_admin_threshold() {
+    let (env, contract_id, implementation, admin, outsider) = setup_proxy();
+    let second = Address::generate(&env);
+    let third = Address::generate(&env);
+    let target = Address::generate(&env);
+    approve(&env, &contract_id, &admin, &target);
+
+    // With the default threshold of one, each admin change applies at once
+    env.as_contract(&contract_id, || {
+        assert_eq!(implementation::add_admin(&env, &admin, &second), Ok(true));
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(implementation::add_admin(&env, &admin, &third), Ok(true));
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(implementation::set_threshold(&env, &admin, 2), Ok(true));
+        assert_eq!(
+            storage::get_admins(&env),
+            soroban_sdk::vec![&env, admin.clone(), second.clone(), third.clone()]
+        );
+    });
+
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            implementation::approve_upgrade(&env, &admin, &target),
+            UpgradeOutcome::Approved
+        );
+        assert_eq!(storage::get_implementation(&env), implementation);
+        assert_eq!(
+            storage::get_action_approvals(&env, &ProxyAction::Upgrade(target.clone())),
+            soroban_sdk::vec![&env, admin.clone()]
+        );
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            implementation::approve_upgrade(&env, &admin, &target),
+            UpgradeOutcome::rejected(ProxyError::AlreadyApproved)
+        );
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            implementation::approve_upgrade(&env, &outsider, &target),
+            UpgradeOutcome::rejected(ProxyError::NotAdmin)
+        );
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            implementation::approve_upgrade(&env, &third, &target),
+            UpgradeOutcome::Applied
+        );
+        assert_eq!(storage::get_implementation(&env), target);
+        assert!(
+            storage::get_action_approvals(&env, &ProxyAction::Upgrade(target.clone())).is_empty()
+        );
+    });
+
+    // `upgrade` counts as one approval too
+    let next = Address::generate(&env);
+    approve(&env, &contract_id, &admin, &next);
+    let client = crate::StellarGuildsContractClient::new(&env, &contract_id);
+    assert_eq!(
+        client.proxy_upgrade(&second, &next),
+        UpgradeOutcome::Approved
+    );
+    assert_eq!(client.proxy_get_upgrade_approvals(&next).len(), 1);
+    assert_eq!(
+        client.proxy_approve_upgrade(&admin, &next),
+        UpgradeOutcome::Applied
+    );
+    assert_eq!(client.proxy_get_info().implementation, next);
+}
+
+#[test]
+fn test_one_admin_cannot_switch_by_any_path_under_a_threshold() {
+    let (env, contract_id, implementation, admin, _) = setup_proxy();
+    let second = Address::generate(&env);
+    let third = Address::generate(&env);
+    let upgraded = Address::generate(&env);
+    let target = env.register_contract(None, handover_impl::HandoverImpl);
+    approve(&env, &contract_id, &admin, &upgraded);
+    approve(&env, &contract_id, &admin, &target);
+
+    // One upgrade while a single admin still suffices, so there is one to revert
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            implementation::upgrade(&env, &admin, &upgraded),
+            UpgradeOutcome::Applied
+        );
+    });
+    for new_admin in [&second, &third] {
+        env.as_contract(&contract_id, || {
+            implementation::add_admin(&env, &admin, new_admin).unwrap();
+        });
+    }
+    env.as_contract(&contract_id, || {
+        implementation::set_threshold(&env, &admin, 3).unwrap();
+    });
+
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            implementation::schedule_upgrade(&env, &admin, &target),
+            Ok(None)
+        );
+        assert_eq!(storage::get_scheduled_upgrade(&env, 1), None);
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            implementation::begin_handover(&env, &admin, &target),
+            Ok(None)
+        );
+        assert_eq!(storage::get_active_handover(&env), None);
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(implementation::revert_last_upgrade(&env, &admin), Ok(None));
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            implementation::freeze_upgrades(&env, &admin, &upgraded),
+            Ok(false)
+        );
+        assert!(!storage::is_frozen(&env));
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            implementation::set_require_delay(&env, &admin, true),
+            Ok(false)
+        );
+        assert!(!storage::is_delay_required(&env));
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(storage::get_implementation(&env), upgraded);
+        assert_eq!(
+            storage::get_action_approvals(&env, &ProxyAction::Revert(implementation.clone())),
+            soroban_sdk::vec![&env, admin.clone()]
+        );
+    });
+    let hook = handover_impl::HandoverImplClient::new(&env, &target);
+    assert_eq!(hook.started_with(), None);
+
+    // The schedule goes up with the third approval and then executes
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            implementation::schedule_upgrade(&env, &second, &target),
+            Ok(None)
+        );
+    });
+    let schedule_id = env.as_contract(&contract_id, || {
+        implementation::schedule_upgrade(&env, &third, &target)
+            .unwrap()
+            .unwrap()
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            implementation::execute_scheduled_upgrade(&env, &admin, schedule_id),
+            Ok(UpgradeOutcome::Applied)
+        );
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(storage::get_implementation(&env), target);
+    });
+}
+
+#[test]
+fn test_admin_changes_need_the_threshold_or_governance() {
+    let (env, contract_id, _, admin, outsider) = setup_proxy();
+    let second = Address::generate(&env);
+    env.as_contract(&contract_id, || {
+        implementation::add_admin(&env, &admin, &second).unwrap();
+    });
+    env.as_contract(&contract_id, || {
+        implementation::set_threshold(&env, &admin, 2).unwrap();
+    });
+
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            implementation::remove_admin(&env, &admin, &second),
+            Err(ProxyError::BelowThreshold)
+        );
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            implementation::remove_admin(&env, &admin, &outsider),
+            Err(ProxyError::UnknownAdmin)
+        );
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            implementation::set_threshold(&env, &admin, 3),
+            Err(ProxyError::InvalidThreshold)
+        );
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            implementation::set_threshold(&env, &admin, 0),
+            Err(ProxyError::InvalidThreshold)
+        );
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            implementation::add_admin(&env, &admin, &second),
+            Err(ProxyError::AlreadyAdmin)
+        );
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            implementation::add_admin(&env, &outsider, &outsider),
+            Err(ProxyError::NotAdmin)
+        );
+    });
+
+    env.as_contract(&contract_id, || {
+        assert_eq!(implementation::set_threshold(&env, &admin, 1), Ok(false));
+        assert_eq!(storage::get_admin_threshold(&env), 2);
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(implementation::set_threshold(&env, &second, 1), Ok(true));
+    });
+    // Removing the first admin hands its place in the config to the next
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            implementation::remove_admin(&env, &second, &admin),
+            Ok(true)
+        );
+        assert_eq!(
+            storage::get_admins(&env),
+            soroban_sdk::vec![&env, second.clone()]
+        );
+        assert_eq!(storage::get_admin(&env), second);
+        assert!(!storage::is_admin(&env, &admin));
+    });
+
+    let governance = Address::generate(&env);
+    env.as_contract(&contract_id, || {
+        crate::upgrade::storage::initialize(&env, Version::new(1, 0, 0), governance.clone())
+            .unwrap();
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            implementation::add_admin(&env, &governance, &outsider),
+            Ok(true)
+        );
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            implementation::set_threshold(&env, &governance, 2),
+            Ok(true)
+        );
+        assert!(!storage::is_admin(&env, &governance));
+    });
+
+    let client = crate::StellarGuildsContractClient::new(&env, &contract_id);
+    assert_eq!(client.proxy_get_threshold(), 2);
+    assert_eq!(client.proxy_get_admins().len(), 2);
+    assert_eq!(
+        client.try_proxy_remove_admin(&second, &outsider),
+        Err(Ok(ProxyError::BelowThreshold.into()))
+    );
 }
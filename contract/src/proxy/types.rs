@@ -1,4 +1,4 @@
-﻿use soroban_sdk::{contracttype, Address};
+﻿use soroban_sdk::{contracterror, contracttype, Address};
 
 /// Proxy contract configuration
 #[contracttype]
@@ -14,6 +14,36 @@ pub struct ProxyConfig {
     pub last_updated: u64,
 }
 
+/// Who stopped proxy upgrades and when
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProxyPause {
+    pub paused_by: Address,
+    pub paused_at: u64,
+}
+
+/// Something the proxy admins approve together before it takes effect
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ProxyAction {
+    /// Switch to the given implementation
+    Upgrade(Address),
+    AddAdmin(Address),
+    RemoveAdmin(Address),
+    /// Require this many admin approvals
+    SetThreshold(u32),
+    /// Schedule a switch to the given implementation
+    Schedule(Address),
+    /// Begin a handover to the given implementation
+    Handover(Address),
+    /// Revert the last upgrade, back to the given implementation
+    Revert(Address),
+    /// Freeze upgrades while the given implementation is current
+    Freeze(Address),
+    /// Turn delay-only mode on or off
+    RequireDelay(bool),
+}
+
 /// Represents an upgrade transaction
 #[contracttype]
 #[derive(Clone, Debug)]
@@ -30,4 +60,297 @@ pub struct UpgradeTransaction {
     pub success: bool,
     /// Reason for failure if upgrade failed
     pub failure_reason: Option<soroban_sdk::String>,
+    /// Set for upgrades applied through the emergency path
+    pub emergency: bool,
+    /// Implementation that was live when the transaction was recorded
+    pub old_implementation: Address,
+    /// Set for switches back made by `revert_last_upgrade`
+    pub revert: bool,
+}
+
+/// Stored layout of `UpgradeTransaction` before it recorded the
+/// implementation it replaced; only read by the upgrade history migrations
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct TaggedUpgradeTransaction {
+    pub id: u64,
+    pub new_implementation: Address,
+    pub initiator: Address,
+    pub timestamp: u64,
+    pub success: bool,
+    pub failure_reason: Option<soroban_sdk::String>,
+    pub emergency: bool,
+}
+
+impl TaggedUpgradeTransaction {
+    /// Convert to the current layout; nothing recorded before was a revert
+    pub fn into_current(self, old_implementation: Address) -> UpgradeTransaction {
+        UpgradeTransaction {
+            id: self.id,
+            new_implementation: self.new_implementation,
+            initiator: self.initiator,
+            timestamp: self.timestamp,
+            success: self.success,
+            failure_reason: self.failure_reason,
+            emergency: self.emergency,
+            old_implementation,
+            revert: false,
+        }
+    }
+}
+
+/// Stored layout of `UpgradeTransaction` before emergency upgrades were
+/// recorded; only read by the upgrade history migrations
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct LegacyUpgradeTransaction {
+    pub id: u64,
+    pub new_implementation: Address,
+    pub initiator: Address,
+    pub timestamp: u64,
+    pub success: bool,
+    pub failure_reason: Option<soroban_sdk::String>,
+}
+
+impl LegacyUpgradeTransaction {
+    /// Convert to the current layout; nothing recorded before was an
+    /// emergency upgrade or a revert
+    pub fn into_current(self, old_implementation: Address) -> UpgradeTransaction {
+        self.into_tagged().into_current(old_implementation)
+    }
+
+    /// Convert to the layout that added the `emergency` tag
+    pub fn into_tagged(self) -> TaggedUpgradeTransaction {
+        TaggedUpgradeTransaction {
+            id: self.id,
+            new_implementation: self.new_implementation,
+            initiator: self.initiator,
+            timestamp: self.timestamp,
+            success: self.success,
+            failure_reason: self.failure_reason,
+            emergency: false,
+        }
+    }
+}
+
+/// What became of an attempt to switch the proxy's implementation
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum UpgradeOutcome {
+    /// The implementation was switched
+    Applied,
+    /// The approval was counted; the admin threshold is not reached yet
+    Approved,
+    /// The attempt was refused with this `ProxyError` code and recorded in
+    /// the upgrade history as a failed transaction
+    Rejected(u32),
+}
+
+impl UpgradeOutcome {
+    /// The outcome of an attempt `error` refused
+    pub fn rejected(error: ProxyError) -> Self {
+        UpgradeOutcome::Rejected(error as u32)
+    }
+}
+
+/// Lifecycle of a scheduled proxy upgrade
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum ScheduleStatus {
+    Pending = 0,
+    Executed = 1,
+    Cancelled = 2,
+}
+
+/// An implementation switch that can only happen after the proxy's delay
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ScheduledUpgrade {
+    /// ID of the schedule entry
+    pub id: u64,
+    /// Address of the new implementation
+    pub new_implementation: Address,
+    /// Address that scheduled the upgrade
+    pub initiator: Address,
+    /// Timestamp when the upgrade was scheduled
+    pub scheduled_at: u64,
+    /// Earliest timestamp at which the upgrade may execute
+    pub executable_at: u64,
+    pub status: ScheduleStatus,
+}
+
+/// Lifecycle of an implementation handover
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum HandoverStatus {
+    Pending = 0,
+    Confirmed = 1,
+    Aborted = 2,
+    TimedOut = 3,
+}
+
+/// A switch to an implementation that must acknowledge it is set up first
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Handover {
+    /// ID of the handover
+    pub id: u64,
+    /// Address of the new implementation
+    pub new_implementation: Address,
+    /// Address that began the handover
+    pub initiator: Address,
+    /// Timestamp when the handover began
+    pub started_at: u64,
+    /// Timestamp after which the handover can no longer be confirmed
+    pub expires_at: u64,
+    pub status: HandoverStatus,
+}
+
+/// Error codes for the proxy module
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum ProxyError {
+    /// Caller is not the proxy admin
+    NotAdmin = 400,
+    /// Caller is neither the admin nor the schedule initiator
+    NotAdminOrInitiator = 401,
+    /// Scheduled upgrade does not exist
+    ScheduleNotFound = 402,
+    /// Scheduled upgrade is not pending
+    ScheduleNotPending = 403,
+    /// Upgrade delay has not elapsed
+    DelayNotElapsed = 404,
+    /// A handover is already pending
+    HandoverPending = 405,
+    /// Handover has timed out
+    HandoverExpired = 406,
+    /// Implementation rejected the handover
+    HandoverRejected = 407,
+    /// No handover is pending
+    NoHandover = 408,
+    /// Caller is neither the admin nor the handover's new implementation
+    NotAdminOrImplementation = 409,
+    /// Proxy upgrades are paused
+    Paused = 410,
+    /// Entrypoint was re-entered while an execute path was running
+    ReentrantCall = 411,
+    /// Proxy upgrades are not paused
+    NotPaused = 412,
+    /// No admin transfer is pending
+    NoPendingAdmin = 413,
+    /// Caller is not the pending admin
+    NotPendingAdmin = 414,
+    /// The new implementation is the one already installed
+    SameImplementation = 415,
+    /// The proxy was already initialized
+    AlreadyInitialized = 416,
+    /// Immediate upgrades are disabled; the upgrade must be scheduled
+    DelayRequired = 417,
+    /// The target implementation is not on the allowlist
+    ImplementationNotApproved = 418,
+    /// Caller may not approve or revoke implementations
+    NotApprover = 419,
+    /// Two-party upgrades are on and the caller approved the target itself
+    SameApprover = 420,
+    /// The new implementation does not report the expected version
+    ImplementationVersionMismatch = 421,
+    /// The minimum interval since the last upgrade has not passed
+    CooldownActive = 422,
+    /// Upgrades were permanently frozen
+    Frozen = 423,
+    /// The freeze confirmation did not name the current implementation
+    FreezeNotConfirmed = 424,
+    /// No successful upgrade has been recorded to revert
+    NothingToRevert = 425,
+    /// The address is already a proxy admin
+    AlreadyAdmin = 426,
+    /// The address is not a proxy admin
+    UnknownAdmin = 427,
+    /// Removing the admin would leave fewer admins than the threshold
+    BelowThreshold = 428,
+    /// The threshold must be between one and the number of admins
+    InvalidThreshold = 429,
+    /// The admin already approved this action
+    AlreadyApproved = 430,
+    /// No recorded version runs the implementation a revert would restore
+    RevertVersionUnknown = 431,
+}
+
+impl ProxyError {
+    /// Every variant, in code order
+    pub const ALL: [ProxyError; 32] = [
+        ProxyError::NotAdmin,
+        ProxyError::NotAdminOrInitiator,
+        ProxyError::ScheduleNotFound,
+        ProxyError::ScheduleNotPending,
+        ProxyError::DelayNotElapsed,
+        ProxyError::HandoverPending,
+        ProxyError::HandoverExpired,
+        ProxyError::HandoverRejected,
+        ProxyError::NoHandover,
+        ProxyError::NotAdminOrImplementation,
+        ProxyError::Paused,
+        ProxyError::ReentrantCall,
+        ProxyError::NotPaused,
+        ProxyError::NoPendingAdmin,
+        ProxyError::NotPendingAdmin,
+        ProxyError::SameImplementation,
+        ProxyError::AlreadyInitialized,
+        ProxyError::DelayRequired,
+        ProxyError::ImplementationNotApproved,
+        ProxyError::NotApprover,
+        ProxyError::SameApprover,
+        ProxyError::ImplementationVersionMismatch,
+        ProxyError::CooldownActive,
+        ProxyError::Frozen,
+        ProxyError::FreezeNotConfirmed,
+        ProxyError::NothingToRevert,
+        ProxyError::AlreadyAdmin,
+        ProxyError::UnknownAdmin,
+        ProxyError::BelowThreshold,
+        ProxyError::InvalidThreshold,
+        ProxyError::AlreadyApproved,
+        ProxyError::RevertVersionUnknown,
+    ];
+
+    /// Stable name used in the on-chain error catalog
+    pub fn name(&self) -> &'static str {
+        match self {
+            ProxyError::NotAdmin => "NotAdmin",
+            ProxyError::NotAdminOrInitiator => "NotAdminOrInitiator",
+            ProxyError::ScheduleNotFound => "ScheduleNotFound",
+            ProxyError::ScheduleNotPending => "ScheduleNotPending",
+            ProxyError::DelayNotElapsed => "DelayNotElapsed",
+            ProxyError::HandoverPending => "HandoverPending",
+            ProxyError::HandoverExpired => "HandoverExpired",
+            ProxyError::HandoverRejected => "HandoverRejected",
+            ProxyError::NoHandover => "NoHandover",
+            ProxyError::NotAdminOrImplementation => "NotAdminOrImplementation",
+            ProxyError::Paused => "Paused",
+            ProxyError::ReentrantCall => "ReentrantCall",
+            ProxyError::NotPaused => "NotPaused",
+            ProxyError::NoPendingAdmin => "NoPendingAdmin",
+            ProxyError::NotPendingAdmin => "NotPendingAdmin",
+            ProxyError::SameImplementation => "SameImplementation",
+            ProxyError::AlreadyInitialized => "AlreadyInitialized",
+            ProxyError::DelayRequired => "DelayRequired",
+            ProxyError::ImplementationNotApproved => "ImplementationNotApproved",
+            ProxyError::NotApprover => "NotApprover",
+            ProxyError::SameApprover => "SameApprover",
+            ProxyError::ImplementationVersionMismatch => "ImplementationVersionMismatch",
+            ProxyError::CooldownActive => "CooldownActive",
+            ProxyError::Frozen => "Frozen",
+            ProxyError::FreezeNotConfirmed => "FreezeNotConfirmed",
+            ProxyError::NothingToRevert => "NothingToRevert",
+            ProxyError::AlreadyAdmin => "AlreadyAdmin",
+            ProxyError::UnknownAdmin => "UnknownAdmin",
+            ProxyError::BelowThreshold => "BelowThreshold",
+            ProxyError::InvalidThreshold => "InvalidThreshold",
+            ProxyError::AlreadyApproved => "AlreadyApproved",
+            ProxyError::RevertVersionUnknown => "RevertVersionUnknown",
+        }
+    }
 }
@@ -1,5 +1,6 @@
 use crate::events::emit::emit_event;
 use crate::events::topics::{ACT_BADGE_EARNED, ACT_UPDATED, MOD_REPUTATION};
+use crate::utils::time;
 use soroban_sdk::{Address, Env, String};
 
 use crate::guild::types::Role;
@@ -27,7 +28,7 @@ pub fn record_contribution(
     reference_id: u64,
 ) {
     let points = points_for_contribution(&contribution_type);
-    let now = env.ledger().timestamp();
+    let now = time::now(env);
 
     // Store the contribution record
     let contrib_id = get_next_contribution_id(env);
@@ -111,7 +112,7 @@ pub fn get_decayed_profile(
     guild_id: u64,
 ) -> Option<ReputationProfile> {
     let mut profile = get_profile(env, address, guild_id)?;
-    let now = env.ledger().timestamp();
+    let now = time::now(env);
     apply_decay_to_profile(&mut profile, now);
     Some(profile)
 }
@@ -134,7 +135,7 @@ pub fn compute_governance_weight(env: &Env, address: &Address, guild_id: u64, ro
 /// Get the global (cross-guild) reputation for a user.
 pub fn get_global_reputation(env: &Env, address: &Address) -> u64 {
     let profiles = crate::reputation::storage::get_all_guild_profiles(env, address);
-    let now = env.ledger().timestamp();
+    let now = time::now(env);
 
     let mut total: u64 = 0;
     for mut profile in profiles.iter() {
@@ -153,7 +154,7 @@ fn check_and_award_badges(
     contributor: &Address,
     profile: &ReputationProfile,
 ) {
-    let now = env.ledger().timestamp();
+    let now = time::now(env);
 
     // FirstContribution â€” contributions_count >= 1
     if profile.contributions_count >= 1 {
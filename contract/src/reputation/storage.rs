@@ -1,15 +1,10 @@
-﻿use soroban_sdk::{symbol_short, Address, Env, Map, Symbol, Vec};
+﻿use crate::keys::reputation::{
+    BADGES_KEY, BADGE_CNT, BADGE_IDX, CONTRIBS_KEY, CONTRIB_CNT, CONTRIB_IDX, PROFILES_KEY,
+};
+use soroban_sdk::{Address, Env, Map, Vec};
 
 use crate::reputation::types::{Badge, ContributionRecord, ReputationProfile};
 
-const PROFILES_KEY: Symbol = symbol_short!("r_prof");
-const CONTRIBS_KEY: Symbol = symbol_short!("r_cont");
-const CONTRIB_IDX: Symbol = symbol_short!("r_cidx");
-const BADGES_KEY: Symbol = symbol_short!("r_badge");
-const BADGE_IDX: Symbol = symbol_short!("r_bidx");
-const CONTRIB_CNT: Symbol = symbol_short!("r_ccnt");
-const BADGE_CNT: Symbol = symbol_short!("r_bcnt");
-
 // â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€ Reputation Profiles â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€
 
 /// Store or update a reputation profile keyed by (address, guild_id).
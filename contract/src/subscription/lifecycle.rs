@@ -16,6 +16,7 @@ use crate::subscription::types::{
     SubscriptionCancelledEvent, SubscriptionChange, SubscriptionCreatedEvent, SubscriptionError,
     SubscriptionPlan, SubscriptionStatus, TierChangedEvent,
 };
+use crate::utils::time;
 use soroban_sdk::{token, Address, Env, String, Vec};
 
 /// Create a new subscription plan
@@ -65,7 +66,7 @@ pub fn create_plan(
         is_active: true,
         benefits,
         created_by: created_by.clone(),
-        created_at: env.ledger().timestamp(),
+        created_at: time::now(env),
     };
 
     store_plan(env, &plan);
@@ -121,7 +122,7 @@ pub fn subscribe(
     }
 
     let subscription_id = get_next_subscription_id(env);
-    let now = env.ledger().timestamp();
+    let now = time::now(env);
     let cycle_duration = plan.billing_cycle.duration_seconds();
 
     let subscription = Subscription {
@@ -187,7 +188,7 @@ pub fn process_payment(
 
     let payment_result = execute_payment(env, &subscription.subscriber, plan.price, &plan.token);
 
-    let now = env.ledger().timestamp();
+    let now = time::now(env);
 
     match payment_result {
         Ok(()) => {
@@ -313,7 +314,7 @@ fn record_revenue(
     retry_attempt: u32,
 ) -> u64 {
     let record_id = get_next_revenue_record_id(env);
-    let now = env.ledger().timestamp();
+    let now = time::now(env);
 
     // Calculate period start (beginning of the cycle this payment covers)
     let cycle_duration = billing_cycle.duration_seconds();
@@ -415,7 +416,7 @@ pub fn resume_subscription(
     subscription.status = SubscriptionStatus::Active;
 
     // Adjust next billing date to account for pause period
-    let now = env.ledger().timestamp();
+    let now = time::now(env);
     let cycle_duration = plan.billing_cycle.duration_seconds();
     subscription.next_billing_at = now + cycle_duration;
 
@@ -455,7 +456,7 @@ pub fn cancel_subscription(
         return Err(SubscriptionError::AlreadyCancelled);
     }
 
-    let now = env.ledger().timestamp();
+    let now = time::now(env);
     subscription.status = SubscriptionStatus::Cancelled;
     subscription.cancelled_at = Some(now);
     subscription.cancellation_reason = reason.clone();
@@ -524,7 +525,7 @@ pub fn change_tier(
 
     let proration = if change.effective_immediately {
         // Calculate proration
-        let now = env.ledger().timestamp();
+        let now = time::now(env);
         let cycle_duration = current_plan.billing_cycle.duration_seconds();
         let time_remaining = subscription.next_billing_at.saturating_sub(now);
 
@@ -607,7 +608,7 @@ pub fn process_due_subscriptions(env: &Env, limit: u32) -> u32 {
     use crate::subscription::storage::get_active_subscriptions;
 
     let active_ids = get_active_subscriptions(env);
-    let now = env.ledger().timestamp();
+    let now = time::now(env);
     let mut processed = 0u32;
 
     for sub_id in active_ids.iter() {
@@ -693,7 +694,7 @@ pub fn is_subscription_active(env: &Env, subscription_id: u64) -> bool {
 /// Days until next billing (0 if past due)
 pub fn days_until_billing(env: &Env, subscription_id: u64) -> u64 {
     if let Some(subscription) = get_subscription(env, subscription_id) {
-        let now = env.ledger().timestamp();
+        let now = time::now(env);
         if subscription.next_billing_at > now {
             (subscription.next_billing_at - now) / (24 * 60 * 60)
         } else {
@@ -3,6 +3,7 @@ use crate::events::topics::{
     ACT_APPROVED, ACT_CREATED, ACT_EXECUTED, ACT_FUNDED, ACT_GRANTED, ACT_PAUSED, ACT_PROPOSED,
     ACT_RESUMED, ACT_UPDATED, MOD_TREASURY,
 };
+use crate::utils::time;
 use soroban_sdk::{token::Client as TokenClient, Address, Env, String, Vec};
 
 use crate::analytics::storage::store_snapshot;
@@ -110,7 +111,7 @@ pub fn deposit(
     store_treasury(env, &treasury);
 
     let tx_id = get_next_tx_id(env);
-    let now = env.ledger().timestamp();
+    let now = time::now(env);
     let tx = Transaction {
         id: tx_id,
         treasury_id,
@@ -162,7 +163,7 @@ pub fn propose_withdrawal(
     assert_signer(env, &treasury, &proposer);
 
     let tx_id = get_next_tx_id(env);
-    let now = env.ledger().timestamp();
+    let now = time::now(env);
     let mut approvals = Vec::new(env);
     approvals.push_back(proposer.clone());
 
@@ -201,7 +202,7 @@ pub fn approve_transaction(env: &Env, tx_id: u64, approver: Address) -> bool {
     let mut tx = crate::treasury::storage::get_transaction(env, tx_id).expect("tx not found");
     let treasury = get_treasury(env, tx.treasury_id).expect("treasury not found");
 
-    let now = env.ledger().timestamp();
+    let now = time::now(env);
     expire_if_needed(&mut tx, now);
     if matches!(
         tx.status,
@@ -239,7 +240,7 @@ fn enforce_budget(
     if amount <= 0 {
         return Ok(());
     }
-    let now = env.ledger().timestamp();
+    let now = time::now(env);
     let mut budget = get_budget(env, treasury_id, category).unwrap_or(Budget {
         treasury_id,
         category: category.clone(),
@@ -304,7 +305,7 @@ pub fn execute_transaction(env: &Env, tx_id: u64, executor: Address) -> bool {
     let mut tx = crate::treasury::storage::get_transaction(env, tx_id).expect("tx not found");
     let mut treasury = get_treasury(env, tx.treasury_id).expect("treasury not found");
 
-    let now = env.ledger().timestamp();
+    let now = time::now(env);
     expire_if_needed(&mut tx, now);
     if matches!(
         tx.status,
@@ -481,7 +482,7 @@ pub fn execute_milestone_payment(
 
     // Record a MilestonePayment transaction as already executed
     let tx_id = get_next_tx_id(env);
-    let now = env.ledger().timestamp();
+    let now = time::now(env);
     let tx = Transaction {
         id: tx_id,
         treasury_id,
@@ -515,7 +516,7 @@ pub fn set_budget(
     let treasury = get_treasury(env, treasury_id).expect("treasury not found");
     assert_signer(env, &treasury, &caller);
 
-    let now = env.ledger().timestamp();
+    let now = time::now(env);
     let mut budget = get_budget(env, treasury_id, &category).unwrap_or(Budget {
         treasury_id,
         category: category.clone(),
@@ -597,7 +598,7 @@ pub fn grant_allowance(
         panic!("admin must be signer");
     }
 
-    let now = env.ledger().timestamp();
+    let now = time::now(env);
     let mut allowance = get_allowance(env, treasury_id, &admin, &token).unwrap_or(Allowance {
         treasury_id,
         admin: admin.clone(),
@@ -651,7 +652,7 @@ fn record_snapshot(env: &Env, treasury: &Treasury) {
     let index = get_snapshot_count(env, treasury.id);
     let snapshot = TreasurySnapshot {
         treasury_id: treasury.id,
-        timestamp: env.ledger().timestamp(),
+        timestamp: time::now(env),
         balance_xlm: treasury.balance_xlm,
         total_deposits: treasury.total_deposits,
         total_withdrawals: treasury.total_withdrawals,
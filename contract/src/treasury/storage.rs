@@ -1,17 +1,11 @@
-﻿use soroban_sdk::{symbol_short, Address, Env, Map, String, Symbol, Vec};
+﻿use crate::keys::treasury::{
+    ALLOWANCES_KEY, BUDGETS_KEY, TRANSACTIONS_KEY, TREASURIES_KEY, TREASURY_CNT_KEY,
+    TREASURY_TX_INDEX_KEY, TX_CNT_KEY,
+};
+use soroban_sdk::{Address, Env, Map, String, Vec};
 
 use crate::treasury::types::{Allowance, Budget, Transaction, Treasury};
 
-const TREASURY_CNT_KEY: Symbol = symbol_short!("t_cnt");
-const TREASURIES_KEY: Symbol = symbol_short!("trsries");
-
-const TX_CNT_KEY: Symbol = symbol_short!("tx_cnt");
-const TRANSACTIONS_KEY: Symbol = symbol_short!("txs");
-const TREASURY_TX_INDEX_KEY: Symbol = symbol_short!("t_tx_idx");
-
-const BUDGETS_KEY: Symbol = symbol_short!("budgets");
-const ALLOWANCES_KEY: Symbol = symbol_short!("allows");
-
 #[allow(dead_code)]
 pub fn initialize_treasury_storage(env: &Env) {
     let storage = env.storage().persistent();
@@ -1,4 +1,5 @@
-﻿use soroban_sdk::{contracterror, contracttype, Address, Env, Map, String, Vec};
+﻿use crate::utils::time;
+use soroban_sdk::{contracterror, contracttype, Address, Env, Map, String, Vec};
 
 /// Error types for treasury operations
 #[contracterror]
@@ -164,7 +165,7 @@ impl Treasury {
 
 impl Allowance {
     pub fn ensure_period_current(&mut self, env: &Env) {
-        let now = env.ledger().timestamp();
+        let now = time::now(env);
         if now >= self.period_start.saturating_add(self.period_seconds) {
             self.period_start = now;
             self.remaining_amount = self.amount_per_period;
@@ -0,0 +1,200 @@
+use crate::emergency::storage::log_emergency_action;
+use crate::events::emit::emit_event;
+use crate::events::topics::{
+    ACT_DUAL_CONTROL_ATTEMPT, ACT_DUAL_CONTROL_REJECTED, ACT_GRANTED, ACT_GUARDIAN_SET,
+    ACT_POLICY_SET, ACT_REVOKED, MOD_UPGRADE,
+};
+use crate::upgrade::storage;
+use crate::upgrade::types::{Action, ControlChange, Role, UpgradeError};
+use crate::utils::time;
+use soroban_sdk::{Address, Env, String, Vec};
+
+/// Check that `caller` holds a role permitted for `action`.
+/// Protected actions are governance-only regardless of the stored matrix.
+/// Callers are expected to have already required the caller's auth.
+pub fn authorize(env: &Env, caller: &Address, action: Action) -> Result<(), UpgradeError> {
+    if action.is_protected() {
+        return if has_role(env, caller, Role::Governance) {
+            Ok(())
+        } else {
+            Err(UpgradeError::NotAuthorized)
+        };
+    }
+
+    for role in storage::get_action_roles(env, action).iter() {
+        if has_role(env, caller, role) {
+            return Ok(());
+        }
+    }
+    Err(UpgradeError::NotAuthorized)
+}
+
+/// Whether `address` holds `role`; governance is always the current governance address
+pub fn has_role(env: &Env, address: &Address, role: Role) -> bool {
+    match role {
+        Role::Governance => *address == storage::get_governance_address(env),
+        _ => storage::get_role_members(env, role).contains(address),
+    }
+}
+
+/// Change which roles may perform a non-protected action
+pub fn set_action_roles(
+    env: &Env,
+    caller: &Address,
+    action: Action,
+    roles: &Vec<Role>,
+) -> Result<(), UpgradeError> {
+    caller.require_auth();
+    authorize(env, caller, Action::SetAuthMatrix)?;
+
+    if action.is_protected() {
+        return Err(UpgradeError::ProtectedAction);
+    }
+    if roles.is_empty() {
+        return Err(UpgradeError::EmptyRoleSet);
+    }
+
+    storage::set_action_roles(env, action, roles);
+
+    emit_event(env, MOD_UPGRADE, ACT_POLICY_SET, (action, roles.clone()));
+
+    Ok(())
+}
+
+/// Grant a role to an address. The governance role cannot be granted.
+pub fn grant_role(
+    env: &Env,
+    caller: &Address,
+    role: Role,
+    member: &Address,
+) -> Result<(), UpgradeError> {
+    caller.require_auth();
+    authorize(env, caller, Action::ManageRoles)?;
+
+    if role == Role::Governance {
+        return Err(UpgradeError::ProtectedAction);
+    }
+
+    let mut members = storage::get_role_members(env, role);
+    if !members.contains(member) {
+        if role == Role::Guardian {
+            enforce_dual_control(env, caller, ControlChange::Guardian, false)?;
+        }
+        members.push_back(member.clone());
+        storage::set_role_members(env, role, &members);
+    }
+
+    emit_event(env, MOD_UPGRADE, ACT_GRANTED, (role, member.clone()));
+
+    Ok(())
+}
+
+/// Revoke a role from an address
+pub fn revoke_role(
+    env: &Env,
+    caller: &Address,
+    role: Role,
+    member: &Address,
+) -> Result<(), UpgradeError> {
+    caller.require_auth();
+    authorize(env, caller, Action::ManageRoles)?;
+
+    if role == Role::Governance {
+        return Err(UpgradeError::ProtectedAction);
+    }
+
+    let mut members = storage::get_role_members(env, role);
+    if let Some(pos) = members.first_index_of(member) {
+        if role == Role::Guardian {
+            enforce_dual_control(env, caller, ControlChange::Guardian, false)?;
+        }
+        members.remove(pos);
+        storage::set_role_members(env, role, &members);
+    }
+
+    emit_event(env, MOD_UPGRADE, ACT_REVOKED, (role, member.clone()));
+
+    Ok(())
+}
+
+/// Make `guardian` the only holder of the guardian role, or clear the role
+/// entirely with `None`
+pub fn set_guardian(
+    env: &Env,
+    caller: &Address,
+    guardian: Option<Address>,
+) -> Result<(), UpgradeError> {
+    caller.require_auth();
+    authorize(env, caller, Action::ManageRoles)?;
+
+    let members = match &guardian {
+        Some(guardian) => Vec::from_array(env, [guardian.clone()]),
+        None => Vec::new(env),
+    };
+    if storage::get_role_members(env, Role::Guardian) != members {
+        enforce_dual_control(env, caller, ControlChange::Guardian, false)?;
+        storage::set_role_members(env, Role::Guardian, &members);
+    }
+
+    emit_event(
+        env,
+        MOD_UPGRADE,
+        ACT_GUARDIAN_SET,
+        (caller.clone(), guardian),
+    );
+
+    Ok(())
+}
+
+/// The guardian, if the role is held; the first holder when `grant_role`
+/// added several
+pub fn get_guardian(env: &Env) -> Option<Address> {
+    storage::get_role_members(env, Role::Guardian).get(0)
+}
+
+/// Record a governance or guardian change, refusing it while the other kind
+/// changed less than the dual-control window ago. Only the voter recovery
+/// flow passes `escape_hatch` to bypass the window.
+pub fn enforce_dual_control(
+    env: &Env,
+    actor: &Address,
+    change: ControlChange,
+    escape_hatch: bool,
+) -> Result<(), UpgradeError> {
+    let now = time::now(env);
+
+    log_emergency_action(
+        env,
+        String::from_str(env, "DualControlAttempt"),
+        actor.clone(),
+        String::from_str(env, "Governance or guardian change attempted"),
+    );
+    emit_event(
+        env,
+        MOD_UPGRADE,
+        ACT_DUAL_CONTROL_ATTEMPT,
+        (change, actor.clone(), escape_hatch),
+    );
+
+    if let Some(last) = storage::get_last_control_change(env, change.counterpart()) {
+        let window = storage::get_dual_control_window(env);
+        if !escape_hatch && now < last.saturating_add(window) {
+            log_emergency_action(
+                env,
+                String::from_str(env, "DualControlRejected"),
+                actor.clone(),
+                String::from_str(env, "Change refused inside the dual-control window"),
+            );
+            emit_event(
+                env,
+                MOD_UPGRADE,
+                ACT_DUAL_CONTROL_REJECTED,
+                (change, actor.clone(), last),
+            );
+            return Err(UpgradeError::DualControlCooldown);
+        }
+    }
+
+    storage::set_last_control_change(env, change, now);
+    Ok(())
+}
@@ -0,0 +1,88 @@
+//! Cross-contract calls made by the upgrade system.
+//!
+//! Hooks and notification handlers return a `CallOutcome`; migration
+//! selectors return a `MigrationVerdict`. Every call goes through `invoke`,
+//! which reports a failure as a `CallFailure` naming the callee and emits
+//! `(callee, function, error code, callee code)` as a `call_failed` event:
+//!
+//! - `CallTrapped` when the callee trapped or returned a contract error
+//! - `MalformedCallReturn` when it returned a value of another type
+//! - `CallRejected` when it returned a failed `CallOutcome`
+
+use crate::events::emit::emit_event;
+use crate::events::topics::{ACT_CALL_FAILED, MOD_UPGRADE};
+use crate::upgrade::types::{CallFailure, CallOutcome, ProposalError};
+use soroban_sdk::{Address, Env, InvokeError, Symbol, TryFromVal, Val, Vec};
+
+/// Call `function` on `callee` and decode its return value as `T`
+pub fn invoke<T>(
+    env: &Env,
+    callee: &Address,
+    function: &Symbol,
+    args: Vec<Val>,
+) -> Result<T, CallFailure>
+where
+    T: TryFromVal<Env, Val>,
+{
+    match env.try_invoke_contract::<T, InvokeError>(callee, function, args) {
+        Ok(Ok(value)) => Ok(value),
+        Ok(Err(_)) => Err(fail(
+            env,
+            callee,
+            function,
+            ProposalError::MalformedCallReturn,
+            0,
+        )),
+        Err(Ok(InvokeError::Contract(code))) => Err(fail(
+            env,
+            callee,
+            function,
+            ProposalError::CallTrapped,
+            code,
+        )),
+        Err(_) => Err(fail(env, callee, function, ProposalError::CallTrapped, 0)),
+    }
+}
+
+/// Call a hook that returns a `CallOutcome`; a failed outcome is an error
+/// carrying the callee's code
+pub fn invoke_outcome(
+    env: &Env,
+    callee: &Address,
+    function: &Symbol,
+    args: Vec<Val>,
+) -> Result<CallOutcome, CallFailure> {
+    let outcome: CallOutcome = invoke(env, callee, function, args)?;
+    if !outcome.success {
+        return Err(fail(
+            env,
+            callee,
+            function,
+            ProposalError::CallRejected,
+            outcome.code,
+        ));
+    }
+    Ok(outcome)
+}
+
+fn fail(
+    env: &Env,
+    callee: &Address,
+    function: &Symbol,
+    error: ProposalError,
+    code: u32,
+) -> CallFailure {
+    let failure = CallFailure {
+        callee: callee.clone(),
+        function: function.clone(),
+        error,
+        code,
+    };
+    emit_event(
+        env,
+        MOD_UPGRADE,
+        ACT_CALL_FAILED,
+        (callee.clone(), function.clone(), error as u32, code),
+    );
+    failure
+}
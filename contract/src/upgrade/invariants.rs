@@ -0,0 +1,139 @@
+use crate::proxy::storage as proxy_storage;
+use crate::upgrade::storage;
+use crate::upgrade::types::{
+    InvariantReport, InvariantScope, InvariantViolation, ProposalIdMode, UpgradeStatus, VoteChoice,
+};
+use soroban_sdk::{Env, Vec};
+
+/// Most items a single `verify_invariants` call examines
+pub const MAX_INVARIANT_WORK: u32 = 50;
+
+/// Check one group of internal invariants, starting at `cursor` and
+/// examining at most `max_work` items (capped at `MAX_INVARIANT_WORK`).
+/// Anyone may call this; violations are reported, never panicked on, and
+/// nothing is written. Walk a scope by passing `next_cursor` back until
+/// the report is `complete`.
+pub fn verify_invariants(
+    env: &Env,
+    scope: InvariantScope,
+    cursor: u32,
+    max_work: u32,
+) -> InvariantReport {
+    let budget = max_work.min(MAX_INVARIANT_WORK);
+    let mut violations = Vec::new(env);
+
+    let total = match scope {
+        InvariantScope::PendingIndex => {
+            let ids = storage::get_status_index(env, UpgradeStatus::Pending);
+            for id in batch(&ids, cursor, budget).iter() {
+                match storage::get_upgrade_proposal(env, id) {
+                    None => violations.push_back(InvariantViolation::IndexedProposalMissing(id)),
+                    Some(proposal) if proposal.status != UpgradeStatus::Pending => {
+                        violations.push_back(InvariantViolation::IndexedStatusMismatch(id))
+                    }
+                    Some(_) => {}
+                }
+            }
+            ids.len()
+        }
+        InvariantScope::Tallies => {
+            let ids = storage::get_proposal_ids(env);
+            for id in batch(&ids, cursor, budget).iter() {
+                if !tallies_match_receipts(env, id) {
+                    violations.push_back(InvariantViolation::TallyMismatch(id));
+                }
+            }
+            ids.len()
+        }
+        InvariantScope::Proxy => {
+            if cursor == 0 && budget > 0 && proxy_storage::PROXY_CONFIG.has(env) {
+                let version = proxy_storage::get_proxy_config(env).version;
+                let switches = (1..=proxy_storage::get_upgrade_count(env))
+                    .filter_map(|id| proxy_storage::get_upgrade_transaction(env, id))
+                    .filter(|transaction| transaction.success)
+                    .count() as u32;
+                if version != switches + 1 {
+                    violations.push_back(InvariantViolation::ProxyVersionMismatch(version));
+                }
+            }
+            1
+        }
+        InvariantScope::Counters => {
+            // Proposal ids first, then the upgrade history id the counter
+            // hands out next, which must not be recorded yet. Content hash
+            // ids are not counter-issued, so proposals are only checked
+            // while ids are sequential.
+            let mut proposal_ids = Vec::new(env);
+            if storage::get_proposal_id_mode(env) == ProposalIdMode::Sequential {
+                proposal_ids = storage::get_proposal_ids(env);
+            }
+            let next_proposal_id = storage::peek_next_proposal_id(env);
+            let next_upgrade_id = proxy_storage::peek_next_upgrade_id(env);
+
+            let split = proposal_ids.len();
+            let end = cursor.saturating_add(budget).min(split + 1);
+            for position in cursor..end {
+                if position < split {
+                    let id = proposal_ids.get(position).unwrap();
+                    if id >= next_proposal_id {
+                        violations.push_back(InvariantViolation::ProposalIdBeyondCounter(id));
+                    }
+                } else if proxy_storage::get_upgrade_transaction(env, next_upgrade_id).is_some() {
+                    violations
+                        .push_back(InvariantViolation::UpgradeIdBeyondCounter(next_upgrade_id));
+                }
+            }
+            split + 1
+        }
+        InvariantScope::Footprint => {
+            if cursor == 0 && budget > 0 {
+                let footprint = storage::get_storage_footprint(env);
+                let mut recount = storage::recount_footprint(env);
+                recount.writes = footprint.writes;
+                if footprint != recount {
+                    violations.push_back(InvariantViolation::FootprintDrift(recount));
+                }
+            }
+            1
+        }
+    };
+
+    let next_cursor = cursor.saturating_add(budget).min(total.max(cursor));
+    InvariantReport {
+        scope,
+        checked: next_cursor - cursor,
+        next_cursor,
+        complete: next_cursor >= total,
+        violations,
+    }
+}
+
+/// The ids from `cursor` up to `budget` of them
+fn batch(ids: &Vec<u64>, cursor: u32, budget: u32) -> Vec<u64> {
+    let start = cursor.min(ids.len());
+    let end = cursor.saturating_add(budget).min(ids.len());
+    ids.slice(start..end)
+}
+
+/// Whether a proposal's tallies equal the sum of its receipts
+fn tallies_match_receipts(env: &Env, proposal_id: u64) -> bool {
+    let proposal = match storage::get_upgrade_proposal(env, proposal_id) {
+        Some(proposal) => proposal,
+        None => return true,
+    };
+
+    let (mut votes_for, mut votes_against, mut votes_abstain) = (0u64, 0u64, 0u64);
+    for receipt in storage::get_vote_receipts(env, proposal_id).iter() {
+        match receipt.choice {
+            VoteChoice::For => votes_for += receipt.weight,
+            VoteChoice::Against => votes_against += receipt.weight,
+            VoteChoice::Abstain => votes_abstain += receipt.weight,
+        }
+    }
+    (votes_for, votes_against, votes_abstain)
+        == (
+            proposal.votes_for,
+            proposal.votes_against,
+            proposal.votes_abstain,
+        )
+}
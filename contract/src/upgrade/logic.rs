@@ -1,253 +1,4420 @@
-use crate::events::emit::emit_event;
+use crate::emergency::storage::log_emergency_action;
+use crate::events::emit::{
+    emit_event, emit_leveled_event, set_event_verbosity as store_event_verbosity,
+    set_version_topics,
+};
+use crate::events::history::{
+    append_history, get_history_digest, get_history_record, get_pruned_through, prune_record,
+};
 use crate::events::topics::{
-    ACT_APPROVED, ACT_COMPLETED, ACT_EMERGENCY_UPGRADE, ACT_REJECTED, ACT_STARTED, ACT_UPDATED,
-    ACT_UPGRADE_EXECUTED, ACT_UPGRADE_PROPOSED, MOD_UPGRADE,
+    ACT_ACTION_EXECUTED, ACT_ACTION_PROPOSED, ACT_AMENDED, ACT_APPROVED, ACT_ARCHIVED,
+    ACT_ARCHIVE_DEFERRED, ACT_AWAITING_PROXY, ACT_BOND_POSTED, ACT_BOND_REFUNDED, ACT_BOND_SLASHED,
+    ACT_COMPENSATED, ACT_COMPENSATION_DISMISSED, ACT_COMPENSATION_GAVE_UP, ACT_COMPENSATION_QUEUED,
+    ACT_COMPLETED, ACT_DELEGATED, ACT_DESCRIPTION_REVEALED, ACT_DUAL_CONTROL_WINDOW_SET,
+    ACT_EMERGENCY_MAX_BUMP, ACT_EMERGENCY_ONE_SHOT, ACT_EMERGENCY_OVERRIDE, ACT_EMERGENCY_UPGRADE,
+    ACT_EMERGENCY_WINDOW, ACT_EXECUTION_DELAY_SET, ACT_EXECUTION_WINDOW_SET, ACT_EXPIRED,
+    ACT_FAILED, ACT_FEATURE_SET, ACT_FINALIZED, ACT_GENESIS_PAGE, ACT_GENESIS_SEALED,
+    ACT_GOVERNANCE_CHANGED, ACT_GOVERNANCE_NOMINATED, ACT_GOVERNANCE_PAUSED,
+    ACT_GOVERNANCE_RESUMED, ACT_GOVERNANCE_TRANSFER_CANCELLED, ACT_HEARTBEAT, ACT_HISTORY_PRUNED,
+    ACT_INDICES_REPAIRED, ACT_INSTANT_REVERTED, ACT_MAINTENANCE_RUN, ACT_MAX_POWER_SET,
+    ACT_MIGRATION_ABORTED, ACT_MIGRATION_DEFERRED, ACT_MIGRATION_STRANDED, ACT_NOTIFIED,
+    ACT_NOTIFY_DROPPED, ACT_NOTIFY_QUEUED, ACT_POST_CHECK_ACKNOWLEDGED, ACT_POST_CHECK_FAILED,
+    ACT_POWER_BATCH, ACT_POWER_CLAIMED, ACT_POWER_GRANTED, ACT_POWER_ROOT_SET,
+    ACT_PRIVATE_PROPOSED, ACT_PROPOSAL_CANCELLED, ACT_PROPOSAL_VETOED, ACT_RECOVERY_EXECUTED,
+    ACT_RECOVERY_INITIATED, ACT_RECOVERY_VETOED, ACT_RECOVERY_VOTED, ACT_REHEARSAL_DISCARDED,
+    ACT_REHEARSED, ACT_REJECTED, ACT_REVALIDATION_FAILED, ACT_ROLLED_BACK, ACT_SAFE_MODE_ENTERED,
+    ACT_SAFE_MODE_EXITED, ACT_STARTED, ACT_STATUS_CHANGED, ACT_STORAGE_MIGRATED,
+    ACT_SUBSCRIBER_ADDED, ACT_SUBSCRIBER_REMOVED, ACT_TEMPLATE_APPLIED, ACT_TEMPLATE_REMOVED,
+    ACT_TEMPLATE_SET, ACT_TRACK_SELECTED, ACT_UNDELEGATED, ACT_UPDATED, ACT_UPGRADE_CONFIRMED,
+    ACT_UPGRADE_EXECUTED, ACT_UPGRADE_PROPOSED, ACT_VERSION_DEPRECATED, ACT_VOTER_REGISTERED,
+    ACT_VOTER_REMOVED, ACT_VOTES_INVALIDATED, MOD_UPGRADE,
 };
+use crate::events::types::{EventVerbosity, HistoryKind, EVENT_SCHEMA_VERSION};
+use crate::keys::tags::{
+    ACTIVATION_DELAY_PARAM, APPROVAL_PARAM, CANCEL_REASON, DEADLINE_REASON, EXECUTE_REASON,
+    MIGRATION_ABORTED_REASON, PROXY_REASON, QUORUM_PARAM, REVERT_REASON, REVERT_WINDOW_PARAM,
+    SCHEMA_TAG, VETO_REASON, VOTE_REASON, WINDOW_REASON,
+};
+use crate::multisig::storage::get_operation;
+use crate::multisig::types::{OperationStatus, OperationType};
+use crate::proxy::implementation as proxy_impl;
+use crate::proxy::storage as proxy_storage;
+use crate::proxy::types::ScheduleStatus;
+use crate::upgrade::auth::{authorize, enforce_dual_control, has_role};
+use crate::upgrade::calls;
 use crate::upgrade::storage;
-use crate::upgrade::types::{MigrationPlan, UpgradeProposal, UpgradeStatus, Version};
-use soroban_sdk::{symbol_short, Address, Env, String};
+use crate::upgrade::types::{
+    approval_ratio_met, weight_bps, Action, AmendmentKind, AppliedTemplate, ArchiveRecord, Blocker,
+    BondConfig, BondStatus, BumpKind, CallFailure, Capabilities, CompensationKind,
+    CompensationStatus, ControlChange, EmergencyUpgradeRecord, ExecutionOutcome, ExecutionReceipt,
+    ExecutionRecord, FeatureFlag, GenesisProgress, GovernanceConfig, GovernanceMinimums,
+    GovernancePause, GovernanceShortfall, GovernanceTrack, GuardError, HealthReport, KeeperConfig,
+    MaintenanceReport, MaintenanceTask, MigrationNamespace, MigrationOutcome, MigrationPlan,
+    MigrationProgress, MigrationStatus, MigrationVerdict, NormalizedTally, NotificationResult,
+    OutcomeProjection, PendingCompensation, PendingNotification, PostCheck, PostCheckFailure,
+    PrivateDescription, ProposalBond, ProposalDetail, ProposalError, ProposalFinalized,
+    ProposalIdMode, ProposalKind, ProposalPage, ProposalParams, ProposalResult, ProposalSummary,
+    ProposalTemplate, RateLimitConfig, RecoveryProposal, RecoveryStatus, RehearsalReport,
+    RehearsalSample, RevertWindow, SchemaMismatch, SlashRecord, StatusChange, TallyCheckpoint,
+    TallySnapshot, UpgradeError, UpgradeProposal, UpgradeStatus, Version, VoteChoice, VoteError,
+    VoteReceipt, VoteReceiptPage, VoterEntry, VoterPage, VotesInvalidated, VotingGrant,
+    BPS_DENOMINATOR, DEFAULT_TRACK_ID, GOVERNANCE_DIGEST_VERSION, MAX_DELEGATORS,
+    MAX_GENESIS_PAGE_LEN, MAX_INLINE_DESCRIPTION_LEN, MAX_POWER_BATCH_LEN, MAX_TEMPLATE_TAGS,
+    SIMPLE_MAJORITY_BPS, STORAGE_SCHEMA,
+};
+use crate::utils::errors::ERROR_CODE_VERSION;
+use crate::utils::merkle;
+use crate::utils::pagination::{self, Cursor, CursorDirection, PageScope};
+use crate::utils::time;
+use core::cmp::Ordering;
+use soroban_sdk::token::Client as TokenClient;
+use soroban_sdk::xdr::ToXdr;
+use soroban_sdk::{Address, Bytes, BytesN, Env, Error, IntoVal, String, Symbol, Val, Vec};
+
+/// Create a new upgrade proposal
+pub fn propose_upgrade(
+    env: &Env,
+    proposer: &Address,
+    new_contract_address: &Address,
+    target_version: &Version,
+    description: String,
+) -> Result<u64, Error> {
+    propose_upgrade_with_salt(
+        env,
+        proposer,
+        new_contract_address,
+        target_version,
+        description,
+        BytesN::from_array(env, &[0u8; 32]),
+    )
+}
+
+/// Create a new upgrade proposal, supplying the salt used for content hash ids
+pub fn propose_upgrade_with_salt(
+    env: &Env,
+    proposer: &Address,
+    new_contract_address: &Address,
+    target_version: &Version,
+    description: String,
+    salt: BytesN<32>,
+) -> Result<u64, Error> {
+    create_proposal(
+        env,
+        proposer,
+        &ProposalKind::Upgrade(new_contract_address.clone(), target_version.clone()),
+        description,
+        salt,
+        None,
+    )
+}
+
+/// Create a new upgrade proposal on a governance track. The track must permit
+/// the size of the bump from the current version; its rules are snapshotted
+/// on the proposal.
+pub fn propose_upgrade_on_track(
+    env: &Env,
+    proposer: &Address,
+    new_contract_address: &Address,
+    target_version: &Version,
+    description: String,
+    track_id: u32,
+) -> Result<u64, Error> {
+    let track = storage::get_governance_track(env, track_id).ok_or(ProposalError::TrackNotFound)?;
+
+    create_proposal(
+        env,
+        proposer,
+        &ProposalKind::Upgrade(new_contract_address.clone(), target_version.clone()),
+        description,
+        BytesN::from_array(env, &[0u8; 32]),
+        Some(track),
+    )
+}
+
+/// Create a governance proposal carrying `kind`. Any action is voted on,
+/// timelocked and executed like an upgrade; one that is not an upgrade names
+/// no implementation or version of its own.
+pub fn propose_action(
+    env: &Env,
+    proposer: &Address,
+    kind: &ProposalKind,
+    description: String,
+) -> Result<u64, Error> {
+    if let ProposalKind::SetParameter(key, value) = kind {
+        with_parameter(&storage::get_governance_config(env), key, *value)?;
+    }
+
+    create_proposal(
+        env,
+        proposer,
+        kind,
+        description,
+        BytesN::from_array(env, &[0u8; 32]),
+        None,
+    )
+}
+
+/// `config` with the parameter named `key` set to `value`
+fn with_parameter(
+    config: &GovernanceConfig,
+    key: &Symbol,
+    value: u64,
+) -> Result<GovernanceConfig, Error> {
+    let mut config = config.clone();
+    let bps = u32::try_from(value).unwrap_or(u32::MAX);
+    if *key == QUORUM_PARAM {
+        if bps > BPS_DENOMINATOR {
+            return Err(UpgradeError::InvalidQuorum.into());
+        }
+        config.quorum_bps = bps;
+    } else if *key == APPROVAL_PARAM {
+        if bps > BPS_DENOMINATOR {
+            return Err(ProposalError::InvalidApprovalThreshold.into());
+        }
+        config.approval_threshold_bps = bps;
+    } else if *key == ACTIVATION_DELAY_PARAM {
+        config.activation_delay = value;
+    } else if *key == REVERT_WINDOW_PARAM {
+        config.revert_window = value;
+    } else {
+        return Err(GuardError::UnknownParameter.into());
+    }
+    Ok(config)
+}
+
+/// Create a proposal with the defaults of a registered template. The
+/// template's name, tags and execution delay are copied onto the proposal,
+/// so later changes to the template do not reach it.
+pub fn propose_from_template(
+    env: &Env,
+    proposer: &Address,
+    template: &Symbol,
+    new_contract_address: &Address,
+    target_version: &Version,
+    description: String,
+) -> Result<u64, Error> {
+    let template = storage::get_template(env, template).ok_or(ProposalError::TemplateNotFound)?;
+    let permitted = template.required_roles.is_empty()
+        || template
+            .required_roles
+            .iter()
+            .any(|role| has_role(env, proposer, role));
+    if !permitted {
+        return Err(UpgradeError::NotAuthorized.into());
+    }
+    let track = if template.default_track == DEFAULT_TRACK_ID {
+        None
+    } else {
+        Some(
+            storage::get_governance_track(env, template.default_track)
+                .ok_or(ProposalError::TrackNotFound)?,
+        )
+    };
+
+    let proposal_id = create_proposal(
+        env,
+        proposer,
+        &ProposalKind::Upgrade(new_contract_address.clone(), target_version.clone()),
+        description,
+        BytesN::from_array(env, &[0u8; 32]),
+        track,
+    )?;
+    let applied = AppliedTemplate {
+        template: template.name,
+        tags: template.default_tags,
+        execution_delay: template.default_execution_delay,
+    };
+    storage::set_applied_template(env, proposal_id, &applied);
+
+    emit_event(
+        env,
+        MOD_UPGRADE,
+        ACT_TEMPLATE_APPLIED,
+        (proposal_id, applied.template),
+    );
+
+    Ok(proposal_id)
+}
+
+/// Register a proposal template or replace the one with the same name.
+/// Proposals already created from it keep the defaults they received.
+pub fn set_proposal_template(
+    env: &Env,
+    caller: &Address,
+    template: &ProposalTemplate,
+) -> Result<(), Error> {
+    caller.require_auth();
+
+    authorize(env, caller, Action::SetGovernanceConfig)?;
+    if template.default_track != DEFAULT_TRACK_ID
+        && storage::get_governance_track(env, template.default_track).is_none()
+    {
+        return Err(ProposalError::TrackNotFound.into());
+    }
+    if template.default_tags.len() > MAX_TEMPLATE_TAGS {
+        return Err(ProposalError::TooManyTemplateTags.into());
+    }
+
+    storage::store_template(env, template);
+
+    emit_event(env, MOD_UPGRADE, ACT_TEMPLATE_SET, template.clone());
+
+    Ok(())
+}
+
+/// Delete a proposal template; proposals created from it are unaffected
+pub fn remove_proposal_template(env: &Env, caller: &Address, name: &Symbol) -> Result<(), Error> {
+    caller.require_auth();
+
+    authorize(env, caller, Action::SetGovernanceConfig)?;
+    if storage::get_template(env, name).is_none() {
+        return Err(ProposalError::TemplateNotFound.into());
+    }
+
+    storage::remove_template(env, name);
+
+    emit_event(env, MOD_UPGRADE, ACT_TEMPLATE_REMOVED, name.clone());
+
+    Ok(())
+}
+
+/// All registered proposal templates in registration order
+pub fn get_proposal_templates(env: &Env) -> Vec<ProposalTemplate> {
+    let mut templates = Vec::new(env);
+    for name in storage::get_template_names(env).iter() {
+        if let Some(template) = storage::get_template(env, &name) {
+            templates.push_back(template);
+        }
+    }
+    templates
+}
+
+/// Longest public placeholder a private proposal may carry
+pub const MAX_PLACEHOLDER_LEN: u32 = 64;
+/// Longest description that can be revealed for a private proposal
+pub const MAX_REVEALED_DESCRIPTION_LEN: u32 = 1_024;
+
+/// Create a proposal whose description stays private until revealed. Only
+/// `placeholder` is stored in the clear; `commitment` is the sha256 of the
+/// full description (see `description_commitment`). The proposer may reveal
+/// once the proposal executed or from `reveal_deadline` on.
+pub fn propose_private_upgrade(
+    env: &Env,
+    proposer: &Address,
+    new_contract_address: &Address,
+    target_version: &Version,
+    placeholder: String,
+    commitment: BytesN<32>,
+    reveal_deadline: u64,
+) -> Result<u64, Error> {
+    if placeholder.len() > MAX_PLACEHOLDER_LEN {
+        return Err(ProposalError::DescriptionTooLong.into());
+    }
+
+    let proposal_id = create_proposal(
+        env,
+        proposer,
+        &ProposalKind::Upgrade(new_contract_address.clone(), target_version.clone()),
+        placeholder,
+        BytesN::from_array(env, &[0u8; 32]),
+        None,
+    )?;
+    storage::store_private_description(
+        env,
+        proposal_id,
+        &PrivateDescription {
+            commitment: commitment.clone(),
+            reveal_deadline,
+            revealed: false,
+        },
+    );
+
+    emit_event(
+        env,
+        MOD_UPGRADE,
+        ACT_PRIVATE_PROPOSED,
+        (proposal_id, commitment),
+    );
+
+    Ok(proposal_id)
+}
+
+/// Set a proposal's description. One longer than `MAX_INLINE_DESCRIPTION_LEN`
+/// is spilled whole to its own entry and referenced by its hash (sha256 of
+/// its XDR); its first bytes, cut at a character boundary, stay inline as
+/// a summary. The caller stores the proposal.
+fn set_description(env: &Env, proposal: &mut UpgradeProposal, description: String) {
+    if description.len() <= MAX_INLINE_DESCRIPTION_LEN {
+        if proposal.description_spilled() {
+            storage::remove_description_blob(env, proposal.id);
+            proposal.description_hash = BytesN::from_array(env, &[0u8; 32]);
+        }
+        proposal.description = description;
+        return;
+    }
+
+    // A String's XDR is its ScVal tag and length (4 bytes each), then its bytes
+    let xdr = description.clone().to_xdr(env);
+    let mut summary = [0u8; MAX_INLINE_DESCRIPTION_LEN as usize + 1];
+    xdr.slice(8..8 + summary.len() as u32)
+        .copy_into_slice(&mut summary);
+    let mut cut = MAX_INLINE_DESCRIPTION_LEN as usize;
+    while cut > 0 && summary[cut] & 0xC0 == 0x80 {
+        cut -= 1;
+    }
+
+    storage::store_description_blob(env, proposal.id, &description);
+    proposal.description = String::from_bytes(env, &summary[..cut]);
+    proposal.description_hash = env.crypto().sha256(&xdr).into();
+}
+
+/// A proposal's full description, read back from its own entry when spilled
+pub fn full_description(env: &Env, proposal: &UpgradeProposal) -> String {
+    if proposal.description_spilled() {
+        storage::get_description_blob(env, proposal.id)
+            .unwrap_or_else(|| proposal.description.clone())
+    } else {
+        proposal.description.clone()
+    }
+}
+
+/// The commitment of a description: sha256 of its UTF-8 bytes
+pub fn description_commitment(env: &Env, description: &String) -> Result<BytesN<32>, Error> {
+    let len = description.len();
+    if len > MAX_REVEALED_DESCRIPTION_LEN {
+        return Err(ProposalError::DescriptionTooLong.into());
+    }
+    let mut buffer = [0u8; MAX_REVEALED_DESCRIPTION_LEN as usize];
+    description.copy_into_slice(&mut buffer[..len as usize]);
+    Ok(env
+        .crypto()
+        .sha256(&Bytes::from_slice(env, &buffer[..len as usize]))
+        .into())
+}
+
+/// Replace a private proposal's placeholder with the full description once
+/// it matches the commitment. Allowed after execution, from the reveal
+/// deadline on, or once approved when reveal is required before execution.
+pub fn reveal_description(
+    env: &Env,
+    proposer: &Address,
+    proposal_id: u64,
+    full_text: String,
+) -> Result<(), Error> {
+    proposer.require_auth();
+
+    let mut proposal = load_proposal(env, proposal_id)?;
+    if proposal.proposer != *proposer {
+        return Err(ProposalError::NotProposer.into());
+    }
+    let mut private = storage::get_private_description(env, proposal_id)
+        .ok_or(ProposalError::ProposalNotPrivate)?;
+    if private.revealed {
+        return Err(ProposalError::DescriptionAlreadyRevealed.into());
+    }
+
+    let revealable = proposal.status == UpgradeStatus::Executed
+        || time::now(env) >= private.reveal_deadline
+        || (proposal.status == UpgradeStatus::Approved && storage::is_reveal_required(env));
+    if !revealable {
+        return Err(ProposalError::RevealTooEarly.into());
+    }
+    if description_commitment(env, &full_text)? != private.commitment {
+        return Err(ProposalError::DescriptionMismatch.into());
+    }
+
+    set_description(env, &mut proposal, full_text.clone());
+    storage::store_upgrade_proposal(env, &proposal);
+    private.revealed = true;
+    storage::store_private_description(env, proposal_id, &private);
+
+    emit_event(
+        env,
+        MOD_UPGRADE,
+        ACT_DESCRIPTION_REVEALED,
+        (proposal_id, full_text),
+    );
+
+    Ok(())
+}
+
+/// Require private proposals to be revealed before they can execute
+pub fn set_reveal_required(env: &Env, caller: &Address, required: bool) -> Result<(), Error> {
+    caller.require_auth();
+
+    authorize(env, caller, Action::SetGovernanceConfig)?;
+
+    storage::set_reveal_required(env, required);
+
+    emit_event(env, MOD_UPGRADE, ACT_UPDATED, required);
+
+    Ok(())
+}
+
+/// Whether a private proposal still hides its description while reveal is
+/// required before execution
+fn reveal_outstanding(env: &Env, proposal_id: u64) -> bool {
+    storage::is_reveal_required(env)
+        && storage::get_private_description(env, proposal_id)
+            .map(|private| !private.revealed)
+            .unwrap_or(false)
+}
+
+/// Shared creation path; `None` decides the proposal under the
+/// governance-wide rules on the default track. A proposal of any other kind
+/// than an upgrade targets this contract at its current version, and skips
+/// the checks on the target.
+fn create_proposal(
+    env: &Env,
+    proposer: &Address,
+    kind: &ProposalKind,
+    description: String,
+    salt: BytesN<32>,
+    track: Option<GovernanceTrack>,
+) -> Result<u64, Error> {
+    proposer.require_auth();
+
+    let (new_contract_address, target_version, action) = match kind {
+        ProposalKind::Upgrade(new_contract_address, target_version) => {
+            (new_contract_address.clone(), target_version.clone(), None)
+        }
+        action => (
+            env.current_contract_address(),
+            storage::get_current_version(env),
+            Some(action),
+        ),
+    };
+
+    if storage::is_safe_mode(env) {
+        return Err(ProposalError::SafeMode.into());
+    }
+    if storage::is_governance_paused(env) {
+        return Err(UpgradeError::GovernancePaused.into());
+    }
+    if storage::is_genesis_pending(env) {
+        return Err(ProposalError::GenesisPending.into());
+    }
+
+    let track_id = track.as_ref().map_or(DEFAULT_TRACK_ID, |track| track.id);
+    if action.is_none() {
+        if let Some(blocker) = target_blockers(env, None, &target_version, track_id).first() {
+            return Err(target_error(&blocker));
+        }
+    }
+
+    // Enforce the per-proposer sliding window; governance is exempt
+    let now = time::now(env);
+    if next_proposal_allowed_at(env, proposer) > now {
+        return Err(UpgradeError::RateLimited.into());
+    }
+
+    let proposal_id = match storage::get_proposal_id_mode(env) {
+        ProposalIdMode::Sequential => storage::take_next_proposal_id(env),
+        ProposalIdMode::ContentHash => match action {
+            Some(kind) => compute_action_hash_id(env, proposer, kind, &salt),
+            None => compute_content_hash_id(
+                env,
+                proposer,
+                &new_contract_address,
+                &target_version,
+                &salt,
+            ),
+        },
+    };
+    if storage::has_upgrade_proposal(env, proposal_id) {
+        return Err(UpgradeError::ProposalIdCollision.into());
+    }
+
+    let rate_limit = storage::get_rate_limit_config(env);
+    if rate_limit.max_proposals_per_window > 0 && *proposer != storage::get_governance_address(env)
+    {
+        storage::push_proposer_timestamp(env, proposer, now, rate_limit.max_proposals_per_window);
+    }
+
+    let config = storage::get_governance_config(env);
+    let mut params = match &track {
+        Some(track) => track_params(&config, track, now),
+        None => config.to_params(),
+    };
+    if track.is_none() {
+        let period = storage::get_voting_period(env);
+        if period > 0 {
+            params.voting_ends_at = now.saturating_add(period);
+        }
+    }
+
+    // Snapshot the registry so later grants cannot move the threshold or any
+    // weight. A proposer barred from voting does not count towards quorum.
+    let excluded = params.separation_of_duties.then_some(proposer);
+    let (total_voters, total_voting_power_snapshot) =
+        storage::snapshot_voting_power(env, proposal_id, excluded);
+
+    let mut proposal = UpgradeProposal {
+        id: proposal_id,
+        proposer: proposer.clone(),
+        new_contract_address,
+        version: target_version,
+        description: description.clone(),
+        timestamp: now,
+        status: UpgradeStatus::Pending,
+        votes_for: 0,
+        votes_against: 0,
+        votes_abstain: 0,
+        total_voters,
+        total_voting_power_snapshot,
+        voting_deadline: params.voting_ends_at,
+        salt,
+        description_hash: BytesN::from_array(env, &[0u8; 32]),
+    };
+    set_description(env, &mut proposal, description);
+
+    storage::store_upgrade_proposal(env, &proposal);
+    storage::store_proposal_params(env, proposal_id, &params);
+    post_bond(env, proposer, proposal_id);
+
+    // Emit event for the proposal
+    emit_event(env, MOD_UPGRADE, ACT_UPGRADE_PROPOSED, proposal_id);
+    if let Some(kind) = action {
+        storage::set_proposal_kind(env, proposal_id, kind);
+        emit_event(
+            env,
+            MOD_UPGRADE,
+            ACT_ACTION_PROPOSED,
+            (proposal_id, kind.clone()),
+        );
+    }
+    if let Some(track) = track {
+        emit_event(
+            env,
+            MOD_UPGRADE,
+            ACT_TRACK_SELECTED,
+            (proposal_id, track.id, track.name),
+        );
+    }
+
+    Ok(proposal_id)
+}
+
+/// The rules a proposal created at `now` on `track` is decided under
+fn track_params(config: &GovernanceConfig, track: &GovernanceTrack, now: u64) -> ProposalParams {
+    ProposalParams {
+        quorum_bps: track.quorum_bps,
+        quorum_counts_abstain: config.quorum_counts_abstain,
+        early_finalize: config.early_finalize,
+        track_id: track.id,
+        threshold_bps: track.threshold_bps,
+        voting_ends_at: if track.voting_period > 0 {
+            now + track.voting_period
+        } else {
+            0
+        },
+        timelock: track.timelock,
+        separation_of_duties: track.separation_of_duties,
+    }
+}
+
+/// Define or replace a governance track. Track 0 stands for the
+/// governance-wide rules and cannot be redefined.
+pub fn set_governance_track(
+    env: &Env,
+    caller: &Address,
+    track: &GovernanceTrack,
+) -> Result<(), Error> {
+    caller.require_auth();
+
+    authorize(env, caller, Action::SetGovernanceConfig)?;
+    if track.id == DEFAULT_TRACK_ID {
+        return Err(ProposalError::InvalidTrack.into());
+    }
+    if track.threshold_bps < SIMPLE_MAJORITY_BPS || track.threshold_bps >= BPS_DENOMINATOR {
+        return Err(ProposalError::InvalidApprovalThreshold.into());
+    }
+    if track.quorum_bps > BPS_DENOMINATOR {
+        return Err(UpgradeError::InvalidQuorum.into());
+    }
+
+    storage::set_governance_track(env, track);
+
+    emit_event(env, MOD_UPGRADE, ACT_UPDATED, track.clone());
+
+    Ok(())
+}
+
+/// Compute the content hash proposal id: the first 8 bytes, big-endian, of
+/// sha256(xdr(proposer) || xdr(target) || major || minor || patch || salt),
+/// with each version component encoded as a big-endian u32.
+pub fn compute_content_hash_id(
+    env: &Env,
+    proposer: &Address,
+    new_contract_address: &Address,
+    version: &Version,
+    salt: &BytesN<32>,
+) -> u64 {
+    let mut preimage = Bytes::new(env);
+    preimage.append(&proposer.clone().to_xdr(env));
+    preimage.append(&new_contract_address.clone().to_xdr(env));
+    preimage.extend_from_array(&version.major.to_be_bytes());
+    preimage.extend_from_array(&version.minor.to_be_bytes());
+    preimage.extend_from_array(&version.patch.to_be_bytes());
+    preimage.append(&Bytes::from(salt.clone()));
+
+    let digest = env.crypto().sha256(&preimage).to_array();
+    let mut id_bytes = [0u8; 8];
+    id_bytes.copy_from_slice(&digest[..8]);
+    u64::from_be_bytes(id_bytes)
+}
+
+/// Compute the content hash id of a proposal carrying an action other than
+/// an upgrade: the first 8 bytes, big-endian, of
+/// sha256(xdr(proposer) || xdr(kind) || salt). Upgrades keep the id of
+/// `compute_content_hash_id`.
+pub fn compute_action_hash_id(
+    env: &Env,
+    proposer: &Address,
+    kind: &ProposalKind,
+    salt: &BytesN<32>,
+) -> u64 {
+    let mut preimage = Bytes::new(env);
+    preimage.append(&proposer.clone().to_xdr(env));
+    preimage.append(&kind.clone().to_xdr(env));
+    preimage.append(&Bytes::from(salt.clone()));
+
+    let digest = env.crypto().sha256(&preimage).to_array();
+    let mut id_bytes = [0u8; 8];
+    id_bytes.copy_from_slice(&digest[..8]);
+    u64::from_be_bytes(id_bytes)
+}
+
+/// Switch how new proposal ids are assigned
+pub fn set_proposal_id_mode(
+    env: &Env,
+    caller: &Address,
+    mode: ProposalIdMode,
+) -> Result<(), Error> {
+    caller.require_auth();
+
+    authorize(env, caller, Action::SetProposalIdMode)?;
+
+    storage::set_proposal_id_mode(env, mode);
+
+    emit_event(env, MOD_UPGRADE, ACT_UPDATED, mode);
+
+    Ok(())
+}
+
+/// Earliest timestamp at which `proposer` may create another proposal.
+/// Returns the current ledger time when the proposer is not rate limited.
+pub fn next_proposal_allowed_at(env: &Env, proposer: &Address) -> u64 {
+    let now = time::now(env);
+    let config = storage::get_rate_limit_config(env);
+    if config.max_proposals_per_window == 0 || *proposer == storage::get_governance_address(env) {
+        return now;
+    }
+
+    let window = storage::get_proposer_window(env, proposer);
+    let recent = window
+        .iter()
+        .filter(|ts| ts.saturating_add(config.window_seconds) > now)
+        .count() as u32;
+    if recent < config.max_proposals_per_window {
+        return now;
+    }
+
+    // The oldest timestamp still counting toward the limit has to age out first
+    let blocking = window
+        .get(window.len() - config.max_proposals_per_window)
+        .unwrap_or(now);
+    blocking.saturating_add(config.window_seconds)
+}
+
+/// Configure the per-proposer proposal rate limit
+pub fn set_rate_limit(
+    env: &Env,
+    caller: &Address,
+    max_proposals_per_window: u32,
+    window_seconds: u64,
+) -> Result<(), Error> {
+    caller.require_auth();
+
+    authorize(env, caller, Action::SetRateLimit)?;
+
+    if max_proposals_per_window > storage::MAX_RATE_LIMIT_SLOTS {
+        return Err(UpgradeError::InvalidRateLimit.into());
+    }
+    if max_proposals_per_window > 0 && window_seconds == 0 {
+        return Err(UpgradeError::InvalidRateLimit.into());
+    }
+
+    let config = RateLimitConfig {
+        max_proposals_per_window,
+        window_seconds,
+    };
+    storage::set_rate_limit_config(env, &config);
+
+    emit_event(env, MOD_UPGRADE, ACT_UPDATED, config);
+
+    Ok(())
+}
+
+/// Vote on an upgrade proposal
+pub fn vote_on_proposal(
+    env: &Env,
+    voter: &Address,
+    proposal_id: u64,
+    vote_for: bool,
+) -> Result<(), Error> {
+    let choice = if vote_for {
+        VoteChoice::For
+    } else {
+        VoteChoice::Against
+    };
+    cast_vote(env, voter, proposal_id, choice)
+}
+
+/// Whether separation of duties bars `actor` from acting on the proposal
+fn bound_by_separation(env: &Env, proposal_id: u64, actor: &Address) -> Result<bool, UpgradeError> {
+    let proposal =
+        storage::get_upgrade_proposal(env, proposal_id).ok_or(UpgradeError::ProposalNotFound)?;
+    Ok(
+        storage::get_proposal_params(env, proposal_id).separation_of_duties
+            && proposal.proposer == *actor,
+    )
+}
+
+/// Refuse a vote by the proposer of a proposal under separation of duties
+pub fn check_vote_separation(
+    env: &Env,
+    proposal_id: u64,
+    voter: &Address,
+) -> Result<(), UpgradeError> {
+    if bound_by_separation(env, proposal_id, voter)? {
+        return Err(UpgradeError::ProposerCannotVote);
+    }
+    Ok(())
+}
+
+/// Refuse execution by the proposer of a proposal under separation of duties
+pub fn check_execute_separation(
+    env: &Env,
+    proposal_id: u64,
+    executor: &Address,
+) -> Result<(), UpgradeError> {
+    if bound_by_separation(env, proposal_id, executor)? {
+        return Err(UpgradeError::ProposerCannotExecute);
+    }
+    Ok(())
+}
+
+/// Vote on an upgrade proposal with a three-way choice
+pub fn cast_vote(
+    env: &Env,
+    voter: &Address,
+    proposal_id: u64,
+    choice: VoteChoice,
+) -> Result<(), Error> {
+    voter.require_auth();
+
+    if storage::is_governance_paused(env) {
+        return Err(UpgradeError::GovernancePaused.into());
+    }
+    load_proposal(env, proposal_id)?;
+    check_vote_separation(env, proposal_id, voter)?;
+
+    // Record the vote
+    storage::record_vote(env, proposal_id, voter, choice)?;
+    settle_after_vote(env, proposal_id)
+}
+
+/// Switch an earlier vote to the other side while the proposal is still open.
+/// The weight moved is the one applied when the vote was cast.
+pub fn change_vote(
+    env: &Env,
+    voter: &Address,
+    proposal_id: u64,
+    new_vote_for: bool,
+) -> Result<(), Error> {
+    voter.require_auth();
+
+    if storage::is_governance_paused(env) {
+        return Err(UpgradeError::GovernancePaused.into());
+    }
+    load_proposal(env, proposal_id)?;
+
+    let choice = if new_vote_for {
+        VoteChoice::For
+    } else {
+        VoteChoice::Against
+    };
+    storage::change_recorded_vote(env, proposal_id, voter, choice)?;
+    settle_after_vote(env, proposal_id)
+}
+
+/// Approve or reject the proposal once the tallies decide it
+fn settle_after_vote(env: &Env, proposal_id: u64) -> Result<(), Error> {
+    let params = storage::get_proposal_params(env, proposal_id);
+
+    // Check if proposal has reached required threshold
+    if let Some(proposal) = storage::get_upgrade_proposal(env, proposal_id) {
+        let projected = project_outcome(&proposal, &params).projected_status;
+        let (status, early) = match projected {
+            UpgradeStatus::Pending if params.early_finalize => {
+                match decided_outcome(&proposal, &params) {
+                    Some(status) => (status, true),
+                    None => (UpgradeStatus::Pending, false),
+                }
+            }
+            status => (status, false),
+        };
+
+        match status {
+            UpgradeStatus::Approved => {
+                transition(env, proposal_id, UpgradeStatus::Approved, VOTE_REASON)?;
+                emit_event(env, MOD_UPGRADE, ACT_APPROVED, proposal_id);
+            }
+            UpgradeStatus::Rejected => {
+                transition(env, proposal_id, UpgradeStatus::Rejected, VOTE_REASON)?;
+                emit_event(env, MOD_UPGRADE, ACT_REJECTED, proposal_id);
+            }
+            _ => {}
+        }
+
+        if early {
+            emit_finalized(env, &proposal, params, status, true);
+        }
+    }
+
+    Ok(())
+}
+
+/// Delegate `delegator`'s upgrade votes to `delegate_to`. Delegations chain, so
+/// the power ends up with the first address in the chain that has not delegated.
+/// Ballots already cast are never re-weighted by a later change. The delegator
+/// must hold voting power, and at most `MAX_DELEGATORS` chains may end at
+/// one voter.
+pub fn delegate(env: &Env, delegator: &Address, delegate_to: &Address) -> Result<(), Error> {
+    delegator.require_auth();
+
+    if delegator == delegate_to {
+        return Err(VoteError::SelfDelegation.into());
+    }
+    if storage::delegation_reaches(env, delegate_to, delegator) {
+        return Err(VoteError::DelegationCycle.into());
+    }
+    // Only holders of voting power may grow the delegation index
+    if storage::get_voting_power(env, delegator) == 0 {
+        return Err(UpgradeError::NoVotingPower.into());
+    }
+    // Moving within the same tree adds no chains to its root
+    let root = storage::delegation_root(env, delegate_to);
+    if storage::delegation_root(env, delegator) != root {
+        let carried = storage::get_delegators(env, delegator).len() + 1;
+        if storage::get_delegators(env, &root).len() + carried > MAX_DELEGATORS {
+            return Err(VoteError::TooManyDelegators.into());
+        }
+    }
+
+    storage::set_delegate(env, delegator, delegate_to);
+    emit_event(
+        env,
+        MOD_UPGRADE,
+        ACT_DELEGATED,
+        (delegator.clone(), delegate_to.clone()),
+    );
+    Ok(())
+}
+
+/// Withdraw `delegator`'s delegation so it may vote directly again
+pub fn undelegate(env: &Env, delegator: &Address) -> Result<(), Error> {
+    delegator.require_auth();
+
+    if !storage::remove_delegate(env, delegator) {
+        return Err(VoteError::NotDelegating.into());
+    }
+    emit_event(env, MOD_UPGRADE, ACT_UNDELEGATED, delegator.clone());
+    Ok(())
+}
+
+/// Publish the finalization event at the configured verbosity
+fn emit_finalized(
+    env: &Env,
+    proposal: &UpgradeProposal,
+    params: ProposalParams,
+    status: UpgradeStatus,
+    early: bool,
+) {
+    let proposal_id = proposal.id;
+    let finalized = ProposalFinalized {
+        proposal_id,
+        status,
+        early,
+        normalized: NormalizedTally::of(proposal),
+    };
+    emit_leveled_event(
+        env,
+        MOD_UPGRADE,
+        ACT_FINALIZED,
+        |verbosity| match verbosity {
+            EventVerbosity::Minimal => (proposal_id, status).into_val(env),
+            EventVerbosity::Standard => finalized.into_val(env),
+            EventVerbosity::Verbose => {
+                (finalized, TallySnapshot::of(proposal, params)).into_val(env)
+            }
+        },
+    );
+}
+
+/// Settle a proposal once its voting deadline has passed. Anyone may call
+/// this. With quorum met, the cast For votes must exceed the threshold share
+/// of For and Against to approve, otherwise the proposal is rejected;
+/// without quorum or any For/Against vote it expires. A proposal already
+/// decided keeps its status. Returns the proposal's resulting status.
+pub fn finalize_proposal(env: &Env, proposal_id: u64) -> Result<UpgradeStatus, Error> {
+    let proposal = load_proposal(env, proposal_id)?;
+    if proposal.status != UpgradeStatus::Pending {
+        return Ok(proposal.status);
+    }
+    if proposal.voting_deadline == 0 || time::now(env) <= proposal.voting_deadline {
+        return Err(ProposalError::VotingPeriodActive.into());
+    }
+
+    let params = storage::get_proposal_params(env, proposal_id);
+    let projection = project_outcome(&proposal, &params);
+    let cast = proposal.votes_for.saturating_add(proposal.votes_against);
+    let status = if !projection.quorum_met || cast == 0 {
+        UpgradeStatus::Expired
+    } else if approval_ratio_met(
+        proposal.votes_for,
+        proposal.votes_against,
+        params.threshold_bps,
+    ) {
+        UpgradeStatus::Approved
+    } else {
+        UpgradeStatus::Rejected
+    };
+
+    transition(env, proposal_id, status, DEADLINE_REASON)?;
+    let action = match status {
+        UpgradeStatus::Approved => ACT_APPROVED,
+        UpgradeStatus::Rejected => ACT_REJECTED,
+        _ => ACT_EXPIRED,
+    };
+    emit_event(env, MOD_UPGRADE, action, proposal_id);
+    emit_finalized(env, &proposal, params, status, false);
+
+    Ok(status)
+}
+
+/// Set the voting period of proposals on the governance-wide rules; tracks
+/// keep their own. Applies to proposals created afterwards (0 disables it).
+pub fn set_voting_period(env: &Env, caller: &Address, period: u64) -> Result<(), Error> {
+    caller.require_auth();
+
+    authorize(env, caller, Action::SetGovernanceConfig)?;
+
+    storage::set_voting_period(env, period);
+
+    emit_event(env, MOD_UPGRADE, ACT_UPDATED, period);
+
+    Ok(())
+}
+
+/// Set how long an approved proposal waits before it may execute, giving
+/// holders time to exit. Applies to proposals approved from now on whose
+/// template does not set its own delay; the emergency path is never delayed.
+pub fn set_execution_delay(env: &Env, caller: &Address, delay: u64) -> Result<(), Error> {
+    caller.require_auth();
+
+    authorize(env, caller, Action::SetGovernanceConfig)?;
+
+    storage::set_execution_delay(env, delay);
+
+    emit_event(env, MOD_UPGRADE, ACT_EXECUTION_DELAY_SET, delay);
+
+    Ok(())
+}
+
+/// Limit how long an approved proposal stays executable once its delay and
+/// timelock are over; past that it expires. `None` removes the limit.
+pub fn set_execution_window(env: &Env, caller: &Address, window: Option<u64>) -> Result<(), Error> {
+    caller.require_auth();
+
+    authorize(env, caller, Action::SetGovernanceConfig)?;
+    if window == Some(0) {
+        return Err(ProposalError::InvalidExecutionWindow.into());
+    }
+
+    storage::set_execution_window(env, window);
+
+    emit_event(env, MOD_UPGRADE, ACT_EXECUTION_WINDOW_SET, window);
+
+    Ok(())
+}
+
+/// The outcome of a pending proposal if the weight not yet cast can no longer
+/// change it: the for side must hold more than the track threshold of all
+/// decisive weight even if every remaining vote goes against, or the against
+/// side more than the rest. At the 5000 bps default this means one side
+/// strictly exceeds the other plus all remaining weight. Approval
+/// additionally needs quorum to be met already.
+pub fn decided_outcome(
+    proposal: &UpgradeProposal,
+    params: &ProposalParams,
+) -> Option<UpgradeStatus> {
+    let votes_for = proposal.votes_for as u128;
+    let votes_against = proposal.votes_against as u128;
+    let cast = votes_for + votes_against + proposal.votes_abstain as u128;
+    let remaining = (proposal.total_voting_power_snapshot as u128).saturating_sub(cast);
+
+    let decisive = votes_for + votes_against + remaining;
+    let threshold = params.threshold_bps as u128;
+    let denominator = BPS_DENOMINATOR as u128;
+
+    if votes_for * denominator > decisive * threshold
+        && project_outcome(proposal, params).quorum_met
+    {
+        Some(UpgradeStatus::Approved)
+    } else if votes_against * denominator > decisive * (denominator - threshold) {
+        Some(UpgradeStatus::Rejected)
+    } else {
+        None
+    }
+}
+
+/// Configure quorum rules for proposals created from now on
+pub fn set_governance_config(
+    env: &Env,
+    caller: &Address,
+    config: &GovernanceConfig,
+) -> Result<(), Error> {
+    caller.require_auth();
+
+    authorize(env, caller, Action::SetGovernanceConfig)?;
+    if config.quorum_bps > BPS_DENOMINATOR {
+        return Err(UpgradeError::InvalidQuorum.into());
+    }
+    if config.approval_threshold_bps > BPS_DENOMINATOR {
+        return Err(ProposalError::InvalidApprovalThreshold.into());
+    }
+
+    storage::set_governance_config(env, config);
+
+    emit_event(env, MOD_UPGRADE, ACT_UPDATED, config.clone());
+
+    Ok(())
+}
+
+/// Require every new proposal to post `amount` of `token` as a bond
+/// (0 disables bonds). Bonds are refunded when a proposal ends, unless it
+/// is rejected; a rejected proposal's bond is slashed to the governance
+/// config's `slash_destination`, or burned when none is set.
+pub fn set_proposal_bond(
+    env: &Env,
+    caller: &Address,
+    token: &Address,
+    amount: i128,
+) -> Result<(), Error> {
+    caller.require_auth();
+
+    authorize(env, caller, Action::SetGovernanceConfig)?;
+    if amount < 0 {
+        return Err(ProposalError::InvalidBondAmount.into());
+    }
+
+    let config = BondConfig {
+        token: token.clone(),
+        amount,
+    };
+    storage::set_bond_config(env, &config);
+
+    emit_event(env, MOD_UPGRADE, ACT_UPDATED, config);
+
+    Ok(())
+}
+
+/// Take the configured bond from the proposer of a new proposal
+fn post_bond(env: &Env, proposer: &Address, proposal_id: u64) {
+    let config = match storage::get_bond_config(env) {
+        Some(config) if config.amount > 0 => config,
+        _ => return,
+    };
+
+    TokenClient::new(env, &config.token).transfer(
+        proposer,
+        &env.current_contract_address(),
+        &config.amount,
+    );
+    storage::store_proposal_bond(
+        env,
+        proposal_id,
+        &ProposalBond {
+            token: config.token,
+            amount: config.amount,
+            status: BondStatus::Held,
+        },
+    );
+
+    emit_event(
+        env,
+        MOD_UPGRADE,
+        ACT_BOND_POSTED,
+        (proposal_id, config.amount),
+    );
+}
+
+/// Release a held bond once its proposal is terminal: slash it when the
+/// proposal was rejected, refund the proposer otherwise. A failed transfer
+/// leaves the bond held and returns the token's error code (0 on a trap).
+fn settle_bond(env: &Env, proposal: &UpgradeProposal) -> Result<(), u32> {
+    let mut bond = match storage::get_proposal_bond(env, proposal.id) {
+        Some(bond) if bond.status == BondStatus::Held => bond,
+        _ => return Ok(()),
+    };
+    let token = TokenClient::new(env, &bond.token);
+    let contract = env.current_contract_address();
+
+    if proposal.status == UpgradeStatus::Rejected {
+        let destination = storage::get_governance_config(env).slash_destination;
+        let moved = match &destination {
+            Some(destination) => token.try_transfer(&contract, destination, &bond.amount),
+            None => token.try_burn(&contract, &bond.amount),
+        };
+        if let Err(failure) = moved {
+            return Err(failure.map_or(0, |error| error.get_code()));
+        }
+        bond.status = BondStatus::Slashed;
+
+        let record = SlashRecord {
+            proposal_id: proposal.id,
+            token: bond.token.clone(),
+            amount: bond.amount,
+            destination,
+            slashed_at: time::now(env),
+        };
+        storage::push_slash_record(env, &record);
+        emit_event(env, MOD_UPGRADE, ACT_BOND_SLASHED, record);
+    } else {
+        if let Err(failure) = token.try_transfer(&contract, &proposal.proposer, &bond.amount) {
+            return Err(failure.map_or(0, |error| error.get_code()));
+        }
+        bond.status = BondStatus::Refunded;
+        emit_event(
+            env,
+            MOD_UPGRADE,
+            ACT_BOND_REFUNDED,
+            (proposal.id, bond.amount),
+        );
+    }
+
+    storage::store_proposal_bond(env, proposal.id, &bond);
+    Ok(())
+}
+
+/// Largest page `get_slash_history` returns
+pub const MAX_SLASH_HISTORY_PAGE: u32 = 50;
+
+/// Page through slashed bonds, oldest first
+pub fn get_slash_history(env: &Env, offset: u32, limit: u32) -> Vec<SlashRecord> {
+    let history = storage::get_slash_history(env);
+    page(&history, offset, limit, MAX_SLASH_HISTORY_PAGE)
+}
+
+/// Hash of the active governance parameters, for partner contracts that pin
+/// the rules they integrated under. The preimage is written field by field
+/// in a fixed order rather than through the XDR of the config, so it stays
+/// stable as fields are added:
+/// digest version (u32), quorum_bps (u32), quorum_counts_abstain (u8),
+/// early_finalize (u8), activation_delay (u64), live_vote_export (u8),
+/// execution timelock (u64), approval_threshold_bps (u32), revert_window
+/// (u64), slash_destination, archive_sink, track count (u32), then each
+/// track ascending by id: id (u32), xdr(name), max_bump (u32),
+/// threshold_bps (u32), quorum_bps (u32), voting_period (u64), timelock
+/// (u64), separation_of_duties (u8). Integers are big-endian; an optional
+/// address is a presence byte followed by its XDR when set.
+pub fn get_governance_digest(env: &Env) -> BytesN<32> {
+    let config = storage::get_governance_config(env);
+
+    let mut preimage = Bytes::new(env);
+    preimage.extend_from_array(&GOVERNANCE_DIGEST_VERSION.to_be_bytes());
+    preimage.extend_from_array(&config.quorum_bps.to_be_bytes());
+    preimage.push_back(config.quorum_counts_abstain as u8);
+    preimage.push_back(config.early_finalize as u8);
+    preimage.extend_from_array(&config.activation_delay.to_be_bytes());
+    preimage.push_back(config.live_vote_export as u8);
+    preimage.extend_from_array(&execution_timelock(env).to_be_bytes());
+    preimage.extend_from_array(&config.approval_threshold_bps.to_be_bytes());
+    preimage.extend_from_array(&config.revert_window.to_be_bytes());
+    for address in [&config.slash_destination, &config.archive_sink] {
+        preimage.push_back(address.is_some() as u8);
+        if let Some(address) = address {
+            preimage.append(&address.clone().to_xdr(env));
+        }
+    }
+
+    let tracks = storage::get_governance_tracks(env);
+    preimage.extend_from_array(&tracks.len().to_be_bytes());
+    for track in tracks.iter() {
+        preimage.extend_from_array(&track.id.to_be_bytes());
+        preimage.append(&track.name.to_xdr(env));
+        preimage.extend_from_array(&(track.max_bump as u32).to_be_bytes());
+        preimage.extend_from_array(&track.threshold_bps.to_be_bytes());
+        preimage.extend_from_array(&track.quorum_bps.to_be_bytes());
+        preimage.extend_from_array(&track.voting_period.to_be_bytes());
+        preimage.extend_from_array(&track.timelock.to_be_bytes());
+        preimage.push_back(track.separation_of_duties as u8);
+    }
+
+    env.crypto().sha256(&preimage).into()
+}
+
+/// Check the active governance parameters against a partner's minimums,
+/// listing every setting that falls short
+pub fn assert_min_standards(
+    env: &Env,
+    min: &GovernanceMinimums,
+) -> Result<(), Vec<GovernanceShortfall>> {
+    let config = storage::get_governance_config(env);
+    let timelock = execution_timelock(env);
+
+    let mut shortfalls = Vec::new(env);
+    if config.quorum_bps < min.quorum_bps {
+        shortfalls.push_back(GovernanceShortfall::QuorumBps(config.quorum_bps));
+    }
+    if config.approval_threshold_bps < min.approval_threshold_bps {
+        shortfalls.push_back(GovernanceShortfall::ApprovalThresholdBps(
+            config.approval_threshold_bps,
+        ));
+    }
+    if timelock < min.timelock {
+        shortfalls.push_back(GovernanceShortfall::Timelock(timelock));
+    }
+    if config.activation_delay < min.activation_delay {
+        shortfalls.push_back(GovernanceShortfall::ActivationDelay(
+            config.activation_delay,
+        ));
+    }
+
+    if shortfalls.is_empty() {
+        Ok(())
+    } else {
+        Err(shortfalls)
+    }
+}
+
+/// Delay between approval and the implementation switch for proposals on
+/// the default track: the execution delay, plus the proxy's upgrade delay
+/// when execution runs through it
+fn execution_timelock(env: &Env) -> u64 {
+    let delay = storage::get_execution_delay(env);
+    if executes_through_proxy(env) {
+        delay.saturating_add(proxy_storage::get_min_upgrade_delay(env))
+    } else {
+        delay
+    }
+}
+
+/// Set the voting power of `member`. Increases only take effect after the
+/// configured `activation_delay`, so they cannot sway proposals created in
+/// the meantime; reductions apply immediately.
+pub fn grant_voting_power(
+    env: &Env,
+    caller: &Address,
+    member: &Address,
+    power: u32,
+) -> Result<u64, Error> {
+    caller.require_auth();
+
+    authorize(env, caller, Action::GrantVotingPower)?;
+
+    let active_from = apply_voting_power(env, member, power)?;
+
+    emit_event(
+        env,
+        MOD_UPGRADE,
+        ACT_POWER_GRANTED,
+        (member.clone(), power, active_from),
+    );
+
+    Ok(active_from)
+}
+
+/// Set the voting power of up to `MAX_POWER_BATCH_LEN` addresses in one call.
+/// Entries take effect as `grant_voting_power` would; immediate ones share a
+/// single registry write. Returns the number of entries applied.
+pub fn set_voting_power_batch(
+    env: &Env,
+    caller: &Address,
+    entries: &Vec<(Address, u32)>,
+) -> Result<u32, Error> {
+    caller.require_auth();
+
+    authorize(env, caller, Action::GrantVotingPower)?;
+    if entries.is_empty() {
+        return Err(VoteError::EmptyBatch.into());
+    }
+    if entries.len() > MAX_POWER_BATCH_LEN {
+        return Err(VoteError::BatchTooLarge.into());
+    }
+
+    for (_, power) in entries.iter() {
+        storage::check_max_voting_power(env, power)?;
+    }
+
+    let now = time::now(env);
+    let delay = storage::get_governance_config(env).activation_delay;
+    let mut immediate = Vec::new(env);
+    for (member, power) in entries.iter() {
+        if delay == 0 || power <= storage::get_voting_power(env, &member) {
+            immediate.push_back((member, power));
+        } else {
+            let active_from = now.saturating_add(delay);
+            storage::store_pending_grant(env, &member, &VotingGrant { power, active_from });
+        }
+    }
+    storage::set_voting_powers(env, &immediate);
+
+    emit_event(env, MOD_UPGRADE, ACT_POWER_BATCH, entries.len());
+
+    Ok(entries.len())
+}
+
+/// Add `voter` to the voter registry with `power`, which activates like any
+/// other grant. Returns when the power takes effect.
+pub fn register_voter(
+    env: &Env,
+    caller: &Address,
+    voter: &Address,
+    power: u32,
+) -> Result<u64, Error> {
+    caller.require_auth();
+
+    authorize(env, caller, Action::GrantVotingPower)?;
+    if storage::is_registered_voter(env, voter) {
+        return Err(VoteError::VoterAlreadyRegistered.into());
+    }
+
+    let active_from = apply_voting_power(env, voter, power)?;
+
+    emit_event(
+        env,
+        MOD_UPGRADE,
+        ACT_VOTER_REGISTERED,
+        (voter.clone(), power, active_from),
+    );
+
+    Ok(active_from)
+}
+
+/// Take `voter` out of the voter registry, dropping its base power from the
+/// running total and any pending grant. Snapshots of open proposals keep it.
+pub fn remove_voter(env: &Env, caller: &Address, voter: &Address) -> Result<u32, Error> {
+    caller.require_auth();
+
+    authorize(env, caller, Action::GrantVotingPower)?;
+    if !storage::is_registered_voter(env, voter) {
+        return Err(VoteError::VoterNotRegistered.into());
+    }
+
+    let removed = storage::remove_voter(env, voter);
+
+    emit_event(
+        env,
+        MOD_UPGRADE,
+        ACT_VOTER_REMOVED,
+        (voter.clone(), removed),
+    );
+
+    Ok(removed)
+}
+
+/// Set `member`'s voting power, delaying increases by the configured
+/// activation delay. Returns when the new power takes effect.
+fn apply_voting_power(env: &Env, member: &Address, power: u32) -> Result<u64, Error> {
+    storage::check_max_voting_power(env, power)?;
+
+    let now = time::now(env);
+    let delay = storage::get_governance_config(env).activation_delay;
+    if delay == 0 || power <= storage::get_voting_power(env, member) {
+        storage::set_voting_power(env, member, power);
+        Ok(now)
+    } else {
+        let active_from = now.saturating_add(delay);
+        storage::store_pending_grant(env, member, &VotingGrant { power, active_from });
+        Ok(active_from)
+    }
+}
+
+/// Set the most voting power one address may hold (0 removes the maximum).
+/// Applies to power set from now on; power already held is left as it is.
+pub fn set_max_voting_power(env: &Env, caller: &Address, max: u32) -> Result<(), Error> {
+    caller.require_auth();
+
+    authorize(env, caller, Action::SetGovernanceConfig)?;
+
+    storage::set_max_voting_power(env, max);
+
+    emit_event(env, MOD_UPGRADE, ACT_MAX_POWER_SET, max);
+
+    Ok(())
+}
+
+/// Commit to a merkle root over (address, power) pairs that members can
+/// claim from. Rotating the root invalidates unclaimed leaves of the old one.
+pub fn set_voting_power_root(env: &Env, caller: &Address, root: &BytesN<32>) -> Result<u32, Error> {
+    caller.require_auth();
+
+    authorize(env, caller, Action::SetVotingPowerRoot)?;
+
+    let epoch = storage::set_voting_power_root(env, root);
+
+    emit_event(env, MOD_UPGRADE, ACT_POWER_ROOT_SET, (root.clone(), epoch));
+
+    Ok(epoch)
+}
+
+/// Claim the voting power allotted to `claimer` under the current root.
+/// Each leaf can be claimed once per root.
+pub fn claim_voting_power(
+    env: &Env,
+    claimer: &Address,
+    power: u32,
+    proof: &Vec<BytesN<32>>,
+) -> Result<u64, Error> {
+    claimer.require_auth();
+
+    let (root, epoch) =
+        storage::get_voting_power_root(env).ok_or(ProposalError::VotingPowerRootMissing)?;
+    if storage::has_claimed_voting_power(env, epoch, claimer) {
+        return Err(ProposalError::VotingPowerClaimed.into());
+    }
+    let leaf = merkle::hash_leaf(env, claimer, power);
+    if !merkle::verify_proof(env, &root, &leaf, proof) {
+        return Err(ProposalError::InvalidMerkleProof.into());
+    }
+
+    storage::mark_voting_power_claimed(env, epoch, claimer);
+    let active_from = apply_voting_power(env, claimer, power)?;
+
+    emit_event(
+        env,
+        MOD_UPGRADE,
+        ACT_POWER_CLAIMED,
+        (claimer.clone(), power, epoch),
+    );
+
+    Ok(active_from)
+}
+
+/// Move a proposal to `to`, rejecting moves outside the legal status graph
+/// (see `UpgradeStatus::can_transition_to`). This is the only way logic
+/// changes a stored proposal's status; every change emits a `StatusChange`.
+pub fn transition(
+    env: &Env,
+    proposal_id: u64,
+    to: UpgradeStatus,
+    reason: Symbol,
+) -> Result<UpgradeProposal, Error> {
+    let mut proposal =
+        storage::get_upgrade_proposal(env, proposal_id).ok_or(UpgradeError::ProposalNotFound)?;
+    let from = proposal.status;
+    if !from.can_transition_to(to) {
+        return Err(ProposalError::IllegalTransition.into());
+    }
+
+    proposal.status = to;
+    storage::store_upgrade_proposal(env, &proposal);
+    if to == UpgradeStatus::Approved {
+        let now = time::now(env);
+        storage::set_approved_at(env, proposal_id, now);
+        let delay = storage::get_applied_template(env, proposal_id)
+            .and_then(|applied| applied.execution_delay)
+            .unwrap_or_else(|| storage::get_execution_delay(env));
+        let eligible_at = now.saturating_add(delay);
+        storage::set_eligible_at(env, proposal_id, eligible_at);
+    }
+    if to.is_terminal() {
+        append_history(env, HistoryKind::Proposals, proposal.clone());
+        if let Err(code) = settle_bond(env, &proposal) {
+            register_compensation(
+                env,
+                CompensationKind::BondSettlement,
+                &proposal.proposer,
+                proposal_id,
+                code,
+            );
+        }
+    }
+
+    let change = StatusChange {
+        proposal_id,
+        from,
+        to,
+        reason,
+    };
+    emit_leveled_event(
+        env,
+        MOD_UPGRADE,
+        ACT_STATUS_CHANGED,
+        |verbosity| match verbosity {
+            EventVerbosity::Minimal => (proposal_id, to).into_val(env),
+            EventVerbosity::Standard => change.into_val(env),
+            EventVerbosity::Verbose => {
+                let params = storage::get_proposal_params(env, proposal_id);
+                (change, TallySnapshot::of(&proposal, params)).into_val(env)
+            }
+        },
+    );
+
+    Ok(proposal)
+}
+
+/// Load a proposal for a state-changing path, telling apart an id that was
+/// never issued, one whose entry is missing (e.g. expired and not yet
+/// restored) and one whose index entries need `repair_indices`.
+fn load_proposal(env: &Env, proposal_id: u64) -> Result<UpgradeProposal, Error> {
+    storage::check_schema(env).map_err(|_| UpgradeError::SchemaMismatch)?;
+    match storage::get_upgrade_proposal(env, proposal_id) {
+        Some(proposal) if storage::proposal_indices_complete(env, &proposal) => Ok(proposal),
+        Some(_) => Err(UpgradeError::ProposalIndicesIncomplete.into()),
+        None if storage::was_proposal_issued(env, proposal_id) => {
+            Err(UpgradeError::ProposalDataUnavailable.into())
+        }
+        None => Err(UpgradeError::ProposalNotFound.into()),
+    }
+}
+
+/// Rebuild the status and proposer index entries of a restored proposal
+pub fn repair_indices(env: &Env, caller: &Address, proposal_id: u64) -> Result<(), Error> {
+    caller.require_auth();
+
+    authorize(env, caller, Action::RepairIndices)?;
+
+    let proposal =
+        storage::get_upgrade_proposal(env, proposal_id).ok_or(UpgradeError::ProposalNotFound)?;
+    storage::repair_proposal_indices(env, &proposal);
+
+    emit_event(env, MOD_UPGRADE, ACT_INDICES_REPAIRED, proposal_id);
+
+    Ok(())
+}
+
+/// Largest page `export_votes` returns
+pub const MAX_VOTE_EXPORT_PAGE: u32 = 50;
+
+/// Load a proposal whose vote ledger may be read. Unless `live_vote_export`
+/// is set the proposal must be finalized; archived proposals are an error.
+fn load_exportable_proposal(env: &Env, proposal_id: u64) -> Result<UpgradeProposal, Error> {
+    let proposal = load_proposal(env, proposal_id)?;
+    if proposal.status == UpgradeStatus::Pending
+        && !storage::get_governance_config(env).live_vote_export
+    {
+        return Err(ProposalError::ProposalNotFinalized.into());
+    }
+    Ok(proposal)
+}
+
+/// Page through a proposal's vote receipts in the order they were cast.
+/// `limit` is capped at `MAX_VOTE_EXPORT_PAGE`; an offset past the end
+/// yields an empty page.
+pub fn export_votes(
+    env: &Env,
+    proposal_id: u64,
+    offset: u32,
+    limit: u32,
+) -> Result<Vec<VoteReceipt>, Error> {
+    let from = positioned_at(env, PageScope::VoteReceipts, offset as u64);
+    Ok(export_votes_after(env, proposal_id, Some(from), limit)?.receipts)
+}
+
+/// Page through a proposal's vote receipts by cursor; positions count from
+/// 1 in the order votes were cast. Resetting the votes of any proposal
+/// invalidates outstanding receipt cursors.
+pub fn export_votes_after(
+    env: &Env,
+    proposal_id: u64,
+    cursor: Option<Cursor>,
+    limit: u32,
+) -> Result<VoteReceiptPage, Error> {
+    load_exportable_proposal(env, proposal_id)?;
+    let cursor = pagination::resolve(env, PageScope::VoteReceipts, cursor)?;
+
+    let receipts = storage::get_vote_receipts(env, proposal_id);
+    let (positions, next, complete) = pagination::page_span(
+        env,
+        &cursor,
+        1,
+        receipts.len() as u64,
+        limit.min(MAX_VOTE_EXPORT_PAGE),
+    );
+    let mut page = Vec::new(env);
+    for position in positions.iter() {
+        page.push_back(receipts.get_unchecked(position as u32 - 1));
+    }
+    Ok(VoteReceiptPage {
+        receipts: page,
+        next,
+        complete,
+    })
+}
+
+/// Largest page `get_voters_after` returns
+pub const MAX_VOTER_PAGE: u32 = 50;
+
+/// Page through the voter registry by cursor; positions count from 1.
+/// A voter joining the registry invalidates outstanding voter cursors.
+pub fn get_voters_after(env: &Env, cursor: Option<Cursor>, limit: u32) -> Result<VoterPage, Error> {
+    let cursor = pagination::resolve(env, PageScope::Voters, cursor)?;
+
+    let voters = storage::get_voters(env);
+    let (positions, next, complete) = pagination::page_span(
+        env,
+        &cursor,
+        1,
+        voters.len() as u64,
+        limit.min(MAX_VOTER_PAGE),
+    );
+    let mut page = Vec::new(env);
+    for position in positions.iter() {
+        page.push_back(voters.get_unchecked(position as u32 - 1));
+    }
+    Ok(VoterPage {
+        voters: page,
+        next,
+        complete,
+    })
+}
+
+/// Page through the voter registry from position `start` (counting from 0);
+/// past the last voter the page is empty
+pub fn get_voters(env: &Env, start: u32, limit: u32) -> Vec<VoterEntry> {
+    let from = positioned_at(env, PageScope::Voters, start as u64);
+    match get_voters_after(env, Some(from), limit) {
+        Ok(page) => page.voters,
+        Err(_) => Vec::new(env),
+    }
+}
+
+/// A current forward cursor that has already returned `last_id`, for the
+/// offset-based wrappers
+fn positioned_at(env: &Env, scope: PageScope, last_id: u64) -> Cursor {
+    Cursor {
+        last_id,
+        ..pagination::start_cursor(env, scope, CursorDirection::Forward)
+    }
+}
+
+/// The `offset..offset + limit` window of `items`, with `limit` capped at `max`
+fn page<T>(items: &Vec<T>, offset: u32, limit: u32, max: u32) -> Vec<T> {
+    let end = offset.saturating_add(limit.min(max)).min(items.len());
+    items.slice(offset.min(end)..end)
+}
+
+/// Largest page `get_proposals_by_version` returns
+pub const MAX_VERSION_QUERY_PAGE: u32 = 50;
+
+/// Page through the ids of proposals that targeted `major` (and `minor`,
+/// when given), including finalized ones that have not been archived
+pub fn get_proposals_by_version(
+    env: &Env,
+    major: u32,
+    minor: Option<u32>,
+    offset: u32,
+    limit: u32,
+) -> Vec<u64> {
+    let ids = storage::get_version_index(env, major, minor);
+    page(&ids, offset, limit, MAX_VERSION_QUERY_PAGE)
+}
+
+/// Largest page `get_proposals` returns
+pub const MAX_PROPOSAL_PAGE: u32 = 50;
+
+/// Page through stored proposals in ascending id order, starting at the
+/// first id at or after `start_id`. Past the last id the page is empty.
+pub fn get_proposals(env: &Env, start_id: u64, limit: u32) -> Vec<UpgradeProposal> {
+    let from = positioned_at(env, PageScope::Proposals, start_id.saturating_sub(1));
+    storage::get_proposal_page(env, &from, limit.min(MAX_PROPOSAL_PAGE)).proposals
+}
+
+/// Page through stored proposals by cursor, in either id order. Archiving
+/// a proposal invalidates outstanding proposal cursors.
+pub fn get_proposals_after(
+    env: &Env,
+    cursor: Option<Cursor>,
+    limit: u32,
+) -> Result<ProposalPage, Error> {
+    let cursor = pagination::resolve(env, PageScope::Proposals, cursor)?;
+    Ok(storage::get_proposal_page(
+        env,
+        &cursor,
+        limit.min(MAX_PROPOSAL_PAGE),
+    ))
+}
+
+/// Amend a pending proposal. Description-only changes keep existing votes;
+/// a new target address or version resets them (see `invalidate_votes`).
+pub fn amend_proposal(
+    env: &Env,
+    proposer: &Address,
+    proposal_id: u64,
+    new_contract_address: &Address,
+    target_version: &Version,
+    description: String,
+) -> Result<AmendmentKind, Error> {
+    proposer.require_auth();
+
+    let mut proposal = load_proposal(env, proposal_id)?;
+    if proposal.proposer != *proposer {
+        return Err(ProposalError::NotProposer.into());
+    }
+    if proposal.status != UpgradeStatus::Pending {
+        return Err(UpgradeError::ProposalNotPending.into());
+    }
+
+    let kind = amendment_kind(&proposal, new_contract_address, target_version);
+    proposal.new_contract_address = new_contract_address.clone();
+    proposal.version = target_version.clone();
+    set_description(env, &mut proposal, description);
+    storage::store_upgrade_proposal(env, &proposal);
+    if kind == AmendmentKind::Substantive {
+        invalidate_votes(env, &mut proposal);
+    }
+
+    emit_event(
+        env,
+        MOD_UPGRADE,
+        ACT_AMENDED,
+        (proposal_id, target_version.clone(), kind),
+    );
+
+    Ok(kind)
+}
+
+/// Withdraw a pending proposal. The proposer or governance may cancel it;
+/// votes already cast stay on record but the proposal can no longer be
+/// voted on, finalized or executed.
+pub fn cancel_proposal(env: &Env, caller: &Address, proposal_id: u64) -> Result<(), Error> {
+    caller.require_auth();
+
+    let proposal = load_proposal(env, proposal_id)?;
+    if proposal.proposer != *caller {
+        authorize(env, caller, Action::CancelProposal)?;
+    }
+    if proposal.status != UpgradeStatus::Pending {
+        return Err(UpgradeError::ProposalNotPending.into());
+    }
+
+    transition(env, proposal_id, UpgradeStatus::Cancelled, CANCEL_REASON)?;
+
+    emit_event(
+        env,
+        MOD_UPGRADE,
+        ACT_PROPOSAL_CANCELLED,
+        (proposal_id, caller.clone()),
+    );
+
+    Ok(())
+}
+
+/// Block a pending or approved proposal for good. The guardian can stop
+/// upgrades this way but has no say in creating them.
+pub fn veto_proposal(env: &Env, guardian: &Address, proposal_id: u64) -> Result<(), Error> {
+    guardian.require_auth();
+
+    load_proposal(env, proposal_id)?;
+    authorize(env, guardian, Action::VetoProposal)?;
+
+    transition(env, proposal_id, UpgradeStatus::Vetoed, VETO_REASON)?;
+
+    emit_event(
+        env,
+        MOD_UPGRADE,
+        ACT_PROPOSAL_VETOED,
+        (proposal_id, guardian.clone()),
+    );
+
+    Ok(())
+}
+
+/// Classify an amendment against the proposal as it stands
+pub fn amendment_kind(
+    proposal: &UpgradeProposal,
+    new_contract_address: &Address,
+    target_version: &Version,
+) -> AmendmentKind {
+    if proposal.new_contract_address == *new_contract_address && proposal.version == *target_version
+    {
+        AmendmentKind::Metadata
+    } else {
+        AmendmentKind::Substantive
+    }
+}
+
+/// Reset the tallies and receipts of a proposal whose substance changed, so
+/// nobody's vote counts toward something they did not vote on. The event
+/// lists the voters so they can vote again.
+fn invalidate_votes(env: &Env, proposal: &mut UpgradeProposal) {
+    let receipts = storage::clear_votes(env, proposal);
+    if receipts.is_empty() {
+        return;
+    }
+
+    let mut voters = Vec::new(env);
+    for receipt in receipts.iter() {
+        voters.push_back(receipt.voter);
+    }
+    emit_event(
+        env,
+        MOD_UPGRADE,
+        ACT_VOTES_INVALIDATED,
+        VotesInvalidated {
+            proposal_id: proposal.id,
+            count: receipts.len(),
+            voters,
+        },
+    );
+}
+
+/// Remove a finalized proposal's entry from storage. The id stays issued,
+/// so later lookups report its data as unavailable. With an archive sink
+/// configured the proposal is only removed once the sink acknowledges its
+/// summary; otherwise it is queued for `retry_archival` and kept.
+/// Returns whether the proposal was removed.
+pub fn archive_proposal(env: &Env, caller: &Address, proposal_id: u64) -> Result<bool, Error> {
+    caller.require_auth();
+
+    authorize(env, caller, Action::ArchiveProposal)?;
+
+    let proposal = load_proposal(env, proposal_id)?;
+    if !proposal.status.is_terminal() {
+        return Err(ProposalError::ProposalNotFinalized.into());
+    }
+
+    let archived = sink_and_archive(env, &proposal);
+    if !archived {
+        let mut queue = storage::get_archive_queue(env);
+        if !queue.contains(proposal_id) {
+            queue.push_back(proposal_id);
+            storage::set_archive_queue(env, &queue);
+        }
+        emit_event(env, MOD_UPGRADE, ACT_ARCHIVE_DEFERRED, proposal_id);
+    }
+
+    Ok(archived)
+}
+
+/// Retry up to `max` queued archivals, oldest first. Anyone may call this.
+/// Returns the number of proposals archived.
+pub fn retry_archival(env: &Env, max: u32) -> u32 {
+    let queue = storage::get_archive_queue(env);
+
+    let mut remaining = Vec::new(env);
+    let mut archived = 0u32;
+    for (i, proposal_id) in queue.iter().enumerate() {
+        if i as u32 >= max {
+            remaining.push_back(proposal_id);
+            continue;
+        }
+        // Entries archived by another path since they were queued are done
+        let Some(proposal) = storage::get_upgrade_proposal(env, proposal_id) else {
+            continue;
+        };
+        if sink_and_archive(env, &proposal) {
+            archived += 1;
+        } else {
+            remaining.push_back(proposal_id);
+        }
+    }
+
+    storage::set_archive_queue(env, &remaining);
+    archived
+}
+
+/// Push the proposal's summary to the archive sink and, once acknowledged,
+/// drop it from storage
+fn sink_and_archive(env: &Env, proposal: &UpgradeProposal) -> bool {
+    if !push_to_archive_sink(env, ArchiveRecord::Proposal(ProposalSummary::of(proposal))) {
+        return false;
+    }
+    storage::archive_upgrade_proposal(env, proposal);
+    emit_event(env, MOD_UPGRADE, ACT_ARCHIVED, proposal.id);
+    true
+}
+
+/// Hand a record to the configured archive sink. Only an explicit `true`
+/// from `archive_record` counts as an acknowledgment; without a sink there
+/// is nothing to wait for.
+fn push_to_archive_sink(env: &Env, record: ArchiveRecord) -> bool {
+    let Some(sink) = storage::get_governance_config(env).archive_sink else {
+        return true;
+    };
+    let args: Vec<Val> = (record,).into_val(env);
+    matches!(
+        calls::invoke::<bool>(env, &sink, &Symbol::new(env, "archive_record"), args),
+        Ok(true)
+    )
+}
+
+/// Prune history records of `kind` oldest first, up to and including
+/// `through`. Every record is handed to the archive sink first and pruning
+/// stops at the first one it does not acknowledge, so a later call resumes
+/// there. History is never pruned without a sink. Returns the highest
+/// sequence pruned so far.
+pub fn prune_history(
+    env: &Env,
+    caller: &Address,
+    kind: HistoryKind,
+    through: u64,
+) -> Result<u64, Error> {
+    caller.require_auth();
+
+    authorize(env, caller, Action::ArchiveProposal)?;
+    if storage::get_governance_config(env).archive_sink.is_none() {
+        return Err(ProposalError::NoArchiveSink.into());
+    }
+
+    let through = through.min(get_history_digest(env, kind).count);
+    let mut pruned = get_pruned_through(env, kind);
+    while pruned < through {
+        let sequence = pruned + 1;
+        if let Some(record) = get_history_record(env, kind, sequence) {
+            if !push_to_archive_sink(env, ArchiveRecord::History(record)) {
+                break;
+            }
+        }
+        prune_record(env, kind, sequence);
+        pruned = sequence;
+    }
+
+    emit_event(env, MOD_UPGRADE, ACT_HISTORY_PRUNED, (kind, pruned));
+
+    Ok(pruned)
+}
+
+/// Final tallies and receipt count, so an export can be checked for completeness
+pub fn get_tally_checkpoint(env: &Env, proposal_id: u64) -> Result<TallyCheckpoint, Error> {
+    let proposal = load_exportable_proposal(env, proposal_id)?;
+
+    Ok(TallyCheckpoint {
+        proposal_id,
+        status: proposal.status,
+        votes_for: proposal.votes_for,
+        votes_against: proposal.votes_against,
+        votes_abstain: proposal.votes_abstain,
+        normalized: NormalizedTally::of(&proposal),
+        receipt_count: storage::get_vote_receipts(env, proposal_id).len(),
+    })
+}
+
+/// Project the outcome of a proposal from its current tallies.
+/// This is the single source of the decision threshold and quorum rule,
+/// used both when voting and by read-only views.
+pub fn project_outcome(proposal: &UpgradeProposal, params: &ProposalParams) -> OutcomeProjection {
+    // Strictly more than the threshold share; 5000 bps is a simple majority
+    let total = proposal.total_voting_power_snapshot as u128;
+    // A unanimous threshold can be met by every vote but never exceeded
+    let required_votes = ((total * params.threshold_bps as u128 / BPS_DENOMINATOR as u128) + 1)
+        .min(total.max(1)) as u64;
+    // Away from a simple majority, reject once the for side can no longer
+    // reach the required votes
+    let rejecting_votes = if params.threshold_bps != SIMPLE_MAJORITY_BPS {
+        (total + 1 - required_votes as u128).max(1) as u64
+    } else {
+        required_votes
+    };
+
+    let mut participating = proposal.votes_for.saturating_add(proposal.votes_against);
+    if params.quorum_counts_abstain {
+        participating = participating.saturating_add(proposal.votes_abstain);
+    }
+    // Round up so a quorum is never met by a fraction of a vote
+    let quorum_required =
+        ((total * params.quorum_bps as u128).div_ceil(BPS_DENOMINATOR as u128)) as u64;
+    let quorum_met = participating >= quorum_required;
+
+    let projected_status = if proposal.status != UpgradeStatus::Pending {
+        proposal.status
+    } else if proposal.votes_for >= required_votes
+        && quorum_met
+        && approval_ratio_met(
+            proposal.votes_for,
+            proposal.votes_against,
+            params.threshold_bps,
+        )
+    {
+        UpgradeStatus::Approved
+    } else if proposal.votes_against >= rejecting_votes {
+        UpgradeStatus::Rejected
+    } else {
+        UpgradeStatus::Pending
+    };
+
+    OutcomeProjection {
+        votes_for: proposal.votes_for,
+        votes_against: proposal.votes_against,
+        votes_abstain: proposal.votes_abstain,
+        normalized: NormalizedTally::of(proposal),
+        required_votes,
+        participating,
+        quorum_required,
+        quorum_met,
+        projected_status,
+    }
+}
+
+/// Who executed a proposal and when, if it was executed
+pub fn get_execution_record(env: &Env, proposal_id: u64) -> Option<ExecutionRecord> {
+    storage::get_execution_receipt(env, proposal_id).map(|receipt| ExecutionRecord::of(&receipt))
+}
+
+/// The vote breakdown of a proposal under its own voting rules
+pub fn get_proposal_result(env: &Env, proposal_id: u64) -> Option<ProposalResult> {
+    let proposal = storage::get_upgrade_proposal(env, proposal_id)?;
+    let projection = project_outcome(&proposal, &storage::get_proposal_params(env, proposal_id));
+    Some(ProposalResult {
+        proposal_id,
+        status: proposal.status,
+        votes_for: proposal.votes_for,
+        votes_against: proposal.votes_against,
+        votes_abstain: proposal.votes_abstain,
+        participating: projection.participating,
+        quorum_met: projection.quorum_met,
+        approval_bps: weight_bps(
+            proposal.votes_for,
+            proposal.votes_for.saturating_add(proposal.votes_against),
+        ),
+    })
+}
+
+/// Assemble a read-only view of a proposal, its migration plan, projected
+/// outcome and, when a viewer is supplied, that viewer's eligibility.
+/// Returns `None` for unknown proposals; this never traps.
+pub fn get_proposal_detail(
+    env: &Env,
+    proposal_id: u64,
+    viewer: Option<Address>,
+) -> Option<ProposalDetail> {
+    let mut proposal = storage::get_upgrade_proposal(env, proposal_id)?;
+    proposal.description = full_description(env, &proposal);
+    let migration_plan = storage::get_migration_plan(env, proposal_id);
+
+    let viewer_voting_power = viewer
+        .as_ref()
+        .map(|v| storage::proposal_voting_power(env, &proposal, v))
+        .unwrap_or(0);
+    let viewer_is_proposer = viewer.as_ref() == Some(&proposal.proposer);
+    let viewer_can_vote = viewer_voting_power > 0 && proposal.status == UpgradeStatus::Pending;
+
+    let params = storage::get_proposal_params(env, proposal_id);
+
+    Some(ProposalDetail {
+        track_id: params.track_id,
+        is_private: storage::get_private_description(env, proposal_id).is_some(),
+        projection: project_outcome(&proposal, &params),
+        migration_selector: migration_plan
+            .as_ref()
+            .map(|plan| plan.migration_function_selector.clone()),
+        migration_estimated_gas: migration_plan.map(|plan| plan.estimated_gas),
+        proposal,
+        viewer,
+        viewer_voting_power,
+        viewer_is_proposer,
+        viewer_can_vote,
+    })
+}
+
+/// Execute an approved proposal of any kind. An upgrade switches the proxy
+/// to the approved implementation when one is configured; other actions are
+/// applied once the same execution checks have passed. The execution guard
+/// stays up while it runs, so a migration or hook calling back in is turned
+/// away.
+pub fn execute_proposal(
+    env: &Env,
+    executor: &Address,
+    proposal_id: u64,
+) -> Result<ExecutionOutcome, Error> {
+    storage::with_execution_guard(env, ProposalError::ReentrantCall.into(), || {
+        match storage::get_proposal_kind(env, proposal_id) {
+            Some(kind) => execute_action(env, executor, proposal_id, kind),
+            None => execute_approved(env, executor, proposal_id),
+        }
+    })
+}
+
+fn execute_action(
+    env: &Env,
+    executor: &Address,
+    proposal_id: u64,
+    kind: ProposalKind,
+) -> Result<ExecutionOutcome, Error> {
+    executor.require_auth();
+
+    let proposal = load_proposal(env, proposal_id)?;
+    if proposal.status != UpgradeStatus::Approved {
+        return Err(UpgradeError::ProposalNotApproved.into());
+    }
+
+    authorize(env, executor, Action::ExecuteUpgrade)?;
+    check_execute_separation(env, proposal_id, executor)?;
+
+    if expire_lapsed_approval(env, proposal_id)? {
+        return Ok(ExecutionOutcome::Expired);
+    }
+    if storage::is_governance_paused(env) {
+        return Err(UpgradeError::GovernancePaused.into());
+    }
+    if execution_delay_until(env, proposal_id).is_some() {
+        return Err(ProposalError::ExecutionDelayActive.into());
+    }
+    if timelock_until(env, proposal_id).is_some() {
+        return Err(ProposalError::TimelockActive.into());
+    }
+    if reveal_outstanding(env, proposal_id) {
+        return Err(ProposalError::DescriptionNotRevealed.into());
+    }
+
+    match &kind {
+        ProposalKind::SetParameter(key, value) => {
+            let config = with_parameter(&storage::get_governance_config(env), key, *value)?;
+            storage::set_governance_config(env, &config);
+            emit_event(env, MOD_UPGRADE, ACT_UPDATED, config);
+        }
+        ProposalKind::SetGovernance(new_governance) => {
+            replace_governance(env, executor, new_governance)?;
+            storage::clear_pending_governance(env);
+        }
+        ProposalKind::ToggleEmergency(enabled) => {
+            storage::set_emergency_upgrade_enabled(env, *enabled);
+            emit_event(env, MOD_UPGRADE, ACT_UPDATED, *enabled);
+        }
+        // Never stored; upgrades execute through `execute_approved`
+        ProposalKind::Upgrade(..) => {}
+    }
+
+    transition(env, proposal_id, UpgradeStatus::Executed, EXECUTE_REASON)?;
+    emit_event(env, MOD_UPGRADE, ACT_ACTION_EXECUTED, (proposal_id, kind));
+
+    Ok(ExecutionOutcome::Executed)
+}
+
+fn execute_approved(
+    env: &Env,
+    executor: &Address,
+    proposal_id: u64,
+) -> Result<ExecutionOutcome, Error> {
+    executor.require_auth();
+
+    let proposal = load_proposal(env, proposal_id)?;
+
+    if proposal.status != UpgradeStatus::Approved {
+        return Err(UpgradeError::ProposalNotApproved.into());
+    }
+
+    // Check if the caller is authorized to execute upgrades
+    authorize(env, executor, Action::ExecuteUpgrade)?;
+    check_execute_separation(env, proposal_id, executor)?;
+    if proxy_storage::is_frozen(env) {
+        return Err(GuardError::UpgradesFrozen.into());
+    }
+
+    if expire_lapsed_approval(env, proposal_id)? {
+        return Ok(ExecutionOutcome::Expired);
+    }
+    if storage::is_governance_paused(env) {
+        return Err(UpgradeError::GovernancePaused.into());
+    }
+    if storage::get_post_check_failure(env).is_some() {
+        return Err(ProposalError::PostCheckUnacknowledged.into());
+    }
+    if migration_plan_mismatch(env, proposal_id).is_some() {
+        return Err(ProposalError::MigrationPlanMismatch.into());
+    }
+    if execution_delay_until(env, proposal_id).is_some() {
+        return Err(ProposalError::ExecutionDelayActive.into());
+    }
+    if timelock_until(env, proposal_id).is_some() {
+        return Err(ProposalError::TimelockActive.into());
+    }
+    if revert_window_until(env).is_some() {
+        return Err(ProposalError::RevertWindowOpen.into());
+    }
+    if reveal_outstanding(env, proposal_id) {
+        return Err(ProposalError::DescriptionNotRevealed.into());
+    }
+
+    // Creation-time checks on the target may have gone stale since approval
+    let track_id = storage::get_proposal_params(env, proposal_id).track_id;
+    let stale = target_blockers(env, Some(proposal_id), &proposal.version, track_id);
+    if !stale.is_empty() {
+        emit_event(
+            env,
+            MOD_UPGRADE,
+            ACT_REVALIDATION_FAILED,
+            (proposal_id, stale),
+        );
+        return Ok(ExecutionOutcome::RevalidationFailed);
+    }
+
+    if proxy_storage::PROXY_CONFIG.has(env) && proxy_impl::is_paused(env) {
+        return Err(ProposalError::ProxyPaused.into());
+    }
+
+    // A build reporting another version must not be switched in
+    if proxy_storage::PROXY_CONFIG.has(env) {
+        proxy_impl::check_reported_version(
+            env,
+            &proposal.new_contract_address,
+            Some(&proposal.version),
+        )
+        .map_err(|_| GuardError::ImplementationVersionMismatch)?;
+    }
+
+    // Remember what the proxy ran before, so an instant revert can restore
+    // it. Written only once every check has passed, since a failed execution
+    // is not rolled back.
+    if proxy_storage::PROXY_CONFIG.has(env) {
+        storage::set_previous_implementation(
+            env,
+            proposal_id,
+            &proxy_storage::get_implementation(env),
+        );
+    }
+
+    // When the proxy enforces an upgrade delay, hand the switch to its
+    // schedule and finish once it has actually executed
+    if executes_through_proxy(env) {
+        let schedule_id =
+            proxy_impl::schedule_upgrade_unchecked(env, executor, &proposal.new_contract_address);
+        storage::set_proxy_link(env, proposal_id, schedule_id);
+        transition(
+            env,
+            proposal_id,
+            UpgradeStatus::AwaitingProxy,
+            EXECUTE_REASON,
+        )?;
+
+        emit_event(
+            env,
+            MOD_UPGRADE,
+            ACT_AWAITING_PROXY,
+            (proposal_id, schedule_id),
+        );
+        return Ok(ExecutionOutcome::AwaitingProxy(schedule_id));
+    }
+
+    finish_execution(env, executor, proposal)
+}
+
+/// When the last executed upgrade's revert window closes, while it is open
+fn revert_window_until(env: &Env) -> Option<u64> {
+    storage::get_revert_window(env)
+        .map(|window| window.closes_at)
+        .filter(|closes_at| time::now(env) < *closes_at)
+}
+
+/// Whether execution is handed to the proxy's schedule, because it enforces
+/// a delay or refuses immediate switches
+fn executes_through_proxy(env: &Env) -> bool {
+    proxy_storage::PROXY_CONFIG.has(env)
+        && (proxy_storage::get_min_upgrade_delay(env) > 0 || proxy_storage::is_delay_required(env))
+}
+
+/// The `from_version` of a registered migration plan that does not start
+/// from the current version
+fn migration_plan_mismatch(env: &Env, proposal_id: u64) -> Option<Version> {
+    storage::get_migration_plan(env, proposal_id)
+        .map(|plan| plan.from_version)
+        .filter(|from| *from != storage::get_current_version(env))
+}
+
+/// When an approved proposal's track timelock ends, while it is still running
+fn timelock_until(env: &Env, proposal_id: u64) -> Option<u64> {
+    let timelock = storage::get_proposal_params(env, proposal_id).timelock;
+    storage::get_approved_at(env, proposal_id)
+        .map(|approved_at| approved_at + timelock)
+        .filter(|until| time::now(env) < *until)
+}
+
+/// When an approved proposal's execution delay ends, while it is still running
+fn execution_delay_until(env: &Env, proposal_id: u64) -> Option<u64> {
+    storage::get_eligible_at(env, proposal_id).filter(|eligible_at| time::now(env) < *eligible_at)
+}
+
+/// When an approved proposal's execution window closes, if one is
+/// configured. The window opens once both the execution delay and the track
+/// timelock are over, so a long timelock cannot use it up.
+fn execution_window_closes_at(env: &Env, proposal_id: u64) -> Option<u64> {
+    let window = storage::get_execution_window(env)?;
+    let approved_at = storage::get_approved_at(env, proposal_id)?;
+    let timelock_ends = approved_at + storage::get_proposal_params(env, proposal_id).timelock;
+    let opens_at = storage::get_eligible_at(env, proposal_id)
+        .unwrap_or(approved_at)
+        .max(timelock_ends);
+    Some(opens_at.saturating_add(window))
+}
+
+/// Expire an approved proposal whose execution window has closed. Returns
+/// whether it was expired; an execution reports that as its outcome rather
+/// than failing, so the expiry is kept.
+fn expire_lapsed_approval(env: &Env, proposal_id: u64) -> Result<bool, Error> {
+    let lapsed = execution_window_closes_at(env, proposal_id)
+        .is_some_and(|closes_at| time::now(env) > closes_at);
+    if !lapsed {
+        return Ok(false);
+    }
+
+    transition(env, proposal_id, UpgradeStatus::Expired, WINDOW_REASON)?;
+    emit_event(env, MOD_UPGRADE, ACT_EXPIRED, proposal_id);
+    Ok(true)
+}
+
+/// Checks on a proposal's target that can go stale between creation and
+/// execution: a target newer than and compatible with the current version,
+/// deprecation, the track's permitted bump from the current version, and
+/// another executed proposal for the same version (found through the
+/// version index). Creation refuses a target failing any of them, and
+/// `execute_proposal` re-runs them. `proposal_id` is excluded from the
+/// duplicate search.
+fn target_blockers(
+    env: &Env,
+    proposal_id: Option<u64>,
+    version: &Version,
+    track_id: u32,
+) -> Vec<Blocker> {
+    let mut blockers = Vec::new(env);
+
+    let current = storage::get_current_version(env);
+    let track = storage::get_governance_track(env, track_id);
+    if !version.is_newer_than(&current) {
+        blockers.push_back(Blocker::VersionNotNewer);
+    } else if !check_version_compatibility(&current, version)
+        // The default rules set no bump limit; a track must permit a breaking change
+        && track
+            .as_ref()
+            .is_some_and(|track| track.max_bump < BumpKind::Major)
+    {
+        blockers.push_back(Blocker::IncompatibleVersion);
+    }
+    if storage::is_version_deprecated(env, version) {
+        blockers.push_back(Blocker::TargetDeprecated);
+    }
+    if let Some(track) = track {
+        let bump = current.diff_kind(version);
+        if bump > track.max_bump {
+            blockers.push_back(Blocker::BumpNotPermitted(bump));
+        }
+    }
+    for id in storage::get_version_index(env, version.major, Some(version.minor)).iter() {
+        if Some(id) == proposal_id {
+            continue;
+        }
+        let executed = storage::get_upgrade_proposal(env, id).is_some_and(|other| {
+            other.version == *version && other.status == UpgradeStatus::Executed
+        });
+        if executed {
+            blockers.push_back(Blocker::TargetAlreadyExecuted(id));
+            break;
+        }
+    }
+
+    blockers
+}
+
+/// The creation error for a blocker returned by `target_blockers`
+fn target_error(blocker: &Blocker) -> Error {
+    match blocker {
+        Blocker::VersionNotNewer => GuardError::VersionNotNewer.into(),
+        Blocker::IncompatibleVersion => GuardError::IncompatibleVersion.into(),
+        Blocker::TargetDeprecated => ProposalError::TargetDeprecated.into(),
+        Blocker::BumpNotPermitted(_) => ProposalError::BumpNotPermitted.into(),
+        Blocker::TargetAlreadyExecuted(_) => ProposalError::TargetAlreadyExecuted.into(),
+        _ => ProposalError::InvalidTarget.into(),
+    }
+}
+
+/// Everything that would stop `execute_proposal` for this proposal right now,
+/// in the order `execute_proposal` checks it. Caller authorization is not
+/// included. An empty list means the proposal is executable.
+pub fn get_execution_blockers(env: &Env, proposal_id: u64) -> Vec<Blocker> {
+    let mut blockers = Vec::new(env);
+
+    let proposal = match storage::get_upgrade_proposal(env, proposal_id) {
+        Some(proposal) if storage::proposal_indices_complete(env, &proposal) => proposal,
+        Some(_) => {
+            blockers.push_back(Blocker::IndicesIncomplete);
+            return blockers;
+        }
+        None if storage::was_proposal_issued(env, proposal_id) => {
+            blockers.push_back(Blocker::ProposalUnavailable);
+            return blockers;
+        }
+        None => {
+            blockers.push_back(Blocker::ProposalNotFound);
+            return blockers;
+        }
+    };
+
+    if proposal.status != UpgradeStatus::Approved {
+        blockers.push_back(Blocker::NotApproved);
+    }
+    if let Some(closes_at) = execution_window_closes_at(env, proposal_id) {
+        if time::now(env) > closes_at {
+            blockers.push_back(Blocker::ExecutionWindowClosed(closes_at));
+        }
+    }
+    if storage::is_governance_paused(env) {
+        blockers.push_back(Blocker::GovernancePaused);
+    }
+    if let Some(failure) = storage::get_post_check_failure(env) {
+        blockers.push_back(Blocker::PostCheckUnacknowledged(failure.proposal_id));
+    }
+    if let Some(from_version) = migration_plan_mismatch(env, proposal_id) {
+        blockers.push_back(Blocker::MigrationPlanMismatch(from_version));
+    }
+    if let Some(until) = execution_delay_until(env, proposal_id) {
+        blockers.push_back(Blocker::ExecutionDelayActive(until));
+    }
+    if let Some(until) = timelock_until(env, proposal_id) {
+        blockers.push_back(Blocker::TimelockActive(until));
+    }
+    if let Some(until) = revert_window_until(env) {
+        blockers.push_back(Blocker::RevertWindowOpen(until));
+    }
+    if reveal_outstanding(env, proposal_id) {
+        blockers.push_back(Blocker::DescriptionNotRevealed);
+    }
+    if storage::get_proposal_kind(env, proposal_id).is_none() {
+        let track_id = storage::get_proposal_params(env, proposal_id).track_id;
+        blockers.append(&target_blockers(
+            env,
+            Some(proposal_id),
+            &proposal.version,
+            track_id,
+        ));
+    }
+    if proxy_storage::PROXY_CONFIG.has(env) && proxy_impl::is_paused(env) {
+        blockers.push_back(Blocker::ProxyPaused);
+    }
+    if let Some(progress) = storage::get_migration_progress(env, proposal_id) {
+        if progress.status == MigrationStatus::InProgress && time::now(env) < progress.not_before {
+            blockers.push_back(Blocker::MigrationDeferred(progress.not_before));
+        }
+    }
+
+    blockers
+}
+
+/// Finish a proposal whose proxy schedule has run. Anyone may call this; it
+/// only reflects the schedule's outcome. A cancelled schedule moves the
+/// proposal to `NeedsAttention`, as does a migration that aborts here.
+/// Returns the proposal's resulting status.
+pub fn complete_execution(env: &Env, proposal_id: u64) -> Result<UpgradeStatus, Error> {
+    let proposal = load_proposal(env, proposal_id)?;
+    if proposal.status != UpgradeStatus::AwaitingProxy {
+        return Err(UpgradeError::NotAwaitingProxy.into());
+    }
+
+    let schedule_id =
+        storage::get_proxy_link(env, proposal_id).ok_or(UpgradeError::ProxyLinkMissing)?;
+    let scheduled = proxy_storage::get_scheduled_upgrade(env, schedule_id)
+        .ok_or(UpgradeError::ScheduleNotFound)?;
+
+    match scheduled.status {
+        ScheduleStatus::Pending => Err(UpgradeError::ProxyNotExecuted.into()),
+        ScheduleStatus::Executed => {
+            finish_execution(env, &scheduled.initiator, proposal)?;
+            Ok(load_proposal(env, proposal_id)?.status)
+        }
+        ScheduleStatus::Cancelled => {
+            transition(
+                env,
+                proposal_id,
+                UpgradeStatus::NeedsAttention,
+                PROXY_REASON,
+            )?;
+            emit_event(env, MOD_UPGRADE, ACT_FAILED, (proposal_id, schedule_id));
+            Ok(UpgradeStatus::NeedsAttention)
+        }
+    }
+}
+
+/// Read back the proxy's implementation after a proposal's switch
+fn check_installed(env: &Env, proposal: &UpgradeProposal) -> PostCheck {
+    if proxy_storage::get_implementation(env) == proposal.new_contract_address {
+        PostCheck::Matched
+    } else {
+        PostCheck::Mismatched
+    }
+}
+
+/// A mismatched switch cannot be undone in the same invocation, so record and
+/// announce it; further executions are blocked until governance acknowledges it
+fn record_post_check_failure(env: &Env, proposal: &UpgradeProposal) {
+    let installed = proxy_storage::get_implementation(env);
+    storage::set_post_check_failure(
+        env,
+        &PostCheckFailure {
+            proposal_id: proposal.id,
+            expected: proposal.new_contract_address.clone(),
+            installed: installed.clone(),
+            detected_at: time::now(env),
+        },
+    );
+    emit_event(
+        env,
+        MOD_UPGRADE,
+        ACT_POST_CHECK_FAILED,
+        (
+            proposal.id,
+            proposal.new_contract_address.clone(),
+            installed,
+        ),
+    );
+}
+
+/// Clear a post-execution check failure once governance has looked into it,
+/// so upgrades may execute again
+pub fn acknowledge_post_check(env: &Env, caller: &Address) -> Result<(), Error> {
+    caller.require_auth();
+    authorize(env, caller, Action::AcknowledgePostCheck)?;
+
+    let failure = storage::get_post_check_failure(env).ok_or(ProposalError::NoPostCheckFailure)?;
+    storage::clear_post_check_failure(env);
+    emit_event(
+        env,
+        MOD_UPGRADE,
+        ACT_POST_CHECK_ACKNOWLEDGED,
+        (failure.proposal_id, caller.clone()),
+    );
+
+    Ok(())
+}
+
+/// Migrate state, switch the proxy to the approved implementation, bump the
+/// version, mark the proposal executed and record its receipt, including the
+/// read-back of the installed implementation. A migration that aborts or
+/// defers stops here, with its progress recorded.
+fn finish_execution(
+    env: &Env,
+    executor: &Address,
+    proposal: UpgradeProposal,
+) -> Result<ExecutionOutcome, Error> {
+    let proposal_id = proposal.id;
+
+    // Perform state migration if a migration plan exists
+    let mut migration_outcome = MigrationOutcome::NotRequired;
+    if let Some(migration_plan) = storage::get_migration_plan(env, proposal_id) {
+        let progress =
+            perform_state_migration(env, &proposal, &migration_plan, &MigrationNamespace::Live)?;
+        match progress.status {
+            MigrationStatus::Completed => migration_outcome = MigrationOutcome::Completed,
+            MigrationStatus::Aborted => {
+                return Ok(ExecutionOutcome::MigrationAborted(progress.abort_code))
+            }
+            MigrationStatus::InProgress => {
+                return Ok(ExecutionOutcome::MigrationDeferred(progress.not_before))
+            }
+        }
+    }
+
+    // A proposal still `Approved` has not been through the proxy's schedule,
+    // so the switch happens here, on behalf of governance
+    let mut post_check = PostCheck::NotApplicable;
+    if proxy_storage::PROXY_CONFIG.has(env) {
+        if proposal.status == UpgradeStatus::Approved {
+            proxy_impl::apply_upgrade(
+                env,
+                &storage::get_governance_address(env),
+                &proposal.new_contract_address,
+            );
+        }
+        post_check = check_installed(env, &proposal);
+    }
+
+    // Update the current version, remembering which implementation each side ran
+    let from_version = storage::get_current_version(env);
+    if let Some(previous) = storage::get_previous_implementation(env, proposal_id) {
+        if storage::get_version_implementation(env, &from_version).is_none() {
+            storage::set_version_implementation(env, &from_version, &previous);
+        }
+    }
+    storage::set_version_implementation(env, &proposal.version, &proposal.new_contract_address);
+    storage::set_current_version(env, &proposal.version, executor, Some(proposal_id));
+
+    transition(env, proposal_id, UpgradeStatus::Executed, EXECUTE_REASON)?;
+
+    let receipt = ExecutionReceipt {
+        proposal_id,
+        executor: executor.clone(),
+        from_version: from_version.clone(),
+        to_version: proposal.version.clone(),
+        implementation: proposal.new_contract_address.clone(),
+        wasm_hash: BytesN::from_array(env, &[0u8; 32]),
+        migration_outcome,
+        timestamp: time::now(env),
+        previous_implementation: storage::get_previous_implementation(env, proposal_id),
+        post_check,
+    };
+    storage::store_execution_receipt(env, &receipt);
+    if post_check == PostCheck::Mismatched {
+        record_post_check_failure(env, &proposal);
+    }
+
+    let revert_window = storage::get_governance_config(env).revert_window;
+    if revert_window > 0 {
+        storage::set_revert_window(
+            env,
+            &RevertWindow {
+                proposal_id,
+                closes_at: receipt.timestamp + revert_window,
+            },
+        );
+    }
+
+    // Emit upgrade execution event with the receipt hash so it can be pinned off-chain
+    emit_event(
+        env,
+        MOD_UPGRADE,
+        ACT_UPGRADE_EXECUTED,
+        (proposal_id, receipt_hash(env, &receipt)),
+    );
+
+    notify_subscribers(env, &from_version, &proposal.version, proposal_id);
+
+    Ok(ExecutionOutcome::Executed)
+}
+
+/// SHA-256 over the XDR encoding of a receipt
+pub fn receipt_hash(env: &Env, receipt: &ExecutionReceipt) -> BytesN<32> {
+    BytesN::from_array(
+        env,
+        &env.crypto().sha256(&receipt.clone().to_xdr(env)).to_array(),
+    )
+}
+
+/// Call `on_upgrade(from_version, to_version, proposal_id)` on every subscriber.
+/// Failures are recorded and queued for retry but never revert the upgrade itself.
+fn notify_subscribers(env: &Env, from_version: &Version, to_version: &Version, proposal_id: u64) {
+    let subscribers = storage::get_subscribers(env);
+    if subscribers.is_empty() {
+        return;
+    }
+
+    let mut results = Vec::new(env);
+    for subscriber in subscribers.iter() {
+        let success =
+            invoke_on_upgrade(env, &subscriber, from_version, to_version, proposal_id).is_ok();
+        if !success {
+            enqueue_retry(
+                env,
+                PendingNotification {
+                    subscriber: subscriber.clone(),
+                    proposal_id,
+                    from_version: from_version.clone(),
+                    to_version: to_version.clone(),
+                    attempts: 1,
+                },
+            );
+        }
+        results.push_back(NotificationResult {
+            subscriber,
+            success,
+        });
+    }
+
+    storage::set_notification_results(env, proposal_id, &results);
+}
+
+/// Deliver a single `on_upgrade` notification. A failure carries the
+/// subscriber's error or `CallOutcome` code, or 0 when it has none.
+fn invoke_on_upgrade(
+    env: &Env,
+    subscriber: &Address,
+    from_version: &Version,
+    to_version: &Version,
+    proposal_id: u64,
+) -> Result<(), u32> {
+    let args: Vec<Val> = (from_version.clone(), to_version.clone(), proposal_id).into_val(env);
+    let outcome = calls::invoke_outcome(env, subscriber, &Symbol::new(env, "on_upgrade"), args)
+        .map(|_| ())
+        .map_err(|failure| failure.code);
+
+    emit_event(
+        env,
+        MOD_UPGRADE,
+        ACT_NOTIFIED,
+        (proposal_id, subscriber.clone(), outcome.is_ok()),
+    );
+    outcome
+}
+
+/// Queue a failed notification; when the queue is full the oldest entry is dropped
+fn enqueue_retry(env: &Env, pending: PendingNotification) {
+    let mut queue = storage::get_retry_queue(env);
+    if queue.len() >= storage::MAX_RETRY_QUEUE_LEN {
+        if let Some(dropped) = queue.pop_front() {
+            register_compensation(
+                env,
+                CompensationKind::Notification,
+                &dropped.subscriber,
+                dropped.proposal_id,
+                0,
+            );
+            emit_event(env, MOD_UPGRADE, ACT_NOTIFY_DROPPED, dropped);
+        }
+    }
+    emit_event(env, MOD_UPGRADE, ACT_NOTIFY_QUEUED, pending.clone());
+    queue.push_back(pending);
+    storage::set_retry_queue(env, &queue);
+}
+
+/// Retry up to `max` queued notifications, oldest first. Anyone may call this.
+/// Entries that reach the configured attempt limit are dropped with a final
+/// event and handed to the compensation registry.
+/// Returns the number of notifications delivered.
+pub fn retry_notifications(env: &Env, max: u32) -> u32 {
+    let queue = storage::get_retry_queue(env);
+    let max_attempts = storage::get_notification_max_attempts(env);
+    let to_process = max.min(queue.len());
+
+    let mut remaining = Vec::new(env);
+    let mut delivered = 0u32;
+    for (i, mut pending) in queue.iter().enumerate() {
+        if i as u32 >= to_process {
+            remaining.push_back(pending);
+            continue;
+        }
+
+        pending.attempts += 1;
+        match invoke_on_upgrade(
+            env,
+            &pending.subscriber,
+            &pending.from_version,
+            &pending.to_version,
+            pending.proposal_id,
+        ) {
+            Ok(()) => {
+                mark_notification_delivered(env, pending.proposal_id, &pending.subscriber);
+                delivered += 1;
+            }
+            Err(code) if pending.attempts >= max_attempts => {
+                register_compensation(
+                    env,
+                    CompensationKind::Notification,
+                    &pending.subscriber,
+                    pending.proposal_id,
+                    code,
+                );
+                emit_event(env, MOD_UPGRADE, ACT_NOTIFY_DROPPED, pending);
+            }
+            Err(_) => remaining.push_back(pending),
+        }
+    }
+
+    storage::set_retry_queue(env, &remaining);
+    delivered
+}
+
+/// Record a side effect that failed after the version switch. It is retried
+/// by `run_compensations` from now on.
+fn register_compensation(
+    env: &Env,
+    kind: CompensationKind,
+    subject: &Address,
+    proposal_id: u64,
+    last_error: u32,
+) {
+    let compensation = PendingCompensation {
+        id: storage::take_next_compensation_id(env),
+        kind,
+        subject: subject.clone(),
+        proposal_id,
+        attempts: 0,
+        next_attempt_at: time::now(env),
+        last_error,
+        status: CompensationStatus::Pending,
+    };
+    let mut compensations = storage::get_compensations(env);
+    compensations.set(compensation.id, compensation.clone());
+    storage::set_compensations(env, &compensations);
+
+    emit_event(env, MOD_UPGRADE, ACT_COMPENSATION_QUEUED, compensation);
+}
+
+/// Every recorded compensation, pending or given up, oldest first
+pub fn get_pending_compensations(env: &Env) -> Vec<PendingCompensation> {
+    storage::get_compensations(env).values()
+}
+
+/// Retry up to `max` due compensations, oldest first. Anyone may call this.
+/// After `n` failed retries the next waits `n * COMPENSATION_BACKOFF`
+/// seconds; after `MAX_COMPENSATION_ATTEMPTS` the entry is given up and
+/// stays until governance dismisses it. Returns how many succeeded.
+pub fn run_compensations(env: &Env, max: u32) -> u32 {
+    let mut compensations = storage::get_compensations(env);
+    let now = time::now(env);
+
+    let mut processed = 0u32;
+    let mut compensated = 0u32;
+    for (id, mut compensation) in compensations.clone().iter() {
+        if processed >= max {
+            break;
+        }
+        if compensation.status != CompensationStatus::Pending || compensation.next_attempt_at > now
+        {
+            continue;
+        }
+        processed += 1;
+
+        match retry_compensation(env, &compensation) {
+            Ok(()) => {
+                compensations.remove(id);
+                compensated += 1;
+                emit_event(env, MOD_UPGRADE, ACT_COMPENSATED, compensation);
+            }
+            Err(code) => {
+                compensation.attempts += 1;
+                compensation.last_error = code;
+                if compensation.attempts >= storage::MAX_COMPENSATION_ATTEMPTS {
+                    compensation.status = CompensationStatus::GivenUp;
+                    emit_event(
+                        env,
+                        MOD_UPGRADE,
+                        ACT_COMPENSATION_GAVE_UP,
+                        compensation.clone(),
+                    );
+                } else {
+                    compensation.next_attempt_at =
+                        now + storage::COMPENSATION_BACKOFF * compensation.attempts as u64;
+                }
+                compensations.set(id, compensation);
+            }
+        }
+    }
+
+    storage::set_compensations(env, &compensations);
+    compensated
+}
+
+/// Run a compensation's side effect once more
+fn retry_compensation(env: &Env, compensation: &PendingCompensation) -> Result<(), u32> {
+    match compensation.kind {
+        CompensationKind::Notification => {
+            let receipt =
+                storage::get_execution_receipt(env, compensation.proposal_id).ok_or(0u32)?;
+            invoke_on_upgrade(
+                env,
+                &compensation.subject,
+                &receipt.from_version,
+                &receipt.to_version,
+                compensation.proposal_id,
+            )?;
+            mark_notification_delivered(env, compensation.proposal_id, &compensation.subject);
+            Ok(())
+        }
+        CompensationKind::BondSettlement => {
+            let proposal =
+                storage::get_upgrade_proposal(env, compensation.proposal_id).ok_or(0u32)?;
+            settle_bond(env, &proposal)
+        }
+    }
+}
+
+/// Remove a compensation that was given up, accepting that its side effect
+/// will not happen
+pub fn dismiss_compensation(
+    env: &Env,
+    caller: &Address,
+    compensation_id: u64,
+) -> Result<(), Error> {
+    caller.require_auth();
+
+    authorize(env, caller, Action::DismissCompensation)?;
+
+    let mut compensations = storage::get_compensations(env);
+    let compensation = compensations
+        .get(compensation_id)
+        .ok_or(ProposalError::CompensationNotFound)?;
+    if compensation.status != CompensationStatus::GivenUp {
+        return Err(ProposalError::CompensationPending.into());
+    }
+    compensations.remove(compensation_id);
+    storage::set_compensations(env, &compensations);
+
+    emit_event(env, MOD_UPGRADE, ACT_COMPENSATION_DISMISSED, compensation);
+
+    Ok(())
+}
+
+/// Flip a subscriber's recorded outcome to success after a retry lands
+fn mark_notification_delivered(env: &Env, proposal_id: u64, subscriber: &Address) {
+    let mut results = storage::get_notification_results(env, proposal_id);
+    for i in 0..results.len() {
+        let mut result = results.get_unchecked(i);
+        if result.subscriber == *subscriber {
+            result.success = true;
+            results.set(i, result);
+        }
+    }
+    storage::set_notification_results(env, proposal_id, &results);
+}
+
+/// Set how many delivery attempts a notification gets before it is dropped
+pub fn set_notification_max_attempts(
+    env: &Env,
+    caller: &Address,
+    max_attempts: u32,
+) -> Result<(), Error> {
+    caller.require_auth();
+
+    authorize(env, caller, Action::SetNotificationRetries)?;
+    if max_attempts == 0 {
+        return Err(UpgradeError::InvalidMaxAttempts.into());
+    }
+
+    storage::set_notification_max_attempts(env, max_attempts);
+
+    emit_event(env, MOD_UPGRADE, ACT_UPDATED, max_attempts);
+
+    Ok(())
+}
+
+/// Register a contract to be notified after executed upgrades
+pub fn add_subscriber(env: &Env, caller: &Address, subscriber: &Address) -> Result<(), Error> {
+    caller.require_auth();
+
+    authorize(env, caller, Action::ManageSubscribers)?;
+
+    let mut subscribers = storage::get_subscribers(env);
+    if subscribers.contains(subscriber) {
+        return Err(UpgradeError::SubscriberExists.into());
+    }
+    if subscribers.len() >= storage::MAX_UPGRADE_SUBSCRIBERS {
+        return Err(UpgradeError::TooManySubscribers.into());
+    }
+
+    subscribers.push_back(subscriber.clone());
+    storage::set_subscribers(env, &subscribers);
+
+    emit_event(env, MOD_UPGRADE, ACT_SUBSCRIBER_ADDED, subscriber.clone());
+
+    Ok(())
+}
+
+/// Stop notifying a contract about executed upgrades
+pub fn remove_subscriber(env: &Env, caller: &Address, subscriber: &Address) -> Result<(), Error> {
+    caller.require_auth();
+
+    authorize(env, caller, Action::ManageSubscribers)?;
+
+    let mut subscribers = storage::get_subscribers(env);
+    let index = subscribers
+        .first_index_of(subscriber)
+        .ok_or(UpgradeError::SubscriberNotFound)?;
+    subscribers.remove(index);
+    storage::set_subscribers(env, &subscribers);
+
+    emit_event(env, MOD_UPGRADE, ACT_SUBSCRIBER_REMOVED, subscriber.clone());
+
+    Ok(())
+}
+
+/// Perform emergency upgrade bypassing the normal governance process.
+/// The version may change by at most the configured `emergency_max_bump`.
+pub fn emergency_upgrade(
+    env: &Env,
+    caller: &Address,
+    new_contract_address: &Address,
+    new_version: &Version,
+) -> Result<(), Error> {
+    apply_emergency_upgrade(env, caller, new_contract_address, new_version, None)
+}
+
+/// Perform an emergency upgrade whose version change exceeds
+/// `emergency_max_bump`, backed by an executed multisig emergency operation.
+/// Each operation can authorize one such upgrade.
+pub fn emergency_upgrade_with_override(
+    env: &Env,
+    caller: &Address,
+    new_contract_address: &Address,
+    new_version: &Version,
+    multisig_op_id: u64,
+) -> Result<(), Error> {
+    apply_emergency_upgrade(
+        env,
+        caller,
+        new_contract_address,
+        new_version,
+        Some(multisig_op_id),
+    )
+}
+
+fn apply_emergency_upgrade(
+    env: &Env,
+    caller: &Address,
+    new_contract_address: &Address,
+    new_version: &Version,
+    override_op_id: Option<u64>,
+) -> Result<(), Error> {
+    caller.require_auth();
+
+    if proxy_storage::is_frozen(env) {
+        return Err(GuardError::UpgradesFrozen.into());
+    }
+    // Check if emergency upgrades are enabled
+    if storage::is_emergency_window_expired(env) {
+        return Err(GuardError::EmergencyWindowExpired.into());
+    }
+    if !storage::is_emergency_upgrade_enabled(env) {
+        return Err(UpgradeError::EmergencyDisabled.into());
+    }
+    if storage::is_safe_mode(env) {
+        return Err(ProposalError::SafeMode.into());
+    }
+
+    // Only governance address can perform emergency upgrades
+    authorize(env, caller, Action::EmergencyUpgrade)?;
+
+    let bump = storage::get_current_version(env).diff_kind(new_version);
+    if bump > storage::get_emergency_max_bump(env) {
+        let op_id = override_op_id.ok_or(UpgradeError::EmergencyBumpTooLarge)?;
+        if !is_valid_emergency_override(env, op_id) {
+            return Err(ProposalError::InvalidEmergencyOverride.into());
+        }
+        storage::mark_emergency_override_used(env, op_id);
+
+        log_emergency_action(
+            env,
+            String::from_str(env, "EmergencyBumpOverride"),
+            caller.clone(),
+            String::from_str(env, "Multisig approved a larger emergency version bump"),
+        );
+        emit_event(env, MOD_UPGRADE, ACT_EMERGENCY_OVERRIDE, (op_id, bump));
+    }
+
+    // Switch the proxy too, so its history has no gap for the emergency path
+    if proxy_storage::PROXY_CONFIG.has(env) {
+        proxy_impl::apply_emergency_upgrade(env, caller, new_contract_address);
+    }
+
+    // Update the current version directly
+    storage::set_version_implementation(env, new_version, new_contract_address);
+    storage::set_current_version(env, new_version, caller, None);
+    storage::push_emergency_execution(
+        env,
+        &ExecutionRecord {
+            executed_by: caller.clone(),
+            executed_at: time::now(env),
+            to_version: new_version.clone(),
+            implementation: new_contract_address.clone(),
+            emergency: true,
+        },
+    );
+    storage::set_last_emergency_upgrade(
+        env,
+        &EmergencyUpgradeRecord {
+            caller: caller.clone(),
+            version: new_version.clone(),
+            implementation: new_contract_address.clone(),
+            timestamp: time::now(env),
+        },
+    );
+
+    // Emit emergency upgrade event
+    emit_event(env, MOD_UPGRADE, ACT_EMERGENCY_UPGRADE, new_version.clone());
+
+    // A further emergency upgrade needs governance to enable the path again
+    if storage::is_emergency_one_shot(env) {
+        storage::set_emergency_upgrade_enabled(env, false);
+        emit_event(env, MOD_UPGRADE, ACT_UPDATED, false);
+    }
+
+    Ok(())
+}
+
+/// An executed, unspent multisig emergency operation
+fn is_valid_emergency_override(env: &Env, multisig_op_id: u64) -> bool {
+    match get_operation(env, multisig_op_id) {
+        Some(op) => {
+            op.status == OperationStatus::Executed
+                && op.op_type == OperationType::EmergencyAction
+                && !storage::is_emergency_override_used(env, multisig_op_id)
+        }
+        None => false,
+    }
+}
+
+/// Set the largest version change emergency upgrades may make without an override
+pub fn set_emergency_max_bump(
+    env: &Env,
+    caller: &Address,
+    max_bump: BumpKind,
+) -> Result<(), Error> {
+    caller.require_auth();
+
+    authorize(env, caller, Action::SetEmergencyMaxBump)?;
+
+    storage::set_emergency_max_bump(env, max_bump);
+
+    log_emergency_action(
+        env,
+        String::from_str(env, "EmergencyMaxBumpSet"),
+        caller.clone(),
+        String::from_str(env, "Emergency upgrade version limit changed"),
+    );
+    emit_event(env, MOD_UPGRADE, ACT_EMERGENCY_MAX_BUMP, max_bump);
+
+    Ok(())
+}
+
+/// Choose whether an emergency upgrade disables emergency upgrades again
+/// once it has run; turning this off restores repeatable emergency upgrades
+pub fn set_emergency_one_shot(env: &Env, caller: &Address, one_shot: bool) -> Result<(), Error> {
+    caller.require_auth();
+
+    authorize(env, caller, Action::ToggleEmergency)?;
+
+    storage::set_emergency_one_shot(env, one_shot);
+
+    log_emergency_action(
+        env,
+        String::from_str(env, "EmergencyOneShotSet"),
+        caller.clone(),
+        String::from_str(env, "Emergency upgrade one-shot mode changed"),
+    );
+    emit_event(env, MOD_UPGRADE, ACT_EMERGENCY_ONE_SHOT, one_shot);
+
+    Ok(())
+}
+
+/// Enable emergency upgrades until `expiry`, inclusive; afterwards they are
+/// refused as if disabled. Toggling replaces the window.
+pub fn enable_emergency_upgrades_until(
+    env: &Env,
+    caller: &Address,
+    expiry: u64,
+) -> Result<(), Error> {
+    caller.require_auth();
+
+    authorize(env, caller, Action::ToggleEmergency)?;
+    if expiry <= time::now(env) {
+        return Err(GuardError::ExpiryInPast.into());
+    }
+
+    storage::set_emergency_upgrade_window(env, expiry);
+
+    emit_event(env, MOD_UPGRADE, ACT_EMERGENCY_WINDOW, expiry);
+
+    Ok(())
+}
+
+/// Enable or disable emergency upgrades
+pub fn toggle_emergency_upgrades(env: &Env, caller: &Address, enable: bool) -> Result<(), Error> {
+    caller.require_auth();
+
+    // Only governance address can enable/disable emergency upgrades
+    authorize(env, caller, Action::ToggleEmergency)?;
+
+    storage::set_emergency_upgrade_enabled(env, enable);
+
+    emit_event(env, MOD_UPGRADE, ACT_UPDATED, enable);
+
+    Ok(())
+}
+
+/// Set how long governance may stay silent before `check_heartbeat` can put
+/// the contract into safe mode (0 disables the switch). Counts as a heartbeat.
+pub fn set_max_silence(env: &Env, caller: &Address, max_silence: u64) -> Result<(), Error> {
+    caller.require_auth();
+
+    authorize(env, caller, Action::SetMaxSilence)?;
+
+    storage::set_max_silence(env, max_silence);
+    storage::set_last_heartbeat(env, time::now(env));
+
+    emit_event(env, MOD_UPGRADE, ACT_UPDATED, max_silence);
+
+    Ok(())
+}
+
+/// Set the minimum separation between a governance change and a guardian change
+pub fn set_dual_control_window(env: &Env, caller: &Address, window: u64) -> Result<(), Error> {
+    caller.require_auth();
+
+    authorize(env, caller, Action::SetDualControlWindow)?;
+
+    storage::set_dual_control_window(env, window);
+
+    emit_event(env, MOD_UPGRADE, ACT_DUAL_CONTROL_WINDOW_SET, window);
+
+    Ok(())
+}
+
+/// Turn the contract-era topics on event meta-topics on or off
+pub fn set_event_version_topics(env: &Env, caller: &Address, enabled: bool) -> Result<(), Error> {
+    caller.require_auth();
+
+    authorize(env, caller, Action::SetGovernanceConfig)?;
+
+    set_version_topics(env, enabled);
+
+    emit_event(env, MOD_UPGRADE, ACT_UPDATED, enabled);
+
+    Ok(())
+}
+
+/// Set how much detail leveled event payloads carry (see `EventVerbosity`)
+pub fn set_event_verbosity(
+    env: &Env,
+    caller: &Address,
+    verbosity: EventVerbosity,
+) -> Result<(), Error> {
+    caller.require_auth();
+
+    authorize(env, caller, Action::SetGovernanceConfig)?;
+
+    store_event_verbosity(env, verbosity);
+
+    emit_event(env, MOD_UPGRADE, ACT_UPDATED, verbosity);
+
+    Ok(())
+}
+
+/// Deprecate `version` as an upgrade target. New proposals for it are
+/// refused, and approved ones can no longer execute.
+pub fn deprecate_version(env: &Env, caller: &Address, version: &Version) -> Result<(), Error> {
+    caller.require_auth();
+
+    authorize(env, caller, Action::DeprecateVersion)?;
+
+    storage::deprecate_version(env, version);
+
+    emit_event(env, MOD_UPGRADE, ACT_VERSION_DEPRECATED, version.clone());
+
+    Ok(())
+}
+
+/// Record that governance is alive, leaving safe mode if it was active
+pub fn heartbeat(env: &Env, governance: &Address) -> Result<(), Error> {
+    governance.require_auth();
+
+    authorize(env, governance, Action::Heartbeat)?;
+
+    let now = time::now(env);
+    storage::set_last_heartbeat(env, now);
+    emit_event(env, MOD_UPGRADE, ACT_HEARTBEAT, now);
+
+    if storage::is_safe_mode(env) {
+        storage::set_safe_mode(env, false);
+        emit_event(env, MOD_UPGRADE, ACT_SAFE_MODE_EXITED, now);
+    }
+
+    Ok(())
+}
+
+/// Enter safe mode if governance has been silent for longer than the
+/// configured window. Anyone may call this. Safe mode blocks new proposals,
+/// emergency upgrades and proxy upgrades, but open proposals can still be
+/// voted on and finalized. Returns whether safe mode is active.
+pub fn check_heartbeat(env: &Env) -> bool {
+    if storage::is_safe_mode(env) {
+        return true;
+    }
+
+    let max_silence = storage::get_max_silence(env);
+    let now = time::now(env);
+    let last = storage::get_last_heartbeat(env);
+    if max_silence == 0 || now.saturating_sub(last) <= max_silence {
+        return false;
+    }
+
+    storage::set_safe_mode(env, true);
+    emit_event(env, MOD_UPGRADE, ACT_SAFE_MODE_ENTERED, (last, now));
+
+    true
+}
+
+/// Pause upgrade governance: proposing, voting and starting executions are
+/// refused until `resume_governance`. Continuing an in-progress migration is
+/// remediation, not governance, and stays callable. Pausing while a live
+/// migration is half-applied leaves state between two versions for as long
+/// as the pause lasts, so it needs `force`; a forced pause records those
+/// migrations as stranded and `health` flags them until they finish.
+pub fn pause_governance(env: &Env, caller: &Address, force: bool) -> Result<(), UpgradeError> {
+    caller.require_auth();
+    authorize(env, caller, Action::PauseGovernance)?;
+
+    if storage::is_governance_paused(env) {
+        return Err(UpgradeError::GovernancePaused);
+    }
+    let in_progress = storage::get_migrations_in_progress(env);
+    if !in_progress.is_empty() {
+        if !force {
+            return Err(UpgradeError::MigrationWouldStrand);
+        }
+        storage::set_stranded_migrations(env, &in_progress);
+        emit_event(env, MOD_UPGRADE, ACT_MIGRATION_STRANDED, in_progress);
+    }
+
+    let paused_at = time::now(env);
+    storage::set_governance_pause(
+        env,
+        &GovernancePause {
+            paused_by: caller.clone(),
+            paused_at,
+        },
+    );
+    emit_event(
+        env,
+        MOD_UPGRADE,
+        ACT_GOVERNANCE_PAUSED,
+        (caller.clone(), paused_at, force),
+    );
+
+    Ok(())
+}
+
+/// Lift a governance pause. Stranded migrations are no longer blocked by
+/// anything, so their attention flag is cleared.
+pub fn resume_governance(env: &Env, caller: &Address) -> Result<(), UpgradeError> {
+    caller.require_auth();
+    authorize(env, caller, Action::ResumeGovernance)?;
+
+    if !storage::is_governance_paused(env) {
+        return Err(UpgradeError::GovernanceNotPaused);
+    }
+    storage::clear_governance_pause(env);
+    storage::clear_stranded_migrations(env);
+    emit_event(env, MOD_UPGRADE, ACT_GOVERNANCE_RESUMED, caller.clone());
+
+    Ok(())
+}
+
+/// Pause and migration state, with an attention flag while a forced pause
+/// has stranded a migration or a post-execution check failure is
+/// unacknowledged
+pub fn health(env: &Env) -> HealthReport {
+    let stranded_migrations = storage::get_stranded_migrations(env);
+    let unacknowledged_post_check =
+        storage::get_post_check_failure(env).map(|failure| failure.proposal_id);
+    HealthReport {
+        governance_paused: storage::is_governance_paused(env),
+        proxy_paused: proxy_impl::is_paused(env),
+        migrations_in_progress: storage::get_migrations_in_progress(env),
+        needs_attention: !stranded_migrations.is_empty() || unacknowledged_post_check.is_some(),
+        stranded_migrations,
+        unacknowledged_post_check,
+    }
+}
+
+/// Run upkeep tasks in order, sharing a budget of `max_work` work units.
+/// Anyone may act as keeper; each task must have rested for the configured
+/// cooldown since its last run. With a bounty configured the keeper is paid
+/// per completed unit, as far as the contract's token balance allows.
+pub fn run_maintenance(
+    env: &Env,
+    keeper: &Address,
+    tasks: &Vec<MaintenanceTask>,
+    max_work: u32,
+) -> Result<MaintenanceReport, UpgradeError> {
+    keeper.require_auth();
+
+    let config = storage::get_keeper_config(env);
+    let now = time::now(env);
+
+    let mut completed = Vec::new(env);
+    let mut work_units = 0u32;
+    for task in tasks.iter() {
+        if let Some(last_run) = storage::get_task_last_run(env, task) {
+            if now < last_run.saturating_add(config.cooldown) {
+                return Err(UpgradeError::TaskCoolingDown);
+            }
+        }
+        let units = run_maintenance_task(env, task, max_work - work_units);
+        storage::set_task_last_run(env, task, now);
+        completed.push_back((task, units));
+        work_units += units;
+    }
+
+    let paid = pay_keeper(env, &config, keeper, work_units);
+
+    let mut stats = storage::get_keeper_stats(env, keeper);
+    stats.runs += 1;
+    stats.work_units += work_units;
+    stats.paid += paid;
+    stats.last_run_at = now;
+    storage::set_keeper_stats(env, keeper, &stats);
+
+    emit_event(
+        env,
+        MOD_UPGRADE,
+        ACT_MAINTENANCE_RUN,
+        (keeper.clone(), work_units, paid),
+    );
+
+    Ok(MaintenanceReport {
+        completed,
+        work_units,
+        paid,
+    })
+}
+
+/// Dispatch one task with at most `budget` units of work
+fn run_maintenance_task(env: &Env, task: MaintenanceTask, budget: u32) -> u32 {
+    match task {
+        MaintenanceTask::SweepExpired => sweep_expired(env, budget),
+        MaintenanceTask::RetryNotifications => retry_notifications(env, budget),
+        MaintenanceTask::RunCompensations => run_compensations(env, budget),
+        MaintenanceTask::RetryArchival => retry_archival(env, budget),
+        MaintenanceTask::BumpTtl => storage::bump_core_ttls(env, budget),
+    }
+}
+
+/// Finalize up to `max` pending proposals past their voting deadline, then
+/// expire approved proposals whose execution window has closed
+fn sweep_expired(env: &Env, max: u32) -> u32 {
+    let now = time::now(env);
+    let mut swept = 0u32;
+    for proposal_id in storage::get_status_index(env, UpgradeStatus::Pending).iter() {
+        if swept >= max {
+            break;
+        }
+        let Some(proposal) = storage::get_upgrade_proposal(env, proposal_id) else {
+            continue;
+        };
+        if proposal.voting_deadline == 0 || now <= proposal.voting_deadline {
+            continue;
+        }
+        if finalize_proposal(env, proposal_id).is_ok() {
+            swept += 1;
+        }
+    }
+    for proposal_id in storage::get_status_index(env, UpgradeStatus::Approved).iter() {
+        if swept >= max {
+            break;
+        }
+        if expire_lapsed_approval(env, proposal_id) == Ok(true) {
+            swept += 1;
+        }
+    }
+    swept
+}
+
+/// Pay the keeper's bounty for `work_units`; returns the amount paid, which
+/// is zero when no bounty is configured or the transfer fails
+fn pay_keeper(env: &Env, config: &KeeperConfig, keeper: &Address, work_units: u32) -> i128 {
+    let Some(token) = &config.token else {
+        return 0;
+    };
+    let amount = config.bounty_per_unit.saturating_mul(work_units as i128);
+    if amount <= 0 {
+        return 0;
+    }
+    let transfer =
+        TokenClient::new(env, token).try_transfer(&env.current_contract_address(), keeper, &amount);
+    if matches!(transfer, Ok(Ok(()))) {
+        amount
+    } else {
+        0
+    }
+}
 
-/// Create a new upgrade proposal
-pub fn propose_upgrade(
+/// Configure keeper bounties and task cooldowns
+pub fn set_keeper_config(
     env: &Env,
-    proposer: &Address,
-    new_contract_address: &Address,
-    target_version: &Version,
-    description: String,
-) -> u64 {
-    // Verify the proposer has the right to propose upgrades
-    let governance_addr = storage::get_governance_address(env);
-    proposer.require_auth();
+    caller: &Address,
+    config: &KeeperConfig,
+) -> Result<(), UpgradeError> {
+    caller.require_auth();
+    authorize(env, caller, Action::SetGovernanceConfig)?;
 
-    // In a real implementation, we might check if the proposer has sufficient voting power
-    // For now, we just verify the governance address
+    if config.bounty_per_unit < 0 {
+        return Err(UpgradeError::InvalidKeeperBounty);
+    }
+    storage::set_keeper_config(env, config);
 
-    // Generate a new proposal ID (in practice, this might be more sophisticated)
-    let proposal_id = env
-        .storage()
-        .instance()
-        .get(&symbol_short!("nxt_prop"))
-        .unwrap_or(1u64);
-    env.storage()
-        .instance()
-        .set(&symbol_short!("nxt_prop"), &(proposal_id + 1));
+    emit_event(env, MOD_UPGRADE, ACT_UPDATED, config.clone());
 
-    let proposal = UpgradeProposal {
-        id: proposal_id,
-        proposer: proposer.clone(),
-        new_contract_address: new_contract_address.clone(),
-        version: target_version.clone(),
-        description,
-        timestamp: env.ledger().timestamp(),
-        status: UpgradeStatus::Pending,
-        votes_for: 0,
-        votes_against: 0,
-        total_voters: 0, // Will be calculated when voting begins
+    Ok(())
+}
+
+/// Apply one page of the genesis voter list. Pages must arrive in order;
+/// re-submitting a page that was already applied is a no-op returning false,
+/// so a failed transaction can simply be retried. Proposals stay locked from
+/// the first page until the last one lands or governance seals the list.
+pub fn load_genesis_voters(
+    env: &Env,
+    governance: &Address,
+    page: &Vec<(Address, u32)>,
+    page_index: u32,
+    total_pages: u32,
+) -> Result<bool, UpgradeError> {
+    governance.require_auth();
+    authorize(env, governance, Action::GrantVotingPower)?;
+
+    let mut progress = match storage::get_genesis_progress(env) {
+        Some(progress) => progress,
+        // Genesis comes before governance; a running deployment grants power instead
+        None if !storage::get_proposal_ids(env).is_empty() => {
+            return Err(UpgradeError::GenesisSealed)
+        }
+        None => GenesisProgress {
+            total_pages,
+            pages_applied: 0,
+            voters: 0,
+            sealed: false,
+        },
     };
+    if total_pages == 0 || total_pages != progress.total_pages || page_index >= total_pages {
+        return Err(UpgradeError::InvalidGenesisPages);
+    }
+    if page_index < progress.pages_applied {
+        return Ok(false);
+    }
+    if progress.sealed {
+        return Err(UpgradeError::GenesisSealed);
+    }
+    if page_index > progress.pages_applied {
+        return Err(UpgradeError::GenesisPageOutOfOrder);
+    }
+    if page.len() > MAX_GENESIS_PAGE_LEN {
+        return Err(UpgradeError::GenesisPageTooLarge);
+    }
 
-    storage::store_upgrade_proposal(env, &proposal);
+    // Genesis power is active at once; there is nothing yet for it to sway
+    for (voter, power) in page.iter() {
+        storage::set_voting_power(env, &voter, power);
+    }
+    progress.pages_applied += 1;
+    progress.voters += page.len();
+    progress.sealed = progress.pages_applied == total_pages;
+    storage::set_genesis_progress(env, &progress);
 
-    // Emit event for the proposal
-    emit_event(env, MOD_UPGRADE, ACT_UPGRADE_PROPOSED, proposal_id);
+    emit_event(
+        env,
+        MOD_UPGRADE,
+        ACT_GENESIS_PAGE,
+        (page_index, total_pages, page.len()),
+    );
+    if progress.sealed {
+        emit_event(env, MOD_UPGRADE, ACT_GENESIS_SEALED, progress);
+    }
 
-    proposal_id
+    Ok(true)
 }
 
-/// Vote on an upgrade proposal
-pub fn vote_on_proposal(
+/// Close the genesis voter list before its last page, unlocking proposals
+/// with the voters loaded so far. Sealing before any page is loaded skips
+/// genesis entirely.
+pub fn seal_genesis_voters(env: &Env, governance: &Address) -> Result<(), UpgradeError> {
+    governance.require_auth();
+    authorize(env, governance, Action::GrantVotingPower)?;
+
+    let mut progress = storage::get_genesis_progress(env).unwrap_or(GenesisProgress {
+        total_pages: 0,
+        pages_applied: 0,
+        voters: 0,
+        sealed: false,
+    });
+    if progress.sealed {
+        return Err(UpgradeError::GenesisSealed);
+    }
+    progress.sealed = true;
+    storage::set_genesis_progress(env, &progress);
+
+    emit_event(env, MOD_UPGRADE, ACT_GENESIS_SEALED, progress);
+
+    Ok(())
+}
+
+/// Register a migration plan for an upgrade
+pub fn register_migration_plan(
     env: &Env,
-    voter: &Address,
+    caller: &Address,
     proposal_id: u64,
-    vote_for: bool,
-) -> Result<(), &'static str> {
-    voter.require_auth();
+    migration_plan: &MigrationPlan,
+) -> Result<(), Error> {
+    caller.require_auth();
 
-    // Record the vote
-    storage::record_vote(env, proposal_id, voter, vote_for)?;
+    // Only governance address can register migration plans
+    authorize(env, caller, Action::RegisterMigrationPlan)?;
 
-    // Check if proposal has reached required threshold
-    if let Some(proposal) = storage::get_upgrade_proposal(env, proposal_id) {
-        let _total_votes = proposal.votes_for + proposal.votes_against;
-        // Simple majority threshold - in real implementation this could be configurable
-        let required_votes = (proposal.total_voters / 2) + 1;
+    storage::store_migration_plan(env, proposal_id, migration_plan);
+
+    // A new plan changes what a pending proposal's voters agreed to
+    if let Some(mut proposal) = storage::get_upgrade_proposal(env, proposal_id) {
+        if proposal.status == UpgradeStatus::Pending {
+            invalidate_votes(env, &mut proposal);
+        }
+    }
+
+    emit_event(env, MOD_UPGRADE, ACT_UPDATED, proposal_id);
+
+    Ok(())
+}
 
-        if proposal.votes_for >= required_votes {
-            storage::update_proposal_status(env, proposal_id, UpgradeStatus::Approved);
-            emit_event(env, MOD_UPGRADE, ACT_APPROVED, proposal_id);
-        } else if proposal.votes_against >= required_votes {
-            storage::update_proposal_status(env, proposal_id, UpgradeStatus::Rejected);
-            emit_event(env, MOD_UPGRADE, ACT_REJECTED, proposal_id);
+/// Give proposals stored before the voting power snapshot existed their
+/// snapshot, the schema 1 to 2 step; returns how many were rewritten
+pub fn migrate_proposal_snapshots(env: &Env, caller: &Address) -> Result<u32, Error> {
+    migrate_storage_schema(env, caller, 2).map_err(Error::from)
+}
+
+/// Move the stored upgrade layout to schema `target`, one step at a time.
+/// A target already reached is a no-op, so steps can be replayed safely;
+/// skipping a step fails. Returns how many entries were rewritten.
+pub fn migrate_storage_schema(
+    env: &Env,
+    caller: &Address,
+    target: u32,
+) -> Result<u32, UpgradeError> {
+    caller.require_auth();
+    authorize(env, caller, Action::MigrateStorage)?;
+
+    let found = storage::get_storage_schema(env);
+    if target <= found {
+        return Ok(0);
+    }
+    if target != found + 1 || target > STORAGE_SCHEMA {
+        return Err(SchemaMismatch {
+            found,
+            expected: target - 1,
         }
+        .into());
     }
 
+    let migrated = match found {
+        1 => storage::migrate_proposal_snapshots(env),
+        2 => storage::migrate_governance_config_threshold(env),
+        3 => storage::migrate_separation_of_duties(env),
+        4 => storage::migrate_proposal_id_index(env),
+        5 => proxy_storage::migrate_upgrade_history(env),
+        6 => storage::migrate_registry_power(env),
+        7 => storage::migrate_proposal_tallies(env),
+        8 | 9 => proxy_storage::migrate_upgrade_transactions(env),
+        10 => storage::migrate_vote_receipt_weights(env),
+        11 => storage::migrate_delegator_index(env),
+        12 => storage::migrate_track_index(env),
+        _ => 0,
+    };
+    storage::set_storage_schema(env, target);
+
+    emit_event(
+        env,
+        MOD_UPGRADE,
+        ACT_STORAGE_MIGRATED,
+        (SCHEMA_TAG, target, migrated),
+    );
+
+    Ok(migrated)
+}
+
+/// Why `migrate_entries` stopped before the last entry
+enum MigrationHalt {
+    /// The call trapped or did not return a `MigrationVerdict`
+    CallFailed(CallFailure),
+    Abort(Symbol, u32),
+    Defer(Symbol, u64),
+}
+
+/// Migrate the entries of `namespace` from `*next_index` on by calling the
+/// plan's selector on `implementation`, moving `next_index` past every entry
+/// that migrated
+fn migrate_entries(
+    env: &Env,
+    implementation: &Address,
+    plan: &MigrationPlan,
+    namespace: &MigrationNamespace,
+    next_index: &mut u32,
+) -> Result<(), MigrationHalt> {
+    let keys = storage::get_namespace_keys(env, namespace);
+    while *next_index < keys.len() {
+        let key = keys.get_unchecked(*next_index);
+        let Some(value) = storage::get_migration_entry(env, namespace, &key) else {
+            *next_index += 1;
+            continue;
+        };
+        let args: Vec<Val> = (
+            plan.from_version.clone(),
+            plan.to_version.clone(),
+            key.clone(),
+            value,
+        )
+            .into_val(env);
+        let verdict = calls::invoke::<MigrationVerdict>(
+            env,
+            implementation,
+            &plan.migration_function_selector,
+            args,
+        )
+        .map_err(MigrationHalt::CallFailed)?;
+
+        match verdict {
+            MigrationVerdict::Ok(migrated) => {
+                storage::set_migration_entry(env, namespace, &key, &migrated);
+                *next_index += 1;
+            }
+            MigrationVerdict::Abort(code) => return Err(MigrationHalt::Abort(key, code)),
+            MigrationVerdict::Defer(retry_after) => {
+                return Err(MigrationHalt::Defer(key, retry_after))
+            }
+        }
+    }
     Ok(())
 }
 
-/// Execute an approved upgrade
-pub fn execute_upgrade(
+/// Run a proposal's migration over every entry in `target_namespace`,
+/// calling the plan's selector on the new implementation for each one (see
+/// `MigrationVerdict`). A live migration keeps its progress, so a deferred
+/// run resumes where it stopped; an abort fails the proposal. Either halt is
+/// returned as the progress it left, so the caller can finish without
+/// rolling it back. Rehearsals always start from the first entry and treat
+/// either verdict as an error.
+fn perform_state_migration(
+    env: &Env,
+    proposal: &UpgradeProposal,
+    plan: &MigrationPlan,
+    target_namespace: &MigrationNamespace,
+) -> Result<MigrationProgress, Error> {
+    let live = *target_namespace == MigrationNamespace::Live;
+    let mut progress = live
+        .then(|| storage::get_migration_progress(env, proposal.id))
+        .flatten()
+        .filter(|progress| progress.status == MigrationStatus::InProgress)
+        .unwrap_or(MigrationProgress {
+            proposal_id: proposal.id,
+            status: MigrationStatus::InProgress,
+            next_index: 0,
+            not_before: 0,
+            abort_code: 0,
+        });
+    if time::now(env) < progress.not_before {
+        return Err(ProposalError::MigrationDeferred.into());
+    }
+    if progress.next_index == 0 {
+        emit_event(env, MOD_UPGRADE, ACT_STARTED, plan.from_version.clone());
+    }
+
+    let halt = migrate_entries(
+        env,
+        &proposal.new_contract_address,
+        plan,
+        target_namespace,
+        &mut progress.next_index,
+    );
+    let halt_error: Option<Error> = match halt {
+        Ok(()) => {
+            progress.status = MigrationStatus::Completed;
+            None
+        }
+        Err(MigrationHalt::CallFailed(failure)) => return Err(failure.into()),
+        Err(MigrationHalt::Abort(key, code)) => {
+            progress.status = MigrationStatus::Aborted;
+            progress.abort_code = code;
+            emit_event(
+                env,
+                MOD_UPGRADE,
+                ACT_MIGRATION_ABORTED,
+                (proposal.id, key, code),
+            );
+            Some(ProposalError::MigrationAborted.into())
+        }
+        Err(MigrationHalt::Defer(key, retry_after)) => {
+            progress.not_before = retry_after;
+            emit_event(
+                env,
+                MOD_UPGRADE,
+                ACT_MIGRATION_DEFERRED,
+                (proposal.id, key, retry_after),
+            );
+            Some(ProposalError::MigrationDeferred.into())
+        }
+    };
+    if let (false, Some(error)) = (live, halt_error) {
+        return Err(error);
+    }
+
+    if live {
+        // Entries already migrated stay migrated, so progress is kept on a halt too
+        storage::set_migration_progress(env, &progress);
+        if progress.status == MigrationStatus::Aborted {
+            let failed = if proposal.status == UpgradeStatus::AwaitingProxy {
+                UpgradeStatus::NeedsAttention
+            } else {
+                UpgradeStatus::Failed
+            };
+            transition(env, proposal.id, failed, MIGRATION_ABORTED_REASON)?;
+        }
+    }
+    if progress.status == MigrationStatus::Completed {
+        emit_event(env, MOD_UPGRADE, ACT_COMPLETED, plan.to_version.clone());
+    }
+
+    Ok(progress)
+}
+
+/// Resume a deferred live migration once its not-before time has passed,
+/// finishing the upgrade when every entry has migrated. Neither a governance
+/// pause nor a proxy pause blocks this.
+pub fn continue_migration(
+    env: &Env,
+    executor: &Address,
+    proposal_id: u64,
+) -> Result<ExecutionOutcome, Error> {
+    storage::with_execution_guard(env, ProposalError::ReentrantCall.into(), || {
+        resume_migration(env, executor, proposal_id)
+    })
+}
+
+fn resume_migration(
     env: &Env,
     executor: &Address,
     proposal_id: u64,
-) -> Result<(), &'static str> {
+) -> Result<ExecutionOutcome, Error> {
     executor.require_auth();
 
-    let mut proposal =
-        storage::get_upgrade_proposal(env, proposal_id).ok_or("Proposal does not exist")?;
+    authorize(env, executor, Action::ExecuteUpgrade)?;
 
-    if proposal.status != UpgradeStatus::Approved {
-        return Err("Proposal is not approved for execution");
+    let proposal = load_proposal(env, proposal_id)?;
+    let progress = storage::get_migration_progress(env, proposal_id)
+        .ok_or(ProposalError::MigrationNotStarted)?;
+    if progress.status != MigrationStatus::InProgress {
+        return Err(ProposalError::MigrationNotInProgress.into());
     }
 
-    // Check if the caller is authorized to execute upgrades
-    let governance_addr = storage::get_governance_address(env);
-    if *executor != governance_addr {
-        return Err("Only governance address can execute upgrades");
-    }
+    finish_execution(env, executor, proposal)
+}
 
-    // Perform state migration if a migration plan exists
-    if let Some(migration_plan) = storage::get_migration_plan(env, proposal_id) {
-        perform_state_migration(env, &migration_plan)?;
-    }
+/// Register the migration an instant revert runs to take state back to the
+/// previous version. Its selector is called on the proposal's implementation.
+pub fn register_reverse_migration(
+    env: &Env,
+    caller: &Address,
+    proposal_id: u64,
+    plan: &MigrationPlan,
+) -> Result<(), Error> {
+    caller.require_auth();
 
-    // Update the current version
-    storage::set_current_version(env, &proposal.version);
+    authorize(env, caller, Action::RegisterMigrationPlan)?;
 
-    // Update proposal status
-    proposal.status = UpgradeStatus::Executed;
-    storage::store_upgrade_proposal(env, &proposal);
+    storage::store_reverse_migration(env, proposal_id, plan);
 
-    // Emit upgrade execution event
-    emit_event(env, MOD_UPGRADE, ACT_UPGRADE_EXECUTED, proposal_id);
+    emit_event(env, MOD_UPGRADE, ACT_UPDATED, proposal_id);
 
     Ok(())
 }
 
-/// Perform emergency upgrade bypassing the normal governance process
-pub fn emergency_upgrade(
+/// Undo a just-executed upgrade inside its revert window: run the reverse
+/// migration, restore the previous version and proxy implementation from
+/// the receipt and mark the proposal `Reverted`. The proxy switch is
+/// recorded in its upgrade history as a revert. An upgrade that migrated
+/// state needs a registered reverse migration unless `force` is set.
+pub fn instant_revert(
     env: &Env,
     caller: &Address,
-    _new_contract_address: &Address,
-    new_version: &Version,
-) -> Result<(), &'static str> {
+    proposal_id: u64,
+    force: bool,
+) -> Result<(), Error> {
     caller.require_auth();
+    authorize(env, caller, Action::InstantRevert)?;
 
-    // Check if emergency upgrades are enabled
-    if !storage::is_emergency_upgrade_enabled(env) {
-        return Err("Emergency upgrades are not enabled");
+    match storage::get_revert_window(env) {
+        Some(window) if window.proposal_id == proposal_id && time::now(env) < window.closes_at => {}
+        _ => return Err(UpgradeError::RevertWindowClosed.into()),
     }
+    let receipt =
+        storage::get_execution_receipt(env, proposal_id).ok_or(UpgradeError::ProposalNotFound)?;
 
-    // Only governance address can perform emergency upgrades
-    let governance_addr = storage::get_governance_address(env);
-    if *caller != governance_addr {
-        return Err("Only governance address can perform emergency upgrades");
+    match storage::get_reverse_migration(env, proposal_id) {
+        Some(plan) => {
+            let mut next_index = 0;
+            migrate_entries(
+                env,
+                &receipt.implementation,
+                &plan,
+                &MigrationNamespace::Live,
+                &mut next_index,
+            )
+            .map_err(|_| UpgradeError::ReverseMigrationFailed)?;
+        }
+        None if receipt.migration_outcome == MigrationOutcome::Completed && !force => {
+            return Err(UpgradeError::ReverseMigrationMissing.into());
+        }
+        None => {}
     }
 
-    // Update the current version directly
-    storage::set_current_version(env, new_version);
+    storage::set_current_version(env, &receipt.from_version, caller, None);
+    if let Some(previous) = &receipt.previous_implementation {
+        if proxy_storage::PROXY_CONFIG.has(env) {
+            proxy_impl::apply_revert(env, caller, previous);
+        }
+    }
+    transition(env, proposal_id, UpgradeStatus::Reverted, REVERT_REASON)?;
+    storage::clear_revert_window(env);
 
-    // Emit emergency upgrade event
-    emit_event(env, MOD_UPGRADE, ACT_EMERGENCY_UPGRADE, new_version.clone());
+    log_emergency_action(
+        env,
+        String::from_str(env, "InstantRevert"),
+        caller.clone(),
+        String::from_str(env, "Executed upgrade reverted inside its revert window"),
+    );
+    emit_event(
+        env,
+        MOD_UPGRADE,
+        ACT_INSTANT_REVERTED,
+        (proposal_id, receipt.to_version, receipt.from_version, force),
+    );
 
     Ok(())
 }
 
-/// Enable or disable emergency upgrades
-pub fn toggle_emergency_upgrades(
-    env: &Env,
-    caller: &Address,
-    enable: bool,
-) -> Result<(), &'static str> {
+/// End a just-executed upgrade's revert window early, unfreezing upgrades
+pub fn confirm_upgrade(env: &Env, caller: &Address, proposal_id: u64) -> Result<(), UpgradeError> {
     caller.require_auth();
+    authorize(env, caller, Action::ConfirmUpgrade)?;
 
-    // Only governance address can enable/disable emergency upgrades
-    let governance_addr = storage::get_governance_address(env);
-    if *caller != governance_addr {
-        return Err("Only governance address can toggle emergency upgrades");
+    match storage::get_revert_window(env) {
+        Some(window) if window.proposal_id == proposal_id && time::now(env) < window.closes_at => {}
+        _ => return Err(UpgradeError::RevertWindowClosed),
     }
+    storage::clear_revert_window(env);
 
-    storage::set_emergency_upgrade_enabled(env, enable);
-
-    emit_event(env, MOD_UPGRADE, ACT_UPDATED, enable);
+    emit_event(
+        env,
+        MOD_UPGRADE,
+        ACT_UPGRADE_CONFIRMED,
+        (proposal_id, caller.clone()),
+    );
 
     Ok(())
 }
 
-/// Register a migration plan for an upgrade
-pub fn register_migration_plan(
+/// Copy the sampled live entries into the proposal's shadow namespace, run
+/// its migration there and compare each result with the expected value.
+/// Live entries are never written; any earlier rehearsal is discarded first.
+pub fn start_rehearsal(
     env: &Env,
     caller: &Address,
     proposal_id: u64,
-    migration_plan: &MigrationPlan,
-) -> Result<(), &'static str> {
+    samples: &Vec<RehearsalSample>,
+) -> Result<RehearsalReport, Error> {
     caller.require_auth();
 
-    // Only governance address can register migration plans
-    let governance_addr = storage::get_governance_address(env);
-    if *caller != governance_addr {
-        return Err("Only governance address can register migration plans");
+    authorize(env, caller, Action::RehearseMigration)?;
+
+    let proposal =
+        storage::get_upgrade_proposal(env, proposal_id).ok_or(UpgradeError::ProposalNotFound)?;
+    let plan = storage::get_migration_plan(env, proposal_id)
+        .ok_or(ProposalError::MigrationPlanNotFound)?;
+
+    let shadow = MigrationNamespace::Shadow(proposal_id);
+    storage::clear_namespace(env, &shadow);
+
+    let mut missing = Vec::new(env);
+    for sample in samples.iter() {
+        match storage::get_migration_entry(env, &MigrationNamespace::Live, &sample.key) {
+            Some(value) => storage::set_migration_entry(env, &shadow, &sample.key, &value),
+            None => missing.push_back(sample.key.clone()),
+        }
     }
 
-    storage::store_migration_plan(env, proposal_id, migration_plan);
+    perform_state_migration(env, &proposal, &plan, &shadow)?;
 
-    emit_event(env, MOD_UPGRADE, ACT_UPDATED, proposal_id);
+    let mut mismatched = Vec::new(env);
+    let mut checked = 0u32;
+    for sample in samples.iter() {
+        if missing.contains(&sample.key) {
+            continue;
+        }
+        checked += 1;
+        if storage::get_migration_entry(env, &shadow, &sample.key) != Some(sample.expected.clone())
+        {
+            mismatched.push_back(sample.key.clone());
+        }
+    }
 
-    Ok(())
+    let report = RehearsalReport {
+        proposal_id,
+        checked,
+        passed: mismatched.is_empty() && missing.is_empty(),
+        mismatched,
+        missing,
+        timestamp: time::now(env),
+    };
+    storage::store_rehearsal_report(env, &report);
+
+    emit_event(
+        env,
+        MOD_UPGRADE,
+        ACT_REHEARSED,
+        (proposal_id, report.passed),
+    );
+
+    Ok(report)
 }
 
-/// Perform state migration based on a migration plan
-fn perform_state_migration(env: &Env, plan: &MigrationPlan) -> Result<(), &'static str> {
-    // In a real implementation, this would call specific migration functions
-    // based on the migration plan's selector
-    // For now, we'll just log the migration attempt
+/// Delete a proposal's shadow data and its rehearsal report
+pub fn discard_rehearsal(env: &Env, caller: &Address, proposal_id: u64) -> Result<(), Error> {
+    caller.require_auth();
 
-    emit_event(env, MOD_UPGRADE, ACT_STARTED, plan.from_version.clone());
+    authorize(env, caller, Action::RehearseMigration)?;
 
-    // Placeholder for actual migration logic
-    // This would involve calling migration functions that transform data
-    // from the old format to the new format
+    storage::clear_namespace(env, &MigrationNamespace::Shadow(proposal_id));
+    storage::remove_rehearsal_report(env, proposal_id);
 
-    emit_event(env, MOD_UPGRADE, ACT_COMPLETED, plan.to_version.clone());
+    emit_event(env, MOD_UPGRADE, ACT_REHEARSAL_DISCARDED, proposal_id);
 
     Ok(())
 }
 
 /// Check version compatibility between current and target version
 pub fn check_version_compatibility(current: &Version, target: &Version) -> bool {
-    // Major version must match for compatibility
-    // Minor version of target should be >= current for forward compatibility
-    current.major == target.major && target.minor >= current.minor
+    // Same major, and the target's minor no older than the current one
+    target.is_compatible_with(current)
+}
+
+/// Describe which modules are initialized and which governance features are active.
+/// Reads storage without trapping so it is safe on a partially initialized contract.
+/// A timelock is reported when any path to execution waits: the default
+/// execution delay, a track's timelock or a delay on the proxy's schedule.
+/// Delegation needs voting power to hand over, so it is reported once some is held.
+pub fn capabilities(env: &Env) -> Capabilities {
+    let upgrade_initialized = storage::is_initialized(env);
+    let timelock_enabled = storage::get_execution_delay(env) > 0
+        || executes_through_proxy(env)
+        || (upgrade_initialized
+            && storage::get_governance_tracks(env)
+                .iter()
+                .any(|track| track.timelock > 0));
+    Capabilities {
+        upgrade_initialized,
+        proxy_initialized: proxy_storage::is_initialized(env),
+        timelock_enabled,
+        quorum_enabled: storage::get_governance_config(env).quorum_bps > 0,
+        delegation_enabled: storage::get_total_voting_power(env) > 0,
+        emergency_enabled: storage::is_emergency_upgrade_enabled(env),
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        error_code_version: ERROR_CODE_VERSION,
+    }
 }
 
-/// Rollback to a previous version (limited capability)
+/// Roll back to an earlier version of the same major, switching the proxy to
+/// the implementation recorded for it. Emits `(from, to)`.
 pub fn rollback_to_version(
     env: &Env,
     caller: &Address,
     target_version: &Version,
-) -> Result<(), &'static str> {
+) -> Result<(), Error> {
     caller.require_auth();
 
     // Only governance address can perform rollbacks
-    let governance_addr = storage::get_governance_address(env);
-    if *caller != governance_addr {
-        return Err("Only governance address can perform rollbacks");
+    authorize(env, caller, Action::Rollback)?;
+
+    if proxy_storage::is_frozen(env) {
+        return Err(GuardError::UpgradesFrozen.into());
     }
 
-    // In a real implementation, this would involve complex state restoration
-    // For now, we'll just check if the rollback is to a previous version
+    // Only an older version of the same major can be rolled back to
     let current_version = storage::get_current_version(env);
 
     if target_version.major != current_version.major
-        || (target_version.major == current_version.major
-            && target_version.minor > current_version.minor)
+        || !current_version.is_newer_than(target_version)
+    {
+        return Err(UpgradeError::InvalidRollbackTarget.into());
+    }
+
+    // The version and the code must agree, so the target's implementation has to be known
+    let implementation = storage::get_version_implementation(env, target_version)
+        .ok_or(GuardError::UnknownVersionImplementation)?;
+    if proxy_storage::PROXY_CONFIG.has(env) {
+        proxy_impl::apply_upgrade(env, caller, &implementation);
+    }
+
+    // Update to the target version; the history gains an entry rather than losing one
+    storage::set_current_version(env, target_version, caller, None);
+
+    emit_event(
+        env,
+        MOD_UPGRADE,
+        ACT_ROLLED_BACK,
+        (current_version, target_version.clone()),
+    );
+
+    Ok(())
+}
+
+/// Set a feature flag, optionally gated on a minimum contract version
+pub fn set_feature(
+    env: &Env,
+    caller: &Address,
+    name: Symbol,
+    enabled: bool,
+    min_version: Option<Version>,
+) -> Result<(), Error> {
+    caller.require_auth();
+
+    authorize(env, caller, Action::SetFeature)?;
+
+    let flag = FeatureFlag {
+        name,
+        enabled,
+        min_version: min_version.unwrap_or(Version::new(0, 0, 0)),
+        updated_at: time::now(env),
+    };
+    storage::store_feature(env, &flag);
+
+    emit_event(env, MOD_UPGRADE, ACT_FEATURE_SET, flag);
+
+    Ok(())
+}
+
+/// A feature is enabled when its flag is set and the current version meets its minimum
+pub fn is_feature_enabled(env: &Env, name: &Symbol) -> bool {
+    match storage::get_feature(env, name) {
+        Some(flag) if flag.enabled => {
+            let current = storage::get_current_version(env);
+            current.cmp_semver(&flag.min_version) != Ordering::Less
+        }
+        _ => false,
+    }
+}
+
+/// List registered feature flags in registration order
+pub fn get_features(env: &Env, offset: u32, limit: u32) -> Vec<FeatureFlag> {
+    let names = storage::get_feature_names(env);
+    let mut result = Vec::new(env);
+    for name in names.iter().skip(offset as usize).take(limit as usize) {
+        if let Some(flag) = storage::get_feature(env, &name) {
+            result.push_back(flag);
+        }
+    }
+    result
+}
+
+/// Share of total voting power a recovery needs, in basis points. This is a
+/// hard floor and deliberately not configurable by the governance it replaces.
+pub const RECOVERY_SUPERMAJORITY_BPS: u64 = 8_000;
+/// Mandatory delay between opening a recovery and executing it
+pub const RECOVERY_TIMELOCK_SECONDS: u64 = 14 * 24 * 60 * 60;
+
+/// Open a proposal to replace the governance address. Anyone may call this;
+/// it only takes effect with a voter supermajority and after a long timelock
+/// during which the current governance can veto it.
+pub fn initiate_recovery(
+    env: &Env,
+    initiator: &Address,
+    new_governance: &Address,
+) -> Result<u64, Error> {
+    initiator.require_auth();
+
+    let total_voting_power = storage::get_total_voting_power(env);
+    if total_voting_power == 0 {
+        return Err(UpgradeError::NoVotingPower.into());
+    }
+
+    let now = time::now(env);
+    let recovery = RecoveryProposal {
+        id: storage::take_next_recovery_id(env),
+        initiator: initiator.clone(),
+        new_governance: new_governance.clone(),
+        total_voting_power,
+        votes_for: 0,
+        created_at: now,
+        executable_at: now + RECOVERY_TIMELOCK_SECONDS,
+        status: RecoveryStatus::Open,
+    };
+    storage::store_recovery(env, &recovery);
+
+    log_emergency_action(
+        env,
+        String::from_str(env, "RecoveryInitiated"),
+        initiator.clone(),
+        String::from_str(env, "Governance recovery proposal opened"),
+    );
+    emit_event(env, MOD_UPGRADE, ACT_RECOVERY_INITIATED, recovery.clone());
+
+    Ok(recovery.id)
+}
+
+/// Support an open recovery proposal with the caller's voting power
+pub fn vote_recovery(env: &Env, voter: &Address, recovery_id: u64) -> Result<(), Error> {
+    voter.require_auth();
+
+    let mut recovery = load_open_recovery(env, recovery_id)?;
+
+    let power = storage::get_voting_power(env, voter);
+    if power == 0 {
+        return Err(UpgradeError::NoVotingPower.into());
+    }
+    let mut voters = storage::get_recovery_voters(env, recovery_id);
+    if voters.contains(voter) {
+        return Err(UpgradeError::AlreadyVoted.into());
+    }
+    voters.push_back(voter.clone());
+    storage::set_recovery_voters(env, recovery_id, &voters);
+
+    recovery.votes_for += power as u64;
+    storage::store_recovery(env, &recovery);
+
+    log_emergency_action(
+        env,
+        String::from_str(env, "RecoveryVoted"),
+        voter.clone(),
+        String::from_str(env, "Supported governance recovery"),
+    );
+    emit_event(
+        env,
+        MOD_UPGRADE,
+        ACT_RECOVERY_VOTED,
+        (recovery_id, voter.clone(), recovery.votes_for),
+    );
+
+    Ok(())
+}
+
+/// Cancel an open recovery. Only the current governance can do this, which
+/// proves its key is not lost.
+pub fn veto_recovery(env: &Env, caller: &Address, recovery_id: u64) -> Result<(), Error> {
+    caller.require_auth();
+
+    authorize(env, caller, Action::VetoRecovery)?;
+
+    let mut recovery = load_open_recovery(env, recovery_id)?;
+    recovery.status = RecoveryStatus::Vetoed;
+    storage::store_recovery(env, &recovery);
+
+    log_emergency_action(
+        env,
+        String::from_str(env, "RecoveryVetoed"),
+        caller.clone(),
+        String::from_str(env, "Governance vetoed recovery"),
+    );
+    emit_event(env, MOD_UPGRADE, ACT_RECOVERY_VETOED, recovery_id);
+
+    Ok(())
+}
+
+/// Nominate a new governance address. Governance keeps its powers until the
+/// nominee accepts; nominating again replaces the pending candidate.
+pub fn transfer_governance(
+    env: &Env,
+    caller: &Address,
+    new_governance: &Address,
+) -> Result<(), Error> {
+    caller.require_auth();
+    authorize(env, caller, Action::TransferGovernance)?;
+
+    storage::set_pending_governance(env, new_governance);
+
+    emit_event(
+        env,
+        MOD_UPGRADE,
+        ACT_GOVERNANCE_NOMINATED,
+        (caller.clone(), new_governance.clone()),
+    );
+
+    Ok(())
+}
+
+/// Accept the governance role nominated by the current governance
+pub fn accept_governance(env: &Env, new_governance: &Address) -> Result<(), Error> {
+    new_governance.require_auth();
+
+    let pending = storage::get_pending_governance(env).ok_or(GuardError::NoPendingGovernance)?;
+    if pending != *new_governance {
+        return Err(GuardError::NotPendingGovernance.into());
+    }
+
+    replace_governance(env, new_governance, new_governance)?;
+    storage::clear_pending_governance(env);
+
+    Ok(())
+}
+
+/// Withdraw a pending governance nomination
+pub fn cancel_governance_transfer(env: &Env, caller: &Address) -> Result<(), Error> {
+    caller.require_auth();
+    authorize(env, caller, Action::TransferGovernance)?;
+
+    let pending = storage::get_pending_governance(env).ok_or(GuardError::NoPendingGovernance)?;
+    storage::clear_pending_governance(env);
+
+    emit_event(
+        env,
+        MOD_UPGRADE,
+        ACT_GOVERNANCE_TRANSFER_CANCELLED,
+        (caller.clone(), pending),
+    );
+
+    Ok(())
+}
+
+/// Swap the governance address on behalf of `actor`. Every governance check
+/// reads the stored address, so the old one loses its powers at once.
+pub(crate) fn replace_governance(
+    env: &Env,
+    actor: &Address,
+    new_governance: &Address,
+) -> Result<(), Error> {
+    enforce_dual_control(env, actor, ControlChange::Governance, false)?;
+
+    let previous = storage::get_governance_address(env);
+    storage::set_governance_address(env, new_governance);
+
+    emit_event(
+        env,
+        MOD_UPGRADE,
+        ACT_GOVERNANCE_CHANGED,
+        (previous, new_governance.clone()),
+    );
+
+    Ok(())
+}
+
+/// Replace the governance address once a recovery has reached the
+/// supermajority and its timelock has elapsed without a veto
+pub fn execute_recovery(env: &Env, caller: &Address, recovery_id: u64) -> Result<(), Error> {
+    caller.require_auth();
+
+    let mut recovery = load_open_recovery(env, recovery_id)?;
+
+    if time::now(env) < recovery.executable_at {
+        return Err(UpgradeError::RecoveryTimelockActive.into());
+    }
+    // Widen before multiplying so large power totals cannot overflow
+    if (recovery.votes_for as u128) * 10_000
+        < (recovery.total_voting_power as u128) * RECOVERY_SUPERMAJORITY_BPS as u128
     {
-        return Err("Can only rollback to earlier versions in the same major series");
+        return Err(UpgradeError::SupermajorityNotReached.into());
     }
 
-    // Update to the target version
-    storage::set_current_version(env, target_version);
+    // The voter supermajority is the escape hatch, so the window never blocks it
+    enforce_dual_control(env, caller, ControlChange::Governance, true)?;
 
-    env.events()
-        .publish(("upgrade", "rollback_completed"), target_version.clone());
+    let previous_governance = storage::get_governance_address(env);
+    storage::set_governance_address(env, &recovery.new_governance);
+    // A nomination by the replaced governance must not outlive it
+    storage::clear_pending_governance(env);
+    recovery.status = RecoveryStatus::Executed;
+    storage::store_recovery(env, &recovery);
+
+    log_emergency_action(
+        env,
+        String::from_str(env, "RecoveryExecuted"),
+        caller.clone(),
+        String::from_str(env, "Governance address replaced by recovery"),
+    );
+    emit_event(
+        env,
+        MOD_UPGRADE,
+        ACT_RECOVERY_EXECUTED,
+        (recovery_id, previous_governance, recovery.new_governance),
+    );
 
     Ok(())
 }
+
+fn load_open_recovery(env: &Env, recovery_id: u64) -> Result<RecoveryProposal, Error> {
+    let recovery = storage::get_recovery(env, recovery_id).ok_or(UpgradeError::RecoveryNotFound)?;
+    if recovery.status != RecoveryStatus::Open {
+        return Err(UpgradeError::RecoveryNotOpen.into());
+    }
+    Ok(recovery)
+}
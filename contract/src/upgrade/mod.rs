@@ -1,4 +1,8 @@
+pub mod auth;
+pub mod calls;
+pub mod invariants;
 pub mod logic;
 pub mod storage;
 pub mod tests;
+pub mod testutils;
 pub mod types;
@@ -1,207 +1,2501 @@
-use crate::upgrade::types::{MigrationPlan, UpgradeProposal, UpgradeStatus, Version};
-use soroban_sdk::{symbol_short, Address, Env, Map, Symbol, Vec};
-
-// Storage keys for upgrade functionality
-const CURRENT_VERSION_KEY: Symbol = symbol_short!("cur_ver");
-const UPGRADE_PROPOSALS_KEY: Symbol = symbol_short!("upg_prop");
-const VOTING_POWER_KEY: Symbol = symbol_short!("vote_pow");
-const GOVERNANCE_ADDRESS_KEY: Symbol = symbol_short!("gov_addr");
-const EMERGENCY_UPGRADE_KEY: Symbol = symbol_short!("emg_upg");
-const MIGRATION_PLANS_KEY: Symbol = symbol_short!("migr_pln");
-
-/// Initialize upgrade storage
-pub fn initialize(env: &Env, initial_version: Version, governance_address: Address) {
-    env.storage()
-        .persistent()
-        .set(&CURRENT_VERSION_KEY, &initial_version);
-    env.storage()
-        .persistent()
-        .set(&GOVERNANCE_ADDRESS_KEY, &governance_address);
+use crate::events::emit::{emit_event, emit_subject_event, set_era_major};
+use crate::events::history::{append_history, get_history_digest, get_pruned_through};
+use crate::events::topics::{ACT_FOOTPRINT, ACT_VOTE_CAST, ACT_VOTE_CHANGED, MOD_UPGRADE};
+use crate::events::types::HistoryKind;
+use crate::keys::tags::{FAST_TRACK_NAME, STANDARD_TRACK_NAME, SUPERMAJORITY_TRACK_NAME};
+use crate::keys::upgrade::{
+    APPLIED_TEMPLATE_KEY, APPROVED_AT_KEY, ARCHIVE_QUEUE_KEY, AUTH_MATRIX_KEY, BOND_CONFIG_KEY,
+    COMPENSATIONS_KEY, CONTROL_CHANGE_KEY, COUNTED_POWER_KEY, CURRENT_VERSION_KEY, DELEGATION_KEY,
+    DELEGATORS_KEY, DEPRECATED_VERSIONS_KEY, DESCRIPTION_BLOB_KEY, DUAL_CONTROL_WINDOW_KEY,
+    ELIGIBLE_AT_KEY, EMERGENCY_EXECUTIONS_KEY, EMERGENCY_EXPIRY_KEY, EMERGENCY_MAX_BUMP_KEY,
+    EMERGENCY_ONE_SHOT_KEY, EMERGENCY_OVERRIDE_KEY, EMERGENCY_UPGRADE_KEY, EXECUTING_KEY,
+    EXECUTION_DELAY_KEY, EXECUTION_WINDOW_KEY, FEATURE_INDEX_KEY, FEATURE_KEY, FOOTPRINT_KEY,
+    GENESIS_KEY, GOVERNANCE_ADDRESS_KEY, GOVERNANCE_CONFIG_KEY, GOVERNANCE_PAUSE_KEY, ID_MODE_KEY,
+    KEEPER_CONFIG_KEY, KEEPER_STATS_KEY, LAST_EMERGENCY_KEY, LAST_HEARTBEAT_KEY, MAX_ATTEMPTS_KEY,
+    MAX_SILENCE_KEY, MAX_VOTING_POWER_KEY, MIGRATING_KEY, MIGRATION_ENTRY_KEY, MIGRATION_KEYS_KEY,
+    MIGRATION_PLANS_KEY, MIGRATION_PROGRESS_KEY, NEXT_COMPENSATION_ID_KEY, NEXT_PROPOSAL_ID_KEY,
+    NEXT_RECOVERY_ID_KEY, NOTIFY_RESULTS_KEY, PARAMS_KEY, PENDING_GOVERNANCE_KEY,
+    PENDING_GRANTS_KEY, POST_CHECK_KEY, POWER_CLAIM_KEY, POWER_ROOT_EPOCH_KEY, POWER_ROOT_KEY,
+    POWER_SNAPSHOT_KEY, PREVIOUS_IMPL_KEY, PRIVATE_DESCRIPTION_KEY, PROPOSAL_BOND_KEY,
+    PROPOSAL_ID_INDEX_KEY, PROPOSAL_KIND_KEY, PROPOSER_INDEX_KEY, PROPOSER_WINDOW_KEY,
+    PROXY_LINK_KEY, RATE_LIMIT_KEY, RECEIPT_KEY, RECOVERY_KEY, RECOVERY_VOTERS_KEY,
+    REGISTRY_POWER_KEY, REHEARSAL_KEY, REQUIRE_REVEAL_KEY, RETRY_QUEUE_KEY, REVERSE_MIGRATION_KEY,
+    REVERT_WINDOW_KEY, ROLE_MEMBERS_KEY, SAFE_MODE_KEY, SCHEMA_VERSION_KEY, SLASH_HISTORY_KEY,
+    STATUS_INDEX_KEY, STRANDED_KEY, SUBSCRIBERS_KEY, TASK_LAST_RUN_KEY, TEMPLATE_INDEX_KEY,
+    TEMPLATE_KEY, TRACK_IDS_KEY, TRACK_KEY, UPGRADE_PROPOSALS_KEY, VERSION_COUNT_KEY,
+    VERSION_HISTORY_KEY, VERSION_IMPL_KEY, VERSION_INDEX_KEY, VERSION_MINORS_KEY, VOTERS_KEY,
+    VOTE_RECEIPTS_KEY, VOTING_PERIOD_KEY, VOTING_POWER_KEY,
+};
+use crate::upgrade::types::{
+    weight_bps, Action, AppliedTemplate, BondConfig, BumpKind, ControlChange,
+    EmergencyUpgradeRecord, ExecutionReceipt, ExecutionRecord, FeatureFlag, FootprintCategory,
+    GenesisProgress, GovernanceConfig, GovernancePause, GovernanceTrack, GuardError, KeeperConfig,
+    KeeperStats, LegacyUpgradeProposal, MaintenanceTask, MigrationNamespace, MigrationPlan,
+    MigrationProgress, MigrationStatus, NarrowUpgradeProposal, NarrowVoteReceipt,
+    NotificationResult, PendingCompensation, PendingNotification, PostCheckFailure,
+    PrivateDescription, ProposalBond, ProposalError, ProposalIdMode, ProposalKind, ProposalPage,
+    ProposalParams, ProposalTemplate, RateLimitConfig, RecoveryProposal, RehearsalReport,
+    RevertWindow, Role, SchemaMismatch, SlashRecord, StorageFootprint, UpgradeError,
+    UpgradeProposal, UpgradeStatus, Version, VersionChange, VoteCast, VoteChanged, VoteChoice,
+    VoteError, VoteReceipt, VoterEntry, VotingGrant, DEFAULT_VOTING_PERIOD, FAST_TRACK_ID,
+    FOOTPRINT_EVENT_INTERVAL, MAX_VERSION_HISTORY_PAGE, SIMPLE_MAJORITY_BPS, STANDARD_TRACK_ID,
+    STORAGE_SCHEMA, SUPERMAJORITY_TRACK_ID,
+};
+use crate::utils::pagination::{self, Cursor, PageScope};
+use crate::utils::time;
+use crate::utils::typed_storage::Slot;
+use soroban_sdk::{
+    panic_with_error, Address, Bytes, BytesN, Env, Error, IntoVal, Map, String, Symbol, TryFromVal,
+    Val, Vec,
+};
 
-    // Initialize empty proposals map
-    let proposals: Map<u64, UpgradeProposal> = Map::new(env);
-    env.storage()
-        .persistent()
-        .set(&UPGRADE_PROPOSALS_KEY, &proposals);
+// Storage slots for upgrade functionality; each key is bound to its value type here
+pub(crate) const CURRENT_VERSION: Slot<Symbol, Version> = Slot::new(CURRENT_VERSION_KEY);
+pub(crate) const UPGRADE_PROPOSALS: Slot<Symbol, Map<u64, UpgradeProposal>> =
+    Slot::new(UPGRADE_PROPOSALS_KEY);
+pub(crate) const VOTING_POWER: Slot<Symbol, Map<Address, u32>> = Slot::new(VOTING_POWER_KEY);
+/// Sum of the base powers in `VOTING_POWER`, kept as they are written
+pub(crate) const REGISTRY_POWER: Slot<Symbol, u64> = Slot::new(REGISTRY_POWER_KEY);
+pub(crate) const GOVERNANCE_ADDRESS: Slot<Symbol, Address> = Slot::new(GOVERNANCE_ADDRESS_KEY);
+pub(crate) const PENDING_GOVERNANCE: Slot<Symbol, Address> = Slot::new(PENDING_GOVERNANCE_KEY);
+pub(crate) const EMERGENCY_UPGRADE: Slot<Symbol, bool> = Slot::new(EMERGENCY_UPGRADE_KEY);
+/// The proposal map read without decoding entries, for layout migrations
+const RAW_UPGRADE_PROPOSALS: Slot<Symbol, Map<u64, Val>> = Slot::new(UPGRADE_PROPOSALS_KEY);
+pub(crate) const MIGRATION_PLANS: Slot<Symbol, Map<u64, MigrationPlan>> =
+    Slot::new(MIGRATION_PLANS_KEY);
+pub(crate) const RATE_LIMIT: Slot<Symbol, RateLimitConfig> = Slot::new(RATE_LIMIT_KEY);
+pub(crate) const ID_MODE: Slot<Symbol, ProposalIdMode> = Slot::new(ID_MODE_KEY);
+pub(crate) const FEATURE_INDEX: Slot<Symbol, Vec<Symbol>> = Slot::new(FEATURE_INDEX_KEY);
+pub(crate) const SUBSCRIBERS: Slot<Symbol, Vec<Address>> = Slot::new(SUBSCRIBERS_KEY);
+pub(crate) const RETRY_QUEUE: Slot<Symbol, Vec<PendingNotification>> = Slot::new(RETRY_QUEUE_KEY);
+pub(crate) const MAX_ATTEMPTS: Slot<Symbol, u32> = Slot::new(MAX_ATTEMPTS_KEY);
+pub(crate) const NEXT_RECOVERY_ID: Slot<Symbol, u64> = Slot::new(NEXT_RECOVERY_ID_KEY);
+pub(crate) const GOVERNANCE_CONFIG: Slot<Symbol, GovernanceConfig> =
+    Slot::new(GOVERNANCE_CONFIG_KEY);
+/// The governance config read field by field, for layout migrations
+const RAW_GOVERNANCE_CONFIG: Slot<Symbol, Map<Symbol, Val>> = Slot::new(GOVERNANCE_CONFIG_KEY);
+pub(crate) const POWER_ROOT: Slot<Symbol, BytesN<32>> = Slot::new(POWER_ROOT_KEY);
+pub(crate) const POWER_ROOT_EPOCH: Slot<Symbol, u32> = Slot::new(POWER_ROOT_EPOCH_KEY);
+pub(crate) const LAST_HEARTBEAT: Slot<Symbol, u64> = Slot::new(LAST_HEARTBEAT_KEY);
+pub(crate) const MAX_SILENCE: Slot<Symbol, u64> = Slot::new(MAX_SILENCE_KEY);
+pub(crate) const SAFE_MODE: Slot<Symbol, bool> = Slot::new(SAFE_MODE_KEY);
+pub(crate) const EMERGENCY_MAX_BUMP: Slot<Symbol, BumpKind> = Slot::new(EMERGENCY_MAX_BUMP_KEY);
+pub(crate) const REQUIRE_REVEAL: Slot<Symbol, bool> = Slot::new(REQUIRE_REVEAL_KEY);
+pub(crate) const DEPRECATED_VERSIONS: Slot<Symbol, Vec<Version>> =
+    Slot::new(DEPRECATED_VERSIONS_KEY);
+pub(crate) const COMPENSATIONS: Slot<Symbol, Map<u64, PendingCompensation>> =
+    Slot::new(COMPENSATIONS_KEY);
+pub(crate) const NEXT_COMPENSATION_ID: Slot<Symbol, u64> = Slot::new(NEXT_COMPENSATION_ID_KEY);
+pub(crate) const DUAL_CONTROL_WINDOW: Slot<Symbol, u64> = Slot::new(DUAL_CONTROL_WINDOW_KEY);
+pub(crate) const BOND_CONFIG: Slot<Symbol, BondConfig> = Slot::new(BOND_CONFIG_KEY);
+pub(crate) const SLASH_HISTORY: Slot<Symbol, Vec<SlashRecord>> = Slot::new(SLASH_HISTORY_KEY);
+pub(crate) const EMERGENCY_EXECUTIONS: Slot<Symbol, Vec<ExecutionRecord>> =
+    Slot::new(EMERGENCY_EXECUTIONS_KEY);
+const LAST_EMERGENCY: Slot<Symbol, EmergencyUpgradeRecord> = Slot::new(LAST_EMERGENCY_KEY);
+const EMERGENCY_ONE_SHOT: Slot<Symbol, bool> = Slot::new(EMERGENCY_ONE_SHOT_KEY);
+/// When an emergency upgrade window closes; absent while enabled indefinitely
+const EMERGENCY_EXPIRY: Slot<Symbol, u64> = Slot::new(EMERGENCY_EXPIRY_KEY);
+/// Number of entries in the version history
+const VERSION_COUNT: Slot<Symbol, u32> = Slot::new(VERSION_COUNT_KEY);
+pub(crate) const REVERT_WINDOW: Slot<Symbol, RevertWindow> = Slot::new(REVERT_WINDOW_KEY);
+pub(crate) const PENDING_GRANTS: Slot<Symbol, Map<Address, VotingGrant>> =
+    Slot::new(PENDING_GRANTS_KEY);
+pub(crate) const GOVERNANCE_PAUSE: Slot<Symbol, GovernancePause> = Slot::new(GOVERNANCE_PAUSE_KEY);
+pub(crate) const POST_CHECK_FAILURE: Slot<Symbol, PostCheckFailure> = Slot::new(POST_CHECK_KEY);
+/// Ids of the tracks stored through `set_governance_track`, ascending
+pub(crate) const TRACK_IDS: Slot<Symbol, Vec<u32>> = Slot::new(TRACK_IDS_KEY);
+/// Delegator to the address it delegated its upgrade votes to
+pub(crate) const DELEGATIONS: Slot<Symbol, Map<Address, Address>> = Slot::new(DELEGATION_KEY);
+pub(crate) const MIGRATING: Slot<Symbol, Vec<u64>> = Slot::new(MIGRATING_KEY);
+pub(crate) const STRANDED: Slot<Symbol, Vec<u64>> = Slot::new(STRANDED_KEY);
+pub(crate) const VOTING_PERIOD: Slot<Symbol, u64> = Slot::new(VOTING_PERIOD_KEY);
+const MAX_VOTING_POWER: Slot<Symbol, u32> = Slot::new(MAX_VOTING_POWER_KEY);
+pub(crate) const ARCHIVE_QUEUE: Slot<Symbol, Vec<u64>> = Slot::new(ARCHIVE_QUEUE_KEY);
+pub(crate) const KEEPER_CONFIG: Slot<Symbol, KeeperConfig> = Slot::new(KEEPER_CONFIG_KEY);
+pub(crate) const KEEPER_STATS: Slot<Symbol, Map<Address, KeeperStats>> =
+    Slot::new(KEEPER_STATS_KEY);
+pub(crate) const TASK_LAST_RUN: Slot<Symbol, Map<MaintenanceTask, u64>> =
+    Slot::new(TASK_LAST_RUN_KEY);
+pub(crate) const SCHEMA_VERSION: Slot<Symbol, u32> = Slot::new(SCHEMA_VERSION_KEY);
+pub(crate) const GENESIS: Slot<Symbol, GenesisProgress> = Slot::new(GENESIS_KEY);
+/// Ids of every stored proposal, kept in ascending order
+pub(crate) const PROPOSAL_ID_INDEX: Slot<Symbol, Vec<u64>> = Slot::new(PROPOSAL_ID_INDEX_KEY);
+/// Seconds an approved proposal waits before it may execute
+pub(crate) const EXECUTION_DELAY: Slot<Symbol, u64> = Slot::new(EXECUTION_DELAY_KEY);
+/// Seconds an approved proposal stays executable once its waits are over
+pub(crate) const EXECUTION_WINDOW: Slot<Symbol, u64> = Slot::new(EXECUTION_WINDOW_KEY);
+/// Names of the registered proposal templates in registration order
+pub(crate) const TEMPLATE_INDEX: Slot<Symbol, Vec<Symbol>> = Slot::new(TEMPLATE_INDEX_KEY);
+pub(crate) const STORAGE_FOOTPRINT: Slot<Symbol, StorageFootprint> = Slot::new(FOOTPRINT_KEY);
 
-    // Initialize empty voting power map
-    let voting_power: Map<Address, u32> = Map::new(env);
-    env.storage()
-        .persistent()
-        .set(&VOTING_POWER_KEY, &voting_power);
+/// Core entries are bumped once fewer than this many ledgers remain (~7 days)
+pub const CORE_TTL_THRESHOLD: u32 = 120_960;
+/// Ledgers a core entry lives after a bump (~30 days)
+pub const CORE_TTL_EXTEND_TO: u32 = 518_400;
 
-    // Initialize empty migration plans map
-    let migration_plans: Map<u64, MigrationPlan> = Map::new(env);
-    env.storage()
-        .persistent()
-        .set(&MIGRATION_PLANS_KEY, &migration_plans);
+// Prefixes for per-entity slots
+pub(crate) fn power_snapshot_slot(proposal_id: u64) -> Slot<(Symbol, u64), Map<Address, u32>> {
+    Slot::new((POWER_SNAPSHOT_KEY, proposal_id))
+}
+
+pub(crate) fn proposer_window_slot(proposer: &Address) -> Slot<(Symbol, Address), Vec<u64>> {
+    Slot::new((PROPOSER_WINDOW_KEY, proposer.clone()))
+}
+
+pub(crate) fn feature_slot(name: &Symbol) -> Slot<(Symbol, Symbol), FeatureFlag> {
+    Slot::new((FEATURE_KEY, name.clone()))
+}
+
+pub(crate) fn notification_results_slot(
+    proposal_id: u64,
+) -> Slot<(Symbol, u64), Vec<NotificationResult>> {
+    Slot::new((NOTIFY_RESULTS_KEY, proposal_id))
+}
+
+pub(crate) fn receipt_slot(proposal_id: u64) -> Slot<(Symbol, u64), ExecutionReceipt> {
+    Slot::new((RECEIPT_KEY, proposal_id))
+}
+
+pub(crate) fn proposal_params_slot(proposal_id: u64) -> Slot<(Symbol, u64), ProposalParams> {
+    Slot::new((PARAMS_KEY, proposal_id))
+}
+
+pub(crate) fn recovery_slot(recovery_id: u64) -> Slot<(Symbol, u64), RecoveryProposal> {
+    Slot::new((RECOVERY_KEY, recovery_id))
+}
+
+pub(crate) fn recovery_voters_slot(recovery_id: u64) -> Slot<(Symbol, u64), Vec<Address>> {
+    Slot::new((RECOVERY_VOTERS_KEY, recovery_id))
+}
+
+pub(crate) fn proxy_link_slot(proposal_id: u64) -> Slot<(Symbol, u64), u64> {
+    Slot::new((PROXY_LINK_KEY, proposal_id))
+}
+
+pub(crate) fn action_roles_slot(action: Action) -> Slot<(Symbol, Action), Vec<Role>> {
+    Slot::new((AUTH_MATRIX_KEY, action))
+}
+
+pub(crate) fn role_members_slot(role: Role) -> Slot<(Symbol, Role), Vec<Address>> {
+    Slot::new((ROLE_MEMBERS_KEY, role))
+}
+
+pub(crate) fn status_index_slot(status: UpgradeStatus) -> Slot<(Symbol, UpgradeStatus), Vec<u64>> {
+    Slot::new((STATUS_INDEX_KEY, status))
+}
+
+pub(crate) fn proposer_index_slot(proposer: &Address) -> Slot<(Symbol, Address), Vec<u64>> {
+    Slot::new((PROPOSER_INDEX_KEY, proposer.clone()))
+}
+
+pub(crate) fn vote_receipts_slot(proposal_id: u64) -> Slot<(Symbol, u64), Vec<VoteReceipt>> {
+    Slot::new((VOTE_RECEIPTS_KEY, proposal_id))
+}
+
+pub(crate) fn voters_slot(proposal_id: u64) -> Slot<(Symbol, u64), Map<Address, VoteChoice>> {
+    Slot::new((VOTERS_KEY, proposal_id))
+}
+
+/// Power holder to the voter whose ballot carried that power on the proposal
+/// Addresses that delegated directly to `delegate`
+pub(crate) fn delegators_slot(delegate: &Address) -> Slot<(Symbol, Address), Vec<Address>> {
+    Slot::new((DELEGATORS_KEY, delegate.clone()))
+}
+
+pub(crate) fn counted_power_slot(proposal_id: u64) -> Slot<(Symbol, u64), Map<Address, Address>> {
+    Slot::new((COUNTED_POWER_KEY, proposal_id))
+}
+
+pub(crate) fn power_claim_slot(
+    epoch: u32,
+    claimer: &Address,
+) -> Slot<(Symbol, u32, Address), bool> {
+    Slot::new((POWER_CLAIM_KEY, epoch, claimer.clone()))
+}
+
+pub(crate) fn version_index_slot(major: u32, minor: u32) -> Slot<(Symbol, u32, u32), Vec<u64>> {
+    Slot::new((VERSION_INDEX_KEY, major, minor))
+}
+
+pub(crate) fn version_minors_slot(major: u32) -> Slot<(Symbol, u32), Vec<u32>> {
+    Slot::new((VERSION_MINORS_KEY, major))
+}
+
+pub(crate) fn emergency_override_slot(multisig_op_id: u64) -> Slot<(Symbol, u64), bool> {
+    Slot::new((EMERGENCY_OVERRIDE_KEY, multisig_op_id))
+}
+
+pub(crate) fn description_blob_slot(proposal_id: u64) -> Slot<(Symbol, u64), String> {
+    Slot::new((DESCRIPTION_BLOB_KEY, proposal_id))
+}
+
+pub(crate) fn bond_slot(proposal_id: u64) -> Slot<(Symbol, u64), ProposalBond> {
+    Slot::new((PROPOSAL_BOND_KEY, proposal_id))
+}
+
+pub(crate) fn private_description_slot(
+    proposal_id: u64,
+) -> Slot<(Symbol, u64), PrivateDescription> {
+    Slot::new((PRIVATE_DESCRIPTION_KEY, proposal_id))
+}
+
+pub(crate) fn track_slot(track_id: u32) -> Slot<(Symbol, u32), GovernanceTrack> {
+    Slot::new((TRACK_KEY, track_id))
+}
+
+pub(crate) fn approved_at_slot(proposal_id: u64) -> Slot<(Symbol, u64), u64> {
+    Slot::new((APPROVED_AT_KEY, proposal_id))
+}
+
+pub(crate) fn eligible_at_slot(proposal_id: u64) -> Slot<(Symbol, u64), u64> {
+    Slot::new((ELIGIBLE_AT_KEY, proposal_id))
+}
+
+pub(crate) fn proposal_kind_slot(proposal_id: u64) -> Slot<(Symbol, u64), ProposalKind> {
+    Slot::new((PROPOSAL_KIND_KEY, proposal_id))
+}
+
+pub(crate) fn template_slot(name: &Symbol) -> Slot<(Symbol, Symbol), ProposalTemplate> {
+    Slot::new((TEMPLATE_KEY, name.clone()))
+}
+
+pub(crate) fn applied_template_slot(proposal_id: u64) -> Slot<(Symbol, u64), AppliedTemplate> {
+    Slot::new((APPLIED_TEMPLATE_KEY, proposal_id))
+}
+
+pub(crate) fn control_change_slot(change: ControlChange) -> Slot<(Symbol, ControlChange), u64> {
+    Slot::new((CONTROL_CHANGE_KEY, change))
+}
+
+/// Prefix a migratable key with its namespace so live and shadow copies never collide
+pub(crate) fn namespaced_key(
+    namespace: &MigrationNamespace,
+    key: &Symbol,
+) -> (Symbol, MigrationNamespace, Symbol) {
+    (MIGRATION_ENTRY_KEY, namespace.clone(), key.clone())
+}
+
+pub(crate) fn migration_entry_slot(
+    namespace: &MigrationNamespace,
+    key: &Symbol,
+) -> Slot<(Symbol, MigrationNamespace, Symbol), Bytes> {
+    Slot::new(namespaced_key(namespace, key))
+}
+
+pub(crate) fn migration_keys_slot(
+    namespace: &MigrationNamespace,
+) -> Slot<(Symbol, MigrationNamespace), Vec<Symbol>> {
+    Slot::new((MIGRATION_KEYS_KEY, namespace.clone()))
+}
+
+pub(crate) fn migration_progress_slot(proposal_id: u64) -> Slot<(Symbol, u64), MigrationProgress> {
+    Slot::new((MIGRATION_PROGRESS_KEY, proposal_id))
+}
+
+pub(crate) fn reverse_migration_slot(proposal_id: u64) -> Slot<(Symbol, u64), MigrationPlan> {
+    Slot::new((REVERSE_MIGRATION_KEY, proposal_id))
+}
+
+pub(crate) fn previous_implementation_slot(proposal_id: u64) -> Slot<(Symbol, u64), Address> {
+    Slot::new((PREVIOUS_IMPL_KEY, proposal_id))
+}
+
+pub(crate) fn version_change_slot(index: u32) -> Slot<(Symbol, u32), VersionChange> {
+    Slot::new((VERSION_HISTORY_KEY, index))
+}
+
+pub(crate) fn version_implementation_slot(version: &Version) -> Slot<(Symbol, Version), Address> {
+    Slot::new((VERSION_IMPL_KEY, version.clone()))
+}
+
+pub(crate) fn rehearsal_slot(proposal_id: u64) -> Slot<(Symbol, u64), RehearsalReport> {
+    Slot::new((REHEARSAL_KEY, proposal_id))
+}
+
+/// Every status a proposal can be indexed under
+const ALL_STATUSES: [UpgradeStatus; 11] = [
+    UpgradeStatus::Pending,
+    UpgradeStatus::Approved,
+    UpgradeStatus::Executed,
+    UpgradeStatus::Rejected,
+    UpgradeStatus::Cancelled,
+    UpgradeStatus::AwaitingProxy,
+    UpgradeStatus::NeedsAttention,
+    UpgradeStatus::Expired,
+    UpgradeStatus::Failed,
+    UpgradeStatus::Reverted,
+    UpgradeStatus::Vetoed,
+];
+
+/// Maximum number of contracts notified after an executed upgrade
+pub const MAX_UPGRADE_SUBSCRIBERS: u32 = 10;
+/// Maximum number of failed notifications kept for retry
+pub const MAX_RETRY_QUEUE_LEN: u32 = 50;
+/// Default delivery attempts before a notification is dropped
+pub const DEFAULT_NOTIFICATION_MAX_ATTEMPTS: u32 = 3;
+/// Retries `run_compensations` makes before giving a compensation up
+pub const MAX_COMPENSATION_ATTEMPTS: u32 = 5;
+/// Seconds of backoff per retry already made
+pub const COMPENSATION_BACKOFF: u64 = 3_600;
+/// Default minimum separation between a governance change and a guardian change
+pub const DEFAULT_DUAL_CONTROL_WINDOW: u64 = 7 * 24 * 60 * 60;
+
+/// Upper bound on the per-proposer ring buffer of proposal timestamps
+pub const MAX_RATE_LIMIT_SLOTS: u32 = 50;
+
+/// Whether the upgrade module holds a version and governance address
+pub fn is_initialized(env: &Env) -> bool {
+    CURRENT_VERSION.has(env) && GOVERNANCE_ADDRESS.has(env)
+}
+
+/// Initialize upgrade storage. A second call is refused rather than
+/// resetting the governance address, version and proposals.
+pub fn initialize(
+    env: &Env,
+    initial_version: Version,
+    governance_address: Address,
+) -> Result<(), GuardError> {
+    if is_initialized(env) {
+        return Err(GuardError::AlreadyInitialized);
+    }
+    crate::keys::debug_assert_unique_keys();
+    set_current_version(env, &initial_version, &governance_address, None);
+    GOVERNANCE_ADDRESS.store(env, &governance_address);
+
+    // Initialize empty proposals, voting power and migration plan maps
+    UPGRADE_PROPOSALS.store(env, &Map::new(env));
+    VOTING_POWER.store(env, &Map::new(env));
+    REGISTRY_POWER.store(env, &0);
+    MIGRATION_PLANS.store(env, &Map::new(env));
+    // Counters are recounted from the fresh maps on first use
+    STORAGE_FOOTPRINT.remove(env);
 
     // Set emergency upgrade flag to false
-    env.storage()
-        .persistent()
-        .set(&EMERGENCY_UPGRADE_KEY, &false);
+    EMERGENCY_UPGRADE.store(env, &false);
+
+    SCHEMA_VERSION.store(env, &STORAGE_SCHEMA);
+    Ok(())
+}
+
+/// Schema of the stored upgrade layout. Storage initialized before the
+/// schema was recorded is on schema 1; uninitialized storage holds nothing
+/// to misread and counts as current.
+pub fn get_storage_schema(env: &Env) -> u32 {
+    SCHEMA_VERSION.load(env).unwrap_or_else(|| {
+        if GOVERNANCE_ADDRESS.has(env) {
+            1
+        } else {
+            STORAGE_SCHEMA
+        }
+    })
+}
+
+/// Record the schema a layout migration step has moved storage to
+pub fn set_storage_schema(env: &Env, schema: u32) {
+    SCHEMA_VERSION.store(env, &schema);
+}
+
+/// Check that the stored layout is the one this code reads
+pub fn check_schema(env: &Env) -> Result<(), SchemaMismatch> {
+    let found = get_storage_schema(env);
+    if found != STORAGE_SCHEMA {
+        return Err(SchemaMismatch {
+            found,
+            expected: STORAGE_SCHEMA,
+        });
+    }
+    Ok(())
+}
+
+/// Fail with `UpgradeError::SchemaMismatch` rather than decode a layout
+/// this code cannot read. Guards every accessor of versioned layouts.
+fn guard_schema(env: &Env) {
+    if check_schema(env).is_err() {
+        panic_with_error!(env, UpgradeError::SchemaMismatch);
+    }
 }
 
 /// Get the current contract version
 pub fn get_current_version(env: &Env) -> Version {
-    env.storage()
-        .persistent()
-        .get(&CURRENT_VERSION_KEY)
+    CURRENT_VERSION
+        .load(env)
         .expect("Current version not initialized")
 }
 
-/// Set the current contract version
-pub fn set_current_version(env: &Env, version: &Version) {
-    env.storage()
-        .persistent()
-        .set(&CURRENT_VERSION_KEY, version);
+/// Set the current contract version, moving event topics to its era, and
+/// append the change to the version history
+pub fn set_current_version(
+    env: &Env,
+    version: &Version,
+    changed_by: &Address,
+    proposal_id: Option<u64>,
+) {
+    CURRENT_VERSION.store(env, version);
+    set_era_major(env, version.major);
+
+    let index = get_version_count(env);
+    version_change_slot(index).store(
+        env,
+        &VersionChange {
+            version: version.clone(),
+            changed_at: time::now(env),
+            changed_by: changed_by.clone(),
+            proposal_id,
+        },
+    );
+    VERSION_COUNT.store(env, &(index + 1));
+}
+
+/// Number of recorded version changes; the history starts with the first
+/// change made after it was introduced
+pub fn get_version_count(env: &Env) -> u32 {
+    VERSION_COUNT.load_or_else(env, || 0)
+}
+
+/// Up to `limit` version changes, oldest first, from position `start`
+/// (capped at `MAX_VERSION_HISTORY_PAGE`)
+pub fn get_version_history(env: &Env, start: u32, limit: u32) -> Vec<VersionChange> {
+    let end = start
+        .saturating_add(limit.min(MAX_VERSION_HISTORY_PAGE))
+        .min(get_version_count(env));
+    let mut page = Vec::new(env);
+    for index in start..end {
+        if let Some(change) = version_change_slot(index).load(env) {
+            page.push_back(change);
+        }
+    }
+    page
+}
+
+/// The version that was current at `timestamp`, or none before the first
+/// recorded change. Changes are appended in time order, so this is a
+/// binary search for the last change made at or before `timestamp`.
+pub fn get_version_at(env: &Env, timestamp: u64) -> Option<Version> {
+    let (mut low, mut high) = (0, get_version_count(env));
+    while low < high {
+        let mid = low + (high - low) / 2;
+        match version_change_slot(mid).load(env) {
+            Some(change) if change.changed_at <= timestamp => low = mid + 1,
+            _ => high = mid,
+        }
+    }
+    if low == 0 {
+        return None;
+    }
+    version_change_slot(low - 1)
+        .load(env)
+        .map(|change| change.version)
 }
 
 /// Get the governance address
 pub fn get_governance_address(env: &Env) -> Address {
-    env.storage()
-        .persistent()
-        .get(&GOVERNANCE_ADDRESS_KEY)
+    GOVERNANCE_ADDRESS
+        .load(env)
         .expect("Governance address not set")
 }
 
-/// Store an upgrade proposal
+fn proposal_map(env: &Env) -> Map<u64, UpgradeProposal> {
+    guard_schema(env);
+    UPGRADE_PROPOSALS.load_or_else(env, || Map::new(env))
+}
+
+/// Rewrite proposals stored before `total_voting_power_snapshot` and
+/// `voting_deadline` existed. Their creation-time registry was never
+/// recorded, so pending ones take the registry as it stands now and
+/// finalized ones keep a zero snapshot; the deadline comes from the
+/// proposal's parameter snapshot. Returns how many entries were rewritten.
+pub fn migrate_proposal_snapshots(env: &Env) -> u32 {
+    let mut raw = RAW_UPGRADE_PROPOSALS.load_or_else(env, || Map::new(env));
+    let snapshot_field = Symbol::new(env, "total_voting_power_snapshot");
+    let (total_voters, total_power) = voter_registry_snapshot(env);
+
+    let mut migrated = 0u32;
+    for (id, value) in raw.clone().iter() {
+        let fields = Map::<Symbol, Val>::try_from_val(env, &value).unwrap();
+        if fields.contains_key(snapshot_field.clone()) {
+            continue;
+        }
+        let legacy = LegacyUpgradeProposal::try_from_val(env, &value).unwrap();
+        let deadline = get_proposal_params(env, id).voting_ends_at;
+        let proposal = if legacy.status == UpgradeStatus::Pending {
+            legacy.with_snapshot(total_voters, total_power, deadline)
+        } else {
+            let total_voters = legacy.total_voters as u64;
+            legacy.with_snapshot(total_voters, 0, deadline)
+        };
+        raw.set(id, proposal.into_val(env));
+        migrated += 1;
+    }
+
+    if migrated > 0 {
+        RAW_UPGRADE_PROPOSALS.store(env, &raw);
+    }
+    migrated
+}
+
+/// Rewrite proposals whose tallies and voter counts were stored as `u32`
+/// in the current `u64` layout. Returns how many entries were rewritten.
+pub fn migrate_proposal_tallies(env: &Env) -> u32 {
+    let mut raw = RAW_UPGRADE_PROPOSALS.load_or_else(env, || Map::new(env));
+    let tally_field = Symbol::new(env, "votes_for");
+
+    let mut migrated = 0u32;
+    for (id, value) in raw.clone().iter() {
+        let fields = Map::<Symbol, Val>::try_from_val(env, &value).unwrap();
+        let narrow = fields
+            .get(tally_field.clone())
+            .is_some_and(|tally| u32::try_from_val(env, &tally).is_ok());
+        if !narrow {
+            continue;
+        }
+        let proposal = NarrowUpgradeProposal::try_from_val(env, &value)
+            .unwrap()
+            .widen();
+        raw.set(id, proposal.into_val(env));
+        migrated += 1;
+    }
+
+    if migrated > 0 {
+        RAW_UPGRADE_PROPOSALS.store(env, &raw);
+    }
+    migrated
+}
+
+/// Rewrite vote receipts whose weight was stored as `u32` in the current
+/// `u64` layout. Returns how many receipt lists were rewritten.
+pub fn migrate_vote_receipt_weights(env: &Env) -> u32 {
+    let proposal_ids = RAW_UPGRADE_PROPOSALS
+        .load_or_else(env, || Map::new(env))
+        .keys();
+
+    let mut migrated = 0u32;
+    for id in proposal_ids.iter() {
+        let raw: Slot<(Symbol, u64), Vec<Val>> = Slot::new((VOTE_RECEIPTS_KEY, id));
+        let Some(stored) = raw.load(env) else {
+            continue;
+        };
+        let narrow = stored
+            .first()
+            .is_some_and(|receipt| NarrowVoteReceipt::try_from_val(env, &receipt).is_ok());
+        if !narrow {
+            continue;
+        }
+        let mut receipts = Vec::new(env);
+        for receipt in stored.iter() {
+            receipts.push_back(
+                NarrowVoteReceipt::try_from_val(env, &receipt)
+                    .unwrap()
+                    .widen(),
+            );
+        }
+        vote_receipts_slot(id).store(env, &receipts);
+        migrated += 1;
+    }
+    migrated
+}
+
+/// Ids of every stored proposal, in ascending order
+pub fn get_proposal_ids(env: &Env) -> Vec<u64> {
+    proposal_map(env).keys()
+}
+
+/// Replace the governance address
+pub fn set_governance_address(env: &Env, governance_address: &Address) {
+    GOVERNANCE_ADDRESS.store(env, governance_address);
+}
+
+/// The address nominated by `transfer_governance` that has not accepted yet
+pub fn get_pending_governance(env: &Env) -> Option<Address> {
+    PENDING_GOVERNANCE.load(env)
+}
+
+pub fn set_pending_governance(env: &Env, candidate: &Address) {
+    PENDING_GOVERNANCE.store(env, candidate);
+}
+
+pub fn clear_pending_governance(env: &Env) {
+    PENDING_GOVERNANCE.remove(env);
+}
+
+/// Store an upgrade proposal, keeping the status and proposer indices in step
 pub fn store_upgrade_proposal(env: &Env, proposal: &UpgradeProposal) {
-    let mut proposals: Map<u64, UpgradeProposal> = env
-        .storage()
-        .persistent()
-        .get(&UPGRADE_PROPOSALS_KEY)
-        .unwrap_or_else(|| Map::new(env));
+    let mut proposals = proposal_map(env);
+    match proposals.get(proposal.id) {
+        None => {
+            adjust_footprint(env, FootprintCategory::ProposalsLive, 1);
+            id_index_insert(env, proposal.id);
+            index_insert(env, &proposer_index_slot(&proposal.proposer), proposal.id);
+            index_insert(env, &status_index_slot(proposal.status), proposal.id);
+            version_index_insert(env, &proposal.version, proposal.id);
+        }
+        Some(previous) => {
+            if previous.status != proposal.status {
+                index_remove(env, &status_index_slot(previous.status), proposal.id);
+                index_insert(env, &status_index_slot(proposal.status), proposal.id);
+            }
+            if (previous.version.major, previous.version.minor)
+                != (proposal.version.major, proposal.version.minor)
+            {
+                version_index_remove(env, &previous.version, proposal.id);
+                version_index_insert(env, &proposal.version, proposal.id);
+            }
+        }
+    }
 
     proposals.set(proposal.id, proposal.clone());
-    env.storage()
-        .persistent()
-        .set(&UPGRADE_PROPOSALS_KEY, &proposals);
+    UPGRADE_PROPOSALS.store(env, &proposals);
 }
 
-/// Get an upgrade proposal by ID
-pub fn get_upgrade_proposal(env: &Env, proposal_id: u64) -> Option<UpgradeProposal> {
-    let proposals: Map<u64, UpgradeProposal> = env
-        .storage()
-        .persistent()
-        .get(&UPGRADE_PROPOSALS_KEY)
-        .unwrap_or_else(|| Map::new(env));
+fn index_insert<K: IntoVal<Env, Val>>(env: &Env, slot: &Slot<K, Vec<u64>>, id: u64) {
+    let mut ids = slot.load_or_else(env, || Vec::new(env));
+    if !ids.contains(id) {
+        ids.push_back(id);
+        slot.store(env, &ids);
+    }
+}
 
-    proposals.get(proposal_id)
+/// Insert into the id index at its sorted position; content hash ids do not
+/// arrive in order
+fn id_index_insert(env: &Env, id: u64) {
+    let mut ids = PROPOSAL_ID_INDEX.load_or_else(env, || Vec::new(env));
+    if let Err(pos) = ids.binary_search(id) {
+        ids.insert(pos, id);
+        PROPOSAL_ID_INDEX.store(env, &ids);
+    }
 }
 
-/// Get all pending upgrade proposals
-pub fn get_pending_proposals(env: &Env) -> Vec<UpgradeProposal> {
-    let _proposals: Map<u64, UpgradeProposal> = env
-        .storage()
-        .persistent()
-        .get(&UPGRADE_PROPOSALS_KEY)
-        .unwrap_or_else(|| Map::new(env));
+fn index_remove<K: IntoVal<Env, Val>>(env: &Env, slot: &Slot<K, Vec<u64>>, id: u64) {
+    let mut ids = slot.load_or_else(env, || Vec::new(env));
+    if let Some(pos) = ids.first_index_of(id) {
+        ids.remove(pos);
+        slot.store(env, &ids);
+    }
+}
 
-    let result = Vec::new(env);
+/// File a proposal under its target (major, minor), registering the minor
+/// with its major so major-only lookups can find the bucket
+fn version_index_insert(env: &Env, version: &Version, id: u64) {
+    index_insert(env, &version_index_slot(version.major, version.minor), id);
 
-    // In Soroban, iteration over maps isn't directly supported in this way
-    // We'll need to store proposal IDs separately to iterate over them
-    // For now, return an empty vector - this would need to be implemented differently
-    result
+    let slot = version_minors_slot(version.major);
+    let mut minors = slot.load_or_else(env, || Vec::new(env));
+    if let Err(pos) = minors.binary_search(version.minor) {
+        minors.insert(pos, version.minor);
+        slot.store(env, &minors);
+    }
 }
 
-/// Update the status of an upgrade proposal
-pub fn update_proposal_status(env: &Env, proposal_id: u64, status: UpgradeStatus) {
-    if let Some(mut proposal) = get_upgrade_proposal(env, proposal_id) {
-        proposal.status = status;
-        store_upgrade_proposal(env, &proposal);
+/// Take a proposal out of its version bucket, dropping the bucket once empty
+fn version_index_remove(env: &Env, version: &Version, id: u64) {
+    let bucket = version_index_slot(version.major, version.minor);
+    index_remove(env, &bucket, id);
+    if !bucket.load_or_else(env, || Vec::new(env)).is_empty() {
+        return;
+    }
+
+    let slot = version_minors_slot(version.major);
+    let mut minors = slot.load_or_else(env, || Vec::new(env));
+    if let Ok(pos) = minors.binary_search(version.minor) {
+        minors.remove(pos);
+        slot.store(env, &minors);
     }
 }
 
-/// Set voting power for an address
-pub fn set_voting_power(env: &Env, address: &Address, power: u32) {
-    let mut voting_power: Map<Address, u32> = env
-        .storage()
-        .persistent()
-        .get(&VOTING_POWER_KEY)
-        .unwrap_or_else(|| Map::new(env));
+/// Get the ids of proposals targeting `major`, optionally narrowed to one
+/// `minor`. Major-only results are ordered by minor, then by creation.
+pub fn get_version_index(env: &Env, major: u32, minor: Option<u32>) -> Vec<u64> {
+    if let Some(minor) = minor {
+        return version_index_slot(major, minor).load_or_else(env, || Vec::new(env));
+    }
 
-    voting_power.set(address.clone(), power);
+    let mut ids = Vec::new(env);
+    for minor in version_minors_slot(major)
+        .load_or_else(env, || Vec::new(env))
+        .iter()
+    {
+        ids.append(&version_index_slot(major, minor).load_or_else(env, || Vec::new(env)));
+    }
+    ids
+}
+
+/// Drop an archived proposal's entry and its version index entry. Status and
+/// proposer indices are kept so the id still reads as issued.
+pub fn archive_upgrade_proposal(env: &Env, proposal: &UpgradeProposal) {
+    adjust_footprint(env, FootprintCategory::ProposalsLive, -1);
+    adjust_footprint(env, FootprintCategory::ProposalsArchived, 1);
+    pagination::bump_generation(env, PageScope::Proposals);
+    let mut proposals = proposal_map(env);
+    proposals.remove(proposal.id);
+    UPGRADE_PROPOSALS.store(env, &proposals);
+    remove_description_blob(env, proposal.id);
+    power_snapshot_slot(proposal.id).remove(env);
+
+    index_remove(env, &PROPOSAL_ID_INDEX, proposal.id);
+    version_index_remove(env, &proposal.version, proposal.id);
+}
+
+/// Ids of finalized proposals waiting for the archive sink to acknowledge them
+pub fn get_archive_queue(env: &Env) -> Vec<u64> {
+    ARCHIVE_QUEUE.load_or_else(env, || Vec::new(env))
+}
+
+pub fn set_archive_queue(env: &Env, queue: &Vec<u64>) {
+    ARCHIVE_QUEUE.store(env, queue);
+}
+
+/// Get the keeper incentive config; unpaid and without cooldown by default
+pub fn get_keeper_config(env: &Env) -> KeeperConfig {
+    KEEPER_CONFIG.load_or_else(env, || KeeperConfig {
+        token: None,
+        bounty_per_unit: 0,
+        cooldown: 0,
+    })
+}
+
+pub fn set_keeper_config(env: &Env, config: &KeeperConfig) {
+    KEEPER_CONFIG.store(env, config);
+}
+
+/// Get a keeper's lifetime work counters
+pub fn get_keeper_stats(env: &Env, keeper: &Address) -> KeeperStats {
+    KEEPER_STATS
+        .load_or_else(env, || Map::new(env))
+        .get(keeper.clone())
+        .unwrap_or(KeeperStats {
+            runs: 0,
+            work_units: 0,
+            paid: 0,
+            last_run_at: 0,
+        })
+}
+
+pub fn set_keeper_stats(env: &Env, keeper: &Address, stats: &KeeperStats) {
+    let mut all = KEEPER_STATS.load_or_else(env, || Map::new(env));
+    all.set(keeper.clone(), stats.clone());
+    KEEPER_STATS.store(env, &all);
+}
+
+/// When a maintenance task last ran, if ever
+pub fn get_task_last_run(env: &Env, task: MaintenanceTask) -> Option<u64> {
+    TASK_LAST_RUN.load(env).and_then(|runs| runs.get(task))
+}
+
+pub fn set_task_last_run(env: &Env, task: MaintenanceTask, at: u64) {
+    let mut runs = TASK_LAST_RUN.load_or_else(env, || Map::new(env));
+    runs.set(task, at);
+    TASK_LAST_RUN.store(env, &runs);
+}
+
+/// Progress of the genesis voter list, if one was ever started or sealed
+pub fn get_genesis_progress(env: &Env) -> Option<GenesisProgress> {
+    GENESIS.load(env)
+}
+
+pub fn set_genesis_progress(env: &Env, progress: &GenesisProgress) {
+    GENESIS.store(env, progress);
+}
+
+/// Whether a started genesis voter list still holds proposals back
+pub fn is_genesis_pending(env: &Env) -> bool {
+    GENESIS.load(env).is_some_and(|progress| !progress.sealed)
+}
+
+/// Extend the TTL of up to `max` core upgrade entries; returns how many
+/// were present and extended
+pub fn bump_core_ttls(env: &Env, max: u32) -> u32 {
+    let (threshold, extend_to) = (CORE_TTL_THRESHOLD, CORE_TTL_EXTEND_TO);
+    let bumps: [&dyn Fn() -> bool; 6] = [
+        &|| GOVERNANCE_ADDRESS.extend_ttl(env, threshold, extend_to),
+        &|| CURRENT_VERSION.extend_ttl(env, threshold, extend_to),
+        &|| GOVERNANCE_CONFIG.extend_ttl(env, threshold, extend_to),
+        &|| UPGRADE_PROPOSALS.extend_ttl(env, threshold, extend_to),
+        &|| VOTING_POWER.extend_ttl(env, threshold, extend_to),
+        &|| MIGRATION_PLANS.extend_ttl(env, threshold, extend_to),
+    ];
+
+    let mut bumped = 0u32;
+    for bump in bumps.iter() {
+        if bumped >= max {
+            break;
+        }
+        if bump() {
+            bumped += 1;
+        }
+    }
+    bumped
+}
+
+/// Get the ids of proposals currently in `status`
+pub fn get_status_index(env: &Env, status: UpgradeStatus) -> Vec<u64> {
+    status_index_slot(status).load_or_else(env, || Vec::new(env))
+}
+
+/// Get the ids of proposals created by `proposer`
+pub fn get_proposer_index(env: &Env, proposer: &Address) -> Vec<u64> {
+    proposer_index_slot(proposer).load_or_else(env, || Vec::new(env))
+}
+
+/// Whether a stored proposal appears in every index it belongs to
+pub fn proposal_indices_complete(env: &Env, proposal: &UpgradeProposal) -> bool {
+    get_status_index(env, proposal.status).contains(proposal.id)
+        && get_proposer_index(env, &proposal.proposer).contains(proposal.id)
+}
+
+/// Whether an id was ever handed out, even if its proposal entry is now missing
+pub fn was_proposal_issued(env: &Env, proposal_id: u64) -> bool {
+    if proposal_id > 0 && proposal_id < peek_next_proposal_id(env) {
+        return true;
+    }
+    ALL_STATUSES
+        .iter()
+        .any(|status| get_status_index(env, *status).contains(proposal_id))
+}
+
+/// Rebuild the index entries of a stored proposal from the proposal itself
+pub fn repair_proposal_indices(env: &Env, proposal: &UpgradeProposal) {
+    for status in ALL_STATUSES.iter() {
+        if *status != proposal.status {
+            index_remove(env, &status_index_slot(*status), proposal.id);
+        }
+    }
+    index_insert(env, &status_index_slot(proposal.status), proposal.id);
+    index_insert(env, &proposer_index_slot(&proposal.proposer), proposal.id);
+    id_index_insert(env, proposal.id);
+    version_index_insert(env, &proposal.version, proposal.id);
+}
+
+/// The id the next sequential proposal will receive
+pub fn peek_next_proposal_id(env: &Env) -> u64 {
     env.storage()
-        .persistent()
-        .set(&VOTING_POWER_KEY, &voting_power);
+        .instance()
+        .get(&NEXT_PROPOSAL_ID_KEY)
+        .unwrap_or(1u64)
 }
 
-/// Get voting power for an address
-pub fn get_voting_power(env: &Env, address: &Address) -> u32 {
-    let voting_power: Map<Address, u32> = env
-        .storage()
-        .persistent()
-        .get(&VOTING_POWER_KEY)
-        .unwrap_or_else(|| Map::new(env));
+/// Hand out the next sequential proposal id
+pub fn take_next_proposal_id(env: &Env) -> u64 {
+    let proposal_id = peek_next_proposal_id(env);
+    env.storage()
+        .instance()
+        .set(&NEXT_PROPOSAL_ID_KEY, &(proposal_id + 1));
+    proposal_id
+}
 
-    voting_power.get(address.clone()).unwrap_or(0)
+/// Whether an execute path is running and may be calling out to other contracts
+pub fn is_executing(env: &Env) -> bool {
+    env.storage().instance().has(&EXECUTING_KEY)
 }
 
-/// Record a vote on an upgrade proposal
-pub fn record_vote(
+/// Run an execute path with the execution guard raised, failing with `busy`
+/// when the guard is already up. The guard is cleared on every return; a trap
+/// rolls the whole invocation back, the guard included.
+pub fn with_execution_guard<T, E>(
     env: &Env,
-    proposal_id: u64,
-    voter: &Address,
-    vote_for: bool,
-) -> Result<(), &'static str> {
-    let mut proposal = get_upgrade_proposal(env, proposal_id).ok_or("Proposal does not exist")?;
+    busy: E,
+    body: impl FnOnce() -> Result<T, E>,
+) -> Result<T, E> {
+    if is_executing(env) {
+        return Err(busy);
+    }
+    env.storage().instance().set(&EXECUTING_KEY, &true);
+    let result = body();
+    env.storage().instance().remove(&EXECUTING_KEY);
+    result
+}
 
-    if proposal.status != UpgradeStatus::Pending {
-        return Err("Proposal is not in pending status");
+/// Fail with `ProposalError::ReentrantCall` when an upgrade entrypoint is
+/// reached while an execute path is running
+pub fn reject_reentry(env: &Env) {
+    if is_executing(env) {
+        panic_with_error!(env, ProposalError::ReentrantCall);
     }
+}
 
-    // Check if voter has already voted
-    // In a real implementation, we'd track who has voted
-    // For simplicity, we'll just update the vote counts
+/// Get an upgrade proposal by ID
+pub fn get_upgrade_proposal(env: &Env, proposal_id: u64) -> Option<UpgradeProposal> {
+    proposal_map(env).get(proposal_id)
+}
 
-    if vote_for {
-        proposal.votes_for += get_voting_power(env, voter);
-    } else {
-        proposal.votes_against += get_voting_power(env, voter);
+/// Check whether a proposal with the given ID has been stored
+pub fn has_upgrade_proposal(env: &Env, proposal_id: u64) -> bool {
+    get_upgrade_proposal(env, proposal_id).is_some()
+}
+
+/// Get all pending upgrade proposals, in ascending id order
+pub fn get_pending_proposals(env: &Env) -> Vec<UpgradeProposal> {
+    get_proposals_by_status(env, UpgradeStatus::Pending)
+}
+
+/// Get the stored proposals in `status`, in ascending id order
+pub fn get_proposals_by_status(env: &Env, status: UpgradeStatus) -> Vec<UpgradeProposal> {
+    let proposals = proposal_map(env);
+    let mut result = Vec::new(env);
+    for id in PROPOSAL_ID_INDEX.load_or_else(env, || Vec::new(env)).iter() {
+        if let Some(proposal) = proposals.get(id) {
+            if proposal.status == status {
+                result.push_back(proposal);
+            }
+        }
     }
+    result
+}
 
-    store_upgrade_proposal(env, &proposal);
-    Ok(())
+/// Number of stored proposals
+pub fn get_proposal_count(env: &Env) -> u64 {
+    PROPOSAL_ID_INDEX.load_or_else(env, || Vec::new(env)).len() as u64
 }
 
-/// Store a migration plan
-pub fn store_migration_plan(env: &Env, proposal_id: u64, plan: &MigrationPlan) {
-    let mut migration_plans: Map<u64, MigrationPlan> = env
-        .storage()
-        .persistent()
-        .get(&MIGRATION_PLANS_KEY)
-        .unwrap_or_else(|| Map::new(env));
+/// Up to `limit` stored proposals past `cursor`; the id index picks the page
+pub fn get_proposal_page(env: &Env, cursor: &Cursor, limit: u32) -> ProposalPage {
+    let ids = PROPOSAL_ID_INDEX.load_or_else(env, || Vec::new(env));
+    let (page_ids, next, complete) = pagination::page_ids(env, cursor, &ids, limit);
 
-    migration_plans.set(proposal_id, plan.clone());
-    env.storage()
-        .persistent()
-        .set(&MIGRATION_PLANS_KEY, &migration_plans);
+    let proposals = proposal_map(env);
+    let mut result = Vec::new(env);
+    for id in page_ids.iter() {
+        if let Some(proposal) = proposals.get(id) {
+            result.push_back(proposal);
+        }
+    }
+    ProposalPage {
+        proposals: result,
+        next,
+        complete,
+    }
 }
 
-/// Get a migration plan by proposal ID
-pub fn get_migration_plan(env: &Env, proposal_id: u64) -> Option<MigrationPlan> {
-    let migration_plans: Map<u64, MigrationPlan> = env
-        .storage()
-        .persistent()
-        .get(&MIGRATION_PLANS_KEY)
-        .unwrap_or_else(|| Map::new(env));
+/// Build the id index over proposals stored before it was kept. Returns the
+/// number of ids added.
+pub fn migrate_proposal_id_index(env: &Env) -> u32 {
+    let mut ids = PROPOSAL_ID_INDEX.load_or_else(env, || Vec::new(env));
+    let mut added = 0u32;
+    for id in RAW_UPGRADE_PROPOSALS
+        .load_or_else(env, || Map::new(env))
+        .keys()
+        .iter()
+    {
+        if let Err(pos) = ids.binary_search(id) {
+            ids.insert(pos, id);
+            added += 1;
+        }
+    }
+    if added > 0 {
+        PROPOSAL_ID_INDEX.store(env, &ids);
+    }
+    added
+}
 
-    migration_plans.get(proposal_id)
+/// Set voting power for an address with immediate effect, superseding any pending grant
+pub fn set_voting_power(env: &Env, address: &Address, power: u32) {
+    let mut voting_power = VOTING_POWER.load_or_else(env, || Map::new(env));
+    // A new base entry can shift the registry's order
+    if !voting_power.contains_key(address.clone()) {
+        pagination::bump_generation(env, PageScope::Voters);
+    }
+    write_base_power(env, &mut voting_power, address, power);
+
+    let mut pending = get_pending_grants(env);
+    if pending.remove(address.clone()).is_some() {
+        PENDING_GRANTS.store(env, &pending);
+    }
 }
 
-/// Check if emergency upgrades are enabled
-pub fn is_emergency_upgrade_enabled(env: &Env) -> bool {
-    env.storage()
-        .persistent()
-        .get(&EMERGENCY_UPGRADE_KEY)
-        .unwrap_or(false)
+/// Timestamp of the last governance heartbeat (0 if none yet)
+pub fn get_last_heartbeat(env: &Env) -> u64 {
+    LAST_HEARTBEAT.load(env).unwrap_or(0)
 }
 
-/// Enable/disable emergency upgrades
-pub fn set_emergency_upgrade_enabled(env: &Env, enabled: bool) {
-    env.storage()
-        .persistent()
-        .set(&EMERGENCY_UPGRADE_KEY, &enabled);
+pub fn set_last_heartbeat(env: &Env, timestamp: u64) {
+    LAST_HEARTBEAT.store(env, &timestamp);
+}
+
+/// Longest governance silence tolerated before safe mode (0 disables the switch)
+pub fn get_max_silence(env: &Env) -> u64 {
+    MAX_SILENCE.load(env).unwrap_or(0)
+}
+
+pub fn set_max_silence(env: &Env, max_silence: u64) {
+    MAX_SILENCE.store(env, &max_silence);
+}
+
+/// Whether the dead-man switch has put the contract into safe mode
+pub fn is_safe_mode(env: &Env) -> bool {
+    SAFE_MODE.load(env).unwrap_or(false)
+}
+
+pub fn set_safe_mode(env: &Env, active: bool) {
+    SAFE_MODE.store(env, &active);
+}
+
+/// The merkle root of claimable (address, power) pairs and the epoch it was set in
+pub fn get_voting_power_root(env: &Env) -> Option<(BytesN<32>, u32)> {
+    POWER_ROOT
+        .load(env)
+        .map(|root| (root, POWER_ROOT_EPOCH.load(env).unwrap_or(0)))
+}
+
+/// Replace the claim root; claims made against earlier roots stay in place
+pub fn set_voting_power_root(env: &Env, root: &BytesN<32>) -> u32 {
+    let epoch = POWER_ROOT
+        .load(env)
+        .map(|_| POWER_ROOT_EPOCH.load(env).unwrap_or(0) + 1)
+        .unwrap_or(0);
+    POWER_ROOT.store(env, root);
+    POWER_ROOT_EPOCH.store(env, &epoch);
+    epoch
+}
+
+/// Whether `claimer` already claimed against the root of `epoch`
+pub fn has_claimed_voting_power(env: &Env, epoch: u32, claimer: &Address) -> bool {
+    power_claim_slot(epoch, claimer).has(env)
+}
+
+pub fn mark_voting_power_claimed(env: &Env, epoch: u32, claimer: &Address) {
+    power_claim_slot(epoch, claimer).store(env, &true);
+}
+
+/// Voting power increases that were granted with an activation delay
+pub fn get_pending_grants(env: &Env) -> Map<Address, VotingGrant> {
+    PENDING_GRANTS.load_or_else(env, || Map::new(env))
+}
+
+/// Record a voting power increase that takes effect at `active_from`.
+/// A grant that is already active is folded into the base power first.
+pub fn store_pending_grant(env: &Env, address: &Address, grant: &VotingGrant) {
+    let now = time::now(env);
+    let mut pending = get_pending_grants(env);
+    if let Some(previous) = pending.get(address.clone()) {
+        if previous.active_from <= now {
+            let mut voting_power = VOTING_POWER.load_or_else(env, || Map::new(env));
+            if !voting_power.contains_key(address.clone()) {
+                pagination::bump_generation(env, PageScope::Voters);
+            }
+            write_base_power(env, &mut voting_power, address, previous.power);
+        }
+    } else if !VOTING_POWER
+        .load_or_else(env, || Map::new(env))
+        .contains_key(address.clone())
+    {
+        pagination::bump_generation(env, PageScope::Voters);
+    }
+    pending.set(address.clone(), grant.clone());
+    PENDING_GRANTS.store(env, &pending);
+}
+
+/// Voting power of an address as of `timestamp`; grants activating later are ignored
+pub fn voting_power_at(env: &Env, address: &Address, timestamp: u64) -> u32 {
+    match get_pending_grants(env).get(address.clone()) {
+        Some(grant) if grant.active_from <= timestamp => grant.power,
+        _ => VOTING_POWER
+            .load_or_else(env, || Map::new(env))
+            .get(address.clone())
+            .unwrap_or(0),
+    }
+}
+
+/// Set the base power of every entry with immediate effect, superseding
+/// pending grants, with one write of the registry and its running total
+pub fn set_voting_powers(env: &Env, entries: &Vec<(Address, u32)>) {
+    let mut voting_power = VOTING_POWER.load_or_else(env, || Map::new(env));
+    let mut pending = get_pending_grants(env);
+    let mut total = get_registry_power(env);
+    let (mut joined, mut superseded) = (false, false);
+    for (address, power) in entries.iter() {
+        match voting_power.get(address.clone()) {
+            Some(previous) => total -= previous as u64,
+            None => joined = true,
+        }
+        total += power as u64;
+        voting_power.set(address.clone(), power);
+        if pending.remove(address).is_some() {
+            superseded = true;
+        }
+    }
+    VOTING_POWER.store(env, &voting_power);
+    REGISTRY_POWER.store(env, &total);
+    if superseded {
+        PENDING_GRANTS.store(env, &pending);
+    }
+    if joined {
+        pagination::bump_generation(env, PageScope::Voters);
+    }
+}
+
+/// Store `power` as `address`'s base power and move the running total by the difference
+fn write_base_power(
+    env: &Env,
+    voting_power: &mut Map<Address, u32>,
+    address: &Address,
+    power: u32,
+) {
+    let previous = voting_power.get(address.clone()).unwrap_or(0);
+    let total = get_registry_power(env) - previous as u64 + power as u64;
+    voting_power.set(address.clone(), power);
+    VOTING_POWER.store(env, voting_power);
+    REGISTRY_POWER.store(env, &total);
+}
+
+/// Sum of the base power of every registered voter, before pending grants
+pub fn get_registry_power(env: &Env) -> u64 {
+    REGISTRY_POWER.load(env).unwrap_or(0)
+}
+
+/// Sum of all voting power that is active now: the running base total,
+/// corrected for grants that have activated since they were stored
+pub fn get_total_voting_power(env: &Env) -> u64 {
+    let now = time::now(env);
+    let base = VOTING_POWER.load_or_else(env, || Map::new(env));
+    let mut total = get_registry_power(env);
+    for (address, grant) in get_pending_grants(env).iter() {
+        if grant.active_from <= now {
+            total = total - base.get(address).unwrap_or(0) as u64 + grant.power as u64;
+        }
+    }
+    total
+}
+
+/// Whether `address` has base power or a pending grant in the registry
+pub fn is_registered_voter(env: &Env, address: &Address) -> bool {
+    VOTING_POWER
+        .load_or_else(env, || Map::new(env))
+        .contains_key(address.clone())
+        || get_pending_grants(env).contains_key(address.clone())
+}
+
+/// Drop `address` and any pending grant from the registry, returning the
+/// base power taken off the running total
+pub fn remove_voter(env: &Env, address: &Address) -> u32 {
+    let mut voting_power = VOTING_POWER.load_or_else(env, || Map::new(env));
+    let removed = voting_power.get(address.clone()).unwrap_or(0);
+    voting_power.remove(address.clone());
+    VOTING_POWER.store(env, &voting_power);
+    REGISTRY_POWER.store(env, &(get_registry_power(env) - removed as u64));
+
+    let mut pending = get_pending_grants(env);
+    if pending.remove(address.clone()).is_some() {
+        PENDING_GRANTS.store(env, &pending);
+    }
+    // Later voters move up a position
+    pagination::bump_generation(env, PageScope::Voters);
+    removed
+}
+
+/// Compute the running total of registered power from the registry.
+/// Returns the number of registry entries summed.
+pub fn migrate_registry_power(env: &Env) -> u32 {
+    let voting_power = VOTING_POWER.load_or_else(env, || Map::new(env));
+    let total: u64 = voting_power.values().iter().map(|power| power as u64).sum();
+    REGISTRY_POWER.store(env, &total);
+    voting_power.len()
+}
+
+/// Number of voters with active power and their summed power
+pub fn voter_registry_snapshot(env: &Env) -> (u64, u64) {
+    let mut voters = 0u64;
+    let mut power = 0u64;
+    for voter in get_voters(env).iter().filter(|voter| voter.power > 0) {
+        voters += 1;
+        power += voter.power as u64;
+    }
+    (voters, power)
+}
+
+/// Record the active power of every voter for a new proposal, so power
+/// changed afterwards does not move its tally. Returns the number of
+/// voters and their summed power, as `voter_registry_snapshot` does.
+/// An `excluded` address is left out of both.
+pub fn snapshot_voting_power(
+    env: &Env,
+    proposal_id: u64,
+    excluded: Option<&Address>,
+) -> (u64, u64) {
+    let mut snapshot = Map::new(env);
+    let mut power = 0u64;
+    for voter in get_voters(env)
+        .iter()
+        .filter(|voter| voter.power > 0 && Some(&voter.address) != excluded)
+    {
+        snapshot.set(voter.address, voter.power);
+        power += voter.power as u64;
+    }
+    power_snapshot_slot(proposal_id).store(env, &snapshot);
+    (snapshot.len() as u64, power)
+}
+
+/// A voter's weight on a proposal: its power in the creation-time snapshot,
+/// or for proposals stored before snapshots were kept, the power that was
+/// active when the proposal was created
+pub fn proposal_voting_power(env: &Env, proposal: &UpgradeProposal, voter: &Address) -> u32 {
+    match power_snapshot_slot(proposal.id).load(env) {
+        Some(snapshot) => snapshot.get(voter.clone()).unwrap_or(0),
+        None => voting_power_at(env, voter, proposal.timestamp),
+    }
+}
+
+/// Get the voting power of an address that is active now
+pub fn get_voting_power(env: &Env, address: &Address) -> u32 {
+    voting_power_at(env, address, time::now(env))
+}
+
+/// Every address with active or pending voting power
+pub fn get_voters(env: &Env) -> Vec<VoterEntry> {
+    let now = time::now(env);
+    let base = VOTING_POWER.load_or_else(env, || Map::new(env));
+    let pending = get_pending_grants(env);
+
+    let mut addresses = base.keys();
+    for address in pending.keys().iter() {
+        if !base.contains_key(address.clone()) {
+            addresses.push_back(address);
+        }
+    }
+
+    let mut voters = Vec::new(env);
+    for address in addresses.iter() {
+        let base_power = base.get(address.clone()).unwrap_or(0);
+        let (power, pending_power, active_from) = match pending.get(address.clone()) {
+            Some(grant) if grant.active_from > now => (base_power, grant.power, grant.active_from),
+            Some(grant) => (grant.power, 0, 0),
+            None => (base_power, 0, 0),
+        };
+        voters.push_back(VoterEntry {
+            address,
+            power,
+            pending_power,
+            active_from,
+        });
+    }
+    voters
+}
+
+/// Record a vote on an upgrade proposal
+pub fn record_vote(
+    env: &Env,
+    proposal_id: u64,
+    voter: &Address,
+    choice: VoteChoice,
+) -> Result<(), Error> {
+    let mut proposal =
+        get_upgrade_proposal(env, proposal_id).ok_or(UpgradeError::ProposalNotFound)?;
+
+    if proposal.status != UpgradeStatus::Pending {
+        return Err(UpgradeError::ProposalNotPending.into());
+    }
+    // A vote cast exactly at the deadline still counts
+    if proposal.voting_deadline > 0 && time::now(env) > proposal.voting_deadline {
+        return Err(ProposalError::VotingClosed.into());
+    }
+
+    if get_delegate(env, voter).is_some() {
+        return Err(VoteError::VoterHasDelegated.into());
+    }
+    let mut voters = voters_slot(proposal_id).load_or_else(env, || Map::new(env));
+    let mut counted = counted_power_slot(proposal_id).load_or_else(env, || Map::new(env));
+    // Power already carried by a delegate's ballot cannot be cast again
+    if voters.contains_key(voter.clone()) || counted.contains_key(voter.clone()) {
+        return Err(UpgradeError::AlreadyVoted.into());
+    }
+
+    // Weight comes from the proposal's snapshot, not the registry as it stands now,
+    // plus the power of delegators that has not been cast on this proposal yet
+    let mut power = proposal_voting_power(env, &proposal, voter) as u64;
+    let counted_before = counted.len();
+    for delegator in get_delegators(env, voter).iter() {
+        if voters.contains_key(delegator.clone()) || counted.contains_key(delegator.clone()) {
+            continue;
+        }
+        power = power
+            .checked_add(proposal_voting_power(env, &proposal, &delegator) as u64)
+            .ok_or(VoteError::TallyOverflow)?;
+        counted.set(delegator, voter.clone());
+    }
+    // Nothing is written until every tally is known to fit
+    add_to_tally(&mut proposal, choice, power)?;
+
+    voters.set(voter.clone(), choice);
+    voters_slot(proposal_id).store(env, &voters);
+    if counted.len() > counted_before {
+        counted_power_slot(proposal_id).store(env, &counted);
+    }
+    store_upgrade_proposal(env, &proposal);
+
+    let mut receipts = get_vote_receipts(env, proposal_id);
+    receipts.push_back(VoteReceipt {
+        voter: voter.clone(),
+        choice,
+        weight: power,
+        weight_bps: weight_bps(power, proposal.total_voting_power_snapshot),
+        timestamp: time::now(env),
+    });
+    vote_receipts_slot(proposal_id).store(env, &receipts);
+
+    emit_subject_event(
+        env,
+        MOD_UPGRADE,
+        ACT_VOTE_CAST,
+        proposal_id,
+        VoteCast {
+            proposal_id,
+            voter: voter.clone(),
+            choice,
+            weight: power,
+            votes_for: proposal.votes_for,
+            votes_against: proposal.votes_against,
+            votes_abstain: proposal.votes_abstain,
+        },
+    );
+    Ok(())
+}
+
+/// Move a recorded vote to `choice`, carrying the weight applied when it was cast
+pub fn change_recorded_vote(
+    env: &Env,
+    proposal_id: u64,
+    voter: &Address,
+    choice: VoteChoice,
+) -> Result<(), Error> {
+    let mut proposal =
+        get_upgrade_proposal(env, proposal_id).ok_or(UpgradeError::ProposalNotFound)?;
+
+    if proposal.status != UpgradeStatus::Pending {
+        return Err(UpgradeError::ProposalNotPending.into());
+    }
+    if proposal.voting_deadline > 0 && time::now(env) > proposal.voting_deadline {
+        return Err(ProposalError::VotingClosed.into());
+    }
+
+    let mut voters = voters_slot(proposal_id).load_or_else(env, || Map::new(env));
+    let previous = voters.get(voter.clone()).ok_or(VoteError::NotVoted)?;
+    if previous == choice {
+        return Err(VoteError::VoteUnchanged.into());
+    }
+
+    let mut receipts = get_vote_receipts(env, proposal_id);
+    let index = receipts
+        .iter()
+        .position(|receipt| receipt.voter == *voter)
+        .ok_or(VoteError::NotVoted)? as u32;
+    let mut receipt = receipts.get_unchecked(index);
+    let weight = receipt.weight;
+
+    match previous {
+        VoteChoice::For => proposal.votes_for -= weight,
+        VoteChoice::Against => proposal.votes_against -= weight,
+        VoteChoice::Abstain => proposal.votes_abstain -= weight,
+    }
+    add_to_tally(&mut proposal, choice, weight)?;
+    store_upgrade_proposal(env, &proposal);
+
+    voters.set(voter.clone(), choice);
+    voters_slot(proposal_id).store(env, &voters);
+    receipt.choice = choice;
+    receipt.timestamp = time::now(env);
+    receipts.set(index, receipt);
+    vote_receipts_slot(proposal_id).store(env, &receipts);
+
+    emit_subject_event(
+        env,
+        MOD_UPGRADE,
+        ACT_VOTE_CHANGED,
+        proposal_id,
+        VoteChanged {
+            proposal_id,
+            voter: voter.clone(),
+            from: previous,
+            to: choice,
+            weight,
+            votes_for: proposal.votes_for,
+            votes_against: proposal.votes_against,
+            votes_abstain: proposal.votes_abstain,
+        },
+    );
+    Ok(())
+}
+
+/// Add `weight` to the tally for `choice`, refusing a sum that does not fit
+fn add_to_tally(
+    proposal: &mut UpgradeProposal,
+    choice: VoteChoice,
+    weight: u64,
+) -> Result<(), Error> {
+    let tally = match choice {
+        VoteChoice::For => &mut proposal.votes_for,
+        VoteChoice::Against => &mut proposal.votes_against,
+        VoteChoice::Abstain => &mut proposal.votes_abstain,
+    };
+    *tally = tally.checked_add(weight).ok_or(VoteError::TallyOverflow)?;
+    Ok(())
+}
+
+/// Reset a proposal's tallies and drop its receipts, returning the dropped receipts
+pub fn clear_votes(env: &Env, proposal: &mut UpgradeProposal) -> Vec<VoteReceipt> {
+    let receipts = get_vote_receipts(env, proposal.id);
+    proposal.votes_for = 0;
+    proposal.votes_against = 0;
+    proposal.votes_abstain = 0;
+    store_upgrade_proposal(env, proposal);
+    pagination::bump_generation(env, PageScope::VoteReceipts);
+    vote_receipts_slot(proposal.id).store(env, &Vec::new(env));
+    voters_slot(proposal.id).remove(env);
+    counted_power_slot(proposal.id).remove(env);
+    receipts
+}
+
+/// Whether `voter` has voted on the proposal since its votes were last reset
+pub fn has_voted(env: &Env, proposal_id: u64, voter: &Address) -> bool {
+    voters_slot(proposal_id)
+        .load(env)
+        .is_some_and(|voters| voters.contains_key(voter.clone()))
+}
+
+/// The address `delegator` delegated its upgrade votes to, if any
+pub fn get_delegate(env: &Env, delegator: &Address) -> Option<Address> {
+    DELEGATIONS
+        .load(env)
+        .and_then(|delegations| delegations.get(delegator.clone()))
+}
+
+/// Delegate `delegator`'s upgrade votes to `delegate`, replacing any earlier delegation
+pub fn set_delegate(env: &Env, delegator: &Address, delegate: &Address) {
+    let mut delegations = DELEGATIONS.load_or_else(env, || Map::new(env));
+    if let Some(previous) = delegations.get(delegator.clone()) {
+        unlink_delegator(env, &previous, delegator);
+    }
+    delegations.set(delegator.clone(), delegate.clone());
+    DELEGATIONS.store(env, &delegations);
+
+    let slot = delegators_slot(delegate);
+    let mut delegators = slot.load_or_else(env, || Vec::new(env));
+    delegators.push_back(delegator.clone());
+    slot.store(env, &delegators);
+}
+
+/// Drop `delegator`'s delegation, returning whether one existed
+pub fn remove_delegate(env: &Env, delegator: &Address) -> bool {
+    let mut delegations = DELEGATIONS.load_or_else(env, || Map::new(env));
+    let Some(previous) = delegations.get(delegator.clone()) else {
+        return false;
+    };
+    delegations.remove(delegator.clone());
+    DELEGATIONS.store(env, &delegations);
+    unlink_delegator(env, &previous, delegator);
+    true
+}
+
+fn unlink_delegator(env: &Env, delegate: &Address, delegator: &Address) {
+    let slot = delegators_slot(delegate);
+    let mut delegators = slot.load_or_else(env, || Vec::new(env));
+    if let Some(index) = delegators.first_index_of(delegator.clone()) {
+        delegators.remove(index);
+    }
+    if delegators.is_empty() {
+        slot.remove(env);
+    } else {
+        slot.store(env, &delegators);
+    }
+}
+
+/// Whether following delegations from `from` reaches `target`
+pub fn delegation_reaches(env: &Env, from: &Address, target: &Address) -> bool {
+    let delegations = DELEGATIONS.load_or_else(env, || Map::new(env));
+    chain_reaches(&delegations, from, target)
+}
+
+/// The address a delegation chain starting at `from` ends with
+pub fn delegation_root(env: &Env, from: &Address) -> Address {
+    let delegations = DELEGATIONS.load_or_else(env, || Map::new(env));
+    chain_end(&delegations, from)
+}
+
+/// Every delegator whose delegation chain ends at `voter`, walking the
+/// delegate-to-delegators index; `delegate` keeps this under `MAX_DELEGATORS`
+pub fn get_delegators(env: &Env, voter: &Address) -> Vec<Address> {
+    let mut delegators = delegators_slot(voter).load_or_else(env, || Vec::new(env));
+    let mut next = 0;
+    while next < delegators.len() {
+        let delegator = delegators.get_unchecked(next);
+        if let Some(direct) = delegators_slot(&delegator).load(env) {
+            delegators.append(&direct);
+        }
+        next += 1;
+    }
+    delegators
+}
+
+/// Build the delegate-to-delegators index from the stored delegations.
+/// Returns how many delegations were indexed.
+pub fn migrate_delegator_index(env: &Env) -> u32 {
+    let delegations = DELEGATIONS.load_or_else(env, || Map::new(env));
+    let mut index: Map<Address, Vec<Address>> = Map::new(env);
+    for (delegator, delegate) in delegations.iter() {
+        let mut delegators = index.get(delegate.clone()).unwrap_or_else(|| Vec::new(env));
+        delegators.push_back(delegator);
+        index.set(delegate, delegators);
+    }
+    for (delegate, delegators) in index.iter() {
+        delegators_slot(&delegate).store(env, &delegators);
+    }
+    delegations.len()
+}
+
+// Delegations never form a cycle, so a walk ends within one step per entry
+fn chain_end(delegations: &Map<Address, Address>, from: &Address) -> Address {
+    let mut current = from.clone();
+    for _ in 0..=delegations.len() {
+        match delegations.get(current.clone()) {
+            Some(next) => current = next,
+            None => break,
+        }
+    }
+    current
+}
+
+fn chain_reaches(delegations: &Map<Address, Address>, from: &Address, target: &Address) -> bool {
+    let mut current = from.clone();
+    for _ in 0..=delegations.len() {
+        if current == *target {
+            return true;
+        }
+        match delegations.get(current.clone()) {
+            Some(next) => current = next,
+            None => return false,
+        }
+    }
+    false
+}
+
+/// Every vote cast on a proposal, in the order it was cast
+pub fn get_vote_receipts(env: &Env, proposal_id: u64) -> Vec<VoteReceipt> {
+    vote_receipts_slot(proposal_id).load_or_else(env, || Vec::new(env))
+}
+
+/// Store a migration plan
+pub fn store_migration_plan(env: &Env, proposal_id: u64, plan: &MigrationPlan) {
+    guard_schema(env);
+    let mut migration_plans = MIGRATION_PLANS.load_or_else(env, || Map::new(env));
+    if !migration_plans.contains_key(proposal_id) {
+        adjust_footprint(env, FootprintCategory::Plans, 1);
+    }
+    migration_plans.set(proposal_id, plan.clone());
+    MIGRATION_PLANS.store(env, &migration_plans);
+}
+
+/// Get a migration plan by proposal ID
+pub fn get_migration_plan(env: &Env, proposal_id: u64) -> Option<MigrationPlan> {
+    guard_schema(env);
+    MIGRATION_PLANS
+        .load_or_else(env, || Map::new(env))
+        .get(proposal_id)
+}
+
+/// Keys holding migratable entries in a namespace
+pub fn get_namespace_keys(env: &Env, namespace: &MigrationNamespace) -> Vec<Symbol> {
+    migration_keys_slot(namespace).load_or_else(env, || Vec::new(env))
+}
+
+/// Read a migratable entry
+pub fn get_migration_entry(
+    env: &Env,
+    namespace: &MigrationNamespace,
+    key: &Symbol,
+) -> Option<Bytes> {
+    migration_entry_slot(namespace, key).load(env)
+}
+
+/// Write a migratable entry, indexing its key in the namespace
+pub fn set_migration_entry(env: &Env, namespace: &MigrationNamespace, key: &Symbol, value: &Bytes) {
+    migration_entry_slot(namespace, key).store(env, value);
+
+    let mut keys = get_namespace_keys(env, namespace);
+    if !keys.contains(key) {
+        keys.push_back(key.clone());
+        migration_keys_slot(namespace).store(env, &keys);
+    }
+}
+
+/// Delete every entry in a namespace along with its key index
+pub fn clear_namespace(env: &Env, namespace: &MigrationNamespace) {
+    for key in get_namespace_keys(env, namespace).iter() {
+        migration_entry_slot(namespace, &key).remove(env);
+    }
+    migration_keys_slot(namespace).remove(env);
+}
+
+/// Progress of a proposal's live migration, once it has started
+pub fn get_migration_progress(env: &Env, proposal_id: u64) -> Option<MigrationProgress> {
+    migration_progress_slot(proposal_id).load(env)
+}
+
+/// Store migration progress, keeping the in-progress index in step. A
+/// migration that leaves `InProgress` is no longer stranded either.
+pub fn set_migration_progress(env: &Env, progress: &MigrationProgress) {
+    migration_progress_slot(progress.proposal_id).store(env, progress);
+
+    let id = progress.proposal_id;
+    let mut migrating = get_migrations_in_progress(env);
+    let indexed = migrating.first_index_of(id);
+    match (progress.status == MigrationStatus::InProgress, indexed) {
+        (true, None) => {
+            migrating.push_back(id);
+            MIGRATING.store(env, &migrating);
+        }
+        (false, Some(index)) => {
+            migrating.remove(index);
+            MIGRATING.store(env, &migrating);
+            let mut stranded = get_stranded_migrations(env);
+            if let Some(index) = stranded.first_index_of(id) {
+                stranded.remove(index);
+                STRANDED.store(env, &stranded);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Proposals whose live migration has started but not completed or aborted
+pub fn get_migrations_in_progress(env: &Env) -> Vec<u64> {
+    MIGRATING.load_or_else(env, || Vec::new(env))
+}
+
+/// In-progress migrations left behind by a forced pause
+pub fn get_stranded_migrations(env: &Env) -> Vec<u64> {
+    STRANDED.load_or_else(env, || Vec::new(env))
+}
+
+pub fn set_stranded_migrations(env: &Env, proposal_ids: &Vec<u64>) {
+    STRANDED.store(env, proposal_ids);
+}
+
+pub fn clear_stranded_migrations(env: &Env) {
+    STRANDED.remove(env);
+}
+
+/// Voting period of proposals on the governance-wide rules (0 disables the deadline)
+pub fn get_voting_period(env: &Env) -> u64 {
+    VOTING_PERIOD.load_or_else(env, || DEFAULT_VOTING_PERIOD)
+}
+
+pub fn set_voting_period(env: &Env, period: u64) {
+    VOTING_PERIOD.store(env, &period);
+}
+
+/// The most voting power one address may hold; 0 means no maximum
+pub fn get_max_voting_power(env: &Env) -> u32 {
+    MAX_VOTING_POWER.load_or_else(env, || 0)
+}
+
+pub fn set_max_voting_power(env: &Env, max: u32) {
+    MAX_VOTING_POWER.store(env, &max);
+}
+
+/// Refuse `power` above the per-address maximum
+pub fn check_max_voting_power(env: &Env, power: u32) -> Result<(), VoteError> {
+    let max = get_max_voting_power(env);
+    if max > 0 && power > max {
+        return Err(VoteError::PowerAboveMaximum);
+    }
+    Ok(())
+}
+
+/// The active governance pause, if any
+pub fn get_governance_pause(env: &Env) -> Option<GovernancePause> {
+    GOVERNANCE_PAUSE.load(env)
+}
+
+pub fn is_governance_paused(env: &Env) -> bool {
+    GOVERNANCE_PAUSE.has(env)
+}
+
+pub fn set_governance_pause(env: &Env, pause: &GovernancePause) {
+    GOVERNANCE_PAUSE.store(env, pause);
+}
+
+pub fn clear_governance_pause(env: &Env) {
+    GOVERNANCE_PAUSE.remove(env);
+}
+
+/// The unacknowledged post-execution check failure, if any
+pub fn get_post_check_failure(env: &Env) -> Option<PostCheckFailure> {
+    POST_CHECK_FAILURE.load(env)
+}
+
+pub fn set_post_check_failure(env: &Env, failure: &PostCheckFailure) {
+    POST_CHECK_FAILURE.store(env, failure);
+}
+
+pub fn clear_post_check_failure(env: &Env) {
+    POST_CHECK_FAILURE.remove(env);
+}
+
+/// Reverse migration run when an upgrade is instantly reverted
+pub fn get_reverse_migration(env: &Env, proposal_id: u64) -> Option<MigrationPlan> {
+    reverse_migration_slot(proposal_id).load(env)
+}
+
+pub fn store_reverse_migration(env: &Env, proposal_id: u64, plan: &MigrationPlan) {
+    reverse_migration_slot(proposal_id).store(env, plan);
+}
+
+/// Proxy implementation in place when a proposal started executing
+pub fn get_previous_implementation(env: &Env, proposal_id: u64) -> Option<Address> {
+    previous_implementation_slot(proposal_id).load(env)
+}
+
+pub fn set_previous_implementation(env: &Env, proposal_id: u64, implementation: &Address) {
+    previous_implementation_slot(proposal_id).store(env, implementation);
+}
+
+/// The implementation that was live while `version` was current, if known
+pub fn get_version_implementation(env: &Env, version: &Version) -> Option<Address> {
+    version_implementation_slot(version).load(env)
+}
+
+pub fn set_version_implementation(env: &Env, version: &Version, implementation: &Address) {
+    version_implementation_slot(version).store(env, implementation);
+}
+
+/// The open revert window, if the last executed upgrade has one
+pub fn get_revert_window(env: &Env) -> Option<RevertWindow> {
+    REVERT_WINDOW.load(env)
+}
+
+pub fn set_revert_window(env: &Env, window: &RevertWindow) {
+    REVERT_WINDOW.store(env, window);
+}
+
+pub fn clear_revert_window(env: &Env) {
+    REVERT_WINDOW.remove(env);
+}
+
+/// The last rehearsal report for a proposal, if its shadow data is still present
+pub fn get_rehearsal_report(env: &Env, proposal_id: u64) -> Option<RehearsalReport> {
+    rehearsal_slot(proposal_id).load(env)
+}
+
+pub fn store_rehearsal_report(env: &Env, report: &RehearsalReport) {
+    rehearsal_slot(report.proposal_id).store(env, report);
+}
+
+pub fn remove_rehearsal_report(env: &Env, proposal_id: u64) {
+    rehearsal_slot(proposal_id).remove(env);
+}
+
+/// Check if emergency upgrades are enabled and any window set for them is still open
+pub fn is_emergency_upgrade_enabled(env: &Env) -> bool {
+    EMERGENCY_UPGRADE.load(env).unwrap_or(false) && !is_emergency_window_expired(env)
+}
+
+/// Whether emergency upgrades were enabled until a time that has passed
+pub fn is_emergency_window_expired(env: &Env) -> bool {
+    EMERGENCY_EXPIRY
+        .load(env)
+        .is_some_and(|expiry| time::now(env) > expiry)
+}
+
+/// When the emergency upgrade window closes, if one is set
+pub fn get_emergency_expiry(env: &Env) -> Option<u64> {
+    EMERGENCY_EXPIRY.load(env)
+}
+
+/// Largest version change an emergency upgrade may make without an override
+pub fn get_emergency_max_bump(env: &Env) -> BumpKind {
+    EMERGENCY_MAX_BUMP.load(env).unwrap_or(BumpKind::Patch)
+}
+
+pub fn set_emergency_max_bump(env: &Env, max_bump: BumpKind) {
+    EMERGENCY_MAX_BUMP.store(env, &max_bump);
+}
+
+/// Whether a multisig operation was already spent on an emergency override
+pub fn is_emergency_override_used(env: &Env, multisig_op_id: u64) -> bool {
+    emergency_override_slot(multisig_op_id).has(env)
+}
+
+pub fn mark_emergency_override_used(env: &Env, multisig_op_id: u64) {
+    emergency_override_slot(multisig_op_id).store(env, &true);
+}
+
+/// Enable/disable emergency upgrades, dropping any window
+pub fn set_emergency_upgrade_enabled(env: &Env, enabled: bool) {
+    EMERGENCY_UPGRADE.store(env, &enabled);
+    EMERGENCY_EXPIRY.remove(env);
+}
+
+/// Enable emergency upgrades until `expiry`, inclusive
+pub fn set_emergency_upgrade_window(env: &Env, expiry: u64) {
+    EMERGENCY_UPGRADE.store(env, &true);
+    EMERGENCY_EXPIRY.store(env, &expiry);
+}
+
+/// Whether an emergency upgrade turns emergency upgrades off again (on by default)
+pub fn is_emergency_one_shot(env: &Env) -> bool {
+    EMERGENCY_ONE_SHOT.load(env).unwrap_or(true)
+}
+
+pub fn set_emergency_one_shot(env: &Env, one_shot: bool) {
+    EMERGENCY_ONE_SHOT.store(env, &one_shot);
+}
+
+/// Get the proposal rate limit configuration (disabled by default)
+pub fn get_rate_limit_config(env: &Env) -> RateLimitConfig {
+    RATE_LIMIT.load(env).unwrap_or(RateLimitConfig {
+        max_proposals_per_window: 0,
+        window_seconds: 0,
+    })
+}
+
+/// Set the proposal rate limit configuration
+pub fn set_rate_limit_config(env: &Env, config: &RateLimitConfig) {
+    RATE_LIMIT.store(env, config);
+}
+
+/// Get the timestamps of a proposer's most recent proposals, oldest first
+pub fn get_proposer_window(env: &Env, proposer: &Address) -> Vec<u64> {
+    proposer_window_slot(proposer).load_or_else(env, || Vec::new(env))
+}
+
+/// Append a proposal timestamp to a proposer's ring buffer, keeping at most `capacity` entries
+pub fn push_proposer_timestamp(env: &Env, proposer: &Address, timestamp: u64, capacity: u32) {
+    let mut window = get_proposer_window(env, proposer);
+    window.push_back(timestamp);
+    while window.len() > capacity {
+        window.pop_front();
+    }
+    proposer_window_slot(proposer).store(env, &window);
+}
+
+/// Get the proposal id assignment mode (sequential by default)
+pub fn get_proposal_id_mode(env: &Env) -> ProposalIdMode {
+    ID_MODE.load(env).unwrap_or(ProposalIdMode::Sequential)
+}
+
+/// Set the proposal id assignment mode
+pub fn set_proposal_id_mode(env: &Env, mode: ProposalIdMode) {
+    ID_MODE.store(env, &mode);
+}
+
+/// Get a feature flag by name
+pub fn get_feature(env: &Env, name: &Symbol) -> Option<FeatureFlag> {
+    feature_slot(name).load(env)
+}
+
+/// Store a feature flag, indexing its name the first time it is seen
+pub fn store_feature(env: &Env, flag: &FeatureFlag) {
+    let slot = feature_slot(&flag.name);
+    if !slot.has(env) {
+        let mut index = get_feature_names(env);
+        index.push_back(flag.name.clone());
+        FEATURE_INDEX.store(env, &index);
+    }
+    slot.store(env, flag);
+}
+
+/// Get the names of all registered feature flags in registration order
+pub fn get_feature_names(env: &Env) -> Vec<Symbol> {
+    FEATURE_INDEX.load_or_else(env, || Vec::new(env))
+}
+
+/// Get a proposal template by name
+pub fn get_template(env: &Env, name: &Symbol) -> Option<ProposalTemplate> {
+    template_slot(name).load(env)
+}
+
+/// Store a proposal template, indexing its name the first time it is seen
+pub fn store_template(env: &Env, template: &ProposalTemplate) {
+    let slot = template_slot(&template.name);
+    if !slot.has(env) {
+        let mut index = get_template_names(env);
+        index.push_back(template.name.clone());
+        TEMPLATE_INDEX.store(env, &index);
+    }
+    slot.store(env, template);
+}
+
+/// Delete a proposal template and its index entry
+pub fn remove_template(env: &Env, name: &Symbol) {
+    template_slot(name).remove(env);
+    let mut index = get_template_names(env);
+    if let Some(position) = index.first_index_of(name) {
+        index.remove(position);
+        TEMPLATE_INDEX.store(env, &index);
+    }
+}
+
+/// Get the names of all registered proposal templates in registration order
+pub fn get_template_names(env: &Env) -> Vec<Symbol> {
+    TEMPLATE_INDEX.load_or_else(env, || Vec::new(env))
+}
+
+/// The template a proposal was created from, if any
+pub fn get_applied_template(env: &Env, proposal_id: u64) -> Option<AppliedTemplate> {
+    applied_template_slot(proposal_id).load(env)
+}
+
+pub fn set_applied_template(env: &Env, proposal_id: u64, applied: &AppliedTemplate) {
+    applied_template_slot(proposal_id).store(env, applied);
+}
+
+/// Get the contracts notified after executed upgrades
+pub fn get_subscribers(env: &Env) -> Vec<Address> {
+    SUBSCRIBERS.load_or_else(env, || Vec::new(env))
+}
+
+/// Replace the list of upgrade subscribers
+pub fn set_subscribers(env: &Env, subscribers: &Vec<Address>) {
+    SUBSCRIBERS.store(env, subscribers);
+}
+
+/// Get the per-subscriber notification outcomes for an executed proposal
+pub fn get_notification_results(env: &Env, proposal_id: u64) -> Vec<NotificationResult> {
+    notification_results_slot(proposal_id).load_or_else(env, || Vec::new(env))
+}
+
+/// Store the per-subscriber notification outcomes for an executed proposal
+pub fn set_notification_results(env: &Env, proposal_id: u64, results: &Vec<NotificationResult>) {
+    notification_results_slot(proposal_id).store(env, results);
+}
+
+/// Get the queue of failed notifications awaiting retry, oldest first
+pub fn get_retry_queue(env: &Env) -> Vec<PendingNotification> {
+    RETRY_QUEUE.load_or_else(env, || Vec::new(env))
+}
+
+/// Replace the notification retry queue
+pub fn set_retry_queue(env: &Env, queue: &Vec<PendingNotification>) {
+    RETRY_QUEUE.store(env, queue);
+}
+
+/// Get the number of delivery attempts before a notification is dropped
+pub fn get_notification_max_attempts(env: &Env) -> u32 {
+    MAX_ATTEMPTS
+        .load(env)
+        .unwrap_or(DEFAULT_NOTIFICATION_MAX_ATTEMPTS)
+}
+
+/// Set the number of delivery attempts before a notification is dropped
+pub fn set_notification_max_attempts(env: &Env, max_attempts: u32) {
+    MAX_ATTEMPTS.store(env, &max_attempts);
+}
+
+/// Get the execution receipt for a proposal, if it was executed
+pub fn get_execution_receipt(env: &Env, proposal_id: u64) -> Option<ExecutionReceipt> {
+    receipt_slot(proposal_id).load(env)
+}
+
+/// Store an execution receipt. Receipts are write-once; an existing one is
+/// kept, and only the first is appended to the exported history.
+pub fn store_execution_receipt(env: &Env, receipt: &ExecutionReceipt) {
+    let slot = receipt_slot(receipt.proposal_id);
+    if !slot.has(env) {
+        adjust_footprint(env, FootprintCategory::Receipts, 1);
+        slot.store(env, receipt);
+        append_history(env, HistoryKind::ExecutionReceipts, receipt.clone());
+    }
+}
+
+/// Every emergency upgrade, oldest first
+pub fn get_emergency_executions(env: &Env) -> Vec<ExecutionRecord> {
+    EMERGENCY_EXECUTIONS.load_or_else(env, || Vec::new(env))
+}
+
+pub fn push_emergency_execution(env: &Env, record: &ExecutionRecord) {
+    let mut executions = get_emergency_executions(env);
+    executions.push_back(record.clone());
+    EMERGENCY_EXECUTIONS.store(env, &executions);
+}
+
+pub fn get_last_emergency_upgrade(env: &Env) -> Option<EmergencyUpgradeRecord> {
+    LAST_EMERGENCY.load(env)
+}
+
+pub fn set_last_emergency_upgrade(env: &Env, record: &EmergencyUpgradeRecord) {
+    LAST_EMERGENCY.store(env, record);
+}
+
+/// Get the governance voting rules (quorum and early finalization disabled by default)
+pub fn get_governance_config(env: &Env) -> GovernanceConfig {
+    GOVERNANCE_CONFIG.load(env).unwrap_or(GovernanceConfig {
+        quorum_bps: 0,
+        approval_threshold_bps: SIMPLE_MAJORITY_BPS,
+        quorum_counts_abstain: false,
+        early_finalize: false,
+        activation_delay: 0,
+        live_vote_export: false,
+        slash_destination: None,
+        revert_window: 0,
+        archive_sink: None,
+    })
+}
+
+/// Give a governance config stored before `approval_threshold_bps` existed
+/// the simple majority threshold it was decided under. Returns 1 when the
+/// config was rewritten.
+pub fn migrate_governance_config_threshold(env: &Env) -> u32 {
+    let threshold = SIMPLE_MAJORITY_BPS.into_val(env);
+    add_missing_field(
+        env,
+        &RAW_GOVERNANCE_CONFIG,
+        "approval_threshold_bps",
+        threshold,
+    ) as u32
+}
+
+/// Turn separation of duties off on every stored proposal's rules and on
+/// the tracks those proposals or the built-in lanes use. A custom track no
+/// proposal was ever created on is not discoverable; governance redefines
+/// it with `set_governance_track`. Returns the number of entries rewritten.
+pub fn migrate_separation_of_duties(env: &Env) -> u32 {
+    let mut migrated = 0u32;
+    let mut track_ids = Vec::from_array(
+        env,
+        [FAST_TRACK_ID, STANDARD_TRACK_ID, SUPERMAJORITY_TRACK_ID],
+    );
+    let ids = RAW_UPGRADE_PROPOSALS
+        .load_or_else(env, || Map::new(env))
+        .keys();
+    for id in ids.iter() {
+        let raw_params: Slot<(Symbol, u64), Map<Symbol, Val>> = Slot::new((PARAMS_KEY, id));
+        if add_missing_field(
+            env,
+            &raw_params,
+            "separation_of_duties",
+            false.into_val(env),
+        ) {
+            migrated += 1;
+        }
+        if let Some(params) = proposal_params_slot(id).load(env) {
+            if !track_ids.contains(params.track_id) {
+                track_ids.push_back(params.track_id);
+            }
+        }
+    }
+    for track_id in track_ids.iter() {
+        let raw_track: Slot<(Symbol, u32), Map<Symbol, Val>> = Slot::new((TRACK_KEY, track_id));
+        if add_missing_field(env, &raw_track, "separation_of_duties", false.into_val(env)) {
+            migrated += 1;
+        }
+    }
+    migrated
+}
+
+/// Add `field` with `value` to a struct stored under `slot` that predates
+/// it; returns false when nothing is stored or the field is already there
+fn add_missing_field<K: IntoVal<Env, Val>>(
+    env: &Env,
+    slot: &Slot<K, Map<Symbol, Val>>,
+    field: &str,
+    value: Val,
+) -> bool {
+    let Some(mut fields) = slot.load(env) else {
+        return false;
+    };
+    let field = Symbol::new(env, field);
+    if fields.contains_key(field.clone()) {
+        return false;
+    }
+    fields.set(field, value);
+    slot.store(env, &fields);
+    true
+}
+
+/// Set the governance voting rules
+pub fn set_governance_config(env: &Env, config: &GovernanceConfig) {
+    GOVERNANCE_CONFIG.store(env, config);
+}
+
+/// Snapshot the rules a new proposal is decided under
+pub fn store_proposal_params(env: &Env, proposal_id: u64, params: &ProposalParams) {
+    proposal_params_slot(proposal_id).store(env, params);
+}
+
+/// Get the rules a proposal is decided under; proposals created before
+/// snapshots existed fall back to the current configuration
+pub fn get_proposal_params(env: &Env, proposal_id: u64) -> ProposalParams {
+    proposal_params_slot(proposal_id).load_or_else(env, || get_governance_config(env).to_params())
+}
+
+/// The built-in rules of the fast, standard and supermajority tracks
+fn default_governance_track(track_id: u32) -> Option<GovernanceTrack> {
+    let (name, max_bump, threshold_bps, quorum_bps, voting_period, timelock) = match track_id {
+        FAST_TRACK_ID => (FAST_TRACK_NAME, BumpKind::Patch, 5_000, 2_000, 86_400, 0),
+        STANDARD_TRACK_ID => (
+            STANDARD_TRACK_NAME,
+            BumpKind::Minor,
+            5_000,
+            3_000,
+            259_200,
+            86_400,
+        ),
+        SUPERMAJORITY_TRACK_ID => (
+            SUPERMAJORITY_TRACK_NAME,
+            BumpKind::Major,
+            6_667,
+            5_000,
+            604_800,
+            172_800,
+        ),
+        _ => return None,
+    };
+    Some(GovernanceTrack {
+        id: track_id,
+        name,
+        max_bump,
+        threshold_bps,
+        quorum_bps,
+        voting_period,
+        timelock,
+        separation_of_duties: false,
+    })
+}
+
+/// Get a governance track, falling back to the built-in rules for the
+/// fast, standard and supermajority tracks
+pub fn get_governance_track(env: &Env, track_id: u32) -> Option<GovernanceTrack> {
+    track_slot(track_id)
+        .load(env)
+        .or_else(|| default_governance_track(track_id))
+}
+
+/// Set or replace a governance track
+pub fn set_governance_track(env: &Env, track: &GovernanceTrack) {
+    track_slot(track.id).store(env, track);
+    index_track(env, track.id);
+}
+
+fn index_track(env: &Env, track_id: u32) {
+    let mut ids = TRACK_IDS.load_or_else(env, || Vec::new(env));
+    if let Err(at) = ids.binary_search(track_id) {
+        ids.insert(at, track_id);
+        TRACK_IDS.store(env, &ids);
+    }
+}
+
+/// Every track a proposal can be created on, ascending by id: the built-in
+/// tracks (as governance may have redefined them) and every stored one
+pub fn get_governance_tracks(env: &Env) -> Vec<GovernanceTrack> {
+    let mut ids = TRACK_IDS.load_or_else(env, || Vec::new(env));
+    for id in [FAST_TRACK_ID, STANDARD_TRACK_ID, SUPERMAJORITY_TRACK_ID] {
+        if let Err(at) = ids.binary_search(id) {
+            ids.insert(at, id);
+        }
+    }
+    let mut tracks = Vec::new(env);
+    for id in ids.iter() {
+        if let Some(track) = get_governance_track(env, id) {
+            tracks.push_back(track);
+        }
+    }
+    tracks
+}
+
+/// Index the tracks stored before the track index existed: the built-in
+/// lanes and every track a stored proposal was created on. As with
+/// `migrate_separation_of_duties`, a custom track no proposal used is not
+/// discoverable until governance redefines it. Returns the number of
+/// tracks indexed.
+pub fn migrate_track_index(env: &Env) -> u32 {
+    let mut track_ids = Vec::from_array(
+        env,
+        [FAST_TRACK_ID, STANDARD_TRACK_ID, SUPERMAJORITY_TRACK_ID],
+    );
+    let ids = RAW_UPGRADE_PROPOSALS
+        .load_or_else(env, || Map::new(env))
+        .keys();
+    for id in ids.iter() {
+        if let Some(params) = proposal_params_slot(id).load(env) {
+            if !track_ids.contains(params.track_id) {
+                track_ids.push_back(params.track_id);
+            }
+        }
+    }
+    let mut indexed = 0u32;
+    for track_id in track_ids.iter() {
+        if track_slot(track_id).has(env) {
+            index_track(env, track_id);
+            indexed += 1;
+        }
+    }
+    indexed
+}
+
+/// The commitment of a private proposal, if it was created private
+pub fn get_private_description(env: &Env, proposal_id: u64) -> Option<PrivateDescription> {
+    private_description_slot(proposal_id).load(env)
+}
+
+pub fn store_private_description(env: &Env, proposal_id: u64, private: &PrivateDescription) {
+    private_description_slot(proposal_id).store(env, private);
+}
+
+/// Whether private proposals must be revealed before they can execute
+pub fn is_reveal_required(env: &Env) -> bool {
+    REQUIRE_REVEAL.load(env).unwrap_or(false)
+}
+
+pub fn set_reveal_required(env: &Env, required: bool) {
+    REQUIRE_REVEAL.store(env, &required);
+}
+
+/// The spilled full description of a proposal, if it had one
+pub fn get_description_blob(env: &Env, proposal_id: u64) -> Option<String> {
+    description_blob_slot(proposal_id).load(env)
+}
+
+/// Written only when a proposal's description is set, never on the vote path
+pub fn store_description_blob(env: &Env, proposal_id: u64, description: &String) {
+    description_blob_slot(proposal_id).store(env, description);
+}
+
+pub fn remove_description_blob(env: &Env, proposal_id: u64) {
+    description_blob_slot(proposal_id).remove(env);
+}
+
+/// Whether governance has deprecated `version` as an upgrade target
+pub fn is_version_deprecated(env: &Env, version: &Version) -> bool {
+    DEPRECATED_VERSIONS
+        .load_or_else(env, || Vec::new(env))
+        .contains(version)
+}
+
+pub fn deprecate_version(env: &Env, version: &Version) {
+    let mut deprecated = DEPRECATED_VERSIONS.load_or_else(env, || Vec::new(env));
+    if !deprecated.contains(version) {
+        deprecated.push_back(version.clone());
+        DEPRECATED_VERSIONS.store(env, &deprecated);
+    }
+}
+
+/// Every recorded compensation, keyed by id
+pub fn get_compensations(env: &Env) -> Map<u64, PendingCompensation> {
+    COMPENSATIONS.load_or_else(env, || Map::new(env))
+}
+
+pub fn set_compensations(env: &Env, compensations: &Map<u64, PendingCompensation>) {
+    COMPENSATIONS.store(env, compensations);
+}
+
+pub fn take_next_compensation_id(env: &Env) -> u64 {
+    let id = NEXT_COMPENSATION_ID.load(env).unwrap_or(1);
+    NEXT_COMPENSATION_ID.store(env, &(id + 1));
+    id
+}
+
+/// Minimum seconds between a governance change and a guardian change
+pub fn get_dual_control_window(env: &Env) -> u64 {
+    DUAL_CONTROL_WINDOW.load_or_else(env, || DEFAULT_DUAL_CONTROL_WINDOW)
+}
+
+pub fn set_dual_control_window(env: &Env, window: u64) {
+    DUAL_CONTROL_WINDOW.store(env, &window);
+}
+
+/// When the given kind of control last changed, if it ever has
+pub fn get_last_control_change(env: &Env, change: ControlChange) -> Option<u64> {
+    control_change_slot(change).load(env)
+}
+
+pub fn set_last_control_change(env: &Env, change: ControlChange, timestamp: u64) {
+    control_change_slot(change).store(env, &timestamp);
+}
+
+/// The bond new proposals must post, if one is configured
+pub fn get_bond_config(env: &Env) -> Option<BondConfig> {
+    BOND_CONFIG.load(env)
+}
+
+pub fn set_bond_config(env: &Env, config: &BondConfig) {
+    BOND_CONFIG.store(env, config);
+}
+
+/// The bond a proposal posted, if any
+pub fn get_proposal_bond(env: &Env, proposal_id: u64) -> Option<ProposalBond> {
+    bond_slot(proposal_id).load(env)
+}
+
+pub fn store_proposal_bond(env: &Env, proposal_id: u64, bond: &ProposalBond) {
+    bond_slot(proposal_id).store(env, bond);
+}
+
+/// Every slashed bond, oldest first
+pub fn get_slash_history(env: &Env) -> Vec<SlashRecord> {
+    SLASH_HISTORY.load_or_else(env, || Vec::new(env))
+}
+
+pub fn push_slash_record(env: &Env, record: &SlashRecord) {
+    let mut history = get_slash_history(env);
+    history.push_back(record.clone());
+    SLASH_HISTORY.store(env, &history);
+}
+
+/// When a proposal was approved, if it has been
+pub fn get_approved_at(env: &Env, proposal_id: u64) -> Option<u64> {
+    approved_at_slot(proposal_id).load(env)
+}
+
+/// Record when a proposal was approved
+pub fn set_approved_at(env: &Env, proposal_id: u64, timestamp: u64) {
+    approved_at_slot(proposal_id).store(env, &timestamp);
+}
+
+/// When an approved proposal clears the execution delay. Proposals approved
+/// before the delay existed have none.
+pub fn get_eligible_at(env: &Env, proposal_id: u64) -> Option<u64> {
+    eligible_at_slot(proposal_id).load(env)
+}
+
+pub fn set_eligible_at(env: &Env, proposal_id: u64, timestamp: u64) {
+    eligible_at_slot(proposal_id).store(env, &timestamp);
+}
+
+/// The action a proposal carries; none is stored for an upgrade proposal
+pub fn get_proposal_kind(env: &Env, proposal_id: u64) -> Option<ProposalKind> {
+    proposal_kind_slot(proposal_id).load(env)
+}
+
+pub fn set_proposal_kind(env: &Env, proposal_id: u64, kind: &ProposalKind) {
+    proposal_kind_slot(proposal_id).store(env, kind);
+}
+
+/// Delay between approval and execution; zero unless configured
+pub fn get_execution_delay(env: &Env) -> u64 {
+    EXECUTION_DELAY.load(env).unwrap_or(0)
+}
+
+pub fn set_execution_delay(env: &Env, delay: u64) {
+    EXECUTION_DELAY.store(env, &delay);
+}
+
+/// How long an approved proposal may wait for execution, if limited
+pub fn get_execution_window(env: &Env) -> Option<u64> {
+    EXECUTION_WINDOW.load(env)
+}
+
+pub fn set_execution_window(env: &Env, window: Option<u64>) {
+    match window {
+        Some(window) => EXECUTION_WINDOW.store(env, &window),
+        None => EXECUTION_WINDOW.remove(env),
+    }
+}
+
+/// Hand out the next recovery proposal id
+pub fn take_next_recovery_id(env: &Env) -> u64 {
+    let recovery_id = NEXT_RECOVERY_ID.load(env).unwrap_or(1);
+    NEXT_RECOVERY_ID.store(env, &(recovery_id + 1));
+    recovery_id
+}
+
+/// Get a recovery proposal by ID
+pub fn get_recovery(env: &Env, recovery_id: u64) -> Option<RecoveryProposal> {
+    recovery_slot(recovery_id).load(env)
+}
+
+/// Store a recovery proposal
+pub fn store_recovery(env: &Env, recovery: &RecoveryProposal) {
+    recovery_slot(recovery.id).store(env, recovery);
+}
+
+/// Get the addresses that have supported a recovery proposal
+pub fn get_recovery_voters(env: &Env, recovery_id: u64) -> Vec<Address> {
+    recovery_voters_slot(recovery_id).load_or_else(env, || Vec::new(env))
+}
+
+/// Replace the supporters of a recovery proposal
+pub fn set_recovery_voters(env: &Env, recovery_id: u64, voters: &Vec<Address>) {
+    recovery_voters_slot(recovery_id).store(env, voters);
+}
+
+/// Get the proxy schedule id an executed proposal is waiting on
+pub fn get_proxy_link(env: &Env, proposal_id: u64) -> Option<u64> {
+    proxy_link_slot(proposal_id).load(env)
+}
+
+/// Link an executed proposal to the proxy schedule that will apply it
+pub fn set_proxy_link(env: &Env, proposal_id: u64, schedule_id: u64) {
+    proxy_link_slot(proposal_id).store(env, &schedule_id);
+}
+
+/// Get the roles allowed to perform an action (governance only by default)
+pub fn get_action_roles(env: &Env, action: Action) -> Vec<Role> {
+    action_roles_slot(action).load_or_else(env, || match action {
+        Action::InstantRevert | Action::ConfirmUpgrade | Action::PauseGovernance => {
+            Vec::from_array(env, [Role::Governance, Role::Guardian])
+        }
+        Action::VetoProposal => Vec::from_array(env, [Role::Guardian]),
+        _ => Vec::from_array(env, [Role::Governance]),
+    })
+}
+
+/// Set the roles allowed to perform an action
+pub fn set_action_roles(env: &Env, action: Action, roles: &Vec<Role>) {
+    action_roles_slot(action).store(env, roles);
+}
+
+/// Get the addresses granted a role
+pub fn get_role_members(env: &Env, role: Role) -> Vec<Address> {
+    role_members_slot(role).load_or_else(env, || Vec::new(env))
+}
+
+/// Replace the addresses granted a role
+pub fn set_role_members(env: &Env, role: Role, members: &Vec<Address>) {
+    role_members_slot(role).store(env, members);
+}
+
+/// Entry counts of governance state. Storage that predates the counters is
+/// recounted on first read.
+pub fn get_storage_footprint(env: &Env) -> StorageFootprint {
+    STORAGE_FOOTPRINT
+        .load(env)
+        .unwrap_or_else(|| recount_footprint(env))
+}
+
+/// Move one footprint counter by `delta`, publishing the footprint every
+/// `FOOTPRINT_EVENT_INTERVAL` updates. Call before the write it accounts
+/// for, so a first-time recount does not see it.
+pub fn adjust_footprint(env: &Env, category: FootprintCategory, delta: i32) {
+    let mut footprint = get_storage_footprint(env);
+    let counter = match category {
+        FootprintCategory::ProposalsLive => &mut footprint.proposals_live,
+        FootprintCategory::ProposalsArchived => &mut footprint.proposals_archived,
+        FootprintCategory::Receipts => &mut footprint.receipts,
+        FootprintCategory::Plans => &mut footprint.plans,
+        FootprintCategory::HistoryEntries => &mut footprint.history_entries,
+        FootprintCategory::LogEntries => &mut footprint.log_entries,
+    };
+    *counter = counter.saturating_add_signed(delta);
+    footprint.writes += 1;
+    STORAGE_FOOTPRINT.store(env, &footprint);
+
+    if footprint.writes.is_multiple_of(FOOTPRINT_EVENT_INTERVAL) {
+        emit_event(env, MOD_UPGRADE, ACT_FOOTPRINT, footprint);
+    }
+}
+
+/// Count every footprint category from the entries themselves. `writes` is 0.
+pub fn recount_footprint(env: &Env) -> StorageFootprint {
+    let proposals_live = RAW_UPGRADE_PROPOSALS
+        .load_or_else(env, || Map::new(env))
+        .len();
+    let issued: u32 = UpgradeStatus::ALL
+        .iter()
+        .map(|status| get_status_index(env, *status).len())
+        .sum();
+    let history_entries = [
+        HistoryKind::Proposals,
+        HistoryKind::ExecutionReceipts,
+        HistoryKind::ProxyTransactions,
+        HistoryKind::EmergencyLog,
+    ]
+    .iter()
+    .map(|kind| (get_history_digest(env, *kind).count - get_pruned_through(env, *kind)) as u32)
+    .sum();
+
+    StorageFootprint {
+        proposals_live,
+        proposals_archived: issued.saturating_sub(proposals_live),
+        receipts: get_history_digest(env, HistoryKind::ExecutionReceipts).count as u32,
+        plans: MIGRATION_PLANS.load_or_else(env, || Map::new(env)).len(),
+        history_entries,
+        log_entries: get_history_digest(env, HistoryKind::EmergencyLog).count as u32,
+        writes: 0,
+    }
 }
@@ -1,10 +1,22 @@
 #![cfg(test)]
 
 use super::types::*;
-use super::{logic, storage};
+use super::{auth, invariants, logic, storage};
+use crate::keys::tags::{QUORUM_PARAM, SUPERMAJORITY_TRACK_NAME, VOTE_REASON};
+use crate::keys::upgrade::{
+    GOVERNANCE_CONFIG_KEY, PARAMS_KEY, TRACK_KEY, UPGRADE_PROPOSALS_KEY, VOTE_RECEIPTS_KEY,
+};
+use crate::multisig::storage as multisig_storage;
+use crate::multisig::types::{MultiSigOperation, OperationStatus, OperationType};
+use crate::proxy::types::{ProxyError, UpgradeOutcome};
+use crate::utils::merkle;
 use crate::StellarGuildsContract;
-use soroban_sdk::testutils::Address as _;
-use soroban_sdk::{Address, Env, String};
+use soroban_sdk::testutils::Events;
+use soroban_sdk::testutils::{Address as _, Ledger};
+use soroban_sdk::xdr::ToXdr;
+use soroban_sdk::{
+    symbol_short, Address, Bytes, BytesN, Env, IntoVal, Map, String, Symbol, TryIntoVal, Vec,
+};
 
 fn create_test_version(major: u32, minor: u32, patch: u32) -> Version {
     Version::new(major, minor, patch)
@@ -19,12 +31,224 @@ fn setup_upgrade_storage() -> (Env, Address, Address, Address) {
     let governance = Address::generate(&env);
     let proposer = Address::generate(&env);
     env.as_contract(&contract_id, || {
-        storage::initialize(&env, create_test_version(1, 0, 0), governance.clone());
+        storage::initialize(&env, create_test_version(1, 0, 0), governance.clone()).unwrap();
     });
 
     (env, contract_id, governance, proposer)
 }
 
+/// Give `voter` `power` now and in the proposal's voting power snapshot, as
+/// if it had been granted before the proposal was created
+fn set_snapshot_power(env: &Env, proposal_id: u64, voter: &Address, power: u32) {
+    storage::set_voting_power(env, voter, power);
+    let slot = storage::power_snapshot_slot(proposal_id);
+    let mut snapshot = slot.load_or_else(env, || Map::new(env));
+    snapshot.set(voter.clone(), power);
+    slot.store(env, &snapshot);
+}
+
+/// Propose an upgrade and force it straight into `Approved`
+fn create_approved_proposal(
+    env: &Env,
+    contract_id: &Address,
+    proposer: &Address,
+    version: Version,
+) -> u64 {
+    let target_contract = Address::generate(env);
+    let proposal_id = env.as_contract(contract_id, || {
+        logic::propose_upgrade(
+            env,
+            proposer,
+            &target_contract,
+            &version,
+            String::from_str(env, "approved"),
+        )
+        .unwrap()
+    });
+    env.as_contract(contract_id, || {
+        logic::transition(
+            env,
+            proposal_id,
+            UpgradeStatus::Approved,
+            symbol_short!("test"),
+        )
+        .unwrap();
+    });
+    proposal_id
+}
+
+/// Subscriber that records the last upgrade it was told about
+mod recording_subscriber {
+    use crate::upgrade::types::{CallOutcome, Version};
+    use soroban_sdk::{contract, contractimpl, symbol_short, Env};
+
+    #[contract]
+    pub struct RecordingSubscriber;
+
+    #[contractimpl]
+    impl RecordingSubscriber {
+        pub fn on_upgrade(
+            env: Env,
+            from_version: Version,
+            to_version: Version,
+            proposal_id: u64,
+        ) -> CallOutcome {
+            env.storage().instance().set(
+                &symbol_short!("seen"),
+                &(from_version, to_version, proposal_id),
+            );
+            CallOutcome::ok()
+        }
+
+        pub fn last_seen(env: Env) -> Option<(Version, Version, u64)> {
+            env.storage().instance().get(&symbol_short!("seen"))
+        }
+    }
+}
+
+/// Subscriber whose hook always traps
+mod panicking_subscriber {
+    use crate::upgrade::types::{CallOutcome, Version};
+    use soroban_sdk::{contract, contractimpl, Env};
+
+    #[contract]
+    pub struct PanickingSubscriber;
+
+    #[contractimpl]
+    impl PanickingSubscriber {
+        pub fn on_upgrade(
+            _env: Env,
+            _from: Version,
+            _to: Version,
+            _proposal_id: u64,
+        ) -> CallOutcome {
+            panic!("subscriber unavailable");
+        }
+    }
+}
+
+/// Subscriber whose availability is toggled by the test
+mod flaky_subscriber {
+    use crate::upgrade::types::{CallOutcome, Version};
+    use soroban_sdk::{contract, contractimpl, symbol_short, Env};
+
+    #[contract]
+    pub struct FlakySubscriber;
+
+    #[contractimpl]
+    impl FlakySubscriber {
+        pub fn set_healthy(env: Env, healthy: bool) {
+            env.storage()
+                .instance()
+                .set(&symbol_short!("healthy"), &healthy);
+        }
+
+        pub fn on_upgrade(
+            env: Env,
+            _from: Version,
+            _to: Version,
+            _proposal_id: u64,
+        ) -> CallOutcome {
+            let healthy: bool = env
+                .storage()
+                .instance()
+                .get(&symbol_short!("healthy"))
+                .unwrap_or(false);
+            if !healthy {
+                panic!("subscriber unavailable");
+            }
+            CallOutcome::ok()
+        }
+    }
+}
+
+/// Migration implementation with one faithful and one corrupting selector
+mod tagging_migration {
+    use crate::upgrade::types::{MigrationVerdict, Version};
+    use soroban_sdk::{contract, contractimpl, Bytes, Env, Symbol};
+
+    #[contract]
+    pub struct TaggingMigration;
+
+    #[contractimpl]
+    impl TaggingMigration {
+        /// Append the target minor version to the value
+        pub fn migrate(
+            _env: Env,
+            _from: Version,
+            to: Version,
+            _key: Symbol,
+            value: Bytes,
+        ) -> MigrationVerdict {
+            let mut migrated = value;
+            migrated.push_back(to.minor as u8);
+            MigrationVerdict::Ok(migrated)
+        }
+
+        /// Throw the value away
+        pub fn corrupt(
+            env: Env,
+            _from: Version,
+            _to: Version,
+            _key: Symbol,
+            _value: Bytes,
+        ) -> MigrationVerdict {
+            MigrationVerdict::Ok(Bytes::new(&env))
+        }
+    }
+}
+
+/// Archive sink that keeps and acknowledges every record
+mod recording_sink {
+    use crate::upgrade::types::ArchiveRecord;
+    use soroban_sdk::{contract, contractimpl, symbol_short, Env, Vec};
+
+    #[contract]
+    pub struct RecordingSink;
+
+    #[contractimpl]
+    impl RecordingSink {
+        pub fn archive_record(env: Env, record: ArchiveRecord) -> bool {
+            let mut records = Self::records(env.clone());
+            records.push_back(record);
+            env.storage()
+                .instance()
+                .set(&symbol_short!("records"), &records);
+            true
+        }
+
+        pub fn records(env: Env) -> Vec<ArchiveRecord> {
+            env.storage()
+                .instance()
+                .get(&symbol_short!("records"))
+                .unwrap_or_else(|| Vec::new(&env))
+        }
+    }
+}
+
+/// Archive sink that traps on every record
+mod failing_sink {
+    use crate::upgrade::types::ArchiveRecord;
+    use soroban_sdk::{contract, contractimpl, Env};
+
+    #[contract]
+    pub struct FailingSink;
+
+    #[contractimpl]
+    impl FailingSink {
+        pub fn archive_record(_env: Env, _record: ArchiveRecord) -> bool {
+            panic!("archive sink unavailable");
+        }
+    }
+}
+
+use failing_sink::FailingSink;
+use flaky_subscriber::{FlakySubscriber, FlakySubscriberClient};
+use panicking_subscriber::PanickingSubscriber;
+use recording_sink::{RecordingSink, RecordingSinkClient};
+use recording_subscriber::{RecordingSubscriber, RecordingSubscriberClient};
+use tagging_migration::TaggingMigration;
+
 #[test]
 fn test_version_compatibility() {
     let _env = Env::default();
@@ -55,6 +279,50 @@ fn test_version_struct() {
     assert_eq!(version.patch, 3);
 }
 
+#[test]
+fn test_version_semver_ordering() {
+    use core::cmp::Ordering;
+
+    let v = create_test_version;
+    for (a, b, expected) in [
+        (v(1, 0, 0), v(1, 0, 0), Ordering::Equal),
+        (v(1, 0, 1), v(1, 0, 0), Ordering::Greater),
+        (v(1, 0, 0), v(1, 0, 1), Ordering::Less),
+        (v(1, 1, 0), v(1, 0, 9), Ordering::Greater),
+        (v(1, 0, 9), v(1, 1, 0), Ordering::Less),
+        (v(2, 0, 0), v(1, 9, 9), Ordering::Greater),
+        (v(1, 9, 9), v(2, 0, 0), Ordering::Less),
+        (v(0, 0, 1), v(0, 0, 0), Ordering::Greater),
+        (v(1, 10, 0), v(1, 9, 0), Ordering::Greater),
+        (v(1, 2, 10), v(1, 2, 9), Ordering::Greater),
+        (v(10, 0, 0), v(9, 99, 99), Ordering::Greater),
+        (
+            v(u32::MAX, 0, 0),
+            v(u32::MAX - 1, u32::MAX, u32::MAX),
+            Ordering::Greater,
+        ),
+        (v(3, 4, 5), v(3, 4, 5), Ordering::Equal),
+    ] {
+        assert_eq!(a.cmp_semver(&b), expected, "{:?} vs {:?}", a, b);
+        assert_eq!(a.cmp(&b), expected, "{:?} vs {:?}", a, b);
+        assert_eq!(a.is_newer_than(&b), expected == Ordering::Greater);
+        assert_eq!(b.is_newer_than(&a), expected == Ordering::Less);
+    }
+
+    let env = Env::default();
+    for (version, rendered) in [
+        (v(1, 2, 3), "1.2.3"),
+        (v(0, 0, 0), "0.0.0"),
+        (v(10, 200, 3_000), "10.200.3000"),
+        (
+            v(u32::MAX, u32::MAX, u32::MAX),
+            "4294967295.4294967295.4294967295",
+        ),
+    ] {
+        assert_eq!(version.to_string(&env), String::from_str(&env, rendered));
+    }
+}
+
 #[test]
 fn test_upgrade_status_enum() {
     let pending = UpgradeStatus::Pending;
@@ -102,7 +370,12 @@ fn test_storage_round_trip_and_flags() {
         status: UpgradeStatus::Pending,
         votes_for: 0,
         votes_against: 0,
+        votes_abstain: 0,
         total_voters: 2,
+        total_voting_power_snapshot: 2,
+        voting_deadline: 0,
+        salt: BytesN::from_array(&env, &[0u8; 32]),
+        description_hash: BytesN::from_array(&env, &[0u8; 32]),
     };
 
     env.as_contract(&contract_id, || {
@@ -125,7 +398,7 @@ fn test_storage_round_trip_and_flags() {
             2
         );
 
-        storage::update_proposal_status(&env, 7, UpgradeStatus::Approved);
+        logic::transition(&env, 7, UpgradeStatus::Approved, symbol_short!("test")).unwrap();
         assert_eq!(
             storage::get_upgrade_proposal(&env, 7).unwrap().status,
             UpgradeStatus::Approved
@@ -165,13 +438,14 @@ fn test_propose_vote_approve_and_execute_upgrade() {
             &target_contract,
             &create_test_version(1, 1, 0),
             String::from_str(&env, "upgrade"),
-        );
+        )
+        .unwrap();
         proposal_id
     });
 
     env.as_contract(&contract_id, || {
         let mut proposal = storage::get_upgrade_proposal(&env, proposal_id).unwrap();
-        proposal.total_voters = 1;
+        proposal.total_voting_power_snapshot = 1;
         storage::store_upgrade_proposal(&env, &proposal);
     });
 
@@ -198,7 +472,7 @@ fn test_propose_vote_approve_and_execute_upgrade() {
     });
 
     env.as_contract(&contract_id, || {
-        assert!(logic::execute_upgrade(&env, &governance, proposal_id).is_ok());
+        assert!(logic::execute_proposal(&env, &governance, proposal_id).is_ok());
     });
     env.as_contract(&contract_id, || {
         assert_eq!(
@@ -211,6 +485,26 @@ fn test_propose_vote_approve_and_execute_upgrade() {
                 .status,
             UpgradeStatus::Executed
         );
+
+        // The executed proposal and its receipt are appended to the exported history
+        let proposals = crate::events::history::export_history_page(
+            &env,
+            crate::events::types::HistoryKind::Proposals,
+            0,
+            10,
+        );
+        assert_eq!(proposals.records.len(), 1);
+        let proposal = storage::get_upgrade_proposal(&env, proposal_id).unwrap();
+        assert_eq!(
+            proposals.records.get(0).unwrap().payload,
+            proposal.to_xdr(&env)
+        );
+
+        let receipts = crate::events::history::get_history_digest(
+            &env,
+            crate::events::types::HistoryKind::ExecutionReceipts,
+        );
+        assert_eq!(receipts.count, 1);
     });
 }
 
@@ -230,12 +524,13 @@ fn test_vote_can_reject_and_execute_requires_approval() {
             &target_contract,
             &create_test_version(1, 0, 1),
             String::from_str(&env, "reject me"),
-        );
+        )
+        .unwrap();
         proposal_id
     });
     env.as_contract(&contract_id, || {
         let mut proposal = storage::get_upgrade_proposal(&env, proposal_id).unwrap();
-        proposal.total_voters = 1;
+        proposal.total_voting_power_snapshot = 1;
         storage::store_upgrade_proposal(&env, &proposal);
     });
 
@@ -252,8 +547,8 @@ fn test_vote_can_reject_and_execute_requires_approval() {
     });
     env.as_contract(&contract_id, || {
         assert_eq!(
-            logic::execute_upgrade(&env, &governance, proposal_id),
-            Err("Proposal is not approved for execution")
+            logic::execute_proposal(&env, &governance, proposal_id),
+            Err(UpgradeError::ProposalNotApproved.into())
         );
     });
 }
@@ -275,14 +570,14 @@ fn test_upgrade_authorization_and_emergency_paths() {
     env.as_contract(&contract_id, || {
         assert_eq!(
             logic::register_migration_plan(&env, &outsider, 1, &migration),
-            Err("Only governance address can register migration plans")
+            Err(UpgradeError::NotAuthorized.into())
         );
     });
 
     env.as_contract(&contract_id, || {
         assert_eq!(
             logic::toggle_emergency_upgrades(&env, &outsider, true),
-            Err("Only governance address can toggle emergency upgrades")
+            Err(UpgradeError::NotAuthorized.into())
         );
     });
     env.as_contract(&contract_id, || {
@@ -292,9 +587,13 @@ fn test_upgrade_authorization_and_emergency_paths() {
     env.as_contract(&contract_id, || {
         assert_eq!(
             logic::emergency_upgrade(&env, &outsider, &target_contract, &v110),
-            Err("Only governance address can perform emergency upgrades")
+            Err(UpgradeError::NotAuthorized.into())
         );
     });
+    env.as_contract(&contract_id, || {
+        // Minor bumps need the default patch-only limit raised
+        logic::set_emergency_max_bump(&env, &governance, BumpKind::Minor).unwrap();
+    });
     env.as_contract(&contract_id, || {
         assert!(logic::emergency_upgrade(&env, &governance, &target_contract, &v110).is_ok());
     });
@@ -310,6 +609,7 @@ fn test_upgrade_authorization_and_emergency_paths() {
             &create_test_version(1, 2, 0),
             String::from_str(&env, "auth"),
         )
+        .unwrap()
     });
     env.as_contract(&contract_id, || {
         let mut proposal = storage::get_upgrade_proposal(&env, proposal_id).unwrap();
@@ -319,8 +619,8 @@ fn test_upgrade_authorization_and_emergency_paths() {
 
     env.as_contract(&contract_id, || {
         assert_eq!(
-            logic::execute_upgrade(&env, &outsider, proposal_id),
-            Err("Only governance address can execute upgrades")
+            logic::execute_proposal(&env, &outsider, proposal_id),
+            Err(UpgradeError::NotAuthorized.into())
         );
     });
 }
@@ -335,7 +635,7 @@ fn test_version_compatibility_and_rollback() {
     let later = create_test_version(1, 3, 0);
     let major_bump = create_test_version(2, 0, 0);
     env.as_contract(&contract_id, || {
-        storage::set_current_version(&env, &current);
+        storage::set_current_version(&env, &current, &governance, None);
     });
 
     env.as_contract(&contract_id, || {
@@ -347,17 +647,9210 @@ fn test_version_compatibility_and_rollback() {
     env.as_contract(&contract_id, || {
         assert_eq!(
             logic::rollback_to_version(&env, &outsider, &earlier),
-            Err("Only governance address can perform rollbacks")
+            Err(UpgradeError::NotAuthorized.into())
         );
     });
     env.as_contract(&contract_id, || {
         assert_eq!(
             logic::rollback_to_version(&env, &governance, &major_bump),
-            Err("Can only rollback to earlier versions in the same major series")
+            Err(UpgradeError::InvalidRollbackTarget.into())
+        );
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            logic::rollback_to_version(&env, &governance, &earlier),
+            Err(GuardError::UnknownVersionImplementation.into())
         );
+        storage::set_version_implementation(&env, &earlier, &Address::generate(&env));
     });
     env.as_contract(&contract_id, || {
         assert!(logic::rollback_to_version(&env, &governance, &earlier).is_ok());
         assert_eq!(storage::get_current_version(&env), earlier);
     });
 }
+
+#[test]
+fn test_rollback_entrypoint_is_governance_only() {
+    let (env, contract_id, governance, proposer) = setup_upgrade_storage();
+    let client = crate::StellarGuildsContractClient::new(&env, &contract_id);
+    let current = create_test_version(1, 2, 0);
+    let earlier = create_test_version(1, 1, 0);
+    let earlier_impl = Address::generate(&env);
+    env.as_contract(&contract_id, || {
+        storage::set_current_version(&env, &current, &governance, None);
+        storage::set_version_implementation(&env, &earlier, &earlier_impl);
+        crate::proxy::storage::initialize(&env, Address::generate(&env), governance.clone())
+            .unwrap();
+    });
+
+    assert_eq!(
+        client.try_rollback_to_version(&proposer, &earlier),
+        Err(Ok(UpgradeError::NotAuthorized.into()))
+    );
+
+    client.rollback_to_version(&governance, &earlier);
+    let (_, topics, data) = env.events().all().last().unwrap();
+    assert_eq!(
+        topics,
+        (symbol_short!("upgrade"), Symbol::new(&env, "rolled_back")).into_val(&env)
+    );
+    let (from, to): (Version, Version) = data.into_val(&env);
+    assert_eq!((from, to), (current, earlier.clone()));
+    env.as_contract(&contract_id, || {
+        assert_eq!(storage::get_current_version(&env), earlier);
+        assert_eq!(
+            crate::proxy::storage::get_implementation(&env),
+            earlier_impl
+        );
+    });
+}
+
+#[test]
+fn test_proposal_rate_limit_window() {
+    let (env, contract_id, governance, proposer) = setup_upgrade_storage();
+    let target_contract = Address::generate(&env);
+    env.ledger().with_mut(|l| l.timestamp = 1_000);
+
+    env.as_contract(&contract_id, || {
+        assert!(logic::set_rate_limit(&env, &governance, 2, 100).is_ok());
+    });
+
+    env.ledger().with_mut(|l| l.timestamp = 1_010);
+    env.as_contract(&contract_id, || {
+        logic::propose_upgrade(
+            &env,
+            &proposer,
+            &target_contract,
+            &create_test_version(1, 1, 0),
+            String::from_str(&env, "first"),
+        )
+        .unwrap();
+    });
+    env.ledger().with_mut(|l| l.timestamp = 1_050);
+    env.as_contract(&contract_id, || {
+        logic::propose_upgrade(
+            &env,
+            &proposer,
+            &target_contract,
+            &create_test_version(1, 2, 0),
+            String::from_str(&env, "second"),
+        )
+        .unwrap();
+    });
+
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            logic::propose_upgrade(
+                &env,
+                &proposer,
+                &target_contract,
+                &create_test_version(1, 3, 0),
+                String::from_str(&env, "third"),
+            ),
+            Err(UpgradeError::RateLimited.into())
+        );
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(logic::next_proposal_allowed_at(&env, &proposer), 1_110);
+        assert_eq!(storage::get_proposer_window(&env, &proposer).len(), 2);
+    });
+
+    // Governance is exempt from the limit
+    for _ in 0..3 {
+        env.as_contract(&contract_id, || {
+            logic::propose_upgrade(
+                &env,
+                &governance,
+                &target_contract,
+                &create_test_version(1, 4, 0),
+                String::from_str(&env, "gov"),
+            )
+            .unwrap();
+        });
+    }
+
+    // Once the oldest entry ages out a new proposal is accepted
+    env.ledger().with_mut(|l| l.timestamp = 1_110);
+    env.as_contract(&contract_id, || {
+        assert_eq!(logic::next_proposal_allowed_at(&env, &proposer), 1_110);
+    });
+    env.as_contract(&contract_id, || {
+        assert!(logic::propose_upgrade(
+            &env,
+            &proposer,
+            &target_contract,
+            &create_test_version(1, 3, 0),
+            String::from_str(&env, "third"),
+        )
+        .is_ok());
+        // The ring buffer stays bounded at the configured size
+        assert_eq!(storage::get_proposer_window(&env, &proposer).len(), 2);
+    });
+}
+
+#[test]
+fn test_rate_limit_configuration_guards() {
+    let (env, contract_id, governance, proposer) = setup_upgrade_storage();
+
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            logic::set_rate_limit(&env, &proposer, 1, 10),
+            Err(UpgradeError::NotAuthorized.into())
+        );
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            logic::set_rate_limit(&env, &governance, storage::MAX_RATE_LIMIT_SLOTS + 1, 10),
+            Err(UpgradeError::InvalidRateLimit.into())
+        );
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            logic::set_rate_limit(&env, &governance, 1, 0),
+            Err(UpgradeError::InvalidRateLimit.into())
+        );
+    });
+}
+
+#[test]
+fn test_proposal_detail_matches_individual_reads() {
+    let (env, contract_id, governance, proposer) = setup_upgrade_storage();
+    let target_contract = Address::generate(&env);
+    let voter = Address::generate(&env);
+
+    env.as_contract(&contract_id, || {
+        storage::set_voting_power(&env, &voter, 2);
+    });
+    let proposal_id = env.as_contract(&contract_id, || {
+        logic::propose_upgrade(
+            &env,
+            &proposer,
+            &target_contract,
+            &create_test_version(1, 1, 0),
+            String::from_str(&env, "detail"),
+        )
+        .unwrap()
+    });
+
+    // Before a plan is registered the migration summary is empty
+    env.as_contract(&contract_id, || {
+        let detail = logic::get_proposal_detail(&env, proposal_id, None).unwrap();
+        assert!(detail.migration_selector.is_none());
+        assert!(detail.migration_estimated_gas.is_none());
+        assert!(detail.viewer.is_none());
+        assert!(!detail.viewer_can_vote);
+    });
+
+    let migration = MigrationPlan {
+        from_version: create_test_version(1, 0, 0),
+        to_version: create_test_version(1, 1, 0),
+        migration_function_selector: soroban_sdk::symbol_short!("migr"),
+        estimated_gas: 9,
+    };
+    env.as_contract(&contract_id, || {
+        logic::register_migration_plan(&env, &governance, proposal_id, &migration).unwrap();
+    });
+
+    env.as_contract(&contract_id, || {
+        let detail = logic::get_proposal_detail(&env, proposal_id, Some(voter.clone())).unwrap();
+        let proposal = storage::get_upgrade_proposal(&env, proposal_id).unwrap();
+        let plan = storage::get_migration_plan(&env, proposal_id).unwrap();
+
+        assert_eq!(detail.proposal.id, proposal.id);
+        assert_eq!(detail.proposal.description, proposal.description);
+        assert_eq!(
+            detail.projection,
+            logic::project_outcome(&proposal, &storage::get_proposal_params(&env, proposal_id))
+        );
+        assert_eq!(
+            detail.migration_selector,
+            Some(plan.migration_function_selector)
+        );
+        assert_eq!(detail.migration_estimated_gas, Some(plan.estimated_gas));
+        assert_eq!(detail.viewer, Some(voter.clone()));
+        assert_eq!(
+            detail.viewer_voting_power,
+            storage::get_voting_power(&env, &voter)
+        );
+        assert!(detail.viewer_can_vote);
+        assert!(!detail.viewer_is_proposer);
+
+        let proposer_view =
+            logic::get_proposal_detail(&env, proposal_id, Some(proposer.clone())).unwrap();
+        assert!(proposer_view.viewer_is_proposer);
+        assert!(!proposer_view.viewer_can_vote);
+    });
+}
+
+#[test]
+fn test_proposal_detail_degrades_gracefully() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, StellarGuildsContract);
+    let viewer = Address::generate(&env);
+
+    // Nothing initialized and no such proposal: no trap, just no detail
+    env.as_contract(&contract_id, || {
+        assert!(logic::get_proposal_detail(&env, 42, Some(viewer.clone())).is_none());
+        assert!(logic::get_proposal_detail(&env, 42, None).is_none());
+    });
+}
+
+#[test]
+fn test_content_hash_proposal_ids() {
+    let (env, contract_id, governance, proposer) = setup_upgrade_storage();
+    let target_contract = Address::generate(&env);
+    let version = create_test_version(1, 1, 0);
+    let salt = BytesN::from_array(&env, &[7u8; 32]);
+
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            logic::set_proposal_id_mode(&env, &proposer, ProposalIdMode::ContentHash),
+            Err(UpgradeError::NotAuthorized.into())
+        );
+    });
+    env.as_contract(&contract_id, || {
+        logic::set_proposal_id_mode(&env, &governance, ProposalIdMode::ContentHash).unwrap();
+    });
+
+    // Recompute the id the way an off-chain integrator would
+    let mut preimage = Bytes::new(&env);
+    preimage.append(&proposer.clone().to_xdr(&env));
+    preimage.append(&target_contract.clone().to_xdr(&env));
+    for part in [1u32, 1, 0] {
+        preimage.extend_from_array(&part.to_be_bytes());
+    }
+    preimage.extend_from_array(&[7u8; 32]);
+    let digest = env.crypto().sha256(&preimage).to_array();
+    let mut id_bytes = [0u8; 8];
+    id_bytes.copy_from_slice(&digest[..8]);
+    let expected_id = u64::from_be_bytes(id_bytes);
+
+    let proposal_id = env.as_contract(&contract_id, || {
+        logic::propose_upgrade_with_salt(
+            &env,
+            &proposer,
+            &target_contract,
+            &version,
+            String::from_str(&env, "hashed"),
+            salt.clone(),
+        )
+        .unwrap()
+    });
+    assert_eq!(proposal_id, expected_id);
+
+    env.as_contract(&contract_id, || {
+        let proposal = storage::get_upgrade_proposal(&env, proposal_id).unwrap();
+        assert_eq!(proposal.id, expected_id);
+        assert_eq!(proposal.salt, salt);
+        assert!(logic::get_proposal_detail(&env, proposal_id, None).is_some());
+    });
+
+    // Same content and salt produce the same id, which is rejected
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            logic::propose_upgrade_with_salt(
+                &env,
+                &proposer,
+                &target_contract,
+                &version,
+                String::from_str(&env, "again"),
+                salt.clone(),
+            ),
+            Err(UpgradeError::ProposalIdCollision.into())
+        );
+    });
+    // Unsalted proposals hash with an all-zero salt
+    let unsalted_id = env.as_contract(&contract_id, || {
+        logic::propose_upgrade(
+            &env,
+            &proposer,
+            &target_contract,
+            &version,
+            String::from_str(&env, "unsalted"),
+        )
+        .unwrap()
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            unsalted_id,
+            logic::compute_content_hash_id(
+                &env,
+                &proposer,
+                &target_contract,
+                &version,
+                &BytesN::from_array(&env, &[0u8; 32]),
+            )
+        );
+    });
+
+    // A different salt yields a fresh id
+    let other_id = env.as_contract(&contract_id, || {
+        logic::propose_upgrade_with_salt(
+            &env,
+            &proposer,
+            &target_contract,
+            &version,
+            String::from_str(&env, "resalted"),
+            BytesN::from_array(&env, &[8u8; 32]),
+        )
+        .unwrap()
+    });
+    assert_ne!(other_id, proposal_id);
+
+    // Actions hash their kind, so one proposer can put up several at once
+    let client = crate::StellarGuildsContractClient::new(&env, &contract_id);
+    let description = String::from_str(&env, "action");
+    let toggle = ProposalKind::ToggleEmergency(true);
+    let quorum = ProposalKind::SetParameter(QUORUM_PARAM, 5_000);
+    let toggle_id = client.propose_governance_action(&proposer, &toggle, &description);
+    let quorum_id = client.propose_governance_action(&proposer, &quorum, &description);
+    assert_ne!(toggle_id, quorum_id);
+    assert_eq!(
+        toggle_id,
+        client.compute_action_proposal_id(&proposer, &toggle)
+    );
+    assert_eq!(
+        quorum_id,
+        client.compute_action_proposal_id(&proposer, &quorum)
+    );
+}
+
+#[test]
+fn test_sequential_ids_remain_default() {
+    let (env, contract_id, _, proposer) = setup_upgrade_storage();
+    let target_contract = Address::generate(&env);
+
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            storage::get_proposal_id_mode(&env),
+            ProposalIdMode::Sequential
+        );
+    });
+    for expected in 1..=2u64 {
+        let id = env.as_contract(&contract_id, || {
+            logic::propose_upgrade_with_salt(
+                &env,
+                &proposer,
+                &target_contract,
+                &create_test_version(1, 1, 0),
+                String::from_str(&env, "seq"),
+                BytesN::from_array(&env, &[1u8; 32]),
+            )
+            .unwrap()
+        });
+        assert_eq!(id, expected);
+    }
+}
+
+#[test]
+fn test_feature_flag_activates_after_upgrade_crosses_min_version() {
+    let (env, contract_id, governance, proposer) = setup_upgrade_storage();
+    let target_contract = Address::generate(&env);
+    let gated = soroban_sdk::symbol_short!("new_tally");
+    let ungated = soroban_sdk::symbol_short!("always");
+
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            logic::set_feature(&env, &proposer, gated.clone(), true, None),
+            Err(UpgradeError::NotAuthorized.into())
+        );
+    });
+    env.as_contract(&contract_id, || {
+        logic::set_feature(
+            &env,
+            &governance,
+            gated.clone(),
+            true,
+            Some(create_test_version(1, 1, 0)),
+        )
+        .unwrap();
+    });
+    env.as_contract(&contract_id, || {
+        logic::set_feature(&env, &governance, ungated.clone(), true, None).unwrap();
+    });
+
+    env.as_contract(&contract_id, || {
+        assert!(!logic::is_feature_enabled(&env, &gated));
+        assert!(logic::is_feature_enabled(&env, &ungated));
+        assert!(!logic::is_feature_enabled(
+            &env,
+            &soroban_sdk::symbol_short!("unknown")
+        ));
+    });
+
+    // Execute an upgrade to 1.1.0; the gated flag switches on without another call
+    env.as_contract(&contract_id, || {
+        storage::set_voting_power(&env, &proposer, 1);
+    });
+    let proposal_id = env.as_contract(&contract_id, || {
+        logic::propose_upgrade(
+            &env,
+            &proposer,
+            &target_contract,
+            &create_test_version(1, 1, 0),
+            String::from_str(&env, "flagged"),
+        )
+        .unwrap()
+    });
+    env.as_contract(&contract_id, || {
+        logic::vote_on_proposal(&env, &proposer, proposal_id, true).unwrap();
+    });
+    env.as_contract(&contract_id, || {
+        logic::execute_proposal(&env, &governance, proposal_id).unwrap();
+    });
+    env.as_contract(&contract_id, || {
+        assert!(logic::is_feature_enabled(&env, &gated));
+    });
+
+    // Disabling wins regardless of version
+    env.as_contract(&contract_id, || {
+        logic::set_feature(&env, &governance, gated.clone(), false, None).unwrap();
+    });
+    env.as_contract(&contract_id, || {
+        assert!(!logic::is_feature_enabled(&env, &gated));
+
+        let all = logic::get_features(&env, 0, 10);
+        assert_eq!(all.len(), 2);
+        assert_eq!(all.get(0).unwrap().name, gated);
+        assert!(!all.get(0).unwrap().enabled);
+        let page = logic::get_features(&env, 1, 10);
+        assert_eq!(page.len(), 1);
+        assert_eq!(page.get(0).unwrap().name, ungated);
+        assert!(logic::get_features(&env, 2, 10).is_empty());
+    });
+}
+
+#[test]
+fn test_executed_upgrade_notifies_subscribers() {
+    let (env, contract_id, governance, proposer) = setup_upgrade_storage();
+    let good = env.register_contract(None, RecordingSubscriber);
+    let bad = env.register_contract(None, PanickingSubscriber);
+
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            logic::add_subscriber(&env, &proposer, &good),
+            Err(UpgradeError::NotAuthorized.into())
+        );
+    });
+    env.as_contract(&contract_id, || {
+        logic::add_subscriber(&env, &governance, &good).unwrap();
+    });
+    env.as_contract(&contract_id, || {
+        logic::add_subscriber(&env, &governance, &bad).unwrap();
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            logic::add_subscriber(&env, &governance, &good),
+            Err(UpgradeError::SubscriberExists.into())
+        );
+    });
+
+    let proposal_id =
+        create_approved_proposal(&env, &contract_id, &proposer, create_test_version(1, 1, 0));
+    env.as_contract(&contract_id, || {
+        assert!(logic::execute_proposal(&env, &governance, proposal_id).is_ok());
+    });
+
+    env.as_contract(&contract_id, || {
+        // The failing subscriber did not revert the upgrade
+        assert_eq!(
+            storage::get_current_version(&env),
+            create_test_version(1, 1, 0)
+        );
+        let results = storage::get_notification_results(&env, proposal_id);
+        assert_eq!(results.len(), 2);
+        assert_eq!(
+            results.get(0).unwrap(),
+            NotificationResult {
+                subscriber: good.clone(),
+                success: true
+            }
+        );
+        assert_eq!(
+            results.get(1).unwrap(),
+            NotificationResult {
+                subscriber: bad.clone(),
+                success: false
+            }
+        );
+    });
+
+    let seen = RecordingSubscriberClient::new(&env, &good).last_seen();
+    assert_eq!(
+        seen,
+        Some((
+            create_test_version(1, 0, 0),
+            create_test_version(1, 1, 0),
+            proposal_id
+        ))
+    );
+}
+
+#[test]
+fn test_subscriber_management_limits() {
+    let (env, contract_id, governance, _) = setup_upgrade_storage();
+    let subscriber = Address::generate(&env);
+
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            logic::remove_subscriber(&env, &governance, &subscriber),
+            Err(UpgradeError::SubscriberNotFound.into())
+        );
+    });
+    for _ in 0..storage::MAX_UPGRADE_SUBSCRIBERS {
+        env.as_contract(&contract_id, || {
+            logic::add_subscriber(&env, &governance, &Address::generate(&env)).unwrap();
+        });
+    }
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            logic::add_subscriber(&env, &governance, &subscriber),
+            Err(UpgradeError::TooManySubscribers.into())
+        );
+    });
+
+    let first = env.as_contract(&contract_id, || {
+        storage::get_subscribers(&env).get(0).unwrap()
+    });
+    env.as_contract(&contract_id, || {
+        logic::remove_subscriber(&env, &governance, &first).unwrap();
+    });
+    env.as_contract(&contract_id, || {
+        assert!(!storage::get_subscribers(&env).contains(&first));
+        assert_eq!(
+            storage::get_subscribers(&env).len(),
+            storage::MAX_UPGRADE_SUBSCRIBERS - 1
+        );
+    });
+}
+
+#[test]
+fn test_notification_retry_recovers_flaky_subscriber() {
+    let (env, contract_id, governance, proposer) = setup_upgrade_storage();
+    let flaky = env.register_contract(None, FlakySubscriber);
+    let flaky_client = FlakySubscriberClient::new(&env, &flaky);
+
+    env.as_contract(&contract_id, || {
+        logic::add_subscriber(&env, &governance, &flaky).unwrap();
+    });
+    let proposal_id =
+        create_approved_proposal(&env, &contract_id, &proposer, create_test_version(1, 1, 0));
+    env.as_contract(&contract_id, || {
+        logic::execute_proposal(&env, &governance, proposal_id).unwrap();
+    });
+
+    // First failure is queued
+    env.as_contract(&contract_id, || {
+        let queue = storage::get_retry_queue(&env);
+        assert_eq!(queue.len(), 1);
+        assert_eq!(queue.get(0).unwrap().attempts, 1);
+        assert!(
+            !storage::get_notification_results(&env, proposal_id)
+                .get(0)
+                .unwrap()
+                .success
+        );
+    });
+
+    // Second failure stays queued with the attempt counted
+    env.as_contract(&contract_id, || {
+        assert_eq!(logic::retry_notifications(&env, 5), 0);
+        assert_eq!(storage::get_retry_queue(&env).get(0).unwrap().attempts, 2);
+    });
+
+    // Subscriber comes back and the retry delivers
+    flaky_client.set_healthy(&true);
+    env.as_contract(&contract_id, || {
+        assert_eq!(logic::retry_notifications(&env, 5), 1);
+        assert!(storage::get_retry_queue(&env).is_empty());
+        assert!(
+            storage::get_notification_results(&env, proposal_id)
+                .get(0)
+                .unwrap()
+                .success
+        );
+    });
+}
+
+#[test]
+fn test_notification_retry_drops_after_max_attempts() {
+    let (env, contract_id, governance, proposer) = setup_upgrade_storage();
+    let broken = env.register_contract(None, PanickingSubscriber);
+    let other = env.register_contract(None, PanickingSubscriber);
+
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            logic::set_notification_max_attempts(&env, &governance, 0),
+            Err(UpgradeError::InvalidMaxAttempts.into())
+        );
+    });
+    env.as_contract(&contract_id, || {
+        logic::set_notification_max_attempts(&env, &governance, 2).unwrap();
+    });
+    env.as_contract(&contract_id, || {
+        logic::add_subscriber(&env, &governance, &broken).unwrap();
+    });
+    env.as_contract(&contract_id, || {
+        logic::add_subscriber(&env, &governance, &other).unwrap();
+    });
+    let proposal_id =
+        create_approved_proposal(&env, &contract_id, &proposer, create_test_version(1, 1, 0));
+    env.as_contract(&contract_id, || {
+        logic::execute_proposal(&env, &governance, proposal_id).unwrap();
+    });
+
+    env.as_contract(&contract_id, || {
+        assert_eq!(storage::get_retry_queue(&env).len(), 2);
+        // Only the oldest entry is processed; its second attempt exhausts it
+        assert_eq!(logic::retry_notifications(&env, 1), 0);
+        let queue = storage::get_retry_queue(&env);
+        assert_eq!(queue.len(), 1);
+        assert_eq!(queue.get(0).unwrap().subscriber, other);
+        assert_eq!(queue.get(0).unwrap().attempts, 1);
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(logic::retry_notifications(&env, 10), 0);
+        assert!(storage::get_retry_queue(&env).is_empty());
+    });
+}
+
+#[test]
+fn test_compensation_retries_with_backoff_until_delivered() {
+    let (env, contract_id, governance, proposer) = setup_upgrade_storage();
+    let flaky = env.register_contract(None, FlakySubscriber);
+    let flaky_client = FlakySubscriberClient::new(&env, &flaky);
+
+    env.as_contract(&contract_id, || {
+        logic::set_notification_max_attempts(&env, &governance, 1).unwrap();
+    });
+    env.as_contract(&contract_id, || {
+        logic::add_subscriber(&env, &governance, &flaky).unwrap();
+    });
+    let proposal_id =
+        create_approved_proposal(&env, &contract_id, &proposer, create_test_version(1, 1, 0));
+    env.as_contract(&contract_id, || {
+        logic::execute_proposal(&env, &governance, proposal_id).unwrap();
+    });
+
+    // Exhausting the notification queue hands the subscriber to the registry
+    env.as_contract(&contract_id, || {
+        assert_eq!(logic::retry_notifications(&env, 5), 0);
+        assert!(storage::get_retry_queue(&env).is_empty());
+        let pending = logic::get_pending_compensations(&env);
+        assert_eq!(pending.len(), 1);
+        let compensation = pending.get(0).unwrap();
+        assert_eq!(compensation.kind, CompensationKind::Notification);
+        assert_eq!(compensation.subject, flaky);
+        assert_eq!(compensation.proposal_id, proposal_id);
+        assert_eq!(compensation.status, CompensationStatus::Pending);
+    });
+
+    // A failed attempt backs off; running again before it is due does nothing
+    env.as_contract(&contract_id, || {
+        assert_eq!(logic::run_compensations(&env, 5), 0);
+        let compensation = logic::get_pending_compensations(&env).get(0).unwrap();
+        assert_eq!(compensation.attempts, 1);
+        assert_eq!(
+            compensation.next_attempt_at,
+            env.ledger().timestamp() + storage::COMPENSATION_BACKOFF
+        );
+        assert_eq!(logic::run_compensations(&env, 5), 0);
+        assert_eq!(
+            logic::get_pending_compensations(&env)
+                .get(0)
+                .unwrap()
+                .attempts,
+            1
+        );
+    });
+
+    env.ledger()
+        .with_mut(|l| l.timestamp += storage::COMPENSATION_BACKOFF);
+    env.as_contract(&contract_id, || {
+        assert_eq!(logic::run_compensations(&env, 5), 0);
+        let compensation = logic::get_pending_compensations(&env).get(0).unwrap();
+        assert_eq!(compensation.attempts, 2);
+        assert_eq!(
+            compensation.next_attempt_at,
+            env.ledger().timestamp() + storage::COMPENSATION_BACKOFF * 2
+        );
+    });
+
+    flaky_client.set_healthy(&true);
+    env.ledger()
+        .with_mut(|l| l.timestamp += storage::COMPENSATION_BACKOFF * 2);
+    env.as_contract(&contract_id, || {
+        assert_eq!(logic::run_compensations(&env, 5), 1);
+        assert!(logic::get_pending_compensations(&env).is_empty());
+        assert!(
+            storage::get_notification_results(&env, proposal_id)
+                .get(0)
+                .unwrap()
+                .success
+        );
+    });
+}
+
+#[test]
+fn test_compensation_gives_up_and_governance_dismisses() {
+    let (env, contract_id, governance, proposer) = setup_upgrade_storage();
+    let broken = env.register_contract(None, PanickingSubscriber);
+
+    env.as_contract(&contract_id, || {
+        logic::set_notification_max_attempts(&env, &governance, 1).unwrap();
+    });
+    env.as_contract(&contract_id, || {
+        logic::add_subscriber(&env, &governance, &broken).unwrap();
+    });
+    let proposal_id =
+        create_approved_proposal(&env, &contract_id, &proposer, create_test_version(1, 1, 0));
+    env.as_contract(&contract_id, || {
+        logic::execute_proposal(&env, &governance, proposal_id).unwrap();
+    });
+    env.as_contract(&contract_id, || {
+        logic::retry_notifications(&env, 5);
+    });
+    let compensation_id = env.as_contract(&contract_id, || {
+        logic::get_pending_compensations(&env).get(0).unwrap().id
+    });
+
+    // Still being retried, so it cannot be dismissed yet
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            logic::dismiss_compensation(&env, &governance, compensation_id),
+            Err(ProposalError::CompensationPending.into())
+        );
+    });
+
+    for _ in 0..storage::MAX_COMPENSATION_ATTEMPTS {
+        env.as_contract(&contract_id, || {
+            assert_eq!(logic::run_compensations(&env, 5), 0);
+        });
+        env.ledger().with_mut(|l| {
+            l.timestamp += storage::COMPENSATION_BACKOFF * storage::MAX_COMPENSATION_ATTEMPTS as u64
+        });
+    }
+
+    env.as_contract(&contract_id, || {
+        let compensation = logic::get_pending_compensations(&env).get(0).unwrap();
+        assert_eq!(compensation.status, CompensationStatus::GivenUp);
+        assert_eq!(compensation.attempts, storage::MAX_COMPENSATION_ATTEMPTS);
+        // Given-up entries are no longer retried
+        assert_eq!(logic::run_compensations(&env, 5), 0);
+        assert_eq!(
+            logic::get_pending_compensations(&env)
+                .get(0)
+                .unwrap()
+                .attempts,
+            storage::MAX_COMPENSATION_ATTEMPTS
+        );
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            logic::dismiss_compensation(&env, &proposer, compensation_id),
+            Err(UpgradeError::NotAuthorized.into())
+        );
+    });
+    env.as_contract(&contract_id, || {
+        logic::dismiss_compensation(&env, &governance, compensation_id).unwrap();
+        assert!(logic::get_pending_compensations(&env).is_empty());
+    });
+}
+
+#[test]
+fn test_typed_slots_round_trip_every_key() {
+    let (env, contract_id, governance, proposer) = setup_upgrade_storage();
+
+    env.as_contract(&contract_id, || {
+        // Values come back with the slot's declared type; no annotation is needed
+        // (or possible) at the call site, so a wrong-type read cannot be written.
+        let version = create_test_version(2, 0, 0);
+        storage::CURRENT_VERSION.store(&env, &version);
+        assert_eq!(storage::CURRENT_VERSION.load(&env), Some(version));
+        assert_eq!(
+            storage::GOVERNANCE_ADDRESS.load(&env),
+            Some(governance.clone())
+        );
+
+        storage::EMERGENCY_UPGRADE.store(&env, &true);
+        assert_eq!(storage::EMERGENCY_UPGRADE.load(&env), Some(true));
+
+        let mut power = Map::new(&env);
+        power.set(proposer.clone(), 7u32);
+        storage::VOTING_POWER.store(&env, &power);
+        assert_eq!(
+            storage::VOTING_POWER
+                .load(&env)
+                .unwrap()
+                .get(proposer.clone()),
+            Some(7)
+        );
+
+        assert!(storage::UPGRADE_PROPOSALS.load(&env).unwrap().is_empty());
+        assert!(storage::MIGRATION_PLANS.load(&env).unwrap().is_empty());
+
+        let config = RateLimitConfig {
+            max_proposals_per_window: 2,
+            window_seconds: 60,
+        };
+        storage::RATE_LIMIT.store(&env, &config);
+        assert_eq!(storage::RATE_LIMIT.load(&env), Some(config));
+
+        assert!(!storage::ID_MODE.has(&env));
+        storage::ID_MODE.store(&env, &ProposalIdMode::ContentHash);
+        assert_eq!(
+            storage::ID_MODE.load(&env),
+            Some(ProposalIdMode::ContentHash)
+        );
+
+        let names = Vec::from_array(&env, [symbol_short!("beta")]);
+        storage::FEATURE_INDEX.store(&env, &names);
+        assert_eq!(storage::FEATURE_INDEX.load(&env), Some(names));
+
+        let subscribers = Vec::from_array(&env, [proposer.clone()]);
+        storage::SUBSCRIBERS.store(&env, &subscribers);
+        assert_eq!(storage::SUBSCRIBERS.load(&env), Some(subscribers));
+
+        let pending = PendingNotification {
+            subscriber: proposer.clone(),
+            proposal_id: 3,
+            from_version: create_test_version(1, 0, 0),
+            to_version: create_test_version(2, 0, 0),
+            attempts: 1,
+        };
+        let queue = Vec::from_array(&env, [pending]);
+        storage::RETRY_QUEUE.store(&env, &queue);
+        assert_eq!(storage::RETRY_QUEUE.load(&env), Some(queue));
+
+        storage::MAX_ATTEMPTS.store(&env, &5);
+        assert_eq!(storage::MAX_ATTEMPTS.load(&env), Some(5));
+
+        let window = Vec::from_array(&env, [10u64, 20]);
+        storage::proposer_window_slot(&proposer).store(&env, &window);
+        assert_eq!(
+            storage::proposer_window_slot(&proposer).load(&env),
+            Some(window)
+        );
+        assert!(!storage::proposer_window_slot(&governance).has(&env));
+
+        let flag = FeatureFlag {
+            name: symbol_short!("beta"),
+            enabled: true,
+            min_version: create_test_version(0, 0, 0),
+            updated_at: 0,
+        };
+        storage::feature_slot(&flag.name).store(&env, &flag);
+        assert_eq!(storage::feature_slot(&flag.name).load(&env), Some(flag));
+
+        let results = Vec::from_array(
+            &env,
+            [NotificationResult {
+                subscriber: proposer.clone(),
+                success: false,
+            }],
+        );
+        storage::notification_results_slot(3).store(&env, &results);
+        assert_eq!(
+            storage::notification_results_slot(3).load(&env),
+            Some(results)
+        );
+        assert!(!storage::notification_results_slot(4).has(&env));
+    });
+}
+
+#[test]
+fn test_capabilities_across_partial_initialization() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, StellarGuildsContract);
+    let governance = Address::generate(&env);
+
+    // Nothing initialized: probing must not trap
+    env.as_contract(&contract_id, || {
+        let caps = logic::capabilities(&env);
+        assert!(!caps.upgrade_initialized);
+        assert!(!caps.proxy_initialized);
+        assert!(!caps.emergency_enabled);
+        assert!(!caps.timelock_enabled);
+        assert!(!caps.delegation_enabled);
+        assert_eq!(
+            caps.event_schema_version,
+            crate::events::types::EVENT_SCHEMA_VERSION
+        );
+        assert_eq!(
+            caps.error_code_version,
+            crate::utils::errors::ERROR_CODE_VERSION
+        );
+    });
+
+    // Upgrade module only
+    env.as_contract(&contract_id, || {
+        storage::initialize(&env, create_test_version(1, 0, 0), governance.clone()).unwrap();
+        let caps = logic::capabilities(&env);
+        assert!(caps.upgrade_initialized);
+        assert!(!caps.proxy_initialized);
+    });
+
+    // Both modules, with emergency upgrades switched on
+    env.as_contract(&contract_id, || {
+        crate::proxy::storage::initialize(&env, Address::generate(&env), governance.clone())
+            .unwrap();
+        storage::set_emergency_upgrade_enabled(&env, true);
+        let caps = logic::capabilities(&env);
+        assert!(caps.upgrade_initialized);
+        assert!(caps.proxy_initialized);
+        assert!(caps.emergency_enabled);
+    });
+
+    // The public entrypoint is reachable on an uninitialized deployment
+    let fresh = env.register_contract(None, StellarGuildsContract);
+    let client = crate::StellarGuildsContractClient::new(&env, &fresh);
+    assert!(!client.capabilities().upgrade_initialized);
+}
+
+#[test]
+fn test_capabilities_follow_the_delay_and_power_config() {
+    let (env, contract_id, governance, _) = setup_upgrade_storage();
+    let client = crate::StellarGuildsContractClient::new(&env, &contract_id);
+
+    // The built-in standard and supermajority tracks carry a timelock
+    let caps = client.capabilities();
+    assert!(caps.timelock_enabled);
+    assert!(!caps.delegation_enabled);
+
+    // With every track's timelock cleared nothing waits
+    env.as_contract(&contract_id, || {
+        for mut track in storage::get_governance_tracks(&env).iter() {
+            track.timelock = 0;
+            storage::set_governance_track(&env, &track);
+        }
+    });
+    assert!(!client.capabilities().timelock_enabled);
+
+    // The default execution delay alone enables it
+    env.as_contract(&contract_id, || storage::set_execution_delay(&env, 3_600));
+    assert!(client.capabilities().timelock_enabled);
+
+    // So does a delay on the proxy's schedule
+    env.as_contract(&contract_id, || {
+        storage::set_execution_delay(&env, 0);
+        crate::proxy::storage::initialize(&env, Address::generate(&env), governance.clone())
+            .unwrap();
+        crate::proxy::storage::set_min_upgrade_delay(&env, 600);
+    });
+    assert!(client.capabilities().timelock_enabled);
+
+    // Delegation is reported once someone holds voting power
+    env.as_contract(&contract_id, || {
+        storage::set_voting_power(&env, &Address::generate(&env), 10)
+    });
+    assert!(client.capabilities().delegation_enabled);
+}
+
+#[test]
+fn test_execution_receipt_survives_proposal_removal() {
+    let (env, contract_id, governance, proposer) = setup_upgrade_storage();
+    env.ledger().with_mut(|l| l.timestamp = 5_000);
+
+    let target = create_test_version(1, 1, 0);
+    let proposal_id = create_approved_proposal(&env, &contract_id, &proposer, target.clone());
+    env.as_contract(&contract_id, || {
+        assert!(storage::get_execution_receipt(&env, proposal_id).is_none());
+        logic::execute_proposal(&env, &governance, proposal_id).unwrap();
+    });
+
+    env.as_contract(&contract_id, || {
+        let receipt = storage::get_execution_receipt(&env, proposal_id).unwrap();
+        let proposal = storage::get_upgrade_proposal(&env, proposal_id).unwrap();
+        assert_eq!(receipt.executor, governance);
+        assert_eq!(receipt.from_version, create_test_version(1, 0, 0));
+        assert_eq!(receipt.to_version, target);
+        assert_eq!(receipt.implementation, proposal.new_contract_address);
+        assert_eq!(receipt.migration_outcome, MigrationOutcome::NotRequired);
+        assert_eq!(receipt.timestamp, 5_000);
+
+        // Receipts are write-once
+        let mut forged = receipt.clone();
+        forged.executor = proposer.clone();
+        storage::store_execution_receipt(&env, &forged);
+        assert_eq!(
+            storage::get_execution_receipt(&env, proposal_id),
+            Some(receipt.clone())
+        );
+
+        // Drop the proposal as archiving would; the receipt remains readable
+        let mut proposals = storage::UPGRADE_PROPOSALS.load(&env).unwrap();
+        proposals.remove(proposal_id);
+        storage::UPGRADE_PROPOSALS.store(&env, &proposals);
+        assert!(storage::get_upgrade_proposal(&env, proposal_id).is_none());
+        assert_eq!(
+            storage::get_execution_receipt(&env, proposal_id),
+            Some(receipt.clone())
+        );
+
+        assert_eq!(
+            logic::receipt_hash(&env, &receipt),
+            BytesN::from_array(
+                &env,
+                &env.crypto()
+                    .sha256(&receipt.clone().to_xdr(&env))
+                    .to_array()
+            )
+        );
+    });
+}
+
+#[test]
+fn test_repair_indices_restores_votes_on_inconsistent_proposal() {
+    let (env, contract_id, governance, proposer) = setup_upgrade_storage();
+    let voter = Address::generate(&env);
+
+    let proposal_id = env.as_contract(&contract_id, || {
+        storage::set_voting_power(&env, &voter, 1);
+        logic::propose_upgrade(
+            &env,
+            &proposer,
+            &Address::generate(&env),
+            &create_test_version(1, 1, 0),
+            String::from_str(&env, "restored"),
+        )
+        .unwrap()
+    });
+
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            storage::get_status_index(&env, UpgradeStatus::Pending),
+            Vec::from_array(&env, [proposal_id])
+        );
+        assert!(storage::get_proposer_index(&env, &proposer).contains(proposal_id));
+
+        // Simulate indices lost while the proposal entry was archived
+        storage::status_index_slot(UpgradeStatus::Pending).store(&env, &Vec::new(&env));
+        storage::proposer_index_slot(&proposer).store(&env, &Vec::new(&env));
+    });
+
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            logic::vote_on_proposal(&env, &voter, proposal_id, true),
+            Err(UpgradeError::ProposalIndicesIncomplete.into())
+        );
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            logic::repair_indices(&env, &proposer, proposal_id),
+            Err(UpgradeError::NotAuthorized.into())
+        );
+    });
+    env.as_contract(&contract_id, || {
+        logic::repair_indices(&env, &governance, proposal_id).unwrap();
+        assert!(storage::get_status_index(&env, UpgradeStatus::Pending).contains(proposal_id));
+        assert!(storage::get_proposer_index(&env, &proposer).contains(proposal_id));
+    });
+    env.as_contract(&contract_id, || {
+        logic::vote_on_proposal(&env, &voter, proposal_id, true).unwrap();
+        // The status index follows the approval
+        assert!(storage::get_status_index(&env, UpgradeStatus::Approved).contains(proposal_id));
+        assert!(!storage::get_status_index(&env, UpgradeStatus::Pending).contains(proposal_id));
+    });
+}
+
+#[test]
+fn test_missing_proposal_distinguishes_unavailable_from_unknown() {
+    let (env, contract_id, governance, proposer) = setup_upgrade_storage();
+    let voter = Address::generate(&env);
+
+    let proposal_id = env.as_contract(&contract_id, || {
+        logic::propose_upgrade(
+            &env,
+            &proposer,
+            &Address::generate(&env),
+            &create_test_version(1, 1, 0),
+            String::from_str(&env, "expires"),
+        )
+        .unwrap()
+    });
+
+    env.as_contract(&contract_id, || {
+        // Drop the proposal entry as if its ledger entry had expired
+        let mut proposals = storage::UPGRADE_PROPOSALS.load(&env).unwrap();
+        proposals.remove(proposal_id);
+        storage::UPGRADE_PROPOSALS.store(&env, &proposals);
+    });
+
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            logic::vote_on_proposal(&env, &voter, proposal_id, true),
+            Err(UpgradeError::ProposalDataUnavailable.into())
+        );
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            logic::execute_proposal(&env, &governance, proposal_id + 10),
+            Err(UpgradeError::ProposalNotFound.into())
+        );
+    });
+}
+
+/// Create a pending proposal over ten units of weight with a 6-power supporter
+/// and a 2-power abstainer registered.
+fn setup_quorum_proposal(
+    env: &Env,
+    contract_id: &Address,
+    governance: &Address,
+    proposer: &Address,
+    quorum_counts_abstain: bool,
+) -> (u64, Address, Address) {
+    let supporter = Address::generate(env);
+    let abstainer = Address::generate(env);
+    env.as_contract(contract_id, || {
+        logic::set_governance_config(
+            env,
+            governance,
+            &GovernanceConfig {
+                quorum_bps: 8_000,
+                approval_threshold_bps: SIMPLE_MAJORITY_BPS,
+                quorum_counts_abstain,
+                early_finalize: false,
+                activation_delay: 0,
+                live_vote_export: false,
+                slash_destination: None,
+                revert_window: 0,
+                archive_sink: None,
+            },
+        )
+        .unwrap();
+    });
+    let proposal_id = env.as_contract(contract_id, || {
+        storage::set_voting_power(env, &supporter, 6);
+        storage::set_voting_power(env, &abstainer, 2);
+        let proposal_id = logic::propose_upgrade(
+            env,
+            proposer,
+            &Address::generate(env),
+            &create_test_version(1, 1, 0),
+            String::from_str(env, "quorum"),
+        )
+        .unwrap();
+        let mut proposal = storage::get_upgrade_proposal(env, proposal_id).unwrap();
+        proposal.total_voting_power_snapshot = 10;
+        storage::store_upgrade_proposal(env, &proposal);
+        proposal_id
+    });
+    (proposal_id, supporter, abstainer)
+}
+
+#[test]
+fn test_quorum_passes_only_when_abstentions_count() {
+    for counts_abstain in [true, false] {
+        let (env, contract_id, governance, proposer) = setup_upgrade_storage();
+        let (proposal_id, supporter, abstainer) =
+            setup_quorum_proposal(&env, &contract_id, &governance, &proposer, counts_abstain);
+
+        env.as_contract(&contract_id, || {
+            logic::cast_vote(&env, &abstainer, proposal_id, VoteChoice::Abstain).unwrap();
+        });
+        env.as_contract(&contract_id, || {
+            logic::cast_vote(&env, &supporter, proposal_id, VoteChoice::For).unwrap();
+        });
+
+        env.as_contract(&contract_id, || {
+            let proposal = storage::get_upgrade_proposal(&env, proposal_id).unwrap();
+            let params = storage::get_proposal_params(&env, proposal_id);
+            assert_eq!(params.quorum_counts_abstain, counts_abstain);
+
+            // 6 for + 2 abstain reaches the 8-weight quorum only when abstentions count
+            let projection = logic::project_outcome(&proposal, &params);
+            assert_eq!(projection.votes_abstain, 2);
+            assert_eq!(projection.quorum_required, 8);
+            if counts_abstain {
+                assert_eq!(projection.participating, 8);
+                assert_eq!(proposal.status, UpgradeStatus::Approved);
+            } else {
+                assert_eq!(projection.participating, 6);
+                assert!(!projection.quorum_met);
+                assert_eq!(proposal.status, UpgradeStatus::Pending);
+            }
+        });
+    }
+}
+
+#[test]
+fn test_proposal_params_are_snapshotted_at_creation() {
+    let (env, contract_id, governance, proposer) = setup_upgrade_storage();
+    let (proposal_id, _, _) =
+        setup_quorum_proposal(&env, &contract_id, &governance, &proposer, true);
+
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            logic::set_governance_config(
+                &env,
+                &governance,
+                &GovernanceConfig {
+                    quorum_bps: 10_001,
+                    approval_threshold_bps: SIMPLE_MAJORITY_BPS,
+                    quorum_counts_abstain: false,
+                    early_finalize: false,
+                    activation_delay: 0,
+                    live_vote_export: false,
+                    slash_destination: None,
+                    revert_window: 0,
+                    archive_sink: None,
+                },
+            ),
+            Err(UpgradeError::InvalidQuorum.into())
+        );
+    });
+    env.as_contract(&contract_id, || {
+        logic::set_governance_config(
+            &env,
+            &governance,
+            &GovernanceConfig {
+                quorum_bps: 0,
+                approval_threshold_bps: SIMPLE_MAJORITY_BPS,
+                quorum_counts_abstain: false,
+                early_finalize: true,
+                activation_delay: 0,
+                live_vote_export: false,
+                slash_destination: None,
+                revert_window: 0,
+                archive_sink: None,
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            storage::get_proposal_params(&env, proposal_id),
+            ProposalParams {
+                quorum_bps: 8_000,
+                quorum_counts_abstain: true,
+                early_finalize: false,
+                track_id: DEFAULT_TRACK_ID,
+                threshold_bps: 5_000,
+                voting_ends_at: DEFAULT_VOTING_PERIOD,
+                timelock: 0,
+                separation_of_duties: false,
+            }
+        );
+    });
+}
+
+/// Pending proposal over ten units of weight decided under `early_finalize`
+fn setup_early_finalize_proposal(
+    env: &Env,
+    contract_id: &Address,
+    governance: &Address,
+    proposer: &Address,
+    early_finalize: bool,
+) -> u64 {
+    env.as_contract(contract_id, || {
+        logic::set_governance_config(
+            env,
+            governance,
+            &GovernanceConfig {
+                quorum_bps: 0,
+                approval_threshold_bps: SIMPLE_MAJORITY_BPS,
+                quorum_counts_abstain: false,
+                early_finalize,
+                activation_delay: 0,
+                live_vote_export: false,
+                slash_destination: None,
+                revert_window: 0,
+                archive_sink: None,
+            },
+        )
+        .unwrap();
+    });
+    env.as_contract(contract_id, || {
+        let proposal_id = logic::propose_upgrade(
+            env,
+            proposer,
+            &Address::generate(env),
+            &create_test_version(1, 1, 0),
+            String::from_str(env, "early"),
+        )
+        .unwrap();
+        let mut proposal = storage::get_upgrade_proposal(env, proposal_id).unwrap();
+        proposal.total_voting_power_snapshot = 10;
+        storage::store_upgrade_proposal(env, &proposal);
+        proposal_id
+    })
+}
+
+#[test]
+fn test_early_finalize_once_outcome_is_decided() {
+    for early_finalize in [true, false] {
+        let (env, contract_id, governance, proposer) = setup_upgrade_storage();
+        let proposal_id = setup_early_finalize_proposal(
+            &env,
+            &contract_id,
+            &governance,
+            &proposer,
+            early_finalize,
+        );
+        let (yes, no, abstain) = (
+            Address::generate(&env),
+            Address::generate(&env),
+            Address::generate(&env),
+        );
+        env.as_contract(&contract_id, || {
+            set_snapshot_power(&env, proposal_id, &yes, 5);
+            set_snapshot_power(&env, proposal_id, &no, 3);
+            set_snapshot_power(&env, proposal_id, &abstain, 1);
+        });
+
+        env.as_contract(&contract_id, || {
+            logic::cast_vote(&env, &yes, proposal_id, VoteChoice::For).unwrap();
+        });
+        env.as_contract(&contract_id, || {
+            logic::cast_vote(&env, &no, proposal_id, VoteChoice::Against).unwrap();
+        });
+
+        // 5 for against 3 + 2 remaining: the remaining weight exactly equals the deficit
+        env.as_contract(&contract_id, || {
+            let proposal = storage::get_upgrade_proposal(&env, proposal_id).unwrap();
+            assert_eq!(proposal.status, UpgradeStatus::Pending);
+            assert_eq!(
+                logic::decided_outcome(&proposal, &storage::get_proposal_params(&env, proposal_id)),
+                None
+            );
+        });
+
+        env.as_contract(&contract_id, || {
+            logic::cast_vote(&env, &abstain, proposal_id, VoteChoice::Abstain).unwrap();
+        });
+
+        // 5 for against 3 + 1 remaining is settled
+        env.as_contract(&contract_id, || {
+            let status = storage::get_upgrade_proposal(&env, proposal_id)
+                .unwrap()
+                .status;
+            if early_finalize {
+                assert_eq!(status, UpgradeStatus::Approved);
+            } else {
+                assert_eq!(status, UpgradeStatus::Pending);
+            }
+        });
+    }
+}
+
+#[test]
+fn test_decided_outcome_rejects_and_respects_quorum() {
+    let env = Env::default();
+    let mut proposal = UpgradeProposal {
+        id: 1,
+        proposer: Address::generate(&env),
+        new_contract_address: Address::generate(&env),
+        version: create_test_version(1, 1, 0),
+        description: String::from_str(&env, "math"),
+        timestamp: 0,
+        status: UpgradeStatus::Pending,
+        votes_for: 1,
+        votes_against: u64::MAX - 2,
+        votes_abstain: 0,
+        total_voters: 3,
+        total_voting_power_snapshot: u64::MAX,
+        voting_deadline: 0,
+        salt: BytesN::from_array(&env, &[0u8; 32]),
+        description_hash: BytesN::from_array(&env, &[0u8; 32]),
+    };
+    let params = ProposalParams {
+        quorum_bps: 0,
+        quorum_counts_abstain: false,
+        early_finalize: true,
+        track_id: DEFAULT_TRACK_ID,
+        threshold_bps: 5_000,
+        voting_ends_at: 0,
+        timelock: 0,
+        separation_of_duties: false,
+    };
+
+    // Sums beyond u64 are handled without overflow
+    assert_eq!(
+        logic::decided_outcome(&proposal, &params),
+        Some(UpgradeStatus::Rejected)
+    );
+
+    // A decided majority still waits for quorum before approving
+    proposal.votes_for = 5;
+    proposal.votes_against = 0;
+    proposal.total_voting_power_snapshot = 10;
+    let strict = ProposalParams {
+        quorum_bps: 10_000,
+        ..params.clone()
+    };
+    assert_eq!(logic::decided_outcome(&proposal, &params), None);
+    proposal.votes_for = 6;
+    assert_eq!(
+        logic::decided_outcome(&proposal, &params),
+        Some(UpgradeStatus::Approved)
+    );
+    assert_eq!(logic::decided_outcome(&proposal, &strict), None);
+}
+
+/// Register voters with powers 5, 3 and 2 and open a recovery to `new_governance`
+fn setup_recovery(
+    env: &Env,
+    contract_id: &Address,
+    new_governance: &Address,
+) -> (u64, Address, Address, Address) {
+    let (big, mid, small) = (
+        Address::generate(env),
+        Address::generate(env),
+        Address::generate(env),
+    );
+    env.ledger().with_mut(|l| l.timestamp = 1_000);
+    let recovery_id = env.as_contract(contract_id, || {
+        storage::set_voting_power(env, &big, 5);
+        storage::set_voting_power(env, &mid, 3);
+        storage::set_voting_power(env, &small, 2);
+        logic::initiate_recovery(env, &Address::generate(env), new_governance).unwrap()
+    });
+    (recovery_id, big, mid, small)
+}
+
+#[test]
+fn test_recovery_replaces_governance_after_supermajority_and_timelock() {
+    let (env, contract_id, governance, _) = setup_upgrade_storage();
+    let new_governance = Address::generate(&env);
+    let (recovery_id, big, mid, _) = setup_recovery(&env, &contract_id, &new_governance);
+
+    for voter in [&big, &mid] {
+        env.as_contract(&contract_id, || {
+            logic::vote_recovery(&env, voter, recovery_id).unwrap();
+        });
+    }
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            logic::vote_recovery(&env, &big, recovery_id),
+            Err(UpgradeError::AlreadyVoted.into())
+        );
+    });
+
+    // 8 of 10 is exactly the floor, but the timelock still applies
+    env.as_contract(&contract_id, || {
+        let recovery = storage::get_recovery(&env, recovery_id).unwrap();
+        assert_eq!(recovery.votes_for, 8);
+        assert_eq!(recovery.total_voting_power, 10);
+        assert_eq!(
+            logic::execute_recovery(&env, &big, recovery_id),
+            Err(UpgradeError::RecoveryTimelockActive.into())
+        );
+    });
+
+    env.ledger()
+        .with_mut(|l| l.timestamp = 1_000 + logic::RECOVERY_TIMELOCK_SECONDS);
+    env.as_contract(&contract_id, || {
+        logic::execute_recovery(&env, &big, recovery_id).unwrap();
+        assert_eq!(storage::get_governance_address(&env), new_governance);
+        assert_eq!(
+            storage::get_recovery(&env, recovery_id).unwrap().status,
+            RecoveryStatus::Executed
+        );
+    });
+
+    // The old governance key no longer controls the module
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            logic::set_rate_limit(&env, &governance, 1, 60),
+            Err(UpgradeError::NotAuthorized.into())
+        );
+    });
+}
+
+#[test]
+fn test_recovery_vetoed_by_existing_governance() {
+    let (env, contract_id, governance, _) = setup_upgrade_storage();
+    let new_governance = Address::generate(&env);
+    let (recovery_id, big, mid, small) = setup_recovery(&env, &contract_id, &new_governance);
+
+    for voter in [&big, &mid, &small] {
+        env.as_contract(&contract_id, || {
+            logic::vote_recovery(&env, voter, recovery_id).unwrap();
+        });
+    }
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            logic::veto_recovery(&env, &big, recovery_id),
+            Err(UpgradeError::NotAuthorized.into())
+        );
+    });
+    env.as_contract(&contract_id, || {
+        logic::veto_recovery(&env, &governance, recovery_id).unwrap();
+    });
+
+    env.ledger()
+        .with_mut(|l| l.timestamp = 1_000 + logic::RECOVERY_TIMELOCK_SECONDS);
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            logic::execute_recovery(&env, &big, recovery_id),
+            Err(UpgradeError::RecoveryNotOpen.into())
+        );
+        assert_eq!(storage::get_governance_address(&env), governance);
+    });
+}
+
+#[test]
+fn test_recovery_requires_supermajority() {
+    let (env, contract_id, governance, _) = setup_upgrade_storage();
+    let new_governance = Address::generate(&env);
+    let (recovery_id, big, _, small) = setup_recovery(&env, &contract_id, &new_governance);
+
+    // 7 of 10 is a majority but below the 80% floor
+    for voter in [&big, &small] {
+        env.as_contract(&contract_id, || {
+            logic::vote_recovery(&env, voter, recovery_id).unwrap();
+        });
+    }
+
+    env.ledger()
+        .with_mut(|l| l.timestamp = 1_000 + logic::RECOVERY_TIMELOCK_SECONDS);
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            logic::execute_recovery(&env, &big, recovery_id),
+            Err(UpgradeError::SupermajorityNotReached.into())
+        );
+        assert_eq!(storage::get_governance_address(&env), governance);
+        assert_eq!(
+            storage::get_recovery(&env, recovery_id).unwrap().status,
+            RecoveryStatus::Open
+        );
+    });
+}
+
+#[test]
+fn test_execute_upgrade_switches_proxy_implementation() {
+    let (env, contract_id, governance, proposer) = setup_upgrade_storage();
+    let admin = Address::generate(&env);
+    let original = Address::generate(&env);
+    env.as_contract(&contract_id, || {
+        crate::proxy::storage::initialize(&env, original.clone(), admin.clone()).unwrap();
+        crate::proxy::storage::set_skip_version_check(&env, true);
+    });
+    let proposal_id =
+        create_approved_proposal(&env, &contract_id, &proposer, create_test_version(1, 1, 0));
+
+    // A stopped proxy holds the execution back
+    env.as_contract(&contract_id, || {
+        crate::proxy::implementation::emergency_stop(&env, &admin).unwrap();
+    });
+    env.as_contract(&contract_id, || {
+        assert!(logic::get_execution_blockers(&env, proposal_id).contains(Blocker::ProxyPaused));
+        assert_eq!(
+            logic::execute_proposal(&env, &governance, proposal_id),
+            Err(ProposalError::ProxyPaused.into())
+        );
+        // The failed attempt leaves nothing behind
+        assert_eq!(
+            storage::get_previous_implementation(&env, proposal_id),
+            None
+        );
+    });
+    env.as_contract(&contract_id, || {
+        crate::proxy::implementation::resume(&env, &admin).unwrap();
+    });
+
+    env.as_contract(&contract_id, || {
+        logic::execute_proposal(&env, &governance, proposal_id).unwrap();
+    });
+    env.as_contract(&contract_id, || {
+        let proposal = storage::get_upgrade_proposal(&env, proposal_id).unwrap();
+        assert_eq!(proposal.status, UpgradeStatus::Executed);
+        assert_eq!(
+            crate::proxy::storage::get_implementation(&env),
+            proposal.new_contract_address
+        );
+        assert_eq!(crate::proxy::storage::get_proxy_config(&env).version, 2);
+
+        let transaction = crate::proxy::storage::get_latest_upgrade(&env).unwrap();
+        assert_eq!(transaction.initiator, governance);
+        assert_eq!(
+            transaction.new_implementation,
+            proposal.new_contract_address
+        );
+        assert!(transaction.success);
+
+        let receipt = storage::get_execution_receipt(&env, proposal_id).unwrap();
+        assert_eq!(receipt.previous_implementation, Some(original));
+        assert_eq!(receipt.post_check, PostCheck::Matched);
+    });
+}
+
+/// Initialize the proxy with `governance` as admin and a one-day upgrade delay
+fn setup_delayed_proxy(env: &Env, contract_id: &Address, governance: &Address) {
+    env.as_contract(contract_id, || {
+        crate::proxy::storage::initialize(env, Address::generate(env), governance.clone()).unwrap();
+        crate::proxy::storage::set_skip_version_check(env, true);
+    });
+    env.as_contract(contract_id, || {
+        crate::proxy::implementation::set_min_upgrade_delay(env, governance, 86_400).unwrap();
+    });
+}
+
+#[test]
+fn test_execute_upgrade_waits_for_proxy_schedule() {
+    let (env, contract_id, governance, proposer) = setup_upgrade_storage();
+    setup_delayed_proxy(&env, &contract_id, &governance);
+    env.ledger().with_mut(|l| l.timestamp = 1_000);
+
+    let target = create_test_version(1, 1, 0);
+    let proposal_id = create_approved_proposal(&env, &contract_id, &proposer, target.clone());
+    env.as_contract(&contract_id, || {
+        logic::execute_proposal(&env, &governance, proposal_id).unwrap();
+    });
+
+    let schedule_id = env.as_contract(&contract_id, || {
+        let proposal = storage::get_upgrade_proposal(&env, proposal_id).unwrap();
+        assert_eq!(proposal.status, UpgradeStatus::AwaitingProxy);
+        // Nothing is applied until the proxy switch runs
+        assert_eq!(
+            storage::get_current_version(&env),
+            create_test_version(1, 0, 0)
+        );
+        assert!(storage::get_execution_receipt(&env, proposal_id).is_none());
+        assert_eq!(
+            logic::complete_execution(&env, proposal_id),
+            Err(UpgradeError::ProxyNotExecuted.into())
+        );
+
+        let schedule_id = storage::get_proxy_link(&env, proposal_id).unwrap();
+        let scheduled = crate::proxy::storage::get_scheduled_upgrade(&env, schedule_id).unwrap();
+        assert_eq!(scheduled.new_implementation, proposal.new_contract_address);
+        assert_eq!(scheduled.executable_at, 1_000 + 86_400);
+        schedule_id
+    });
+
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            crate::proxy::implementation::execute_scheduled_upgrade(&env, &governance, schedule_id),
+            Err(ProxyError::DelayNotElapsed)
+        );
+    });
+    env.ledger().with_mut(|l| l.timestamp = 1_000 + 86_400);
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            crate::proxy::implementation::execute_scheduled_upgrade(&env, &governance, schedule_id),
+            Ok(UpgradeOutcome::Applied)
+        );
+    });
+
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            logic::complete_execution(&env, proposal_id),
+            Ok(UpgradeStatus::Executed)
+        );
+        let proposal = storage::get_upgrade_proposal(&env, proposal_id).unwrap();
+        assert_eq!(proposal.status, UpgradeStatus::Executed);
+        assert_eq!(storage::get_current_version(&env), target);
+        assert_eq!(
+            crate::proxy::storage::get_implementation(&env),
+            proposal.new_contract_address
+        );
+        let receipt = storage::get_execution_receipt(&env, proposal_id).unwrap();
+        assert_eq!(receipt.executor, governance);
+        assert_eq!(receipt.post_check, PostCheck::Matched);
+        assert_eq!(
+            logic::complete_execution(&env, proposal_id),
+            Err(UpgradeError::NotAwaitingProxy.into())
+        );
+    });
+}
+
+#[test]
+fn test_post_check_mismatch_blocks_upgrades_until_acknowledged() {
+    let (env, contract_id, governance, proposer) = setup_upgrade_storage();
+    setup_delayed_proxy(&env, &contract_id, &governance);
+    env.ledger().with_mut(|l| l.timestamp = 1_000);
+
+    let proposal_id =
+        create_approved_proposal(&env, &contract_id, &proposer, create_test_version(1, 1, 0));
+    let next_id =
+        create_approved_proposal(&env, &contract_id, &proposer, create_test_version(1, 2, 0));
+    env.as_contract(&contract_id, || {
+        logic::execute_proposal(&env, &governance, proposal_id).unwrap();
+    });
+
+    // The linked schedule ends up switching the proxy somewhere else
+    let rogue = Address::generate(&env);
+    env.as_contract(&contract_id, || {
+        let schedule_id = storage::get_proxy_link(&env, proposal_id).unwrap();
+        let mut scheduled =
+            crate::proxy::storage::get_scheduled_upgrade(&env, schedule_id).unwrap();
+        scheduled.new_implementation = rogue.clone();
+        crate::proxy::storage::store_scheduled_upgrade(&env, &scheduled);
+    });
+    env.ledger().with_mut(|l| l.timestamp = 1_000 + 86_400);
+    env.as_contract(&contract_id, || {
+        let schedule_id = storage::get_proxy_link(&env, proposal_id).unwrap();
+        assert_eq!(
+            crate::proxy::implementation::execute_scheduled_upgrade(&env, &governance, schedule_id),
+            Ok(UpgradeOutcome::Applied)
+        );
+    });
+
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            logic::complete_execution(&env, proposal_id),
+            Ok(UpgradeStatus::Executed)
+        );
+        let expected = storage::get_upgrade_proposal(&env, proposal_id)
+            .unwrap()
+            .new_contract_address;
+        let receipt = storage::get_execution_receipt(&env, proposal_id).unwrap();
+        assert_eq!(receipt.post_check, PostCheck::Mismatched);
+        assert_eq!(
+            storage::get_post_check_failure(&env),
+            Some(PostCheckFailure {
+                proposal_id,
+                expected,
+                installed: rogue.clone(),
+                detected_at: 1_000 + 86_400,
+            })
+        );
+        let health = logic::health(&env);
+        assert_eq!(health.unacknowledged_post_check, Some(proposal_id));
+        assert!(health.needs_attention);
+    });
+
+    // Further upgrades are blocked until governance acknowledges the mismatch
+    env.as_contract(&contract_id, || {
+        assert!(logic::get_execution_blockers(&env, next_id)
+            .contains(Blocker::PostCheckUnacknowledged(proposal_id)));
+        assert_eq!(
+            logic::execute_proposal(&env, &governance, next_id),
+            Err(ProposalError::PostCheckUnacknowledged.into())
+        );
+        assert_eq!(
+            logic::acknowledge_post_check(&env, &proposer),
+            Err(UpgradeError::NotAuthorized.into())
+        );
+    });
+    env.as_contract(&contract_id, || {
+        logic::acknowledge_post_check(&env, &governance).unwrap();
+    });
+
+    env.as_contract(&contract_id, || {
+        assert_eq!(storage::get_post_check_failure(&env), None);
+        assert!(!logic::health(&env).needs_attention);
+        assert_eq!(
+            logic::acknowledge_post_check(&env, &governance),
+            Err(ProposalError::NoPostCheckFailure.into())
+        );
+    });
+    env.as_contract(&contract_id, || {
+        logic::execute_proposal(&env, &governance, next_id).unwrap();
+        assert_eq!(
+            storage::get_upgrade_proposal(&env, next_id).unwrap().status,
+            UpgradeStatus::AwaitingProxy
+        );
+    });
+}
+
+#[test]
+fn test_cancelled_proxy_schedule_flags_proposal() {
+    let (env, contract_id, governance, proposer) = setup_upgrade_storage();
+    setup_delayed_proxy(&env, &contract_id, &governance);
+
+    let proposal_id =
+        create_approved_proposal(&env, &contract_id, &proposer, create_test_version(1, 1, 0));
+    env.as_contract(&contract_id, || {
+        logic::execute_proposal(&env, &governance, proposal_id).unwrap();
+    });
+    let schedule_id = env.as_contract(&contract_id, || {
+        storage::get_proxy_link(&env, proposal_id).unwrap()
+    });
+    env.as_contract(&contract_id, || {
+        crate::proxy::implementation::cancel_scheduled_upgrade(&env, &governance, schedule_id)
+            .unwrap();
+    });
+
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            logic::complete_execution(&env, proposal_id),
+            Ok(UpgradeStatus::NeedsAttention)
+        );
+        assert_eq!(
+            storage::get_upgrade_proposal(&env, proposal_id)
+                .unwrap()
+                .status,
+            UpgradeStatus::NeedsAttention
+        );
+        assert_eq!(
+            storage::get_current_version(&env),
+            create_test_version(1, 0, 0)
+        );
+        assert!(storage::get_execution_receipt(&env, proposal_id).is_none());
+    });
+}
+
+#[test]
+fn test_default_auth_matrix_is_governance_only() {
+    let (env, contract_id, governance, proposer) = setup_upgrade_storage();
+
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            storage::get_action_roles(&env, Action::ExecuteUpgrade),
+            Vec::from_array(&env, [Role::Governance])
+        );
+        for action in [
+            Action::SetFeature,
+            Action::VetoRecovery,
+            Action::SetAuthMatrix,
+        ] {
+            assert_eq!(auth::authorize(&env, &governance, action), Ok(()));
+            assert_eq!(
+                auth::authorize(&env, &proposer, action),
+                Err(UpgradeError::NotAuthorized)
+            );
+        }
+        assert!(auth::has_role(&env, &governance, Role::Governance));
+        assert!(!auth::has_role(&env, &proposer, Role::Executor));
+    });
+}
+
+#[test]
+fn test_custom_auth_matrix_lets_executor_execute() {
+    let (env, contract_id, governance, proposer) = setup_upgrade_storage();
+    let executor = Address::generate(&env);
+
+    env.as_contract(&contract_id, || {
+        auth::grant_role(&env, &governance, Role::Executor, &executor).unwrap();
+    });
+    env.as_contract(&contract_id, || {
+        auth::set_action_roles(
+            &env,
+            &governance,
+            Action::ExecuteUpgrade,
+            &Vec::from_array(&env, [Role::Governance, Role::Executor]),
+        )
+        .unwrap();
+    });
+
+    let proposal_id =
+        create_approved_proposal(&env, &contract_id, &proposer, create_test_version(1, 1, 0));
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            logic::execute_proposal(&env, &proposer, proposal_id),
+            Err(UpgradeError::NotAuthorized.into())
+        );
+    });
+    env.as_contract(&contract_id, || {
+        logic::execute_proposal(&env, &executor, proposal_id).unwrap();
+        assert_eq!(
+            storage::get_execution_receipt(&env, proposal_id)
+                .unwrap()
+                .executor,
+            executor
+        );
+    });
+
+    // Revoking the role removes the permission again
+    env.as_contract(&contract_id, || {
+        auth::revoke_role(&env, &governance, Role::Executor, &executor).unwrap();
+        assert_eq!(
+            auth::authorize(&env, &executor, Action::ExecuteUpgrade),
+            Err(UpgradeError::NotAuthorized)
+        );
+    });
+}
+
+#[test]
+fn test_protected_auth_entries_cannot_be_weakened() {
+    let (env, contract_id, governance, _) = setup_upgrade_storage();
+    let guardian = Address::generate(&env);
+
+    env.as_contract(&contract_id, || {
+        auth::grant_role(&env, &governance, Role::Guardian, &guardian).unwrap();
+    });
+    for action in [
+        Action::SetAuthMatrix,
+        Action::ManageRoles,
+        Action::VetoRecovery,
+    ] {
+        env.as_contract(&contract_id, || {
+            assert_eq!(
+                auth::set_action_roles(
+                    &env,
+                    &governance,
+                    action,
+                    &Vec::from_array(&env, [Role::Guardian]),
+                ),
+                Err(UpgradeError::ProtectedAction)
+            );
+        });
+    }
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            auth::grant_role(&env, &governance, Role::Governance, &guardian),
+            Err(UpgradeError::ProtectedAction)
+        );
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            auth::set_action_roles(&env, &governance, Action::SetFeature, &Vec::new(&env)),
+            Err(UpgradeError::EmptyRoleSet)
+        );
+    });
+    // A non-governance caller cannot edit the matrix at all
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            auth::set_action_roles(
+                &env,
+                &guardian,
+                Action::SetFeature,
+                &Vec::from_array(&env, [Role::Guardian]),
+            ),
+            Err(UpgradeError::NotAuthorized)
+        );
+        assert_eq!(
+            auth::authorize(&env, &guardian, Action::SetAuthMatrix),
+            Err(UpgradeError::NotAuthorized)
+        );
+    });
+}
+
+#[test]
+fn test_dual_control_separates_governance_and_guardian_changes() {
+    let (env, contract_id, governance, _) = setup_upgrade_storage();
+    let new_governance = Address::generate(&env);
+    let guardian = Address::generate(&env);
+    let window = 1_000;
+    let (recovery_id, big, mid, _) = setup_recovery(&env, &contract_id, &new_governance);
+    for voter in [&big, &mid] {
+        env.as_contract(&contract_id, || {
+            logic::vote_recovery(&env, voter, recovery_id).unwrap();
+        });
+    }
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            storage::get_dual_control_window(&env),
+            storage::DEFAULT_DUAL_CONTROL_WINDOW
+        );
+        assert_eq!(
+            logic::set_dual_control_window(&env, &guardian, window),
+            Err(UpgradeError::NotAuthorized.into())
+        );
+    });
+    env.as_contract(&contract_id, || {
+        logic::set_dual_control_window(&env, &governance, window).unwrap();
+    });
+
+    // Guardian first: a governance change inside the window is refused...
+    let timelock_end = 1_000 + logic::RECOVERY_TIMELOCK_SECONDS;
+    env.ledger().with_mut(|l| l.timestamp = timelock_end - 10);
+    env.as_contract(&contract_id, || {
+        auth::grant_role(&env, &governance, Role::Guardian, &guardian).unwrap();
+        assert_eq!(
+            auth::enforce_dual_control(&env, &governance, ControlChange::Governance, false),
+            Err(UpgradeError::DualControlCooldown)
+        );
+    });
+    let rejections = env
+        .events()
+        .all()
+        .iter()
+        .filter(|(_, topics, _)| {
+            *topics
+                == (
+                    symbol_short!("upgrade"),
+                    Symbol::new(&env, "dual_control_rejected"),
+                )
+                    .into_val(&env)
+        })
+        .count();
+    assert!(rejections >= 1);
+
+    // ...unless it comes through the voter supermajority escape hatch
+    env.ledger().with_mut(|l| l.timestamp = timelock_end);
+    env.as_contract(&contract_id, || {
+        logic::execute_recovery(&env, &big, recovery_id).unwrap();
+        assert_eq!(storage::get_governance_address(&env), new_governance);
+        assert_eq!(
+            storage::get_last_control_change(&env, ControlChange::Governance),
+            Some(timelock_end)
+        );
+    });
+
+    // Governance first: the guardian cannot change inside the window
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            auth::revoke_role(&env, &new_governance, Role::Guardian, &guardian),
+            Err(UpgradeError::DualControlCooldown)
+        );
+        assert!(auth::has_role(&env, &guardian, Role::Guardian));
+    });
+
+    // Outside the window either order goes through
+    env.ledger()
+        .with_mut(|l| l.timestamp = timelock_end + window);
+    env.as_contract(&contract_id, || {
+        auth::revoke_role(&env, &new_governance, Role::Guardian, &guardian).unwrap();
+        assert!(!auth::has_role(&env, &guardian, Role::Guardian));
+    });
+    env.ledger()
+        .with_mut(|l| l.timestamp = timelock_end + 2 * window);
+    env.as_contract(&contract_id, || {
+        auth::enforce_dual_control(&env, &new_governance, ControlChange::Governance, false)
+            .unwrap();
+    });
+}
+
+#[test]
+fn test_delayed_voting_power_grant_ignored_by_earlier_proposals() {
+    let (env, contract_id, governance, proposer) = setup_upgrade_storage();
+    let newcomer = Address::generate(&env);
+    env.ledger().with_mut(|l| l.timestamp = 1_000);
+
+    env.as_contract(&contract_id, || {
+        logic::set_governance_config(
+            &env,
+            &governance,
+            &GovernanceConfig {
+                quorum_bps: 0,
+                approval_threshold_bps: SIMPLE_MAJORITY_BPS,
+                quorum_counts_abstain: false,
+                early_finalize: false,
+                activation_delay: 100,
+                live_vote_export: false,
+                slash_destination: None,
+                revert_window: 0,
+                archive_sink: None,
+            },
+        )
+        .unwrap();
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            logic::grant_voting_power(&env, &governance, &newcomer, 4),
+            Ok(1_100)
+        );
+        assert_eq!(storage::get_voting_power(&env, &newcomer), 0);
+        assert_eq!(storage::get_total_voting_power(&env), 0);
+        assert_eq!(
+            storage::get_voters(&env),
+            Vec::from_array(
+                &env,
+                [VoterEntry {
+                    address: newcomer.clone(),
+                    power: 0,
+                    pending_power: 4,
+                    active_from: 1_100,
+                }]
+            )
+        );
+    });
+
+    // Created while the grant is still pending
+    env.ledger().with_mut(|l| l.timestamp = 1_050);
+    let early_id = env.as_contract(&contract_id, || {
+        logic::propose_upgrade(
+            &env,
+            &proposer,
+            &Address::generate(&env),
+            &create_test_version(1, 1, 0),
+            String::from_str(&env, "early"),
+        )
+        .unwrap()
+    });
+
+    env.ledger().with_mut(|l| l.timestamp = 1_200);
+    let later_id = env.as_contract(&contract_id, || {
+        assert_eq!(storage::get_voting_power(&env, &newcomer), 4);
+        assert_eq!(storage::get_voters(&env).get(0).unwrap().pending_power, 0);
+        logic::propose_upgrade(
+            &env,
+            &proposer,
+            &Address::generate(&env),
+            &create_test_version(1, 2, 0),
+            String::from_str(&env, "later"),
+        )
+        .unwrap()
+    });
+
+    env.as_contract(&contract_id, || {
+        logic::vote_on_proposal(&env, &newcomer, early_id, true).unwrap();
+    });
+    env.as_contract(&contract_id, || {
+        logic::vote_on_proposal(&env, &newcomer, later_id, true).unwrap();
+        assert_eq!(
+            storage::get_upgrade_proposal(&env, early_id)
+                .unwrap()
+                .votes_for,
+            0
+        );
+        assert_eq!(
+            storage::get_upgrade_proposal(&env, later_id)
+                .unwrap()
+                .votes_for,
+            4
+        );
+    });
+}
+
+#[test]
+fn test_voting_power_reduction_applies_immediately() {
+    let (env, contract_id, governance, _) = setup_upgrade_storage();
+    let member = Address::generate(&env);
+    env.ledger().with_mut(|l| l.timestamp = 1_000);
+
+    env.as_contract(&contract_id, || {
+        storage::set_voting_power(&env, &member, 5);
+        let mut config = storage::get_governance_config(&env);
+        config.activation_delay = 100;
+        storage::set_governance_config(&env, &config);
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            logic::grant_voting_power(&env, &governance, &member, 2),
+            Ok(1_000)
+        );
+        assert_eq!(storage::get_voting_power(&env, &member), 2);
+        assert_eq!(storage::get_total_voting_power(&env), 2);
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            logic::grant_voting_power(&env, &member, &member, 9),
+            Err(UpgradeError::NotAuthorized.into())
+        );
+    });
+}
+
+/// Create a pending proposal over ten units of weight and cast the first
+/// three votes: 2 for, 1 against and 1 abstain.
+fn setup_vote_ledger(env: &Env, contract_id: &Address, proposer: &Address) -> (u64, Address) {
+    let voters = [
+        (Address::generate(env), 2, VoteChoice::For),
+        (Address::generate(env), 1, VoteChoice::Against),
+        (Address::generate(env), 1, VoteChoice::Abstain),
+    ];
+    let closer = Address::generate(env);
+    let proposal_id = env.as_contract(contract_id, || {
+        for (voter, power, _) in voters.iter() {
+            storage::set_voting_power(env, voter, *power);
+        }
+        storage::set_voting_power(env, &closer, 4);
+        let proposal_id = logic::propose_upgrade(
+            env,
+            proposer,
+            &Address::generate(env),
+            &create_test_version(1, 1, 0),
+            String::from_str(env, "ledger"),
+        )
+        .unwrap();
+        let mut proposal = storage::get_upgrade_proposal(env, proposal_id).unwrap();
+        proposal.total_voting_power_snapshot = 10;
+        storage::store_upgrade_proposal(env, &proposal);
+        proposal_id
+    });
+    for (voter, _, choice) in voters.iter() {
+        env.as_contract(contract_id, || {
+            logic::cast_vote(env, voter, proposal_id, *choice).unwrap();
+        });
+    }
+    (proposal_id, closer)
+}
+
+#[test]
+fn test_export_votes_after_finalization() {
+    let (env, contract_id, _, proposer) = setup_upgrade_storage();
+    let (proposal_id, closer) = setup_vote_ledger(&env, &contract_id, &proposer);
+
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            logic::export_votes(&env, proposal_id, 0, 10),
+            Err(ProposalError::ProposalNotFinalized.into())
+        );
+        assert_eq!(
+            logic::get_tally_checkpoint(&env, proposal_id),
+            Err(ProposalError::ProposalNotFinalized.into())
+        );
+    });
+    env.as_contract(&contract_id, || {
+        logic::cast_vote(&env, &closer, proposal_id, VoteChoice::For).unwrap();
+    });
+
+    env.as_contract(&contract_id, || {
+        let checkpoint = logic::get_tally_checkpoint(&env, proposal_id).unwrap();
+        assert_eq!(
+            checkpoint,
+            TallyCheckpoint {
+                proposal_id,
+                status: UpgradeStatus::Approved,
+                votes_for: 6,
+                votes_against: 1,
+                votes_abstain: 1,
+                normalized: NormalizedTally {
+                    for_bps: 6_000,
+                    against_bps: 1_000,
+                    abstain_bps: 1_000,
+                    zero_total: false,
+                },
+                receipt_count: 4,
+            }
+        );
+
+        // Two pages reassemble the whole ledger in cast order
+        let first = logic::export_votes(&env, proposal_id, 0, 2).unwrap();
+        let second = logic::export_votes(&env, proposal_id, 2, 2).unwrap();
+        assert_eq!(first.len(), 2);
+        assert_eq!(second.len(), 2);
+        assert_eq!(second.get(1).unwrap().voter, closer);
+
+        // Recompute the tally independently from the receipts
+        let (mut votes_for, mut votes_against, mut votes_abstain) = (0u64, 0u64, 0u64);
+        for receipt in first.iter().chain(second.iter()) {
+            match receipt.choice {
+                VoteChoice::For => votes_for += receipt.weight,
+                VoteChoice::Against => votes_against += receipt.weight,
+                VoteChoice::Abstain => votes_abstain += receipt.weight,
+            }
+        }
+        assert_eq!(
+            (votes_for, votes_against, votes_abstain),
+            (
+                checkpoint.votes_for,
+                checkpoint.votes_against,
+                checkpoint.votes_abstain
+            )
+        );
+
+        // Pagination bounds
+        assert_eq!(
+            logic::export_votes(&env, proposal_id, 4, 10).unwrap().len(),
+            0
+        );
+        assert_eq!(
+            logic::export_votes(&env, proposal_id, 3, 10).unwrap().len(),
+            1
+        );
+        assert_eq!(
+            logic::export_votes(&env, proposal_id, 0, 0).unwrap().len(),
+            0
+        );
+        assert_eq!(
+            logic::export_votes(&env, proposal_id, u32::MAX, u32::MAX)
+                .unwrap()
+                .len(),
+            0
+        );
+    });
+}
+
+#[test]
+fn test_vote_and_voter_cursors_invalidate_when_lists_shift() {
+    let (env, contract_id, _, proposer) = setup_upgrade_storage();
+    let (proposal_id, closer) = setup_vote_ledger(&env, &contract_id, &proposer);
+    env.as_contract(&contract_id, || {
+        logic::cast_vote(&env, &closer, proposal_id, VoteChoice::For).unwrap();
+    });
+
+    env.as_contract(&contract_id, || {
+        // Receipts page in cast order and the cursor ends with the ledger
+        let first = logic::export_votes_after(&env, proposal_id, None, 3).unwrap();
+        assert_eq!(first.receipts.len(), 3);
+        let second =
+            logic::export_votes_after(&env, proposal_id, Some(first.next.clone()), 3).unwrap();
+        assert_eq!(second.receipts.len(), 1);
+        assert_eq!(second.receipts.get(0).unwrap().voter, closer);
+        assert!(second.complete);
+
+        // Resetting any proposal's votes invalidates receipt cursors
+        let mut other = stored_proposal(&env, 99, UpgradeStatus::Pending);
+        storage::store_upgrade_proposal(&env, &other);
+        storage::clear_votes(&env, &mut other);
+        assert_eq!(
+            logic::export_votes_after(&env, proposal_id, Some(first.next.clone()), 3),
+            Err(ProposalError::CursorInvalidated.into())
+        );
+        assert_eq!(
+            logic::export_votes(&env, proposal_id, 3, 3).unwrap(),
+            second.receipts
+        );
+
+        // Power changes keep voter cursors; a new voter shifts the registry
+        let voters = logic::get_voters_after(&env, None, 2).unwrap();
+        assert_eq!(voters.voters.len(), 2);
+        storage::set_voting_power(&env, &closer, 5);
+        let rest = logic::get_voters_after(&env, Some(voters.next.clone()), 10).unwrap();
+        assert_eq!(rest.voters.len(), 2);
+        assert!(rest.complete);
+
+        storage::set_voting_power(&env, &Address::generate(&env), 1);
+        assert_eq!(
+            logic::get_voters_after(&env, Some(voters.next.clone()), 10),
+            Err(ProposalError::CursorInvalidated.into())
+        );
+        assert_eq!(
+            logic::get_voters_after(&env, None, 10)
+                .unwrap()
+                .voters
+                .len(),
+            5
+        );
+    });
+}
+
+#[test]
+fn test_export_votes_live_when_configured() {
+    let (env, contract_id, _, proposer) = setup_upgrade_storage();
+    let (proposal_id, _) = setup_vote_ledger(&env, &contract_id, &proposer);
+
+    env.as_contract(&contract_id, || {
+        let mut config = storage::get_governance_config(&env);
+        config.live_vote_export = true;
+        storage::set_governance_config(&env, &config);
+
+        assert_eq!(
+            logic::export_votes(&env, proposal_id, 0, 10).unwrap().len(),
+            3
+        );
+        let checkpoint = logic::get_tally_checkpoint(&env, proposal_id).unwrap();
+        assert_eq!(checkpoint.status, UpgradeStatus::Pending);
+        assert_eq!(checkpoint.receipt_count, 3);
+    });
+}
+
+#[test]
+fn test_export_votes_for_archived_proposal_fails() {
+    let (env, contract_id, _, proposer) = setup_upgrade_storage();
+    let (proposal_id, closer) = setup_vote_ledger(&env, &contract_id, &proposer);
+
+    env.as_contract(&contract_id, || {
+        logic::cast_vote(&env, &closer, proposal_id, VoteChoice::For).unwrap();
+    });
+    env.as_contract(&contract_id, || {
+        let mut proposals = storage::UPGRADE_PROPOSALS.load(&env).unwrap();
+        proposals.remove(proposal_id);
+        storage::UPGRADE_PROPOSALS.store(&env, &proposals);
+
+        assert_eq!(
+            logic::export_votes(&env, proposal_id, 0, 10),
+            Err(UpgradeError::ProposalDataUnavailable.into())
+        );
+        assert_eq!(
+            logic::get_tally_checkpoint(&env, proposal_id),
+            Err(UpgradeError::ProposalDataUnavailable.into())
+        );
+    });
+}
+
+#[test]
+fn test_claim_voting_power_with_merkle_proof() {
+    let (env, contract_id, governance, _) = setup_upgrade_storage();
+    let members = [
+        (Address::generate(&env), 5u32),
+        (Address::generate(&env), 3),
+        (Address::generate(&env), 2),
+        (Address::generate(&env), 1),
+    ];
+    let leaves = members
+        .clone()
+        .map(|(member, power)| merkle::hash_leaf(&env, &member, power));
+    let left = merkle::hash_pair(&env, &leaves[0], &leaves[1]);
+    let right = merkle::hash_pair(&env, &leaves[2], &leaves[3]);
+    let root = merkle::hash_pair(&env, &left, &right);
+    let (first, first_power) = members[0].clone();
+    let first_proof = Vec::from_array(&env, [leaves[1].clone(), right.clone()]);
+
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            logic::claim_voting_power(&env, &first, first_power, &first_proof),
+            Err(ProposalError::VotingPowerRootMissing.into())
+        );
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            logic::set_voting_power_root(&env, &first, &root),
+            Err(UpgradeError::NotAuthorized.into())
+        );
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            logic::set_voting_power_root(&env, &governance, &root),
+            Ok(0)
+        );
+    });
+
+    // Wrong power and someone else's proof are both rejected
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            logic::claim_voting_power(&env, &first, 50, &first_proof),
+            Err(ProposalError::InvalidMerkleProof.into())
+        );
+    });
+    env.as_contract(&contract_id, || {
+        let third_proof = Vec::from_array(&env, [leaves[3].clone(), left.clone()]);
+        assert_eq!(
+            logic::claim_voting_power(&env, &first, first_power, &third_proof),
+            Err(ProposalError::InvalidMerkleProof.into())
+        );
+    });
+
+    env.as_contract(&contract_id, || {
+        logic::claim_voting_power(&env, &first, first_power, &first_proof).unwrap();
+        assert_eq!(storage::get_voting_power(&env, &first), 5);
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            logic::claim_voting_power(&env, &first, first_power, &first_proof),
+            Err(ProposalError::VotingPowerClaimed.into())
+        );
+    });
+}
+
+#[test]
+fn test_voting_power_root_rotation() {
+    let (env, contract_id, governance, _) = setup_upgrade_storage();
+    let (claimed, unclaimed, newcomer) = (
+        Address::generate(&env),
+        Address::generate(&env),
+        Address::generate(&env),
+    );
+
+    let claimed_leaf = merkle::hash_leaf(&env, &claimed, 4);
+    let unclaimed_leaf = merkle::hash_leaf(&env, &unclaimed, 6);
+    let old_root = merkle::hash_pair(&env, &claimed_leaf, &unclaimed_leaf);
+    env.as_contract(&contract_id, || {
+        logic::set_voting_power_root(&env, &governance, &old_root).unwrap();
+    });
+    env.as_contract(&contract_id, || {
+        let proof = Vec::from_array(&env, [unclaimed_leaf.clone()]);
+        logic::claim_voting_power(&env, &claimed, 4, &proof).unwrap();
+    });
+
+    let newcomer_leaf = merkle::hash_leaf(&env, &newcomer, 2);
+    let rotated_unclaimed_leaf = merkle::hash_leaf(&env, &unclaimed, 1);
+    let new_root = merkle::hash_pair(&env, &newcomer_leaf, &rotated_unclaimed_leaf);
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            logic::set_voting_power_root(&env, &governance, &new_root),
+            Ok(1)
+        );
+    });
+
+    // The unclaimed leaf of the old root is gone; claimed power stays
+    env.as_contract(&contract_id, || {
+        let proof = Vec::from_array(&env, [claimed_leaf.clone()]);
+        assert_eq!(
+            logic::claim_voting_power(&env, &unclaimed, 6, &proof),
+            Err(ProposalError::InvalidMerkleProof.into())
+        );
+        assert_eq!(storage::get_voting_power(&env, &claimed), 4);
+    });
+    env.as_contract(&contract_id, || {
+        let proof = Vec::from_array(&env, [rotated_unclaimed_leaf.clone()]);
+        logic::claim_voting_power(&env, &newcomer, 2, &proof).unwrap();
+    });
+    env.as_contract(&contract_id, || {
+        let proof = Vec::from_array(&env, [newcomer_leaf.clone()]);
+        logic::claim_voting_power(&env, &unclaimed, 1, &proof).unwrap();
+        assert_eq!(storage::get_voting_power(&env, &newcomer), 2);
+        assert_eq!(storage::get_voting_power(&env, &unclaimed), 1);
+        assert_eq!(storage::get_total_voting_power(&env), 7);
+    });
+}
+
+const ALL_UPGRADE_STATUSES: [UpgradeStatus; 11] = [
+    UpgradeStatus::Pending,
+    UpgradeStatus::Approved,
+    UpgradeStatus::Executed,
+    UpgradeStatus::Rejected,
+    UpgradeStatus::Cancelled,
+    UpgradeStatus::AwaitingProxy,
+    UpgradeStatus::NeedsAttention,
+    UpgradeStatus::Expired,
+    UpgradeStatus::Failed,
+    UpgradeStatus::Reverted,
+    UpgradeStatus::Vetoed,
+];
+
+/// The legal status graph, spelled out independently of `can_transition_to`
+fn is_legal_transition(from: UpgradeStatus, to: UpgradeStatus) -> bool {
+    use UpgradeStatus::*;
+    let legal: &[(UpgradeStatus, UpgradeStatus)] = &[
+        (Pending, Approved),
+        (Pending, Rejected),
+        (Pending, Cancelled),
+        (Pending, Expired),
+        (Pending, Vetoed),
+        (Approved, Executed),
+        (Approved, Cancelled),
+        (Approved, Expired),
+        (Approved, Failed),
+        (Approved, AwaitingProxy),
+        (Approved, Vetoed),
+        (AwaitingProxy, Executed),
+        (AwaitingProxy, NeedsAttention),
+        (NeedsAttention, Cancelled),
+        (NeedsAttention, Failed),
+        (Executed, Reverted),
+    ];
+    legal.contains(&(from, to))
+}
+
+#[test]
+fn test_transition_matrix() {
+    let (env, contract_id, _, proposer) = setup_upgrade_storage();
+    let proposal_id = env.as_contract(&contract_id, || {
+        logic::propose_upgrade(
+            &env,
+            &proposer,
+            &Address::generate(&env),
+            &create_test_version(1, 1, 0),
+            String::from_str(&env, "matrix"),
+        )
+        .unwrap()
+    });
+
+    for from in ALL_UPGRADE_STATUSES {
+        for to in ALL_UPGRADE_STATUSES {
+            env.as_contract(&contract_id, || {
+                let mut proposal = storage::get_upgrade_proposal(&env, proposal_id).unwrap();
+                proposal.status = from;
+                storage::store_upgrade_proposal(&env, &proposal);
+
+                let result = logic::transition(&env, proposal_id, to, symbol_short!("test"));
+                let stored = storage::get_upgrade_proposal(&env, proposal_id).unwrap();
+                if is_legal_transition(from, to) {
+                    assert_eq!(result.unwrap().status, to, "{:?} -> {:?}", from, to);
+                    assert_eq!(stored.status, to);
+                    assert!(storage::get_status_index(&env, to).contains(proposal_id));
+                } else {
+                    assert_eq!(
+                        result.err(),
+                        Some(ProposalError::IllegalTransition.into()),
+                        "{:?} -> {:?}",
+                        from,
+                        to
+                    );
+                    assert_eq!(stored.status, from);
+                }
+                assert_eq!(from.can_transition_to(to), is_legal_transition(from, to));
+            });
+        }
+    }
+}
+
+#[test]
+fn test_terminal_statuses_are_absorbing() {
+    use UpgradeStatus::*;
+    for from in [Rejected, Cancelled, Expired, Failed, Reverted, Vetoed] {
+        for to in ALL_UPGRADE_STATUSES {
+            assert!(!from.can_transition_to(to), "{:?} -> {:?}", from, to);
+        }
+    }
+    // An executed upgrade can only be instantly reverted
+    for to in ALL_UPGRADE_STATUSES {
+        assert_eq!(Executed.can_transition_to(to), to == Reverted);
+    }
+}
+
+#[test]
+fn test_transition_emits_status_change() {
+    let (env, contract_id, _, proposer) = setup_upgrade_storage();
+    let proposal_id =
+        create_approved_proposal(&env, &contract_id, &proposer, create_test_version(1, 1, 0));
+
+    env.as_contract(&contract_id, || {
+        logic::transition(
+            &env,
+            proposal_id,
+            UpgradeStatus::Failed,
+            symbol_short!("abandon"),
+        )
+        .unwrap();
+
+        let (_, topics, data) = env.events().all().last().unwrap();
+        assert_eq!(
+            topics,
+            (
+                symbol_short!("upgrade"),
+                Symbol::new(&env, "status_changed")
+            )
+                .into_val(&env)
+        );
+        let change: StatusChange = data.into_val(&env);
+        assert_eq!(
+            change,
+            StatusChange {
+                proposal_id,
+                from: UpgradeStatus::Approved,
+                to: UpgradeStatus::Failed,
+                reason: symbol_short!("abandon"),
+            }
+        );
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            logic::transition(
+                &env,
+                proposal_id + 100,
+                UpgradeStatus::Approved,
+                symbol_short!("test")
+            )
+            .err(),
+            Some(UpgradeError::ProposalNotFound.into())
+        );
+    });
+}
+
+fn propose_version(env: &Env, contract_id: &Address, proposer: &Address, version: Version) -> u64 {
+    env.as_contract(contract_id, || {
+        logic::propose_upgrade(
+            env,
+            proposer,
+            &Address::generate(env),
+            &version,
+            String::from_str(env, "by version"),
+        )
+        .unwrap()
+    })
+}
+
+#[test]
+fn test_get_proposals_by_version_spans_minors() {
+    let (env, contract_id, _, proposer) = setup_upgrade_storage();
+    let v2_0 = propose_version(&env, &contract_id, &proposer, create_test_version(2, 0, 0));
+    let v2_3 = propose_version(&env, &contract_id, &proposer, create_test_version(2, 3, 0));
+    let v2_1 = propose_version(&env, &contract_id, &proposer, create_test_version(2, 1, 4));
+    let v3_0 = propose_version(&env, &contract_id, &proposer, create_test_version(3, 0, 0));
+    let v2_1_again = propose_version(&env, &contract_id, &proposer, create_test_version(2, 1, 5));
+
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            logic::get_proposals_by_version(&env, 2, None, 0, 10),
+            Vec::from_array(&env, [v2_0, v2_1, v2_1_again, v2_3])
+        );
+        assert_eq!(
+            logic::get_proposals_by_version(&env, 2, Some(1), 0, 10),
+            Vec::from_array(&env, [v2_1, v2_1_again])
+        );
+        assert_eq!(
+            logic::get_proposals_by_version(&env, 3, None, 0, 10),
+            Vec::from_array(&env, [v3_0])
+        );
+
+        // Pagination bounds
+        assert_eq!(
+            logic::get_proposals_by_version(&env, 2, None, 1, 2),
+            Vec::from_array(&env, [v2_1, v2_1_again])
+        );
+        assert!(logic::get_proposals_by_version(&env, 2, None, 4, 10).is_empty());
+        assert!(logic::get_proposals_by_version(&env, 2, None, u32::MAX, u32::MAX).is_empty());
+        assert!(logic::get_proposals_by_version(&env, 4, None, 0, 10).is_empty());
+        assert!(logic::get_proposals_by_version(&env, 2, Some(2), 0, 10).is_empty());
+    });
+}
+
+#[test]
+fn test_amended_proposal_moves_version_bucket() {
+    let (env, contract_id, _, proposer) = setup_upgrade_storage();
+    let amended = propose_version(&env, &contract_id, &proposer, create_test_version(2, 1, 0));
+    let other = propose_version(&env, &contract_id, &proposer, create_test_version(3, 0, 0));
+
+    env.as_contract(&contract_id, || {
+        logic::amend_proposal(
+            &env,
+            &proposer,
+            amended,
+            &Address::generate(&env),
+            &create_test_version(3, 2, 0),
+            String::from_str(&env, "retargeted"),
+        )
+        .unwrap();
+
+        assert!(logic::get_proposals_by_version(&env, 2, None, 0, 10).is_empty());
+        assert_eq!(
+            logic::get_proposals_by_version(&env, 3, None, 0, 10),
+            Vec::from_array(&env, [other, amended])
+        );
+        assert_eq!(
+            storage::get_upgrade_proposal(&env, amended)
+                .unwrap()
+                .version,
+            create_test_version(3, 2, 0)
+        );
+    });
+
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            logic::amend_proposal(
+                &env,
+                &Address::generate(&env),
+                amended,
+                &Address::generate(&env),
+                &create_test_version(4, 0, 0),
+                String::from_str(&env, "not mine"),
+            ),
+            Err(ProposalError::NotProposer.into())
+        );
+    });
+}
+
+/// Propose 2.0.0 over ten units of weight and have two voters back it
+fn setup_voted_amendment(
+    env: &Env,
+    contract_id: &Address,
+    proposer: &Address,
+) -> (u64, Address, Address) {
+    let proposal_id = propose_version(env, contract_id, proposer, create_test_version(2, 0, 0));
+    let supporter = Address::generate(env);
+    let opponent = Address::generate(env);
+    env.as_contract(contract_id, || {
+        let mut proposal = storage::get_upgrade_proposal(env, proposal_id).unwrap();
+        proposal.total_voting_power_snapshot = 10;
+        storage::store_upgrade_proposal(env, &proposal);
+        set_snapshot_power(env, proposal_id, &supporter, 3);
+        set_snapshot_power(env, proposal_id, &opponent, 2);
+    });
+    for (voter, choice) in [
+        (&supporter, VoteChoice::For),
+        (&opponent, VoteChoice::Against),
+    ] {
+        env.as_contract(contract_id, || {
+            logic::cast_vote(env, voter, proposal_id, choice).unwrap();
+        });
+    }
+    (proposal_id, supporter, opponent)
+}
+
+#[test]
+fn test_metadata_amendment_keeps_votes() {
+    let (env, contract_id, _, proposer) = setup_upgrade_storage();
+    let (proposal_id, _, _) = setup_voted_amendment(&env, &contract_id, &proposer);
+    let target = env.as_contract(&contract_id, || {
+        storage::get_upgrade_proposal(&env, proposal_id)
+            .unwrap()
+            .new_contract_address
+    });
+
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            logic::amend_proposal(
+                &env,
+                &proposer,
+                proposal_id,
+                &target,
+                &create_test_version(2, 0, 0),
+                String::from_str(&env, "typo fixed"),
+            ),
+            Ok(AmendmentKind::Metadata)
+        );
+        let proposal = storage::get_upgrade_proposal(&env, proposal_id).unwrap();
+        assert_eq!(proposal.description, String::from_str(&env, "typo fixed"));
+        assert_eq!((proposal.votes_for, proposal.votes_against), (3, 2));
+        assert_eq!(storage::get_vote_receipts(&env, proposal_id).len(), 2);
+    });
+}
+
+#[test]
+fn test_substantive_amendment_invalidates_votes() {
+    let (env, contract_id, governance, proposer) = setup_upgrade_storage();
+    let (proposal_id, supporter, opponent) = setup_voted_amendment(&env, &contract_id, &proposer);
+
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            logic::amend_proposal(
+                &env,
+                &proposer,
+                proposal_id,
+                &Address::generate(&env),
+                &create_test_version(2, 1, 0),
+                String::from_str(&env, "retargeted"),
+            ),
+            Ok(AmendmentKind::Substantive)
+        );
+    });
+    let topics: Vec<soroban_sdk::Val> = (
+        symbol_short!("upgrade"),
+        Symbol::new(&env, "votes_invalidated"),
+    )
+        .into_val(&env);
+    let (_, _, data) = env
+        .events()
+        .all()
+        .iter()
+        .find(|(_, event_topics, _)| *event_topics == topics)
+        .unwrap();
+    let invalidated: VotesInvalidated = data.into_val(&env);
+    assert_eq!(
+        invalidated,
+        VotesInvalidated {
+            proposal_id,
+            count: 2,
+            voters: Vec::from_array(&env, [supporter.clone(), opponent.clone()]),
+        }
+    );
+    env.as_contract(&contract_id, || {
+        let proposal = storage::get_upgrade_proposal(&env, proposal_id).unwrap();
+        assert_eq!(
+            (
+                proposal.votes_for,
+                proposal.votes_against,
+                proposal.votes_abstain
+            ),
+            (0, 0, 0)
+        );
+        assert!(storage::get_vote_receipts(&env, proposal_id).is_empty());
+    });
+
+    // Voters can revote on the amended target
+    env.as_contract(&contract_id, || {
+        logic::cast_vote(&env, &supporter, proposal_id, VoteChoice::For).unwrap();
+        assert_eq!(
+            storage::get_upgrade_proposal(&env, proposal_id)
+                .unwrap()
+                .votes_for,
+            3
+        );
+    });
+
+    // Replacing the migration plan is just as substantive
+    env.as_contract(&contract_id, || {
+        logic::register_migration_plan(
+            &env,
+            &governance,
+            proposal_id,
+            &MigrationPlan {
+                from_version: create_test_version(1, 0, 0),
+                to_version: create_test_version(2, 1, 0),
+                migration_function_selector: symbol_short!("migrate"),
+                estimated_gas: 1_000,
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            storage::get_upgrade_proposal(&env, proposal_id)
+                .unwrap()
+                .votes_for,
+            0
+        );
+        assert!(storage::get_vote_receipts(&env, proposal_id).is_empty());
+    });
+}
+
+#[test]
+fn test_archived_proposal_leaves_version_index() {
+    let (env, contract_id, governance, proposer) = setup_upgrade_storage();
+    let archived = propose_version(&env, &contract_id, &proposer, create_test_version(2, 1, 0));
+    let kept = propose_version(&env, &contract_id, &proposer, create_test_version(2, 1, 1));
+
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            logic::archive_proposal(&env, &governance, archived),
+            Err(ProposalError::ProposalNotFinalized.into())
+        );
+    });
+    env.as_contract(&contract_id, || {
+        logic::transition(
+            &env,
+            archived,
+            UpgradeStatus::Rejected,
+            symbol_short!("test"),
+        )
+        .unwrap();
+        logic::archive_proposal(&env, &governance, archived).unwrap();
+
+        assert_eq!(
+            logic::get_proposals_by_version(&env, 2, None, 0, 10),
+            Vec::from_array(&env, [kept])
+        );
+        assert_eq!(
+            logic::get_tally_checkpoint(&env, archived),
+            Err(UpgradeError::ProposalDataUnavailable.into())
+        );
+    });
+}
+
+/// Reject fresh proposals for each version and point archival at `sink`
+fn setup_archive_sink(
+    env: &Env,
+    contract_id: &Address,
+    proposer: &Address,
+    sink: Option<Address>,
+    count: u32,
+) -> Vec<u64> {
+    let mut ids = Vec::new(env);
+    for patch in 0..count {
+        let id = propose_version(env, contract_id, proposer, create_test_version(2, 1, patch));
+        env.as_contract(contract_id, || {
+            logic::transition(env, id, UpgradeStatus::Rejected, symbol_short!("test")).unwrap();
+        });
+        ids.push_back(id);
+    }
+    env.as_contract(contract_id, || {
+        let mut config = storage::get_governance_config(env);
+        config.archive_sink = sink;
+        storage::set_governance_config(env, &config);
+    });
+    ids
+}
+
+#[test]
+fn test_unacknowledged_archival_is_queued_and_retried() {
+    let (env, contract_id, governance, proposer) = setup_upgrade_storage();
+    let failing = env.register_contract(None, FailingSink);
+    let ids = setup_archive_sink(&env, &contract_id, &proposer, Some(failing), 1);
+    let proposal_id = ids.get(0).unwrap();
+
+    // The sink traps, so the proposal is kept and queued
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            logic::archive_proposal(&env, &governance, proposal_id),
+            Ok(false)
+        );
+        assert!(storage::get_upgrade_proposal(&env, proposal_id).is_some());
+        assert_eq!(
+            storage::get_archive_queue(&env),
+            Vec::from_array(&env, [proposal_id])
+        );
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(logic::retry_archival(&env, 10), 0);
+        assert_eq!(storage::get_archive_queue(&env).len(), 1);
+    });
+
+    let recording = env.register_contract(None, RecordingSink);
+    env.as_contract(&contract_id, || {
+        let mut config = storage::get_governance_config(&env);
+        config.archive_sink = Some(recording.clone());
+        storage::set_governance_config(&env, &config);
+    });
+    let summary = env.as_contract(&contract_id, || {
+        ProposalSummary::of(&storage::get_upgrade_proposal(&env, proposal_id).unwrap())
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(logic::retry_archival(&env, 10), 1);
+        assert!(storage::get_upgrade_proposal(&env, proposal_id).is_none());
+        assert!(storage::get_archive_queue(&env).is_empty());
+    });
+
+    assert_eq!(
+        RecordingSinkClient::new(&env, &recording).records(),
+        Vec::from_array(&env, [ArchiveRecord::Proposal(summary)])
+    );
+}
+
+#[test]
+fn test_archival_without_sink_is_immediate() {
+    let (env, contract_id, governance, proposer) = setup_upgrade_storage();
+    let ids = setup_archive_sink(&env, &contract_id, &proposer, None, 1);
+    let proposal_id = ids.get(0).unwrap();
+
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            logic::archive_proposal(&env, &governance, proposal_id),
+            Ok(true)
+        );
+        assert!(storage::get_upgrade_proposal(&env, proposal_id).is_none());
+        assert!(storage::get_archive_queue(&env).is_empty());
+    });
+}
+
+#[test]
+fn test_history_is_pruned_only_after_sink_acknowledges() {
+    use crate::events::history::{get_history_record, get_pruned_through};
+    use crate::events::types::HistoryKind;
+
+    let (env, contract_id, governance, proposer) = setup_upgrade_storage();
+    setup_archive_sink(&env, &contract_id, &proposer, None, 3);
+
+    // Without a sink there is nowhere to keep the records
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            logic::prune_history(&env, &governance, HistoryKind::Proposals, 2),
+            Err(ProposalError::NoArchiveSink.into())
+        );
+    });
+
+    let failing = env.register_contract(None, FailingSink);
+    env.as_contract(&contract_id, || {
+        let mut config = storage::get_governance_config(&env);
+        config.archive_sink = Some(failing);
+        storage::set_governance_config(&env, &config);
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            logic::prune_history(&env, &governance, HistoryKind::Proposals, 2),
+            Ok(0)
+        );
+        assert!(get_history_record(&env, HistoryKind::Proposals, 1).is_some());
+    });
+
+    let recording = env.register_contract(None, RecordingSink);
+    env.as_contract(&contract_id, || {
+        let mut config = storage::get_governance_config(&env);
+        config.archive_sink = Some(recording.clone());
+        storage::set_governance_config(&env, &config);
+    });
+    let first = env.as_contract(&contract_id, || {
+        get_history_record(&env, HistoryKind::Proposals, 1).unwrap()
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            logic::prune_history(&env, &governance, HistoryKind::Proposals, 2),
+            Ok(2)
+        );
+        assert_eq!(get_pruned_through(&env, HistoryKind::Proposals), 2);
+        assert!(get_history_record(&env, HistoryKind::Proposals, 1).is_none());
+        assert!(get_history_record(&env, HistoryKind::Proposals, 3).is_some());
+    });
+
+    let records = RecordingSinkClient::new(&env, &recording).records();
+    assert_eq!(records.len(), 2);
+    assert_eq!(records.get(0).unwrap(), ArchiveRecord::History(first));
+}
+
+#[test]
+fn test_footprint_follows_archival_pruning_and_logs() {
+    use crate::emergency::storage::log_emergency_action;
+    use crate::events::types::HistoryKind;
+
+    let (env, contract_id, governance, proposer) = setup_upgrade_storage();
+    let ids = setup_archive_sink(&env, &contract_id, &proposer, None, 3);
+    let footprint = env.as_contract(&contract_id, || storage::get_storage_footprint(&env));
+    assert_eq!(
+        (
+            footprint.proposals_live,
+            footprint.proposals_archived,
+            footprint.history_entries
+        ),
+        (3, 0, 3)
+    );
+
+    // Archival moves a proposal across; pruning drops its history records
+    env.as_contract(&contract_id, || {
+        logic::archive_proposal(&env, &governance, ids.get(0).unwrap()).unwrap();
+    });
+    let recording = env.register_contract(None, RecordingSink);
+    env.as_contract(&contract_id, || {
+        let mut config = storage::get_governance_config(&env);
+        config.archive_sink = Some(recording);
+        storage::set_governance_config(&env, &config);
+    });
+    env.as_contract(&contract_id, || {
+        logic::prune_history(&env, &governance, HistoryKind::Proposals, 2).unwrap();
+    });
+
+    // Replacing a plan does not add one; emergency logs count twice, as a
+    // log entry and a history record
+    let plan = MigrationPlan {
+        from_version: create_test_version(1, 0, 0),
+        to_version: create_test_version(2, 1, 1),
+        migration_function_selector: symbol_short!("migrate"),
+        estimated_gas: 100,
+    };
+    env.as_contract(&contract_id, || {
+        storage::store_migration_plan(&env, ids.get(1).unwrap(), &plan);
+        storage::store_migration_plan(&env, ids.get(1).unwrap(), &plan);
+        log_emergency_action(
+            &env,
+            String::from_str(&env, "Paused"),
+            governance.clone(),
+            String::from_str(&env, "drill"),
+        );
+    });
+
+    env.as_contract(&contract_id, || {
+        let footprint = storage::get_storage_footprint(&env);
+        assert_eq!(footprint.proposals_live, 2);
+        assert_eq!(footprint.proposals_archived, 1);
+        assert_eq!(footprint.plans, 1);
+        assert_eq!(footprint.history_entries, 2);
+        assert_eq!(footprint.log_entries, 1);
+        assert_eq!(footprint.receipts, 0);
+        assert!(walk_invariants(&env, InvariantScope::Footprint, 1).is_empty());
+    });
+
+    // Enough writes publish the footprint for indexers
+    env.as_contract(&contract_id, || {
+        for _ in 0..FOOTPRINT_EVENT_INTERVAL {
+            log_emergency_action(
+                &env,
+                String::from_str(&env, "Paused"),
+                governance.clone(),
+                String::from_str(&env, "drill"),
+            );
+        }
+    });
+    let footprint_action = Symbol::new(&env, "footprint");
+    let published = env.events().all().iter().any(|(_, topics, _)| {
+        let action: Result<Symbol, _> = topics.get(1).unwrap().try_into_val(&env);
+        action == Ok(footprint_action.clone())
+    });
+    assert!(published);
+}
+
+#[test]
+fn test_heartbeat_silence_enters_and_leaves_safe_mode() {
+    let (env, contract_id, governance, proposer) = setup_upgrade_storage();
+    env.ledger().with_mut(|l| l.timestamp = 1_000);
+    env.as_contract(&contract_id, || {
+        crate::proxy::storage::initialize(&env, Address::generate(&env), governance.clone())
+            .unwrap();
+        crate::proxy::storage::set_skip_version_check(&env, true);
+    });
+    env.as_contract(&contract_id, || {
+        logic::set_max_silence(&env, &governance, 100).unwrap();
+    });
+    env.as_contract(&contract_id, || {
+        logic::toggle_emergency_upgrades(&env, &governance, true).unwrap();
+    });
+
+    let voter = Address::generate(&env);
+    let open_id = propose_version(&env, &contract_id, &proposer, create_test_version(1, 1, 0));
+    env.as_contract(&contract_id, || {
+        set_snapshot_power(&env, open_id, &voter, 6);
+        let mut proposal = storage::get_upgrade_proposal(&env, open_id).unwrap();
+        proposal.total_voting_power_snapshot = 10;
+        storage::store_upgrade_proposal(&env, &proposal);
+    });
+
+    // Within the window nothing happens
+    env.ledger().with_mut(|l| l.timestamp = 1_100);
+    env.as_contract(&contract_id, || {
+        assert!(!logic::check_heartbeat(&env));
+    });
+
+    env.ledger().with_mut(|l| l.timestamp = 1_101);
+    env.as_contract(&contract_id, || {
+        assert!(logic::check_heartbeat(&env));
+        assert!(storage::is_safe_mode(&env));
+        assert!(crate::proxy::implementation::is_paused(&env));
+    });
+
+    // Lockdown: no new proposals, emergency upgrades or proxy upgrades
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            logic::propose_upgrade(
+                &env,
+                &proposer,
+                &Address::generate(&env),
+                &create_test_version(1, 2, 0),
+                String::from_str(&env, "blocked"),
+            ),
+            Err(ProposalError::SafeMode.into())
+        );
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            logic::emergency_upgrade(
+                &env,
+                &governance,
+                &Address::generate(&env),
+                &create_test_version(9, 0, 0),
+            ),
+            Err(ProposalError::SafeMode.into())
+        );
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            crate::proxy::implementation::upgrade(&env, &governance, &Address::generate(&env)),
+            UpgradeOutcome::rejected(ProxyError::Paused)
+        );
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            crate::proxy::implementation::schedule_upgrade(
+                &env,
+                &governance,
+                &Address::generate(&env),
+            ),
+            Err(ProxyError::Paused)
+        );
+    });
+
+    // Open proposals can still be voted on and finalized
+    env.as_contract(&contract_id, || {
+        logic::cast_vote(&env, &voter, open_id, VoteChoice::For).unwrap();
+        assert_eq!(
+            storage::get_upgrade_proposal(&env, open_id).unwrap().status,
+            UpgradeStatus::Approved
+        );
+    });
+
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            logic::heartbeat(&env, &proposer),
+            Err(UpgradeError::NotAuthorized.into())
+        );
+    });
+    env.as_contract(&contract_id, || {
+        logic::heartbeat(&env, &governance).unwrap();
+        assert!(!storage::is_safe_mode(&env));
+        assert!(!logic::check_heartbeat(&env));
+        assert_eq!(storage::get_last_heartbeat(&env), 1_101);
+    });
+    propose_version(&env, &contract_id, &proposer, create_test_version(1, 2, 0));
+    let next = Address::generate(&env);
+    env.as_contract(&contract_id, || {
+        crate::proxy::implementation::approve_implementation(&env, &governance, &next).unwrap();
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            crate::proxy::implementation::upgrade(&env, &governance, &next),
+            UpgradeOutcome::Applied
+        );
+    });
+}
+
+#[test]
+fn test_heartbeat_switch_disabled_by_default() {
+    let (env, contract_id, _, _) = setup_upgrade_storage();
+    env.ledger().with_mut(|l| l.timestamp = 10_000_000);
+
+    env.as_contract(&contract_id, || {
+        assert!(!logic::check_heartbeat(&env));
+        assert!(!storage::is_safe_mode(&env));
+    });
+}
+
+/// Preconditions that can be broken independently for the blocker matrix
+#[derive(Clone, Copy)]
+struct BrokenPreconditions {
+    pending: bool,
+    stale_migration_plan: bool,
+    proxy_paused: bool,
+}
+
+#[test]
+fn test_execution_blockers_match_execution_outcome() {
+    for mask in 0u32..8 {
+        let broken = BrokenPreconditions {
+            pending: mask & 1 != 0,
+            stale_migration_plan: mask & 2 != 0,
+            proxy_paused: mask & 4 != 0,
+        };
+        let (env, contract_id, governance, proposer) = setup_upgrade_storage();
+        env.ledger().with_mut(|l| l.timestamp = 1_000);
+        let target = create_test_version(1, 1, 0);
+
+        let proposal_id = if broken.pending {
+            propose_version(&env, &contract_id, &proposer, target.clone())
+        } else {
+            create_approved_proposal(&env, &contract_id, &proposer, target.clone())
+        };
+        let stale_from = create_test_version(0, 9, 0);
+        let plan = MigrationPlan {
+            from_version: if broken.stale_migration_plan {
+                stale_from.clone()
+            } else {
+                create_test_version(1, 0, 0)
+            },
+            to_version: target,
+            migration_function_selector: symbol_short!("migr"),
+            estimated_gas: 0,
+        };
+        env.as_contract(&contract_id, || {
+            logic::register_migration_plan(&env, &governance, proposal_id, &plan).unwrap();
+        });
+        if broken.proxy_paused {
+            setup_delayed_proxy(&env, &contract_id, &governance);
+            env.as_contract(&contract_id, || {
+                logic::set_max_silence(&env, &governance, 10).unwrap();
+            });
+            env.ledger().with_mut(|l| l.timestamp = 2_000);
+            env.as_contract(&contract_id, || {
+                assert!(logic::check_heartbeat(&env));
+            });
+        }
+
+        let mut expected = Vec::new(&env);
+        if broken.pending {
+            expected.push_back(Blocker::NotApproved);
+        }
+        if broken.stale_migration_plan {
+            expected.push_back(Blocker::MigrationPlanMismatch(stale_from));
+        }
+        if broken.proxy_paused {
+            expected.push_back(Blocker::ProxyPaused);
+        }
+
+        env.as_contract(&contract_id, || {
+            assert_eq!(logic::get_execution_blockers(&env, proposal_id), expected);
+        });
+        let outcome = env.as_contract(&contract_id, || {
+            logic::execute_proposal(&env, &governance, proposal_id)
+        });
+        assert_eq!(outcome.is_ok(), expected.is_empty(), "mask {}", mask);
+
+        // The first blocker is the error execution reports
+        let expected_error = match expected.first() {
+            None => None,
+            Some(Blocker::NotApproved) => Some(UpgradeError::ProposalNotApproved.into()),
+            Some(Blocker::MigrationPlanMismatch(_)) => {
+                Some(ProposalError::MigrationPlanMismatch.into())
+            }
+            Some(Blocker::ProxyPaused) => Some(ProposalError::ProxyPaused.into()),
+            Some(other) => panic!("unexpected blocker {:?}", other),
+        };
+        assert_eq!(outcome.err(), expected_error, "mask {}", mask);
+    }
+}
+
+#[test]
+fn test_execution_blockers_for_missing_proposals() {
+    let (env, contract_id, _, proposer) = setup_upgrade_storage();
+    let proposal_id = propose_version(&env, &contract_id, &proposer, create_test_version(1, 1, 0));
+
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            logic::get_execution_blockers(&env, proposal_id + 10),
+            Vec::from_array(&env, [Blocker::ProposalNotFound])
+        );
+
+        // Losing the status index entry leaves only the indices blocker
+        let proposal = storage::get_upgrade_proposal(&env, proposal_id).unwrap();
+        storage::status_index_slot(proposal.status).store(&env, &Vec::new(&env));
+        assert_eq!(
+            logic::get_execution_blockers(&env, proposal_id),
+            Vec::from_array(&env, [Blocker::IndicesIncomplete])
+        );
+
+        let mut proposals = storage::UPGRADE_PROPOSALS.load(&env).unwrap();
+        proposals.remove(proposal_id);
+        storage::UPGRADE_PROPOSALS.store(&env, &proposals);
+        assert_eq!(
+            logic::get_execution_blockers(&env, proposal_id),
+            Vec::from_array(&env, [Blocker::ProposalUnavailable])
+        );
+    });
+}
+
+fn store_emergency_op(env: &Env, id: u64, status: OperationStatus) {
+    let op = MultiSigOperation {
+        id,
+        account_id: 1,
+        op_type: OperationType::EmergencyAction,
+        description: String::from_str(env, "emergency bump"),
+        proposer: Address::generate(env),
+        signatures: Vec::new(env),
+        nonce: 0,
+        created_at: env.ledger().timestamp(),
+        expires_at: env.ledger().timestamp() + 100,
+        status,
+    };
+    multisig_storage::store_operation(env, id, &op);
+}
+
+#[test]
+fn test_version_diff_kind() {
+    let current = create_test_version(1, 2, 3);
+    assert_eq!(
+        current.diff_kind(&create_test_version(1, 2, 3)),
+        BumpKind::NoChange
+    );
+    assert_eq!(
+        current.diff_kind(&create_test_version(1, 2, 4)),
+        BumpKind::Patch
+    );
+    assert_eq!(
+        current.diff_kind(&create_test_version(1, 3, 0)),
+        BumpKind::Minor
+    );
+    assert_eq!(
+        current.diff_kind(&create_test_version(2, 0, 0)),
+        BumpKind::Major
+    );
+    assert_eq!(
+        current.diff_kind(&create_test_version(0, 2, 3)),
+        BumpKind::Major
+    );
+}
+
+#[test]
+fn test_emergency_bump_limits_under_each_setting() {
+    let attempts = [
+        (create_test_version(1, 0, 1), BumpKind::Patch),
+        (create_test_version(1, 1, 0), BumpKind::Minor),
+        (create_test_version(2, 0, 0), BumpKind::Major),
+    ];
+    for max_bump in [None, Some(BumpKind::Minor), Some(BumpKind::Major)] {
+        for (target, bump) in attempts.iter() {
+            let (env, contract_id, governance, _) = setup_upgrade_storage();
+            env.as_contract(&contract_id, || {
+                logic::toggle_emergency_upgrades(&env, &governance, true).unwrap();
+            });
+            if let Some(max_bump) = max_bump {
+                env.as_contract(&contract_id, || {
+                    logic::set_emergency_max_bump(&env, &governance, max_bump).unwrap();
+                });
+            }
+
+            let allowed = *bump <= max_bump.unwrap_or(BumpKind::Patch);
+            let result = env.as_contract(&contract_id, || {
+                logic::emergency_upgrade(&env, &governance, &Address::generate(&env), target)
+            });
+            if allowed {
+                assert_eq!(result, Ok(()), "{:?} under {:?}", bump, max_bump);
+            } else {
+                assert_eq!(
+                    result,
+                    Err(UpgradeError::EmergencyBumpTooLarge.into()),
+                    "{:?} under {:?}",
+                    bump,
+                    max_bump
+                );
+            }
+            env.as_contract(&contract_id, || {
+                let expected = if allowed {
+                    target.clone()
+                } else {
+                    create_test_version(1, 0, 0)
+                };
+                assert_eq!(storage::get_current_version(&env), expected);
+            });
+        }
+    }
+}
+
+#[test]
+fn test_emergency_bump_override_requires_executed_multisig_op() {
+    let (env, contract_id, governance, _) = setup_upgrade_storage();
+    let major = create_test_version(2, 0, 0);
+    env.as_contract(&contract_id, || {
+        logic::toggle_emergency_upgrades(&env, &governance, true).unwrap();
+        store_emergency_op(&env, 1, OperationStatus::Pending);
+        store_emergency_op(&env, 2, OperationStatus::Executed);
+    });
+
+    for op_id in [1, 99] {
+        env.as_contract(&contract_id, || {
+            assert_eq!(
+                logic::emergency_upgrade_with_override(
+                    &env,
+                    &governance,
+                    &Address::generate(&env),
+                    &major,
+                    op_id,
+                ),
+                Err(ProposalError::InvalidEmergencyOverride.into())
+            );
+        });
+    }
+    env.as_contract(&contract_id, || {
+        logic::emergency_upgrade_with_override(
+            &env,
+            &governance,
+            &Address::generate(&env),
+            &major,
+            2,
+        )
+        .unwrap();
+        assert_eq!(storage::get_current_version(&env), major);
+        assert!(storage::is_emergency_override_used(&env, 2));
+    });
+
+    // The same approval cannot be spent twice
+    env.as_contract(&contract_id, || {
+        logic::toggle_emergency_upgrades(&env, &governance, true).unwrap();
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            logic::emergency_upgrade_with_override(
+                &env,
+                &governance,
+                &Address::generate(&env),
+                &create_test_version(3, 0, 0),
+                2,
+            ),
+            Err(ProposalError::InvalidEmergencyOverride.into())
+        );
+    });
+}
+
+/// Register voters with powers 4, 2 and 4 and open one proposal per built-in
+/// track: a patch on fast, a minor on standard and a major on supermajority
+fn setup_track_proposals(
+    env: &Env,
+    contract_id: &Address,
+    proposer: &Address,
+) -> ([u64; 3], [Address; 3]) {
+    let voters = [
+        Address::generate(env),
+        Address::generate(env),
+        Address::generate(env),
+    ];
+    let targets = [
+        (FAST_TRACK_ID, create_test_version(1, 0, 1)),
+        (STANDARD_TRACK_ID, create_test_version(1, 1, 0)),
+        (SUPERMAJORITY_TRACK_ID, create_test_version(2, 0, 0)),
+    ];
+    env.as_contract(contract_id, || {
+        for (voter, power) in voters.iter().zip([4, 2, 4]) {
+            storage::set_voting_power(env, voter, power);
+        }
+    });
+
+    let mut ids = [0u64; 3];
+    for (slot, (track_id, version)) in ids.iter_mut().zip(targets) {
+        *slot = env.as_contract(contract_id, || {
+            let proposal_id = logic::propose_upgrade_on_track(
+                env,
+                proposer,
+                &Address::generate(env),
+                &version,
+                String::from_str(env, "track"),
+                track_id,
+            )
+            .unwrap();
+            let mut proposal = storage::get_upgrade_proposal(env, proposal_id).unwrap();
+            proposal.total_voting_power_snapshot = 10;
+            storage::store_upgrade_proposal(env, &proposal);
+            proposal_id
+        });
+    }
+    (ids, voters)
+}
+
+#[test]
+fn test_governance_tracks_govern_each_lifecycle() {
+    let (env, contract_id, governance, proposer) = setup_upgrade_storage();
+    env.ledger().with_mut(|l| l.timestamp = 1_000);
+    let ([fast, standard, supermajority], [a, b, c]) =
+        setup_track_proposals(&env, &contract_id, &proposer);
+
+    // Each proposal snapshots its own track's rules
+    env.as_contract(&contract_id, || {
+        for (proposal_id, track_id, ends_at, timelock) in [
+            (fast, FAST_TRACK_ID, 1_000 + 86_400, 0),
+            (standard, STANDARD_TRACK_ID, 1_000 + 259_200, 86_400),
+            (
+                supermajority,
+                SUPERMAJORITY_TRACK_ID,
+                1_000 + 604_800,
+                172_800,
+            ),
+        ] {
+            let params = storage::get_proposal_params(&env, proposal_id);
+            assert_eq!(params.track_id, track_id);
+            assert_eq!(params.voting_ends_at, ends_at);
+            assert_eq!(params.timelock, timelock);
+            assert_eq!(
+                logic::get_proposal_detail(&env, proposal_id, None)
+                    .unwrap()
+                    .track_id,
+                track_id
+            );
+        }
+    });
+    let selected = env.events().all().iter().any(|(_, _, data)| {
+        let data: Result<(u64, u32, Symbol), _> = data.try_into_val(&env);
+        data == Ok((
+            supermajority,
+            SUPERMAJORITY_TRACK_ID,
+            SUPERMAJORITY_TRACK_NAME,
+        ))
+    });
+    assert!(selected);
+
+    // Six of ten for passes a simple majority but not the two-thirds track
+    for proposal_id in [fast, standard, supermajority] {
+        for voter in [&a, &b] {
+            env.as_contract(&contract_id, || {
+                logic::cast_vote(&env, voter, proposal_id, VoteChoice::For).unwrap();
+            });
+        }
+    }
+    env.as_contract(&contract_id, || {
+        let status = |id| storage::get_upgrade_proposal(&env, id).unwrap().status;
+        assert_eq!(status(fast), UpgradeStatus::Approved);
+        assert_eq!(status(standard), UpgradeStatus::Approved);
+        assert_eq!(status(supermajority), UpgradeStatus::Pending);
+    });
+    // Four against is already a blocking minority there
+    env.as_contract(&contract_id, || {
+        logic::cast_vote(&env, &c, supermajority, VoteChoice::Against).unwrap();
+        assert_eq!(
+            storage::get_upgrade_proposal(&env, supermajority)
+                .unwrap()
+                .status,
+            UpgradeStatus::Rejected
+        );
+    });
+
+    // The fast lane executes at once; the standard lane waits out its timelock
+    env.as_contract(&contract_id, || {
+        logic::execute_proposal(&env, &governance, fast).unwrap();
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            logic::execute_proposal(&env, &governance, standard),
+            Err(ProposalError::TimelockActive.into())
+        );
+        assert_eq!(
+            logic::get_execution_blockers(&env, standard),
+            Vec::from_array(&env, [Blocker::TimelockActive(1_000 + 86_400)])
+        );
+    });
+    env.ledger().with_mut(|l| l.timestamp = 1_000 + 86_400);
+    env.as_contract(&contract_id, || {
+        logic::execute_proposal(&env, &governance, standard).unwrap();
+        assert_eq!(
+            storage::get_current_version(&env),
+            create_test_version(1, 1, 0)
+        );
+    });
+}
+
+#[test]
+fn test_governance_track_limits_bump_and_voting_period() {
+    let (env, contract_id, governance, proposer) = setup_upgrade_storage();
+    env.ledger().with_mut(|l| l.timestamp = 1_000);
+
+    for (track_id, version, expected) in [
+        (
+            FAST_TRACK_ID,
+            create_test_version(1, 1, 0),
+            soroban_sdk::Error::from(ProposalError::BumpNotPermitted),
+        ),
+        // A major bump breaks compatibility, which only the supermajority track permits
+        (
+            STANDARD_TRACK_ID,
+            create_test_version(2, 0, 0),
+            GuardError::IncompatibleVersion.into(),
+        ),
+        (
+            DEFAULT_TRACK_ID,
+            create_test_version(1, 0, 1),
+            ProposalError::TrackNotFound.into(),
+        ),
+        (
+            9,
+            create_test_version(1, 0, 1),
+            ProposalError::TrackNotFound.into(),
+        ),
+    ] {
+        env.as_contract(&contract_id, || {
+            assert_eq!(
+                logic::propose_upgrade_on_track(
+                    &env,
+                    &proposer,
+                    &Address::generate(&env),
+                    &version,
+                    String::from_str(&env, "track"),
+                    track_id,
+                ),
+                Err(expected)
+            );
+        });
+    }
+
+    // Thresholds must sit between a simple majority and unanimity, and
+    // track 0 stays bound to the governance-wide rules
+    let mut track = env.as_contract(&contract_id, || {
+        storage::get_governance_track(&env, FAST_TRACK_ID).unwrap()
+    });
+    for (id, threshold_bps, expected) in [
+        (
+            FAST_TRACK_ID,
+            10_000,
+            ProposalError::InvalidApprovalThreshold,
+        ),
+        (
+            FAST_TRACK_ID,
+            4_999,
+            ProposalError::InvalidApprovalThreshold,
+        ),
+        (DEFAULT_TRACK_ID, 5_000, ProposalError::InvalidTrack),
+    ] {
+        track.id = id;
+        track.threshold_bps = threshold_bps;
+        env.as_contract(&contract_id, || {
+            assert_eq!(
+                logic::set_governance_track(&env, &governance, &track),
+                Err(expected.into())
+            );
+        });
+    }
+
+    // A governance-defined fast lane closes voting after an hour
+    track.id = FAST_TRACK_ID;
+    track.threshold_bps = 5_000;
+    track.voting_period = 3_600;
+    env.as_contract(&contract_id, || {
+        logic::set_governance_track(&env, &governance, &track).unwrap();
+    });
+    let voter = Address::generate(&env);
+    let proposal_id = env.as_contract(&contract_id, || {
+        storage::set_voting_power(&env, &voter, 1);
+        logic::propose_upgrade_on_track(
+            &env,
+            &proposer,
+            &Address::generate(&env),
+            &create_test_version(1, 0, 1),
+            String::from_str(&env, "track"),
+            FAST_TRACK_ID,
+        )
+        .unwrap()
+    });
+    env.ledger().with_mut(|l| l.timestamp = 1_000 + 3_601);
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            logic::cast_vote(&env, &voter, proposal_id, VoteChoice::For),
+            Err(ProposalError::VotingClosed.into())
+        );
+    });
+}
+
+#[test]
+fn test_governance_digest_tracks_every_parameter() {
+    let (env, contract_id, governance, _) = setup_upgrade_storage();
+    let base = GovernanceConfig {
+        quorum_bps: 2_000,
+        approval_threshold_bps: SIMPLE_MAJORITY_BPS,
+        quorum_counts_abstain: false,
+        early_finalize: false,
+        activation_delay: 600,
+        live_vote_export: false,
+        slash_destination: None,
+        revert_window: 0,
+        archive_sink: None,
+    };
+    let digest_with = |config: &GovernanceConfig| {
+        env.as_contract(&contract_id, || {
+            logic::set_governance_config(&env, &governance, config).unwrap();
+            logic::get_governance_digest(&env)
+        })
+    };
+
+    let base_digest = digest_with(&base);
+    assert_eq!(digest_with(&base), base_digest);
+
+    let variants = [
+        GovernanceConfig {
+            quorum_bps: 2_001,
+            ..base.clone()
+        },
+        GovernanceConfig {
+            approval_threshold_bps: 6_000,
+            ..base.clone()
+        },
+        GovernanceConfig {
+            quorum_counts_abstain: true,
+            ..base.clone()
+        },
+        GovernanceConfig {
+            early_finalize: true,
+            ..base.clone()
+        },
+        GovernanceConfig {
+            activation_delay: 601,
+            ..base.clone()
+        },
+        GovernanceConfig {
+            live_vote_export: true,
+            ..base.clone()
+        },
+        GovernanceConfig {
+            revert_window: 3_600,
+            ..base.clone()
+        },
+        GovernanceConfig {
+            slash_destination: Some(Address::generate(&env)),
+            ..base.clone()
+        },
+        GovernanceConfig {
+            archive_sink: Some(Address::generate(&env)),
+            ..base.clone()
+        },
+    ];
+    let mut seen = Vec::from_array(&env, [base_digest.clone()]);
+    for config in variants.iter() {
+        let digest = digest_with(config);
+        assert!(!seen.contains(&digest));
+        seen.push_back(digest);
+    }
+
+    // So are the track definitions, built-in or custom
+    digest_with(&base);
+    let standard = env.as_contract(&contract_id, || {
+        storage::get_governance_track(&env, STANDARD_TRACK_ID).unwrap()
+    });
+    let tracks = [
+        GovernanceTrack {
+            timelock: 1,
+            ..standard.clone()
+        },
+        GovernanceTrack {
+            id: 9,
+            name: symbol_short!("custom"),
+            ..standard
+        },
+    ];
+    for track in tracks.iter() {
+        let digest = env.as_contract(&contract_id, || {
+            logic::set_governance_track(&env, &governance, track).unwrap();
+            logic::get_governance_digest(&env)
+        });
+        assert!(!seen.contains(&digest));
+        seen.push_back(digest);
+    }
+
+    // The execution timelock is part of the digest too
+    setup_delayed_proxy(&env, &contract_id, &governance);
+    let delayed = env.as_contract(&contract_id, || logic::get_governance_digest(&env));
+    assert!(!seen.contains(&delayed));
+}
+
+#[test]
+fn test_min_standards_enumerate_every_shortfall() {
+    let (env, contract_id, governance, _) = setup_upgrade_storage();
+    env.as_contract(&contract_id, || {
+        logic::set_governance_config(
+            &env,
+            &governance,
+            &GovernanceConfig {
+                quorum_bps: 2_000,
+                approval_threshold_bps: SIMPLE_MAJORITY_BPS,
+                quorum_counts_abstain: false,
+                early_finalize: false,
+                activation_delay: 600,
+                live_vote_export: false,
+                slash_destination: None,
+                revert_window: 0,
+                archive_sink: None,
+            },
+        )
+        .unwrap();
+    });
+    let strict = GovernanceMinimums {
+        quorum_bps: 3_000,
+        approval_threshold_bps: 6_000,
+        timelock: 86_400,
+        activation_delay: 3_600,
+    };
+
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            logic::assert_min_standards(&env, &strict),
+            Err(Vec::from_array(
+                &env,
+                [
+                    GovernanceShortfall::QuorumBps(2_000),
+                    GovernanceShortfall::ApprovalThresholdBps(SIMPLE_MAJORITY_BPS),
+                    GovernanceShortfall::Timelock(0),
+                    GovernanceShortfall::ActivationDelay(600),
+                ]
+            ))
+        );
+        assert_eq!(
+            logic::assert_min_standards(
+                &env,
+                &GovernanceMinimums {
+                    quorum_bps: 2_000,
+                    approval_threshold_bps: SIMPLE_MAJORITY_BPS,
+                    timelock: 0,
+                    activation_delay: 600,
+                }
+            ),
+            Ok(())
+        );
+    });
+
+    // A delayed proxy satisfies the timelock minimum only
+    setup_delayed_proxy(&env, &contract_id, &governance);
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            logic::assert_min_standards(&env, &strict),
+            Err(Vec::from_array(
+                &env,
+                [
+                    GovernanceShortfall::QuorumBps(2_000),
+                    GovernanceShortfall::ApprovalThresholdBps(SIMPLE_MAJORITY_BPS),
+                    GovernanceShortfall::ActivationDelay(600),
+                ]
+            ))
+        );
+    });
+}
+
+#[test]
+fn test_normalized_weights_match_raw_tallies() {
+    // (total, for, against, abstain)
+    let distributions = [
+        (10, 6, 1, 1),
+        (3, 1, 1, 1),
+        (7, 0, 5, 2),
+        (1_000_000, 333_333, 1, 666_666),
+        (0, 0, 0, 0),
+    ];
+    for (total, votes_for, votes_against, votes_abstain) in distributions {
+        let (env, contract_id, _, proposer) = setup_upgrade_storage();
+        let proposal_id =
+            propose_version(&env, &contract_id, &proposer, create_test_version(1, 1, 0));
+        let weights = [
+            (votes_for, VoteChoice::For),
+            (votes_against, VoteChoice::Against),
+            (votes_abstain, VoteChoice::Abstain),
+        ];
+        env.as_contract(&contract_id, || {
+            let mut proposal = storage::get_upgrade_proposal(&env, proposal_id).unwrap();
+            proposal.total_voting_power_snapshot = total as u64;
+            storage::store_upgrade_proposal(&env, &proposal);
+        });
+        for (weight, choice) in weights.iter().filter(|(weight, _)| *weight > 0) {
+            let voter = Address::generate(&env);
+            env.as_contract(&contract_id, || {
+                set_snapshot_power(&env, proposal_id, &voter, *weight);
+                storage::record_vote(&env, proposal_id, &voter, *choice).unwrap();
+            });
+        }
+
+        env.as_contract(&contract_id, || {
+            let expected = NormalizedTally {
+                for_bps: weight_bps(votes_for as u64, total as u64),
+                against_bps: weight_bps(votes_against as u64, total as u64),
+                abstain_bps: weight_bps(votes_abstain as u64, total as u64),
+                zero_total: total == 0,
+            };
+            // Floor of the exact share, never more than the whole
+            for (weight, bps) in [
+                (votes_for, expected.for_bps),
+                (votes_against, expected.against_bps),
+                (votes_abstain, expected.abstain_bps),
+            ] {
+                if total > 0 {
+                    assert!(bps as u64 * total as u64 <= weight as u64 * 10_000);
+                    assert!((bps as u64 + 1) * total as u64 > weight as u64 * 10_000);
+                } else {
+                    assert_eq!(bps, 0);
+                }
+            }
+            assert!(expected.for_bps + expected.against_bps + expected.abstain_bps <= 10_000);
+
+            let detail = logic::get_proposal_detail(&env, proposal_id, None).unwrap();
+            assert_eq!(detail.projection.normalized, expected);
+            for receipt in storage::get_vote_receipts(&env, proposal_id).iter() {
+                assert_eq!(receipt.weight_bps, weight_bps(receipt.weight, total as u64));
+            }
+        });
+    }
+
+    // Distinct weights can collapse to the same share when the total is large
+    assert_eq!(weight_bps(1, u64::MAX), weight_bps(2, u64::MAX));
+    assert_eq!(weight_bps(u64::MAX, u64::MAX), 10_000);
+}
+
+/// Walk a scope to the end in batches of `max_work`, collecting violations
+fn walk_invariants(env: &Env, scope: InvariantScope, max_work: u32) -> Vec<InvariantViolation> {
+    let mut violations = Vec::new(env);
+    let mut cursor = 0;
+    loop {
+        let report = invariants::verify_invariants(env, scope, cursor, max_work);
+        violations.append(&report.violations);
+        if report.complete {
+            return violations;
+        }
+        assert!(report.checked > 0);
+        cursor = report.next_cursor;
+    }
+}
+
+const ALL_INVARIANT_SCOPES: [InvariantScope; 5] = [
+    InvariantScope::PendingIndex,
+    InvariantScope::Tallies,
+    InvariantScope::Proxy,
+    InvariantScope::Counters,
+    InvariantScope::Footprint,
+];
+
+#[test]
+fn test_invariants_hold_on_healthy_state_in_bounded_batches() {
+    let (env, contract_id, governance, proposer) = setup_upgrade_storage();
+    for minor in 1..4 {
+        propose_version(
+            &env,
+            &contract_id,
+            &proposer,
+            create_test_version(1, minor, 0),
+        );
+    }
+    setup_vote_ledger(&env, &contract_id, &proposer);
+    env.as_contract(&contract_id, || {
+        crate::proxy::storage::initialize(&env, Address::generate(&env), governance.clone())
+            .unwrap();
+        crate::proxy::storage::set_skip_version_check(&env, true);
+    });
+    let next = Address::generate(&env);
+    env.as_contract(&contract_id, || {
+        crate::proxy::implementation::approve_implementation(&env, &governance, &next).unwrap();
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            crate::proxy::implementation::upgrade(&env, &governance, &next),
+            UpgradeOutcome::Applied
+        );
+    });
+
+    env.as_contract(&contract_id, || {
+        let first = invariants::verify_invariants(&env, InvariantScope::PendingIndex, 0, 2);
+        assert_eq!(
+            (first.checked, first.next_cursor, first.complete),
+            (2, 2, false)
+        );
+        let rest = invariants::verify_invariants(&env, InvariantScope::PendingIndex, 2, 100);
+        assert_eq!((rest.checked, rest.complete), (2, true));
+    });
+
+    // Work per call is capped whatever the caller asks for
+    for _ in 0..invariants::MAX_INVARIANT_WORK {
+        propose_version(&env, &contract_id, &proposer, create_test_version(1, 0, 1));
+    }
+    env.as_contract(&contract_id, || {
+        let capped = invariants::verify_invariants(&env, InvariantScope::Tallies, 0, u32::MAX);
+        assert_eq!(capped.checked, invariants::MAX_INVARIANT_WORK);
+
+        for scope in ALL_INVARIANT_SCOPES {
+            assert!(walk_invariants(&env, scope, 3).is_empty());
+        }
+    });
+}
+
+#[test]
+fn test_invariants_detect_corrupted_state() {
+    let (env, contract_id, governance, proposer) = setup_upgrade_storage();
+    let missing = propose_version(&env, &contract_id, &proposer, create_test_version(1, 1, 0));
+    let moved = propose_version(&env, &contract_id, &proposer, create_test_version(1, 2, 0));
+    let (tallied, _) = setup_vote_ledger(&env, &contract_id, &proposer);
+    env.as_contract(&contract_id, || {
+        crate::proxy::storage::initialize(&env, Address::generate(&env), governance.clone())
+            .unwrap();
+    });
+
+    env.as_contract(&contract_id, || {
+        for scope in ALL_INVARIANT_SCOPES {
+            assert!(walk_invariants(&env, scope, 2).is_empty());
+        }
+
+        // Write around the storage helpers so the indices are left stale
+        let mut proposals = storage::UPGRADE_PROPOSALS.load(&env).unwrap();
+        let mut stray = proposals.get(moved).unwrap();
+        proposals.remove(missing);
+        let mut changed = proposals.get(moved).unwrap();
+        changed.status = UpgradeStatus::Approved;
+        proposals.set(moved, changed);
+        let mut miscounted = proposals.get(tallied).unwrap();
+        miscounted.votes_for += 1;
+        proposals.set(tallied, miscounted);
+        stray.id = 999;
+        proposals.set(999, stray);
+        storage::UPGRADE_PROPOSALS.store(&env, &proposals);
+
+        let mut config = crate::proxy::storage::get_proxy_config(&env);
+        config.version = 5;
+        crate::proxy::storage::PROXY_CONFIG.store(&env, &config);
+        // Recorded under the id the counter has not handed out yet
+        crate::proxy::storage::upgrade_transaction_slot(1).store(
+            &env,
+            &crate::proxy::types::UpgradeTransaction {
+                id: 1,
+                new_implementation: Address::generate(&env),
+                initiator: governance.clone(),
+                timestamp: 0,
+                success: false,
+                failure_reason: None,
+                emergency: false,
+                old_implementation: Address::generate(&env),
+                revert: false,
+            },
+        );
+        let mut plans = Map::new(&env);
+        plans.set(
+            moved,
+            MigrationPlan {
+                from_version: create_test_version(1, 0, 0),
+                to_version: create_test_version(1, 2, 0),
+                migration_function_selector: symbol_short!("migrate"),
+                estimated_gas: 100,
+            },
+        );
+        storage::MIGRATION_PLANS.store(&env, &plans);
+
+        assert_eq!(
+            walk_invariants(&env, InvariantScope::PendingIndex, 1),
+            Vec::from_array(
+                &env,
+                [
+                    InvariantViolation::IndexedProposalMissing(missing),
+                    InvariantViolation::IndexedStatusMismatch(moved),
+                ]
+            )
+        );
+        assert_eq!(
+            walk_invariants(&env, InvariantScope::Tallies, 1),
+            Vec::from_array(&env, [InvariantViolation::TallyMismatch(tallied)])
+        );
+        assert_eq!(
+            walk_invariants(&env, InvariantScope::Proxy, 1),
+            Vec::from_array(&env, [InvariantViolation::ProxyVersionMismatch(5)])
+        );
+        assert_eq!(
+            walk_invariants(&env, InvariantScope::Counters, 1),
+            Vec::from_array(
+                &env,
+                [
+                    InvariantViolation::ProposalIdBeyondCounter(999),
+                    InvariantViolation::UpgradeIdBeyondCounter(1),
+                ]
+            )
+        );
+        let mut recount = storage::recount_footprint(&env);
+        assert_eq!(recount.plans, 1);
+        recount.writes = storage::get_storage_footprint(&env).writes;
+        assert_eq!(
+            walk_invariants(&env, InvariantScope::Footprint, 1),
+            Vec::from_array(&env, [InvariantViolation::FootprintDrift(recount)])
+        );
+    });
+}
+
+/// Propose a private 1.1.0 whose full description is `secret`, revealable from 5_000
+fn propose_private(env: &Env, contract_id: &Address, proposer: &Address, secret: &str) -> u64 {
+    env.as_contract(contract_id, || {
+        let commitment =
+            logic::description_commitment(env, &String::from_str(env, secret)).unwrap();
+        logic::propose_private_upgrade(
+            env,
+            proposer,
+            &Address::generate(env),
+            &create_test_version(1, 1, 0),
+            String::from_str(env, "security fix"),
+            commitment,
+            5_000,
+        )
+        .unwrap()
+    })
+}
+
+#[test]
+fn test_private_description_reveal_must_match_commitment() {
+    let (env, contract_id, _, proposer) = setup_upgrade_storage();
+    env.ledger().with_mut(|l| l.timestamp = 1_000);
+    let secret = "Patch reentrancy in treasury payouts";
+    let proposal_id = propose_private(&env, &contract_id, &proposer, secret);
+
+    env.as_contract(&contract_id, || {
+        let detail = logic::get_proposal_detail(&env, proposal_id, None).unwrap();
+        assert!(detail.is_private);
+        assert_eq!(
+            detail.proposal.description,
+            String::from_str(&env, "security fix")
+        );
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            logic::reveal_description(&env, &proposer, proposal_id, String::from_str(&env, secret)),
+            Err(ProposalError::RevealTooEarly.into())
+        );
+    });
+
+    env.ledger().with_mut(|l| l.timestamp = 5_000);
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            logic::reveal_description(
+                &env,
+                &proposer,
+                proposal_id,
+                String::from_str(&env, "Routine dependency bump")
+            ),
+            Err(ProposalError::DescriptionMismatch.into())
+        );
+    });
+    env.as_contract(&contract_id, || {
+        logic::reveal_description(&env, &proposer, proposal_id, String::from_str(&env, secret))
+            .unwrap();
+        assert_eq!(
+            storage::get_upgrade_proposal(&env, proposal_id)
+                .unwrap()
+                .description,
+            String::from_str(&env, secret)
+        );
+        assert!(
+            storage::get_private_description(&env, proposal_id)
+                .unwrap()
+                .revealed
+        );
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            logic::reveal_description(&env, &proposer, proposal_id, String::from_str(&env, secret)),
+            Err(ProposalError::DescriptionAlreadyRevealed.into())
+        );
+    });
+}
+
+#[test]
+fn test_reveal_required_before_execution() {
+    for required in [false, true] {
+        let (env, contract_id, governance, proposer) = setup_upgrade_storage();
+        let secret = "Rotate the compromised signer set";
+        let proposal_id = propose_private(&env, &contract_id, &proposer, secret);
+        env.as_contract(&contract_id, || {
+            logic::set_reveal_required(&env, &governance, required).unwrap();
+            logic::transition(
+                &env,
+                proposal_id,
+                UpgradeStatus::Approved,
+                symbol_short!("test"),
+            )
+            .unwrap();
+        });
+
+        if required {
+            env.as_contract(&contract_id, || {
+                assert_eq!(
+                    logic::execute_proposal(&env, &governance, proposal_id),
+                    Err(ProposalError::DescriptionNotRevealed.into())
+                );
+                assert_eq!(
+                    logic::get_execution_blockers(&env, proposal_id),
+                    Vec::from_array(&env, [Blocker::DescriptionNotRevealed])
+                );
+            });
+            env.as_contract(&contract_id, || {
+                logic::reveal_description(
+                    &env,
+                    &proposer,
+                    proposal_id,
+                    String::from_str(&env, secret),
+                )
+                .unwrap();
+            });
+            env.as_contract(&contract_id, || {
+                logic::execute_proposal(&env, &governance, proposal_id).unwrap();
+            });
+        } else {
+            // Finalization and execution go ahead without the reveal, which
+            // then becomes possible
+            env.as_contract(&contract_id, || {
+                logic::execute_proposal(&env, &governance, proposal_id).unwrap();
+            });
+            env.as_contract(&contract_id, || {
+                logic::reveal_description(
+                    &env,
+                    &proposer,
+                    proposal_id,
+                    String::from_str(&env, secret),
+                )
+                .unwrap();
+            });
+        }
+        env.as_contract(&contract_id, || {
+            assert_eq!(
+                storage::get_upgrade_proposal(&env, proposal_id)
+                    .unwrap()
+                    .status,
+                UpgradeStatus::Executed
+            );
+        });
+    }
+}
+
+/// Post 100-unit bonds on two proposals, cancel one and have the other
+/// voted down, with slashed bonds routed to a fresh pool address when
+/// `with_pool` is set. Returns the bond token, the rejected proposal and the pool.
+fn reject_bonded_proposal(with_pool: bool) -> (Env, Address, Address, u64, Option<Address>) {
+    let (env, contract_id, governance, proposer) = setup_upgrade_storage();
+    let token = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    soroban_sdk::token::StellarAssetClient::new(&env, &token).mint(&proposer, &250);
+    let voter = Address::generate(&env);
+    let destination = with_pool.then(|| Address::generate(&env));
+
+    env.as_contract(&contract_id, || {
+        logic::set_proposal_bond(&env, &governance, &token, 100).unwrap();
+    });
+    env.as_contract(&contract_id, || {
+        let config = GovernanceConfig {
+            slash_destination: destination.clone(),
+            revert_window: 0,
+            archive_sink: None,
+            ..storage::get_governance_config(&env)
+        };
+        logic::set_governance_config(&env, &governance, &config).unwrap();
+        storage::set_voting_power(&env, &voter, 1);
+    });
+
+    // A proposal that ends without rejection gets its bond back
+    let cancelled = env.as_contract(&contract_id, || {
+        logic::propose_upgrade(
+            &env,
+            &proposer,
+            &Address::generate(&env),
+            &create_test_version(1, 0, 1),
+            String::from_str(&env, "withdrawn"),
+        )
+        .unwrap()
+    });
+    env.as_contract(&contract_id, || {
+        logic::transition(
+            &env,
+            cancelled,
+            UpgradeStatus::Cancelled,
+            symbol_short!("test"),
+        )
+        .unwrap();
+        let bond = storage::get_proposal_bond(&env, cancelled).unwrap();
+        assert_eq!(bond.status, BondStatus::Refunded);
+    });
+
+    let rejected = env.as_contract(&contract_id, || {
+        logic::propose_upgrade(
+            &env,
+            &proposer,
+            &Address::generate(&env),
+            &create_test_version(1, 0, 2),
+            String::from_str(&env, "rejected"),
+        )
+        .unwrap()
+    });
+    let balances = soroban_sdk::token::TokenClient::new(&env, &token);
+    assert_eq!(balances.balance(&proposer), 150);
+    assert_eq!(balances.balance(&contract_id), 100);
+
+    env.as_contract(&contract_id, || {
+        let mut proposal = storage::get_upgrade_proposal(&env, rejected).unwrap();
+        proposal.total_voting_power_snapshot = 1;
+        storage::store_upgrade_proposal(&env, &proposal);
+    });
+    env.as_contract(&contract_id, || {
+        logic::vote_on_proposal(&env, &voter, rejected, false).unwrap();
+    });
+    let slashed_events = env
+        .events()
+        .all()
+        .iter()
+        .filter(|(_, topics, _)| {
+            *topics == (symbol_short!("upgrade"), Symbol::new(&env, "bond_slashed")).into_val(&env)
+        })
+        .count();
+    assert_eq!(slashed_events, 1);
+
+    assert_eq!(balances.balance(&proposer), 150);
+    assert_eq!(balances.balance(&contract_id), 0);
+    env.as_contract(&contract_id, || {
+        let bond = storage::get_proposal_bond(&env, rejected).unwrap();
+        assert_eq!(bond.status, BondStatus::Slashed);
+    });
+    (env, contract_id, token, rejected, destination)
+}
+
+#[test]
+fn test_slashed_bond_goes_to_insurance_pool() {
+    let (env, contract_id, token, proposal_id, pool) = reject_bonded_proposal(true);
+    let pool = pool.unwrap();
+
+    let balances = soroban_sdk::token::TokenClient::new(&env, &token);
+    assert_eq!(balances.balance(&pool), 100);
+
+    env.as_contract(&contract_id, || {
+        let history = logic::get_slash_history(&env, 0, 10);
+        assert_eq!(history.len(), 1);
+        let record = history.get(0).unwrap();
+        assert_eq!(record.proposal_id, proposal_id);
+        assert_eq!(record.token, token);
+        assert_eq!(record.amount, 100);
+        assert_eq!(record.destination, Some(pool.clone()));
+        assert!(logic::get_slash_history(&env, 1, 10).is_empty());
+    });
+}
+
+#[test]
+fn test_slashed_bond_is_burned_without_destination() {
+    let (env, contract_id, _, proposal_id, _) = reject_bonded_proposal(false);
+
+    // Nobody holds the slashed 100: proposer and contract balances were
+    // checked by the helper, and there is no pool to receive it
+    env.as_contract(&contract_id, || {
+        let history = logic::get_slash_history(&env, 0, 10);
+        assert_eq!(history.len(), 1);
+        let record = history.get(0).unwrap();
+        assert_eq!(record.proposal_id, proposal_id);
+        assert_eq!(record.amount, 100);
+        assert_eq!(record.destination, None);
+    });
+}
+
+/// Topics of the most recent meta-event
+fn last_meta_topic(env: &Env) -> Vec<soroban_sdk::Val> {
+    let meta = Symbol::new(env, "stellar_guilds");
+    let mut last = None;
+    for (_, topics, _) in env.events().all().iter() {
+        let first: Symbol = topics.get(0).unwrap().into_val(env);
+        if first == meta {
+            last = Some(topics);
+        }
+    }
+    last.expect("no meta-event was published")
+}
+
+#[test]
+fn test_meta_event_topics_follow_the_contract_era() {
+    let (env, contract_id, governance, proposer) = setup_upgrade_storage();
+    let proposal_id =
+        create_approved_proposal(&env, &contract_id, &proposer, create_test_version(2, 0, 0));
+
+    env.as_contract(&contract_id, || {
+        logic::heartbeat(&env, &governance).unwrap();
+    });
+    let topic = last_meta_topic(&env);
+    assert_eq!(topic.len(), 4);
+    let major: u32 = topic.get(2).unwrap().into_val(&env);
+    let proxy_version: u32 = topic.get(3).unwrap().into_val(&env);
+    assert_eq!((major, proxy_version), (1, 0));
+
+    // Executing the major upgrade moves later events into the new era
+    env.as_contract(&contract_id, || {
+        logic::execute_proposal(&env, &governance, proposal_id).unwrap();
+    });
+    env.as_contract(&contract_id, || {
+        logic::heartbeat(&env, &governance).unwrap();
+    });
+    let major: u32 = last_meta_topic(&env).get(2).unwrap().into_val(&env);
+    assert_eq!(major, 2);
+
+    // Disabled era topics fall back to the plain meta-topic
+    env.as_contract(&contract_id, || {
+        logic::set_event_version_topics(&env, &governance, false).unwrap();
+    });
+    env.as_contract(&contract_id, || {
+        logic::heartbeat(&env, &governance).unwrap();
+    });
+    assert_eq!(last_meta_topic(&env).len(), 2);
+}
+
+#[test]
+fn test_execution_revalidates_target_after_approval() {
+    let (env, contract_id, governance, proposer) = setup_upgrade_storage();
+    let target = create_test_version(1, 1, 0);
+    let first = create_approved_proposal(&env, &contract_id, &proposer, target.clone());
+    let second = create_approved_proposal(&env, &contract_id, &proposer, target.clone());
+
+    env.as_contract(&contract_id, || {
+        logic::deprecate_version(&env, &governance, &target).unwrap();
+    });
+    // The refusal is an outcome, so its announcement outlives the call
+    let client = crate::StellarGuildsContractClient::new(&env, &contract_id);
+    assert_eq!(
+        client.try_execute_upgrade_proposal(&governance, &first),
+        Ok(Ok(ExecutionOutcome::RevalidationFailed))
+    );
+    let failed = env
+        .events()
+        .all()
+        .iter()
+        .filter(|(_, topics, _)| {
+            *topics
+                == (
+                    symbol_short!("upgrade"),
+                    Symbol::new(&env, "revalidation_failed"),
+                )
+                    .into_val(&env)
+        })
+        .count();
+    assert_eq!(failed, 1);
+
+    env.as_contract(&contract_id, || {
+        // The readiness endpoint reports the same reason
+        let blockers = logic::get_execution_blockers(&env, first);
+        assert_eq!(blockers.len(), 1);
+        assert_eq!(blockers.get(0).unwrap(), Blocker::TargetDeprecated);
+        assert_eq!(
+            storage::get_current_version(&env),
+            create_test_version(1, 0, 0)
+        );
+
+        // New proposals for a deprecated target are refused outright
+        assert_eq!(
+            logic::propose_upgrade(
+                &env,
+                &proposer,
+                &Address::generate(&env),
+                &target,
+                String::from_str(&env, "again"),
+            ),
+            Err(ProposalError::TargetDeprecated.into())
+        );
+    });
+
+    // Once one proposal reaches the version, a duplicate approval is stale
+    // too, and no longer newer than the current version
+    env.as_contract(&contract_id, || {
+        storage::DEPRECATED_VERSIONS.store(&env, &Vec::new(&env));
+        logic::execute_proposal(&env, &governance, first).unwrap();
+    });
+    env.as_contract(&contract_id, || {
+        let blockers = logic::get_execution_blockers(&env, second);
+        assert_eq!(
+            blockers,
+            Vec::from_array(
+                &env,
+                [
+                    Blocker::VersionNotNewer,
+                    Blocker::TargetAlreadyExecuted(first)
+                ]
+            )
+        );
+        assert_eq!(
+            logic::execute_proposal(&env, &governance, second),
+            Ok(ExecutionOutcome::RevalidationFailed)
+        );
+    });
+}
+
+/// A description of `len` bytes: ASCII, with a two-byte 'é' straddling
+/// the inline limit
+fn long_description(env: &Env, len: usize) -> String {
+    let mut bytes = [b'd'; 1_024];
+    bytes[199] = 0xC3;
+    bytes[200] = 0xA9;
+    String::from_bytes(env, &bytes[..len])
+}
+
+#[test]
+fn test_long_description_spills_out_of_the_proposal() {
+    let (env, contract_id, governance, proposer) = setup_upgrade_storage();
+    let description = long_description(&env, 1_024);
+
+    let proposal_id = env.as_contract(&contract_id, || {
+        logic::propose_upgrade(
+            &env,
+            &proposer,
+            &Address::generate(&env),
+            &create_test_version(1, 1, 0),
+            description.clone(),
+        )
+        .unwrap()
+    });
+
+    env.as_contract(&contract_id, || {
+        let proposal = storage::get_upgrade_proposal(&env, proposal_id).unwrap();
+        // The summary stops before the character that crosses the limit
+        assert_eq!(proposal.description.len(), 199);
+        assert_eq!(
+            proposal.description_hash.to_array(),
+            env.crypto()
+                .sha256(&description.clone().to_xdr(&env))
+                .to_array()
+        );
+        assert_eq!(
+            storage::get_description_blob(&env, proposal_id),
+            Some(description.clone())
+        );
+
+        let detail = logic::get_proposal_detail(&env, proposal_id, None).unwrap();
+        assert_eq!(detail.proposal.description, description);
+
+        logic::transition(
+            &env,
+            proposal_id,
+            UpgradeStatus::Cancelled,
+            symbol_short!("test"),
+        )
+        .unwrap();
+    });
+    env.as_contract(&contract_id, || {
+        logic::archive_proposal(&env, &governance, proposal_id).unwrap();
+    });
+    env.as_contract(&contract_id, || {
+        assert!(storage::get_description_blob(&env, proposal_id).is_none());
+    });
+}
+
+/// CPU and memory cost of one vote on a proposal with a 1 KB description,
+/// kept inline (the layout before descriptions were spilled) or spilled
+fn vote_cost_with_long_description(spilled: bool) -> (u64, u64) {
+    let (env, contract_id, _, proposer) = setup_upgrade_storage();
+    let voter = Address::generate(&env);
+
+    let proposal_id = env.as_contract(&contract_id, || {
+        storage::set_voting_power(&env, &voter, 1);
+        let proposal_id = logic::propose_upgrade(
+            &env,
+            &proposer,
+            &Address::generate(&env),
+            &create_test_version(1, 1, 0),
+            long_description(&env, 1_024),
+        )
+        .unwrap();
+
+        let mut proposal = storage::get_upgrade_proposal(&env, proposal_id).unwrap();
+        proposal.total_voting_power_snapshot = 10;
+        if !spilled {
+            proposal.description = long_description(&env, 1_024);
+            proposal.description_hash = BytesN::from_array(&env, &[0u8; 32]);
+        }
+        storage::store_upgrade_proposal(&env, &proposal);
+        proposal_id
+    });
+
+    env.budget().reset_unlimited();
+    env.as_contract(&contract_id, || {
+        logic::vote_on_proposal(&env, &voter, proposal_id, true).unwrap();
+    });
+    (
+        env.budget().cpu_instruction_cost(),
+        env.budget().memory_bytes_cost(),
+    )
+}
+
+#[test]
+fn test_spilled_description_keeps_votes_cheap() {
+    let (inline_cpu, inline_mem) = vote_cost_with_long_description(false);
+    let (spilled_cpu, spilled_mem) = vote_cost_with_long_description(true);
+    assert!(spilled_cpu < inline_cpu);
+    // The vote path no longer copies the description around
+    assert!(
+        spilled_mem + 1_024 < inline_mem,
+        "vote memory {} with spilled description, {} inline",
+        spilled_mem,
+        inline_mem
+    );
+}
+
+/// Propose and approve an upgrade with events at `verbosity`. Returns the
+/// number of events the vote fired and the status change payload.
+fn approve_at_verbosity(
+    verbosity: crate::events::types::EventVerbosity,
+) -> (Env, u32, soroban_sdk::Val) {
+    let (env, contract_id, governance, proposer) = setup_upgrade_storage();
+    env.as_contract(&contract_id, || {
+        logic::set_event_verbosity(&env, &governance, verbosity).unwrap();
+        storage::set_voting_power(&env, &proposer, 1);
+    });
+    let proposal_id = env.as_contract(&contract_id, || {
+        logic::propose_upgrade(
+            &env,
+            &proposer,
+            &Address::generate(&env),
+            &create_test_version(1, 1, 0),
+            String::from_str(&env, "leveled"),
+        )
+        .unwrap()
+    });
+    env.as_contract(&contract_id, || {
+        let mut proposal = storage::get_upgrade_proposal(&env, proposal_id).unwrap();
+        proposal.total_voting_power_snapshot = 1;
+        storage::store_upgrade_proposal(&env, &proposal);
+    });
+
+    let before = env.events().all().len();
+    env.as_contract(&contract_id, || {
+        logic::vote_on_proposal(&env, &proposer, proposal_id, true).unwrap();
+    });
+    let events = env.events().all();
+    let status_topic: Vec<soroban_sdk::Val> = (
+        symbol_short!("upgrade"),
+        Symbol::new(&env, "status_changed"),
+    )
+        .into_val(&env);
+    let (_, _, payload) = events
+        .iter()
+        .filter(|(_, topics, _)| *topics == status_topic)
+        .last()
+        .unwrap();
+
+    // The envelope before the payload records the level it was built at
+    let (_, _, envelope) = events.get(events.len() - 2).unwrap();
+    let envelope: crate::events::types::EventEnvelope = envelope.into_val(&env);
+    assert_eq!(envelope.verbosity, verbosity);
+
+    (env, events.len() - before, payload)
+}
+
+#[test]
+fn test_event_verbosity_changes_payloads_not_events() {
+    use crate::events::types::EventVerbosity;
+
+    let (env, minimal_count, payload) = approve_at_verbosity(EventVerbosity::Minimal);
+    let (proposal_id, to): (u64, UpgradeStatus) = payload.into_val(&env);
+    assert_eq!((proposal_id, to), (1, UpgradeStatus::Approved));
+
+    let (env, standard_count, payload) = approve_at_verbosity(EventVerbosity::Standard);
+    let change: StatusChange = payload.into_val(&env);
+    assert_eq!(change.to, UpgradeStatus::Approved);
+    assert_eq!(change.reason, VOTE_REASON);
+
+    let (env, verbose_count, payload) = approve_at_verbosity(EventVerbosity::Verbose);
+    let (change, snapshot): (StatusChange, TallySnapshot) = payload.into_val(&env);
+    assert_eq!(change.to, UpgradeStatus::Approved);
+    assert_eq!(snapshot.votes_for, 1);
+    assert_eq!(snapshot.normalized.for_bps, 10_000);
+
+    assert_eq!(minimal_count, standard_count);
+    assert_eq!(standard_count, verbose_count);
+}
+
+#[test]
+fn test_clock_override_drives_every_time_check() {
+    use crate::emergency::storage as emergency_storage;
+    use crate::emergency::types::{EmergencyConfig, EmergencyStatus};
+    use crate::utils::time;
+
+    let (env, contract_id, governance, proposer) = setup_upgrade_storage();
+    env.ledger().with_mut(|l| l.timestamp = 1_000);
+
+    // Fast track closes voting after a day; standard track has a day of timelock
+    let voter = Address::generate(&env);
+    env.as_contract(&contract_id, || {
+        storage::set_voting_power(&env, &voter, 1);
+        emergency_storage::set_emergency_config(
+            &env,
+            &EmergencyConfig {
+                status: EmergencyStatus::Active,
+                paused_at: 1_000,
+                expires_at: 1_500,
+                paused_by: Some(governance.clone()),
+                emergency_contact: String::from_str(&env, ""),
+            },
+        );
+    });
+    env.as_contract(&contract_id, || {
+        logic::set_max_silence(&env, &governance, 100).unwrap();
+    });
+    let fast_id = env.as_contract(&contract_id, || {
+        logic::propose_upgrade_on_track(
+            &env,
+            &proposer,
+            &Address::generate(&env),
+            &create_test_version(1, 0, 1),
+            String::from_str(&env, "fast"),
+            FAST_TRACK_ID,
+        )
+        .unwrap()
+    });
+    let standard_id = env.as_contract(&contract_id, || {
+        logic::propose_upgrade_on_track(
+            &env,
+            &proposer,
+            &Address::generate(&env),
+            &create_test_version(1, 1, 0),
+            String::from_str(&env, "standard"),
+            STANDARD_TRACK_ID,
+        )
+        .unwrap()
+    });
+    env.as_contract(&contract_id, || {
+        logic::transition(
+            &env,
+            standard_id,
+            UpgradeStatus::Approved,
+            symbol_short!("test"),
+        )
+        .unwrap();
+    });
+
+    // Frozen at the ledger time nothing has elapsed
+    time::testutils::freeze(1_000);
+    env.as_contract(&contract_id, || {
+        assert_eq!(time::now(&env), 1_000);
+        assert!(emergency_storage::is_paused(&env));
+        assert!(!logic::check_heartbeat(&env));
+        assert_eq!(
+            logic::get_execution_blockers(&env, standard_id),
+            Vec::from_array(&env, [Blocker::TimelockActive(1_000 + 86_400)])
+        );
+    });
+
+    // Stepping the clock moves every check while the ledger stays put
+    time::testutils::step(&env, 86_401);
+    env.as_contract(&contract_id, || {
+        assert_eq!(time::now(&env), 87_401);
+        assert_eq!(env.ledger().timestamp(), 1_000);
+
+        assert!(!emergency_storage::is_paused(&env));
+        assert!(logic::check_heartbeat(&env));
+        assert!(logic::get_execution_blockers(&env, standard_id).is_empty());
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            logic::cast_vote(&env, &voter, fast_id, VoteChoice::For),
+            Err(ProposalError::VotingClosed.into())
+        );
+    });
+
+    // Released, the clock reads the ledger again
+    time::testutils::release();
+    env.as_contract(&contract_id, || {
+        assert_eq!(time::now(&env), 1_000);
+        assert_eq!(
+            logic::get_execution_blockers(&env, standard_id),
+            Vec::from_array(&env, [Blocker::TimelockActive(1_000 + 86_400)])
+        );
+    });
+}
+
+#[test]
+fn test_rehearsal_flags_corrupting_migration_without_touching_live_state() {
+    let (env, contract_id, governance, proposer) = setup_upgrade_storage();
+    let migration_contract = env.register_contract(None, TaggingMigration);
+    let live = MigrationNamespace::Live;
+    let alpha = symbol_short!("alpha");
+    let beta = symbol_short!("beta");
+    let gamma = symbol_short!("gamma");
+
+    env.as_contract(&contract_id, || {
+        storage::set_migration_entry(&env, &live, &alpha, &Bytes::from_slice(&env, b"a1"));
+        storage::set_migration_entry(&env, &live, &beta, &Bytes::from_slice(&env, b"b1"));
+    });
+    let proposal_id = env.as_contract(&contract_id, || {
+        logic::propose_upgrade(
+            &env,
+            &proposer,
+            &migration_contract,
+            &create_test_version(1, 1, 0),
+            String::from_str(&env, "tagged entries"),
+        )
+        .unwrap()
+    });
+    let plan = |selector: Symbol| MigrationPlan {
+        from_version: create_test_version(1, 0, 0),
+        to_version: create_test_version(1, 1, 0),
+        migration_function_selector: selector,
+        estimated_gas: 100,
+    };
+    env.as_contract(&contract_id, || {
+        logic::register_migration_plan(
+            &env,
+            &governance,
+            proposal_id,
+            &plan(symbol_short!("corrupt")),
+        )
+        .unwrap();
+    });
+
+    let samples = Vec::from_array(
+        &env,
+        [
+            RehearsalSample {
+                key: alpha.clone(),
+                expected: Bytes::from_slice(&env, b"a1\x01"),
+            },
+            RehearsalSample {
+                key: beta.clone(),
+                expected: Bytes::from_slice(&env, b"b1\x01"),
+            },
+            RehearsalSample {
+                key: gamma.clone(),
+                expected: Bytes::new(&env),
+            },
+        ],
+    );
+    let report = env.as_contract(&contract_id, || {
+        logic::start_rehearsal(&env, &governance, proposal_id, &samples).unwrap()
+    });
+
+    assert!(!report.passed);
+    assert_eq!(report.checked, 2);
+    assert_eq!(
+        report.mismatched,
+        Vec::from_array(&env, [alpha.clone(), beta.clone()])
+    );
+    assert_eq!(report.missing, Vec::from_array(&env, [gamma.clone()]));
+
+    let shadow = MigrationNamespace::Shadow(proposal_id);
+    env.as_contract(&contract_id, || {
+        // The corruption landed in the shadow copy only
+        assert_eq!(
+            storage::get_migration_entry(&env, &shadow, &alpha),
+            Some(Bytes::new(&env))
+        );
+        assert_eq!(
+            storage::get_migration_entry(&env, &live, &alpha),
+            Some(Bytes::from_slice(&env, b"a1"))
+        );
+        assert_eq!(
+            storage::get_migration_entry(&env, &live, &beta),
+            Some(Bytes::from_slice(&env, b"b1"))
+        );
+        assert_eq!(storage::get_namespace_keys(&env, &live).len(), 2);
+        assert_eq!(
+            storage::get_rehearsal_report(&env, proposal_id),
+            Some(report)
+        );
+    });
+
+    // Discarding removes the shadow data and the report
+    env.as_contract(&contract_id, || {
+        logic::discard_rehearsal(&env, &governance, proposal_id).unwrap();
+    });
+    env.as_contract(&contract_id, || {
+        assert!(storage::get_migration_entry(&env, &shadow, &alpha).is_none());
+        assert!(storage::get_namespace_keys(&env, &shadow).is_empty());
+        assert!(storage::get_rehearsal_report(&env, proposal_id).is_none());
+    });
+
+    // A faithful migration passes its rehearsal
+    env.as_contract(&contract_id, || {
+        logic::register_migration_plan(
+            &env,
+            &governance,
+            proposal_id,
+            &plan(symbol_short!("migrate")),
+        )
+        .unwrap();
+    });
+    let report = env.as_contract(&contract_id, || {
+        logic::start_rehearsal(&env, &governance, proposal_id, &samples.slice(0..2)).unwrap()
+    });
+    assert!(report.passed);
+    assert_eq!(report.checked, 2);
+
+    // Only governance may rehearse
+    let outsider = Address::generate(&env);
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            logic::start_rehearsal(&env, &outsider, proposal_id, &samples),
+            Err(UpgradeError::NotAuthorized.into())
+        );
+    });
+}
+
+#[test]
+fn test_registry_snapshot_sets_the_threshold() {
+    let (env, contract_id, _, proposer) = setup_upgrade_storage();
+    let voters = [
+        Address::generate(&env),
+        Address::generate(&env),
+        Address::generate(&env),
+    ];
+    env.as_contract(&contract_id, || {
+        for voter in voters.iter() {
+            storage::set_voting_power(&env, voter, 1);
+        }
+    });
+    let proposal_id = propose_version(&env, &contract_id, &proposer, create_test_version(1, 1, 0));
+
+    // Power granted after creation does not move the snapshot
+    env.as_contract(&contract_id, || {
+        storage::set_voting_power(&env, &Address::generate(&env), 50);
+        let proposal = storage::get_upgrade_proposal(&env, proposal_id).unwrap();
+        assert_eq!(proposal.total_voters, 3);
+        assert_eq!(proposal.total_voting_power_snapshot, 3);
+    });
+
+    // One vote of three is no longer enough to approve
+    env.as_contract(&contract_id, || {
+        logic::vote_on_proposal(&env, &voters[0], proposal_id, true).unwrap();
+    });
+    env.as_contract(&contract_id, || {
+        let proposal = storage::get_upgrade_proposal(&env, proposal_id).unwrap();
+        assert_eq!(proposal.status, UpgradeStatus::Pending);
+        let params = storage::get_proposal_params(&env, proposal_id);
+        assert_eq!(logic::project_outcome(&proposal, &params).required_votes, 2);
+    });
+
+    env.as_contract(&contract_id, || {
+        logic::vote_on_proposal(&env, &voters[1], proposal_id, true).unwrap();
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            storage::get_upgrade_proposal(&env, proposal_id)
+                .unwrap()
+                .status,
+            UpgradeStatus::Approved
+        );
+    });
+}
+
+/// Store schema 1 data: proposal 5 pending and 6 executed in the legacy
+/// layout, with 5 voting power across two voters
+fn seed_schema_v1(env: &Env, contract_id: &Address, proposer: &Address) {
+    let legacy = |id: u64, status: UpgradeStatus| LegacyUpgradeProposal {
+        id,
+        proposer: proposer.clone(),
+        new_contract_address: Address::generate(env),
+        version: create_test_version(1, 1, 0),
+        description: String::from_str(env, "legacy"),
+        timestamp: 0,
+        status,
+        votes_for: 0,
+        votes_against: 0,
+        votes_abstain: 0,
+        total_voters: 0,
+        salt: BytesN::from_array(env, &[0u8; 32]),
+        description_hash: BytesN::from_array(env, &[0u8; 32]),
+    };
+
+    env.as_contract(contract_id, || {
+        let mut stored: Map<u64, LegacyUpgradeProposal> = Map::new(env);
+        stored.set(5, legacy(5, UpgradeStatus::Pending));
+        stored.set(6, legacy(6, UpgradeStatus::Executed));
+        env.storage()
+            .persistent()
+            .set(&UPGRADE_PROPOSALS_KEY, &stored);
+        storage::set_storage_schema(env, 1);
+        storage::set_voting_power(env, &Address::generate(env), 2);
+        storage::set_voting_power(env, &Address::generate(env), 3);
+    });
+}
+
+#[test]
+fn test_migrate_proposal_snapshots_rewrites_legacy_entries() {
+    let (env, contract_id, governance, proposer) = setup_upgrade_storage();
+    seed_schema_v1(&env, &contract_id, &proposer);
+
+    let migrated = env.as_contract(&contract_id, || {
+        logic::migrate_proposal_snapshots(&env, &governance).unwrap()
+    });
+    assert_eq!(migrated, 2);
+    for target in 3..=STORAGE_SCHEMA {
+        env.as_contract(&contract_id, || {
+            logic::migrate_storage_schema(&env, &governance, target).unwrap();
+        });
+    }
+
+    env.as_contract(&contract_id, || {
+        let pending = storage::get_upgrade_proposal(&env, 5).unwrap();
+        assert_eq!(pending.total_voters, 2);
+        assert_eq!(pending.total_voting_power_snapshot, 5);
+        let executed = storage::get_upgrade_proposal(&env, 6).unwrap();
+        assert_eq!(executed.total_voting_power_snapshot, 0);
+    });
+
+    // Already migrated entries are left alone
+    env.as_contract(&contract_id, || {
+        assert_eq!(logic::migrate_proposal_snapshots(&env, &governance), Ok(0));
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            logic::migrate_proposal_snapshots(&env, &proposer),
+            Err(UpgradeError::NotAuthorized.into())
+        );
+    });
+}
+
+#[test]
+fn test_schema_guard_refuses_v1_data_until_migrated() {
+    let (env, contract_id, governance, proposer) = setup_upgrade_storage();
+    seed_schema_v1(&env, &contract_id, &proposer);
+
+    // v2 code reports the mismatch instead of decoding v1 entries
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            storage::check_schema(&env),
+            Err(SchemaMismatch {
+                found: 1,
+                expected: STORAGE_SCHEMA,
+            })
+        );
+        assert_eq!(
+            logic::finalize_proposal(&env, 5),
+            Err(UpgradeError::SchemaMismatch.into())
+        );
+    });
+    let client = crate::StellarGuildsContractClient::new(&env, &contract_id);
+    let error = client
+        .try_get_upgrade_proposal_detail(&5, &None)
+        .err()
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        error,
+        soroban_sdk::Error::from_contract_error(UpgradeError::SchemaMismatch as u32)
+    );
+
+    // Steps cannot be skipped
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            logic::migrate_storage_schema(&env, &governance, 3),
+            Err(UpgradeError::SchemaMismatch)
+        );
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(logic::migrate_storage_schema(&env, &governance, 2), Ok(2));
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(logic::migrate_storage_schema(&env, &governance, 3), Ok(0));
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(logic::migrate_storage_schema(&env, &governance, 4), Ok(0));
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(logic::migrate_storage_schema(&env, &governance, 5), Ok(2));
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(logic::migrate_storage_schema(&env, &governance, 6), Ok(0));
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(logic::migrate_storage_schema(&env, &governance, 7), Ok(2));
+        assert_eq!(storage::get_registry_power(&env), 5);
+    });
+    env.as_contract(&contract_id, || {
+        // Step 1 already wrote the wide tally layout
+        assert_eq!(logic::migrate_storage_schema(&env, &governance, 8), Ok(0));
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(logic::migrate_storage_schema(&env, &governance, 9), Ok(0));
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(logic::migrate_storage_schema(&env, &governance, 10), Ok(0));
+    });
+    env.as_contract(&contract_id, || {
+        // The receipt was written by current code, so it is already wide
+        assert_eq!(logic::migrate_storage_schema(&env, &governance, 11), Ok(0));
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(logic::migrate_storage_schema(&env, &governance, 12), Ok(0));
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(logic::migrate_storage_schema(&env, &governance, 13), Ok(0));
+    });
+
+    env.as_contract(&contract_id, || {
+        assert_eq!(storage::get_storage_schema(&env), STORAGE_SCHEMA);
+        assert!(storage::check_schema(&env).is_ok());
+        assert_eq!(
+            storage::get_upgrade_proposal(&env, 6).unwrap().status,
+            UpgradeStatus::Executed
+        );
+        assert_eq!(storage::get_pending_proposals(&env).len(), 1);
+    });
+    assert!(client.get_upgrade_proposal_detail(&5, &None).is_some());
+    env.as_contract(&contract_id, || {
+        assert!(walk_invariants(&env, InvariantScope::Footprint, 1).is_empty());
+    });
+
+    // Replaying the step is a no-op
+    env.as_contract(&contract_id, || {
+        assert_eq!(logic::migrate_storage_schema(&env, &governance, 2), Ok(0));
+    });
+}
+
+/// Seed two live entries and an approved proposal whose plan calls `selector`
+/// on the verdict stub
+fn setup_verdict_migration(
+    env: &Env,
+    contract_id: &Address,
+    proposer: &Address,
+    governance: &Address,
+    selector: &str,
+) -> u64 {
+    use super::testutils::VerdictMigration;
+
+    let implementation = env.register_contract(None, VerdictMigration);
+    setup_migration(
+        env,
+        contract_id,
+        proposer,
+        governance,
+        &implementation,
+        selector,
+    )
+}
+
+/// Seed two live entries and an approved proposal whose plan calls `selector`
+/// on `implementation`
+fn setup_migration(
+    env: &Env,
+    contract_id: &Address,
+    proposer: &Address,
+    governance: &Address,
+    implementation: &Address,
+    selector: &str,
+) -> u64 {
+    env.as_contract(contract_id, || {
+        let live = MigrationNamespace::Live;
+        storage::set_migration_entry(
+            env,
+            &live,
+            &symbol_short!("alpha"),
+            &Bytes::from_slice(env, b"a1"),
+        );
+        storage::set_migration_entry(
+            env,
+            &live,
+            &symbol_short!("beta"),
+            &Bytes::from_slice(env, b"b1"),
+        );
+    });
+    let proposal_id = env.as_contract(contract_id, || {
+        logic::propose_upgrade(
+            env,
+            proposer,
+            implementation,
+            &create_test_version(1, 1, 0),
+            String::from_str(env, "verdicts"),
+        )
+        .unwrap()
+    });
+    let plan = MigrationPlan {
+        from_version: create_test_version(1, 0, 0),
+        to_version: create_test_version(1, 1, 0),
+        migration_function_selector: Symbol::new(env, selector),
+        estimated_gas: 100,
+    };
+    env.as_contract(contract_id, || {
+        logic::register_migration_plan(env, governance, proposal_id, &plan).unwrap();
+    });
+    env.as_contract(contract_id, || {
+        logic::transition(
+            env,
+            proposal_id,
+            UpgradeStatus::Approved,
+            symbol_short!("test"),
+        )
+        .unwrap();
+    });
+    proposal_id
+}
+
+#[test]
+fn test_migration_verdict_ok_completes_the_upgrade() {
+    let (env, contract_id, governance, proposer) = setup_upgrade_storage();
+    let proposal_id =
+        setup_verdict_migration(&env, &contract_id, &proposer, &governance, "migrate_ok");
+
+    env.as_contract(&contract_id, || {
+        logic::execute_proposal(&env, &governance, proposal_id).unwrap();
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            storage::get_current_version(&env),
+            create_test_version(1, 1, 0)
+        );
+        assert_eq!(
+            storage::get_migration_entry(&env, &MigrationNamespace::Live, &symbol_short!("beta")),
+            Some(Bytes::from_slice(&env, b"b1\x01"))
+        );
+        let progress = storage::get_migration_progress(&env, proposal_id).unwrap();
+        assert_eq!(progress.status, MigrationStatus::Completed);
+        assert_eq!(progress.next_index, 2);
+    });
+}
+
+#[test]
+fn test_migration_verdict_abort_fails_the_proposal() {
+    let (env, contract_id, governance, proposer) = setup_upgrade_storage();
+    let proposal_id =
+        setup_verdict_migration(&env, &contract_id, &proposer, &governance, "migrate_abort");
+
+    // The abort is an outcome, so the failed proposal outlives the call
+    let client = crate::StellarGuildsContractClient::new(&env, &contract_id);
+    assert_eq!(
+        client.try_execute_upgrade_proposal(&governance, &proposal_id),
+        Ok(Ok(ExecutionOutcome::MigrationAborted(
+            super::testutils::STUB_ABORT_CODE
+        )))
+    );
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            storage::get_current_version(&env),
+            create_test_version(1, 0, 0)
+        );
+        assert_eq!(
+            storage::get_upgrade_proposal(&env, proposal_id)
+                .unwrap()
+                .status,
+            UpgradeStatus::Failed
+        );
+        let progress = storage::get_migration_progress(&env, proposal_id).unwrap();
+        assert_eq!(progress.status, MigrationStatus::Aborted);
+        assert_eq!(progress.abort_code, super::testutils::STUB_ABORT_CODE);
+        assert_eq!(
+            storage::get_migration_entry(&env, &MigrationNamespace::Live, &symbol_short!("alpha")),
+            Some(Bytes::from_slice(&env, b"a1"))
+        );
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            logic::continue_migration(&env, &governance, proposal_id),
+            Err(ProposalError::MigrationNotInProgress.into())
+        );
+    });
+}
+
+#[test]
+fn test_migration_call_failures_reach_the_executor() {
+    for (selector, expected) in [
+        ("migrate_trap", ProposalError::CallTrapped),
+        ("migrate_malformed", ProposalError::MalformedCallReturn),
+    ] {
+        let (env, contract_id, governance, proposer) = setup_upgrade_storage();
+        let proposal_id =
+            setup_verdict_migration(&env, &contract_id, &proposer, &governance, selector);
+
+        env.as_contract(&contract_id, || {
+            assert_eq!(
+                logic::execute_proposal(&env, &governance, proposal_id),
+                Err(expected.into())
+            );
+        });
+        env.as_contract(&contract_id, || {
+            assert_eq!(
+                storage::get_current_version(&env),
+                create_test_version(1, 0, 0)
+            );
+        });
+    }
+}
+
+#[test]
+fn test_contract_execution_calls_the_migration_selector() {
+    // A migration that succeeds moves every entry and bumps the version
+    let (env, contract_id, governance, proposer) = setup_upgrade_storage();
+    let proposal_id =
+        setup_verdict_migration(&env, &contract_id, &proposer, &governance, "migrate_ok");
+    let client = crate::StellarGuildsContractClient::new(&env, &contract_id);
+    assert_eq!(
+        client.execute_upgrade_proposal(&governance, &proposal_id),
+        ExecutionOutcome::Executed
+    );
+    assert_eq!(client.get_current_version(), create_test_version(1, 1, 0));
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            storage::get_migration_entry(&env, &MigrationNamespace::Live, &symbol_short!("alpha")),
+            Some(Bytes::from_slice(&env, b"a1\x01"))
+        );
+    });
+
+    // One that traps fails the execution and leaves the proposal approved
+    let (env, contract_id, governance, proposer) = setup_upgrade_storage();
+    let proposal_id =
+        setup_verdict_migration(&env, &contract_id, &proposer, &governance, "migrate_trap");
+    let client = crate::StellarGuildsContractClient::new(&env, &contract_id);
+    assert_eq!(
+        client.try_execute_upgrade_proposal(&governance, &proposal_id),
+        Err(Ok(ProposalError::CallTrapped.into()))
+    );
+    assert_eq!(client.get_current_version(), create_test_version(1, 0, 0));
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            storage::get_upgrade_proposal(&env, proposal_id)
+                .unwrap()
+                .status,
+            UpgradeStatus::Approved
+        );
+        assert_eq!(
+            storage::get_migration_entry(&env, &MigrationNamespace::Live, &symbol_short!("alpha")),
+            Some(Bytes::from_slice(&env, b"a1"))
+        );
+    });
+}
+
+#[test]
+fn test_migration_callbacks_cannot_reenter_execution() {
+    use super::testutils::{ReentrantMigration, ReentrantMigrationClient};
+
+    let (env, contract_id, governance, proposer) = setup_upgrade_storage();
+    let implementation = env.register_contract(None, ReentrantMigration);
+    let proposal_id = setup_migration(
+        &env,
+        &contract_id,
+        &proposer,
+        &governance,
+        &implementation,
+        "migrate_reenter",
+    );
+    let stub = ReentrantMigrationClient::new(&env, &implementation);
+    stub.arm(&contract_id, &governance, &proposal_id);
+
+    // The migration's vote and execute callbacks are both turned away, and
+    // the outer execution still completes exactly once
+    let client = crate::StellarGuildsContractClient::new(&env, &contract_id);
+    assert_eq!(
+        client.execute_upgrade_proposal(&governance, &proposal_id),
+        ExecutionOutcome::Executed
+    );
+    assert_eq!(stub.reentry_results(), Some((true, true)));
+    assert_eq!(client.get_current_version(), create_test_version(1, 1, 0));
+    env.as_contract(&contract_id, || {
+        let proposal = storage::get_upgrade_proposal(&env, proposal_id).unwrap();
+        assert_eq!(proposal.status, UpgradeStatus::Executed);
+        assert_eq!(proposal.votes_for, 0);
+        assert!(!storage::is_executing(&env));
+    });
+}
+
+#[test]
+fn test_execution_guard_rejects_entrypoints_and_clears_on_errors() {
+    use crate::keys::upgrade::EXECUTING_KEY;
+
+    let (env, contract_id, governance, proposer) = setup_upgrade_storage();
+    env.as_contract(&contract_id, || {
+        crate::proxy::storage::initialize(&env, Address::generate(&env), governance.clone())
+            .unwrap();
+        crate::proxy::storage::set_skip_version_check(&env, true);
+    });
+    let pending = propose_version(&env, &contract_id, &proposer, create_test_version(1, 0, 1));
+    let approved =
+        create_approved_proposal(&env, &contract_id, &proposer, create_test_version(1, 1, 0));
+    let client = crate::StellarGuildsContractClient::new(&env, &contract_id);
+
+    // While the guard is up every mutating entrypoint fails with ReentrantCall
+    env.as_contract(&contract_id, || {
+        env.storage().instance().set(&EXECUTING_KEY, &true);
+    });
+    assert_eq!(
+        client.try_vote_on_upgrade_proposal(&governance, &pending, &true),
+        Err(Ok(ProposalError::ReentrantCall.into()))
+    );
+    assert_eq!(
+        client.try_execute_upgrade_proposal(&governance, &approved),
+        Err(Ok(ProposalError::ReentrantCall.into()))
+    );
+    assert_eq!(
+        client.try_proxy_emergency_stop(&governance),
+        Err(Ok(ProxyError::ReentrantCall.into()))
+    );
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            logic::execute_proposal(&env, &governance, approved),
+            Err(ProposalError::ReentrantCall.into())
+        );
+        assert_eq!(
+            crate::proxy::implementation::execute_scheduled_upgrade(&env, &governance, 1),
+            Err(ProxyError::ReentrantCall)
+        );
+        // Queries stay open
+        assert_eq!(
+            storage::get_current_version(&env),
+            create_test_version(1, 0, 0)
+        );
+        env.storage().instance().remove(&EXECUTING_KEY);
+    });
+
+    // A failed execution clears the guard on its way out
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            logic::execute_proposal(&env, &governance, pending),
+            Err(UpgradeError::ProposalNotApproved.into())
+        );
+        assert!(!storage::is_executing(&env));
+    });
+    client.vote_on_upgrade_proposal(&governance, &pending, &true);
+    assert_eq!(
+        client.execute_upgrade_proposal(&governance, &approved),
+        ExecutionOutcome::Executed
+    );
+    env.as_contract(&contract_id, || {
+        assert!(!storage::is_executing(&env));
+    });
+}
+
+#[test]
+fn test_hook_failures_name_the_callee_and_shape() {
+    use super::testutils::{MalformedHook, RejectingHook, TrappingHook, STUB_REJECT_CODE};
+
+    let (env, contract_id, governance, proposer) = setup_upgrade_storage();
+    let trapping = env.register_contract(None, TrappingHook);
+    let malformed = env.register_contract(None, MalformedHook);
+    let rejecting = env.register_contract(None, RejectingHook);
+    for hook in [&trapping, &malformed, &rejecting] {
+        env.as_contract(&contract_id, || {
+            logic::add_subscriber(&env, &governance, hook).unwrap();
+        });
+    }
+
+    let proposal_id =
+        create_approved_proposal(&env, &contract_id, &proposer, create_test_version(1, 1, 0));
+    env.as_contract(&contract_id, || {
+        logic::execute_proposal(&env, &governance, proposal_id).unwrap();
+    });
+
+    let call_failed = Symbol::new(&env, "call_failed");
+    let mut failures: Vec<(Address, Symbol, u32, u32)> = Vec::new(&env);
+    for (_, topics, data) in env.events().all().iter() {
+        let action: Result<Symbol, _> = topics.get(1).unwrap().try_into_val(&env);
+        if action == Ok(call_failed.clone()) {
+            failures.push_back(data.into_val(&env));
+        }
+    }
+    let on_upgrade = Symbol::new(&env, "on_upgrade");
+    assert_eq!(
+        failures,
+        Vec::from_array(
+            &env,
+            [
+                (
+                    trapping,
+                    on_upgrade.clone(),
+                    ProposalError::CallTrapped as u32,
+                    0
+                ),
+                (
+                    malformed,
+                    on_upgrade.clone(),
+                    ProposalError::MalformedCallReturn as u32,
+                    0
+                ),
+                (
+                    rejecting,
+                    on_upgrade,
+                    ProposalError::CallRejected as u32,
+                    STUB_REJECT_CODE
+                ),
+            ]
+        )
+    );
+    env.as_contract(&contract_id, || {
+        assert_eq!(storage::get_retry_queue(&env).len(), 3);
+    });
+}
+
+#[test]
+fn test_migration_verdict_defer_resumes_after_not_before() {
+    let (env, contract_id, governance, proposer) = setup_upgrade_storage();
+    env.ledger().with_mut(|l| l.timestamp = 1_000);
+    let proposal_id =
+        setup_verdict_migration(&env, &contract_id, &proposer, &governance, "migrate_defer");
+    let not_before = 1_000 + super::testutils::STUB_DEFER_SECONDS;
+
+    let client = crate::StellarGuildsContractClient::new(&env, &contract_id);
+    assert_eq!(
+        client.try_execute_upgrade_proposal(&governance, &proposal_id),
+        Ok(Ok(ExecutionOutcome::MigrationDeferred(not_before)))
+    );
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            storage::get_upgrade_proposal(&env, proposal_id)
+                .unwrap()
+                .status,
+            UpgradeStatus::Approved
+        );
+        let progress = storage::get_migration_progress(&env, proposal_id).unwrap();
+        assert_eq!(progress.status, MigrationStatus::InProgress);
+        assert_eq!(progress.not_before, not_before);
+        assert_eq!(
+            logic::get_execution_blockers(&env, proposal_id),
+            Vec::from_array(&env, [Blocker::MigrationDeferred(not_before)])
+        );
+    });
+
+    // The not-before time is enforced
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            logic::continue_migration(&env, &governance, proposal_id),
+            Err(ProposalError::MigrationDeferred.into())
+        );
+    });
+
+    env.ledger().with_mut(|l| l.timestamp = not_before);
+    assert_eq!(
+        client.continue_migration(&governance, &proposal_id),
+        ExecutionOutcome::Executed
+    );
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            storage::get_current_version(&env),
+            create_test_version(1, 1, 0)
+        );
+        assert_eq!(
+            storage::get_upgrade_proposal(&env, proposal_id)
+                .unwrap()
+                .status,
+            UpgradeStatus::Executed
+        );
+        assert_eq!(
+            storage::get_migration_entry(&env, &MigrationNamespace::Live, &symbol_short!("alpha")),
+            Some(Bytes::from_slice(&env, b"a1\x01"))
+        );
+        assert_eq!(
+            storage::get_migration_progress(&env, proposal_id)
+                .unwrap()
+                .status,
+            MigrationStatus::Completed
+        );
+    });
+}
+
+/// Open a one-hour revert window, put a proxy in front of `original` and
+/// execute a migrating upgrade at t=1_000. Returns (proposal_id, original).
+fn execute_with_revert_window(
+    env: &Env,
+    contract_id: &Address,
+    governance: &Address,
+    proposer: &Address,
+) -> (u64, Address) {
+    let original = Address::generate(env);
+    env.ledger().with_mut(|l| l.timestamp = 1_000);
+    env.as_contract(contract_id, || {
+        crate::proxy::storage::initialize(env, original.clone(), governance.clone()).unwrap();
+        crate::proxy::storage::set_skip_version_check(env, true);
+        let mut config = storage::get_governance_config(env);
+        config.revert_window = 3_600;
+        storage::set_governance_config(env, &config);
+    });
+    let proposal_id = setup_verdict_migration(env, contract_id, proposer, governance, "migrate_ok");
+    env.as_contract(contract_id, || {
+        logic::execute_proposal(env, governance, proposal_id).unwrap();
+    });
+    (proposal_id, original)
+}
+
+#[test]
+fn test_instant_revert_inside_window_restores_previous_state() {
+    let (env, contract_id, governance, proposer) = setup_upgrade_storage();
+    let (proposal_id, original) =
+        execute_with_revert_window(&env, &contract_id, &governance, &proposer);
+    let guardian = Address::generate(&env);
+    env.as_contract(&contract_id, || {
+        auth::grant_role(&env, &governance, Role::Guardian, &guardian).unwrap();
+    });
+    let next_id =
+        create_approved_proposal(&env, &contract_id, &proposer, create_test_version(1, 2, 0));
+
+    env.as_contract(&contract_id, || {
+        let receipt = storage::get_execution_receipt(&env, proposal_id).unwrap();
+        assert_eq!(receipt.previous_implementation, Some(original.clone()));
+        // The proxy has since switched to the new implementation
+        crate::proxy::storage::set_implementation(&env, &receipt.implementation);
+
+        // Further upgrades are frozen while the window is open
+        assert_eq!(
+            logic::get_execution_blockers(&env, next_id),
+            Vec::from_array(&env, [Blocker::RevertWindowOpen(4_600)])
+        );
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            logic::execute_proposal(&env, &governance, next_id),
+            Err(ProposalError::RevertWindowOpen.into())
+        );
+    });
+
+    // State was migrated, so reverting needs a reverse migration or force
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            logic::instant_revert(&env, &guardian, proposal_id, false),
+            Err(UpgradeError::ReverseMigrationMissing.into())
+        );
+    });
+    let reverse = MigrationPlan {
+        from_version: create_test_version(1, 1, 0),
+        to_version: create_test_version(1, 0, 0),
+        migration_function_selector: Symbol::new(&env, "migrate_ok"),
+        estimated_gas: 100,
+    };
+    env.as_contract(&contract_id, || {
+        logic::register_reverse_migration(&env, &governance, proposal_id, &reverse).unwrap();
+    });
+
+    env.ledger().with_mut(|l| l.timestamp = 2_000);
+    env.as_contract(&contract_id, || {
+        logic::instant_revert(&env, &guardian, proposal_id, false).unwrap();
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            storage::get_current_version(&env),
+            create_test_version(1, 0, 0)
+        );
+        assert_eq!(crate::proxy::storage::get_implementation(&env), original);
+        // The proxy recorded the switch back as a revert
+        let switch = crate::proxy::storage::get_latest_upgrade(&env).unwrap();
+        assert!(switch.success && switch.revert);
+        assert_eq!(switch.new_implementation, original);
+        assert_eq!(switch.initiator, guardian);
+        assert_eq!(
+            storage::get_upgrade_proposal(&env, proposal_id)
+                .unwrap()
+                .status,
+            UpgradeStatus::Reverted
+        );
+        // The reverse migration ran over the live entries
+        assert_eq!(
+            storage::get_migration_entry(&env, &MigrationNamespace::Live, &symbol_short!("alpha")),
+            Some(Bytes::from_slice(&env, b"a1\x01\x00"))
+        );
+        assert!(storage::get_revert_window(&env).is_none());
+        assert!(logic::get_execution_blockers(&env, next_id).is_empty());
+    });
+}
+
+#[test]
+fn test_instant_revert_outside_window_fails() {
+    let (env, contract_id, governance, proposer) = setup_upgrade_storage();
+    let (proposal_id, _) = execute_with_revert_window(&env, &contract_id, &governance, &proposer);
+    let next_id =
+        create_approved_proposal(&env, &contract_id, &proposer, create_test_version(1, 2, 0));
+
+    env.ledger().with_mut(|l| l.timestamp = 4_600);
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            logic::instant_revert(&env, &governance, proposal_id, true),
+            Err(UpgradeError::RevertWindowClosed.into())
+        );
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            storage::get_upgrade_proposal(&env, proposal_id)
+                .unwrap()
+                .status,
+            UpgradeStatus::Executed
+        );
+        assert_eq!(
+            storage::get_current_version(&env),
+            create_test_version(1, 1, 0)
+        );
+        // With the window elapsed upgrades resume
+        assert!(logic::get_execution_blockers(&env, next_id).is_empty());
+    });
+    env.as_contract(&contract_id, || {
+        logic::execute_proposal(&env, &governance, next_id).unwrap();
+    });
+}
+
+#[test]
+fn test_confirm_upgrade_ends_revert_window_early() {
+    let (env, contract_id, governance, proposer) = setup_upgrade_storage();
+    let (proposal_id, _) = execute_with_revert_window(&env, &contract_id, &governance, &proposer);
+    let next_id =
+        create_approved_proposal(&env, &contract_id, &proposer, create_test_version(1, 2, 0));
+
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            logic::confirm_upgrade(&env, &proposer, proposal_id),
+            Err(UpgradeError::NotAuthorized)
+        );
+    });
+    env.as_contract(&contract_id, || {
+        logic::confirm_upgrade(&env, &governance, proposal_id).unwrap();
+    });
+    env.as_contract(&contract_id, || {
+        assert!(storage::get_revert_window(&env).is_none());
+        assert_eq!(
+            logic::instant_revert(&env, &governance, proposal_id, true),
+            Err(UpgradeError::RevertWindowClosed.into())
+        );
+    });
+    env.as_contract(&contract_id, || {
+        logic::execute_proposal(&env, &governance, next_id).unwrap();
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            storage::get_current_version(&env),
+            create_test_version(1, 2, 0)
+        );
+    });
+}
+
+/// Start a live migration that the implementation defers, leaving it
+/// `InProgress` until `not_before`. Returns (proposal_id, not_before).
+fn start_deferred_migration(
+    env: &Env,
+    contract_id: &Address,
+    governance: &Address,
+    proposer: &Address,
+) -> (u64, u64) {
+    env.ledger().with_mut(|l| l.timestamp = 1_000);
+    let proposal_id =
+        setup_verdict_migration(env, contract_id, proposer, governance, "migrate_defer");
+    let not_before = 1_000 + super::testutils::STUB_DEFER_SECONDS;
+    env.as_contract(contract_id, || {
+        assert_eq!(
+            logic::execute_proposal(env, governance, proposal_id),
+            Ok(ExecutionOutcome::MigrationDeferred(not_before))
+        );
+    });
+    (proposal_id, not_before)
+}
+
+#[test]
+fn test_pausing_over_in_progress_migration_requires_force() {
+    let (env, contract_id, governance, proposer) = setup_upgrade_storage();
+    let (proposal_id, not_before) =
+        start_deferred_migration(&env, &contract_id, &governance, &proposer);
+
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            logic::pause_governance(&env, &governance, false),
+            Err(UpgradeError::MigrationWouldStrand)
+        );
+    });
+    env.as_contract(&contract_id, || {
+        let health = logic::health(&env);
+        assert!(!health.governance_paused);
+        assert_eq!(
+            health.migrations_in_progress,
+            Vec::from_array(&env, [proposal_id])
+        );
+        assert!(!health.needs_attention);
+    });
+
+    env.as_contract(&contract_id, || {
+        logic::pause_governance(&env, &governance, true).unwrap();
+    });
+    env.as_contract(&contract_id, || {
+        let health = logic::health(&env);
+        assert!(health.governance_paused);
+        assert_eq!(
+            health.stranded_migrations,
+            Vec::from_array(&env, [proposal_id])
+        );
+        assert!(health.needs_attention);
+    });
+
+    // Continuing the migration is remediation and goes ahead under the pause
+    env.ledger().with_mut(|l| l.timestamp = not_before);
+    env.as_contract(&contract_id, || {
+        logic::continue_migration(&env, &governance, proposal_id).unwrap();
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            storage::get_upgrade_proposal(&env, proposal_id)
+                .unwrap()
+                .status,
+            UpgradeStatus::Executed
+        );
+        let health = logic::health(&env);
+        assert!(health.governance_paused);
+        assert!(health.migrations_in_progress.is_empty());
+        assert!(health.stranded_migrations.is_empty());
+        assert!(!health.needs_attention);
+    });
+}
+
+#[test]
+fn test_governance_pause_blocks_proposing_voting_and_executing() {
+    let (env, contract_id, governance, proposer) = setup_upgrade_storage();
+    let guardian = Address::generate(&env);
+    let voter = Address::generate(&env);
+    env.as_contract(&contract_id, || {
+        auth::grant_role(&env, &governance, Role::Guardian, &guardian).unwrap();
+        storage::set_voting_power(&env, &voter, 1);
+    });
+    let approved_id =
+        create_approved_proposal(&env, &contract_id, &proposer, create_test_version(1, 1, 0));
+    let pending_id = propose_version(&env, &contract_id, &proposer, create_test_version(1, 2, 0));
+
+    // Nothing is migrating, so the guardian can pause without force
+    env.as_contract(&contract_id, || {
+        logic::pause_governance(&env, &guardian, false).unwrap();
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            logic::pause_governance(&env, &governance, false),
+            Err(UpgradeError::GovernancePaused)
+        );
+        assert_eq!(
+            storage::get_governance_pause(&env).unwrap().paused_by,
+            guardian
+        );
+        assert_eq!(
+            logic::get_execution_blockers(&env, approved_id),
+            Vec::from_array(&env, [Blocker::GovernancePaused])
+        );
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            logic::execute_proposal(&env, &governance, approved_id),
+            Err(UpgradeError::GovernancePaused.into())
+        );
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            logic::vote_on_proposal(&env, &voter, pending_id, true),
+            Err(UpgradeError::GovernancePaused.into())
+        );
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            logic::propose_upgrade(
+                &env,
+                &proposer,
+                &Address::generate(&env),
+                &create_test_version(1, 3, 0),
+                String::from_str(&env, "paused"),
+            ),
+            Err(UpgradeError::GovernancePaused.into())
+        );
+    });
+
+    // Only governance lifts the pause
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            logic::resume_governance(&env, &guardian),
+            Err(UpgradeError::NotAuthorized)
+        );
+    });
+    env.as_contract(&contract_id, || {
+        logic::resume_governance(&env, &governance).unwrap();
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            logic::resume_governance(&env, &governance),
+            Err(UpgradeError::GovernanceNotPaused)
+        );
+    });
+    env.as_contract(&contract_id, || {
+        logic::execute_proposal(&env, &governance, approved_id).unwrap();
+    });
+}
+
+#[test]
+fn test_proxy_pause_blocks_new_executions_but_not_continuations() {
+    let (env, contract_id, governance, proposer) = setup_upgrade_storage();
+    let (proposal_id, not_before) =
+        start_deferred_migration(&env, &contract_id, &governance, &proposer);
+    let next_id =
+        create_approved_proposal(&env, &contract_id, &proposer, create_test_version(1, 2, 0));
+
+    // Route executions through a delayed proxy, then pause it
+    env.as_contract(&contract_id, || {
+        crate::proxy::storage::initialize(&env, Address::generate(&env), governance.clone())
+            .unwrap();
+        crate::proxy::storage::set_min_upgrade_delay(&env, 60);
+        storage::set_safe_mode(&env, true);
+    });
+    env.as_contract(&contract_id, || {
+        assert!(logic::health(&env).proxy_paused);
+        assert!(logic::get_execution_blockers(&env, next_id).contains(Blocker::ProxyPaused));
+        assert_eq!(
+            logic::execute_proposal(&env, &governance, next_id),
+            Err(ProposalError::ProxyPaused.into())
+        );
+    });
+
+    env.ledger().with_mut(|l| l.timestamp = not_before);
+    env.as_contract(&contract_id, || {
+        logic::continue_migration(&env, &governance, proposal_id).unwrap();
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            storage::get_current_version(&env),
+            create_test_version(1, 1, 0)
+        );
+        assert!(logic::health(&env).migrations_in_progress.is_empty());
+    });
+}
+
+/// A pending proposal one power-1 voter cannot decide alone
+fn setup_single_voter(env: &Env, contract_id: &Address, proposer: &Address) -> (u64, Address) {
+    let voter = Address::generate(env);
+    env.as_contract(contract_id, || {
+        storage::set_voting_power(env, &voter, 1);
+    });
+    let proposal_id = propose_version(env, contract_id, proposer, create_test_version(1, 1, 0));
+    env.as_contract(contract_id, || {
+        let mut proposal = storage::get_upgrade_proposal(env, proposal_id).unwrap();
+        proposal.total_voting_power_snapshot = 10;
+        storage::store_upgrade_proposal(env, &proposal);
+    });
+    (proposal_id, voter)
+}
+
+#[test]
+fn test_voting_twice_in_same_direction_fails() {
+    let (env, contract_id, _, proposer) = setup_upgrade_storage();
+    let (proposal_id, voter) = setup_single_voter(&env, &contract_id, &proposer);
+
+    env.as_contract(&contract_id, || {
+        assert!(!storage::has_voted(&env, proposal_id, &voter));
+        logic::vote_on_proposal(&env, &voter, proposal_id, true).unwrap();
+    });
+    env.as_contract(&contract_id, || {
+        assert!(storage::has_voted(&env, proposal_id, &voter));
+        assert_eq!(
+            logic::vote_on_proposal(&env, &voter, proposal_id, true),
+            Err(UpgradeError::AlreadyVoted.into())
+        );
+    });
+    env.as_contract(&contract_id, || {
+        let proposal = storage::get_upgrade_proposal(&env, proposal_id).unwrap();
+        assert_eq!(proposal.votes_for, 1);
+        assert_eq!(proposal.status, UpgradeStatus::Pending);
+        assert_eq!(storage::get_vote_receipts(&env, proposal_id).len(), 1);
+    });
+}
+
+#[test]
+fn test_voting_twice_in_opposite_directions_fails() {
+    let (env, contract_id, _, proposer) = setup_upgrade_storage();
+    let (proposal_id, voter) = setup_single_voter(&env, &contract_id, &proposer);
+    let other = Address::generate(&env);
+
+    env.as_contract(&contract_id, || {
+        logic::vote_on_proposal(&env, &voter, proposal_id, false).unwrap();
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            logic::vote_on_proposal(&env, &voter, proposal_id, true),
+            Err(UpgradeError::AlreadyVoted.into())
+        );
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            logic::cast_vote(&env, &voter, proposal_id, VoteChoice::Abstain),
+            Err(UpgradeError::AlreadyVoted.into())
+        );
+    });
+    env.as_contract(&contract_id, || {
+        let proposal = storage::get_upgrade_proposal(&env, proposal_id).unwrap();
+        assert_eq!(
+            (
+                proposal.votes_for,
+                proposal.votes_against,
+                proposal.votes_abstain
+            ),
+            (0, 1, 0)
+        );
+        assert!(storage::has_voted(&env, proposal_id, &voter));
+        assert!(!storage::has_voted(&env, proposal_id, &other));
+    });
+}
+
+/// Propose at t=1_000 under a 100 second voting period and a registry of
+/// `powers`, returning the proposal and its voters
+fn setup_deadline_proposal(
+    env: &Env,
+    contract_id: &Address,
+    governance: &Address,
+    proposer: &Address,
+    powers: &[u32],
+) -> (u64, Vec<Address>) {
+    env.ledger().with_mut(|l| l.timestamp = 1_000);
+    let mut voters = Vec::new(env);
+    env.as_contract(contract_id, || {
+        for power in powers {
+            let voter = Address::generate(env);
+            storage::set_voting_power(env, &voter, *power);
+            voters.push_back(voter);
+        }
+    });
+    env.as_contract(contract_id, || {
+        logic::set_voting_period(env, governance, 100).unwrap();
+    });
+    let proposal_id = propose_version(env, contract_id, proposer, create_test_version(1, 1, 0));
+    (proposal_id, voters)
+}
+
+#[test]
+fn test_vote_at_deadline_counts_and_later_votes_are_refused() {
+    let (env, contract_id, governance, proposer) = setup_upgrade_storage();
+    let (proposal_id, voters) =
+        setup_deadline_proposal(&env, &contract_id, &governance, &proposer, &[1, 1, 1, 1]);
+
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            storage::get_upgrade_proposal(&env, proposal_id)
+                .unwrap()
+                .voting_deadline,
+            1_100
+        );
+    });
+
+    env.ledger().with_mut(|l| l.timestamp = 1_100);
+    env.as_contract(&contract_id, || {
+        logic::vote_on_proposal(&env, &voters.get(0).unwrap(), proposal_id, true).unwrap();
+    });
+    env.ledger().with_mut(|l| l.timestamp = 1_101);
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            logic::vote_on_proposal(&env, &voters.get(1).unwrap(), proposal_id, true),
+            Err(ProposalError::VotingClosed.into())
+        );
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            storage::get_upgrade_proposal(&env, proposal_id)
+                .unwrap()
+                .votes_for,
+            1
+        );
+    });
+}
+
+#[test]
+fn test_finalize_after_deadline_settles_by_tallies() {
+    let (env, contract_id, governance, proposer) = setup_upgrade_storage();
+    let (approved_id, voters) =
+        setup_deadline_proposal(&env, &contract_id, &governance, &proposer, &[3, 1, 6]);
+    let rejected_id = propose_version(&env, &contract_id, &proposer, create_test_version(1, 2, 0));
+    let silent_id = propose_version(&env, &contract_id, &proposer, create_test_version(1, 3, 0));
+
+    // 3 For and 1 Against of 10 decides nothing while voting is open
+    env.as_contract(&contract_id, || {
+        logic::vote_on_proposal(&env, &voters.get(0).unwrap(), approved_id, true).unwrap();
+    });
+    env.as_contract(&contract_id, || {
+        logic::vote_on_proposal(&env, &voters.get(1).unwrap(), approved_id, false).unwrap();
+    });
+    env.as_contract(&contract_id, || {
+        logic::vote_on_proposal(&env, &voters.get(1).unwrap(), rejected_id, true).unwrap();
+    });
+    env.as_contract(&contract_id, || {
+        logic::vote_on_proposal(&env, &voters.get(0).unwrap(), rejected_id, false).unwrap();
+    });
+
+    env.ledger().with_mut(|l| l.timestamp = 1_100);
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            logic::finalize_proposal(&env, approved_id),
+            Err(ProposalError::VotingPeriodActive.into())
+        );
+    });
+
+    env.ledger().with_mut(|l| l.timestamp = 1_101);
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            logic::finalize_proposal(&env, approved_id),
+            Ok(UpgradeStatus::Approved)
+        );
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            logic::finalize_proposal(&env, rejected_id),
+            Ok(UpgradeStatus::Rejected)
+        );
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            logic::finalize_proposal(&env, silent_id),
+            Ok(UpgradeStatus::Expired)
+        );
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            storage::get_upgrade_proposal(&env, silent_id)
+                .unwrap()
+                .status,
+            UpgradeStatus::Expired
+        );
+    });
+}
+
+#[test]
+fn test_finalize_without_quorum_expires() {
+    let (env, contract_id, governance, proposer) = setup_upgrade_storage();
+    env.as_contract(&contract_id, || {
+        let mut config = storage::get_governance_config(&env);
+        config.quorum_bps = 5_000;
+        storage::set_governance_config(&env, &config);
+    });
+    let (proposal_id, voters) =
+        setup_deadline_proposal(&env, &contract_id, &governance, &proposer, &[1, 9]);
+
+    env.as_contract(&contract_id, || {
+        logic::vote_on_proposal(&env, &voters.get(0).unwrap(), proposal_id, true).unwrap();
+    });
+    env.ledger().with_mut(|l| l.timestamp = 2_000);
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            logic::finalize_proposal(&env, proposal_id),
+            Ok(UpgradeStatus::Expired)
+        );
+    });
+}
+
+#[test]
+fn test_finalize_keeps_status_reached_early() {
+    let (env, contract_id, governance, proposer) = setup_upgrade_storage();
+    let (proposal_id, voters) =
+        setup_deadline_proposal(&env, &contract_id, &governance, &proposer, &[6, 4]);
+
+    env.as_contract(&contract_id, || {
+        logic::vote_on_proposal(&env, &voters.get(0).unwrap(), proposal_id, true).unwrap();
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            storage::get_upgrade_proposal(&env, proposal_id)
+                .unwrap()
+                .status,
+            UpgradeStatus::Approved
+        );
+    });
+
+    // Finalizing after the deadline is a no-op that reports the decision
+    env.ledger().with_mut(|l| l.timestamp = 2_000);
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            logic::finalize_proposal(&env, proposal_id),
+            Ok(UpgradeStatus::Approved)
+        );
+    });
+    env.as_contract(&contract_id, || {
+        logic::execute_proposal(&env, &governance, proposal_id).unwrap();
+    });
+}
+
+#[test]
+fn test_proposer_can_cancel_pending_proposal() {
+    let (env, contract_id, governance, proposer) = setup_upgrade_storage();
+    let (proposal_id, voter) = setup_single_voter(&env, &contract_id, &proposer);
+
+    env.as_contract(&contract_id, || {
+        logic::vote_on_proposal(&env, &voter, proposal_id, true).unwrap();
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            logic::cancel_proposal(&env, &Address::generate(&env), proposal_id),
+            Err(UpgradeError::NotAuthorized.into())
+        );
+    });
+    env.as_contract(&contract_id, || {
+        logic::cancel_proposal(&env, &proposer, proposal_id).unwrap();
+        assert_eq!(
+            storage::get_upgrade_proposal(&env, proposal_id)
+                .unwrap()
+                .status,
+            UpgradeStatus::Cancelled
+        );
+    });
+
+    // The votes already cast no longer lead anywhere
+    env.as_contract(&contract_id, || {
+        assert!(
+            logic::vote_on_proposal(&env, &Address::generate(&env), proposal_id, true).is_err()
+        );
+    });
+    env.as_contract(&contract_id, || {
+        assert!(logic::execute_proposal(&env, &governance, proposal_id).is_err());
+        assert_eq!(
+            logic::cancel_proposal(&env, &proposer, proposal_id),
+            Err(UpgradeError::ProposalNotPending.into())
+        );
+    });
+}
+
+#[test]
+fn test_governance_can_cancel_but_not_after_decision() {
+    let (env, contract_id, governance, proposer) = setup_upgrade_storage();
+    let pending = propose_version(&env, &contract_id, &proposer, create_test_version(1, 1, 0));
+    let approved =
+        create_approved_proposal(&env, &contract_id, &proposer, create_test_version(1, 2, 0));
+
+    env.as_contract(&contract_id, || {
+        assert!(logic::cancel_proposal(&env, &governance, pending).is_ok());
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            logic::cancel_proposal(&env, &proposer, approved),
+            Err(UpgradeError::ProposalNotPending.into())
+        );
+    });
+}
+
+/// Two silent proposals whose 100-second voting period has run out
+fn setup_expired_proposals(
+    env: &Env,
+    contract_id: &Address,
+    governance: &Address,
+    proposer: &Address,
+) -> (u64, u64) {
+    let (first, _) = setup_deadline_proposal(env, contract_id, governance, proposer, &[1]);
+    let second = propose_version(env, contract_id, proposer, create_test_version(1, 2, 0));
+    env.ledger().with_mut(|l| l.timestamp = 1_200);
+    (first, second)
+}
+
+#[test]
+fn test_run_maintenance_shares_work_budget_across_tasks() {
+    let (env, contract_id, governance, proposer) = setup_upgrade_storage();
+    let (first, second) = setup_expired_proposals(&env, &contract_id, &governance, &proposer);
+    let keeper = Address::generate(&env);
+
+    let tasks = Vec::from_array(
+        &env,
+        [
+            MaintenanceTask::SweepExpired,
+            MaintenanceTask::RetryNotifications,
+            MaintenanceTask::BumpTtl,
+        ],
+    );
+    let report = env.as_contract(&contract_id, || {
+        logic::run_maintenance(&env, &keeper, &tasks, 3).unwrap()
+    });
+    assert_eq!(
+        report.completed,
+        Vec::from_array(
+            &env,
+            [
+                (MaintenanceTask::SweepExpired, 2),
+                (MaintenanceTask::RetryNotifications, 0),
+                (MaintenanceTask::BumpTtl, 1),
+            ]
+        )
+    );
+    assert_eq!((report.work_units, report.paid), (3, 0));
+
+    env.as_contract(&contract_id, || {
+        for proposal_id in [first, second] {
+            assert_eq!(
+                storage::get_upgrade_proposal(&env, proposal_id)
+                    .unwrap()
+                    .status,
+                UpgradeStatus::Expired
+            );
+        }
+        let stats = storage::get_keeper_stats(&env, &keeper);
+        assert_eq!((stats.runs, stats.work_units, stats.paid), (1, 3, 0));
+    });
+}
+
+#[test]
+fn test_run_maintenance_pays_bounty_per_work_unit() {
+    let (env, contract_id, governance, proposer) = setup_upgrade_storage();
+    setup_expired_proposals(&env, &contract_id, &governance, &proposer);
+    let keeper = Address::generate(&env);
+    let token = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    soroban_sdk::token::StellarAssetClient::new(&env, &token).mint(&contract_id, &1_000);
+
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            logic::set_keeper_config(
+                &env,
+                &governance,
+                &KeeperConfig {
+                    token: Some(token.clone()),
+                    bounty_per_unit: -1,
+                    cooldown: 0,
+                },
+            ),
+            Err(UpgradeError::InvalidKeeperBounty)
+        );
+    });
+    env.as_contract(&contract_id, || {
+        logic::set_keeper_config(
+            &env,
+            &governance,
+            &KeeperConfig {
+                token: Some(token.clone()),
+                bounty_per_unit: 25,
+                cooldown: 0,
+            },
+        )
+        .unwrap();
+    });
+
+    let tasks = Vec::from_array(&env, [MaintenanceTask::SweepExpired]);
+    let report = env.as_contract(&contract_id, || {
+        logic::run_maintenance(&env, &keeper, &tasks, 10).unwrap()
+    });
+    assert_eq!((report.work_units, report.paid), (2, 50));
+
+    let token_client = soroban_sdk::token::Client::new(&env, &token);
+    assert_eq!(token_client.balance(&keeper), 50);
+    assert_eq!(token_client.balance(&contract_id), 950);
+    env.as_contract(&contract_id, || {
+        assert_eq!(storage::get_keeper_stats(&env, &keeper).paid, 50);
+    });
+}
+
+#[test]
+fn test_run_maintenance_rejects_task_in_cooldown() {
+    let (env, contract_id, governance, proposer) = setup_upgrade_storage();
+    setup_expired_proposals(&env, &contract_id, &governance, &proposer);
+    let keeper = Address::generate(&env);
+
+    env.as_contract(&contract_id, || {
+        logic::set_keeper_config(
+            &env,
+            &governance,
+            &KeeperConfig {
+                token: None,
+                bounty_per_unit: 0,
+                cooldown: 60,
+            },
+        )
+        .unwrap();
+    });
+
+    let sweep = Vec::from_array(&env, [MaintenanceTask::SweepExpired]);
+    env.as_contract(&contract_id, || {
+        logic::run_maintenance(&env, &keeper, &sweep, 10).unwrap();
+    });
+    env.ledger().with_mut(|l| l.timestamp = 1_259);
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            logic::run_maintenance(&env, &keeper, &sweep, 10),
+            Err(UpgradeError::TaskCoolingDown)
+        );
+    });
+    // Other tasks have their own cooldown
+    env.as_contract(&contract_id, || {
+        let bump = Vec::from_array(&env, [MaintenanceTask::BumpTtl]);
+        assert!(logic::run_maintenance(&env, &keeper, &bump, 10).is_ok());
+    });
+
+    env.ledger().with_mut(|l| l.timestamp = 1_260);
+    env.as_contract(&contract_id, || {
+        assert!(logic::run_maintenance(&env, &keeper, &sweep, 10).is_ok());
+    });
+}
+
+#[test]
+fn test_power_changes_after_creation_do_not_move_tally() {
+    let (env, contract_id, _, proposer) = setup_upgrade_storage();
+    let (steady, boosted, latecomer) = (
+        Address::generate(&env),
+        Address::generate(&env),
+        Address::generate(&env),
+    );
+    env.as_contract(&contract_id, || {
+        storage::set_voting_power(&env, &steady, 4);
+        storage::set_voting_power(&env, &boosted, 2);
+    });
+    let proposal_id = propose_version(&env, &contract_id, &proposer, create_test_version(1, 1, 0));
+
+    env.as_contract(&contract_id, || {
+        let proposal = storage::get_upgrade_proposal(&env, proposal_id).unwrap();
+        assert_eq!(
+            (proposal.total_voters, proposal.total_voting_power_snapshot),
+            (2, 6)
+        );
+        storage::set_voting_power(&env, &boosted, 50);
+        storage::set_voting_power(&env, &steady, 0);
+        storage::set_voting_power(&env, &latecomer, 9);
+    });
+
+    for voter in [&steady, &boosted, &latecomer] {
+        env.as_contract(&contract_id, || {
+            logic::cast_vote(&env, voter, proposal_id, VoteChoice::Abstain).unwrap();
+        });
+    }
+    env.as_contract(&contract_id, || {
+        let proposal = storage::get_upgrade_proposal(&env, proposal_id).unwrap();
+        assert_eq!(proposal.votes_abstain, 6);
+        let receipts = storage::get_vote_receipts(&env, proposal_id);
+        for (i, weight) in [4, 2, 0].into_iter().enumerate() {
+            assert_eq!(receipts.get(i as u32).unwrap().weight, weight);
+        }
+    });
+}
+
+fn set_approval_threshold(
+    env: &Env,
+    contract_id: &Address,
+    governance: &Address,
+    quorum_bps: u32,
+    approval_threshold_bps: u32,
+) {
+    env.as_contract(contract_id, || {
+        logic::set_governance_config(
+            env,
+            governance,
+            &GovernanceConfig {
+                quorum_bps,
+                approval_threshold_bps,
+                ..storage::get_governance_config(env)
+            },
+        )
+        .unwrap();
+    });
+}
+
+fn proposal_status(env: &Env, contract_id: &Address, proposal_id: u64) -> UpgradeStatus {
+    env.as_contract(contract_id, || {
+        storage::get_upgrade_proposal(env, proposal_id)
+            .unwrap()
+            .status
+    })
+}
+
+#[test]
+fn test_zero_approval_threshold_still_needs_quorum() {
+    let (env, contract_id, governance, proposer) = setup_upgrade_storage();
+    set_approval_threshold(&env, &contract_id, &governance, 5_000, 0);
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            logic::set_governance_config(
+                &env,
+                &governance,
+                &GovernanceConfig {
+                    approval_threshold_bps: 10_001,
+                    ..storage::get_governance_config(&env)
+                },
+            ),
+            Err(ProposalError::InvalidApprovalThreshold.into())
+        );
+    });
+    let (proposal_id, voters) =
+        setup_deadline_proposal(&env, &contract_id, &governance, &proposer, &[1, 1, 1, 1]);
+
+    // A lone for vote clears the ratio but not the quorum
+    env.as_contract(&contract_id, || {
+        logic::cast_vote(&env, &voters.get(0).unwrap(), proposal_id, VoteChoice::For).unwrap();
+    });
+    assert_eq!(
+        proposal_status(&env, &contract_id, proposal_id),
+        UpgradeStatus::Pending
+    );
+
+    // An against vote brings quorum, and any for share beats a 0% threshold
+    env.as_contract(&contract_id, || {
+        logic::cast_vote(
+            &env,
+            &voters.get(1).unwrap(),
+            proposal_id,
+            VoteChoice::Against,
+        )
+        .unwrap();
+    });
+    assert_eq!(
+        proposal_status(&env, &contract_id, proposal_id),
+        UpgradeStatus::Approved
+    );
+}
+
+#[test]
+fn test_full_approval_threshold_requires_unanimity() {
+    let (env, contract_id, governance, proposer) = setup_upgrade_storage();
+    set_approval_threshold(&env, &contract_id, &governance, 0, 10_000);
+    let (proposal_id, voters) =
+        setup_deadline_proposal(&env, &contract_id, &governance, &proposer, &[1, 1, 1]);
+    for voter in voters.iter() {
+        env.as_contract(&contract_id, || {
+            logic::cast_vote(&env, &voter, proposal_id, VoteChoice::For).unwrap();
+        });
+    }
+    assert_eq!(
+        proposal_status(&env, &contract_id, proposal_id),
+        UpgradeStatus::Approved
+    );
+
+    // A single against vote blocks
+    let (env, contract_id, governance, proposer) = setup_upgrade_storage();
+    set_approval_threshold(&env, &contract_id, &governance, 0, 10_000);
+    let (proposal_id, voters) =
+        setup_deadline_proposal(&env, &contract_id, &governance, &proposer, &[5, 5, 1]);
+    env.as_contract(&contract_id, || {
+        logic::cast_vote(&env, &voters.get(0).unwrap(), proposal_id, VoteChoice::For).unwrap();
+    });
+    env.as_contract(&contract_id, || {
+        logic::cast_vote(
+            &env,
+            &voters.get(2).unwrap(),
+            proposal_id,
+            VoteChoice::Against,
+        )
+        .unwrap();
+    });
+    assert_eq!(
+        proposal_status(&env, &contract_id, proposal_id),
+        UpgradeStatus::Rejected
+    );
+    assert!(!approval_ratio_met(10, 1, 10_000));
+    assert!(!approval_ratio_met(0, 0, 10_000));
+}
+
+#[test]
+fn test_approval_exactly_at_threshold_is_rejected() {
+    // The for share must strictly exceed the threshold, with no rounding
+    assert!(!approval_ratio_met(3, 2, 6_000));
+    assert!(approval_ratio_met(3_001, 2_000, 6_000));
+    assert!(approval_ratio_met(1, 2, 3_333));
+    assert!(!approval_ratio_met(1, 2, 3_334));
+    assert!(!approval_ratio_met(1, 1, SIMPLE_MAJORITY_BPS));
+
+    let (env, contract_id, governance, proposer) = setup_upgrade_storage();
+    set_approval_threshold(&env, &contract_id, &governance, 0, 6_000);
+    let (proposal_id, voters) =
+        setup_deadline_proposal(&env, &contract_id, &governance, &proposer, &[3, 2, 5]);
+    env.as_contract(&contract_id, || {
+        logic::cast_vote(&env, &voters.get(0).unwrap(), proposal_id, VoteChoice::For).unwrap();
+    });
+    env.as_contract(&contract_id, || {
+        logic::cast_vote(
+            &env,
+            &voters.get(1).unwrap(),
+            proposal_id,
+            VoteChoice::Against,
+        )
+        .unwrap();
+    });
+    env.ledger().with_mut(|l| l.timestamp = 1_101);
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            logic::finalize_proposal(&env, proposal_id),
+            Ok(UpgradeStatus::Rejected)
+        );
+    });
+}
+
+#[test]
+fn test_schema_step_fills_in_approval_threshold() {
+    let (env, contract_id, governance, _) = setup_upgrade_storage();
+    set_approval_threshold(&env, &contract_id, &governance, 2_500, 7_000);
+    env.as_contract(&contract_id, || {
+        // Rewrite the config in its schema 2 layout
+        let key = GOVERNANCE_CONFIG_KEY;
+        let mut fields: Map<Symbol, soroban_sdk::Val> =
+            env.storage().persistent().get(&key).unwrap();
+        fields.remove(Symbol::new(&env, "approval_threshold_bps"));
+        env.storage().persistent().set(&key, &fields);
+        storage::set_storage_schema(&env, 2);
+    });
+
+    env.as_contract(&contract_id, || {
+        assert_eq!(logic::migrate_storage_schema(&env, &governance, 3), Ok(1));
+    });
+    env.as_contract(&contract_id, || {
+        let config = storage::get_governance_config(&env);
+        assert_eq!(config.quorum_bps, 2_500);
+        assert_eq!(config.approval_threshold_bps, SIMPLE_MAJORITY_BPS);
+    });
+}
+
+/// Major-version proposal by `proposer` (holding 4 units of power) on a
+/// supermajority track with separation of duties and a 70% quorum
+fn setup_separated_proposal(
+    env: &Env,
+    contract_id: &Address,
+    governance: &Address,
+    proposer: &Address,
+    powers: &[u32],
+) -> (u64, Vec<Address>) {
+    let mut voters = Vec::new(env);
+    env.as_contract(contract_id, || {
+        let mut track = storage::get_governance_track(env, SUPERMAJORITY_TRACK_ID).unwrap();
+        track.threshold_bps = SIMPLE_MAJORITY_BPS;
+        track.quorum_bps = 7_000;
+        track.timelock = 0;
+        track.separation_of_duties = true;
+        logic::set_governance_track(env, governance, &track).unwrap();
+    });
+    env.as_contract(contract_id, || {
+        storage::set_voting_power(env, proposer, 4);
+        for power in powers {
+            let voter = Address::generate(env);
+            storage::set_voting_power(env, &voter, *power);
+            voters.push_back(voter);
+        }
+    });
+    let proposal_id = env.as_contract(contract_id, || {
+        logic::propose_upgrade_on_track(
+            env,
+            proposer,
+            &Address::generate(env),
+            &create_test_version(2, 0, 0),
+            String::from_str(env, "separated"),
+            SUPERMAJORITY_TRACK_ID,
+        )
+        .unwrap()
+    });
+    (proposal_id, voters)
+}
+
+#[test]
+fn test_separation_of_duties_refuses_proposer_vote() {
+    let (env, contract_id, governance, proposer) = setup_upgrade_storage();
+    let (proposal_id, voters) =
+        setup_separated_proposal(&env, &contract_id, &governance, &proposer, &[3, 3]);
+
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            logic::check_vote_separation(&env, proposal_id, &proposer),
+            Err(UpgradeError::ProposerCannotVote)
+        );
+        assert_eq!(
+            logic::check_vote_separation(&env, proposal_id, &voters.get(0).unwrap()),
+            Ok(())
+        );
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            logic::cast_vote(&env, &proposer, proposal_id, VoteChoice::For),
+            Err(UpgradeError::ProposerCannotVote.into())
+        );
+    });
+    env.as_contract(&contract_id, || {
+        let proposal = storage::get_upgrade_proposal(&env, proposal_id).unwrap();
+        assert_eq!(proposal.votes_for, 0);
+        assert!(storage::get_vote_receipts(&env, proposal_id).is_empty());
+    });
+}
+
+#[test]
+fn test_separation_of_duties_leaves_proposer_out_of_quorum() {
+    let (env, contract_id, governance, proposer) = setup_upgrade_storage();
+    // The proposer holds 40%: counted in the denominator, a 70% quorum
+    // would need 7 of 10 while only 6 units can ever vote
+    let (proposal_id, voters) =
+        setup_separated_proposal(&env, &contract_id, &governance, &proposer, &[3, 3]);
+
+    env.as_contract(&contract_id, || {
+        let proposal = storage::get_upgrade_proposal(&env, proposal_id).unwrap();
+        assert_eq!(proposal.total_voting_power_snapshot, 6);
+        assert_eq!(proposal.total_voters, 2);
+        assert!(storage::get_proposal_params(&env, proposal_id).separation_of_duties);
+    });
+
+    env.as_contract(&contract_id, || {
+        logic::cast_vote(&env, &voters.get(0).unwrap(), proposal_id, VoteChoice::For).unwrap();
+    });
+    env.as_contract(&contract_id, || {
+        let proposal = storage::get_upgrade_proposal(&env, proposal_id).unwrap();
+        let projection =
+            logic::project_outcome(&proposal, &storage::get_proposal_params(&env, proposal_id));
+        assert_eq!(projection.quorum_required, 5);
+        assert!(!projection.quorum_met);
+        assert_eq!(proposal.status, UpgradeStatus::Pending);
+    });
+
+    env.as_contract(&contract_id, || {
+        logic::cast_vote(&env, &voters.get(1).unwrap(), proposal_id, VoteChoice::For).unwrap();
+    });
+    assert_eq!(
+        proposal_status(&env, &contract_id, proposal_id),
+        UpgradeStatus::Approved
+    );
+}
+
+#[test]
+fn test_separation_of_duties_refuses_proposer_execution() {
+    let (env, contract_id, governance, proposer) = setup_upgrade_storage();
+    env.as_contract(&contract_id, || {
+        auth::grant_role(&env, &governance, Role::Executor, &proposer).unwrap();
+    });
+    env.as_contract(&contract_id, || {
+        auth::set_action_roles(
+            &env,
+            &governance,
+            Action::ExecuteUpgrade,
+            &Vec::from_array(&env, [Role::Governance, Role::Executor]),
+        )
+        .unwrap();
+    });
+    let (proposal_id, voters) =
+        setup_separated_proposal(&env, &contract_id, &governance, &proposer, &[3, 3]);
+    for voter in voters.iter() {
+        env.as_contract(&contract_id, || {
+            logic::cast_vote(&env, &voter, proposal_id, VoteChoice::For).unwrap();
+        });
+    }
+
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            logic::check_execute_separation(&env, proposal_id, &proposer),
+            Err(UpgradeError::ProposerCannotExecute)
+        );
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            logic::execute_proposal(&env, &proposer, proposal_id),
+            Err(UpgradeError::ProposerCannotExecute.into())
+        );
+    });
+    env.as_contract(&contract_id, || {
+        logic::execute_proposal(&env, &governance, proposal_id).unwrap();
+        assert_eq!(
+            storage::get_execution_receipt(&env, proposal_id)
+                .unwrap()
+                .executor,
+            governance
+        );
+    });
+}
+
+#[test]
+fn test_schema_step_turns_separation_of_duties_off() {
+    let (env, contract_id, governance, proposer) = setup_upgrade_storage();
+    let (proposal_id, _) =
+        setup_separated_proposal(&env, &contract_id, &governance, &proposer, &[3]);
+    env.as_contract(&contract_id, || {
+        // Rewrite the track and the proposal's rules in their schema 3 layout
+        let field = Symbol::new(&env, "separation_of_duties");
+        let track_key = (TRACK_KEY, SUPERMAJORITY_TRACK_ID);
+        let params_key = (PARAMS_KEY, proposal_id);
+        let mut track: Map<Symbol, soroban_sdk::Val> =
+            env.storage().persistent().get(&track_key).unwrap();
+        track.remove(field.clone());
+        env.storage().persistent().set(&track_key, &track);
+        let mut params: Map<Symbol, soroban_sdk::Val> =
+            env.storage().persistent().get(&params_key).unwrap();
+        params.remove(field);
+        env.storage().persistent().set(&params_key, &params);
+        storage::set_storage_schema(&env, 3);
+    });
+
+    env.as_contract(&contract_id, || {
+        assert_eq!(logic::migrate_storage_schema(&env, &governance, 4), Ok(2));
+    });
+    env.as_contract(&contract_id, || {
+        let track = storage::get_governance_track(&env, SUPERMAJORITY_TRACK_ID).unwrap();
+        assert!(!track.separation_of_duties);
+        assert_eq!(track.quorum_bps, 7_000);
+        assert!(!storage::get_proposal_params(&env, proposal_id).separation_of_duties);
+    });
+}
+
+#[test]
+fn test_schema_step_moves_upgrade_history_into_entries() {
+    let (env, contract_id, governance, _) = setup_upgrade_storage();
+    let implementation = Address::generate(&env);
+    env.as_contract(&contract_id, || {
+        // Two transactions kept in the schema 5 single map
+        let mut history = Map::new(&env);
+        for success in [false, true] {
+            let id = crate::proxy::storage::take_next_upgrade_id(&env);
+            history.set(
+                id,
+                crate::proxy::types::LegacyUpgradeTransaction {
+                    id,
+                    new_implementation: implementation.clone(),
+                    initiator: governance.clone(),
+                    timestamp: 0,
+                    success,
+                    failure_reason: None,
+                },
+            );
+        }
+        crate::proxy::storage::LEGACY_UPGRADE_HISTORY.store(&env, &history);
+        storage::set_storage_schema(&env, 5);
+        assert!(crate::proxy::storage::get_latest_upgrade(&env).is_none());
+    });
+
+    env.as_contract(&contract_id, || {
+        assert_eq!(logic::migrate_storage_schema(&env, &governance, 6), Ok(2));
+    });
+    env.as_contract(&contract_id, || {
+        assert!(!crate::proxy::storage::LEGACY_UPGRADE_HISTORY.has(&env));
+        assert_eq!(crate::proxy::storage::get_upgrade_count(&env), 2);
+        assert!(
+            crate::proxy::storage::get_latest_upgrade(&env)
+                .unwrap()
+                .success
+        );
+        assert_eq!(crate::proxy::storage::get_failed_upgrades(&env).len(), 1);
+        assert_eq!(crate::proxy::storage::migrate_upgrade_history(&env), 0);
+    });
+}
+
+fn genesis_page(env: &Env, voters: u32, power: u32) -> Vec<(Address, u32)> {
+    let mut page = Vec::new(env);
+    for _ in 0..voters {
+        page.push_back((Address::generate(env), power));
+    }
+    page
+}
+
+fn try_propose(
+    env: &Env,
+    contract_id: &Address,
+    proposer: &Address,
+) -> Result<u64, soroban_sdk::Error> {
+    env.as_contract(contract_id, || {
+        logic::propose_upgrade(
+            env,
+            proposer,
+            &Address::generate(env),
+            &create_test_version(1, 1, 0),
+            String::from_str(env, "after genesis"),
+        )
+    })
+}
+
+#[test]
+fn test_genesis_page_replay_is_a_noop() {
+    let (env, contract_id, governance, proposer) = setup_upgrade_storage();
+    let first = genesis_page(&env, 2, 3);
+    let second = genesis_page(&env, 2, 5);
+
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            logic::load_genesis_voters(&env, &governance, &first, 0, 2),
+            Ok(true)
+        );
+    });
+    let page_event = env.events().all().last().unwrap();
+    assert_eq!(
+        page_event.1,
+        (symbol_short!("upgrade"), Symbol::new(&env, "genesis_page")).into_val(&env)
+    );
+    assert_eq!(
+        try_propose(&env, &contract_id, &proposer),
+        Err(ProposalError::GenesisPending.into())
+    );
+
+    // A retried page changes nothing
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            logic::load_genesis_voters(&env, &governance, &first, 0, 2),
+            Ok(false)
+        );
+        let progress = storage::get_genesis_progress(&env).unwrap();
+        assert_eq!(progress.pages_applied, 1);
+        assert_eq!(progress.voters, 2);
+    });
+
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            logic::load_genesis_voters(&env, &governance, &second, 1, 2),
+            Ok(true)
+        );
+    });
+    let sealed_event = env.events().all().last().unwrap();
+    assert_eq!(
+        sealed_event.1,
+        (
+            symbol_short!("upgrade"),
+            Symbol::new(&env, "genesis_sealed")
+        )
+            .into_val(&env)
+    );
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            storage::get_genesis_progress(&env),
+            Some(GenesisProgress {
+                total_pages: 2,
+                pages_applied: 2,
+                voters: 4,
+                sealed: true,
+            })
+        );
+        assert_eq!(
+            storage::get_voting_power(&env, &second.get(1).unwrap().0),
+            5
+        );
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            logic::load_genesis_voters(&env, &governance, &second, 1, 2),
+            Ok(false)
+        );
+    });
+    assert!(try_propose(&env, &contract_id, &proposer).is_ok());
+}
+
+#[test]
+fn test_genesis_rejects_skipped_and_malformed_pages() {
+    let (env, contract_id, governance, _) = setup_upgrade_storage();
+    let page = genesis_page(&env, 1, 1);
+    env.as_contract(&contract_id, || {
+        logic::load_genesis_voters(&env, &governance, &page, 0, 3).unwrap();
+    });
+
+    for (index, total, expected) in [
+        (2, 3, UpgradeError::GenesisPageOutOfOrder),
+        (3, 3, UpgradeError::InvalidGenesisPages),
+        (1, 4, UpgradeError::InvalidGenesisPages),
+    ] {
+        env.as_contract(&contract_id, || {
+            assert_eq!(
+                logic::load_genesis_voters(&env, &governance, &page, index, total),
+                Err(expected)
+            );
+        });
+    }
+    let oversized = genesis_page(&env, MAX_GENESIS_PAGE_LEN + 1, 1);
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            logic::load_genesis_voters(&env, &governance, &oversized, 1, 3),
+            Err(UpgradeError::GenesisPageTooLarge)
+        );
+        assert_eq!(
+            storage::get_genesis_progress(&env).unwrap().pages_applied,
+            1
+        );
+    });
+}
+
+#[test]
+fn test_genesis_sealed_early_unlocks_proposals() {
+    let (env, contract_id, governance, proposer) = setup_upgrade_storage();
+    let page = genesis_page(&env, 2, 1);
+    env.as_contract(&contract_id, || {
+        logic::load_genesis_voters(&env, &governance, &page, 0, 3).unwrap();
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            logic::seal_genesis_voters(&env, &proposer),
+            Err(UpgradeError::NotAuthorized)
+        );
+    });
+    env.as_contract(&contract_id, || {
+        logic::seal_genesis_voters(&env, &governance).unwrap();
+    });
+    assert!(try_propose(&env, &contract_id, &proposer).is_ok());
+
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            logic::load_genesis_voters(&env, &governance, &page, 1, 3),
+            Err(UpgradeError::GenesisSealed)
+        );
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            logic::seal_genesis_voters(&env, &governance),
+            Err(UpgradeError::GenesisSealed)
+        );
+    });
+
+    // Once governance is running, genesis cannot be started
+    let (env, contract_id, governance, proposer) = setup_upgrade_storage();
+    assert!(try_propose(&env, &contract_id, &proposer).is_ok());
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            logic::load_genesis_voters(&env, &governance, &genesis_page(&env, 1, 1), 0, 1),
+            Err(UpgradeError::GenesisSealed)
+        );
+    });
+}
+
+fn stored_proposal(env: &Env, id: u64, status: UpgradeStatus) -> UpgradeProposal {
+    UpgradeProposal {
+        id,
+        proposer: Address::generate(env),
+        new_contract_address: Address::generate(env),
+        version: create_test_version(1, 1, 0),
+        description: String::from_str(env, "listed"),
+        timestamp: 0,
+        status,
+        votes_for: 0,
+        votes_against: 0,
+        votes_abstain: 0,
+        total_voters: 0,
+        total_voting_power_snapshot: 0,
+        voting_deadline: 0,
+        salt: BytesN::from_array(env, &[0u8; 32]),
+        description_hash: BytesN::from_array(env, &[0u8; 32]),
+    }
+}
+
+#[test]
+fn test_proposals_by_status_list_in_ascending_id_order() {
+    let (env, contract_id, _, _) = setup_upgrade_storage();
+    let statuses = [
+        UpgradeStatus::Pending,
+        UpgradeStatus::Approved,
+        UpgradeStatus::Rejected,
+    ];
+    env.as_contract(&contract_id, || {
+        // Scattered ids, as content hash mode hands them out
+        for i in 0..300u64 {
+            let id = (i * 7_919) % 1_000 + 1;
+            let status = statuses[(i % 3) as usize];
+            storage::store_upgrade_proposal(&env, &stored_proposal(&env, id, status));
+        }
+    });
+
+    env.as_contract(&contract_id, || {
+        let pending = storage::get_pending_proposals(&env);
+        assert_eq!(pending.len(), 100);
+        let mut previous = 0;
+        for proposal in pending.iter() {
+            assert!(proposal.id > previous);
+            assert_eq!(proposal.status, UpgradeStatus::Pending);
+            previous = proposal.id;
+        }
+        assert_eq!(
+            storage::get_proposals_by_status(&env, UpgradeStatus::Approved).len(),
+            100
+        );
+        assert!(storage::get_proposals_by_status(&env, UpgradeStatus::Executed).is_empty());
+    });
+
+    // Status changes and archival are reflected
+    env.as_contract(&contract_id, || {
+        let first = storage::get_pending_proposals(&env).get(0).unwrap();
+        let mut executed = first.clone();
+        executed.status = UpgradeStatus::Executed;
+        storage::store_upgrade_proposal(&env, &executed);
+        let rejected = storage::get_proposals_by_status(&env, UpgradeStatus::Rejected)
+            .get(0)
+            .unwrap();
+        storage::archive_upgrade_proposal(&env, &rejected);
+
+        assert_eq!(storage::get_pending_proposals(&env).len(), 99);
+        assert_eq!(
+            storage::get_proposals_by_status(&env, UpgradeStatus::Executed),
+            Vec::from_array(&env, [executed])
+        );
+        assert_eq!(
+            storage::get_proposals_by_status(&env, UpgradeStatus::Rejected).len(),
+            99
+        );
+    });
+}
+
+#[test]
+fn test_get_proposals_pages_by_start_id() {
+    let (env, contract_id, _, _) = setup_upgrade_storage();
+    env.as_contract(&contract_id, || {
+        for i in (1..=60u64).rev() {
+            storage::store_upgrade_proposal(
+                &env,
+                &stored_proposal(&env, i * 10, UpgradeStatus::Pending),
+            );
+        }
+    });
+    let ids = |page: Vec<UpgradeProposal>| {
+        let mut ids = Vec::new(&env);
+        for proposal in page.iter() {
+            ids.push_back(proposal.id);
+        }
+        ids
+    };
+
+    env.as_contract(&contract_id, || {
+        assert_eq!(storage::get_proposal_count(&env), 60);
+        assert_eq!(
+            ids(logic::get_proposals(&env, 0, 3)),
+            Vec::from_array(&env, [10, 20, 30])
+        );
+        // A start between ids begins at the next one
+        assert_eq!(
+            ids(logic::get_proposals(&env, 25, 3)),
+            Vec::from_array(&env, [30, 40, 50])
+        );
+        assert_eq!(
+            ids(logic::get_proposals(&env, 600, 5)),
+            Vec::from_array(&env, [600])
+        );
+        assert!(logic::get_proposals(&env, 20, 0).is_empty());
+        assert_eq!(
+            logic::get_proposals(&env, 0, u32::MAX).len(),
+            logic::MAX_PROPOSAL_PAGE
+        );
+    });
+
+    let client = crate::StellarGuildsContractClient::new(&env, &contract_id);
+    assert!(client.get_upgrade_proposals(&601, &10).is_empty());
+    assert!(client.get_upgrade_proposals(&u64::MAX, &10).is_empty());
+    assert_eq!(client.get_upgrade_proposal_count(), 60);
+}
+
+#[test]
+fn test_proposal_cursor_walks_both_ways_and_invalidates_on_archive() {
+    use crate::utils::pagination::{CursorDirection, PageScope};
+
+    let (env, contract_id, _, _) = setup_upgrade_storage();
+    env.as_contract(&contract_id, || {
+        for i in 1..=5u64 {
+            storage::store_upgrade_proposal(
+                &env,
+                &stored_proposal(&env, i * 10, UpgradeStatus::Pending),
+            );
+        }
+    });
+    let ids = |page: &ProposalPage| {
+        let mut ids = Vec::new(&env);
+        for proposal in page.proposals.iter() {
+            ids.push_back(proposal.id);
+        }
+        ids
+    };
+    let client = crate::StellarGuildsContractClient::new(&env, &contract_id);
+
+    // Forward from the lowest id until the cursor runs out
+    let first = client.get_upgrade_proposals_by_cursor(&None, &2);
+    assert_eq!(ids(&first), Vec::from_array(&env, [10, 20]));
+    assert!(!first.complete);
+    let second = client.get_upgrade_proposals_by_cursor(&Some(first.next.clone()), &2);
+    assert_eq!(ids(&second), Vec::from_array(&env, [30, 40]));
+    let last = client.get_upgrade_proposals_by_cursor(&Some(second.next.clone()), &2);
+    assert_eq!(ids(&last), Vec::from_array(&env, [50]));
+    assert!(last.complete);
+
+    // Backward from the highest id
+    let newest = client.start_page_cursor(&PageScope::Proposals, &CursorDirection::Backward);
+    let back = client.get_upgrade_proposals_by_cursor(&Some(newest), &3);
+    assert_eq!(ids(&back), Vec::from_array(&env, [50, 40, 30]));
+    let rest = client.get_upgrade_proposals_by_cursor(&Some(back.next.clone()), &3);
+    assert_eq!(ids(&rest), Vec::from_array(&env, [20, 10]));
+    assert!(rest.complete);
+
+    // Archiving between pages invalidates the walk; a fresh one skips the gap
+    let first = client.get_upgrade_proposals_by_cursor(&None, &2);
+    env.as_contract(&contract_id, || {
+        let archived = storage::get_upgrade_proposal(&env, 30).unwrap();
+        storage::archive_upgrade_proposal(&env, &archived);
+    });
+    assert_eq!(
+        client.try_get_upgrade_proposals_by_cursor(&Some(first.next.clone()), &2),
+        Err(Ok(ProposalError::CursorInvalidated.into()))
+    );
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            logic::get_proposals_after(&env, Some(first.next.clone()), 2),
+            Err(ProposalError::CursorInvalidated.into())
+        );
+        let restarted = logic::get_proposals_after(&env, None, 10).unwrap();
+        assert_eq!(ids(&restarted), Vec::from_array(&env, [10, 20, 40, 50]));
+    });
+
+    // A cursor from another list is refused too
+    let voters = client.start_page_cursor(&PageScope::Voters, &CursorDirection::Forward);
+    assert_eq!(
+        client.try_get_upgrade_proposals_by_cursor(&Some(voters), &2),
+        Err(Ok(ProposalError::CursorInvalidated.into()))
+    );
+}
+
+/// Approve a single-voter proposal at t=1_000 under the given execution
+/// delay and window
+fn setup_delayed_approval(
+    env: &Env,
+    contract_id: &Address,
+    governance: &Address,
+    proposer: &Address,
+    delay: u64,
+    window: Option<u64>,
+) -> u64 {
+    env.as_contract(contract_id, || {
+        logic::set_execution_delay(env, governance, delay).unwrap();
+    });
+    env.as_contract(contract_id, || {
+        logic::set_execution_window(env, governance, window).unwrap();
+    });
+    let (proposal_id, voters) =
+        setup_deadline_proposal(env, contract_id, governance, proposer, &[1]);
+    env.as_contract(contract_id, || {
+        logic::cast_vote(env, &voters.get(0).unwrap(), proposal_id, VoteChoice::For).unwrap();
+    });
+    assert_eq!(
+        proposal_status(env, contract_id, proposal_id),
+        UpgradeStatus::Approved
+    );
+    proposal_id
+}
+
+#[test]
+fn test_execution_delay_holds_approved_proposal_until_eligible() {
+    let (env, contract_id, governance, proposer) = setup_upgrade_storage();
+    let proposal_id = setup_delayed_approval(&env, &contract_id, &governance, &proposer, 500, None);
+
+    env.as_contract(&contract_id, || {
+        assert_eq!(storage::get_eligible_at(&env, proposal_id), Some(1_500));
+    });
+
+    env.ledger().with_mut(|l| l.timestamp = 1_499);
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            logic::execute_proposal(&env, &governance, proposal_id),
+            Err(ProposalError::ExecutionDelayActive.into())
+        );
+        assert_eq!(
+            logic::get_execution_blockers(&env, proposal_id),
+            Vec::from_array(&env, [Blocker::ExecutionDelayActive(1_500)])
+        );
+    });
+
+    env.ledger().with_mut(|l| l.timestamp = 1_500);
+    env.as_contract(&contract_id, || {
+        assert!(logic::get_execution_blockers(&env, proposal_id).is_empty());
+        logic::execute_proposal(&env, &governance, proposal_id).unwrap();
+    });
+    assert_eq!(
+        proposal_status(&env, &contract_id, proposal_id),
+        UpgradeStatus::Executed
+    );
+}
+
+#[test]
+fn test_execution_window_expires_unexecuted_approval() {
+    let (env, contract_id, governance, proposer) = setup_upgrade_storage();
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            logic::set_execution_window(&env, &governance, Some(0)),
+            Err(ProposalError::InvalidExecutionWindow.into())
+        );
+    });
+    let proposal_id =
+        setup_delayed_approval(&env, &contract_id, &governance, &proposer, 500, Some(200));
+
+    // Still executable on the last second of the window
+    env.ledger().with_mut(|l| l.timestamp = 1_700);
+    env.as_contract(&contract_id, || {
+        assert!(logic::get_execution_blockers(&env, proposal_id).is_empty());
+    });
+
+    env.ledger().with_mut(|l| l.timestamp = 1_701);
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            logic::get_execution_blockers(&env, proposal_id),
+            Vec::from_array(&env, [Blocker::ExecutionWindowClosed(1_700)])
+        );
+    });
+    let client = crate::StellarGuildsContractClient::new(&env, &contract_id);
+    assert_eq!(
+        client.try_execute_upgrade_proposal(&governance, &proposal_id),
+        Ok(Ok(ExecutionOutcome::Expired))
+    );
+    assert_eq!(
+        proposal_status(&env, &contract_id, proposal_id),
+        UpgradeStatus::Expired
+    );
+    assert_eq!(
+        client.try_execute_upgrade_proposal(&governance, &proposal_id),
+        Err(Ok(UpgradeError::ProposalNotApproved.into()))
+    );
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            storage::get_current_version(&env),
+            create_test_version(1, 0, 0)
+        );
+    });
+}
+
+#[test]
+fn test_emergency_upgrade_ignores_execution_delay() {
+    let (env, contract_id, governance, _) = setup_upgrade_storage();
+    env.as_contract(&contract_id, || {
+        logic::set_execution_delay(&env, &governance, 86_400).unwrap();
+    });
+    env.as_contract(&contract_id, || {
+        logic::toggle_emergency_upgrades(&env, &governance, true).unwrap();
+    });
+    env.as_contract(&contract_id, || {
+        logic::emergency_upgrade(
+            &env,
+            &governance,
+            &Address::generate(&env),
+            &create_test_version(1, 0, 1),
+        )
+        .unwrap();
+        assert_eq!(
+            storage::get_current_version(&env),
+            create_test_version(1, 0, 1)
+        );
+    });
+}
+
+fn patch_template(env: &Env, delay: u64, tag: Symbol, required_roles: &[Role]) -> ProposalTemplate {
+    ProposalTemplate {
+        name: symbol_short!("patch"),
+        default_track: FAST_TRACK_ID,
+        default_tags: Vec::from_array(env, [tag]),
+        default_execution_delay: Some(delay),
+        required_roles: Vec::from_slice(env, required_roles),
+    }
+}
+
+fn propose_patch_from_template(
+    env: &Env,
+    contract_id: &Address,
+    proposer: &Address,
+    patch: u32,
+) -> Result<u64, soroban_sdk::Error> {
+    env.as_contract(contract_id, || {
+        logic::propose_from_template(
+            env,
+            proposer,
+            &symbol_short!("patch"),
+            &Address::generate(env),
+            &create_test_version(1, 0, patch),
+            String::from_str(env, "from template"),
+        )
+    })
+}
+
+#[test]
+fn test_propose_from_template_applies_defaults() {
+    let (env, contract_id, governance, proposer) = setup_upgrade_storage();
+    env.ledger().with_mut(|l| l.timestamp = 1_000);
+    let voter = Address::generate(&env);
+    env.as_contract(&contract_id, || {
+        storage::set_voting_power(&env, &voter, 1);
+    });
+    let template = patch_template(&env, 300, symbol_short!("security"), &[]);
+    env.as_contract(&contract_id, || {
+        logic::set_proposal_template(&env, &governance, &template).unwrap();
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            logic::get_proposal_templates(&env),
+            Vec::from_array(&env, [template.clone()])
+        );
+    });
+
+    let proposal_id = propose_patch_from_template(&env, &contract_id, &proposer, 1).unwrap();
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            storage::get_proposal_params(&env, proposal_id).track_id,
+            FAST_TRACK_ID
+        );
+        assert_eq!(
+            storage::get_applied_template(&env, proposal_id),
+            Some(AppliedTemplate {
+                template: symbol_short!("patch"),
+                tags: Vec::from_array(&env, [symbol_short!("security")]),
+                execution_delay: Some(300),
+            })
+        );
+    });
+
+    // The template's delay replaces the governance-wide one
+    env.as_contract(&contract_id, || {
+        logic::set_execution_delay(&env, &governance, 5_000).unwrap();
+    });
+    env.as_contract(&contract_id, || {
+        logic::cast_vote(&env, &voter, proposal_id, VoteChoice::For).unwrap();
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(storage::get_eligible_at(&env, proposal_id), Some(1_300));
+    });
+}
+
+#[test]
+fn test_template_requiring_role_refuses_other_proposers() {
+    let (env, contract_id, governance, proposer) = setup_upgrade_storage();
+    let template = patch_template(&env, 0, symbol_short!("routine"), &[Role::Proposer]);
+    env.as_contract(&contract_id, || {
+        logic::set_proposal_template(&env, &governance, &template).unwrap();
+    });
+
+    assert_eq!(
+        propose_patch_from_template(&env, &contract_id, &proposer, 1),
+        Err(UpgradeError::NotAuthorized.into())
+    );
+
+    env.as_contract(&contract_id, || {
+        auth::grant_role(&env, &governance, Role::Proposer, &proposer).unwrap();
+    });
+    assert!(propose_patch_from_template(&env, &contract_id, &proposer, 1).is_ok());
+}
+
+#[test]
+fn test_template_updates_do_not_reach_existing_proposals() {
+    let (env, contract_id, governance, proposer) = setup_upgrade_storage();
+    let original = patch_template(&env, 300, symbol_short!("v1"), &[]);
+    env.as_contract(&contract_id, || {
+        logic::set_proposal_template(&env, &governance, &original).unwrap();
+    });
+    let first = propose_patch_from_template(&env, &contract_id, &proposer, 1).unwrap();
+
+    let updated = patch_template(&env, 900, symbol_short!("v2"), &[]);
+    env.as_contract(&contract_id, || {
+        logic::set_proposal_template(&env, &governance, &updated).unwrap();
+    });
+    let second = propose_patch_from_template(&env, &contract_id, &proposer, 2).unwrap();
+
+    env.as_contract(&contract_id, || {
+        let first = storage::get_applied_template(&env, first).unwrap();
+        assert_eq!(first.tags, Vec::from_array(&env, [symbol_short!("v1")]));
+        assert_eq!(first.execution_delay, Some(300));
+        let second = storage::get_applied_template(&env, second).unwrap();
+        assert_eq!(second.tags, Vec::from_array(&env, [symbol_short!("v2")]));
+        assert_eq!(second.execution_delay, Some(900));
+        assert_eq!(logic::get_proposal_templates(&env).len(), 1);
+    });
+
+    // Deleting the template leaves its proposals' records in place
+    env.as_contract(&contract_id, || {
+        logic::remove_proposal_template(&env, &governance, &symbol_short!("patch")).unwrap();
+    });
+    assert_eq!(
+        propose_patch_from_template(&env, &contract_id, &proposer, 3),
+        Err(ProposalError::TemplateNotFound.into())
+    );
+    env.as_contract(&contract_id, || {
+        assert!(logic::get_proposal_templates(&env).is_empty());
+        assert!(storage::get_applied_template(&env, first).is_some());
+    });
+}
+
+#[test]
+fn test_delegated_power_counts_for_the_end_of_the_chain() {
+    let (env, contract_id, _, proposer) = setup_upgrade_storage();
+    let proposal_id = propose_version(&env, &contract_id, &proposer, create_test_version(2, 0, 0));
+    let member = Address::generate(&env);
+    let officer = Address::generate(&env);
+    let deputy = Address::generate(&env);
+
+    env.as_contract(&contract_id, || {
+        set_snapshot_power(&env, proposal_id, &member, 3);
+        set_snapshot_power(&env, proposal_id, &deputy, 5);
+        set_snapshot_power(&env, proposal_id, &officer, 7);
+    });
+    // member -> deputy -> officer: both delegations end with the officer
+    env.as_contract(&contract_id, || {
+        logic::delegate(&env, &member, &deputy).unwrap();
+    });
+    env.as_contract(&contract_id, || {
+        logic::delegate(&env, &deputy, &officer).unwrap();
+    });
+
+    env.as_contract(&contract_id, || {
+        assert_eq!(storage::get_delegate(&env, &member), Some(deputy.clone()));
+        let err = logic::cast_vote(&env, &deputy, proposal_id, VoteChoice::Abstain).unwrap_err();
+        assert_eq!(err, VoteError::VoterHasDelegated.into());
+    });
+    env.as_contract(&contract_id, || {
+        logic::cast_vote(&env, &officer, proposal_id, VoteChoice::Abstain).unwrap();
+        let proposal = storage::get_upgrade_proposal(&env, proposal_id).unwrap();
+        assert_eq!(proposal.votes_abstain, 15);
+        assert_eq!(
+            storage::get_vote_receipts(&env, proposal_id)
+                .get(0)
+                .unwrap()
+                .weight,
+            15
+        );
+        assert!(logic::capabilities(&env).delegation_enabled);
+    });
+}
+
+#[test]
+fn test_delegation_rejects_self_and_cycles() {
+    let (env, contract_id, _, _) = setup_upgrade_storage();
+    let a = Address::generate(&env);
+    let b = Address::generate(&env);
+    let c = Address::generate(&env);
+
+    env.as_contract(&contract_id, || {
+        for voter in [&a, &b, &c] {
+            storage::set_voting_power(&env, voter, 1);
+        }
+        let err = logic::delegate(&env, &a, &a).unwrap_err();
+        assert_eq!(err, VoteError::SelfDelegation.into());
+    });
+    env.as_contract(&contract_id, || {
+        logic::delegate(&env, &a, &b).unwrap();
+    });
+    env.as_contract(&contract_id, || {
+        logic::delegate(&env, &b, &c).unwrap();
+    });
+    env.as_contract(&contract_id, || {
+        let err = logic::delegate(&env, &b, &a).unwrap_err();
+        assert_eq!(err, VoteError::DelegationCycle.into());
+    });
+    env.as_contract(&contract_id, || {
+        let err = logic::delegate(&env, &c, &a).unwrap_err();
+        assert_eq!(err, VoteError::DelegationCycle.into());
+    });
+    env.as_contract(&contract_id, || {
+        let err = logic::undelegate(&env, &c).unwrap_err();
+        assert_eq!(err, VoteError::NotDelegating.into());
+    });
+    env.as_contract(&contract_id, || {
+        logic::undelegate(&env, &a).unwrap();
+        assert_eq!(storage::get_delegate(&env, &a), None);
+    });
+}
+
+#[test]
+fn test_delegation_changes_do_not_reweight_cast_votes() {
+    let (env, contract_id, _, proposer) = setup_upgrade_storage();
+    let proposal_id = propose_version(&env, &contract_id, &proposer, create_test_version(2, 0, 0));
+    let member = Address::generate(&env);
+    let officer = Address::generate(&env);
+    let late = Address::generate(&env);
+
+    env.as_contract(&contract_id, || {
+        set_snapshot_power(&env, proposal_id, &member, 4);
+        set_snapshot_power(&env, proposal_id, &officer, 6);
+        set_snapshot_power(&env, proposal_id, &late, 9);
+    });
+    env.as_contract(&contract_id, || {
+        logic::delegate(&env, &member, &officer).unwrap();
+    });
+    env.as_contract(&contract_id, || {
+        logic::cast_vote(&env, &officer, proposal_id, VoteChoice::Abstain).unwrap();
+    });
+    // A delegation made after the officer voted leaves the cast ballot alone
+    env.as_contract(&contract_id, || {
+        logic::delegate(&env, &late, &officer).unwrap();
+    });
+    env.as_contract(&contract_id, || {
+        let proposal = storage::get_upgrade_proposal(&env, proposal_id).unwrap();
+        assert_eq!(proposal.votes_abstain, 10);
+    });
+
+    // The member's power was already cast through the officer
+    env.as_contract(&contract_id, || {
+        logic::undelegate(&env, &member).unwrap();
+    });
+    env.as_contract(&contract_id, || {
+        let err = logic::cast_vote(&env, &member, proposal_id, VoteChoice::Abstain).unwrap_err();
+        assert_eq!(err, UpgradeError::AlreadyVoted.into());
+    });
+    // Undelegating lets the late delegator vote with its own power
+    env.as_contract(&contract_id, || {
+        logic::undelegate(&env, &late).unwrap();
+    });
+    env.as_contract(&contract_id, || {
+        logic::cast_vote(&env, &late, proposal_id, VoteChoice::Abstain).unwrap();
+        let proposal = storage::get_upgrade_proposal(&env, proposal_id).unwrap();
+        assert_eq!(proposal.votes_abstain, 19);
+    });
+}
+
+#[test]
+fn test_record_vote_emits_vote_cast_with_running_totals() {
+    let (env, contract_id, _, proposer) = setup_upgrade_storage();
+    let proposal_id = propose_version(&env, &contract_id, &proposer, create_test_version(2, 0, 0));
+    let voter = Address::generate(&env);
+    let other = Address::generate(&env);
+
+    env.as_contract(&contract_id, || {
+        set_snapshot_power(&env, proposal_id, &voter, 4);
+        set_snapshot_power(&env, proposal_id, &other, 2);
+    });
+    env.as_contract(&contract_id, || {
+        logic::cast_vote(&env, &voter, proposal_id, VoteChoice::Abstain).unwrap();
+    });
+    env.as_contract(&contract_id, || {
+        logic::cast_vote(&env, &other, proposal_id, VoteChoice::Abstain).unwrap();
+
+        let (_, topics, data) = env.events().all().last().unwrap();
+        assert_eq!(
+            topics,
+            (
+                symbol_short!("upgrade"),
+                Symbol::new(&env, "vote_cast"),
+                proposal_id
+            )
+                .into_val(&env)
+        );
+        let cast: VoteCast = data.into_val(&env);
+        assert_eq!(
+            cast,
+            VoteCast {
+                proposal_id,
+                voter: other.clone(),
+                choice: VoteChoice::Abstain,
+                weight: 2,
+                votes_for: 0,
+                votes_against: 0,
+                votes_abstain: 6,
+            }
+        );
+    });
+}
+
+#[test]
+fn test_change_vote_moves_the_original_weight() {
+    let (env, contract_id, _, proposer) = setup_upgrade_storage();
+    let early = Address::generate(&env);
+    let other = Address::generate(&env);
+    let absent = Address::generate(&env);
+    env.as_contract(&contract_id, || {
+        storage::set_voting_power(&env, &early, 3);
+        storage::set_voting_power(&env, &other, 3);
+        storage::set_voting_power(&env, &absent, 4);
+    });
+    let proposal_id = propose_version(&env, &contract_id, &proposer, create_test_version(2, 0, 0));
+
+    env.as_contract(&contract_id, || {
+        logic::vote_on_proposal(&env, &early, proposal_id, true).unwrap();
+        // Power granted after the vote does not change what moves
+        storage::set_voting_power(&env, &early, 1);
+    });
+    env.as_contract(&contract_id, || {
+        logic::change_vote(&env, &early, proposal_id, false).unwrap();
+        let proposal = storage::get_upgrade_proposal(&env, proposal_id).unwrap();
+        assert_eq!((proposal.votes_for, proposal.votes_against), (0, 3));
+        assert_eq!(proposal.status, UpgradeStatus::Pending);
+        let receipt = storage::get_vote_receipts(&env, proposal_id)
+            .get(0)
+            .unwrap();
+        assert_eq!((receipt.choice, receipt.weight), (VoteChoice::Against, 3));
+
+        let (_, _, data) = env.events().all().last().unwrap();
+        let changed: VoteChanged = data.into_val(&env);
+        assert_eq!(
+            (changed.from, changed.to, changed.weight),
+            (VoteChoice::For, VoteChoice::Against, 3)
+        );
+    });
+    env.as_contract(&contract_id, || {
+        let err = logic::change_vote(&env, &early, proposal_id, false).unwrap_err();
+        assert_eq!(err, VoteError::VoteUnchanged.into());
+    });
+    env.as_contract(&contract_id, || {
+        let err = logic::change_vote(&env, &absent, proposal_id, true).unwrap_err();
+        assert_eq!(err, VoteError::NotVoted.into());
+    });
+
+    // Switching back can decide the proposal, after which votes are final
+    env.as_contract(&contract_id, || {
+        logic::vote_on_proposal(&env, &other, proposal_id, true).unwrap();
+    });
+    env.as_contract(&contract_id, || {
+        logic::change_vote(&env, &early, proposal_id, true).unwrap();
+        let proposal = storage::get_upgrade_proposal(&env, proposal_id).unwrap();
+        assert_eq!(proposal.status, UpgradeStatus::Approved);
+    });
+    env.as_contract(&contract_id, || {
+        let err = logic::change_vote(&env, &other, proposal_id, false).unwrap_err();
+        assert_eq!(err, UpgradeError::ProposalNotPending.into());
+    });
+}
+
+#[test]
+fn test_proposal_result_keeps_abstentions_out_of_the_approval_share() {
+    let (env, contract_id, _, proposer) = setup_upgrade_storage();
+    let abstainer = Address::generate(&env);
+    let supporter = Address::generate(&env);
+    let opponent = Address::generate(&env);
+    env.as_contract(&contract_id, || {
+        storage::set_voting_power(&env, &abstainer, 5);
+        storage::set_voting_power(&env, &supporter, 3);
+        storage::set_voting_power(&env, &opponent, 1);
+        storage::set_voting_power(&env, &proposer, 3);
+    });
+    let proposal_id = propose_version(&env, &contract_id, &proposer, create_test_version(2, 0, 0));
+
+    env.as_contract(&contract_id, || {
+        logic::cast_vote(&env, &abstainer, proposal_id, VoteChoice::Abstain).unwrap();
+    });
+    env.as_contract(&contract_id, || {
+        logic::vote_on_proposal(&env, &supporter, proposal_id, true).unwrap();
+    });
+    env.as_contract(&contract_id, || {
+        logic::vote_on_proposal(&env, &opponent, proposal_id, false).unwrap();
+    });
+
+    let client = crate::StellarGuildsContractClient::new(&env, &contract_id);
+    let result = client.get_proposal_result(&proposal_id).unwrap();
+    assert_eq!(result.status, UpgradeStatus::Pending);
+    assert_eq!(
+        (result.votes_for, result.votes_against, result.votes_abstain),
+        (3, 1, 5)
+    );
+    assert_eq!(result.approval_bps, 7500);
+    env.as_contract(&contract_id, || {
+        let proposal = storage::get_upgrade_proposal(&env, proposal_id).unwrap();
+        let params = storage::get_proposal_params(&env, proposal_id);
+        let projection = logic::project_outcome(&proposal, &params);
+        assert_eq!(result.participating, projection.participating);
+        assert_eq!(result.quorum_met, projection.quorum_met);
+    });
+    assert_eq!(client.get_proposal_result(&99), None);
+}
+
+#[test]
+fn test_voter_registry_tracks_total_power_and_feeds_proposals() {
+    let (env, contract_id, governance, proposer) = setup_upgrade_storage();
+    let voters = [
+        Address::generate(&env),
+        Address::generate(&env),
+        Address::generate(&env),
+    ];
+    for (voter, power) in voters.iter().zip([4u32, 2, 6]) {
+        env.as_contract(&contract_id, || {
+            logic::register_voter(&env, &governance, voter, power).unwrap();
+        });
+    }
+
+    env.as_contract(&contract_id, || {
+        assert_eq!(storage::get_registry_power(&env), 12);
+        assert_eq!(storage::get_total_voting_power(&env), 12);
+        let page = logic::get_voters(&env, 1, 5);
+        assert_eq!(page.len(), 2);
+        assert!(logic::get_voters(&env, 3, 5).is_empty());
+    });
+    env.as_contract(&contract_id, || {
+        let err = logic::register_voter(&env, &governance, &voters[0], 1).unwrap_err();
+        assert_eq!(err, VoteError::VoterAlreadyRegistered.into());
+    });
+    env.as_contract(&contract_id, || {
+        let err = logic::register_voter(&env, &proposer, &proposer, 1).unwrap_err();
+        assert_eq!(err, UpgradeError::NotAuthorized.into());
+    });
+
+    // Changing a voter's power moves the total by the difference
+    env.as_contract(&contract_id, || {
+        storage::set_voting_power(&env, &voters[1], 5);
+        assert_eq!(storage::get_registry_power(&env), 15);
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(logic::remove_voter(&env, &governance, &voters[2]), Ok(6));
+        assert_eq!(storage::get_registry_power(&env), 9);
+        assert!(!storage::is_registered_voter(&env, &voters[2]));
+    });
+    env.as_contract(&contract_id, || {
+        let err = logic::remove_voter(&env, &governance, &voters[2]).unwrap_err();
+        assert_eq!(err, VoteError::VoterNotRegistered.into());
+    });
+
+    let proposal_id = propose_version(&env, &contract_id, &proposer, create_test_version(2, 0, 0));
+    env.as_contract(&contract_id, || {
+        let proposal = storage::get_upgrade_proposal(&env, proposal_id).unwrap();
+        assert_eq!(proposal.total_voters, 2);
+        assert_eq!(proposal.total_voting_power_snapshot, 9);
+    });
+}
+
+#[test]
+fn test_voting_power_batch_applies_entries_in_one_call() {
+    let (env, contract_id, governance, proposer) = setup_upgrade_storage();
+    let members = [
+        Address::generate(&env),
+        Address::generate(&env),
+        Address::generate(&env),
+    ];
+    let entries = Vec::from_array(
+        &env,
+        [
+            (members[0].clone(), 2u32),
+            (members[1].clone(), 3),
+            (members[2].clone(), 5),
+        ],
+    );
+
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            logic::set_voting_power_batch(&env, &governance, &entries),
+            Ok(3)
+        );
+        let (_, topics, data) = env.events().all().last().unwrap();
+        assert_eq!(
+            topics,
+            (symbol_short!("upgrade"), Symbol::new(&env, "power_batch")).into_val(&env)
+        );
+        let applied: u32 = data.into_val(&env);
+        assert_eq!(applied, 3);
+        assert_eq!(storage::get_voting_power(&env, &members[2]), 5);
+        assert_eq!(storage::get_registry_power(&env), 10);
+    });
+
+    env.as_contract(&contract_id, || {
+        let err = logic::set_voting_power_batch(&env, &governance, &Vec::new(&env)).unwrap_err();
+        assert_eq!(err, VoteError::EmptyBatch.into());
+    });
+    let mut oversized = Vec::new(&env);
+    for _ in 0..=MAX_POWER_BATCH_LEN {
+        oversized.push_back((Address::generate(&env), 1u32));
+    }
+    env.as_contract(&contract_id, || {
+        let err = logic::set_voting_power_batch(&env, &governance, &oversized).unwrap_err();
+        assert_eq!(err, VoteError::BatchTooLarge.into());
+    });
+    env.as_contract(&contract_id, || {
+        let err = logic::set_voting_power_batch(&env, &proposer, &entries).unwrap_err();
+        assert_eq!(err, UpgradeError::NotAuthorized.into());
+    });
+}
+
+#[test]
+fn test_execution_records_stamp_executor_and_emergency_path() {
+    let (env, contract_id, governance, proposer) = setup_upgrade_storage();
+    env.ledger().with_mut(|l| l.timestamp = 7_000);
+
+    let target = create_test_version(1, 0, 1);
+    let proposal_id = create_approved_proposal(&env, &contract_id, &proposer, target.clone());
+    env.as_contract(&contract_id, || {
+        assert_eq!(logic::get_execution_record(&env, proposal_id), None);
+        logic::execute_proposal(&env, &governance, proposal_id).unwrap();
+    });
+    env.as_contract(&contract_id, || {
+        let record = logic::get_execution_record(&env, proposal_id).unwrap();
+        assert_eq!(record.executed_by, governance);
+        assert_eq!(record.executed_at, 7_000);
+        assert_eq!(record.to_version, target);
+        assert!(!record.emergency);
+    });
+
+    let emergency_target = Address::generate(&env);
+    env.as_contract(&contract_id, || {
+        logic::toggle_emergency_upgrades(&env, &governance, true).unwrap();
+    });
+    env.ledger().with_mut(|l| l.timestamp = 8_000);
+    env.as_contract(&contract_id, || {
+        logic::emergency_upgrade(
+            &env,
+            &governance,
+            &emergency_target,
+            &create_test_version(1, 0, 2),
+        )
+        .unwrap();
+        let executions = storage::get_emergency_executions(&env);
+        assert_eq!(
+            executions,
+            Vec::from_array(
+                &env,
+                [ExecutionRecord {
+                    executed_by: governance.clone(),
+                    executed_at: 8_000,
+                    to_version: create_test_version(1, 0, 2),
+                    implementation: emergency_target.clone(),
+                    emergency: true,
+                }]
+            )
+        );
+    });
+}
+
+#[test]
+fn test_second_initialize_keeps_original_governance() {
+    let (env, contract_id, governance, proposer) = setup_upgrade_storage();
+    let proposal_id = propose_version(&env, &contract_id, &proposer, create_test_version(1, 1, 0));
+
+    env.as_contract(&contract_id, || {
+        assert!(storage::is_initialized(&env));
+        assert_eq!(
+            storage::initialize(&env, create_test_version(9, 0, 0), proposer.clone()),
+            Err(GuardError::AlreadyInitialized)
+        );
+        assert_eq!(storage::get_governance_address(&env), governance);
+        assert_eq!(
+            storage::get_current_version(&env),
+            create_test_version(1, 0, 0)
+        );
+        assert!(storage::get_upgrade_proposal(&env, proposal_id).is_some());
+    });
+
+    let client = crate::StellarGuildsContractClient::new(&env, &contract_id);
+    assert!(client.is_upgrade_initialized());
+    assert_eq!(
+        client.try_initialize_upgrade_system(&2, &0, &0, &proposer),
+        Err(Ok(GuardError::AlreadyInitialized.into()))
+    );
+    let fresh = env.register_contract(None, StellarGuildsContract);
+    let fresh_client = crate::StellarGuildsContractClient::new(&env, &fresh);
+    assert!(!fresh_client.is_upgrade_initialized());
+}
+
+#[test]
+fn test_vote_tallies_are_wide_and_refuse_overflow() {
+    let (env, contract_id, _, proposer) = setup_upgrade_storage();
+    let proposal_id = propose_version(&env, &contract_id, &proposer, create_test_version(1, 1, 0));
+    let (big, last) = (Address::generate(&env), Address::generate(&env));
+
+    // Tallies carry past u32 without wrapping
+    env.as_contract(&contract_id, || {
+        let mut proposal = storage::get_upgrade_proposal(&env, proposal_id).unwrap();
+        proposal.votes_for = u32::MAX as u64;
+        storage::store_upgrade_proposal(&env, &proposal);
+        set_snapshot_power(&env, proposal_id, &big, u32::MAX);
+        storage::record_vote(&env, proposal_id, &big, VoteChoice::For).unwrap();
+        let proposal = storage::get_upgrade_proposal(&env, proposal_id).unwrap();
+        assert_eq!(proposal.votes_for, 2 * u32::MAX as u64);
+    });
+
+    // A sum that does not fit is refused and nothing is recorded
+    env.as_contract(&contract_id, || {
+        let mut proposal = storage::get_upgrade_proposal(&env, proposal_id).unwrap();
+        proposal.votes_for = u64::MAX - 1;
+        storage::store_upgrade_proposal(&env, &proposal);
+        set_snapshot_power(&env, proposal_id, &last, 2);
+        assert_eq!(
+            storage::record_vote(&env, proposal_id, &last, VoteChoice::For),
+            Err(VoteError::TallyOverflow.into())
+        );
+        assert_eq!(
+            storage::get_upgrade_proposal(&env, proposal_id)
+                .unwrap()
+                .votes_for,
+            u64::MAX - 1
+        );
+        assert_eq!(storage::get_vote_receipts(&env, proposal_id).len(), 1);
+    });
+}
+
+#[test]
+fn test_schema_step_widens_proposal_tallies() {
+    let (env, contract_id, governance, proposer) = setup_upgrade_storage();
+    env.as_contract(&contract_id, || {
+        let narrow = NarrowUpgradeProposal {
+            id: 4,
+            proposer: proposer.clone(),
+            new_contract_address: Address::generate(&env),
+            version: create_test_version(1, 1, 0),
+            description: String::from_str(&env, "narrow"),
+            timestamp: 0,
+            status: UpgradeStatus::Pending,
+            votes_for: 3,
+            votes_against: 1,
+            votes_abstain: 2,
+            total_voters: 4,
+            total_voting_power_snapshot: 9,
+            voting_deadline: 0,
+            salt: BytesN::from_array(&env, &[0u8; 32]),
+            description_hash: BytesN::from_array(&env, &[0u8; 32]),
+        };
+        let mut stored: Map<u64, NarrowUpgradeProposal> = Map::new(&env);
+        stored.set(4, narrow);
+        env.storage()
+            .persistent()
+            .set(&UPGRADE_PROPOSALS_KEY, &stored);
+        storage::set_storage_schema(&env, 7);
+    });
+
+    env.as_contract(&contract_id, || {
+        assert_eq!(logic::migrate_storage_schema(&env, &governance, 8), Ok(1));
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(logic::migrate_storage_schema(&env, &governance, 9), Ok(0));
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(logic::migrate_storage_schema(&env, &governance, 10), Ok(0));
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(logic::migrate_storage_schema(&env, &governance, 11), Ok(0));
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(logic::migrate_storage_schema(&env, &governance, 12), Ok(0));
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(logic::migrate_storage_schema(&env, &governance, 13), Ok(0));
+    });
+    env.as_contract(&contract_id, || {
+        let proposal = storage::get_upgrade_proposal(&env, 4).unwrap();
+        assert_eq!(
+            (
+                proposal.votes_for,
+                proposal.votes_against,
+                proposal.votes_abstain
+            ),
+            (3, 1, 2)
+        );
+        assert_eq!(proposal.total_voters, 4);
+        assert_eq!(proposal.total_voting_power_snapshot, 9);
+        assert_eq!(storage::migrate_proposal_tallies(&env), 0);
+    });
+}
+
+#[test]
+fn test_schema_step_widens_vote_receipt_weights() {
+    let (env, contract_id, governance, proposer) = setup_upgrade_storage();
+    let proposal_id = propose_version(&env, &contract_id, &proposer, create_test_version(1, 1, 0));
+    let voter = Address::generate(&env);
+    env.as_contract(&contract_id, || {
+        let mut narrow: Vec<NarrowVoteReceipt> = Vec::new(&env);
+        narrow.push_back(NarrowVoteReceipt {
+            voter: voter.clone(),
+            choice: VoteChoice::Against,
+            weight: 7,
+            weight_bps: 700,
+            timestamp: 3,
+        });
+        env.storage()
+            .persistent()
+            .set(&(VOTE_RECEIPTS_KEY, proposal_id), &narrow);
+        storage::set_storage_schema(&env, 10);
+    });
+
+    env.as_contract(&contract_id, || {
+        assert_eq!(logic::migrate_storage_schema(&env, &governance, 11), Ok(1));
+    });
+    env.as_contract(&contract_id, || {
+        let receipts = storage::get_vote_receipts(&env, proposal_id);
+        assert_eq!(
+            receipts.get(0).unwrap(),
+            VoteReceipt {
+                voter: voter.clone(),
+                choice: VoteChoice::Against,
+                weight: 7,
+                weight_bps: 700,
+                timestamp: 3,
+            }
+        );
+        assert_eq!(storage::migrate_vote_receipt_weights(&env), 0);
+    });
+}
+
+#[test]
+fn test_delegation_keeps_a_bounded_reverse_index() {
+    let (env, contract_id, _, _) = setup_upgrade_storage();
+    let officer = Address::generate(&env);
+    let deputy = Address::generate(&env);
+    let member = Address::generate(&env);
+
+    env.as_contract(&contract_id, || {
+        let err = logic::delegate(&env, &member, &officer).unwrap_err();
+        assert_eq!(err, UpgradeError::NoVotingPower.into());
+        storage::set_voting_power(&env, &member, 1);
+        storage::set_voting_power(&env, &deputy, 1);
+    });
+    env.as_contract(&contract_id, || {
+        logic::delegate(&env, &member, &deputy).unwrap();
+    });
+    env.as_contract(&contract_id, || {
+        logic::delegate(&env, &deputy, &officer).unwrap();
+    });
+    env.as_contract(&contract_id, || {
+        let mut expected = Vec::new(&env);
+        expected.push_back(deputy.clone());
+        expected.push_back(member.clone());
+        assert_eq!(storage::get_delegators(&env, &officer), expected);
+        assert_eq!(storage::get_delegators(&env, &deputy).len(), 1);
+    });
+    // Moving a delegation drops it from the old delegate's list
+    env.as_contract(&contract_id, || {
+        logic::delegate(&env, &member, &officer).unwrap();
+        assert!(storage::get_delegators(&env, &deputy).is_empty());
+        assert_eq!(storage::get_delegators(&env, &officer).len(), 2);
+    });
+    env.as_contract(&contract_id, || {
+        logic::undelegate(&env, &deputy).unwrap();
+        assert!(!storage::delegators_slot(&officer)
+            .load(&env)
+            .unwrap()
+            .contains(&deputy));
+    });
+
+    // Fill the officer's tree up to the bound
+    env.as_contract(&contract_id, || {
+        for _ in 1..MAX_DELEGATORS {
+            let delegator = Address::generate(&env);
+            storage::set_voting_power(&env, &delegator, 1);
+            logic::delegate(&env, &delegator, &officer).unwrap();
+        }
+        assert_eq!(
+            storage::get_delegators(&env, &officer).len(),
+            MAX_DELEGATORS
+        );
+    });
+    // A delegation anywhere in the tree counts toward its root
+    env.as_contract(&contract_id, || {
+        let err = logic::delegate(&env, &deputy, &member).unwrap_err();
+        assert_eq!(err, VoteError::TooManyDelegators.into());
+    });
+    // Moving within the tree adds nothing
+    env.as_contract(&contract_id, || {
+        let mover = storage::get_delegators(&env, &officer).get(1).unwrap();
+        logic::delegate(&env, &mover, &member).unwrap();
+        assert_eq!(
+            storage::get_delegators(&env, &officer).len(),
+            MAX_DELEGATORS
+        );
+    });
+}
+
+#[test]
+fn test_schema_step_builds_the_delegator_index() {
+    let (env, contract_id, governance, _) = setup_upgrade_storage();
+    let officer = Address::generate(&env);
+    let deputy = Address::generate(&env);
+    let member = Address::generate(&env);
+    env.as_contract(&contract_id, || {
+        // Delegations stored before the index existed
+        let mut delegations = Map::new(&env);
+        delegations.set(member.clone(), deputy.clone());
+        delegations.set(deputy.clone(), officer.clone());
+        storage::DELEGATIONS.store(&env, &delegations);
+        storage::set_storage_schema(&env, 11);
+    });
+
+    env.as_contract(&contract_id, || {
+        assert_eq!(logic::migrate_storage_schema(&env, &governance, 12), Ok(2));
+    });
+    env.as_contract(&contract_id, || {
+        let delegators = storage::get_delegators(&env, &officer);
+        assert_eq!(delegators.len(), 2);
+        assert!(delegators.contains(&member) && delegators.contains(&deputy));
+    });
+}
+
+#[test]
+fn test_schema_step_builds_the_track_index() {
+    let (env, contract_id, governance, proposer) = setup_upgrade_storage();
+    let proposal_id = propose_version(&env, &contract_id, &proposer, create_test_version(1, 1, 0));
+    let standard = env.as_contract(&contract_id, || {
+        storage::get_governance_track(&env, STANDARD_TRACK_ID).unwrap()
+    });
+    let custom = GovernanceTrack {
+        id: 9,
+        name: symbol_short!("custom"),
+        ..standard.clone()
+    };
+    env.as_contract(&contract_id, || {
+        // Tracks stored before the index existed: a redefined built-in
+        // track, and a custom one a proposal was created on
+        storage::track_slot(STANDARD_TRACK_ID).store(
+            &env,
+            &GovernanceTrack {
+                timelock: 1,
+                ..standard.clone()
+            },
+        );
+        storage::track_slot(custom.id).store(&env, &custom);
+        let mut params = storage::get_proposal_params(&env, proposal_id);
+        params.track_id = custom.id;
+        storage::store_proposal_params(&env, proposal_id, &params);
+        storage::set_storage_schema(&env, 12);
+    });
+    let before = env.as_contract(&contract_id, || logic::get_governance_digest(&env));
+
+    env.as_contract(&contract_id, || {
+        assert_eq!(logic::migrate_storage_schema(&env, &governance, 13), Ok(2));
+    });
+    env.as_contract(&contract_id, || {
+        let tracks = storage::get_governance_tracks(&env);
+        assert_eq!(tracks.len(), 4);
+        assert_eq!(tracks.get(3).unwrap(), custom);
+        assert_ne!(logic::get_governance_digest(&env), before);
+    });
+}
+
+#[test]
+fn test_delegated_weight_can_exceed_u32() {
+    let (env, contract_id, _, proposer) = setup_upgrade_storage();
+    let proposal_id = propose_version(&env, &contract_id, &proposer, create_test_version(1, 1, 0));
+    let (delegate, delegator) = (Address::generate(&env), Address::generate(&env));
+
+    env.as_contract(&contract_id, || {
+        set_snapshot_power(&env, proposal_id, &delegate, u32::MAX);
+        set_snapshot_power(&env, proposal_id, &delegator, u32::MAX);
+    });
+    env.as_contract(&contract_id, || {
+        logic::delegate(&env, &delegator, &delegate).unwrap();
+    });
+    env.as_contract(&contract_id, || {
+        storage::record_vote(&env, proposal_id, &delegate, VoteChoice::For).unwrap();
+        let weight = 2 * u32::MAX as u64;
+        assert_eq!(
+            storage::get_upgrade_proposal(&env, proposal_id)
+                .unwrap()
+                .votes_for,
+            weight
+        );
+        let receipt = storage::get_vote_receipts(&env, proposal_id)
+            .get(0)
+            .unwrap();
+        assert_eq!(receipt.weight, weight);
+    });
+}
+
+#[test]
+fn test_max_voting_power_caps_every_grant_path() {
+    let (env, contract_id, governance, proposer) = setup_upgrade_storage();
+    let member = Address::generate(&env);
+
+    env.as_contract(&contract_id, || {
+        assert_eq!(storage::get_max_voting_power(&env), 0);
+        let err = logic::set_max_voting_power(&env, &proposer, 10).unwrap_err();
+        assert_eq!(err, UpgradeError::NotAuthorized.into());
+    });
+    env.as_contract(&contract_id, || {
+        logic::set_max_voting_power(&env, &governance, 10).unwrap();
+        assert_eq!(storage::get_max_voting_power(&env), 10);
+    });
+
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            logic::grant_voting_power(&env, &governance, &member, 11),
+            Err(VoteError::PowerAboveMaximum.into())
+        );
+    });
+    env.as_contract(&contract_id, || {
+        logic::grant_voting_power(&env, &governance, &member, 10).unwrap();
+        assert_eq!(storage::get_voting_power(&env, &member), 10);
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            logic::register_voter(&env, &governance, &Address::generate(&env), 11),
+            Err(VoteError::PowerAboveMaximum.into())
+        );
+    });
+    let entries = Vec::from_array(
+        &env,
+        [
+            (Address::generate(&env), 1u32),
+            (Address::generate(&env), 11),
+        ],
+    );
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            logic::set_voting_power_batch(&env, &governance, &entries),
+            Err(VoteError::PowerAboveMaximum.into())
+        );
+        assert_eq!(
+            storage::get_voting_power(&env, &entries.get(0).unwrap().0),
+            0
+        );
+    });
+
+    // Lifting the maximum allows larger grants again
+    env.as_contract(&contract_id, || {
+        logic::set_max_voting_power(&env, &governance, 0).unwrap();
+    });
+    env.as_contract(&contract_id, || {
+        logic::grant_voting_power(&env, &governance, &member, 11).unwrap();
+    });
+}
+
+#[test]
+fn test_emergency_upgrade_disarms_after_one_use() {
+    let (env, contract_id, governance, proposer) = setup_upgrade_storage();
+    env.ledger().with_mut(|l| l.timestamp = 4_000);
+    let implementation = Address::generate(&env);
+    let patch = create_test_version(1, 0, 1);
+
+    env.as_contract(&contract_id, || {
+        assert!(storage::is_emergency_one_shot(&env));
+        assert_eq!(storage::get_last_emergency_upgrade(&env), None);
+        logic::toggle_emergency_upgrades(&env, &governance, true).unwrap();
+    });
+    env.as_contract(&contract_id, || {
+        logic::emergency_upgrade(&env, &governance, &implementation, &patch).unwrap();
+    });
+    env.as_contract(&contract_id, || {
+        assert!(!storage::is_emergency_upgrade_enabled(&env));
+        assert_eq!(
+            storage::get_last_emergency_upgrade(&env),
+            Some(EmergencyUpgradeRecord {
+                caller: governance.clone(),
+                version: patch.clone(),
+                implementation: implementation.clone(),
+                timestamp: 4_000,
+            })
+        );
+        assert_eq!(
+            logic::emergency_upgrade(
+                &env,
+                &governance,
+                &implementation,
+                &create_test_version(1, 0, 2)
+            ),
+            Err(UpgradeError::EmergencyDisabled.into())
+        );
+    });
+
+    // Opting out keeps the path open after use
+    env.as_contract(&contract_id, || {
+        let err = logic::set_emergency_one_shot(&env, &proposer, false).unwrap_err();
+        assert_eq!(err, UpgradeError::NotAuthorized.into());
+    });
+    env.as_contract(&contract_id, || {
+        logic::set_emergency_one_shot(&env, &governance, false).unwrap();
+    });
+    env.as_contract(&contract_id, || {
+        logic::toggle_emergency_upgrades(&env, &governance, true).unwrap();
+    });
+    for patch in [2, 3] {
+        env.as_contract(&contract_id, || {
+            let version = create_test_version(1, 0, patch);
+            logic::emergency_upgrade(&env, &governance, &implementation, &version).unwrap();
+        });
+    }
+    env.as_contract(&contract_id, || {
+        assert!(storage::is_emergency_upgrade_enabled(&env));
+        assert_eq!(
+            storage::get_last_emergency_upgrade(&env).unwrap().version,
+            create_test_version(1, 0, 3)
+        );
+    });
+}
+
+#[test]
+fn test_emergency_window_closes_at_expiry() {
+    let (env, contract_id, governance, proposer) = setup_upgrade_storage();
+    env.ledger().with_mut(|l| l.timestamp = 1_000);
+    let implementation = Address::generate(&env);
+
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            logic::enable_emergency_upgrades_until(&env, &governance, 1_000),
+            Err(GuardError::ExpiryInPast.into())
+        );
+        let err = logic::enable_emergency_upgrades_until(&env, &proposer, 2_000).unwrap_err();
+        assert_eq!(err, UpgradeError::NotAuthorized.into());
+    });
+    env.as_contract(&contract_id, || {
+        logic::enable_emergency_upgrades_until(&env, &governance, 2_000).unwrap();
+        let (_, topics, data) = env.events().all().last().unwrap();
+        assert_eq!(
+            topics,
+            (symbol_short!("upgrade"), Symbol::new(&env, "emerg_window")).into_val(&env)
+        );
+        let expiry: u64 = data.into_val(&env);
+        assert_eq!(expiry, 2_000);
+        assert_eq!(storage::get_emergency_expiry(&env), Some(2_000));
+    });
+
+    // Open up to and including the expiry
+    env.ledger().with_mut(|l| l.timestamp = 2_000);
+    env.as_contract(&contract_id, || {
+        assert!(storage::is_emergency_upgrade_enabled(&env));
+    });
+    env.ledger().with_mut(|l| l.timestamp = 2_001);
+    env.as_contract(&contract_id, || {
+        assert!(!storage::is_emergency_upgrade_enabled(&env));
+        assert_eq!(
+            logic::emergency_upgrade(
+                &env,
+                &governance,
+                &implementation,
+                &create_test_version(1, 0, 1)
+            ),
+            Err(GuardError::EmergencyWindowExpired.into())
+        );
+        assert_eq!(
+            storage::get_current_version(&env),
+            create_test_version(1, 0, 0)
+        );
+    });
+
+    // Toggling replaces the window with an open-ended enablement
+    env.as_contract(&contract_id, || {
+        logic::toggle_emergency_upgrades(&env, &governance, true).unwrap();
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(storage::get_emergency_expiry(&env), None);
+        logic::emergency_upgrade(
+            &env,
+            &governance,
+            &implementation,
+            &create_test_version(1, 0, 1),
+        )
+        .unwrap();
+    });
+}
+
+#[test]
+fn test_emergency_upgrade_is_recorded_in_proxy_history() {
+    let (env, contract_id, governance, _) = setup_upgrade_storage();
+    env.ledger().with_mut(|l| l.timestamp = 3_000);
+    let implementation = Address::generate(&env);
+    env.as_contract(&contract_id, || {
+        crate::proxy::storage::initialize(&env, Address::generate(&env), governance.clone())
+            .unwrap();
+        logic::toggle_emergency_upgrades(&env, &governance, true).unwrap();
+    });
+    let before = env.as_contract(&contract_id, || {
+        crate::proxy::storage::get_upgrade_count(&env)
+    });
+
+    env.as_contract(&contract_id, || {
+        logic::emergency_upgrade(
+            &env,
+            &governance,
+            &implementation,
+            &create_test_version(1, 0, 1),
+        )
+        .unwrap();
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(crate::proxy::storage::get_upgrade_count(&env), before + 1);
+        assert_eq!(
+            crate::proxy::storage::get_implementation(&env),
+            implementation
+        );
+        let transaction = crate::proxy::storage::get_latest_upgrade(&env).unwrap();
+        assert_eq!(transaction.initiator, governance);
+        assert_eq!(transaction.new_implementation, implementation);
+        assert_eq!(transaction.timestamp, 3_000);
+        assert!(transaction.success);
+        assert!(transaction.emergency);
+    });
+}
+
+#[test]
+fn test_schema_step_tags_proxy_transactions() {
+    let (env, contract_id, governance, _) = setup_upgrade_storage();
+    let implementation = Address::generate(&env);
+    env.as_contract(&contract_id, || {
+        // One transaction in the schema 8 layout, without the tag
+        let id = crate::proxy::storage::take_next_upgrade_id(&env);
+        let legacy = crate::proxy::types::LegacyUpgradeTransaction {
+            id,
+            new_implementation: implementation.clone(),
+            initiator: governance.clone(),
+            timestamp: 0,
+            success: true,
+            failure_reason: None,
+        };
+        env.storage()
+            .persistent()
+            .set(&(crate::keys::proxy::UPGRADE_HISTORY_KEY, id), &legacy);
+        storage::set_storage_schema(&env, 8);
+    });
+
+    env.as_contract(&contract_id, || {
+        assert_eq!(logic::migrate_storage_schema(&env, &governance, 9), Ok(1));
+    });
+    env.as_contract(&contract_id, || {
+        let transaction = crate::proxy::storage::get_latest_upgrade(&env).unwrap();
+        assert_eq!(transaction.new_implementation, implementation);
+        assert!(!transaction.emergency);
+        assert_eq!(crate::proxy::storage::migrate_upgrade_transactions(&env), 0);
+    });
+}
+
+#[test]
+fn test_proposals_must_target_a_newer_compatible_version() {
+    let (env, contract_id, governance, proposer) = setup_upgrade_storage();
+    env.as_contract(&contract_id, || {
+        storage::set_current_version(&env, &create_test_version(1, 2, 3), &governance, None);
+    });
+
+    for (version, expected) in [
+        (create_test_version(1, 2, 3), GuardError::VersionNotNewer),
+        (create_test_version(1, 2, 2), GuardError::VersionNotNewer),
+        (create_test_version(1, 1, 9), GuardError::VersionNotNewer),
+        (create_test_version(0, 9, 9), GuardError::VersionNotNewer),
+    ] {
+        env.as_contract(&contract_id, || {
+            assert_eq!(
+                logic::propose_upgrade(
+                    &env,
+                    &proposer,
+                    &Address::generate(&env),
+                    &version,
+                    String::from_str(&env, "older"),
+                ),
+                Err(expected.into())
+            );
+        });
+    }
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            logic::propose_upgrade_on_track(
+                &env,
+                &proposer,
+                &Address::generate(&env),
+                &create_test_version(2, 0, 0),
+                String::from_str(&env, "breaking"),
+                STANDARD_TRACK_ID,
+            ),
+            Err(GuardError::IncompatibleVersion.into())
+        );
+    });
+
+    // Execution checks against the version current at that time
+    let patch =
+        create_approved_proposal(&env, &contract_id, &proposer, create_test_version(1, 2, 4));
+    let minor =
+        create_approved_proposal(&env, &contract_id, &proposer, create_test_version(1, 3, 0));
+    env.as_contract(&contract_id, || {
+        logic::execute_proposal(&env, &governance, minor).unwrap();
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            logic::get_execution_blockers(&env, patch),
+            Vec::from_array(&env, [Blocker::VersionNotNewer])
+        );
+        assert_eq!(
+            logic::execute_proposal(&env, &governance, patch),
+            Ok(ExecutionOutcome::RevalidationFailed)
+        );
+    });
+}
+
+#[test]
+fn test_version_history_records_every_change() {
+    let (env, contract_id, governance, proposer) = setup_upgrade_storage();
+    let v = create_test_version;
+
+    env.ledger().with_mut(|l| l.timestamp = 100);
+    let proposal_id = create_approved_proposal(&env, &contract_id, &proposer, v(1, 1, 0));
+    env.as_contract(&contract_id, || {
+        logic::execute_proposal(&env, &governance, proposal_id).unwrap();
+    });
+    env.ledger().with_mut(|l| l.timestamp = 200);
+    env.as_contract(&contract_id, || {
+        logic::toggle_emergency_upgrades(&env, &governance, true).unwrap();
+    });
+    env.as_contract(&contract_id, || {
+        logic::emergency_upgrade(&env, &governance, &Address::generate(&env), &v(1, 1, 1)).unwrap();
+    });
+    env.ledger().with_mut(|l| l.timestamp = 300);
+    env.as_contract(&contract_id, || {
+        logic::rollback_to_version(&env, &governance, &v(1, 1, 0)).unwrap();
+    });
+
+    env.as_contract(&contract_id, || {
+        // The rollback is a new entry; earlier ones are left as they were
+        let history = storage::get_version_history(&env, 0, 10);
+        let expected = [
+            (v(1, 0, 0), 0, None),
+            (v(1, 1, 0), 100, Some(proposal_id)),
+            (v(1, 1, 1), 200, None),
+            (v(1, 1, 0), 300, None),
+        ];
+        assert_eq!(history.len(), expected.len() as u32);
+        for (change, (version, changed_at, source)) in history.iter().zip(expected) {
+            assert_eq!(change.version, version);
+            assert_eq!(change.changed_at, changed_at);
+            assert_eq!(change.proposal_id, source);
+        }
+        assert_eq!(history.get(1).unwrap().changed_by, governance);
+
+        let page = storage::get_version_history(&env, 1, 2);
+        assert_eq!(page.len(), 2);
+        assert_eq!(page.get(0).unwrap().version, v(1, 1, 0));
+        assert_eq!(storage::get_version_history(&env, 4, 10).len(), 0);
+
+        for (timestamp, expected) in [
+            (0, v(1, 0, 0)),
+            (99, v(1, 0, 0)),
+            (100, v(1, 1, 0)),
+            (250, v(1, 1, 1)),
+            (300, v(1, 1, 0)),
+            (u64::MAX, v(1, 1, 0)),
+        ] {
+            assert_eq!(storage::get_version_at(&env, timestamp), Some(expected));
+        }
+    });
+}
+
+#[test]
+fn test_rollback_restores_the_target_implementation() {
+    let (env, contract_id, governance, proposer) = setup_upgrade_storage();
+    let v = create_test_version;
+    let original = Address::generate(&env);
+    env.as_contract(&contract_id, || {
+        crate::proxy::storage::initialize(&env, original.clone(), governance.clone()).unwrap();
+        crate::proxy::storage::set_skip_version_check(&env, true);
+    });
+
+    let mut implementations = Vec::new(&env);
+    for version in [v(1, 1, 0), v(1, 2, 0)] {
+        let proposal_id = create_approved_proposal(&env, &contract_id, &proposer, version);
+        env.as_contract(&contract_id, || {
+            logic::execute_proposal(&env, &governance, proposal_id).unwrap();
+        });
+        implementations.push_back(env.as_contract(&contract_id, || {
+            storage::get_upgrade_proposal(&env, proposal_id)
+                .unwrap()
+                .new_contract_address
+        }));
+    }
+
+    env.as_contract(&contract_id, || {
+        logic::rollback_to_version(&env, &governance, &v(1, 1, 0)).unwrap();
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(storage::get_current_version(&env), v(1, 1, 0));
+        assert_eq!(
+            crate::proxy::storage::get_implementation(&env),
+            implementations.get(0).unwrap()
+        );
+        let transaction = crate::proxy::storage::get_latest_upgrade(&env).unwrap();
+        assert_eq!(
+            transaction.new_implementation,
+            implementations.get(0).unwrap()
+        );
+        assert_eq!(transaction.initiator, governance);
+        assert!(transaction.success);
+        assert!(!transaction.emergency);
+    });
+
+    // The implementation replaced by the first upgrade is remembered for 1.0.0
+    env.as_contract(&contract_id, || {
+        logic::rollback_to_version(&env, &governance, &v(1, 0, 0)).unwrap();
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(crate::proxy::storage::get_implementation(&env), original);
+        assert_eq!(
+            storage::get_version_implementation(&env, &v(1, 2, 0)),
+            implementations.get(1)
+        );
+    });
+}
+
+#[test]
+fn test_execute_upgrade_checks_the_reported_version() {
+    let (env, contract_id, governance, proposer) = setup_upgrade_storage();
+    let candidate = env.register_contract(None, crate::upgrade::testutils::VersionedImplementation);
+    let reporter = crate::upgrade::testutils::VersionedImplementationClient::new(&env, &candidate);
+    reporter.report(&create_test_version(1, 2, 0));
+    env.as_contract(&contract_id, || {
+        crate::proxy::storage::initialize(&env, Address::generate(&env), governance.clone())
+            .unwrap();
+    });
+    let proposal_id = env.as_contract(&contract_id, || {
+        logic::propose_upgrade(
+            &env,
+            &proposer,
+            &candidate,
+            &create_test_version(1, 1, 0),
+            String::from_str(&env, "wrong build"),
+        )
+        .unwrap()
+    });
+    env.as_contract(&contract_id, || {
+        logic::transition(
+            &env,
+            proposal_id,
+            UpgradeStatus::Approved,
+            symbol_short!("test"),
+        )
+        .unwrap();
+    });
+
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            logic::execute_proposal(&env, &governance, proposal_id),
+            Err(GuardError::ImplementationVersionMismatch.into())
+        );
+    });
+
+    // The right build goes through
+    reporter.report(&create_test_version(1, 1, 0));
+    env.as_contract(&contract_id, || {
+        logic::execute_proposal(&env, &governance, proposal_id).unwrap();
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(crate::proxy::storage::get_implementation(&env), candidate);
+        assert_eq!(
+            storage::get_current_version(&env),
+            create_test_version(1, 1, 0)
+        );
+    });
+}
+
+#[test]
+fn test_emergency_upgrade_bypasses_the_proxy_cooldown() {
+    let (env, contract_id, governance, _) = setup_upgrade_storage();
+    env.ledger().with_mut(|l| l.timestamp = 5_000);
+    let implementation = Address::generate(&env);
+    env.as_contract(&contract_id, || {
+        crate::proxy::storage::initialize(&env, Address::generate(&env), governance.clone())
+            .unwrap();
+    });
+    env.as_contract(&contract_id, || {
+        crate::proxy::implementation::set_min_upgrade_interval(&env, &governance, 86_400).unwrap();
+    });
+    env.as_contract(&contract_id, || {
+        logic::toggle_emergency_upgrades(&env, &governance, true).unwrap();
+    });
+    env.as_contract(&contract_id, || {
+        logic::emergency_upgrade(
+            &env,
+            &governance,
+            &implementation,
+            &create_test_version(1, 0, 1),
+        )
+        .unwrap();
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            crate::proxy::storage::get_implementation(&env),
+            implementation
+        );
+    });
+}
+
+#[test]
+fn test_governance_cannot_bypass_a_frozen_proxy() {
+    let (env, contract_id, governance, proposer) = setup_upgrade_storage();
+    let original = Address::generate(&env);
+    env.as_contract(&contract_id, || {
+        crate::proxy::storage::initialize(&env, original.clone(), governance.clone()).unwrap();
+        crate::proxy::storage::set_skip_version_check(&env, true);
+        storage::set_version_implementation(&env, &create_test_version(1, 0, 0), &original);
+        logic::toggle_emergency_upgrades(&env, &governance, true).unwrap();
+    });
+    let proposal_id =
+        create_approved_proposal(&env, &contract_id, &proposer, create_test_version(1, 1, 0));
+    env.as_contract(&contract_id, || {
+        crate::proxy::implementation::freeze_upgrades(&env, &governance, &original).unwrap();
+    });
+
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            logic::execute_proposal(&env, &governance, proposal_id),
+            Err(GuardError::UpgradesFrozen.into())
+        );
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            logic::emergency_upgrade(
+                &env,
+                &governance,
+                &Address::generate(&env),
+                &create_test_version(1, 0, 1),
+            ),
+            Err(GuardError::UpgradesFrozen.into())
+        );
+    });
+    env.as_contract(&contract_id, || {
+        storage::set_current_version(&env, &create_test_version(1, 2, 0), &governance, None);
+        assert_eq!(
+            logic::rollback_to_version(&env, &governance, &create_test_version(1, 0, 0)),
+            Err(GuardError::UpgradesFrozen.into())
+        );
+        assert_eq!(crate::proxy::storage::get_implementation(&env), original);
+    });
+}
+
+#[test]
+fn test_schema_step_records_replaced_implementations() {
+    let (env, contract_id, governance, _) = setup_upgrade_storage();
+    let first = Address::generate(&env);
+    let second = Address::generate(&env);
+    env.as_contract(&contract_id, || {
+        // A success, a failure and another success in the schema 9 layout
+        for (target, success) in [(&first, true), (&first, false), (&second, true)] {
+            let id = crate::proxy::storage::take_next_upgrade_id(&env);
+            let tagged = crate::proxy::types::TaggedUpgradeTransaction {
+                id,
+                new_implementation: target.clone(),
+                initiator: governance.clone(),
+                timestamp: 0,
+                success,
+                failure_reason: None,
+                emergency: false,
+            };
+            env.storage()
+                .persistent()
+                .set(&(crate::keys::proxy::UPGRADE_HISTORY_KEY, id), &tagged);
+        }
+        storage::set_storage_schema(&env, 9);
+    });
+
+    env.as_contract(&contract_id, || {
+        assert_eq!(logic::migrate_storage_schema(&env, &governance, 10), Ok(3));
+    });
+    env.as_contract(&contract_id, || {
+        let transaction = |id| crate::proxy::storage::get_upgrade_transaction(&env, id).unwrap();
+        // Nothing is known before the first one
+        assert_eq!(transaction(1).old_implementation, first);
+        assert_eq!(transaction(2).old_implementation, first);
+        assert_eq!(transaction(3).old_implementation, first);
+        assert!(!transaction(3).revert);
+        assert_eq!(crate::proxy::storage::migrate_upgrade_transactions(&env), 0);
+    });
+}
+
+#[test]
+fn test_guardian_vetoes_pending_and_approved_proposals() {
+    let (env, contract_id, governance, proposer) = setup_upgrade_storage();
+    let guardian = Address::generate(&env);
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            auth::set_guardian(&env, &guardian, Some(guardian.clone())),
+            Err(UpgradeError::NotAuthorized)
+        );
+    });
+    env.as_contract(&contract_id, || {
+        auth::set_guardian(&env, &governance, Some(guardian.clone())).unwrap();
+        assert_eq!(auth::get_guardian(&env), Some(guardian.clone()));
+    });
+
+    let pending = env.as_contract(&contract_id, || {
+        logic::propose_upgrade(
+            &env,
+            &proposer,
+            &Address::generate(&env),
+            &create_test_version(1, 1, 0),
+            String::from_str(&env, "pending"),
+        )
+        .unwrap()
+    });
+    let approved =
+        create_approved_proposal(&env, &contract_id, &proposer, create_test_version(1, 2, 0));
+    let executed =
+        create_approved_proposal(&env, &contract_id, &proposer, create_test_version(1, 3, 0));
+
+    // Blocking is all the guardian may do; governance does not veto by default
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            logic::veto_proposal(&env, &governance, pending),
+            Err(UpgradeError::NotAuthorized.into())
+        );
+    });
+    env.as_contract(&contract_id, || {
+        logic::veto_proposal(&env, &guardian, pending).unwrap();
+    });
+    let (_, topics, data) = env.events().all().last().unwrap();
+    assert_eq!(
+        topics,
+        (
+            symbol_short!("upgrade"),
+            Symbol::new(&env, "proposal_vetoed")
+        )
+            .into_val(&env)
+    );
+    let vetoed: (u64, Address) = data.into_val(&env);
+    assert_eq!(vetoed, (pending, guardian.clone()));
+    env.as_contract(&contract_id, || {
+        logic::veto_proposal(&env, &guardian, approved).unwrap();
+    });
+    env.as_contract(&contract_id, || {
+        for proposal_id in [pending, approved] {
+            assert_eq!(
+                storage::get_upgrade_proposal(&env, proposal_id)
+                    .unwrap()
+                    .status,
+                UpgradeStatus::Vetoed
+            );
+        }
+        assert_eq!(
+            logic::execute_proposal(&env, &governance, approved),
+            Err(UpgradeError::ProposalNotApproved.into())
+        );
+    });
+
+    env.as_contract(&contract_id, || {
+        logic::transition(
+            &env,
+            executed,
+            UpgradeStatus::Executed,
+            symbol_short!("test"),
+        )
+        .unwrap();
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            logic::veto_proposal(&env, &guardian, executed),
+            Err(ProposalError::IllegalTransition.into())
+        );
+    });
+
+    // Governance can do away with the role altogether
+    env.as_contract(&contract_id, || {
+        auth::set_guardian(&env, &governance, None).unwrap();
+        assert_eq!(auth::get_guardian(&env), None);
+    });
+    let fresh =
+        create_approved_proposal(&env, &contract_id, &proposer, create_test_version(1, 4, 0));
+    let client = crate::StellarGuildsContractClient::new(&env, &contract_id);
+    assert_eq!(
+        client.try_veto_upgrade_proposal(&guardian, &fresh),
+        Err(Ok(UpgradeError::NotAuthorized.into()))
+    );
+    assert_eq!(client.get_upgrade_guardian(), None);
+}
+
+#[test]
+fn test_governance_transfer_strips_the_old_address() {
+    let (env, contract_id, governance, proposer) = setup_upgrade_storage();
+    let successor = Address::generate(&env);
+    let approved =
+        create_approved_proposal(&env, &contract_id, &proposer, create_test_version(1, 1, 0));
+
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            logic::transfer_governance(&env, &proposer, &proposer),
+            Err(UpgradeError::NotAuthorized.into())
+        );
+        assert_eq!(
+            logic::accept_governance(&env, &successor),
+            Err(GuardError::NoPendingGovernance.into())
+        );
+    });
+    env.as_contract(&contract_id, || {
+        logic::transfer_governance(&env, &governance, &successor).unwrap();
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            logic::accept_governance(&env, &proposer),
+            Err(GuardError::NotPendingGovernance.into())
+        );
+        // Nothing changes until the nominee accepts
+        assert_eq!(storage::get_governance_address(&env), governance);
+    });
+    env.as_contract(&contract_id, || {
+        logic::accept_governance(&env, &successor).unwrap();
+    });
+    let (_, topics, data) = env.events().all().last().unwrap();
+    assert_eq!(
+        topics,
+        (
+            symbol_short!("upgrade"),
+            Symbol::new(&env, "governance_changed")
+        )
+            .into_val(&env)
+    );
+    let change: (Address, Address) = data.into_val(&env);
+    assert_eq!(change, (governance.clone(), successor.clone()));
+
+    env.as_contract(&contract_id, || {
+        assert_eq!(storage::get_governance_address(&env), successor);
+        assert_eq!(storage::get_pending_governance(&env), None);
+        assert_eq!(
+            logic::execute_proposal(&env, &governance, approved),
+            Err(UpgradeError::NotAuthorized.into())
+        );
+    });
+    env.as_contract(&contract_id, || {
+        let plan = MigrationPlan {
+            from_version: create_test_version(1, 0, 0),
+            to_version: create_test_version(1, 1, 0),
+            migration_function_selector: symbol_short!("migrate"),
+            estimated_gas: 100_000,
+        };
+        assert_eq!(
+            logic::register_migration_plan(&env, &governance, approved, &plan),
+            Err(UpgradeError::NotAuthorized.into())
+        );
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            logic::toggle_emergency_upgrades(&env, &governance, true),
+            Err(UpgradeError::NotAuthorized.into())
+        );
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            logic::rollback_to_version(&env, &governance, &create_test_version(1, 0, 0)),
+            Err(UpgradeError::NotAuthorized.into())
+        );
+    });
+    env.as_contract(&contract_id, || {
+        logic::toggle_emergency_upgrades(&env, &successor, true).unwrap();
+        assert!(storage::is_emergency_upgrade_enabled(&env));
+    });
+
+    let client = crate::StellarGuildsContractClient::new(&env, &contract_id);
+    assert_eq!(
+        client.try_transfer_upgrade_governance(&governance, &governance),
+        Err(Ok(UpgradeError::NotAuthorized.into()))
+    );
+    client.transfer_upgrade_governance(&successor, &governance);
+    client.cancel_governance_transfer(&successor);
+    assert_eq!(client.get_pending_upgrade_governance(), None);
+}
+
+#[test]
+fn test_governance_actions_execute_after_a_vote() {
+    let (env, contract_id, governance, proposer) = setup_upgrade_storage();
+    let successor = Address::generate(&env);
+    let raise_quorum = ProposalKind::SetParameter(QUORUM_PARAM, 6_000);
+
+    env.as_contract(&contract_id, || {
+        storage::set_voting_power(&env, &proposer, 1);
+        let description = String::from_str(&env, "quorum");
+        assert_eq!(
+            logic::propose_action(
+                &env,
+                &proposer,
+                &ProposalKind::SetParameter(symbol_short!("nope"), 1),
+                description.clone(),
+            ),
+            Err(GuardError::UnknownParameter.into())
+        );
+        assert_eq!(
+            logic::propose_action(
+                &env,
+                &proposer,
+                &ProposalKind::SetParameter(QUORUM_PARAM, 10_001),
+                description,
+            ),
+            Err(UpgradeError::InvalidQuorum.into())
+        );
+    });
+    let proposal_id = env.as_contract(&contract_id, || {
+        logic::propose_action(
+            &env,
+            &proposer,
+            &raise_quorum,
+            String::from_str(&env, "quorum"),
+        )
+        .unwrap()
+    });
+    env.as_contract(&contract_id, || {
+        let mut proposal = storage::get_upgrade_proposal(&env, proposal_id).unwrap();
+        proposal.total_voting_power_snapshot = 1;
+        storage::store_upgrade_proposal(&env, &proposal);
+        assert_eq!(
+            storage::get_proposal_kind(&env, proposal_id),
+            Some(raise_quorum.clone())
+        );
+    });
+    env.as_contract(&contract_id, || {
+        logic::vote_on_proposal(&env, &proposer, proposal_id, true).unwrap();
+    });
+    env.as_contract(&contract_id, || {
+        logic::execute_proposal(&env, &governance, proposal_id).unwrap();
+    });
+    let (_, topics, data) = env.events().all().last().unwrap();
+    assert_eq!(
+        topics,
+        (
+            symbol_short!("upgrade"),
+            Symbol::new(&env, "action_executed")
+        )
+            .into_val(&env)
+    );
+    let executed: (u64, ProposalKind) = data.into_val(&env);
+    assert_eq!(executed, (proposal_id, raise_quorum));
+
+    env.as_contract(&contract_id, || {
+        assert_eq!(storage::get_governance_config(&env).quorum_bps, 6_000);
+        assert_eq!(
+            storage::get_upgrade_proposal(&env, proposal_id)
+                .unwrap()
+                .status,
+            UpgradeStatus::Executed
+        );
+        // Nothing was upgraded
+        assert_eq!(
+            storage::get_current_version(&env),
+            create_test_version(1, 0, 0)
+        );
+    });
+
+    // The other kinds share the same path through the client
+    let client = crate::StellarGuildsContractClient::new(&env, &contract_id);
+    let approve = |kind: &ProposalKind| {
+        let proposal_id =
+            client.propose_governance_action(&proposer, kind, &String::from_str(&env, "action"));
+        env.as_contract(&contract_id, || {
+            logic::transition(
+                &env,
+                proposal_id,
+                UpgradeStatus::Approved,
+                symbol_short!("test"),
+            )
+            .unwrap();
+        });
+        proposal_id
+    };
+    let toggle = approve(&ProposalKind::ToggleEmergency(true));
+    let handover = approve(&ProposalKind::SetGovernance(successor.clone()));
+    assert_eq!(
+        client.get_upgrade_proposal_kind(&handover),
+        Some(ProposalKind::SetGovernance(successor.clone()))
+    );
+
+    assert_eq!(
+        client.try_execute_upgrade_proposal(&proposer, &toggle),
+        Err(Ok(UpgradeError::NotAuthorized.into()))
+    );
+    assert_eq!(
+        client.execute_upgrade_proposal(&governance, &toggle),
+        ExecutionOutcome::Executed
+    );
+    assert_eq!(
+        client.execute_upgrade_proposal(&governance, &handover),
+        ExecutionOutcome::Executed
+    );
+    env.as_contract(&contract_id, || {
+        assert!(storage::is_emergency_upgrade_enabled(&env));
+        assert_eq!(storage::get_governance_address(&env), successor);
+    });
+}
+
+#[test]
+fn test_proxy_revert_moves_the_version_back_with_the_code() {
+    let (env, contract_id, governance, proposer) = setup_upgrade_storage();
+    let admin = Address::generate(&env);
+    let original = Address::generate(&env);
+    env.as_contract(&contract_id, || {
+        crate::proxy::storage::initialize(&env, original.clone(), admin.clone()).unwrap();
+        crate::proxy::storage::set_skip_version_check(&env, true);
+    });
+    let proposal_id =
+        create_approved_proposal(&env, &contract_id, &proposer, create_test_version(1, 1, 0));
+    env.as_contract(&contract_id, || {
+        logic::execute_proposal(&env, &governance, proposal_id).unwrap();
+    });
+
+    // Delay-only mode holds the revert back like any other switch
+    env.as_contract(&contract_id, || {
+        crate::proxy::implementation::set_require_delay(&env, &admin, true).unwrap();
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            crate::proxy::implementation::revert_last_upgrade(&env, &admin),
+            Err(ProxyError::DelayRequired)
+        );
+    });
+    env.as_contract(&contract_id, || {
+        crate::proxy::implementation::set_require_delay(&env, &admin, false).unwrap();
+    });
+
+    // Without a version that ran the old code, the two would disagree
+    let installed = env.as_contract(&contract_id, || {
+        let installed = crate::proxy::storage::get_implementation(&env);
+        storage::set_version_implementation(&env, &create_test_version(1, 0, 0), &installed);
+        installed
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            crate::proxy::implementation::revert_last_upgrade(&env, &admin),
+            Err(ProxyError::RevertVersionUnknown)
+        );
+    });
+    env.as_contract(&contract_id, || {
+        storage::set_version_implementation(&env, &create_test_version(1, 0, 0), &original);
+    });
+
+    env.as_contract(&contract_id, || {
+        assert!(
+            crate::proxy::implementation::revert_last_upgrade(&env, &admin)
+                .unwrap()
+                .is_some()
+        );
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(crate::proxy::storage::get_implementation(&env), original);
+        assert_eq!(
+            storage::get_current_version(&env),
+            create_test_version(1, 0, 0)
+        );
+        assert_eq!(
+            storage::get_version_implementation(&env, &create_test_version(1, 1, 0)),
+            Some(installed)
+        );
+    });
+}
@@ -0,0 +1,239 @@
+#![cfg(any(test, feature = "testutils"))]
+
+//! Stub contracts for exercising `MigrationVerdict` and `CallOutcome` handling.
+//!
+//! Register `VerdictMigration` as a proposal's new contract address and pick
+//! the verdict with the plan's selector: `migrate_ok`, `migrate_abort` or
+//! `migrate_defer`, or `migrate_trap` and `migrate_malformed` for a failed
+//! call. `TrappingHook`, `MalformedHook` and `RejectingHook` answer
+//! `on_upgrade` and `on_handover_start` with one failure shape each.
+//! `ReentrantMigration` calls back into the contract being upgraded.
+//! `VersionedImplementation` answers `version()` with whatever it was told
+//! to report.
+
+use crate::keys::tags::{STUB_READY_AT_KEY, STUB_REENTRY_KEY, STUB_TARGET_KEY, STUB_VERSION_KEY};
+use crate::upgrade::types::{ExecutionOutcome, MigrationVerdict, Version};
+use crate::utils::time;
+use soroban_sdk::{contract, contractimpl, vec, Address, Bytes, Env, Error, IntoVal, Symbol};
+
+/// Code returned by `migrate_abort`
+pub const STUB_ABORT_CODE: u32 = 7;
+/// Seconds `migrate_defer` asks the migration to wait
+pub const STUB_DEFER_SECONDS: u64 = 3_600;
+/// Code carried by `RejectingHook`'s failed outcomes
+pub const STUB_REJECT_CODE: u32 = 11;
+
+#[contract]
+pub struct VerdictMigration;
+
+#[contractimpl]
+impl VerdictMigration {
+    /// Append the target minor version to the value
+    pub fn migrate_ok(
+        _env: Env,
+        _from: Version,
+        to: Version,
+        _key: Symbol,
+        value: Bytes,
+    ) -> MigrationVerdict {
+        let mut migrated = value;
+        migrated.push_back(to.minor as u8);
+        MigrationVerdict::Ok(migrated)
+    }
+
+    /// Refuse every entry with `STUB_ABORT_CODE`
+    pub fn migrate_abort(
+        _env: Env,
+        _from: Version,
+        _to: Version,
+        _key: Symbol,
+        _value: Bytes,
+    ) -> MigrationVerdict {
+        MigrationVerdict::Abort(STUB_ABORT_CODE)
+    }
+
+    /// Defer for `STUB_DEFER_SECONDS` on first use, then migrate like `migrate_ok`
+    pub fn migrate_defer(
+        env: Env,
+        from: Version,
+        to: Version,
+        key: Symbol,
+        value: Bytes,
+    ) -> MigrationVerdict {
+        let ready_key = STUB_READY_AT_KEY;
+        let now = time::now(&env);
+        let ready_at: u64 = match env.storage().instance().get(&ready_key) {
+            Some(ready_at) => ready_at,
+            None => {
+                let ready_at = now + STUB_DEFER_SECONDS;
+                env.storage().instance().set(&ready_key, &ready_at);
+                ready_at
+            }
+        };
+        if now < ready_at {
+            return MigrationVerdict::Defer(ready_at);
+        }
+        Self::migrate_ok(env, from, to, key, value)
+    }
+
+    /// Trap on every entry
+    pub fn migrate_trap(
+        _env: Env,
+        _from: Version,
+        _to: Version,
+        _key: Symbol,
+        _value: Bytes,
+    ) -> MigrationVerdict {
+        panic!("migration unavailable");
+    }
+
+    /// Return a number where a `MigrationVerdict` is expected
+    pub fn migrate_malformed(
+        _env: Env,
+        _from: Version,
+        _to: Version,
+        _key: Symbol,
+        _value: Bytes,
+    ) -> u32 {
+        STUB_ABORT_CODE
+    }
+}
+
+#[contract]
+pub struct ReentrantMigration;
+
+#[contractimpl]
+impl ReentrantMigration {
+    /// Point `migrate_reenter` at the contract being upgraded
+    pub fn arm(env: Env, target: Address, voter: Address, proposal_id: u64) {
+        env.storage()
+            .instance()
+            .set(&STUB_TARGET_KEY, &(target, voter, proposal_id));
+    }
+
+    /// Try to vote on and execute the proposal mid-migration, record whether
+    /// each callback was turned away, then migrate like `migrate_ok`
+    pub fn migrate_reenter(
+        env: Env,
+        _from: Version,
+        to: Version,
+        _key: Symbol,
+        value: Bytes,
+    ) -> MigrationVerdict {
+        let (target, voter, proposal_id): (Address, Address, u64) =
+            env.storage().instance().get(&STUB_TARGET_KEY).unwrap();
+        let vote = env.try_invoke_contract::<(), Error>(
+            &target,
+            &Symbol::new(&env, "vote_on_upgrade_proposal"),
+            vec![
+                &env,
+                voter.into_val(&env),
+                proposal_id.into_val(&env),
+                true.into_val(&env),
+            ],
+        );
+        let execute = env.try_invoke_contract::<ExecutionOutcome, Error>(
+            &target,
+            &Symbol::new(&env, "execute_upgrade_proposal"),
+            vec![&env, voter.into_val(&env), proposal_id.into_val(&env)],
+        );
+        let rejected = (vote.is_err(), execute.is_err());
+        env.storage().instance().set(&STUB_REENTRY_KEY, &rejected);
+
+        let mut migrated = value;
+        migrated.push_back(to.minor as u8);
+        MigrationVerdict::Ok(migrated)
+    }
+
+    /// Whether the vote and execute callbacks were rejected, once attempted
+    pub fn reentry_results(env: Env) -> Option<(bool, bool)> {
+        env.storage().instance().get(&STUB_REENTRY_KEY)
+    }
+}
+
+#[contract]
+pub struct VersionedImplementation;
+
+#[contractimpl]
+impl VersionedImplementation {
+    /// Set the version `version()` reports
+    pub fn report(env: Env, version: Version) {
+        env.storage().instance().set(&STUB_VERSION_KEY, &version);
+    }
+
+    /// The reported version; traps until `report` has been called
+    pub fn version(env: Env) -> Version {
+        env.storage().instance().get(&STUB_VERSION_KEY).unwrap()
+    }
+}
+
+// Each hook lives in its own module since contracts sharing one cannot
+// export functions of the same name
+
+mod trapping_hook {
+    use crate::proxy::types::ProxyConfig;
+    use crate::upgrade::types::{CallOutcome, Version};
+    use soroban_sdk::{contract, contractimpl, Env};
+
+    #[contract]
+    pub struct TrappingHook;
+
+    #[contractimpl]
+    impl TrappingHook {
+        pub fn on_upgrade(_env: Env, _from: Version, _to: Version, _id: u64) -> CallOutcome {
+            panic!("hook unavailable");
+        }
+
+        pub fn on_handover_start(_env: Env, _config: ProxyConfig) -> CallOutcome {
+            panic!("hook unavailable");
+        }
+    }
+}
+
+mod malformed_hook {
+    use super::STUB_REJECT_CODE;
+    use crate::proxy::types::ProxyConfig;
+    use crate::upgrade::types::Version;
+    use soroban_sdk::{contract, contractimpl, Env};
+
+    #[contract]
+    pub struct MalformedHook;
+
+    /// Returns a number where a `CallOutcome` is expected
+    #[contractimpl]
+    impl MalformedHook {
+        pub fn on_upgrade(_env: Env, _from: Version, _to: Version, _id: u64) -> u32 {
+            STUB_REJECT_CODE
+        }
+
+        pub fn on_handover_start(_env: Env, _config: ProxyConfig) -> u32 {
+            STUB_REJECT_CODE
+        }
+    }
+}
+
+mod rejecting_hook {
+    use super::STUB_REJECT_CODE;
+    use crate::proxy::types::ProxyConfig;
+    use crate::upgrade::types::{CallOutcome, Version};
+    use soroban_sdk::{contract, contractimpl, symbol_short, Env};
+
+    #[contract]
+    pub struct RejectingHook;
+
+    /// Refuses with `STUB_REJECT_CODE`
+    #[contractimpl]
+    impl RejectingHook {
+        pub fn on_upgrade(_env: Env, _from: Version, _to: Version, _id: u64) -> CallOutcome {
+            CallOutcome::failed(STUB_REJECT_CODE, Some(symbol_short!("busy")))
+        }
+
+        pub fn on_handover_start(_env: Env, _config: ProxyConfig) -> CallOutcome {
+            CallOutcome::failed(STUB_REJECT_CODE, Some(symbol_short!("busy")))
+        }
+    }
+}
+
+pub use malformed_hook::MalformedHook;
+pub use rejecting_hook::RejectingHook;
+pub use trapping_hook::TrappingHook;
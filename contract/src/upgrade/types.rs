@@ -1,8 +1,12 @@
-use soroban_sdk::{contracttype, Address};
+use crate::events::types::HistoryRecord;
+use crate::utils::pagination::Cursor;
+use core::cmp::Ordering;
+use soroban_sdk::{contracterror, contracttype, Address, Bytes, BytesN, Env, String, Symbol, Vec};
 
-/// Represents the current version of the contract
+/// Represents the current version of the contract. Versions order by
+/// major, then minor, then patch.
 #[contracttype]
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
 pub struct Version {
     pub major: u32,
     pub minor: u32,
@@ -18,6 +22,19 @@ impl Version {
         }
     }
 
+    /// The largest component that changes going from `self` to `next`
+    pub fn diff_kind(&self, next: &Version) -> BumpKind {
+        if self.major != next.major {
+            BumpKind::Major
+        } else if self.minor != next.minor {
+            BumpKind::Minor
+        } else if self.patch != next.patch {
+            BumpKind::Patch
+        } else {
+            BumpKind::NoChange
+        }
+    }
+
     /// Compare two versions for compatibility
     /// Returns true if `self` is compatible with `other`
     pub fn is_compatible_with(&self, other: &Version) -> bool {
@@ -26,6 +43,60 @@ impl Version {
         // Minor version of self should be >= other for backward compatibility
         && self.minor >= other.minor
     }
+
+    /// Semver ordering: major, then minor, then patch
+    pub fn cmp_semver(&self, other: &Version) -> Ordering {
+        (self.major, self.minor, self.patch).cmp(&(other.major, other.minor, other.patch))
+    }
+
+    /// Whether `self` comes strictly after `other`
+    pub fn is_newer_than(&self, other: &Version) -> bool {
+        self.cmp_semver(other) == Ordering::Greater
+    }
+
+    /// Render as "major.minor.patch"
+    pub fn to_string(&self, env: &Env) -> String {
+        // Three u32 components and two dots fit in 32 bytes
+        let mut buf = [0u8; 32];
+        let mut len = 0;
+        for (i, part) in [self.major, self.minor, self.patch].into_iter().enumerate() {
+            if i > 0 {
+                buf[len] = b'.';
+                len += 1;
+            }
+            len += write_decimal(&mut buf[len..], part);
+        }
+        String::from_bytes(env, &buf[..len])
+    }
+}
+
+/// Write `value` in decimal at the start of `out`, returning the digits written
+fn write_decimal(out: &mut [u8], mut value: u32) -> usize {
+    let mut digits = [0u8; 10];
+    let mut count = 0;
+    loop {
+        digits[count] = b'0' + (value % 10) as u8;
+        count += 1;
+        value /= 10;
+        if value == 0 {
+            break;
+        }
+    }
+    for i in 0..count {
+        out[i] = digits[count - 1 - i];
+    }
+    count
+}
+
+/// Size of a version change, ordered from smallest to largest
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum BumpKind {
+    NoChange = 0,
+    Patch = 1,
+    Minor = 2,
+    Major = 3,
 }
 
 /// Status of an upgrade proposal
@@ -37,12 +108,143 @@ pub enum UpgradeStatus {
     Executed = 2,
     Rejected = 3,
     Cancelled = 4,
+    /// Executed by governance; waiting for the proxy's scheduled switch
+    AwaitingProxy = 5,
+    /// The proxy schedule was cancelled; the proposal needs governance attention
+    NeedsAttention = 6,
+    /// Not decided or executed in time
+    Expired = 7,
+    /// Execution was abandoned after approval
+    Failed = 8,
+    /// Executed, then reverted inside the revert window
+    Reverted = 9,
+    /// Blocked by the guardian before it was executed
+    Vetoed = 10,
+}
+
+impl UpgradeStatus {
+    /// Every status, in code order
+    pub const ALL: [UpgradeStatus; 11] = [
+        UpgradeStatus::Pending,
+        UpgradeStatus::Approved,
+        UpgradeStatus::Executed,
+        UpgradeStatus::Rejected,
+        UpgradeStatus::Cancelled,
+        UpgradeStatus::AwaitingProxy,
+        UpgradeStatus::NeedsAttention,
+        UpgradeStatus::Expired,
+        UpgradeStatus::Failed,
+        UpgradeStatus::Reverted,
+        UpgradeStatus::Vetoed,
+    ];
+
+    /// Whether a proposal in this status may move to `to`.
+    /// Rejected, Cancelled, Expired, Failed, Reverted and Vetoed are terminal;
+    /// Executed is terminal except for an instant revert.
+    pub fn can_transition_to(self, to: UpgradeStatus) -> bool {
+        use UpgradeStatus::*;
+        matches!(
+            (self, to),
+            (Pending, Approved | Rejected | Cancelled | Expired | Vetoed)
+                | (
+                    Approved,
+                    Executed | Cancelled | Expired | Failed | AwaitingProxy | Vetoed
+                )
+                | (AwaitingProxy, Executed | NeedsAttention)
+                | (Executed, Reverted)
+                | (NeedsAttention, Cancelled | Failed)
+        )
+    }
+
+    /// Whether no further transition is possible from this status
+    pub fn is_terminal(self) -> bool {
+        use UpgradeStatus::*;
+        matches!(
+            self,
+            Executed | Rejected | Cancelled | Expired | Failed | Reverted | Vetoed
+        )
+    }
+}
+
+/// Event payload for every proposal status change
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StatusChange {
+    pub proposal_id: u64,
+    pub from: UpgradeStatus,
+    pub to: UpgradeStatus,
+    /// What caused the change, e.g. `vote` or `execute`
+    pub reason: Symbol,
+}
+
+/// How new upgrade proposal ids are assigned
+#[contracttype]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ProposalIdMode {
+    /// Incrementing counter starting at 1
+    Sequential = 0,
+    /// First 8 bytes of sha256(proposer, target, version, salt) as a big-endian u64
+    ContentHash = 1,
+}
+
+/// What a governance proposal does once executed. Voting, thresholds,
+/// timelocks and events are the same for every kind.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ProposalKind {
+    /// Switch to this implementation at this version
+    Upgrade(Address, Version),
+    /// Set a governance config parameter, named by one of the `*_PARAM` tags
+    SetParameter(Symbol, u64),
+    /// Replace the governance address
+    SetGovernance(Address),
+    /// Enable or disable emergency upgrades
+    ToggleEmergency(bool),
 }
 
 /// Information about a proposed upgrade
 #[contracttype]
 #[derive(Clone, Debug)]
 pub struct UpgradeProposal {
+    pub id: u64,
+    pub proposer: Address,
+    pub new_contract_address: Address,
+    pub version: Version,
+    /// The full description, or its first bytes when it was spilled (see `description_hash`)
+    pub description: soroban_sdk::String,
+    pub timestamp: u64,
+    pub status: UpgradeStatus,
+    pub votes_for: u64,
+    pub votes_against: u64,
+    pub votes_abstain: u64,
+    /// Voters holding active power when the proposal was created
+    pub total_voters: u64,
+    /// Active voting power of those voters at creation; the denominator of
+    /// the threshold, quorum and normalized tallies
+    pub total_voting_power_snapshot: u64,
+    /// Votes are refused after this timestamp and anyone may then finalize
+    /// the proposal (0 means voting never closes)
+    pub voting_deadline: u64,
+    /// Salt supplied at creation (all zeroes when none); part of the id preimage in content hash mode
+    pub salt: BytesN<32>,
+    /// sha256 of a description longer than `MAX_INLINE_DESCRIPTION_LEN`, which
+    /// is kept in its own entry so vote-path rewrites don't carry it (all
+    /// zeroes when the description is inline)
+    pub description_hash: BytesN<32>,
+}
+
+impl UpgradeProposal {
+    /// Whether the full description was spilled to its own entry
+    pub fn description_spilled(&self) -> bool {
+        self.description_hash.to_array() != [0u8; 32]
+    }
+}
+
+/// Stored layout of `UpgradeProposal` while its tallies and voter counts
+/// were `u32` (schemas 2 to 7); only read by the tally widening migration
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct NarrowUpgradeProposal {
     pub id: u64,
     pub proposer: Address,
     pub new_contract_address: Address,
@@ -52,9 +254,115 @@ pub struct UpgradeProposal {
     pub status: UpgradeStatus,
     pub votes_for: u32,
     pub votes_against: u32,
+    pub votes_abstain: u32,
     pub total_voters: u32,
+    pub total_voting_power_snapshot: u32,
+    pub voting_deadline: u64,
+    pub salt: BytesN<32>,
+    pub description_hash: BytesN<32>,
 }
 
+impl NarrowUpgradeProposal {
+    /// Convert to the current layout
+    pub fn widen(self) -> UpgradeProposal {
+        UpgradeProposal {
+            id: self.id,
+            proposer: self.proposer,
+            new_contract_address: self.new_contract_address,
+            version: self.version,
+            description: self.description,
+            timestamp: self.timestamp,
+            status: self.status,
+            votes_for: self.votes_for as u64,
+            votes_against: self.votes_against as u64,
+            votes_abstain: self.votes_abstain as u64,
+            total_voters: self.total_voters as u64,
+            total_voting_power_snapshot: self.total_voting_power_snapshot as u64,
+            voting_deadline: self.voting_deadline,
+            salt: self.salt,
+            description_hash: self.description_hash,
+        }
+    }
+}
+
+/// Stored layout of `UpgradeProposal` before `total_voting_power_snapshot`
+/// was added; only read by the proposal snapshot migration
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct LegacyUpgradeProposal {
+    pub id: u64,
+    pub proposer: Address,
+    pub new_contract_address: Address,
+    pub version: Version,
+    pub description: soroban_sdk::String,
+    pub timestamp: u64,
+    pub status: UpgradeStatus,
+    pub votes_for: u32,
+    pub votes_against: u32,
+    pub votes_abstain: u32,
+    pub total_voters: u32,
+    pub salt: BytesN<32>,
+    pub description_hash: BytesN<32>,
+}
+
+impl LegacyUpgradeProposal {
+    /// Convert to the current layout with the given registry snapshot and deadline
+    pub fn with_snapshot(
+        self,
+        total_voters: u64,
+        total_voting_power: u64,
+        voting_deadline: u64,
+    ) -> UpgradeProposal {
+        UpgradeProposal {
+            id: self.id,
+            proposer: self.proposer,
+            new_contract_address: self.new_contract_address,
+            version: self.version,
+            description: self.description,
+            timestamp: self.timestamp,
+            status: self.status,
+            votes_for: self.votes_for as u64,
+            votes_against: self.votes_against as u64,
+            votes_abstain: self.votes_abstain as u64,
+            total_voters,
+            total_voting_power_snapshot: total_voting_power,
+            voting_deadline,
+            salt: self.salt,
+            description_hash: self.description_hash,
+        }
+    }
+}
+
+/// Upgrade storage layout this code reads. Schema 1 stored proposals
+/// without a voting power snapshot or voting deadline; schema 2 stored the
+/// governance config without an approval threshold; schema 3 stored tracks
+/// and proposal rules without separation of duties; schema 4 kept no index
+/// of proposal ids; schema 5 kept the proxy's upgrade history in a single map;
+/// schema 6 kept no running total of registered voting power; schema 7
+/// stored proposal tallies and voter counts as `u32`; schema 8 recorded
+/// proxy upgrade transactions without the `emergency` tag; schema 9 recorded
+/// them without the implementation they replaced; schema 10 stored vote
+/// receipt weights as `u32`; schema 11 kept no index from a delegate to its
+/// delegators; schema 12 kept no index of the stored governance tracks.
+pub const STORAGE_SCHEMA: u32 = 13;
+
+/// The stored upgrade layout is not the one this code reads
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct SchemaMismatch {
+    pub found: u32,
+    pub expected: u32,
+}
+
+impl From<SchemaMismatch> for UpgradeError {
+    fn from(_: SchemaMismatch) -> Self {
+        UpgradeError::SchemaMismatch
+    }
+}
+
+/// Longest description, in bytes, stored inline in an `UpgradeProposal`
+pub const MAX_INLINE_DESCRIPTION_LEN: u32 = 200;
+
 /// Represents a migration plan between contract versions
 #[contracttype]
 #[derive(Clone, Debug)]
@@ -64,3 +372,1809 @@ pub struct MigrationPlan {
     pub migration_function_selector: soroban_sdk::Symbol,
     pub estimated_gas: u64,
 }
+
+/// What a migration selector returns for each entry it is handed.
+///
+/// The selector is called on the new implementation as
+/// `selector(from_version, to_version, key, value) -> MigrationVerdict`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum MigrationVerdict {
+    /// The entry migrated; its new value
+    Ok(Bytes),
+    /// The implementation cannot migrate this data; the code is recorded and
+    /// the proposal fails
+    Abort(u32),
+    /// Not ready yet; the migration resumes from this entry no earlier than
+    /// the given timestamp
+    Defer(u64),
+}
+
+/// What a hook or notification handler returns to the upgrade system:
+/// subscribers' `on_upgrade` and an implementation's `on_handover_start`.
+/// A failed outcome carries the callee's own code and an optional detail.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CallOutcome {
+    pub success: bool,
+    pub code: u32,
+    pub detail: Option<Symbol>,
+}
+
+impl CallOutcome {
+    /// The call did what was asked
+    pub fn ok() -> Self {
+        CallOutcome {
+            success: true,
+            code: 0,
+            detail: None,
+        }
+    }
+
+    /// The callee refused; `code` is its own
+    pub fn failed(code: u32, detail: Option<Symbol>) -> Self {
+        CallOutcome {
+            success: false,
+            code,
+            detail,
+        }
+    }
+}
+
+/// A cross-contract call made through `calls::invoke` that did not succeed.
+/// `code` is the callee's contract error or failure code, 0 when it has none.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CallFailure {
+    pub callee: Address,
+    pub function: Symbol,
+    pub error: ProposalError,
+    pub code: u32,
+}
+
+impl From<CallFailure> for soroban_sdk::Error {
+    fn from(failure: CallFailure) -> Self {
+        failure.error.into()
+    }
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum MigrationStatus {
+    /// Started and deferred; resumed by `continue_migration`
+    InProgress = 0,
+    Completed = 1,
+    /// Halted by the implementation with an abort code
+    Aborted = 2,
+}
+
+/// What became of a call to execute an approved upgrade proposal. Halts
+/// that leave a record behind come back here rather than as an error, so the
+/// record outlives the call.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ExecutionOutcome {
+    /// The proposal was executed
+    Executed,
+    /// The switch was handed to the proxy schedule with this id
+    AwaitingProxy(u64),
+    /// The execution window had closed, so the proposal is now `Expired`
+    Expired,
+    /// A check on the target went stale after approval. Nothing changed; the
+    /// reasons were announced and `get_execution_blockers` lists them.
+    RevalidationFailed,
+    /// The migration aborted with this code and the proposal has failed
+    MigrationAborted(u32),
+    /// The migration deferred; `continue_migration` resumes it from this
+    /// timestamp on
+    MigrationDeferred(u64),
+}
+
+/// How far a proposal's live migration has got
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MigrationProgress {
+    pub proposal_id: u64,
+    pub status: MigrationStatus,
+    /// Position in the live key index of the next entry to migrate
+    pub next_index: u32,
+    /// A deferred migration may not resume before this timestamp
+    pub not_before: u64,
+    /// Code returned with `MigrationVerdict::Abort` (0 unless aborted)
+    pub abort_code: u32,
+}
+
+/// Where migratable entries live. Real executions migrate `Live`; a
+/// rehearsal copies sample entries into the proposal's `Shadow` namespace
+/// and migrates those instead.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum MigrationNamespace {
+    Live,
+    Shadow(u64),
+}
+
+/// An entry to rehearse and the value the migration should leave in it
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RehearsalSample {
+    pub key: Symbol,
+    pub expected: Bytes,
+}
+
+/// Result of running a proposal's migration against shadow copies
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RehearsalReport {
+    pub proposal_id: u64,
+    /// Samples that were copied and compared
+    pub checked: u32,
+    /// Samples whose migrated shadow value differs from the expected one
+    pub mismatched: Vec<Symbol>,
+    /// Samples with no live entry to copy
+    pub missing: Vec<Symbol>,
+    pub passed: bool,
+    pub timestamp: u64,
+}
+
+/// Something that currently prevents a proposal from being executed
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Blocker {
+    ProposalNotFound,
+    ProposalUnavailable,
+    IndicesIncomplete,
+    NotApproved,
+    /// The registered plan migrates from this version, not the current one
+    MigrationPlanMismatch(Version),
+    /// The proposal's track timelock runs until this timestamp
+    TimelockActive(u64),
+    /// The governance-wide execution delay runs until this timestamp
+    ExecutionDelayActive(u64),
+    /// The execution window closed at this timestamp; the proposal expires
+    /// instead of executing
+    ExecutionWindowClosed(u64),
+    /// The proposal is private and must be revealed before execution
+    DescriptionNotRevealed,
+    ProxyPaused,
+    /// The target version has been deprecated
+    TargetDeprecated,
+    /// The target version is not newer than the current version
+    VersionNotNewer,
+    /// The target version is incompatible with the current version and the
+    /// proposal's track does not permit a major bump
+    IncompatibleVersion,
+    /// The proposal's track does not permit this bump from the current version
+    BumpNotPermitted(BumpKind),
+    /// Another proposal already executed an upgrade to the same version
+    TargetAlreadyExecuted(u64),
+    /// The implementation deferred the migration until this timestamp
+    MigrationDeferred(u64),
+    /// The last executed upgrade can still be reverted until this timestamp
+    RevertWindowOpen(u64),
+    GovernancePaused,
+    /// This proposal's execution installed something other than what it approved
+    PostCheckUnacknowledged(u64),
+}
+
+/// Commitment behind a private proposal's placeholder description
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PrivateDescription {
+    /// sha256 of the full description's UTF-8 bytes
+    pub commitment: BytesN<32>,
+    /// From this timestamp the proposer may reveal even before execution
+    pub reveal_deadline: u64,
+    pub revealed: bool,
+}
+
+/// Bond every new proposal must post, in `token` (an amount of 0 disables it)
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BondConfig {
+    pub token: Address,
+    pub amount: i128,
+}
+
+/// What happened to a proposal's bond
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum BondStatus {
+    /// Held by the contract while the proposal is live
+    Held = 0,
+    /// Returned to the proposer when the proposal ended other than by rejection
+    Refunded = 1,
+    /// Kept because the proposal was rejected
+    Slashed = 2,
+}
+
+/// A bond posted by a proposal's proposer
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProposalBond {
+    pub token: Address,
+    pub amount: i128,
+    pub status: BondStatus,
+}
+
+/// Audit record of a slashed bond; `destination` is `None` when it was burned
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SlashRecord {
+    pub proposal_id: u64,
+    pub token: Address,
+    pub amount: i128,
+    pub destination: Option<Address>,
+    pub slashed_at: u64,
+}
+
+/// Which group of internal invariants `verify_invariants` walks
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum InvariantScope {
+    /// Every id in the pending index has a stored proposal in `Pending`
+    PendingIndex = 0,
+    /// Every proposal's tallies equal the sum of its vote receipts
+    Tallies = 1,
+    /// The proxy version equals one plus the successful implementation switches
+    Proxy = 2,
+    /// Id counters are ahead of every id they handed out
+    Counters = 3,
+    /// The storage footprint counters match a recount of what they track
+    Footprint = 4,
+}
+
+/// A broken invariant, carrying what is needed to locate it
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum InvariantViolation {
+    /// This id is in the pending index but no proposal is stored under it
+    IndexedProposalMissing(u64),
+    /// This id is in the pending index but its proposal has another status
+    IndexedStatusMismatch(u64),
+    /// This proposal's tallies differ from the sum of its receipts
+    TallyMismatch(u64),
+    /// The proxy config reports this version, which the history does not account for
+    ProxyVersionMismatch(u32),
+    /// This stored proposal id was not handed out by the sequential counter
+    ProposalIdBeyondCounter(u64),
+    /// This upgrade history id was not handed out by the proxy counter
+    UpgradeIdBeyondCounter(u64),
+    /// The footprint counters have drifted from this recount
+    FootprintDrift(StorageFootprint),
+}
+
+/// Result of one bounded `verify_invariants` call
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InvariantReport {
+    pub scope: InvariantScope,
+    /// Items examined by this call
+    pub checked: u32,
+    /// Cursor to pass to the next call; meaningless once `complete`
+    pub next_cursor: u32,
+    /// Whether the scope has been walked to the end
+    pub complete: bool,
+    pub violations: Vec<InvariantViolation>,
+}
+
+/// How far an amendment reaches into a proposal
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum AmendmentKind {
+    /// Only the description changed; existing votes stand
+    Metadata = 0,
+    /// The target address, version or migration plan changed; votes are reset
+    Substantive = 1,
+}
+
+/// Payload of the `votes_invalidated` event; listed voters may vote again
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VotesInvalidated {
+    pub proposal_id: u64,
+    pub count: u32,
+    pub voters: Vec<Address>,
+}
+
+/// A voter's choice on an upgrade proposal
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum VoteChoice {
+    For = 0,
+    Against = 1,
+    Abstain = 2,
+}
+
+/// Governance-wide voting rules; new proposals snapshot these as `ProposalParams`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GovernanceConfig {
+    /// Participating weight required, in basis points of the snapshot voting power (0 disables quorum)
+    pub quorum_bps: u32,
+    /// Share of the decisive (for + against) weight the for side must
+    /// exceed, in basis points; 10000 requires a unanimous for side
+    pub approval_threshold_bps: u32,
+    /// Whether abstentions count toward quorum participation
+    pub quorum_counts_abstain: bool,
+    /// Finalize as soon as the remaining uncast weight can no longer change the outcome
+    pub early_finalize: bool,
+    /// Seconds before an increase in voting power takes effect (0 applies it at once)
+    pub activation_delay: u64,
+    /// Allow `export_votes` while a proposal is still pending
+    pub live_vote_export: bool,
+    /// Where slashed proposal bonds are sent; they are burned when unset
+    pub slash_destination: Option<Address>,
+    /// Seconds after execution during which an upgrade can be instantly
+    /// reverted; further upgrades wait for it to pass (0 disables the window)
+    pub revert_window: u64,
+    /// Contract that receives archived proposals and pruned history through
+    /// `archive_record`; nothing is deleted here until it acknowledges
+    pub archive_sink: Option<Address>,
+}
+
+impl GovernanceConfig {
+    /// The per-proposal snapshot of these rules
+    pub fn to_params(&self) -> ProposalParams {
+        ProposalParams {
+            quorum_bps: self.quorum_bps,
+            quorum_counts_abstain: self.quorum_counts_abstain,
+            early_finalize: self.early_finalize,
+            track_id: DEFAULT_TRACK_ID,
+            threshold_bps: self.approval_threshold_bps,
+            voting_ends_at: 0,
+            timelock: 0,
+            separation_of_duties: false,
+        }
+    }
+}
+
+/// Version of the canonical serialization behind `get_governance_digest`
+pub const GOVERNANCE_DIGEST_VERSION: u32 = 3;
+
+/// Lowest governance settings a partner contract is willing to integrate with
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GovernanceMinimums {
+    pub quorum_bps: u32,
+    /// Share of the decisive weight the for side must exceed, in basis points
+    pub approval_threshold_bps: u32,
+    /// Seconds between approval and the switch to the new implementation
+    pub timelock: u64,
+    pub activation_delay: u64,
+}
+
+/// A governance setting below the required minimum, carrying its current value
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum GovernanceShortfall {
+    QuorumBps(u32),
+    ApprovalThresholdBps(u32),
+    Timelock(u64),
+    ActivationDelay(u64),
+}
+
+/// Track used by plain `propose_upgrade`: the governance-wide rules, any bump
+pub const DEFAULT_TRACK_ID: u32 = 0;
+/// Patch-only track with a short voting period and no timelock
+pub const FAST_TRACK_ID: u32 = 1;
+/// Track for minor bumps
+pub const STANDARD_TRACK_ID: u32 = 2;
+/// Track required for major bumps; approval needs a two-thirds supermajority
+pub const SUPERMAJORITY_TRACK_ID: u32 = 3;
+/// Approval threshold of a strict majority
+pub const SIMPLE_MAJORITY_BPS: u32 = 5_000;
+/// Basis point denominator used by quorum, thresholds and normalized weights
+pub const BPS_DENOMINATOR: u32 = 10_000;
+/// Voting period of proposals on the governance-wide rules until governance sets one
+pub const DEFAULT_VOTING_PERIOD: u64 = 604_800;
+
+/// Whether the for side carries the approval ratio: strictly more than
+/// `threshold_bps` of the decisive (for + against) weight, or all of it at
+/// 10000. Needs at least one for vote.
+pub fn approval_ratio_met(votes_for: u64, votes_against: u64, threshold_bps: u32) -> bool {
+    if votes_for == 0 {
+        return false;
+    }
+    let decisive = votes_for as u128 + votes_against as u128;
+    if threshold_bps >= BPS_DENOMINATOR {
+        return votes_against == 0;
+    }
+    votes_for as u128 * BPS_DENOMINATOR as u128 > decisive * threshold_bps as u128
+}
+
+/// `weight` in basis points of `total`, rounded down. Every normalized view
+/// goes through this, so views can never disagree. Zero when `total` is zero;
+/// above 10000 total, weights closer than `total / 10000` can round to the
+/// same value.
+pub fn weight_bps(weight: u64, total: u64) -> u32 {
+    if total == 0 {
+        return 0;
+    }
+    (weight as u128 * BPS_DENOMINATOR as u128 / total as u128).min(u32::MAX as u128) as u32
+}
+
+/// Tallies in basis points of the proposal's snapshot total power (`total_voting_power_snapshot`)
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct NormalizedTally {
+    pub for_bps: u32,
+    pub against_bps: u32,
+    pub abstain_bps: u32,
+    /// The snapshot total is zero, so the shares above are zero rather than measured
+    pub zero_total: bool,
+}
+
+impl NormalizedTally {
+    /// Normalize a proposal's current tallies
+    pub fn of(proposal: &UpgradeProposal) -> Self {
+        let total = proposal.total_voting_power_snapshot;
+        NormalizedTally {
+            for_bps: weight_bps(proposal.votes_for, total),
+            against_bps: weight_bps(proposal.votes_against, total),
+            abstain_bps: weight_bps(proposal.votes_abstain, total),
+            zero_total: total == 0,
+        }
+    }
+}
+
+/// A governance lane a proposer can choose at creation. The track's rules are
+/// snapshotted into the proposal's `ProposalParams`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GovernanceTrack {
+    pub id: u32,
+    pub name: Symbol,
+    /// Largest version bump a proposal on this track may carry
+    pub max_bump: BumpKind,
+    /// Share of the snapshot voting power that must vote for, in basis points (strictly exceeded)
+    pub threshold_bps: u32,
+    /// Participating weight required, in basis points of the snapshot voting power
+    pub quorum_bps: u32,
+    /// Seconds votes are accepted after creation (0 leaves voting open)
+    pub voting_period: u64,
+    /// Seconds between approval and execution
+    pub timelock: u64,
+    /// Bar the proposer from voting on or executing its own proposal; its
+    /// power is left out of the proposal's snapshot
+    pub separation_of_duties: bool,
+}
+
+/// Most default tags a proposal template may carry
+pub const MAX_TEMPLATE_TAGS: u32 = 8;
+
+/// Named defaults for a recurring kind of upgrade proposal, registered by
+/// governance
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProposalTemplate {
+    pub name: Symbol,
+    /// Track proposals are created on; 0 decides them under the
+    /// governance-wide rules
+    pub default_track: u32,
+    pub default_tags: Vec<Symbol>,
+    /// Seconds between approval and execution; `None` uses the
+    /// governance-wide execution delay
+    pub default_execution_delay: Option<u64>,
+    /// Roles of which the proposer must hold one, as for an action's role
+    /// set; empty leaves the template open to anyone
+    pub required_roles: Vec<Role>,
+}
+
+/// The template a proposal was created from and the defaults it received,
+/// copied at creation so later template changes leave the proposal alone
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AppliedTemplate {
+    pub template: Symbol,
+    pub tags: Vec<Symbol>,
+    pub execution_delay: Option<u64>,
+}
+
+/// One vote as recorded in a proposal's vote ledger
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VoteReceipt {
+    pub voter: Address,
+    pub choice: VoteChoice,
+    pub weight: u64,
+    /// `weight` in basis points of the proposal's snapshot total power
+    pub weight_bps: u32,
+    pub timestamp: u64,
+}
+
+/// Stored layout of `VoteReceipt` while its weight was `u32` (schemas 7 to
+/// 10); only read by the receipt widening migration
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct NarrowVoteReceipt {
+    pub voter: Address,
+    pub choice: VoteChoice,
+    pub weight: u32,
+    pub weight_bps: u32,
+    pub timestamp: u64,
+}
+
+impl NarrowVoteReceipt {
+    /// Convert to the current layout
+    pub fn widen(self) -> VoteReceipt {
+        VoteReceipt {
+            voter: self.voter,
+            choice: self.choice,
+            weight: self.weight as u64,
+            weight_bps: self.weight_bps,
+            timestamp: self.timestamp,
+        }
+    }
+}
+
+/// Payload of the per-vote event, published under `("upgrade", "vote_cast", proposal_id)`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VoteCast {
+    pub proposal_id: u64,
+    pub voter: Address,
+    pub choice: VoteChoice,
+    /// Weight applied, including power delegated to the voter
+    pub weight: u64,
+    /// Running tallies after this vote
+    pub votes_for: u64,
+    pub votes_against: u64,
+    pub votes_abstain: u64,
+}
+
+/// Payload of the event published under `("upgrade", "vote_changed", proposal_id)`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VoteChanged {
+    pub proposal_id: u64,
+    pub voter: Address,
+    pub from: VoteChoice,
+    pub to: VoteChoice,
+    /// Weight moved, as originally applied when the vote was cast
+    pub weight: u64,
+    /// Running tallies after the change
+    pub votes_for: u64,
+    pub votes_against: u64,
+    pub votes_abstain: u64,
+}
+
+/// Final tallies of a proposal and the number of receipts backing them
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TallyCheckpoint {
+    pub proposal_id: u64,
+    pub status: UpgradeStatus,
+    pub votes_for: u64,
+    pub votes_against: u64,
+    pub votes_abstain: u64,
+    pub normalized: NormalizedTally,
+    pub receipt_count: u32,
+}
+
+/// A voting power increase that only counts from `active_from` onwards
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VotingGrant {
+    pub power: u32,
+    pub active_from: u64,
+}
+
+/// A registered voter as seen now
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VoterEntry {
+    pub address: Address,
+    /// Weight that counts right now
+    pub power: u32,
+    /// Weight granted but not yet active (0 when nothing is pending)
+    pub pending_power: u32,
+    /// When `pending_power` becomes active (0 when nothing is pending)
+    pub active_from: u64,
+}
+
+/// A page of upgrade proposals and the cursor to continue from
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProposalPage {
+    pub proposals: Vec<UpgradeProposal>,
+    /// Cursor to pass back for the following page
+    pub next: Cursor,
+    /// Whether this page reaches the end of the list
+    pub complete: bool,
+}
+
+/// A page of the voter registry and the cursor to continue from
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VoterPage {
+    pub voters: Vec<VoterEntry>,
+    /// Cursor to pass back for the following page
+    pub next: Cursor,
+    /// Whether this page reaches the end of the list
+    pub complete: bool,
+}
+
+/// A page of a proposal's vote receipts and the cursor to continue from
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VoteReceiptPage {
+    pub receipts: Vec<VoteReceipt>,
+    /// Cursor to pass back for the following page
+    pub next: Cursor,
+    /// Whether this page reaches the end of the list
+    pub complete: bool,
+}
+
+/// Voting rules in force for a single proposal, fixed at creation
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProposalParams {
+    pub quorum_bps: u32,
+    pub quorum_counts_abstain: bool,
+    pub early_finalize: bool,
+    /// Governance track the proposal was created on
+    pub track_id: u32,
+    /// Share of the snapshot voting power that must vote for, in basis points (strictly exceeded)
+    pub threshold_bps: u32,
+    /// Votes are rejected after this timestamp (0 leaves voting open)
+    pub voting_ends_at: u64,
+    /// Seconds between approval and execution
+    pub timelock: u64,
+    /// The proposer may neither vote on nor execute the proposal
+    pub separation_of_duties: bool,
+}
+
+/// Payload of the finalization event
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProposalFinalized {
+    pub proposal_id: u64,
+    pub status: UpgradeStatus,
+    /// True when settled before voting closed because the outcome was already decided
+    pub early: bool,
+    pub normalized: NormalizedTally,
+}
+
+/// Tallies and rules appended to status and finalization events at
+/// `EventVerbosity::Verbose`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TallySnapshot {
+    pub votes_for: u64,
+    pub votes_against: u64,
+    pub votes_abstain: u64,
+    pub total_voters: u64,
+    pub total_voting_power_snapshot: u64,
+    pub normalized: NormalizedTally,
+    pub params: ProposalParams,
+}
+
+impl TallySnapshot {
+    pub fn of(proposal: &UpgradeProposal, params: ProposalParams) -> Self {
+        TallySnapshot {
+            votes_for: proposal.votes_for,
+            votes_against: proposal.votes_against,
+            votes_abstain: proposal.votes_abstain,
+            total_voters: proposal.total_voters,
+            total_voting_power_snapshot: proposal.total_voting_power_snapshot,
+            normalized: NormalizedTally::of(proposal),
+            params,
+        }
+    }
+}
+
+/// Per-proposer rate limit on upgrade proposal creation
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RateLimitConfig {
+    /// Maximum proposals a single proposer may create within the window (0 disables the limit)
+    pub max_proposals_per_window: u32,
+    /// Length of the sliding window in seconds
+    pub window_seconds: u64,
+}
+
+/// Projected outcome of a proposal given its current tallies
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OutcomeProjection {
+    pub votes_for: u64,
+    pub votes_against: u64,
+    pub votes_abstain: u64,
+    pub normalized: NormalizedTally,
+    /// Weight either side needs to decide the proposal
+    pub required_votes: u64,
+    /// Weight counted toward quorum under the proposal's rules
+    pub participating: u64,
+    /// Participating weight needed for quorum
+    pub quorum_required: u64,
+    pub quorum_met: bool,
+    /// Status the proposal holds or would move to with the current tallies
+    pub projected_status: UpgradeStatus,
+}
+
+/// Vote breakdown of a proposal. Abstentions count toward quorum when the
+/// proposal's rules say so, and never toward the approval ratio.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProposalResult {
+    pub proposal_id: u64,
+    pub status: UpgradeStatus,
+    pub votes_for: u64,
+    pub votes_against: u64,
+    pub votes_abstain: u64,
+    /// Weight counted toward quorum
+    pub participating: u64,
+    pub quorum_met: bool,
+    /// For votes in basis points of the for and against votes together
+    pub approval_bps: u32,
+}
+
+/// Everything needed to render a single proposal page in one read.
+/// Migration plan and viewer fields are empty when not applicable.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct ProposalDetail {
+    pub proposal: UpgradeProposal,
+    pub track_id: u32,
+    /// The description is a placeholder until the proposer reveals it
+    pub is_private: bool,
+    pub projection: OutcomeProjection,
+    pub migration_selector: Option<soroban_sdk::Symbol>,
+    pub migration_estimated_gas: Option<u64>,
+    pub viewer: Option<Address>,
+    pub viewer_voting_power: u32,
+    pub viewer_is_proposer: bool,
+    pub viewer_can_vote: bool,
+}
+
+/// Governance-controlled feature switch gated on a minimum contract version
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FeatureFlag {
+    pub name: soroban_sdk::Symbol,
+    pub enabled: bool,
+    /// Minimum version the contract must be on; 0.0.0 means ungated
+    pub min_version: Version,
+    pub updated_at: u64,
+}
+
+/// Outcome of notifying one subscriber about an executed upgrade
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct NotificationResult {
+    pub subscriber: Address,
+    pub success: bool,
+}
+
+/// A failed subscriber notification awaiting retry
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PendingNotification {
+    pub subscriber: Address,
+    pub proposal_id: u64,
+    pub from_version: Version,
+    pub to_version: Version,
+    /// Delivery attempts made so far, including the initial one
+    pub attempts: u32,
+}
+
+/// A side effect that runs after the version switch and may fail on its own
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum CompensationKind {
+    /// An `on_upgrade` notification the retry queue gave up on
+    Notification = 0,
+    /// A bond refund or slash whose token transfer failed
+    BondSettlement = 1,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum CompensationStatus {
+    /// Retried by `run_compensations` once `next_attempt_at` has passed
+    Pending = 0,
+    /// Out of attempts; stays until governance dismisses it
+    GivenUp = 1,
+}
+
+/// A failed post-execution side effect awaiting compensation
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PendingCompensation {
+    pub id: u64,
+    pub kind: CompensationKind,
+    /// The subscriber to notify, or the proposer whose bond is unsettled
+    pub subject: Address,
+    pub proposal_id: u64,
+    /// Retries made by `run_compensations` so far
+    pub attempts: u32,
+    pub next_attempt_at: u64,
+    /// Contract error or `CallOutcome` code of the last failure (0 when it
+    /// has none)
+    pub last_error: u32,
+    pub status: CompensationStatus,
+}
+
+/// What this contract supports, for tooling probing an unknown address.
+/// Fields are only ever appended so older integrators keep working.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Capabilities {
+    pub upgrade_initialized: bool,
+    pub proxy_initialized: bool,
+    pub timelock_enabled: bool,
+    pub quorum_enabled: bool,
+    pub delegation_enabled: bool,
+    pub emergency_enabled: bool,
+    pub event_schema_version: u32,
+    pub error_code_version: u32,
+}
+
+/// What happened to state migration during an upgrade execution
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum MigrationOutcome {
+    NotRequired = 0,
+    Completed = 1,
+}
+
+/// Read-back of the installed implementation right after an execution
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum PostCheck {
+    /// The execution did not switch any code, so there was nothing to read back
+    NotApplicable = 0,
+    /// The installed implementation is the one the proposal approved
+    Matched = 1,
+    /// Something else is installed; see `PostCheckFailure`
+    Mismatched = 2,
+}
+
+/// An execution whose installed implementation did not match the approved
+/// one. Further upgrades are blocked until governance acknowledges it.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PostCheckFailure {
+    pub proposal_id: u64,
+    pub expected: Address,
+    pub installed: Address,
+    pub detected_at: u64,
+}
+
+/// Immutable record of an executed upgrade, kept independently of the proposal
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ExecutionReceipt {
+    pub proposal_id: u64,
+    pub executor: Address,
+    pub from_version: Version,
+    pub to_version: Version,
+    pub implementation: Address,
+    /// Wasm hash installed by the upgrade; all zeroes when it targeted an address only
+    pub wasm_hash: BytesN<32>,
+    pub migration_outcome: MigrationOutcome,
+    pub timestamp: u64,
+    /// Proxy implementation the upgrade replaced; `None` without a proxy
+    pub previous_implementation: Option<Address>,
+    /// Whether the implementation read back after the switch was the approved one
+    pub post_check: PostCheck,
+}
+
+/// Who executed an upgrade and when. Proposal executions are read from their
+/// `ExecutionReceipt`; emergency upgrades have no proposal and are kept in
+/// their own list.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ExecutionRecord {
+    pub executed_by: Address,
+    pub executed_at: u64,
+    pub to_version: Version,
+    pub implementation: Address,
+    /// Set for upgrades applied through the emergency path
+    pub emergency: bool,
+}
+
+impl ExecutionRecord {
+    /// The record of a proposal execution
+    pub fn of(receipt: &ExecutionReceipt) -> Self {
+        ExecutionRecord {
+            executed_by: receipt.executor.clone(),
+            executed_at: receipt.timestamp,
+            to_version: receipt.to_version.clone(),
+            implementation: receipt.implementation.clone(),
+            emergency: false,
+        }
+    }
+}
+
+/// The most recent emergency upgrade, kept for audits
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EmergencyUpgradeRecord {
+    pub caller: Address,
+    pub version: Version,
+    pub implementation: Address,
+    pub timestamp: u64,
+}
+
+/// The just-executed upgrade that can still be instantly reverted
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RevertWindow {
+    pub proposal_id: u64,
+    pub closes_at: u64,
+}
+
+/// Compact summary of an archived proposal
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProposalSummary {
+    pub id: u64,
+    pub proposer: Address,
+    pub new_contract_address: Address,
+    pub version: Version,
+    pub status: UpgradeStatus,
+    pub votes_for: u64,
+    pub votes_against: u64,
+    pub votes_abstain: u64,
+    pub timestamp: u64,
+}
+
+impl ProposalSummary {
+    pub fn of(proposal: &UpgradeProposal) -> Self {
+        ProposalSummary {
+            id: proposal.id,
+            proposer: proposal.proposer.clone(),
+            new_contract_address: proposal.new_contract_address.clone(),
+            version: proposal.version.clone(),
+            status: proposal.status,
+            votes_for: proposal.votes_for,
+            votes_against: proposal.votes_against,
+            votes_abstain: proposal.votes_abstain,
+            timestamp: proposal.timestamp,
+        }
+    }
+}
+
+/// What the archive sink receives through `archive_record`; it returns true
+/// to acknowledge that the record is safely stored
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ArchiveRecord {
+    Proposal(ProposalSummary),
+    History(HistoryRecord),
+}
+
+/// Who paused upgrade governance and when
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GovernancePause {
+    pub paused_by: Address,
+    pub paused_at: u64,
+}
+
+/// Approximate entry counts of governance state, kept in step by the storage
+/// helpers that write each category
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct StorageFootprint {
+    pub proposals_live: u32,
+    pub proposals_archived: u32,
+    /// Execution receipts
+    pub receipts: u32,
+    /// Migration plans
+    pub plans: u32,
+    /// History records of every kind not yet pruned
+    pub history_entries: u32,
+    /// Emergency action log entries
+    pub log_entries: u32,
+    /// Counter updates so far
+    pub writes: u64,
+}
+
+/// One counter of `StorageFootprint`
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum FootprintCategory {
+    ProposalsLive,
+    ProposalsArchived,
+    Receipts,
+    Plans,
+    HistoryEntries,
+    LogEntries,
+}
+
+/// Counter updates between two footprint events
+pub const FOOTPRINT_EVENT_INTERVAL: u64 = 32;
+
+/// Pause and migration state an operator needs to get the contract unstuck
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct HealthReport {
+    pub governance_paused: bool,
+    /// Proxy upgrades are blocked; continuations are not
+    pub proxy_paused: bool,
+    /// Proposals whose live migration is half-applied
+    pub migrations_in_progress: Vec<u64>,
+    /// In-progress migrations a forced pause left behind
+    pub stranded_migrations: Vec<u64>,
+    /// Proposal whose post-execution check failed and is not yet acknowledged
+    pub unacknowledged_post_check: Option<u64>,
+    /// Set while any migration is stranded or a post-check failure is unacknowledged
+    pub needs_attention: bool,
+}
+
+/// Bounded upkeep routine a keeper can run through `run_maintenance`
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum MaintenanceTask {
+    /// Finalize pending proposals whose voting deadline has passed and expire
+    /// approved ones whose execution window has closed
+    SweepExpired = 0,
+    RetryNotifications = 1,
+    RunCompensations = 2,
+    RetryArchival = 3,
+    /// Extend the TTL of the core upgrade storage entries
+    BumpTtl = 4,
+}
+
+/// Keeper incentives and rate limits. The bounty is paid per completed
+/// work unit from the contract's balance of `token`; no token or a zero
+/// bounty means keepers are not paid.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct KeeperConfig {
+    pub token: Option<Address>,
+    pub bounty_per_unit: i128,
+    /// Seconds a task must rest after a run before it can run again
+    pub cooldown: u64,
+}
+
+/// Work a keeper has done over its lifetime
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct KeeperStats {
+    pub runs: u32,
+    pub work_units: u32,
+    pub paid: i128,
+    pub last_run_at: u64,
+}
+
+/// Outcome of one `run_maintenance` call
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MaintenanceReport {
+    /// Work units completed by each task run, in request order
+    pub completed: Vec<(MaintenanceTask, u32)>,
+    pub work_units: u32,
+    pub paid: i128,
+}
+
+/// Progress of a paged genesis voter list. Proposals stay locked while a
+/// started list is neither complete nor sealed.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GenesisProgress {
+    pub total_pages: u32,
+    /// Pages applied so far; the next accepted page has this index
+    pub pages_applied: u32,
+    /// Voters written by the applied pages
+    pub voters: u32,
+    pub sealed: bool,
+}
+
+/// Most voters one genesis page may carry
+pub const MAX_GENESIS_PAGE_LEN: u32 = 50;
+
+/// Most entries one `set_voting_power_batch` call may carry
+pub const MAX_POWER_BATCH_LEN: u32 = 100;
+
+/// Most delegators whose chains may end at one voter; bounds the walk each vote makes
+pub const MAX_DELEGATORS: u32 = 50;
+
+/// Most entries one version history page may return
+pub const MAX_VERSION_HISTORY_PAGE: u32 = 50;
+
+/// One change of the current version, kept in an append-only log
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VersionChange {
+    pub version: Version,
+    pub changed_at: u64,
+    pub changed_by: Address,
+    /// The proposal whose execution made the change; none for emergency
+    /// upgrades, rollbacks and reverts
+    pub proposal_id: Option<u64>,
+}
+
+/// Lifecycle of a governance recovery proposal
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum RecoveryStatus {
+    Open = 0,
+    Executed = 1,
+    Vetoed = 2,
+}
+
+/// Voter-driven proposal to replace a lost governance address
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RecoveryProposal {
+    pub id: u64,
+    pub initiator: Address,
+    pub new_governance: Address,
+    /// Total registered voting power when the recovery was opened
+    pub total_voting_power: u64,
+    pub votes_for: u64,
+    pub created_at: u64,
+    /// Earliest time the recovery can be executed
+    pub executable_at: u64,
+    pub status: RecoveryStatus,
+}
+
+/// A privileged operation in the upgrade module
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum Action {
+    SetProposalIdMode = 0,
+    SetRateLimit = 1,
+    SetGovernanceConfig = 2,
+    SetNotificationRetries = 3,
+    ManageSubscribers = 4,
+    SetFeature = 5,
+    ExecuteUpgrade = 6,
+    EmergencyUpgrade = 7,
+    ToggleEmergency = 8,
+    RegisterMigrationPlan = 9,
+    Rollback = 10,
+    RepairIndices = 11,
+    /// Protected: always governance-only
+    VetoRecovery = 12,
+    /// Protected: always governance-only
+    SetAuthMatrix = 13,
+    /// Protected: always governance-only
+    ManageRoles = 14,
+    GrantVotingPower = 15,
+    SetVotingPowerRoot = 16,
+    ArchiveProposal = 17,
+    SetMaxSilence = 18,
+    Heartbeat = 19,
+    SetEmergencyMaxBump = 20,
+    DeprecateVersion = 21,
+    DismissCompensation = 22,
+    /// Protected: always governance-only
+    SetDualControlWindow = 23,
+    RehearseMigration = 24,
+    MigrateStorage = 25,
+    /// Guardian or governance by default
+    InstantRevert = 26,
+    /// Guardian or governance by default
+    ConfirmUpgrade = 27,
+    /// Guardian or governance by default
+    PauseGovernance = 28,
+    ResumeGovernance = 29,
+    /// The proposer may always cancel their own proposal
+    CancelProposal = 30,
+    AcknowledgePostCheck = 31,
+    /// Guardian only by default
+    VetoProposal = 32,
+    /// Protected: always governance-only
+    TransferGovernance = 33,
+}
+
+impl Action {
+    /// Actions whose role set is fixed to governance and cannot be changed
+    pub fn is_protected(&self) -> bool {
+        matches!(
+            self,
+            Action::VetoRecovery
+                | Action::SetAuthMatrix
+                | Action::ManageRoles
+                | Action::SetDualControlWindow
+                | Action::TransferGovernance
+        )
+    }
+}
+
+/// A change to one of the two keys that check each other. Both kinds may not
+/// change within the dual-control window of each other.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum ControlChange {
+    Governance = 0,
+    Guardian = 1,
+}
+
+impl ControlChange {
+    /// The change that must not have happened recently
+    pub fn counterpart(&self) -> ControlChange {
+        match self {
+            ControlChange::Governance => ControlChange::Guardian,
+            ControlChange::Guardian => ControlChange::Governance,
+        }
+    }
+}
+
+/// A role that can be granted permission for an `Action`
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum Role {
+    /// Held only by the governance address
+    Governance = 0,
+    Guardian = 1,
+    Executor = 2,
+    Proposer = 3,
+    Pauser = 4,
+}
+
+/// Error codes for the upgrade module
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum UpgradeError {
+    /// Caller is not the governance address
+    NotGovernance = 300,
+    /// Proposal does not exist
+    ProposalNotFound = 301,
+    /// Proposal id was issued but its entry is missing
+    ProposalDataUnavailable = 302,
+    /// Proposal exists but its index entries need repair
+    ProposalIndicesIncomplete = 303,
+    /// A proposal with the derived id already exists
+    ProposalIdCollision = 304,
+    /// Proposal is not in pending status
+    ProposalNotPending = 305,
+    /// Proposal is not approved for execution
+    ProposalNotApproved = 306,
+    /// Proposal is not awaiting the proxy
+    NotAwaitingProxy = 307,
+    /// Proxy upgrade has not executed yet
+    ProxyNotExecuted = 308,
+    /// Proxy schedule link missing
+    ProxyLinkMissing = 309,
+    /// Proposal rate limit exceeded
+    RateLimited = 310,
+    /// Rate limit configuration is invalid
+    InvalidRateLimit = 311,
+    /// Quorum cannot exceed 10000 basis points
+    InvalidQuorum = 312,
+    /// Max attempts must be positive
+    InvalidMaxAttempts = 313,
+    /// Emergency upgrades are not enabled
+    EmergencyDisabled = 314,
+    /// Rollback target is not an earlier version in the same major series
+    InvalidRollbackTarget = 315,
+    /// Subscriber already registered
+    SubscriberExists = 316,
+    /// Too many upgrade subscribers
+    TooManySubscribers = 317,
+    /// Subscriber not registered
+    SubscriberNotFound = 318,
+    /// Caller or registry has no voting power
+    NoVotingPower = 319,
+    /// Voter has already voted
+    AlreadyVoted = 320,
+    /// Recovery proposal does not exist
+    RecoveryNotFound = 321,
+    /// Recovery proposal is not open
+    RecoveryNotOpen = 322,
+    /// Recovery timelock has not elapsed
+    RecoveryTimelockActive = 323,
+    /// Recovery supermajority not reached
+    SupermajorityNotReached = 324,
+    /// Scheduled upgrade does not exist
+    ScheduleNotFound = 325,
+    /// Caller holds no role permitted for the action
+    NotAuthorized = 326,
+    /// The action's role set or the role is protected
+    ProtectedAction = 327,
+    /// A role set must contain at least one role
+    EmptyRoleSet = 328,
+    /// Emergency upgrade changes the version by more than allowed
+    EmergencyBumpTooLarge = 329,
+    /// The governance address and the guardian cannot both change within the dual-control window
+    DualControlCooldown = 330,
+    /// No revert window is open for the proposal
+    RevertWindowClosed = 331,
+    /// The upgrade migrated state but no reverse migration is registered
+    ReverseMigrationMissing = 332,
+    /// The reverse migration did not complete
+    ReverseMigrationFailed = 333,
+    /// Upgrade governance is paused
+    GovernancePaused = 334,
+    /// Upgrade governance is not paused
+    GovernanceNotPaused = 335,
+    /// Pausing would strand an in-progress migration and `force` was not set
+    MigrationWouldStrand = 336,
+    /// A maintenance task ran too recently
+    TaskCoolingDown = 337,
+    /// Keeper bounties cannot be negative
+    InvalidKeeperBounty = 338,
+    /// Stored upgrade data is on a different schema than the code, or a
+    /// schema migration would skip a step
+    SchemaMismatch = 339,
+    /// The proposer cannot vote on a proposal under separation of duties
+    ProposerCannotVote = 340,
+    /// The proposer cannot execute a proposal under separation of duties
+    ProposerCannotExecute = 341,
+    /// The genesis voter list is sealed, or governance is already running
+    GenesisSealed = 342,
+    /// A genesis page arrived ahead of the pages before it
+    GenesisPageOutOfOrder = 343,
+    /// A genesis page carries more than `MAX_GENESIS_PAGE_LEN` voters
+    GenesisPageTooLarge = 344,
+    /// A page index or page count that does not fit the list being loaded
+    InvalidGenesisPages = 345,
+}
+
+impl UpgradeError {
+    /// Every variant, in code order
+    pub const ALL: [UpgradeError; 46] = [
+        UpgradeError::NotGovernance,
+        UpgradeError::ProposalNotFound,
+        UpgradeError::ProposalDataUnavailable,
+        UpgradeError::ProposalIndicesIncomplete,
+        UpgradeError::ProposalIdCollision,
+        UpgradeError::ProposalNotPending,
+        UpgradeError::ProposalNotApproved,
+        UpgradeError::NotAwaitingProxy,
+        UpgradeError::ProxyNotExecuted,
+        UpgradeError::ProxyLinkMissing,
+        UpgradeError::RateLimited,
+        UpgradeError::InvalidRateLimit,
+        UpgradeError::InvalidQuorum,
+        UpgradeError::InvalidMaxAttempts,
+        UpgradeError::EmergencyDisabled,
+        UpgradeError::InvalidRollbackTarget,
+        UpgradeError::SubscriberExists,
+        UpgradeError::TooManySubscribers,
+        UpgradeError::SubscriberNotFound,
+        UpgradeError::NoVotingPower,
+        UpgradeError::AlreadyVoted,
+        UpgradeError::RecoveryNotFound,
+        UpgradeError::RecoveryNotOpen,
+        UpgradeError::RecoveryTimelockActive,
+        UpgradeError::SupermajorityNotReached,
+        UpgradeError::ScheduleNotFound,
+        UpgradeError::NotAuthorized,
+        UpgradeError::ProtectedAction,
+        UpgradeError::EmptyRoleSet,
+        UpgradeError::EmergencyBumpTooLarge,
+        UpgradeError::DualControlCooldown,
+        UpgradeError::RevertWindowClosed,
+        UpgradeError::ReverseMigrationMissing,
+        UpgradeError::ReverseMigrationFailed,
+        UpgradeError::GovernancePaused,
+        UpgradeError::GovernanceNotPaused,
+        UpgradeError::MigrationWouldStrand,
+        UpgradeError::TaskCoolingDown,
+        UpgradeError::InvalidKeeperBounty,
+        UpgradeError::SchemaMismatch,
+        UpgradeError::ProposerCannotVote,
+        UpgradeError::ProposerCannotExecute,
+        UpgradeError::GenesisSealed,
+        UpgradeError::GenesisPageOutOfOrder,
+        UpgradeError::GenesisPageTooLarge,
+        UpgradeError::InvalidGenesisPages,
+    ];
+
+    /// Stable name used in the on-chain error catalog
+    pub fn name(&self) -> &'static str {
+        match self {
+            UpgradeError::NotGovernance => "NotGovernance",
+            UpgradeError::ProposalNotFound => "ProposalNotFound",
+            UpgradeError::ProposalDataUnavailable => "ProposalDataUnavailable",
+            UpgradeError::ProposalIndicesIncomplete => "ProposalIndicesIncomplete",
+            UpgradeError::ProposalIdCollision => "ProposalIdCollision",
+            UpgradeError::ProposalNotPending => "ProposalNotPending",
+            UpgradeError::ProposalNotApproved => "ProposalNotApproved",
+            UpgradeError::NotAwaitingProxy => "NotAwaitingProxy",
+            UpgradeError::ProxyNotExecuted => "ProxyNotExecuted",
+            UpgradeError::ProxyLinkMissing => "ProxyLinkMissing",
+            UpgradeError::RateLimited => "RateLimited",
+            UpgradeError::InvalidRateLimit => "InvalidRateLimit",
+            UpgradeError::InvalidQuorum => "InvalidQuorum",
+            UpgradeError::InvalidMaxAttempts => "InvalidMaxAttempts",
+            UpgradeError::EmergencyDisabled => "EmergencyDisabled",
+            UpgradeError::InvalidRollbackTarget => "InvalidRollbackTarget",
+            UpgradeError::SubscriberExists => "SubscriberExists",
+            UpgradeError::TooManySubscribers => "TooManySubscribers",
+            UpgradeError::SubscriberNotFound => "SubscriberNotFound",
+            UpgradeError::NoVotingPower => "NoVotingPower",
+            UpgradeError::AlreadyVoted => "AlreadyVoted",
+            UpgradeError::RecoveryNotFound => "RecoveryNotFound",
+            UpgradeError::RecoveryNotOpen => "RecoveryNotOpen",
+            UpgradeError::RecoveryTimelockActive => "RecoveryTimelockActive",
+            UpgradeError::SupermajorityNotReached => "SupermajorityNotReached",
+            UpgradeError::ScheduleNotFound => "ScheduleNotFound",
+            UpgradeError::NotAuthorized => "NotAuthorized",
+            UpgradeError::ProtectedAction => "ProtectedAction",
+            UpgradeError::EmptyRoleSet => "EmptyRoleSet",
+            UpgradeError::EmergencyBumpTooLarge => "EmergencyBumpTooLarge",
+            UpgradeError::DualControlCooldown => "DualControlCooldown",
+            UpgradeError::RevertWindowClosed => "RevertWindowClosed",
+            UpgradeError::ReverseMigrationMissing => "ReverseMigrationMissing",
+            UpgradeError::ReverseMigrationFailed => "ReverseMigrationFailed",
+            UpgradeError::GovernancePaused => "GovernancePaused",
+            UpgradeError::GovernanceNotPaused => "GovernanceNotPaused",
+            UpgradeError::MigrationWouldStrand => "MigrationWouldStrand",
+            UpgradeError::TaskCoolingDown => "TaskCoolingDown",
+            UpgradeError::InvalidKeeperBounty => "InvalidKeeperBounty",
+            UpgradeError::SchemaMismatch => "SchemaMismatch",
+            UpgradeError::ProposerCannotVote => "ProposerCannotVote",
+            UpgradeError::ProposerCannotExecute => "ProposerCannotExecute",
+            UpgradeError::GenesisSealed => "GenesisSealed",
+            UpgradeError::GenesisPageOutOfOrder => "GenesisPageOutOfOrder",
+            UpgradeError::GenesisPageTooLarge => "GenesisPageTooLarge",
+            UpgradeError::InvalidGenesisPages => "InvalidGenesisPages",
+        }
+    }
+}
+
+/// Error codes for the proposal lifecycle, continuing the numbering of
+/// `UpgradeError`, which has reached the 50 cases a contract error enum may
+/// hold
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum ProposalError {
+    /// Governance track does not exist
+    TrackNotFound = 346,
+    /// Proposal template does not exist
+    TemplateNotFound = 347,
+    /// Proposal template carries more than `MAX_TEMPLATE_TAGS` tags
+    TooManyTemplateTags = 348,
+    /// Approval threshold is outside the allowed range
+    InvalidApprovalThreshold = 349,
+    /// Track 0 is reserved for the default rules
+    InvalidTrack = 350,
+    /// Contract is in safe mode
+    SafeMode = 351,
+    /// Genesis voter list is still loading
+    GenesisPending = 352,
+    /// Target version is deprecated
+    TargetDeprecated = 353,
+    /// Track does not permit this version bump
+    BumpNotPermitted = 354,
+    /// Target version was already executed
+    TargetAlreadyExecuted = 355,
+    /// Proposal target is invalid
+    InvalidTarget = 356,
+    /// Proposal target failed revalidation before execution
+    TargetRevalidationFailed = 357,
+    /// Illegal proposal status transition
+    IllegalTransition = 358,
+    /// Voting period has ended
+    VotingClosed = 359,
+    /// Voting period has not ended
+    VotingPeriodActive = 360,
+    /// Caller is not the proposer
+    NotProposer = 361,
+    /// Proposal is not private
+    ProposalNotPrivate = 362,
+    /// Description or placeholder is too long
+    DescriptionTooLong = 363,
+    /// Description already revealed
+    DescriptionAlreadyRevealed = 364,
+    /// Description cannot be revealed yet
+    RevealTooEarly = 365,
+    /// Description does not match the commitment
+    DescriptionMismatch = 366,
+    /// Private description must be revealed before execution
+    DescriptionNotRevealed = 367,
+    /// Proposal is not finalized
+    ProposalNotFinalized = 368,
+    /// History can only be pruned to an archive sink
+    NoArchiveSink = 369,
+    /// Execution delay has not elapsed
+    ExecutionDelayActive = 370,
+    /// Execution window has closed
+    ExecutionWindowClosed = 371,
+    /// Execution window must be positive
+    InvalidExecutionWindow = 372,
+    /// Track timelock has not elapsed
+    TimelockActive = 373,
+    /// Revert window of the last upgrade is still open
+    RevertWindowOpen = 374,
+    /// Migration plan does not start from the current version
+    MigrationPlanMismatch = 375,
+    /// No migration plan registered
+    MigrationPlanNotFound = 376,
+    /// Migration has not started
+    MigrationNotStarted = 377,
+    /// Migration is not in progress
+    MigrationNotInProgress = 378,
+    /// Migration is deferred
+    MigrationDeferred = 379,
+    /// Migration aborted by the implementation
+    MigrationAborted = 380,
+    /// Cross-contract call trapped or returned a contract error
+    CallTrapped = 381,
+    /// Proxy upgrades are paused
+    ProxyPaused = 382,
+    /// No voting power root set
+    VotingPowerRootMissing = 383,
+    /// Invalid merkle proof
+    InvalidMerkleProof = 384,
+    /// Voting power already claimed
+    VotingPowerClaimed = 385,
+    /// Bond amount cannot be negative
+    InvalidBondAmount = 386,
+    /// Compensation does not exist
+    CompensationNotFound = 387,
+    /// Compensation is still being retried
+    CompensationPending = 388,
+    /// Emergency override does not authorize this upgrade
+    InvalidEmergencyOverride = 389,
+    /// Cross-contract call returned a value of the wrong type
+    MalformedCallReturn = 390,
+    /// Callee reported a failed `CallOutcome`
+    CallRejected = 391,
+    /// Entrypoint was re-entered while an execute path was running
+    ReentrantCall = 392,
+    /// Cursor was issued before entries were pruned; restart the walk
+    CursorInvalidated = 393,
+    /// A post-execution check failed and governance has not acknowledged it
+    PostCheckUnacknowledged = 394,
+    /// There is no post-execution check failure to acknowledge
+    NoPostCheckFailure = 395,
+}
+
+impl ProposalError {
+    /// Every variant, in code order
+    pub const ALL: [ProposalError; 50] = [
+        ProposalError::TrackNotFound,
+        ProposalError::TemplateNotFound,
+        ProposalError::TooManyTemplateTags,
+        ProposalError::InvalidApprovalThreshold,
+        ProposalError::InvalidTrack,
+        ProposalError::SafeMode,
+        ProposalError::GenesisPending,
+        ProposalError::TargetDeprecated,
+        ProposalError::BumpNotPermitted,
+        ProposalError::TargetAlreadyExecuted,
+        ProposalError::InvalidTarget,
+        ProposalError::TargetRevalidationFailed,
+        ProposalError::IllegalTransition,
+        ProposalError::VotingClosed,
+        ProposalError::VotingPeriodActive,
+        ProposalError::NotProposer,
+        ProposalError::ProposalNotPrivate,
+        ProposalError::DescriptionTooLong,
+        ProposalError::DescriptionAlreadyRevealed,
+        ProposalError::RevealTooEarly,
+        ProposalError::DescriptionMismatch,
+        ProposalError::DescriptionNotRevealed,
+        ProposalError::ProposalNotFinalized,
+        ProposalError::NoArchiveSink,
+        ProposalError::ExecutionDelayActive,
+        ProposalError::ExecutionWindowClosed,
+        ProposalError::InvalidExecutionWindow,
+        ProposalError::TimelockActive,
+        ProposalError::RevertWindowOpen,
+        ProposalError::MigrationPlanMismatch,
+        ProposalError::MigrationPlanNotFound,
+        ProposalError::MigrationNotStarted,
+        ProposalError::MigrationNotInProgress,
+        ProposalError::MigrationDeferred,
+        ProposalError::MigrationAborted,
+        ProposalError::CallTrapped,
+        ProposalError::ProxyPaused,
+        ProposalError::VotingPowerRootMissing,
+        ProposalError::InvalidMerkleProof,
+        ProposalError::VotingPowerClaimed,
+        ProposalError::InvalidBondAmount,
+        ProposalError::CompensationNotFound,
+        ProposalError::CompensationPending,
+        ProposalError::InvalidEmergencyOverride,
+        ProposalError::MalformedCallReturn,
+        ProposalError::CallRejected,
+        ProposalError::ReentrantCall,
+        ProposalError::CursorInvalidated,
+        ProposalError::PostCheckUnacknowledged,
+        ProposalError::NoPostCheckFailure,
+    ];
+
+    /// Stable name used in the on-chain error catalog
+    pub fn name(&self) -> &'static str {
+        match self {
+            ProposalError::TrackNotFound => "TrackNotFound",
+            ProposalError::TemplateNotFound => "TemplateNotFound",
+            ProposalError::TooManyTemplateTags => "TooManyTemplateTags",
+            ProposalError::InvalidApprovalThreshold => "InvalidApprovalThreshold",
+            ProposalError::InvalidTrack => "InvalidTrack",
+            ProposalError::SafeMode => "SafeMode",
+            ProposalError::GenesisPending => "GenesisPending",
+            ProposalError::TargetDeprecated => "TargetDeprecated",
+            ProposalError::BumpNotPermitted => "BumpNotPermitted",
+            ProposalError::TargetAlreadyExecuted => "TargetAlreadyExecuted",
+            ProposalError::InvalidTarget => "InvalidTarget",
+            ProposalError::TargetRevalidationFailed => "TargetRevalidationFailed",
+            ProposalError::IllegalTransition => "IllegalTransition",
+            ProposalError::VotingClosed => "VotingClosed",
+            ProposalError::VotingPeriodActive => "VotingPeriodActive",
+            ProposalError::NotProposer => "NotProposer",
+            ProposalError::ProposalNotPrivate => "ProposalNotPrivate",
+            ProposalError::DescriptionTooLong => "DescriptionTooLong",
+            ProposalError::DescriptionAlreadyRevealed => "DescriptionAlreadyRevealed",
+            ProposalError::RevealTooEarly => "RevealTooEarly",
+            ProposalError::DescriptionMismatch => "DescriptionMismatch",
+            ProposalError::DescriptionNotRevealed => "DescriptionNotRevealed",
+            ProposalError::ProposalNotFinalized => "ProposalNotFinalized",
+            ProposalError::NoArchiveSink => "NoArchiveSink",
+            ProposalError::ExecutionDelayActive => "ExecutionDelayActive",
+            ProposalError::ExecutionWindowClosed => "ExecutionWindowClosed",
+            ProposalError::InvalidExecutionWindow => "InvalidExecutionWindow",
+            ProposalError::TimelockActive => "TimelockActive",
+            ProposalError::RevertWindowOpen => "RevertWindowOpen",
+            ProposalError::MigrationPlanMismatch => "MigrationPlanMismatch",
+            ProposalError::MigrationPlanNotFound => "MigrationPlanNotFound",
+            ProposalError::MigrationNotStarted => "MigrationNotStarted",
+            ProposalError::MigrationNotInProgress => "MigrationNotInProgress",
+            ProposalError::MigrationDeferred => "MigrationDeferred",
+            ProposalError::MigrationAborted => "MigrationAborted",
+            ProposalError::CallTrapped => "CallTrapped",
+            ProposalError::ProxyPaused => "ProxyPaused",
+            ProposalError::VotingPowerRootMissing => "VotingPowerRootMissing",
+            ProposalError::InvalidMerkleProof => "InvalidMerkleProof",
+            ProposalError::VotingPowerClaimed => "VotingPowerClaimed",
+            ProposalError::InvalidBondAmount => "InvalidBondAmount",
+            ProposalError::CompensationNotFound => "CompensationNotFound",
+            ProposalError::CompensationPending => "CompensationPending",
+            ProposalError::InvalidEmergencyOverride => "InvalidEmergencyOverride",
+            ProposalError::MalformedCallReturn => "MalformedCallReturn",
+            ProposalError::CallRejected => "CallRejected",
+            ProposalError::ReentrantCall => "ReentrantCall",
+            ProposalError::CursorInvalidated => "CursorInvalidated",
+            ProposalError::PostCheckUnacknowledged => "PostCheckUnacknowledged",
+            ProposalError::NoPostCheckFailure => "NoPostCheckFailure",
+        }
+    }
+}
+
+/// Voting and delegation errors, kept apart once `ProposalError` filled up
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum VoteError {
+    /// An address cannot delegate to itself
+    SelfDelegation = 450,
+    /// The delegate's chain already leads back to the delegator
+    DelegationCycle = 451,
+    /// The address has no active delegation
+    NotDelegating = 452,
+    /// The voter has delegated its power and cannot vote directly
+    VoterHasDelegated = 453,
+    /// The new choice is the one already recorded
+    VoteUnchanged = 454,
+    /// The voter has no vote on the proposal to change
+    NotVoted = 455,
+    /// The address is already in the voter registry
+    VoterAlreadyRegistered = 456,
+    /// The address is not in the voter registry
+    VoterNotRegistered = 457,
+    /// A voting power batch carries no entries
+    EmptyBatch = 458,
+    /// A voting power batch carries more than `MAX_POWER_BATCH_LEN` entries
+    BatchTooLarge = 459,
+    /// Adding a vote's weight would overflow a tally
+    TallyOverflow = 460,
+    /// The power exceeds the per-address maximum governance set
+    PowerAboveMaximum = 461,
+    /// The delegation would end more than `MAX_DELEGATORS` chains at one voter
+    TooManyDelegators = 462,
+}
+
+impl VoteError {
+    /// Every variant, in code order
+    pub const ALL: [VoteError; 13] = [
+        VoteError::SelfDelegation,
+        VoteError::DelegationCycle,
+        VoteError::NotDelegating,
+        VoteError::VoterHasDelegated,
+        VoteError::VoteUnchanged,
+        VoteError::NotVoted,
+        VoteError::VoterAlreadyRegistered,
+        VoteError::VoterNotRegistered,
+        VoteError::EmptyBatch,
+        VoteError::BatchTooLarge,
+        VoteError::TallyOverflow,
+        VoteError::PowerAboveMaximum,
+        VoteError::TooManyDelegators,
+    ];
+
+    /// Stable name used in the on-chain error catalog
+    pub fn name(&self) -> &'static str {
+        match self {
+            VoteError::SelfDelegation => "SelfDelegation",
+            VoteError::DelegationCycle => "DelegationCycle",
+            VoteError::NotDelegating => "NotDelegating",
+            VoteError::VoterHasDelegated => "VoterHasDelegated",
+            VoteError::VoteUnchanged => "VoteUnchanged",
+            VoteError::NotVoted => "NotVoted",
+            VoteError::VoterAlreadyRegistered => "VoterAlreadyRegistered",
+            VoteError::VoterNotRegistered => "VoterNotRegistered",
+            VoteError::EmptyBatch => "EmptyBatch",
+            VoteError::BatchTooLarge => "BatchTooLarge",
+            VoteError::TallyOverflow => "TallyOverflow",
+            VoteError::PowerAboveMaximum => "PowerAboveMaximum",
+            VoteError::TooManyDelegators => "TooManyDelegators",
+        }
+    }
+}
+
+/// Lifecycle and safety guard errors of the upgrade module
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum GuardError {
+    /// The upgrade module was already initialized
+    AlreadyInitialized = 500,
+    /// The emergency upgrade window has closed
+    EmergencyWindowExpired = 501,
+    /// An emergency upgrade window must close after the current time
+    ExpiryInPast = 502,
+    /// The target version is not newer than the current version
+    VersionNotNewer = 503,
+    /// The target version breaks compatibility with the current version
+    IncompatibleVersion = 504,
+    /// No implementation is recorded for the rollback target version
+    UnknownVersionImplementation = 505,
+    /// The new implementation does not report the proposal's version
+    ImplementationVersionMismatch = 506,
+    /// The proxy's upgrades were permanently frozen
+    UpgradesFrozen = 507,
+    /// No governance transfer is pending
+    NoPendingGovernance = 508,
+    /// Caller is not the nominated governance address
+    NotPendingGovernance = 509,
+    /// The proposal names a governance parameter that does not exist
+    UnknownParameter = 510,
+}
+
+impl GuardError {
+    /// Every variant, in code order
+    pub const ALL: [GuardError; 11] = [
+        GuardError::AlreadyInitialized,
+        GuardError::EmergencyWindowExpired,
+        GuardError::ExpiryInPast,
+        GuardError::VersionNotNewer,
+        GuardError::IncompatibleVersion,
+        GuardError::UnknownVersionImplementation,
+        GuardError::ImplementationVersionMismatch,
+        GuardError::UpgradesFrozen,
+        GuardError::NoPendingGovernance,
+        GuardError::NotPendingGovernance,
+        GuardError::UnknownParameter,
+    ];
+
+    /// Stable name used in the on-chain error catalog
+    pub fn name(&self) -> &'static str {
+        match self {
+            GuardError::AlreadyInitialized => "AlreadyInitialized",
+            GuardError::EmergencyWindowExpired => "EmergencyWindowExpired",
+            GuardError::ExpiryInPast => "ExpiryInPast",
+            GuardError::VersionNotNewer => "VersionNotNewer",
+            GuardError::IncompatibleVersion => "IncompatibleVersion",
+            GuardError::UnknownVersionImplementation => "UnknownVersionImplementation",
+            GuardError::ImplementationVersionMismatch => "ImplementationVersionMismatch",
+            GuardError::UpgradesFrozen => "UpgradesFrozen",
+            GuardError::NoPendingGovernance => "NoPendingGovernance",
+            GuardError::NotPendingGovernance => "NotPendingGovernance",
+            GuardError::UnknownParameter => "UnknownParameter",
+        }
+    }
+}
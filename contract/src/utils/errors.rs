@@ -1,4 +1,47 @@
-use soroban_sdk::{contracterror, Env, String};
+use crate::proxy::types::ProxyError;
+use crate::upgrade::types::{GuardError, ProposalError, UpgradeError, VoteError};
+use soroban_sdk::{contracterror, contracttype, Env, String, Symbol, Vec};
+
+/// Version of the error-code namespace. Bump when existing codes change meaning.
+pub const ERROR_CODE_VERSION: u32 = 1;
+
+/// Version of the `ErrorCatalog` layout itself
+pub const ERROR_CATALOG_SCHEMA_VERSION: u32 = 1;
+
+/// Code-to-name table for the upgrade, proposal, proxy, vote and guard errors, for client decoders
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ErrorCatalog {
+    pub schema_version: u32,
+    pub error_code_version: u32,
+    pub entries: Vec<(u32, Symbol)>,
+}
+
+/// Build the error catalog from the error enums
+pub fn error_catalog(env: &Env) -> ErrorCatalog {
+    let mut entries = Vec::new(env);
+    for error in UpgradeError::ALL.iter() {
+        entries.push_back((*error as u32, Symbol::new(env, error.name())));
+    }
+    for error in ProposalError::ALL.iter() {
+        entries.push_back((*error as u32, Symbol::new(env, error.name())));
+    }
+    for error in ProxyError::ALL.iter() {
+        entries.push_back((*error as u32, Symbol::new(env, error.name())));
+    }
+    for error in VoteError::ALL.iter() {
+        entries.push_back((*error as u32, Symbol::new(env, error.name())));
+    }
+    for error in GuardError::ALL.iter() {
+        entries.push_back((*error as u32, Symbol::new(env, error.name())));
+    }
+
+    ErrorCatalog {
+        schema_version: ERROR_CATALOG_SCHEMA_VERSION,
+        error_code_version: ERROR_CODE_VERSION,
+        entries,
+    }
+}
 
 #[contracterror]
 #[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
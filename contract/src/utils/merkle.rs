@@ -0,0 +1,34 @@
+use soroban_sdk::xdr::ToXdr;
+use soroban_sdk::{Address, Bytes, BytesN, Env, Vec};
+
+/// Leaf hash for an (address, power) allowlist entry:
+/// sha256(xdr(address) || power), with power encoded as a big-endian u32.
+pub fn hash_leaf(env: &Env, address: &Address, power: u32) -> BytesN<32> {
+    let mut preimage = Bytes::new(env);
+    preimage.append(&address.clone().to_xdr(env));
+    preimage.extend_from_array(&power.to_be_bytes());
+    BytesN::from_array(env, &env.crypto().sha256(&preimage).to_array())
+}
+
+/// Parent hash of two nodes. The pair is sorted first so proofs do not
+/// need to carry left/right positions.
+pub fn hash_pair(env: &Env, a: &BytesN<32>, b: &BytesN<32>) -> BytesN<32> {
+    let (low, high) = if a <= b { (a, b) } else { (b, a) };
+    let mut preimage = Bytes::from(low.clone());
+    preimage.append(&Bytes::from(high.clone()));
+    BytesN::from_array(env, &env.crypto().sha256(&preimage).to_array())
+}
+
+/// Check that `leaf` is part of the tree committed to by `root`
+pub fn verify_proof(
+    env: &Env,
+    root: &BytesN<32>,
+    leaf: &BytesN<32>,
+    proof: &Vec<BytesN<32>>,
+) -> bool {
+    let mut node = leaf.clone();
+    for sibling in proof.iter() {
+        node = hash_pair(env, &node, &sibling);
+    }
+    node == *root
+}
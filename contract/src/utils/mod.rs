@@ -1,4 +1,8 @@
 pub mod errors;
+pub mod merkle;
+pub mod pagination;
+pub mod time;
+pub mod typed_storage;
 pub mod validation;
 
 #[cfg(test)]
@@ -0,0 +1,170 @@
+//! Cursor pagination for list endpoints.
+//!
+//! A `Cursor` remembers the last id a page returned, which way the walk is
+//! going and the generation of the list it was issued for. Each list keeps
+//! its own generation, bumped whenever entries are pruned or shift position;
+//! a cursor from an older generation is refused with `CursorInvalidated` so
+//! clients restart rather than silently skip or repeat entries.
+
+use crate::keys::upgrade::CURSOR_GENERATION_KEY;
+use crate::upgrade::types::ProposalError;
+use crate::utils::typed_storage::Slot;
+use soroban_sdk::{contracttype, Env, Map, Symbol, Vec};
+
+/// Which way a cursor walks its list
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum CursorDirection {
+    /// Ascending ids, oldest first
+    Forward = 0,
+    /// Descending ids, newest first
+    Backward = 1,
+}
+
+/// The list a cursor belongs to; each has its own generation
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum PageScope {
+    /// Stored upgrade proposals, by id
+    Proposals = 0,
+    /// The voter registry, by position
+    Voters = 1,
+    /// A proposal's vote receipts, by position
+    VoteReceipts = 2,
+    /// History records and logs, by sequence
+    History = 3,
+}
+
+/// Opaque position in a paged list. Pass back the cursor a page returned;
+/// only `start_cursor` should be used to build a fresh one.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Cursor {
+    pub scope: PageScope,
+    /// Last id (or position, or sequence) already returned
+    pub last_id: u64,
+    pub direction: CursorDirection,
+    pub generation: u64,
+}
+
+const GENERATIONS: Slot<Symbol, Map<PageScope, u64>> = Slot::new(CURSOR_GENERATION_KEY);
+
+/// Current generation of a list
+pub fn generation(env: &Env, scope: PageScope) -> u64 {
+    GENERATIONS
+        .load(env)
+        .and_then(|generations| generations.get(scope))
+        .unwrap_or(0)
+}
+
+/// Invalidate every cursor issued for a list
+pub fn bump_generation(env: &Env, scope: PageScope) {
+    let mut generations = GENERATIONS.load_or_else(env, || Map::new(env));
+    generations.set(scope, generation(env, scope) + 1);
+    GENERATIONS.store(env, &generations);
+}
+
+/// A cursor positioned before the first entry a walk in `direction` returns
+pub fn start_cursor(env: &Env, scope: PageScope, direction: CursorDirection) -> Cursor {
+    Cursor {
+        scope,
+        last_id: match direction {
+            CursorDirection::Forward => 0,
+            CursorDirection::Backward => u64::MAX,
+        },
+        direction,
+        generation: generation(env, scope),
+    }
+}
+
+/// The cursor to page from: `cursor` when it is current for `scope`, or a
+/// forward start when none is given
+pub fn resolve(
+    env: &Env,
+    scope: PageScope,
+    cursor: Option<Cursor>,
+) -> Result<Cursor, ProposalError> {
+    match cursor {
+        None => Ok(start_cursor(env, scope, CursorDirection::Forward)),
+        Some(cursor) if cursor.scope == scope && cursor.generation == generation(env, scope) => {
+            Ok(cursor)
+        }
+        Some(_) => Err(ProposalError::CursorInvalidated),
+    }
+}
+
+/// Up to `limit` of the ascending `ids` past `cursor`, the cursor to
+/// continue from and whether the walk has reached the end
+pub fn page_ids(
+    env: &Env,
+    cursor: &Cursor,
+    ids: &Vec<u64>,
+    limit: u32,
+) -> (Vec<u64>, Cursor, bool) {
+    let mut picked = Vec::new(env);
+    let more = match cursor.direction {
+        CursorDirection::Forward => {
+            let mut index = match ids.binary_search(cursor.last_id.saturating_add(1)) {
+                Ok(index) | Err(index) => index,
+            };
+            while index < ids.len() && picked.len() < limit {
+                picked.push_back(ids.get_unchecked(index));
+                index += 1;
+            }
+            index < ids.len()
+        }
+        CursorDirection::Backward => {
+            let mut remaining = match ids.binary_search(cursor.last_id) {
+                Ok(index) | Err(index) => index,
+            };
+            while remaining > 0 && picked.len() < limit {
+                remaining -= 1;
+                picked.push_back(ids.get_unchecked(remaining));
+            }
+            remaining > 0
+        }
+    };
+    let next = continue_after(cursor, &picked);
+    (picked, next, !more)
+}
+
+/// Up to `limit` of the ids `first..=last` past `cursor`, the cursor to
+/// continue from and whether the walk has reached the end
+pub fn page_span(
+    env: &Env,
+    cursor: &Cursor,
+    first: u64,
+    last: u64,
+    limit: u32,
+) -> (Vec<u64>, Cursor, bool) {
+    let mut picked = Vec::new(env);
+    let more = match cursor.direction {
+        CursorDirection::Forward => {
+            let mut id = cursor.last_id.saturating_add(1).max(first);
+            while id <= last && picked.len() < limit {
+                picked.push_back(id);
+                id += 1;
+            }
+            id <= last
+        }
+        CursorDirection::Backward => {
+            let mut bound = cursor.last_id.min(last.saturating_add(1));
+            while bound > first && picked.len() < limit {
+                bound -= 1;
+                picked.push_back(bound);
+            }
+            bound > first
+        }
+    };
+    let next = continue_after(cursor, &picked);
+    (picked, next, !more)
+}
+
+fn continue_after(cursor: &Cursor, picked: &Vec<u64>) -> Cursor {
+    Cursor {
+        last_id: picked.last().unwrap_or(cursor.last_id),
+        ..cursor.clone()
+    }
+}
@@ -1,11 +1,14 @@
 #[cfg(test)]
 mod tests {
-    use crate::upgrade::types::Version;
-    use crate::utils::errors::{format_error, IntegrationErrorCode};
+    use crate::proxy::types::ProxyError;
+    use crate::upgrade::types::{GuardError, ProposalError, UpgradeError, Version, VoteError};
+    use crate::utils::errors::{
+        error_catalog, format_error, IntegrationErrorCode, ERROR_CATALOG_SCHEMA_VERSION,
+    };
     use crate::utils::validation::{is_version_increment, validate_address};
     use soroban_sdk::testutils::Address as _;
     use soroban_sdk::xdr::{Hash, ScAddress};
-    use soroban_sdk::{Address, Env, String, TryFromVal};
+    use soroban_sdk::{Address, Env, String, Symbol, TryFromVal};
 
     #[test]
     fn test_format_error_prefers_context_and_prefixes() {
@@ -135,4 +138,285 @@ mod tests {
         assert!(!is_version_increment(&current, &Version::new(1, 2, 2)));
         assert!(!is_version_increment(&current, &Version::new(1, 1, 9)));
     }
+
+    /// Exhaustive over `UpgradeError`: a new variant will not compile until
+    /// it is listed here, and the assertion then requires it in `ALL`.
+    fn upgrade_error_listed(error: UpgradeError) -> bool {
+        match error {
+            UpgradeError::NotGovernance
+            | UpgradeError::ProposalNotFound
+            | UpgradeError::ProposalDataUnavailable
+            | UpgradeError::ProposalIndicesIncomplete
+            | UpgradeError::ProposalIdCollision
+            | UpgradeError::ProposalNotPending
+            | UpgradeError::ProposalNotApproved
+            | UpgradeError::NotAwaitingProxy
+            | UpgradeError::ProxyNotExecuted
+            | UpgradeError::ProxyLinkMissing
+            | UpgradeError::RateLimited
+            | UpgradeError::InvalidRateLimit
+            | UpgradeError::InvalidQuorum
+            | UpgradeError::InvalidMaxAttempts
+            | UpgradeError::EmergencyDisabled
+            | UpgradeError::InvalidRollbackTarget
+            | UpgradeError::SubscriberExists
+            | UpgradeError::TooManySubscribers
+            | UpgradeError::SubscriberNotFound
+            | UpgradeError::NoVotingPower
+            | UpgradeError::AlreadyVoted
+            | UpgradeError::RecoveryNotFound
+            | UpgradeError::RecoveryNotOpen
+            | UpgradeError::RecoveryTimelockActive
+            | UpgradeError::SupermajorityNotReached
+            | UpgradeError::ScheduleNotFound
+            | UpgradeError::NotAuthorized
+            | UpgradeError::ProtectedAction
+            | UpgradeError::EmptyRoleSet
+            | UpgradeError::EmergencyBumpTooLarge
+            | UpgradeError::DualControlCooldown
+            | UpgradeError::RevertWindowClosed
+            | UpgradeError::ReverseMigrationMissing
+            | UpgradeError::ReverseMigrationFailed
+            | UpgradeError::GovernancePaused
+            | UpgradeError::GovernanceNotPaused
+            | UpgradeError::MigrationWouldStrand
+            | UpgradeError::TaskCoolingDown
+            | UpgradeError::InvalidKeeperBounty
+            | UpgradeError::SchemaMismatch
+            | UpgradeError::ProposerCannotVote
+            | UpgradeError::ProposerCannotExecute
+            | UpgradeError::GenesisSealed
+            | UpgradeError::GenesisPageOutOfOrder
+            | UpgradeError::GenesisPageTooLarge
+            | UpgradeError::InvalidGenesisPages => UpgradeError::ALL.contains(&error),
+        }
+    }
+
+    /// Exhaustive over `ProposalError`; see `upgrade_error_listed`
+    fn proposal_error_listed(error: ProposalError) -> bool {
+        match error {
+            ProposalError::TrackNotFound
+            | ProposalError::TemplateNotFound
+            | ProposalError::TooManyTemplateTags
+            | ProposalError::InvalidApprovalThreshold
+            | ProposalError::InvalidTrack
+            | ProposalError::SafeMode
+            | ProposalError::GenesisPending
+            | ProposalError::TargetDeprecated
+            | ProposalError::BumpNotPermitted
+            | ProposalError::TargetAlreadyExecuted
+            | ProposalError::InvalidTarget
+            | ProposalError::TargetRevalidationFailed
+            | ProposalError::IllegalTransition
+            | ProposalError::VotingClosed
+            | ProposalError::VotingPeriodActive
+            | ProposalError::NotProposer
+            | ProposalError::ProposalNotPrivate
+            | ProposalError::DescriptionTooLong
+            | ProposalError::DescriptionAlreadyRevealed
+            | ProposalError::RevealTooEarly
+            | ProposalError::DescriptionMismatch
+            | ProposalError::DescriptionNotRevealed
+            | ProposalError::ProposalNotFinalized
+            | ProposalError::NoArchiveSink
+            | ProposalError::ExecutionDelayActive
+            | ProposalError::ExecutionWindowClosed
+            | ProposalError::InvalidExecutionWindow
+            | ProposalError::TimelockActive
+            | ProposalError::RevertWindowOpen
+            | ProposalError::MigrationPlanMismatch
+            | ProposalError::MigrationPlanNotFound
+            | ProposalError::MigrationNotStarted
+            | ProposalError::MigrationNotInProgress
+            | ProposalError::MigrationDeferred
+            | ProposalError::MigrationAborted
+            | ProposalError::CallTrapped
+            | ProposalError::ProxyPaused
+            | ProposalError::VotingPowerRootMissing
+            | ProposalError::InvalidMerkleProof
+            | ProposalError::VotingPowerClaimed
+            | ProposalError::InvalidBondAmount
+            | ProposalError::CompensationNotFound
+            | ProposalError::CompensationPending
+            | ProposalError::InvalidEmergencyOverride
+            | ProposalError::MalformedCallReturn
+            | ProposalError::CallRejected
+            | ProposalError::ReentrantCall
+            | ProposalError::CursorInvalidated
+            | ProposalError::PostCheckUnacknowledged
+            | ProposalError::NoPostCheckFailure => ProposalError::ALL.contains(&error),
+        }
+    }
+
+    /// Exhaustive over `ProxyError`; see `upgrade_error_listed`
+    fn proxy_error_listed(error: ProxyError) -> bool {
+        match error {
+            ProxyError::NotAdmin
+            | ProxyError::NotAdminOrInitiator
+            | ProxyError::ScheduleNotFound
+            | ProxyError::ScheduleNotPending
+            | ProxyError::DelayNotElapsed
+            | ProxyError::HandoverPending
+            | ProxyError::HandoverExpired
+            | ProxyError::HandoverRejected
+            | ProxyError::NoHandover
+            | ProxyError::NotAdminOrImplementation
+            | ProxyError::Paused
+            | ProxyError::ReentrantCall
+            | ProxyError::NotPaused
+            | ProxyError::NoPendingAdmin
+            | ProxyError::NotPendingAdmin
+            | ProxyError::SameImplementation
+            | ProxyError::AlreadyInitialized
+            | ProxyError::DelayRequired
+            | ProxyError::ImplementationNotApproved
+            | ProxyError::NotApprover
+            | ProxyError::SameApprover
+            | ProxyError::ImplementationVersionMismatch
+            | ProxyError::CooldownActive
+            | ProxyError::Frozen
+            | ProxyError::FreezeNotConfirmed
+            | ProxyError::NothingToRevert
+            | ProxyError::AlreadyAdmin
+            | ProxyError::UnknownAdmin
+            | ProxyError::BelowThreshold
+            | ProxyError::InvalidThreshold
+            | ProxyError::AlreadyApproved
+            | ProxyError::RevertVersionUnknown => ProxyError::ALL.contains(&error),
+        }
+    }
+
+    /// Exhaustive over `VoteError`; see `upgrade_error_listed`
+    fn vote_error_listed(error: VoteError) -> bool {
+        match error {
+            VoteError::SelfDelegation
+            | VoteError::DelegationCycle
+            | VoteError::NotDelegating
+            | VoteError::VoterHasDelegated
+            | VoteError::VoteUnchanged
+            | VoteError::NotVoted
+            | VoteError::VoterAlreadyRegistered
+            | VoteError::VoterNotRegistered
+            | VoteError::EmptyBatch
+            | VoteError::BatchTooLarge
+            | VoteError::TallyOverflow
+            | VoteError::PowerAboveMaximum
+            | VoteError::TooManyDelegators => VoteError::ALL.contains(&error),
+        }
+    }
+
+    /// Exhaustive over `GuardError`; see `upgrade_error_listed`
+    fn guard_error_listed(error: GuardError) -> bool {
+        match error {
+            GuardError::AlreadyInitialized
+            | GuardError::EmergencyWindowExpired
+            | GuardError::ExpiryInPast
+            | GuardError::VersionNotNewer
+            | GuardError::IncompatibleVersion
+            | GuardError::UnknownVersionImplementation
+            | GuardError::ImplementationVersionMismatch
+            | GuardError::UpgradesFrozen
+            | GuardError::NoPendingGovernance
+            | GuardError::NotPendingGovernance
+            | GuardError::UnknownParameter => GuardError::ALL.contains(&error),
+        }
+    }
+
+    #[test]
+    fn test_error_catalog_matches_error_enums() {
+        let env = Env::default();
+        let catalog = error_catalog(&env);
+        assert_eq!(catalog.schema_version, ERROR_CATALOG_SCHEMA_VERSION);
+        assert_eq!(
+            catalog.entries.len() as usize,
+            UpgradeError::ALL.len()
+                + ProposalError::ALL.len()
+                + ProxyError::ALL.len()
+                + VoteError::ALL.len()
+                + GuardError::ALL.len()
+        );
+
+        let mut index = 0;
+        for error in UpgradeError::ALL {
+            assert!(upgrade_error_listed(error));
+            let entry = catalog.entries.get(index).unwrap();
+            assert_eq!(entry, (error as u32, Symbol::new(&env, error.name())));
+            index += 1;
+        }
+        for error in ProposalError::ALL {
+            assert!(proposal_error_listed(error));
+            let entry = catalog.entries.get(index).unwrap();
+            assert_eq!(entry, (error as u32, Symbol::new(&env, error.name())));
+            index += 1;
+        }
+        for error in ProxyError::ALL {
+            assert!(proxy_error_listed(error));
+            let entry = catalog.entries.get(index).unwrap();
+            assert_eq!(entry, (error as u32, Symbol::new(&env, error.name())));
+            index += 1;
+        }
+        for error in VoteError::ALL {
+            assert!(vote_error_listed(error));
+            let entry = catalog.entries.get(index).unwrap();
+            assert_eq!(entry, (error as u32, Symbol::new(&env, error.name())));
+            index += 1;
+        }
+        for error in GuardError::ALL {
+            assert!(guard_error_listed(error));
+            let entry = catalog.entries.get(index).unwrap();
+            assert_eq!(entry, (error as u32, Symbol::new(&env, error.name())));
+            index += 1;
+        }
+
+        // Codes are unique across all enums
+        for (i, (code, _)) in catalog.entries.iter().enumerate() {
+            for (j, (other, _)) in catalog.entries.iter().enumerate() {
+                assert!(i == j || code != other);
+            }
+        }
+
+        // Codes are contiguous within each enum so gaps signal a removed variant
+        for window in UpgradeError::ALL.windows(2) {
+            assert_eq!(window[0] as u32 + 1, window[1] as u32);
+        }
+        for window in ProposalError::ALL.windows(2) {
+            assert_eq!(window[0] as u32 + 1, window[1] as u32);
+        }
+        for window in ProxyError::ALL.windows(2) {
+            assert_eq!(window[0] as u32 + 1, window[1] as u32);
+        }
+        for window in VoteError::ALL.windows(2) {
+            assert_eq!(window[0] as u32 + 1, window[1] as u32);
+        }
+        for window in GuardError::ALL.windows(2) {
+            assert_eq!(window[0] as u32 + 1, window[1] as u32);
+        }
+    }
+
+    #[test]
+    fn test_clock_override_freezes_steps_and_releases() {
+        use crate::utils::time;
+        use soroban_sdk::testutils::Ledger;
+
+        let env = Env::default();
+        env.ledger().with_mut(|l| {
+            l.timestamp = 500;
+            l.sequence_number = 7;
+        });
+        assert_eq!(time::now(&env), 500);
+        assert_eq!(time::current_sequence(&env), 7);
+
+        time::testutils::freeze(2_000);
+        time::testutils::freeze_sequence(40);
+        env.ledger().with_mut(|l| l.timestamp = 900);
+        assert_eq!(time::now(&env), 2_000);
+        assert_eq!(time::current_sequence(&env), 40);
+
+        time::testutils::step(&env, 25);
+        assert_eq!(time::now(&env), 2_025);
+
+        time::testutils::release();
+        assert_eq!(time::now(&env), 900);
+        assert_eq!(time::current_sequence(&env), 7);
+    }
 }
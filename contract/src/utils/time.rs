@@ -0,0 +1,73 @@
+//! The contract's single view of time.
+//!
+//! Every deadline, timelock, cooldown and expiry check reads the clock
+//! through `now` (or `current_sequence`) rather than the ledger directly, so
+//! scenario tests and off-chain simulators can freeze or step time in one
+//! place. The override hook only exists in test and `testutils` builds;
+//! release builds always read the ledger.
+
+use soroban_sdk::Env;
+
+/// Current ledger timestamp, in seconds
+pub fn now(env: &Env) -> u64 {
+    #[cfg(any(test, feature = "testutils"))]
+    if let Some(timestamp) = testutils::frozen_timestamp() {
+        return timestamp;
+    }
+    env.ledger().timestamp()
+}
+
+/// Current ledger sequence number
+#[allow(dead_code)]
+pub fn current_sequence(env: &Env) -> u32 {
+    #[cfg(any(test, feature = "testutils"))]
+    if let Some(sequence) = testutils::frozen_sequence() {
+        return sequence;
+    }
+    env.ledger().sequence()
+}
+
+/// Clock override for scenario tests. The override is held per thread, so
+/// each test (which runs on its own thread) starts with the ledger clock and
+/// can freeze or step time without entering a contract context.
+#[cfg(any(test, feature = "testutils"))]
+pub mod testutils {
+    extern crate std;
+
+    use core::cell::Cell;
+    use soroban_sdk::Env;
+
+    std::thread_local! {
+        static FROZEN_TIMESTAMP: Cell<Option<u64>> = const { Cell::new(None) };
+        static FROZEN_SEQUENCE: Cell<Option<u32>> = const { Cell::new(None) };
+    }
+
+    pub(super) fn frozen_timestamp() -> Option<u64> {
+        FROZEN_TIMESTAMP.with(Cell::get)
+    }
+
+    pub(super) fn frozen_sequence() -> Option<u32> {
+        FROZEN_SEQUENCE.with(Cell::get)
+    }
+
+    /// Pin `now` at `timestamp` until stepped or released
+    pub fn freeze(timestamp: u64) {
+        FROZEN_TIMESTAMP.with(|frozen| frozen.set(Some(timestamp)));
+    }
+
+    /// Pin `current_sequence` at `sequence` until released
+    pub fn freeze_sequence(sequence: u32) {
+        FROZEN_SEQUENCE.with(|frozen| frozen.set(Some(sequence)));
+    }
+
+    /// Move the clock forward by `seconds`, freezing it at the current time first
+    pub fn step(env: &Env, seconds: u64) {
+        freeze(super::now(env) + seconds);
+    }
+
+    /// Go back to reading the ledger
+    pub fn release() {
+        FROZEN_TIMESTAMP.with(|frozen| frozen.set(None));
+        FROZEN_SEQUENCE.with(|frozen| frozen.set(None));
+    }
+}
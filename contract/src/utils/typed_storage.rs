@@ -0,0 +1,63 @@
+use core::marker::PhantomData;
+use soroban_sdk::{Env, IntoVal, TryFromVal, Val};
+
+/// A persistent storage key paired with the one value type stored under it.
+///
+/// Reads and writes go through the slot, so the value type is fixed where the
+/// slot is declared and cannot be chosen at the call site.
+pub struct Slot<K, V> {
+    key: K,
+    _value: PhantomData<fn() -> V>,
+}
+
+impl<K, V> Slot<K, V> {
+    pub const fn new(key: K) -> Self {
+        Slot {
+            key,
+            _value: PhantomData,
+        }
+    }
+}
+
+impl<K, V> Slot<K, V>
+where
+    K: IntoVal<Env, Val>,
+    V: IntoVal<Env, Val> + TryFromVal<Env, Val>,
+{
+    /// Read the value, if one has been stored
+    pub fn load(&self, env: &Env) -> Option<V> {
+        env.storage().persistent().get(&self.key)
+    }
+
+    /// Read the value, falling back to `default` when nothing is stored
+    pub fn load_or_else(&self, env: &Env, default: impl FnOnce() -> V) -> V {
+        self.load(env).unwrap_or_else(default)
+    }
+
+    /// Write the value
+    pub fn store(&self, env: &Env, value: &V) {
+        env.storage().persistent().set(&self.key, value);
+    }
+
+    /// Check whether a value has been stored
+    pub fn has(&self, env: &Env) -> bool {
+        env.storage().persistent().has(&self.key)
+    }
+
+    /// Delete the stored value, if any
+    pub fn remove(&self, env: &Env) {
+        env.storage().persistent().remove(&self.key);
+    }
+
+    /// Extend the entry's TTL to `extend_to` ledgers once it drops below
+    /// `threshold`; returns false when nothing is stored
+    pub fn extend_ttl(&self, env: &Env, threshold: u32, extend_to: u32) -> bool {
+        if !self.has(env) {
+            return false;
+        }
+        env.storage()
+            .persistent()
+            .extend_ttl(&self.key, threshold, extend_to);
+        true
+    }
+}